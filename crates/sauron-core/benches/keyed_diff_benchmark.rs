@@ -0,0 +1,99 @@
+//! The keyed diff of the operations in the js-framework-benchmark, on the vdom only.
+//!
+//! ```sh
+//! cargo bench --bench keyed_diff_benchmark
+//! ```
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use sauron_core::{
+    diff,
+    html::{attributes::*, *},
+    Node,
+};
+
+/// a row of the benchmark table, with its key and the label
+fn row(id: usize, label: &str) -> Node<()> {
+    tr(
+        vec![key(id)],
+        vec![
+            td(vec![class("col-md-1")], vec![text(id)]),
+            td(
+                vec![class("col-md-4")],
+                vec![a(vec![class("lbl")], vec![text(label)])],
+            ),
+            td(
+                vec![class("col-md-1")],
+                vec![a(
+                    vec![class("remove")],
+                    vec![span(
+                        vec![class("glyphicon glyphicon-remove")],
+                        vec![],
+                    )],
+                )],
+            ),
+        ],
+    )
+}
+
+fn table(rows: &[(usize, String)]) -> Node<()> {
+    tbody(vec![], rows.iter().map(|(id, label)| row(*id, label)))
+}
+
+fn rows(count: usize) -> Vec<(usize, String)> {
+    (0..count).map(|id| (id, format!("row {}", id))).collect()
+}
+
+fn bench_keyed_diff(c: &mut Criterion) {
+    let thousand = rows(1_000);
+    let old = table(&thousand);
+
+    let mut updated = thousand.clone();
+    for (_id, label) in updated.iter_mut().step_by(10) {
+        label.push_str(" !!!");
+    }
+    let new = table(&updated);
+    c.bench_function("partial update of 1k rows", |b| {
+        b.iter(|| black_box(diff(&old, &new)))
+    });
+
+    let mut swapped = thousand.clone();
+    swapped.swap(1, 998);
+    let new = table(&swapped);
+    c.bench_function("swap rows of 1k rows", |b| {
+        b.iter(|| black_box(diff(&old, &new)))
+    });
+
+    let mut removed = thousand.clone();
+    removed.remove(500);
+    let new = table(&removed);
+    c.bench_function("remove a row of 1k rows", |b| {
+        b.iter(|| black_box(diff(&old, &new)))
+    });
+
+    let mut reversed = thousand.clone();
+    reversed.reverse();
+    let new = table(&reversed);
+    c.bench_function("reverse 1k rows", |b| {
+        b.iter(|| black_box(diff(&old, &new)))
+    });
+
+    let appended = rows(2_000);
+    let new = table(&appended);
+    c.bench_function("append 1k rows to 1k rows", |b| {
+        b.iter(|| black_box(diff(&old, &new)))
+    });
+
+    let ten_thousand = rows(10_000);
+    let old = table(&ten_thousand);
+    let mut updated = ten_thousand.clone();
+    for (_id, label) in updated.iter_mut().step_by(10) {
+        label.push_str(" !!!");
+    }
+    let new = table(&updated);
+    c.bench_function("partial update of 10k rows", |b| {
+        b.iter(|| black_box(diff(&old, &new)))
+    });
+}
+
+criterion_group!(benches, bench_keyed_diff);
+criterion_main!(benches);
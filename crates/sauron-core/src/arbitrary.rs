@@ -0,0 +1,141 @@
+//! provides the [proptest] strategies which generate random vdom trees,
+//! so the invariants of the diff and the patches can be fuzzed, enabled with the `with-proptest` feature.
+//!
+//! The generated trees have elements with classes, ids, styles and other attributes,
+//! text nodes and keyed children, where the keys of the siblings are unique.
+//!
+//! # Examples
+//! ```rust,ignore
+//! use proptest::prelude::*;
+//! use sauron::{apply_patch_to_vdom, arbitrary::arb_node, diff};
+//!
+//! proptest! {
+//!     #[test]
+//!     fn patches_old_into_new(old in arb_node(), new in arb_node()) {
+//!         let patches = diff(&old, &new);
+//!         prop_assert_eq!(apply_patch_to_vdom(&old, &patches).unwrap(), new);
+//!     }
+//! }
+//! ```
+use crate::{
+    html::{attributes::*, html_element, text},
+    Attribute, Node,
+};
+use proptest::{collection, prelude::*, sample};
+
+/// the tags of the generated elements
+const TAGS: [&str; 8] = [
+    "div", "span", "p", "ul", "li", "section", "button", "article",
+];
+
+/// the classes of the generated elements, picked from a few so the diff finds similar nodes
+const CLASSES: [&str; 4] = ["active", "hidden", "item", "selected"];
+
+/// the keys of the keyed children are picked from `0..MAX_KEY`
+const MAX_KEY: usize = 64;
+
+/// The size of the generated trees
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TreeSize {
+    /// the maximum depth of the elements
+    pub depth: u32,
+    /// the maximum number of the nodes in the tree
+    pub nodes: u32,
+    /// the maximum number of the children of an element
+    pub children: usize,
+}
+
+impl Default for TreeSize {
+    fn default() -> Self {
+        TreeSize {
+            depth: 4,
+            nodes: 64,
+            children: 6,
+        }
+    }
+}
+
+/// a strategy which generates random vdom trees of the default [`TreeSize`]
+pub fn arb_node() -> impl Strategy<Value = Node<()>> {
+    arb_node_with_size(TreeSize::default())
+}
+
+/// a strategy which generates random vdom trees of the `size`
+pub fn arb_node_with_size(size: TreeSize) -> impl Strategy<Value = Node<()>> {
+    let leaf = prop_oneof![
+        1 => arb_text(),
+        2 => arb_element(Just(vec![])),
+    ];
+    leaf.prop_recursive(
+        size.depth,
+        size.nodes,
+        size.children as u32,
+        move |inner| {
+            prop_oneof![
+                arb_element(collection::vec(inner.clone(), 0..=size.children)),
+                arb_element(arb_keyed(collection::vec(
+                    inner,
+                    0..=size.children
+                ))),
+            ]
+        },
+    )
+}
+
+/// a strategy which generates an attribute, ie: a class, an id, a style or a plain attribute
+pub fn arb_attribute() -> impl Strategy<Value = Attribute<()>> {
+    prop_oneof![
+        sample::select(&CLASSES[..]).prop_map(class),
+        (0..8u8).prop_map(|n| id(format!("id{}", n))),
+        sample::select(&["red", "blue", "green"][..])
+            .prop_map(|color| style("color", color)),
+        (0..100u32).prop_map(|width| style("width", format!("{}px", width))),
+        "[a-z ]{0,8}".prop_map(|title| attr("title", title)),
+        any::<bool>().prop_map(|value| attr("data-flag", value)),
+    ]
+}
+
+/// a strategy which generates a text node
+fn arb_text() -> impl Strategy<Value = Node<()>> {
+    "[a-z ]{0,8}".prop_map(text)
+}
+
+/// a strategy which generates an element with the `children`
+fn arb_element(
+    children: impl Strategy<Value = Vec<Node<()>>>,
+) -> impl Strategy<Value = Node<()>> {
+    (
+        sample::select(&TAGS[..]),
+        collection::vec(arb_attribute(), 0..4),
+        children,
+    )
+        .prop_map(|(tag, attrs, children)| html_element(tag, attrs, children))
+}
+
+/// add the unique keys into the `children`, the text nodes are wrapped in a span to be keyed
+fn arb_keyed(
+    children: impl Strategy<Value = Vec<Node<()>>>,
+) -> impl Strategy<Value = Vec<Node<()>>> {
+    children
+        .prop_flat_map(|children| {
+            let keys = sample::subsequence(
+                (0..MAX_KEY).collect::<Vec<_>>(),
+                children.len(),
+            )
+            .prop_shuffle();
+            (Just(children), keys)
+        })
+        .prop_map(|(children, keys)| {
+            children
+                .into_iter()
+                .zip(keys)
+                .map(|(child, k)| match child {
+                    Node::Element(mut element) => {
+                        element.attrs.push(key(k));
+                        Node::Element(element)
+                    }
+                    child => html_element("span", [key(k)], [child]),
+                })
+                .collect()
+        })
+}
@@ -0,0 +1,23 @@
+//! provides [`HeadlessProgram`] which runs the mount, dispatch and patch cycle
+//! of an [`Application`](crate::Application) on the host without a browser,
+//! so the view and the patches can be asserted with `cargo test`.
+//!
+//! The patches are applied into the vdom with [`apply_patch_to_vdom`](crate::vdom::apply_patch_to_vdom),
+//! then the html is rendered from the patched vdom.
+//! The event listeners, the focus and the `inner_html` of the elements are specific
+//! to the browser and are not handled here.
+use thiserror::Error;
+
+#[cfg(feature = "with-dom")]
+mod headless;
+
+#[cfg(feature = "with-dom")]
+pub use headless::HeadlessProgram;
+
+/// Errors when applying the patches into the vdom
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PatchError {
+    /// the node at the path of the patch can not be found
+    #[error("unable to find the node at the path: {0:?}")]
+    NodeNotFound(Vec<usize>),
+}
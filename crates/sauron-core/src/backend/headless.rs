@@ -0,0 +1,98 @@
+use super::PatchError;
+use crate::{diff, vdom::apply_patch_to_vdom, Application, Cmd, Node, Render};
+
+/// Runs the mount, dispatch and patch cycle of an app into its vdom,
+/// so the html of the app can be asserted with `cargo test` on the host,
+/// without a browser.
+///
+/// The msgs are dispatched and patched synchronously. The commands returned from the app
+/// are not executed, since they need the browser, only whether they update the view is used.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+/// use sauron::backend::HeadlessProgram;
+///
+/// struct Counter(i32);
+///
+/// impl Application<i32> for Counter {
+///     fn update(&mut self, delta: i32) -> Cmd<Self, i32> {
+///         self.0 += delta;
+///         Cmd::none()
+///     }
+///
+///     fn view(&self) -> Node<i32> {
+///         div(vec![class("counter")], vec![text(self.0)])
+///     }
+/// }
+///
+/// let mut program = HeadlessProgram::mount(Counter(0));
+/// assert_eq!(program.html(), r#"<div class="counter">0</div>"#);
+///
+/// program.dispatch(2).unwrap();
+/// assert_eq!(program.html(), r#"<div class="counter">2</div>"#);
+/// ```
+pub struct HeadlessProgram<APP, MSG> {
+    app: APP,
+    current_vdom: Node<MSG>,
+}
+
+impl<APP, MSG> HeadlessProgram<APP, MSG>
+where
+    MSG: 'static,
+    APP: Application<MSG> + 'static,
+{
+    /// mount the view of the app, then call the init of the app
+    pub fn mount(app: APP) -> Self {
+        let current_vdom = app.view();
+        let mut program = HeadlessProgram { app, current_vdom };
+        let _cmd = program.app.init();
+        program
+    }
+
+    /// dispatch the msg into the app, then patch the view if the app is updated
+    ///
+    /// Returns the number of the patches applied
+    pub fn dispatch(&mut self, msg: MSG) -> Result<usize, PatchError> {
+        self.dispatch_multiple(vec![msg])
+    }
+
+    /// dispatch the msgs into the app, then patch the view once if the app is updated
+    ///
+    /// The patches are applied into the current vdom, instead of replacing it with the new view.
+    ///
+    /// Returns the number of the patches applied
+    pub fn dispatch_multiple(
+        &mut self,
+        msgs: impl IntoIterator<Item = MSG>,
+    ) -> Result<usize, PatchError> {
+        let cmds: Vec<Cmd<APP, MSG>> =
+            msgs.into_iter().map(|msg| self.app.update(msg)).collect();
+        let cmd = Cmd::batch(cmds);
+        if !cmd.modifier.should_update_view {
+            return Ok(0);
+        }
+        let new_vdom = self.app.view();
+        let patches = diff(&self.current_vdom, &new_vdom);
+        let total_patches = patches.len();
+        let patched_vdom = apply_patch_to_vdom(&self.current_vdom, &patches)?;
+        drop(patches);
+        self.current_vdom = patched_vdom;
+        Ok(total_patches)
+    }
+
+    /// the app
+    pub fn app(&self) -> &APP {
+        &self.app
+    }
+
+    /// the current view of the app
+    pub fn current_vdom(&self) -> &Node<MSG> {
+        &self.current_vdom
+    }
+
+    /// the html of the current view of the app
+    pub fn html(&self) -> String {
+        self.current_vdom.render_to_string()
+    }
+}
@@ -1,35 +1,102 @@
 //! This module provides functionalities for
 //! manipulating the actual Document Object Model in the browser
 //!
+mod animate;
 mod application;
 pub mod apply_patches;
+pub mod audio;
+#[cfg(feature = "with-persist")]
+mod broadcast;
 mod callback;
+mod canvas_draw;
+pub mod capabilities;
+mod child_window;
+mod click_outside;
+mod clipboard;
 pub mod cmd;
 mod component;
 mod created_node;
+#[cfg(feature = "with-persist")]
+pub mod custom_element;
+#[cfg(feature = "with-devtools")]
+pub mod devtools;
 mod dispatch;
 mod dom_updater;
 mod effects;
+#[cfg(feature = "with-error-overlay")]
+pub mod error_overlay;
+mod event_delegation;
+mod event_timing;
 pub mod events;
+pub mod file;
+mod focus_trap;
+pub mod gpu_canvas;
+mod head;
+pub mod hotkey;
 mod http;
+mod injected_style;
+pub mod metrics;
+mod mount_options;
+mod node_ref;
 mod program;
+mod resize;
+mod scheduler;
+pub mod service_worker;
+pub mod speech;
+#[cfg(feature = "with-persist")]
+pub mod storage;
+pub mod sub;
+mod template;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+mod timer;
+pub mod tooltip;
+mod transition_group;
+mod tween;
 mod util;
-mod window;
+mod view_transition;
+mod viewport;
+pub mod window;
+#[cfg(feature = "with-worker")]
+pub mod worker;
 
-pub use application::{Application, Measurements};
+pub use animate::{animate_enter, animate_exit, AnimationTiming, EXITING_ATTR};
+pub use application::{Application, Measurements, PatchTiming};
 pub use callback::Callback;
+pub use canvas_draw::canvas_draw;
+pub use capabilities::{capabilities, Capabilities, Motion};
+pub use child_window::{ChildWindow, Opener, WindowOptions};
+pub use clipboard::ClipboardError;
 pub use component::{Component, Container, View};
 pub use created_node::CreatedNode;
 pub use dispatch::Dispatch;
-pub use dom_updater::DomUpdater;
+pub use dom_updater::{DomError, DomUpdater};
 pub use effects::Effects;
+pub use event_timing::EventTiming;
 pub use events::*;
-pub use http::Http;
+pub use file::{FileError, FileHandle, UploadProgress};
+pub use focus_trap::focus_trap;
+pub use gpu_canvas::{gpu_canvas, GpuCanvasEvent, GpuRenderer};
+#[cfg(feature = "with-persist")]
+pub use http::auth;
+pub use http::{FetchError, Http};
+pub use metrics::{MemoryStats, Metrics};
+pub use mount_options::MountOptions;
+pub use node_ref::{node_ref, ElementTarget, NodeRef, Rect};
 pub use program::Program;
+pub use sub::Subscription;
+pub use timer::TimerHandle;
+pub use tooltip::tooltip;
+pub use transition_group::transition_group;
+pub use tween::{AnimationHandle, Easing, Spring};
 pub use util::{
-    body, document, history, now, performance, request_animation_frame, window,
+    body, document, embedded_state, history, now, performance,
+    request_animation_frame, window,
 };
 pub use window::Window;
 
 /// alias Cmd to use Program as the APP
 pub type Cmd<APP, MSG> = cmd::Cmd<Program<APP, MSG>>;
+
+/// alias Sub to use Program as the APP
+pub type Sub<APP, MSG> = sub::Sub<Program<APP, MSG>>;
@@ -0,0 +1,284 @@
+//! provides the enter and exit animations of the elements using the
+//! [Web Animations API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Animations_API)
+//!
+//! The enter animation is played after the element is created and inserted.
+//! When an element with an exit animation is removed by a patch, it is kept in the document
+//! until its exit animation is finished, and it is skipped when the other patches look up
+//! their nodes.
+use crate::{html::attributes::AttributeValue, Attribute};
+use js_sys::{Array, Function, Object, Promise, Reflect};
+use std::cell::Cell;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Element, Node};
+
+/// the name of the attribute which carries the enter animation
+pub(crate) const ENTER_ATTR: &str = "animateenter";
+/// the name of the attribute which carries the exit animation
+pub(crate) const EXIT_ATTR: &str = "animateexit";
+/// the attribute of the elements which are removed, but are still playing their exit animation
+pub const EXITING_ATTR: &str = "data-sauron-exiting";
+/// the property of the element where its exit animation is kept until it is removed
+const EXIT_PROPERTY: &str = "__sauronExitAnimation";
+
+thread_local! {
+    /// the number of the elements playing their exit animation
+    static EXITING_COUNT: Cell<usize> = Cell::new(0);
+}
+
+/// The timing of the enter and exit animations
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnimationTiming {
+    /// the duration of the animation in milliseconds
+    pub duration: f64,
+    /// the delay before the animation starts in milliseconds
+    pub delay: f64,
+    /// the [easing](https://developer.mozilla.org/en-US/docs/Web/CSS/easing-function) of the animation
+    pub easing: &'static str,
+}
+
+impl AnimationTiming {
+    /// an animation which last `duration` milliseconds
+    pub fn new(duration: f64) -> Self {
+        AnimationTiming {
+            duration,
+            delay: 0.0,
+            easing: "ease",
+        }
+    }
+
+    /// start the animation after `delay` milliseconds
+    pub fn delay(mut self, delay: f64) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// use the `easing` such as `"ease-out"` or `"cubic-bezier(0.2, 0, 0, 1)"`
+    pub fn easing(mut self, easing: &'static str) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// the keyframes and the timing of an animation, carried as a custom value of the attribute
+#[derive(Clone, Debug, PartialEq)]
+struct KeyframeAnimation {
+    keyframes: Vec<Vec<(String, String)>>,
+    timing: AnimationTiming,
+}
+
+impl KeyframeAnimation {
+    fn new<V: ToString>(
+        keyframes: Vec<Vec<(&'static str, V)>>,
+        timing: AnimationTiming,
+    ) -> Self {
+        KeyframeAnimation {
+            keyframes: keyframes
+                .into_iter()
+                .map(|keyframe| {
+                    keyframe
+                        .into_iter()
+                        .map(|(property, value)| {
+                            (camel_case(property), value.to_string())
+                        })
+                        .collect()
+                })
+                .collect(),
+            timing,
+        }
+    }
+
+    /// the keyframes and the options arguments of `Element.animate`
+    fn to_js(&self, fill: &str) -> Array {
+        animate_args(
+            &self.keyframes,
+            self.timing.duration,
+            self.timing.delay,
+            self.timing.easing,
+            fill,
+        )
+    }
+}
+
+/// the keyframes and the options arguments of `Element.animate`
+fn animate_args(
+    keyframes: &[Vec<(String, String)>],
+    duration: f64,
+    delay: f64,
+    easing: &str,
+    fill: &str,
+) -> Array {
+    let keyframes_js = Array::new();
+    for keyframe in keyframes.iter() {
+        let object = Object::new();
+        for (property, value) in keyframe.iter() {
+            set(&object, property, &JsValue::from_str(value));
+        }
+        keyframes_js.push(&object);
+    }
+    let options = Object::new();
+    set(&options, "duration", &JsValue::from_f64(duration));
+    set(&options, "delay", &JsValue::from_f64(delay));
+    set(&options, "easing", &JsValue::from_str(easing));
+    set(&options, "fill", &JsValue::from_str(fill));
+    Array::of2(&keyframes_js, &options)
+}
+
+/// Play the `keyframes` animation on the element after it is inserted, ie: fade in the list items.
+///
+/// Each keyframe is a list of the css properties and their values.
+/// ```rust,ignore
+/// li(
+///     vec![
+///         key(item.id),
+///         animate_enter(
+///             vec![vec![("opacity", "0"), ("transform", "translateY(-8px)")], vec![("opacity", "1"), ("transform", "none")]],
+///             AnimationTiming::new(200.0).easing("ease-out"),
+///         ),
+///     ],
+///     vec![text(&item.name)],
+/// )
+/// ```
+pub fn animate_enter<MSG, V: ToString>(
+    keyframes: Vec<Vec<(&'static str, V)>>,
+    timing: AnimationTiming,
+) -> Attribute<MSG> {
+    mt_dom::attr(
+        ENTER_ATTR,
+        AttributeValue::from_custom(KeyframeAnimation::new(keyframes, timing)),
+    )
+}
+
+/// Play the `keyframes` animation on the element when it is removed from the view,
+/// the element is removed from the document only after the animation is finished.
+pub fn animate_exit<MSG, V: ToString>(
+    keyframes: Vec<Vec<(&'static str, V)>>,
+    timing: AnimationTiming,
+) -> Attribute<MSG> {
+    mt_dom::attr(
+        EXIT_ATTR,
+        AttributeValue::from_custom(KeyframeAnimation::new(keyframes, timing)),
+    )
+}
+
+/// play the enter animation of the `attr`, if any
+pub(crate) fn play_enter<MSG>(element: &Element, attr: &Attribute<MSG>) {
+    if let Some(animation) = keyframe_animation(attr) {
+        let _ = play(element, &animation.to_js("backwards"));
+    }
+}
+
+/// play the `keyframes` animation on the element right away
+pub(crate) fn play_keyframes(
+    element: &Element,
+    keyframes: &[Vec<(String, String)>],
+    duration: f64,
+    easing: &str,
+) {
+    let _ = play(
+        element,
+        &animate_args(keyframes, duration, 0.0, easing, "none"),
+    );
+}
+
+/// keep the exit animation in the element, to be played when the element is removed
+pub(crate) fn set_exit<MSG>(element: &Element, attr: &Attribute<MSG>) {
+    if let Some(animation) = keyframe_animation(attr) {
+        set(element, EXIT_PROPERTY, &animation.to_js("forwards"));
+    }
+}
+
+/// the element no longer has an exit animation
+pub(crate) fn remove_exit(element: &Element) {
+    let _ =
+        Reflect::delete_property(element, &JsValue::from_str(EXIT_PROPERTY));
+}
+
+/// Play the exit animation of the element and remove it after the animation is finished.
+/// Returns false if the element has no exit animation, which is then removed right away by the caller.
+pub(crate) fn remove_after_exit(element: &Element) -> bool {
+    let args = match Reflect::get(element, &JsValue::from_str(EXIT_PROPERTY)) {
+        Ok(args) if args.is_instance_of::<Array>() => args,
+        _ => return false,
+    };
+    let animation = match play(element, args.unchecked_ref()) {
+        Some(animation) => animation,
+        None => return false,
+    };
+    let finished: Promise =
+        match Reflect::get(&animation, &JsValue::from_str("finished")) {
+            Ok(finished) if finished.is_instance_of::<Promise>() => {
+                finished.unchecked_into()
+            }
+            _ => return false,
+        };
+    element
+        .set_attribute(EXITING_ATTR, "")
+        .expect("must set the exiting attribute");
+    EXITING_COUNT.with(|count| count.set(count.get() + 1));
+    let element = element.clone();
+    wasm_bindgen_futures::spawn_local(async move {
+        // the animation is also done when it is cancelled
+        let _ = JsFuture::from(finished).await;
+        element.remove();
+        EXITING_COUNT.with(|count| count.set(count.get() - 1));
+    });
+    true
+}
+
+/// the child node at `index`, not counting the children which are playing their exit animation
+pub(crate) fn child_node(node: &Node, index: usize) -> Option<Node> {
+    let children = node.child_nodes();
+    if EXITING_COUNT.with(|count| count.get()) == 0 {
+        return children.item(index as u32);
+    }
+    (0..children.length())
+        .filter_map(|i| children.item(i))
+        .filter(|child| !is_exiting(child))
+        .nth(index)
+}
+
+/// whether the node is playing its exit animation, which is removed after the animation
+pub(crate) fn is_exiting(node: &Node) -> bool {
+    node.dyn_ref::<Element>()
+        .map(|element| element.has_attribute(EXITING_ATTR))
+        .unwrap_or(false)
+}
+
+fn keyframe_animation<MSG>(
+    attr: &Attribute<MSG>,
+) -> Option<&KeyframeAnimation> {
+    attr.value()
+        .iter()
+        .find_map(|value| value.as_custom::<KeyframeAnimation>())
+}
+
+/// call `element.animate` with the `args`, returns the animation,
+/// or None if the Web Animations API is not supported
+fn play(element: &Element, args: &Array) -> Option<JsValue> {
+    let animate: Function =
+        Reflect::get(element, &JsValue::from_str("animate"))
+            .ok()?
+            .dyn_into()
+            .ok()?;
+    animate.apply(element, args).ok()
+}
+
+fn set(target: &JsValue, property: &str, value: &JsValue) {
+    Reflect::set(target, &JsValue::from_str(property), value)
+        .expect("must set the property");
+}
+
+/// convert the css property name into the name used in the keyframes, ie: `background-color` into `backgroundColor`
+fn camel_case(property: &str) -> String {
+    let mut parts = property.split('-');
+    let mut name = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.push_str(chars.as_str());
+        }
+    }
+    name
+}
@@ -1,4 +1,5 @@
-use crate::{Cmd, Node};
+use crate::{html::Head, Cmd, DomError, Node, Sub};
+use std::collections::BTreeMap;
 
 /// An Application is the root component of your program.
 /// Everything that happens in your application is done here.
@@ -24,11 +25,75 @@ where
     /// Returns a node on how the component is presented.
     fn view(&self) -> Node<MSG>;
 
+    /// Subscriptions to events outside of the view, such as the scrolling of the page.
+    /// This is called once, right after the program is mounted.
+    fn subscriptions(&self) -> Sub<Self, MSG>
+    where
+        Self: Sized + 'static,
+    {
+        Sub::none()
+    }
+
+    /// The state of the app serialized into json, ie: using `serde_json::to_string(self)`.
+    ///
+    /// This is embedded by the server into the rendered page with
+    /// [`serialized_state`](crate::html::serialized_state), so the client can start with the same
+    /// state using [`Program::mount_with_serialized_state`](crate::Program::mount_with_serialized_state)
+    /// instead of flashing the default state after the page is loaded.
+    #[cfg(feature = "with-persist")]
+    fn persist(&self) -> Option<String> {
+        None
+    }
+
+    /// The title and the meta and link tags of the document, which are applied into the head
+    /// of the document when the program is mounted and after the updates which return
+    /// a Cmd with [`Cmd::update_head`](crate::Cmd::update_head).
+    ///
+    /// The server can render the same [`Head`] into the page with [`Head::nodes`],
+    /// so the page has its title and meta tags before the app is loaded.
+    fn head(&self) -> Head {
+        Head::default()
+    }
+
     /// optionally an Application can specify its own css style
     fn style(&self) -> String {
         String::new()
     }
 
+    /// optionally an Application can specify its own css style which is scoped to the app,
+    /// the selectors are prefixed with a class derived from the type of the app
+    /// which is added into the root element of the view, use `:host` to select the root element.
+    ///
+    /// This prevents the style of the apps from colliding when multiple programs share a page.
+    /// See [`scoped_style`](crate::html::scoped_style) for how the css is rewritten.
+    fn style_scoped(&self) -> String {
+        String::new()
+    }
+
+    /// optionally an Application can specify its own css style which is only applied
+    /// when the page is printed, ie: hiding the navigation and buttons of a report.
+    fn style_print(&self) -> String {
+        String::new()
+    }
+
+    /// Called when the view can not be mounted or patched into the DOM,
+    /// the returned Cmd can be used to recover, ie: showing an error message.
+    ///
+    /// By default the error is logged, and the view is rebuilt from scratch when
+    /// patching the DOM failed, since the DOM could be left partially patched.
+    fn on_error(&mut self, error: DomError) -> Cmd<Self, MSG>
+    where
+        Self: Sized + 'static,
+    {
+        log::error!("{}", error);
+        match error {
+            DomError::Patch(_) => {
+                Cmd::new(|program| program.rebuild_view()).no_render()
+            }
+            DomError::Mount(_) => Cmd::none().no_render(),
+        }
+    }
+
     /// This is called after dispatching and updating the dom for the component
     /// This is for diagnostic and performance measurement purposes.
     ///
@@ -63,4 +128,15 @@ pub struct Measurements {
     pub dom_update_took: f64,
     /// Total time it took for the component dispatch
     pub total_time: f64,
+    /// The number and time it took for each type of the patches applied, ie: `ReplaceNode`
+    pub patch_timings: BTreeMap<&'static str, PatchTiming>,
+}
+
+/// The number of patches of a type applied into the DOM, and the time it took
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PatchTiming {
+    /// the number of the patches applied
+    pub count: usize,
+    /// the total time it took for applying the patches
+    pub duration: f64,
 }
@@ -1,21 +1,29 @@
 //! provides functionalities related to patching the DOM in the browser.
+#[cfg(feature = "with-measure")]
+use crate::{dom::PatchTiming, vdom::patch::PatchKind};
 use crate::{
     dom::{
-        created_node,
+        animate, click_outside, created_node,
         created_node::{ActiveClosure, CreatedNode},
+        event_delegation, resize, viewport, NodeRef,
     },
-    html::attributes::AttributeValue,
-    mt_dom::patch::{
-        AddAttributes, AppendChildren, InsertNode, RemoveAttributes,
-        RemoveNode, ReplaceNode,
+    html::attributes::{self, AttributeValue, Style, UpdateStyles},
+    vdom::patch::{
+        AddAttributes, AppendChildren, InsertNode, MoveNode, RemoveAttributes,
+        RemoveChildrenFrom, RemoveNode, ReplaceNode,
     },
-    Dispatch, Patch,
+    Attribute, Dispatch, Patch,
 };
 use js_sys::Function;
+#[cfg(feature = "with-measure")]
+use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use wasm_bindgen::{JsCast, JsValue};
-use web_sys::{Element, Node};
+use web_sys::{CssStyleDeclaration, Element, Node};
+
+#[cfg(feature = "with-measure")]
+thread_local!(static PATCH_TIMINGS: RefCell<BTreeMap<&'static str, PatchTiming>> = RefCell::new(BTreeMap::new()));
 
 /// Apply all of the patches to our old root node in order to create the new root node
 /// that we desire.
@@ -41,6 +49,33 @@ where
     )
 }
 
+/// Apply the patches which is the result of diffing the `old_vdom` with the new vdom.
+///
+/// The `old_vdom` is used to determine which classes are managed by the view,
+/// so changes in the class attribute only add or remove those classes using the `classList`
+/// of the element, leaving the classes added by third-party javascript intact.
+pub fn patch_with_old_vdom<DSP, MSG>(
+    program: &DSP,
+    old_vdom: &crate::Node<MSG>,
+    root_node: &mut Node,
+    old_closures: &mut ActiveClosure,
+    focused_node: &mut Option<Node>,
+    patches: Vec<Patch<MSG>>,
+) -> Result<ActiveClosure, JsValue>
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    patch_nodes(
+        program,
+        Some(old_vdom),
+        root_node,
+        old_closures,
+        focused_node,
+        patches,
+    )
+}
+
 /// patch using the tree path traversal instead of node_idx
 pub fn patch_by_traversal_path<DSP, MSG>(
     program: &DSP,
@@ -53,38 +88,368 @@ where
     MSG: 'static,
     DSP: Clone + Dispatch<MSG> + 'static,
 {
-    let nodes_to_find: Vec<(&[usize], Option<&&'static str>)> = patches
-        .iter()
-        .map(|patch| (patch.path(), patch.tag()))
-        .collect();
-
-    let mut paths = vec![];
-    for patch in patches.iter() {
-        paths.push(patch.path());
-    }
+    patch_nodes(
+        program,
+        None,
+        root_node,
+        old_closures,
+        focused_node,
+        patches,
+    )
+}
 
+fn patch_nodes<DSP, MSG>(
+    program: &DSP,
+    old_vdom: Option<&crate::Node<MSG>>,
+    root_node: &mut Node,
+    old_closures: &mut ActiveClosure,
+    focused_node: &mut Option<Node>,
+    patches: Vec<Patch<MSG>>,
+) -> Result<ActiveClosure, JsValue>
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let mut operations = PatchOperations::new(old_vdom, root_node, &patches)?;
     let mut active_closures = HashMap::new();
-    let nodes_to_patch =
-        find_all_nodes_by_path(root_node.clone(), &nodes_to_find);
+    while let Some(new_closures) =
+        operations.apply_next(program, root_node, old_closures, focused_node)
+    {
+        created_node::merge_closures(&mut active_closures, new_closures?);
+    }
+    Ok(active_closures)
+}
+
+/// The patches to be applied one at a time, along with the DOM nodes they are applied to.
+///
+/// The nodes are all looked up before any of the patches is applied, since the paths
+/// of the patches are the paths in the old view, which are shifted by the applied patches.
+/// This allows the patches to be applied in slices across multiple frames.
+pub(crate) struct PatchOperations<'p, 'a, MSG> {
+    old_vdom: Option<&'p crate::Node<MSG>>,
+    operations: std::vec::IntoIter<Operation<'p, 'a, MSG>>,
+    nodes_to_patch: BTreeMap<Vec<usize>, Node>,
+    /// the children removed by the `RemoveChildrenFrom` patches, keyed by the path of their parent
+    children_to_remove: BTreeMap<Vec<usize>, Vec<Node>>,
+}
+
+impl<'p, 'a, MSG> PatchOperations<'p, 'a, MSG>
+where
+    MSG: 'static,
+{
+    /// look up the nodes of the `patches` in the DOM of the `root_node`
+    pub(crate) fn new(
+        old_vdom: Option<&'p crate::Node<MSG>>,
+        root_node: &Node,
+        patches: &'p [Patch<'a, MSG>],
+    ) -> Result<Self, JsValue> {
+        let operations = coalesce_removals(patches);
 
-    for patch in patches.iter() {
+        let nodes_to_find: Vec<(&[usize], Option<&&'static str>)> = operations
+            .iter()
+            .flat_map(|operation| match operation {
+                Operation::Patch(patch) => std::slice::from_ref(*patch),
+                Operation::TruncateChildren(truncate) => truncate.patches,
+            })
+            .flat_map(|patch| {
+                // the node which a node is moved before is also looked up
+                let before = match patch {
+                    Patch::MoveNode(MoveNode {
+                        before_path: Some(before_path),
+                        ..
+                    }) => Some((&before_path.path[..], None)),
+                    _ => None,
+                };
+                std::iter::once((patch.path(), patch.tag())).chain(before)
+            })
+            .collect();
+
+        let nodes_to_patch =
+            find_all_nodes_by_path(root_node.clone(), &nodes_to_find)?;
+        let children_to_remove = patches
+            .iter()
+            .filter_map(|patch| match patch {
+                Patch::RemoveChildrenFrom(RemoveChildrenFrom {
+                    parent_path,
+                    start_index,
+                    ..
+                }) => {
+                    let parent = nodes_to_patch.get(&parent_path.path)?;
+                    Some((
+                        parent_path.path.clone(),
+                        find_children_from(parent, *start_index),
+                    ))
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(PatchOperations {
+            old_vdom,
+            operations: operations.into_iter(),
+            nodes_to_patch,
+            children_to_remove,
+        })
+    }
+
+    /// apply the next patch, returns the closures of the nodes it created,
+    /// or `None` when all of the patches are applied
+    pub(crate) fn apply_next<DSP>(
+        &mut self,
+        program: &DSP,
+        root_node: &mut Node,
+        old_closures: &mut ActiveClosure,
+        focused_node: &mut Option<Node>,
+    ) -> Option<Result<ActiveClosure, JsValue>>
+    where
+        DSP: Clone + Dispatch<MSG> + 'static,
+    {
+        let operation = self.operations.next()?;
+        #[cfg(feature = "with-measure")]
+        let t1 = crate::now();
+        let patch = match operation {
+            Operation::Patch(patch) => patch,
+            Operation::TruncateChildren(truncate) => {
+                let mut children = Vec::with_capacity(truncate.patches.len());
+                for patch in truncate.patches {
+                    let patch_path = patch.path();
+                    if let Patch::RemoveChildrenFrom(_) = patch {
+                        if let Some(removed) =
+                            self.children_to_remove.get(patch_path)
+                        {
+                            children.extend(removed);
+                        } else {
+                            return Some(Err(patch_error(format!("unable to find the parent of the children to be removed, patch_path: {:?}", patch_path))));
+                        }
+                    } else if let Some(child) =
+                        self.nodes_to_patch.get(patch_path)
+                    {
+                        children.push(child);
+                    } else {
+                        return Some(Err(patch_error(format!("unable to find the child to be removed, patch_path: {:?}", patch_path))));
+                    }
+                }
+                if let Err(e) = truncate_children(&children, old_closures) {
+                    return Some(Err(e));
+                }
+                #[cfg(feature = "with-measure")]
+                record_patch_timing(
+                    PatchKind::from(&truncate.patches[0]).name(),
+                    children.len(),
+                    crate::now() - t1,
+                );
+                return Some(Ok(ActiveClosure::new()));
+            }
+        };
         let patch_path = patch.path();
-        if let Some(element) = nodes_to_patch.get(patch_path) {
-            let new_closures = apply_patch_to_node(
+        let new_closures = if let Patch::MoveNode(move_node) = patch {
+            self.move_node(move_node).map(|()| ActiveClosure::new())
+        } else if let Some(element) = self.nodes_to_patch.get(patch_path) {
+            let old_node = self
+                .old_vdom
+                .and_then(|old_vdom| find_vdom_by_path(old_vdom, patch_path));
+            apply_patch_to_node(
                 program,
                 root_node,
                 element,
+                old_node,
                 old_closures,
                 focused_node,
                 patch,
-            )?;
-            active_closures.extend(new_closures);
+            )
         } else {
-            unreachable!("Getting here means we didn't find the element of next node that we are supposed to patch, patch_path: {:?}", patch_path);
+            Err(patch_error(format!(
+                "unable to find the node to be patched, patch_path: {:?}",
+                patch_path
+            )))
+        };
+        #[cfg(feature = "with-measure")]
+        record_patch_timing(
+            PatchKind::from(patch).name(),
+            1,
+            crate::now() - t1,
+        );
+        Some(new_closures)
+    }
+
+    /// move the existing node before the node at the `before_path`, or to the end of its parent,
+    /// both of the nodes are looked up before any of the patches is applied
+    fn move_node(&self, move_node: &MoveNode) -> Result<(), JsValue> {
+        let MoveNode {
+            patch_path,
+            before_path,
+            ..
+        } = move_node;
+        let node =
+            self.nodes_to_patch.get(&patch_path.path).ok_or_else(|| {
+                patch_error(format!(
+                    "unable to find the node to be moved, patch_path: {:?}",
+                    patch_path.path
+                ))
+            })?;
+        let parent_node = node.parent_node().ok_or_else(|| {
+            patch_error(format!(
+                "the node to be moved has no parent node, patch_path: {:?}",
+                patch_path.path
+            ))
+        })?;
+        match before_path {
+            Some(before_path) => {
+                let before =
+                    self.nodes_to_patch.get(&before_path.path).ok_or_else(
+                        || {
+                            patch_error(format!(
+                                "unable to find the node to move before, patch_path: {:?}",
+                                before_path.path
+                            ))
+                        },
+                    )?;
+                parent_node.insert_before(node, Some(before))?;
+            }
+            None => {
+                parent_node.append_child(node)?;
+            }
         }
+        Ok(())
     }
+}
 
-    Ok(active_closures)
+#[cfg(feature = "with-measure")]
+fn record_patch_timing(patch_kind: &'static str, count: usize, duration: f64) {
+    PATCH_TIMINGS.with(|patch_timings| {
+        let mut patch_timings = patch_timings.borrow_mut();
+        let patch_timing = patch_timings.entry(patch_kind).or_default();
+        patch_timing.count += count;
+        patch_timing.duration += duration;
+    });
+}
+
+/// take the timings of the patches applied since it was last taken
+#[cfg(feature = "with-measure")]
+pub(crate) fn take_patch_timings() -> BTreeMap<&'static str, PatchTiming> {
+    PATCH_TIMINGS.with(|patch_timings| patch_timings.take())
+}
+
+/// The patches are applied as is, except for the consecutive `RemoveNode` patches
+/// of the contiguous children of the same parent, which are removed together
+enum Operation<'p, 'a, MSG> {
+    Patch(&'p Patch<'a, MSG>),
+    TruncateChildren(TruncateChildren<'p, 'a, MSG>),
+}
+
+/// Remove the contiguous children of the `RemoveNode` `patches` together,
+/// this happens when the tail of the children disappears such as when a long list is cleared.
+/// The `RemoveChildrenFrom` patch is also applied as a single truncation of the children.
+///
+/// Each of the children is looked up along with the nodes of the other patches, before any of them
+/// is applied, since the index of the child in the patches is its index in the old view
+/// which is shifted by the nodes inserted into the same parent.
+struct TruncateChildren<'p, 'a, MSG> {
+    patches: &'p [Patch<'a, MSG>],
+}
+
+/// group the consecutive `RemoveNode` patches of the contiguous children of the same parent
+fn coalesce_removals<'p, 'a, MSG>(
+    patches: &'p [Patch<'a, MSG>],
+) -> Vec<Operation<'p, 'a, MSG>> {
+    let mut operations = vec![];
+    let mut i = 0;
+    while i < patches.len() {
+        let patch = &patches[i];
+        if let Patch::RemoveChildrenFrom(_) = patch {
+            operations.push(Operation::TruncateChildren(TruncateChildren {
+                patches: std::slice::from_ref(patch),
+            }));
+            i += 1;
+            continue;
+        }
+        let parent_path = match patch.path().split_last() {
+            Some((_, parent_path)) if is_remove_node(patch) => parent_path,
+            _ => {
+                operations.push(Operation::Patch(patch));
+                i += 1;
+                continue;
+            }
+        };
+        // the consecutive removals of the children of the same parent
+        let run_len = patches[i..]
+            .iter()
+            .take_while(|next| {
+                is_remove_node(next)
+                    && next
+                        .path()
+                        .split_last()
+                        .map(|(_, next_parent_path)| {
+                            next_parent_path == parent_path
+                        })
+                        .unwrap_or(false)
+            })
+            .count();
+        let run = &patches[i..i + run_len];
+        let mut indexes: Vec<usize> = run
+            .iter()
+            .filter_map(|patch| patch.path().last().copied())
+            .collect();
+        indexes.sort_unstable();
+        let is_contiguous =
+            indexes.windows(2).all(|pair| pair[1] == pair[0] + 1);
+        if run_len > 1 && is_contiguous {
+            operations.push(Operation::TruncateChildren(TruncateChildren {
+                patches: run,
+            }));
+        } else {
+            operations.extend(run.iter().map(Operation::Patch));
+        }
+        i += run_len;
+    }
+    operations
+}
+
+fn is_remove_node<MSG>(patch: &Patch<MSG>) -> bool {
+    matches!(patch, Patch::RemoveNode(_))
+}
+
+/// remove the `children` of the same parent in a single loop,
+/// including the event listeners attached to them
+fn truncate_children(
+    children: &[&Node],
+    old_closures: &mut ActiveClosure,
+) -> Result<(), JsValue> {
+    let parent_node = match children.first() {
+        Some(first_child) => first_child.parent_node().ok_or_else(|| {
+            patch_error(
+                "the children to be removed has no parent node".to_string(),
+            )
+        })?,
+        None => return Ok(()),
+    };
+    for node in children {
+        if node.node_type() == Node::ELEMENT_NODE {
+            dispatch_unmount_events(node.unchecked_ref(), old_closures)?;
+            remove_event_listeners(node.unchecked_ref(), old_closures)?;
+            if animate::remove_after_exit(node.unchecked_ref()) {
+                continue;
+            }
+        }
+        parent_node.remove_child(node)?;
+    }
+    Ok(())
+}
+
+/// the error when the DOM is not in the state the patches are created for,
+/// such as when its nodes are changed by a third-party javascript
+fn patch_error(message: String) -> JsValue {
+    js_sys::Error::new(&message).into()
+}
+
+/// the children of the `parent` from the child at `start_index` up to its last child,
+/// not counting the children which are playing their exit animation
+fn find_children_from(parent: &Node, start_index: usize) -> Vec<Node> {
+    let children = parent.child_nodes();
+    (0..children.length())
+        .filter_map(|i| children.item(i))
+        .filter(|child| !animate::is_exiting(child))
+        .skip(start_index)
+        .collect()
 }
 
 fn find_node_by_path_recursive(
@@ -95,8 +460,7 @@ fn find_node_by_path_recursive(
         Some(node)
     } else {
         let idx = path.remove(0);
-        let children = node.child_nodes();
-        if let Some(child) = children.item(idx as u32) {
+        if let Some(child) = animate::child_node(&node, idx) {
             find_node_by_path_recursive(child, path)
         } else {
             None
@@ -104,16 +468,41 @@ fn find_node_by_path_recursive(
     }
 }
 
+/// find the node in the vdom which is at this path,
+/// the first element of the path is the root node
+fn find_vdom_by_path<'a, MSG>(
+    node: &'a crate::Node<MSG>,
+    path: &[usize],
+) -> Option<&'a crate::Node<MSG>> {
+    let (_root_idx, path) = path.split_first()?;
+    let mut node = node;
+    for idx in path {
+        node = match node {
+            crate::Node::Element(element) => {
+                element.get_children().get(*idx)?
+            }
+            _ => return None,
+        };
+    }
+    Some(node)
+}
+
 fn find_all_nodes_by_path(
     node: Node,
     nodes_to_find: &[(&[usize], Option<&&'static str>)],
-) -> BTreeMap<Vec<usize>, Node> {
+) -> Result<BTreeMap<Vec<usize>, Node>, JsValue> {
     let mut nodes_to_patch: BTreeMap<Vec<usize>, Node> = BTreeMap::new();
 
     for (path, tag) in nodes_to_find {
-        let mut traverse_path = path.to_vec();
-        let root_idx = traverse_path.remove(0);
-        assert_eq!(0, root_idx, "path should start at 0");
+        let mut traverse_path = match path.split_first() {
+            Some((0, rest)) => rest.to_vec(),
+            _ => {
+                return Err(patch_error(format!(
+                    "the path {:?} should start at 0",
+                    path
+                )))
+            }
+        };
         if let Some(found) =
             find_node_by_path_recursive(node.clone(), &mut traverse_path)
         {
@@ -122,12 +511,14 @@ fn find_all_nodes_by_path(
             log::warn!("can not find: {:?} {:?}", path, tag);
         }
     }
-    nodes_to_patch
+    Ok(nodes_to_patch)
 }
 
 /// Get the "data-sauron-vdom-id" of all the desendent of this node including itself
 /// This is needed to free-up the closure that was attached ActiveClosure manually
-fn get_node_descendant_data_vdom_id(root_element: &Element) -> Vec<usize> {
+fn get_node_descendant_data_vdom_id(
+    root_element: &Element,
+) -> Result<Vec<usize>, JsValue> {
     let mut data_vdom_id = vec![];
 
     // TODO: there should be a better way to get the node-id back
@@ -135,24 +526,47 @@ fn get_node_descendant_data_vdom_id(root_element: &Element) -> Vec<usize> {
     if let Some(vdom_id_str) =
         root_element.get_attribute(created_node::DATA_VDOM_ID)
     {
-        let vdom_id = vdom_id_str
-            .parse::<usize>()
-            .expect("unable to parse sauron_vdom-id");
+        let vdom_id = vdom_id_str.parse::<usize>().map_err(|_| {
+            patch_error(format!("invalid vdom id: {:?}", vdom_id_str))
+        })?;
         data_vdom_id.push(vdom_id);
     }
 
     let children = root_element.child_nodes();
     let child_node_count = children.length();
-    for i in 0..child_node_count {
-        let child_node = children.item(i).expect("Expecting a child node");
+    for child_node in (0..child_node_count).filter_map(|i| children.item(i)) {
         if child_node.node_type() == Node::ELEMENT_NODE {
             let child_element = child_node.unchecked_ref::<Element>();
             let child_data_vdom_id =
-                get_node_descendant_data_vdom_id(child_element);
+                get_node_descendant_data_vdom_id(child_element)?;
             data_vdom_id.extend(child_data_vdom_id);
         }
     }
-    data_vdom_id
+    Ok(data_vdom_id)
+}
+
+/// dispatch the `unmount` event to this element and its descendants which listen to it,
+/// this is done before they are removed, so the listeners can still access them in the dom
+pub(crate) fn dispatch_unmount_events(
+    element: &Element,
+    old_closures: &ActiveClosure,
+) -> Result<(), JsValue> {
+    let is_listening = element
+        .get_attribute(created_node::DATA_VDOM_ID)
+        .and_then(|vdom_id| vdom_id.parse::<usize>().ok())
+        .and_then(|vdom_id| old_closures.get(&vdom_id))
+        .map(|closures| closures.iter().any(|(event, _)| *event == "unmount"))
+        .unwrap_or(false);
+    if is_listening {
+        element.dispatch_event(&web_sys::Event::new("unmount")?)?;
+    }
+    let children = element.child_nodes();
+    for child_node in (0..children.length()).filter_map(|i| children.item(i)) {
+        if child_node.node_type() == Node::ELEMENT_NODE {
+            dispatch_unmount_events(child_node.unchecked_ref(), old_closures)?;
+        }
+    }
+    Ok(())
 }
 
 /// remove all the event listeners for this node
@@ -160,18 +574,18 @@ fn remove_event_listeners(
     node: &Element,
     old_closures: &mut ActiveClosure,
 ) -> Result<(), JsValue> {
-    let all_descendant_vdom_id = get_node_descendant_data_vdom_id(node);
+    let all_descendant_vdom_id = get_node_descendant_data_vdom_id(node)?;
     for vdom_id in all_descendant_vdom_id {
-        if let Some(old_closure) = old_closures.get(&vdom_id) {
+        event_delegation::unregister(vdom_id);
+        viewport::unobserve(vdom_id);
+        resize::unobserve(vdom_id);
+        click_outside::unregister(vdom_id);
+        // remove closure active_closure in dom_updater to free up memory
+        if let Some(old_closure) = old_closures.remove(&vdom_id) {
             for (event, oc) in old_closure.iter() {
                 let func: &Function = oc.as_ref().unchecked_ref();
                 node.remove_event_listener_with_callback(event, func)?;
             }
-
-            // remove closure active_closure in dom_updater to free up memory
-            old_closures
-                .remove(&vdom_id)
-                .expect("Unable to remove old closure");
         } else {
             log::warn!(
                 "There is no closure marked with that vdom_id: {}",
@@ -188,8 +602,9 @@ fn remove_event_listener_with_name(
     node: &Element,
     old_closures: &mut ActiveClosure,
 ) -> Result<(), JsValue> {
-    let all_descendant_vdom_id = get_node_descendant_data_vdom_id(node);
+    let all_descendant_vdom_id = get_node_descendant_data_vdom_id(node)?;
     for vdom_id in all_descendant_vdom_id {
+        event_delegation::unregister_event(vdom_id, event_name);
         if let Some(old_closure) = old_closures.get_mut(&vdom_id) {
             for (event, oc) in old_closure.iter() {
                 if *event == event_name {
@@ -199,12 +614,22 @@ fn remove_event_listener_with_name(
             }
 
             old_closure.retain(|(event, _oc)| *event != event_name);
+            if !old_closure
+                .iter()
+                .any(|(event, _oc)| viewport::is_viewport_event(event))
+            {
+                viewport::unobserve(vdom_id);
+            }
+            if event_name == resize::RESIZE_ELEMENT {
+                resize::unobserve(vdom_id);
+            }
+            if event_name == click_outside::CLICK_OUTSIDE {
+                click_outside::unregister(vdom_id);
+            }
 
             // remove closure active_closure in dom_updater to free up memory
             if old_closure.is_empty() {
-                old_closures
-                    .remove(&vdom_id)
-                    .expect("Unable to remove old closure");
+                old_closures.remove(&vdom_id);
             }
         } else {
             log::warn!(
@@ -216,16 +641,146 @@ fn remove_event_listener_with_name(
     Ok(())
 }
 
+/// returns true if this is the focus or the autofocus attribute which is turned on
+fn is_focus_attribute<MSG>(attr: &Attribute<MSG>) -> bool {
+    (*attr.name() == "focus" || *attr.name() == "autofocus")
+        && attr.value().iter().any(|value| {
+            value
+                .get_simple()
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false)
+        })
+}
+
+/// returns true if this is the class attribute
+fn is_class_attribute<MSG>(attr: &Attribute<MSG>) -> bool {
+    *attr.name() == "class" && attr.namespace().is_none()
+}
+
+/// the class names of these class attributes, after applying the class transformer
+fn class_names<'a, MSG: 'a>(
+    attrs: impl IntoIterator<Item = &'a Attribute<MSG>>,
+) -> Vec<String> {
+    let mut class_names: Vec<String> = vec![];
+    for attr in attrs {
+        for att_value in attr.value() {
+            if let AttributeValue::Simple(value) = att_value {
+                let classes = attributes::transform_classes(&value.to_string());
+                for class_name in classes.split_whitespace() {
+                    if !class_names.iter().any(|c| c == class_name) {
+                        class_names.push(class_name.to_string());
+                    }
+                }
+            }
+        }
+    }
+    class_names
+}
+
+/// the class names in the class attribute of this old vdom node
+fn old_class_names<MSG>(old_node: &crate::Node<MSG>) -> Vec<String> {
+    match old_node {
+        crate::Node::Element(old_element) => class_names(
+            old_element
+                .get_attributes()
+                .iter()
+                .filter(|attr| is_class_attribute(attr)),
+        ),
+        _ => vec![],
+    }
+}
+
+/// Only add the new class names and remove the old class names which are not
+/// in the new class names, any other classes in the element are left intact.
+fn patch_class_list(
+    element: &Element,
+    old_class_names: &[String],
+    new_class_names: &[String],
+) -> Result<(), JsValue> {
+    let class_list = element.class_list();
+    for old_class in old_class_names {
+        if !new_class_names.contains(old_class) {
+            class_list.remove_1(old_class)?;
+        }
+    }
+    for new_class in new_class_names {
+        if !old_class_names.contains(new_class) {
+            class_list.add_1(new_class)?;
+        }
+    }
+    Ok(())
+}
+
+/// returns true if this is the style attribute
+fn is_style_attribute<MSG>(attr: &Attribute<MSG>) -> bool {
+    *attr.name() == "style" && attr.namespace().is_none()
+}
+
+/// the styles of these style attributes,
+/// returns None if some of the values are not `AttributeValue::Style`
+/// such as styles that are set using a plain string
+fn styles<'a, MSG: 'a>(
+    attrs: impl IntoIterator<Item = &'a Attribute<MSG>>,
+) -> Option<Vec<Style>> {
+    let mut styles = vec![];
+    for attr in attrs {
+        for att_value in attr.value() {
+            match att_value {
+                AttributeValue::Style(style) => styles.extend_from_slice(style),
+                AttributeValue::Empty => (),
+                _ => return None,
+            }
+        }
+    }
+    Some(styles)
+}
+
+/// the styles in the style attribute of this old vdom node
+fn old_styles<MSG>(old_node: &crate::Node<MSG>) -> Option<Vec<Style>> {
+    match old_node {
+        crate::Node::Element(old_element) => styles(
+            old_element
+                .get_attributes()
+                .iter()
+                .filter(|attr| is_style_attribute(attr)),
+        ),
+        _ => None,
+    }
+}
+
+/// set and remove only the style properties that changed,
+/// any other style properties in the element are left intact.
+fn patch_style_properties(
+    element: &Element,
+    update_styles: &UpdateStyles,
+) -> Result<(), JsValue> {
+    // both html and svg elements has a style property
+    let style: CssStyleDeclaration =
+        js_sys::Reflect::get(element, &JsValue::from_str("style"))?
+            .unchecked_into();
+    for name in update_styles.remove.iter() {
+        style.remove_property(name)?;
+    }
+    for new_style in update_styles.set.iter() {
+        style.set_property(&new_style.name, &new_style.value.to_string())?;
+    }
+    Ok(())
+}
+
 /// apply a the patch to this element node.
 /// and return the ActiveClosure that may be attached to that element
 ///
 /// Note: a mutable root_node is passed here
 /// for the sole purpose of setting it when the a patch ReplaceNode at 0 is encountered.
+///
+/// The `old_node` is the vdom counterpart of this node, when it is available
+/// changes to the class attribute are applied using the `classList` of the element.
 #[track_caller]
 fn apply_patch_to_node<DSP, MSG>(
     program: &DSP,
     root_node: &mut Node,
     node: &Node,
+    old_node: Option<&crate::Node<MSG>>,
     old_closures: &mut ActiveClosure,
     focused_node: &mut Option<Node>,
     patch: &Patch<MSG>,
@@ -248,41 +803,110 @@ where
                 program,
                 for_insert,
                 focused_node,
-            );
+            )?;
             if let Some(parent_node) = target_element.parent_node() {
                 let parent_element: &Element = parent_node.unchecked_ref();
                 if let Some(tag) = tag {
                     let parent_tag = parent_element.tag_name().to_lowercase();
                     if parent_tag != **tag {
-                        panic!(
+                        return Err(patch_error(format!(
                             "expecting a tag: {:?}, but found: {:?}",
                             tag, parent_tag
-                        );
+                        )));
                     }
                 }
                 parent_node
-                    .insert_before(&created_node.node, Some(target_element))
-                    .expect("must remove target node");
+                    .insert_before(&created_node.node, Some(target_element))?;
             } else {
-                panic!("unable to get parent node of the target element: {:?} thas has a tag: {:?} in path: {:?}, for patching: {:#?}", target_element, tag, patch_path, for_insert);
+                return Err(patch_error(format!("unable to get parent node of the target element that has a tag: {:?} in path: {:?}", tag, patch_path)));
             }
 
             Ok(active_closures)
         }
         Patch::AddAttributes(AddAttributes { attrs, .. }) => {
             let element: &Element = node.unchecked_ref();
-            CreatedNode::set_element_attributes(
-                program,
-                &mut active_closures,
-                element,
-                attrs,
-            );
+            if let Some(old_node) = old_node {
+                let mut class_attrs = vec![];
+                let mut style_attrs = vec![];
+                let mut other_attrs = vec![];
+                for attr in attrs.iter().copied() {
+                    if is_class_attribute(attr) {
+                        class_attrs.push(attr);
+                    } else if is_style_attribute(attr) {
+                        style_attrs.push(attr);
+                    } else {
+                        other_attrs.push(attr);
+                    }
+                }
+                if !class_attrs.is_empty() {
+                    patch_class_list(
+                        element,
+                        &old_class_names(old_node),
+                        &class_names(class_attrs),
+                    )?;
+                }
+                if !style_attrs.is_empty() {
+                    match (old_styles(old_node), styles(style_attrs.clone())) {
+                        (Some(old_styles), Some(new_styles)) => {
+                            patch_style_properties(
+                                element,
+                                &UpdateStyles::diff(&old_styles, &new_styles),
+                            )?;
+                        }
+                        // styles which are set as plain string are set as a whole
+                        _ => other_attrs.extend(style_attrs),
+                    }
+                }
+                CreatedNode::set_element_attributes(
+                    program,
+                    &mut active_closures,
+                    element,
+                    &other_attrs,
+                )?;
+            } else {
+                CreatedNode::set_element_attributes(
+                    program,
+                    &mut active_closures,
+                    element,
+                    attrs,
+                )?;
+            }
+            // the element is focused when the focus is turned on in the patch,
+            // not only when it is created
+            if attrs.iter().any(|attr| is_focus_attribute(attr)) {
+                *focused_node = Some(node.clone());
+                CreatedNode::set_element_focus(element)?;
+            }
 
             Ok(active_closures)
         }
         Patch::RemoveAttributes(RemoveAttributes { attrs, .. }) => {
             let element: &Element = node.unchecked_ref();
             for attr in attrs.iter() {
+                if *attr.name() == animate::EXIT_ATTR {
+                    animate::remove_exit(element);
+                    continue;
+                }
+                // only remove the classes and styles that was set by the view
+                if let Some(old_node) = old_node {
+                    if is_class_attribute(attr) {
+                        patch_class_list(
+                            element,
+                            &old_class_names(old_node),
+                            &[],
+                        )?;
+                        continue;
+                    }
+                    if is_style_attribute(attr) {
+                        if let Some(old_styles) = old_styles(old_node) {
+                            patch_style_properties(
+                                element,
+                                &UpdateStyles::diff(&old_styles, &[]),
+                            )?;
+                            continue;
+                        }
+                    }
+                }
                 for att_value in attr.value() {
                     match att_value {
                         AttributeValue::Simple(_) => {
@@ -298,6 +922,26 @@ where
                                 old_closures,
                             )?;
                         }
+                        // reset the property to the blank state
+                        AttributeValue::Property(value) => {
+                            let blank = match value.as_bool() {
+                                Some(_) => JsValue::from_bool(false),
+                                None => JsValue::from_str(""),
+                            };
+                            js_sys::Reflect::set(
+                                element,
+                                &JsValue::from_str(attr.name()),
+                                &blank,
+                            )?;
+                        }
+                        // the node ref is no longer attached to this element
+                        AttributeValue::Custom(custom) => {
+                            if let Some(node_ref) =
+                                custom.as_any().downcast_ref::<NodeRef>()
+                            {
+                                node_ref.detach(element);
+                            }
+                        }
                         AttributeValue::FunctionCall(_)
                         | AttributeValue::Style(_)
                         | AttributeValue::Empty => (),
@@ -327,23 +971,22 @@ where
                 program,
                 replacement,
                 focused_node,
-            );
+            )?;
             if let Some(tag) = tag {
                 let target_tag = element.tag_name().to_lowercase();
                 if target_tag != **tag {
-                    panic!(
+                    return Err(patch_error(format!(
                         "expecting a tag: {:?}, but found: {:?}",
                         tag, target_tag
-                    );
+                    )));
                 }
             }
 
             if element.node_type() == Node::ELEMENT_NODE {
+                dispatch_unmount_events(element, old_closures)?;
                 remove_event_listeners(element, old_closures)?;
             }
-            element
-                .replace_with_with_node_1(&created_node.node)
-                .expect("must replace node");
+            element.replace_with_with_node_1(&created_node.node)?;
 
             // if what we are replacing is a root node:
             // we replace the root node here, so that's reference is updated
@@ -355,34 +998,55 @@ where
         }
         Patch::RemoveNode(RemoveNode { .. }) => {
             let element: &Element = node.unchecked_ref();
-            let parent_node =
-                element.parent_node().expect("must have a parent node");
-            parent_node
-                .remove_child(element)
-                .expect("must remove target node");
+            if element.node_type() == Node::ELEMENT_NODE {
+                dispatch_unmount_events(element, old_closures)?;
+            }
+            // the element with an exit animation is removed after the animation is finished
+            let is_exiting = element.node_type() == Node::ELEMENT_NODE
+                && animate::remove_after_exit(element);
+            if !is_exiting {
+                let parent_node = element.parent_node().ok_or_else(|| {
+                    patch_error(format!(
+                        "the node to be removed has no parent node, patch_path: {:?}",
+                        patch.path()
+                    ))
+                })?;
+                parent_node.remove_child(element)?;
+            }
             if element.node_type() == Node::ELEMENT_NODE {
                 let element: &Element = node.unchecked_ref();
                 remove_event_listeners(element, old_closures)?;
             }
             Ok(active_closures)
         }
+        // the node it is moved before is looked up before any of the patches is applied,
+        // and is moved in `PatchOperations::move_node` instead
+        Patch::MoveNode(MoveNode { patch_path, .. }) => Err(patch_error(format!(
+            "MoveNode must be applied along with the node it is moved before, patch_path: {:?}",
+            patch_path.path
+        ))),
+        // the children to be removed are looked up before any of the patches is applied,
+        // and are removed as a `TruncateChildren` operation instead
+        Patch::RemoveChildrenFrom(RemoveChildrenFrom { parent_path, .. }) => {
+            Err(patch_error(format!(
+                "RemoveChildrenFrom must be applied as a truncation of the children, patch_path: {:?}",
+                parent_path.path
+            )))
+        }
         Patch::AppendChildren(AppendChildren {
             tag: _,
             patch_path: _,
             children: new_nodes,
         }) => {
             let element: &Element = node.unchecked_ref();
-            let mut active_closures = HashMap::new();
-            for new_node in new_nodes.iter() {
-                let created_node = CreatedNode::create_dom_node::<DSP, MSG>(
-                    program,
-                    new_node,
-                    focused_node,
-                );
-                element.append_child(&created_node.node)?;
-                active_closures.extend(created_node.closures);
-            }
-            Ok(active_closures)
+            // the nodes are appended at once, so the document reflows only once
+            let created_node = CreatedNode::create_dom_nodes::<DSP, MSG>(
+                program,
+                new_nodes.iter().copied(),
+                focused_node,
+            )?;
+            element.append_child(&created_node.node)?;
+            Ok(created_node.closures)
         }
         Patch::ChangeText(ct) => {
             node.set_node_value(Some(&ct.new.text));
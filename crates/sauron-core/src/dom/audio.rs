@@ -0,0 +1,177 @@
+//! provides functionalities for playing simple sounds, such as notification sounds.
+//!
+//! A shared AudioContext is created on first use and is resumed on the first user gesture,
+//! since browsers don't allow playing audio before the user has interacted with the page.
+//! The decoded sounds are cached, so playing the same url again doesn't fetch it again.
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AudioBuffer, AudioContext, AudioContextState, GainNode};
+
+/// the events which counts as a user gesture to unlock the audio
+const GESTURE_EVENTS: [&str; 3] = ["pointerdown", "keydown", "touchend"];
+
+thread_local!(static AUDIO: RefCell<Option<Audio>> = RefCell::new(None));
+thread_local!(static VOLUME: Cell<f32> = Cell::new(1.0));
+thread_local!(static IS_MUTED: Cell<bool> = Cell::new(false));
+thread_local!(static BUFFERS: RefCell<HashMap<String, AudioBuffer>> = RefCell::new(HashMap::new()));
+
+/// A sound to be played with [`Cmd::play_sound`](crate::dom::cmd::Cmd::play_sound)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Sound {
+    /// the url of the sound file, the decoded sound is cached
+    Url(String),
+    /// the content of the sound file
+    Buffer(Vec<u8>),
+}
+
+impl From<&str> for Sound {
+    fn from(url: &str) -> Self {
+        Sound::Url(url.to_string())
+    }
+}
+
+impl From<String> for Sound {
+    fn from(url: String) -> Self {
+        Sound::Url(url)
+    }
+}
+
+impl From<Vec<u8>> for Sound {
+    fn from(bytes: Vec<u8>) -> Self {
+        Sound::Buffer(bytes)
+    }
+}
+
+/// the shared audio context and the gain node which controls the volume
+struct Audio {
+    context: AudioContext,
+    gain: GainNode,
+}
+
+/// the shared audio context, created on first use
+fn with_audio<F, R>(f: F) -> R
+where
+    F: FnOnce(&Audio) -> R,
+{
+    AUDIO.with(|audio| {
+        let mut audio = audio.borrow_mut();
+        let audio = audio.get_or_insert_with(|| {
+            let context =
+                AudioContext::new().expect("must create an audio context");
+            let gain = context.create_gain().expect("must create a gain node");
+            gain.connect_with_audio_node(&context.destination())
+                .expect("must connect to the destination");
+            gain.gain().set_value(effective_volume());
+            unlock_on_user_gesture(&context);
+            Audio { context, gain }
+        });
+        f(audio)
+    })
+}
+
+/// resume the audio context on the user gestures, until it is running
+fn unlock_on_user_gesture(context: &AudioContext) {
+    if context.state() == AudioContextState::Running {
+        return;
+    }
+    let context = context.clone();
+    let unlock: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+        if context.state() == AudioContextState::Suspended {
+            let _ = context.resume();
+        }
+    }));
+    let window = crate::window();
+    for event in GESTURE_EVENTS.iter() {
+        window
+            .add_event_listener_with_callback(
+                event,
+                unlock.as_ref().unchecked_ref(),
+            )
+            .expect("must add the unlock listener");
+    }
+    // the audio context lives for the rest of the page lifetime
+    unlock.forget();
+}
+
+fn effective_volume() -> f32 {
+    if is_muted() {
+        0.0
+    } else {
+        volume()
+    }
+}
+
+fn update_gain() {
+    AUDIO.with(|audio| {
+        if let Some(audio) = audio.borrow().as_ref() {
+            audio.gain.gain().set_value(effective_volume());
+        }
+    })
+}
+
+/// set the volume of the sounds, from `0.0` to `1.0`
+pub fn set_volume(volume: f32) {
+    VOLUME.with(|v| v.set(volume.max(0.0).min(1.0)));
+    update_gain();
+}
+
+/// the volume of the sounds
+pub fn volume() -> f32 {
+    VOLUME.with(|v| v.get())
+}
+
+/// mute or unmute the sounds
+pub fn set_muted(is_muted: bool) {
+    IS_MUTED.with(|m| m.set(is_muted));
+    update_gain();
+}
+
+/// returns true if the sounds are muted
+pub fn is_muted() -> bool {
+    IS_MUTED.with(|m| m.get())
+}
+
+/// fetch and decode the sound, the sounds from url are cached
+pub(crate) async fn load(sound: &Sound) -> Result<AudioBuffer, JsValue> {
+    let bytes = match sound {
+        Sound::Url(url) => {
+            if let Some(buffer) =
+                BUFFERS.with(|buffers| buffers.borrow().get(url).cloned())
+            {
+                return Ok(buffer);
+            }
+            let response: web_sys::Response =
+                JsFuture::from(crate::window().fetch_with_str(url))
+                    .await?
+                    .unchecked_into();
+            JsFuture::from(response.array_buffer()?)
+                .await?
+                .unchecked_into::<js_sys::ArrayBuffer>()
+        }
+        Sound::Buffer(bytes) => {
+            js_sys::Uint8Array::from(bytes.as_slice()).buffer()
+        }
+    };
+    let decode = with_audio(|audio| audio.context.decode_audio_data(&bytes))?;
+    let buffer: AudioBuffer = JsFuture::from(decode).await?.unchecked_into();
+    if let Sound::Url(url) = sound {
+        BUFFERS.with(|buffers| {
+            buffers.borrow_mut().insert(url.to_string(), buffer.clone())
+        });
+    }
+    Ok(buffer)
+}
+
+/// play the decoded sound through the shared audio context
+pub(crate) fn play(buffer: &AudioBuffer) -> Result<(), JsValue> {
+    with_audio(|audio| {
+        let source = audio.context.create_buffer_source()?;
+        source.set_buffer(Some(buffer));
+        source.connect_with_audio_node(&audio.gain)?;
+        source.start()
+    })
+}
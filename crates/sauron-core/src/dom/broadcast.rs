@@ -0,0 +1,90 @@
+//! sends the msgs to the other tabs and windows of the same origin through a `BroadcastChannel`,
+//! see [`Cmd::broadcast`](crate::dom::cmd::Cmd::broadcast)
+//! and [`Sub::broadcast_channel`](crate::dom::sub::Sub::broadcast_channel).
+use crate::dom::sub::Subscription;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{BroadcastChannel, MessageEvent};
+
+thread_local! {
+    /// identifies the msgs sent from this tab, which are delivered to the channels
+    /// of this tab too, so they are not dispatched back into the program which sent them
+    static SENDER_ID: String = format!("{:x}", (js_sys::Math::random() * u64::MAX as f64) as u64);
+}
+
+/// the msg along with the tab which sent it
+#[derive(Serialize, Deserialize)]
+struct Envelope<MSG> {
+    sender: String,
+    msg: MSG,
+}
+
+/// serialize the `msg` to be sent with [`post`]
+pub(crate) fn serialize<MSG>(msg: &MSG) -> serde_json::Result<String>
+where
+    MSG: Serialize,
+{
+    SENDER_ID.with(|sender| {
+        serde_json::to_string(&Envelope {
+            sender: sender.clone(),
+            msg,
+        })
+    })
+}
+
+/// post the serialized msg into the channel `name`
+pub(crate) fn post(name: &str, json: &str) -> Result<(), JsValue> {
+    let channel = BroadcastChannel::new(name)?;
+    let posted = channel.post_message(&JsValue::from_str(json));
+    channel.close();
+    posted
+}
+
+/// call `f` with the msgs received in the channel `name` from the other tabs,
+/// the channel is closed when the subscription is unsubscribed
+pub(crate) fn listen<MSG, F>(name: &str, f: F) -> Subscription
+where
+    MSG: DeserializeOwned,
+    F: Fn(MSG) + 'static,
+{
+    let channel = match BroadcastChannel::new(name) {
+        Ok(channel) => channel,
+        Err(e) => {
+            log::warn!(
+                "unable to open the broadcast channel {}: {:?}",
+                name,
+                e
+            );
+            return Subscription::none();
+        }
+    };
+    let name = name.to_string();
+    let listener: Closure<dyn FnMut(MessageEvent)> = Closure::wrap(Box::new(
+        move |event: MessageEvent| {
+            let json = match event.data().as_string() {
+                Some(json) => json,
+                None => return,
+            };
+            match serde_json::from_str::<Envelope<MSG>>(&json) {
+                Ok(envelope) => {
+                    let is_own = SENDER_ID
+                        .with(|sender| *sender == envelope.sender);
+                    if !is_own {
+                        f(envelope.msg);
+                    }
+                }
+                Err(e) => log::warn!(
+                    "unable to deserialize the msg of the broadcast channel {}: {}",
+                    name,
+                    e
+                ),
+            }
+        },
+    ));
+    channel.set_onmessage(Some(listener.as_ref().unchecked_ref()));
+    Subscription::new(move || {
+        channel.set_onmessage(None);
+        channel.close();
+        drop(listener);
+    })
+}
@@ -0,0 +1,130 @@
+//! provides a 2d canvas which is drawn with a callback, instead of with the vdom.
+//!
+use crate::{
+    dom::{events::Size, gpu_canvas},
+    html::{
+        attributes::{AttributeValue, CustomValue},
+        tags::canvas,
+    },
+    Attribute, Node,
+};
+use std::{fmt, rc::Rc};
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, Element, HtmlCanvasElement};
+
+/// the name of the attribute which carries the drawing of the canvas
+pub(crate) const DRAW_ATTR: &str = "canvasdraw";
+
+/// The draw callback of the canvas together with the dependencies it is drawn with.
+///
+/// Only the dependencies are compared when diffing, so the draw callback which is created
+/// on every view does not trigger a redraw unless the dependencies changed.
+struct Drawing {
+    deps: Rc<dyn CustomValue>,
+    draw: Rc<dyn Fn(&CanvasRenderingContext2d, Size)>,
+}
+
+impl PartialEq for Drawing {
+    fn eq(&self, other: &Self) -> bool {
+        self.deps.eq_custom(other.deps.as_ref())
+    }
+}
+
+impl fmt::Debug for Drawing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Drawing").field("deps", &self.deps).finish()
+    }
+}
+
+/// Creates a 2d canvas which is drawn with `draw`, which is called with the context and the
+/// size of the canvas in css pixels.
+///
+/// The canvas is drawn after it is mounted and after each update where the `deps` changed.
+/// The diff treats the drawing as opaque, only the `deps` are compared, so the canvas element
+/// is kept and is not redrawn when the other parts of the view change.
+///
+/// The drawing buffer follows the size of the canvas multiplied by the device pixel ratio,
+/// and the context is scaled accordingly, so the drawing is in css pixels.
+///
+/// # Examples
+/// ```rust,ignore
+/// canvas_draw(vec![class("chart")], self.points.clone(), {
+///     let points = self.points.clone();
+///     move |ctx, size| {
+///         ctx.clear_rect(0.0, 0.0, size.width, size.height);
+///         for (x, y) in points.iter() {
+///             ctx.fill_rect(*x, size.height - *y, 2.0, 2.0);
+///         }
+///     }
+/// })
+/// ```
+pub fn canvas_draw<MSG, D, F>(
+    attrs: impl IntoIterator<Item = Attribute<MSG>>,
+    deps: D,
+    draw: F,
+) -> Node<MSG>
+where
+    D: PartialEq + fmt::Debug + 'static,
+    F: Fn(&CanvasRenderingContext2d, Size) + 'static,
+{
+    let drawing = Drawing {
+        deps: Rc::new(deps),
+        draw: Rc::new(draw),
+    };
+    canvas(
+        attrs.into_iter().chain(vec![mt_dom::attr(
+            DRAW_ATTR,
+            AttributeValue::from_custom(drawing),
+        )]),
+        vec![],
+    )
+}
+
+/// draw the canvas in the next animation frame, when it is already in the document and has its size.
+/// This is called when the drawing attribute is set, which is when the canvas is created
+/// and when the deps of the drawing changed.
+pub(crate) fn draw_later<MSG>(element: &Element, attr: &Attribute<MSG>) {
+    let draw = match attr
+        .value()
+        .iter()
+        .find_map(|value| value.as_custom::<Drawing>())
+    {
+        Some(drawing) => Rc::clone(&drawing.draw),
+        None => return,
+    };
+    let canvas = match element.dyn_ref::<HtmlCanvasElement>() {
+        Some(canvas) => canvas.clone(),
+        None => {
+            log::warn!(
+                "canvas_draw is only for canvas elements: {:?}",
+                element
+            );
+            return;
+        }
+    };
+    crate::dom::request_animation_frame(move || {
+        gpu_canvas::resize_drawing_buffer(&canvas);
+        let ctx: CanvasRenderingContext2d = match canvas.get_context("2d") {
+            Ok(Some(ctx)) => ctx.unchecked_into(),
+            _ => {
+                log::warn!("unable to get the 2d context of the canvas");
+                return;
+            }
+        };
+        let device_pixel_ratio = crate::window().device_pixel_ratio();
+        ctx.set_transform(
+            device_pixel_ratio,
+            0.0,
+            0.0,
+            device_pixel_ratio,
+            0.0,
+            0.0,
+        )
+        .expect("must set the transform");
+        let size = Size {
+            width: f64::from(canvas.client_width()),
+            height: f64::from(canvas.client_height()),
+        };
+        draw(&ctx, size);
+    });
+}
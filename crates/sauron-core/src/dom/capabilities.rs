@@ -0,0 +1,134 @@
+//! Detects the features supported by the browser and the preferences of the user.
+//!
+//! The capabilities are detected once on first use, so views and the modules
+//! which has feature dependent code paths can consult it without probing the `window` each time.
+use std::cell::RefCell;
+use wasm_bindgen::JsValue;
+
+thread_local!(static CAPABILITIES: RefCell<Option<Capabilities>> = RefCell::new(None));
+
+/// The features supported by the browser and the preferences of the user
+#[derive(Clone, Debug, PartialEq)]
+pub struct Capabilities {
+    /// the device has a touch screen
+    pub touch: bool,
+    /// the user prefers reduced motion, animations should be kept to a minimum
+    pub prefers_reduced_motion: bool,
+    /// the effective connection type of the network ie: `4g`, `3g`, `2g`, `slow-2g`,
+    /// `None` if the browser doesn't support the Network Information api
+    pub connection_type: Option<String>,
+    /// `ResizeObserver` is supported
+    pub resize_observer: bool,
+    /// `IntersectionObserver` is supported
+    pub intersection_observer: bool,
+}
+
+impl Capabilities {
+    /// probe the window for the capabilities of the browser
+    pub fn detect() -> Self {
+        let window = crate::window();
+        let navigator = window.navigator();
+        let touch = has_property(&window, "ontouchstart")
+            || navigator.max_touch_points() > 0;
+        let prefers_reduced_motion = window
+            .match_media("(prefers-reduced-motion: reduce)")
+            .ok()
+            .flatten()
+            .map(|media_query| media_query.matches())
+            .unwrap_or(false);
+        let connection_type = get_property(&navigator, "connection")
+            .and_then(|connection| get_property(&connection, "effectiveType"))
+            .and_then(|effective_type| effective_type.as_string());
+        Capabilities {
+            touch,
+            prefers_reduced_motion,
+            connection_type,
+            resize_observer: has_property(&window, "ResizeObserver"),
+            intersection_observer: has_property(
+                &window,
+                "IntersectionObserver",
+            ),
+        }
+    }
+
+    /// returns true if the connection is known to be slow
+    pub fn is_slow_connection(&self) -> bool {
+        matches!(
+            self.connection_type.as_deref(),
+            Some("slow-2g") | Some("2g")
+        )
+    }
+}
+
+/// detect the capabilities again and store it, the capabilities are otherwise
+/// detected only once on first use
+pub fn init() {
+    let detected = Capabilities::detect();
+    CAPABILITIES
+        .with(|capabilities| *capabilities.borrow_mut() = Some(detected));
+}
+
+/// returns the capabilities of the browser,
+/// this is detected on first use.
+pub fn capabilities() -> Capabilities {
+    CAPABILITIES.with(|capabilities| {
+        capabilities
+            .borrow_mut()
+            .get_or_insert_with(Capabilities::detect)
+            .clone()
+    })
+}
+
+/// update the stored capabilities, ie: when the user changes the reduced motion preference
+pub(crate) fn update<F>(f: F)
+where
+    F: FnOnce(&mut Capabilities),
+{
+    CAPABILITIES.with(|capabilities| {
+        f(capabilities
+            .borrow_mut()
+            .get_or_insert_with(Capabilities::detect))
+    })
+}
+
+/// Determines whether an animation is run in full or completed instantly.
+///
+/// The animations consult this so they respect the `prefers-reduced-motion` preference of the user,
+/// while still allowing an override for each animation,
+/// ie: a progress indicator which conveys information should still be animated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Motion {
+    /// follow the `prefers-reduced-motion` preference of the user
+    Auto,
+    /// always run the animation in full
+    Full,
+    /// always complete the animation instantly
+    Reduced,
+}
+
+impl Default for Motion {
+    fn default() -> Self {
+        Motion::Auto
+    }
+}
+
+impl Motion {
+    /// returns true if the animation should be completed instantly
+    pub fn is_reduced(&self) -> bool {
+        match self {
+            Motion::Auto => capabilities().prefers_reduced_motion,
+            Motion::Full => false,
+            Motion::Reduced => true,
+        }
+    }
+}
+
+fn has_property(target: &JsValue, name: &str) -> bool {
+    js_sys::Reflect::has(target, &JsValue::from_str(name)).unwrap_or(false)
+}
+
+fn get_property(target: &JsValue, name: &str) -> Option<JsValue> {
+    js_sys::Reflect::get(target, &JsValue::from_str(name))
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
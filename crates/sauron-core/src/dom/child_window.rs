@@ -0,0 +1,162 @@
+//! provides functionalities for opening a child window in which a sauron app can be mounted.
+//!
+use crate::{Application, Dispatch, Program};
+use std::fmt;
+use std::rc::Rc;
+
+/// The position and size of the window opened with [`Cmd::open_window`](crate::dom::cmd::Cmd::open_window)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WindowOptions {
+    /// the width of the window in pixels
+    pub width: Option<i32>,
+    /// the height of the window in pixels
+    pub height: Option<i32>,
+    /// the distance of the window from the left of the screen
+    pub left: Option<i32>,
+    /// the distance of the window from the top of the screen
+    pub top: Option<i32>,
+    /// open as a minimal popup window instead of a new tab
+    pub popup: bool,
+}
+
+impl WindowOptions {
+    /// the window features used in `window.open`
+    pub fn to_features(&self) -> String {
+        let mut features = vec![];
+        if self.popup {
+            features.push("popup".to_string());
+        }
+        let dimensions = [
+            ("width", self.width),
+            ("height", self.height),
+            ("left", self.left),
+            ("top", self.top),
+        ];
+        for (name, value) in dimensions.iter() {
+            if let Some(value) = value {
+                features.push(format!("{}={}", name, value));
+            }
+        }
+        features.join(",")
+    }
+}
+
+/// Sends the MSG of the child window app back to the program of the opener window
+pub struct Opener<MSG> {
+    dispatcher: Rc<dyn Fn(MSG)>,
+}
+
+impl<MSG> Clone for Opener<MSG> {
+    fn clone(&self) -> Self {
+        Opener {
+            dispatcher: Rc::clone(&self.dispatcher),
+        }
+    }
+}
+
+impl<MSG> fmt::Debug for Opener<MSG> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Opener")
+    }
+}
+
+impl<MSG> Opener<MSG>
+where
+    MSG: 'static,
+{
+    pub(crate) fn new<DSP>(program: DSP) -> Self
+    where
+        DSP: Dispatch<MSG> + 'static,
+    {
+        Opener {
+            dispatcher: Rc::new(move |msg| program.dispatch(msg)),
+        }
+    }
+
+    /// dispatch the msg into the program of the opener window
+    pub fn dispatch(&self, msg: MSG) {
+        (self.dispatcher)(msg)
+    }
+}
+
+/// A handle to the child window opened with [`Cmd::open_window`](crate::dom::cmd::Cmd::open_window)
+#[derive(Debug)]
+pub struct ChildWindow<MSG> {
+    window: web_sys::Window,
+    opener: Opener<MSG>,
+}
+
+impl<MSG> Clone for ChildWindow<MSG> {
+    fn clone(&self) -> Self {
+        ChildWindow {
+            window: self.window.clone(),
+            opener: self.opener.clone(),
+        }
+    }
+}
+
+impl<MSG> ChildWindow<MSG>
+where
+    MSG: 'static,
+{
+    pub(crate) fn new(window: web_sys::Window, opener: Opener<MSG>) -> Self {
+        ChildWindow { window, opener }
+    }
+
+    /// the browser window of this child window
+    pub fn window(&self) -> &web_sys::Window {
+        &self.window
+    }
+
+    /// the handle to dispatch MSG back into the opener program
+    pub fn opener(&self) -> Opener<MSG> {
+        self.opener.clone()
+    }
+
+    /// Mount the app created from `f` into the body of the child window.
+    /// The `Opener` can be stored in the app so it can send MSG back into the opener program.
+    ///
+    /// The child window must be of the same origin as the opener, ie: opened with `about:blank`.
+    /// The styles of the opener document are also copied into the child window.
+    pub fn mount<APP, CMSG, F>(&self, f: F) -> Program<APP, CMSG>
+    where
+        F: FnOnce(Opener<MSG>) -> APP,
+        APP: Application<CMSG> + 'static,
+        CMSG: 'static,
+    {
+        let document = self
+            .window
+            .document()
+            .expect("child window must be of the same origin");
+        let body = document.body().expect("child window must have a body");
+        let program = Program::append_to_mount(f(self.opener()), &body);
+        self.copy_styles(&document);
+        program
+    }
+
+    /// copy the styles injected in the opener document into the child document
+    fn copy_styles(&self, document: &web_sys::Document) {
+        let head = document.head().expect("child window must have a head");
+        let styles = crate::document()
+            .query_selector_all("head > style, head > link[rel=stylesheet]")
+            .expect("must query the styles");
+        for i in 0..styles.length() {
+            if let Some(style) = styles.get(i) {
+                let style = style
+                    .clone_node_with_deep(true)
+                    .expect("must clone the style");
+                head.append_child(&style).expect("must append the style");
+            }
+        }
+    }
+
+    /// close the child window
+    pub fn close(&self) {
+        self.window.close().expect("must close the window");
+    }
+
+    /// returns true if the child window has been closed
+    pub fn is_closed(&self) -> bool {
+        self.window.closed().unwrap_or(true)
+    }
+}
@@ -0,0 +1,80 @@
+//! The elements with the [`on_click_outside`] listener are registered to one click listener
+//! on the document, which dispatches the `clickoutside` event to the registered elements
+//! which don't contain the target of the click.
+//!
+//! The click is checked in the capture phase, before the listeners of the target are called,
+//! so a target which is removed by its own click listener is still in the document.
+//! The element is unregistered when its listener is removed, which is also when
+//! the element is removed by a patch.
+//!
+//! [`on_click_outside`]: crate::dom::events::on_click_outside
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Element, MouseEvent};
+
+/// the event dispatched when the click lands outside of the element
+pub(crate) const CLICK_OUTSIDE: &str = "clickoutside";
+
+thread_local!(static IS_INSTALLED: Cell<bool> = Cell::new(false));
+thread_local!(static REGISTERED: RefCell<HashMap<usize, Element>> = RefCell::new(HashMap::new()));
+
+/// register the element, installing the click listener of the document on the first element
+pub(crate) fn register(vdom_id: usize, element: &Element) {
+    install();
+    REGISTERED.with(|registered| {
+        registered.borrow_mut().insert(vdom_id, element.clone())
+    });
+}
+
+/// unregister the element with the `vdom_id`, if it is registered
+pub(crate) fn unregister(vdom_id: usize) {
+    REGISTERED.with(|registered| registered.borrow_mut().remove(&vdom_id));
+}
+
+/// install the click listener of the document, this is done only once
+fn install() {
+    if IS_INSTALLED.with(|is_installed| is_installed.replace(true)) {
+        return;
+    }
+    let listener: Closure<dyn FnMut(MouseEvent)> =
+        Closure::wrap(Box::new(on_click));
+    crate::document()
+        .add_event_listener_with_callback_and_bool(
+            "click",
+            listener.as_ref().unchecked_ref(),
+            true,
+        )
+        .expect("must add the click outside listener");
+    // the listener lives for the rest of the page lifetime
+    listener.forget();
+}
+
+fn on_click(event: MouseEvent) {
+    let target = event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+    // collected first, since the listeners could add or remove the registered elements
+    let outside: Vec<Element> = REGISTERED.with(|registered| {
+        registered
+            .borrow()
+            .values()
+            .filter(|element| !element.contains(target.as_ref()))
+            .cloned()
+            .collect()
+    });
+    for element in outside {
+        let mut event_init = web_sys::CustomEventInit::new();
+        event_init.detail(&event);
+        let click_outside = web_sys::CustomEvent::new_with_event_init_dict(
+            CLICK_OUTSIDE,
+            &event_init,
+        )
+        .expect("must create the click outside event");
+        if let Err(e) = element.dispatch_event(&click_outside) {
+            log::error!("unable to dispatch {}: {:?}", CLICK_OUTSIDE, e);
+        }
+    }
+}
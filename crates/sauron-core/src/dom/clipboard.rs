@@ -0,0 +1,80 @@
+//! provides the Cmds which write and read the text of the clipboard of the system.
+//!
+//! The async clipboard api is called dynamically, so this doesn't need the
+//! `web_sys_unstable_apis` cfg flag.
+use crate::dom::{cmd::Cmd, Dispatch};
+use js_sys::{Array, Function, Promise, Reflect};
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// Errors when accessing the clipboard
+#[derive(Debug, Error)]
+pub enum ClipboardError {
+    /// the clipboard api is not available, ie: the page is not served over https
+    #[error("clipboard is not available")]
+    Unavailable,
+    /// the browser rejected the access, ie: the user denied the permission
+    /// or the document is not focused
+    #[error("clipboard access is rejected: {0}")]
+    Rejected(String),
+}
+
+impl From<JsValue> for ClipboardError {
+    fn from(js_value: JsValue) -> Self {
+        ClipboardError::Rejected(format!("{:?}", js_value))
+    }
+}
+
+/// call the `method` of the clipboard of the navigator and wait for its promise
+async fn call(method: &str, args: &Array) -> Result<JsValue, ClipboardError> {
+    let navigator = crate::window().navigator();
+    let clipboard = Reflect::get(&navigator, &JsValue::from_str("clipboard"))
+        .ok()
+        .filter(|clipboard| !clipboard.is_undefined())
+        .ok_or(ClipboardError::Unavailable)?;
+    let function: Function =
+        Reflect::get(&clipboard, &JsValue::from_str(method))?
+            .dyn_into()
+            .map_err(|_| ClipboardError::Unavailable)?;
+    let promise: Promise = function.apply(&clipboard, args)?.unchecked_into();
+    Ok(JsFuture::from(promise).await?)
+}
+
+impl<DSP> Cmd<DSP>
+where
+    DSP: 'static,
+{
+    /// Creates a Cmd which writes the `text` into the clipboard.
+    ///
+    /// The browser allows this only in response to a user interaction, ie: a click.
+    pub fn copy_to_clipboard(text: &str) -> Self {
+        let args = Array::of1(&JsValue::from_str(text));
+        Cmd::new_async(move |_program| async move {
+            if let Err(e) = call("writeText", &args).await {
+                log::warn!("unable to copy to the clipboard: {}", e);
+            }
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which reads the text in the clipboard.
+    ///
+    /// The browser may ask the user for the permission to read the clipboard,
+    /// the result is a [`ClipboardError::Rejected`] when it is denied.
+    pub fn read_clipboard<F, MSG>(f: F) -> Self
+    where
+        F: FnOnce(Result<String, ClipboardError>) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        Cmd::try_from_async(
+            async move {
+                call("readText", &Array::new())
+                    .await
+                    .map(|text| text.as_string().unwrap_or_default())
+            },
+            f,
+        )
+    }
+}
@@ -1,8 +1,17 @@
 //! provides functionalities for commands to be executed by the system, such as
 //! when the application starts or after the application updates.
 //!
+#[cfg(feature = "with-persist")]
+use crate::dom::broadcast;
+use crate::dom::{
+    audio::{self, Sound},
+    timer, ChildWindow, ElementTarget, Opener, Rect, TimerHandle,
+    WindowOptions,
+};
 use crate::Dispatch;
 use crate::Effects;
+use std::{cell::RefCell, collections::HashMap, future::Future, pin::Pin};
+use wasm_bindgen::{closure::Closure, JsCast};
 
 /// Cmd is a command to be executed by the system.
 /// This is returned at the init function of a component and is executed right
@@ -13,9 +22,18 @@ use crate::Effects;
 pub struct Cmd<DSP> {
     /// the functions that would be executed when this Cmd is emited
     pub commands: Vec<Box<dyn FnOnce(DSP)>>,
+    /// the functions that would be executed in the read phase, before the patches are applied
+    pub(crate) read_commands: Vec<Box<dyn FnOnce(DSP)>>,
+    /// the functions which return the futures that are spawned when this Cmd is emitted,
+    /// these are awaited in order when this Cmd is a step of [`Cmd::seq`]
+    pub(crate) async_commands: Vec<AsyncCommand<DSP>>,
     pub(crate) modifier: Modifier,
 }
 
+/// a function which returns the future to be spawned
+pub(crate) type AsyncCommand<DSP> =
+    Box<dyn FnOnce(DSP) -> Pin<Box<dyn Future<Output = ()>>>>;
+
 /// These are collections of fields where we can modify the Cmd
 /// such as logging measurement or should update the view
 #[derive(Clone)]
@@ -35,6 +53,10 @@ pub struct Modifier {
     /// into the [`Measurement`](crate::Measurement) passed in
     /// [`Application::measurement`](crate::Application::measurement)
     pub measurement_name: String,
+    /// update the view in a view transition, in the browsers which support it
+    pub with_view_transition: bool,
+    /// apply the [`Application::head`](crate::Application::head) into the document
+    pub should_update_head: bool,
 }
 
 impl Default for Modifier {
@@ -46,10 +68,21 @@ impl Default for Modifier {
             log_measurements: false,
             // empty string by default
             measurement_name: String::new(),
+            // the view is updated without a view transition by default
+            with_view_transition: false,
+            // the head is only applied when the program is mounted by default
+            should_update_head: false,
         }
     }
 }
 
+thread_local! {
+    /// the number of the emitted [`Cmd::latest_only`] for each of the tags
+    static LATEST_GENERATIONS: RefCell<HashMap<String, u64>> = RefCell::new(HashMap::new());
+    /// the number of the emitted [`Cmd::debounced`] for each of the keys
+    static DEBOUNCE_GENERATIONS: RefCell<HashMap<String, u64>> = RefCell::new(HashMap::new());
+}
+
 impl<DSP> Cmd<DSP>
 where
     DSP: 'static,
@@ -61,15 +94,56 @@ where
     {
         Self {
             commands: vec![Box::new(f)],
+            read_commands: vec![],
+            async_commands: vec![],
+            modifier: Default::default(),
+        }
+    }
+
+    /// Creates a Cmd from a function which returns a future, the future is spawned
+    /// when the Cmd is emitted.
+    ///
+    /// Unlike spawning the future inside of [`Cmd::new`], the future is awaited
+    /// before the next step when this Cmd is a step of [`Cmd::seq`].
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// Cmd::new_async(move |program| async move {
+    ///     let orders = fetch_orders(&url).await;
+    ///     program.dispatch(Msg::OrdersReceived(orders));
+    /// })
+    /// ```
+    pub fn new_async<F, FUT>(f: F) -> Self
+    where
+        F: FnOnce(DSP) -> FUT + 'static,
+        FUT: Future<Output = ()> + 'static,
+    {
+        Self {
+            commands: vec![],
+            read_commands: vec![],
+            async_commands: vec![Box::new(
+                move |program| -> Pin<Box<dyn Future<Output = ()>>> {
+                    Box::pin(f(program))
+                },
+            )],
             modifier: Default::default(),
         }
     }
 
     /// creates a unified Cmd which batches all the other Cmds in one.
+    ///
+    /// The commands are started in the order they are batched: the commands are executed
+    /// in order, then the futures of the async commands are spawned in order.
+    /// The futures run concurrently, so they can complete in any order,
+    /// use [`Cmd::seq`] when a command needs the result of the previous one.
     pub fn batch(cmds: impl IntoIterator<Item = Self>) -> Self {
         let mut commands = vec![];
+        let mut read_commands = vec![];
+        let mut async_commands = vec![];
         let mut should_update_view = false;
         let mut log_measurements = false;
+        let mut with_view_transition = false;
+        let mut should_update_head = false;
         for cmd in cmds {
             if cmd.modifier.should_update_view {
                 should_update_view = true;
@@ -77,13 +151,25 @@ where
             if cmd.modifier.log_measurements {
                 log_measurements = true;
             }
+            if cmd.modifier.with_view_transition {
+                with_view_transition = true;
+            }
+            if cmd.modifier.should_update_head {
+                should_update_head = true;
+            }
             commands.extend(cmd.commands);
+            read_commands.extend(cmd.read_commands);
+            async_commands.extend(cmd.async_commands);
         }
         Self {
             commands,
+            read_commands,
+            async_commands,
             modifier: Modifier {
                 should_update_view,
                 log_measurements,
+                with_view_transition,
+                should_update_head,
                 ..Default::default()
             },
         }
@@ -98,7 +184,15 @@ where
             if cmd.modifier.log_measurements {
                 self.modifier.log_measurements = true;
             }
+            if cmd.modifier.with_view_transition {
+                self.modifier.with_view_transition = true;
+            }
+            if cmd.modifier.should_update_head {
+                self.modifier.should_update_head = true;
+            }
             self.commands.extend(cmd.commands);
+            self.read_commands.extend(cmd.read_commands);
+            self.async_commands.extend(cmd.async_commands);
         }
         self
     }
@@ -107,10 +201,157 @@ where
     pub fn none() -> Self {
         Cmd {
             commands: vec![],
+            read_commands: vec![],
+            async_commands: vec![],
             modifier: Default::default(),
         }
     }
 
+    /// open the print dialog of the browser
+    pub fn print() -> Self {
+        Cmd::new(|_program| {
+            crate::window().print().expect("must open the print dialog");
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which reads from the DOM, ie: the size or the scroll position of an element.
+    ///
+    /// The reads are batched and executed before the writes queued with [`Cmd::dom_write`],
+    /// so reading the layout doesn't interleave with the writes and cause layout thrashing.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let height = Rc::new(Cell::new(0));
+    /// let height_clone = Rc::clone(&height);
+    /// // the read is executed first, even if it is batched after the write
+    /// Cmd::batch([
+    ///     Cmd::dom_write(move || {
+    ///         sidebar.set_attribute("style", &format!("height: {}px", height_clone.get())).unwrap();
+    ///     }),
+    ///     Cmd::dom_read(move || height.set(panel.client_height())),
+    /// ])
+    /// ```
+    pub fn dom_read<F>(f: F) -> Self
+    where
+        F: FnOnce() + 'static,
+    {
+        Cmd::read_phase(move |_program| f())
+    }
+
+    /// Creates a Cmd which is executed in the read phase of the frame.
+    ///
+    /// When it is returned from the `update`, it is executed before the patches of the view
+    /// are applied, so the DOM is read before it is written in the same frame.
+    /// Otherwise it is executed along with the other reads on the next animation frame.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// Cmd::read_phase(|program| {
+    ///     let is_focused = document().active_element() == panel.get();
+    ///     program.dispatch(Msg::PanelFocused(is_focused));
+    /// })
+    /// ```
+    pub fn read_phase<F>(f: F) -> Self
+    where
+        F: FnOnce(DSP) + 'static,
+    {
+        Self {
+            commands: vec![],
+            read_commands: vec![Box::new(f)],
+            async_commands: vec![],
+            modifier: Default::default(),
+        }
+        .no_render()
+    }
+
+    /// Creates a Cmd which is executed in the write phase of the frame,
+    /// after the reads and the patches of the view.
+    pub fn write_phase<F>(f: F) -> Self
+    where
+        F: FnOnce(DSP) + 'static,
+    {
+        Cmd::new(move |program| {
+            crate::dom::scheduler::write(move || f(program))
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which measures the size and position of the element targeted by
+    /// a [`NodeRef`](crate::dom::NodeRef) or a css selector, and dispatch the MSG created from it.
+    ///
+    /// The element is measured in the same read phase as [`Cmd::dom_read`], so it doesn't
+    /// interleave with the writes, and the MSG is dispatched after the patches are applied.
+    /// No MSG is dispatched if the element is not in the document.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// Cmd::measure_element(&self.panel, Msg::PanelMeasured)
+    /// Cmd::measure_element("#sidebar", |rect| Msg::SidebarWidth(rect.width))
+    /// ```
+    pub fn measure_element<F, MSG>(
+        target: impl Into<ElementTarget>,
+        f: F,
+    ) -> Self
+    where
+        F: FnOnce(Rect) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let target = target.into();
+        Cmd::read_phase(move |program: DSP| match target.element() {
+            Some(element) => {
+                let rect = Rect::from(element.get_bounding_client_rect());
+                // dispatching in the write phase, so the update doesn't run in between
+                // the reads and the patches of the current dispatch
+                crate::dom::scheduler::write(move || program.dispatch(f(rect)));
+            }
+            None => {
+                log::warn!(
+                    "unable to find the element to measure: {:?}",
+                    target
+                );
+            }
+        })
+    }
+
+    /// Creates a Cmd which focuses the element targeted by a [`NodeRef`](crate::dom::NodeRef)
+    /// or a css selector, after the patches of the view are applied,
+    /// so the element can be one which is created in the same update.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// Msg::OpenSearch => {
+    ///     self.is_search_open = true;
+    ///     Cmd::focus("#search-input")
+    /// }
+    /// ```
+    pub fn focus(target: impl Into<ElementTarget>) -> Self {
+        let target = target.into();
+        Cmd::write_phase(move |_program| match target.element() {
+            Some(element) => {
+                let element: &web_sys::HtmlElement = element.unchecked_ref();
+                if let Err(e) = element.focus() {
+                    log::warn!("unable to focus {:?}: {:?}", target, e);
+                }
+            }
+            None => {
+                log::warn!("unable to find the element to focus: {:?}", target)
+            }
+        })
+    }
+
+    /// Creates a Cmd which writes into the DOM, ie: the style of an element.
+    ///
+    /// The writes are batched and executed after the reads queued with [`Cmd::dom_read`]
+    /// and after the patches of the view are applied.
+    pub fn dom_write<F>(f: F) -> Self
+    where
+        F: FnOnce() + 'static,
+    {
+        Cmd::write_phase(move |_program| f())
+    }
+
     /// Modify the Cmd such that whether or not it will update the view set by `should_update_view`
     /// when the cmd is executed in the program
     pub fn should_update_view(mut self, should_update_view: bool) -> Self {
@@ -137,6 +378,26 @@ where
         self.modifier.measurement_name = name.to_string();
         self
     }
+
+    /// Modify the Cmd such that the view is updated in a
+    /// [view transition](https://developer.mozilla.org/en-US/docs/Web/API/View_Transitions_API),
+    /// which cross-fades the old and the new view, ie: on route changes.
+    ///
+    /// The elements with the same `view-transition-name` style in the old and the new view
+    /// are morphed into each other. The view is updated as usual in the browsers which don't
+    /// support view transitions.
+    pub fn with_view_transition(mut self) -> Self {
+        self.modifier.with_view_transition = true;
+        self
+    }
+
+    /// Modify the Cmd such that the [`Application::head`](crate::Application::head) is applied
+    /// into the document after the update, this is used when the update changed the title or
+    /// the tags of the head, ie: navigating to another page.
+    pub fn update_head(mut self) -> Self {
+        self.modifier.should_update_head = true;
+        self
+    }
 }
 
 impl<DSP> Cmd<DSP>
@@ -144,11 +405,63 @@ where
     DSP: Clone + 'static,
 {
     /// Executes the Cmd
-    pub fn emit(self, program: &DSP) {
+    pub fn emit(mut self, program: &DSP) {
+        self.emit_reads(program);
         for cb in self.commands {
             let program_clone = program.clone();
             cb(program_clone);
         }
+        for cb in self.async_commands {
+            wasm_bindgen_futures::spawn_local(cb(program.clone()));
+        }
+    }
+
+    /// Creates a Cmd which executes the `cmds` one after another,
+    /// each Cmd is started only after the futures of the previous Cmd have completed.
+    /// The futures of a batched Cmd in the `cmds` are also awaited one after another.
+    ///
+    /// Only the futures of the async commands, such as [`Cmd::from_async`], [`Cmd::new_async`]
+    /// and [`Http::fetch_remote_data`](crate::dom::Http::fetch_remote_data) are awaited,
+    /// the callbacks which are registered by the other commands are not.
+    /// The view is updated if any of the `cmds` updates the view.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// // the token is stored in the session before the orders are fetched with it
+    /// Cmd::seq([
+    ///     Cmd::from_async(async move { Msg::LoggedIn(login(&credentials).await) }),
+    ///     session.fetch_remote_data("/api/orders", Msg::OrdersReceived),
+    /// ])
+    /// ```
+    pub fn seq(cmds: impl IntoIterator<Item = Self>) -> Self {
+        let cmds: Vec<Self> = cmds.into_iter().collect();
+        let should_update_view =
+            cmds.iter().any(|cmd| cmd.modifier.should_update_view);
+        Cmd::new_async(move |program: DSP| async move {
+            for cmd in cmds {
+                cmd.run(&program).await;
+            }
+        })
+        .should_update_view(should_update_view)
+    }
+
+    /// execute the commands, then await the futures of the async commands in order
+    async fn run(mut self, program: &DSP) {
+        self.emit_reads(program);
+        for cb in self.commands {
+            cb(program.clone());
+        }
+        for cb in self.async_commands {
+            cb(program.clone()).await;
+        }
+    }
+
+    /// queue the commands of the read phase, leaving the rest of the commands in this Cmd
+    pub(crate) fn emit_reads(&mut self, program: &DSP) {
+        for cb in self.read_commands.drain(..) {
+            let program_clone = program.clone();
+            crate::dom::scheduler::read(move || cb(program_clone));
+        }
     }
 }
 
@@ -165,6 +478,307 @@ impl<DSP> Cmd<DSP> {
             program.dispatch_multiple(msg_list);
         })
     }
+
+    /// Creates a Cmd which dispatch the MSG created from the timestamp of the next animation frame.
+    ///
+    /// The MSG is processed in the same frame, so the view is updated along with it.
+    pub fn animation_frame<F, MSG>(f: F) -> Self
+    where
+        F: FnOnce(f64) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        Cmd::new(move |program: DSP| {
+            let closure: Closure<dyn FnMut(f64)> =
+                Closure::once(move |timestamp: f64| {
+                    crate::dom::util::with_animation_frame(|| {
+                        program.dispatch(f(timestamp))
+                    })
+                });
+            crate::window()
+                .request_animation_frame(closure.as_ref().unchecked_ref())
+                .expect("should register `requestAnimationFrame` OK");
+            closure.forget();
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which dispatch the `msg` after `ms` milliseconds
+    pub fn delay<MSG>(ms: i32, msg: MSG) -> Self
+    where
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        Cmd::new(move |program: DSP| {
+            let closure: Closure<dyn FnMut()> =
+                Closure::once(move || program.dispatch(msg));
+            timer::set_timeout(closure.as_ref().unchecked_ref(), ms);
+            closure.forget();
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which dispatch the `msg` after `ms` milliseconds, only if no other Cmd
+    /// with the same `key` is emitted in the meantime, otherwise the `msg` is dropped
+    /// and the later Cmd waits for its own `ms` milliseconds.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// Msg::QueryChanged(query) => {
+    ///     self.query = query;
+    ///     // search only once the user has stopped typing for 300ms
+    ///     Cmd::debounced("search", 300, Msg::Search)
+    /// }
+    /// ```
+    pub fn debounced<MSG>(key: &str, ms: i32, msg: MSG) -> Self
+    where
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let key = key.to_string();
+        Cmd::new(move |program: DSP| {
+            let generation = DEBOUNCE_GENERATIONS.with(|generations| {
+                let mut generations = generations.borrow_mut();
+                let generation = generations.entry(key.clone()).or_insert(0);
+                *generation += 1;
+                *generation
+            });
+            // the timeout of the earlier Cmd is left to fire, so its closure is freed
+            let closure: Closure<dyn FnMut()> = Closure::once(move || {
+                let is_latest = DEBOUNCE_GENERATIONS.with(|generations| {
+                    generations.borrow().get(&key) == Some(&generation)
+                });
+                if is_latest {
+                    program.dispatch(msg);
+                }
+            });
+            timer::set_timeout(closure.as_ref().unchecked_ref(), ms);
+            closure.forget();
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which dispatch the MSG created from the tick count every `ms` milliseconds,
+    /// the tick count starts at 1.
+    ///
+    /// The returned [`TimerHandle`] can be stored in the app to stop the interval.
+    /// # Example
+    /// ```rust,ignore
+    /// fn init(&mut self) -> Cmd<Self, Msg> {
+    ///     let (cmd, timer) = Cmd::every(1000, Msg::Tick);
+    ///     self.timer = Some(timer);
+    ///     cmd
+    /// }
+    ///
+    /// fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+    ///     match msg {
+    ///         Msg::Stop => {
+    ///             if let Some(timer) = self.timer.take() {
+    ///                 timer.cancel();
+    ///             }
+    ///         }
+    ///         ...
+    ///     }
+    /// }
+    /// ```
+    pub fn every<F, MSG>(ms: i32, f: F) -> (Self, TimerHandle)
+    where
+        F: Fn(u32) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let timer = TimerHandle::default();
+        let timer_clone = timer.clone();
+        let cmd = Cmd::new(move |program: DSP| {
+            let mut tick = 0;
+            let closure: Closure<dyn FnMut()> =
+                Closure::wrap(Box::new(move || {
+                    tick += 1;
+                    program.dispatch(f(tick));
+                }));
+            let interval_id =
+                timer::set_interval(closure.as_ref().unchecked_ref(), ms);
+            timer_clone.started(interval_id, closure);
+        })
+        .no_render();
+        (cmd, timer)
+    }
+
+    /// Creates a Cmd which plays the sound from a url or the content of a sound file.
+    ///
+    /// The sound is played through a shared audio context which is unlocked on the first
+    /// user gesture, use [`audio::set_volume`] and [`audio::set_muted`] to control the volume.
+    pub fn play_sound(sound: impl Into<Sound>) -> Self {
+        let sound = sound.into();
+        Cmd::new_async(move |_program| async move {
+            let played = match audio::load(&sound).await {
+                Ok(buffer) => audio::play(&buffer),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = played {
+                log::warn!("unable to play the sound: {:?}", e);
+            }
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which fetch and decode the sound from the url ahead of time,
+    /// so it plays without delay when used in [`Cmd::play_sound`].
+    pub fn preload_sound(url: &str) -> Self {
+        let sound = Sound::from(url);
+        Cmd::new_async(move |_program| async move {
+            if let Err(e) = audio::load(&sound).await {
+                log::warn!("unable to preload the sound: {:?}", e);
+            }
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which sends the `msg` to the other tabs and windows of the same origin,
+    /// where it is dispatched by their [`Sub::broadcast_channel`](crate::dom::sub::Sub::broadcast_channel)
+    /// of the same `channel_name`, ie: so a logout in one tab also logs out the other tabs.
+    ///
+    /// The msg is serialized right away, it is not dispatched back into this program.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// Msg::Logout => {
+    ///     self.session = None;
+    ///     Cmd::broadcast("session", &Msg::LoggedOut)
+    /// }
+    /// ```
+    #[cfg(feature = "with-persist")]
+    pub fn broadcast<MSG>(channel_name: &str, msg: &MSG) -> Self
+    where
+        MSG: serde::Serialize,
+    {
+        let channel_name = channel_name.to_string();
+        let json = broadcast::serialize(msg);
+        Cmd::new(move |_program| match json {
+            Ok(json) => {
+                if let Err(e) = broadcast::post(&channel_name, &json) {
+                    log::warn!("unable to broadcast the msg: {:?}", e);
+                }
+            }
+            Err(e) => {
+                log::warn!("unable to serialize the broadcast msg: {}", e)
+            }
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which opens a child window of `url` and dispatch the MSG created
+    /// from the [`ChildWindow`] handle, which an app can then be mounted into.
+    ///
+    /// Use `about:blank` as the `url` to mount an app into the child window,
+    /// since it needs to be of the same origin as the opener.
+    /// The MSG of the app in the child window can be sent back into this program
+    /// using the [`Opener`] of the `ChildWindow`.
+    pub fn open_window<F, MSG>(url: &str, options: WindowOptions, f: F) -> Self
+    where
+        F: FnOnce(ChildWindow<MSG>) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let url = url.to_string();
+        Cmd::new(move |program: DSP| {
+            let window = crate::window()
+                .open_with_url_and_target_and_features(
+                    &url,
+                    "_blank",
+                    &options.to_features(),
+                )
+                .ok()
+                .flatten();
+            match window {
+                Some(window) => {
+                    let opener = Opener::new(program.clone());
+                    program.dispatch(f(ChildWindow::new(window, opener)));
+                }
+                None => log::warn!("the window was blocked from opening"),
+            }
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which spawns the future and dispatch the resulting MSG
+    /// into the program once the future completes.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// Cmd::from_async(async move {
+    ///     let content = fetch_content(&url).await;
+    ///     Msg::ContentReceived(content)
+    /// })
+    /// ```
+    pub fn from_async<F, MSG>(future: F) -> Self
+    where
+        F: Future<Output = MSG> + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        Cmd::new_async(move |program: DSP| async move {
+            let msg = future.await;
+            program.dispatch(msg);
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which spawns the future and dispatch the resulting MSG, only if no other
+    /// Cmd with the same `tag` is emitted while the future is pending.
+    ///
+    /// This keeps the latest of the overlapping requests, ie: the search results as the user
+    /// types, where the responses of the older queries could arrive after the newer ones.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// Msg::QueryChanged(query) => {
+    ///     Cmd::latest_only("search", async move { Msg::Results(search(&query).await) })
+    /// }
+    /// ```
+    pub fn latest_only<F, MSG>(tag: &str, future: F) -> Self
+    where
+        F: Future<Output = MSG> + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let tag = tag.to_string();
+        Cmd::new_async(move |program: DSP| {
+            let generation = LATEST_GENERATIONS.with(|generations| {
+                let mut generations = generations.borrow_mut();
+                let generation = generations.entry(tag.clone()).or_insert(0);
+                *generation += 1;
+                *generation
+            });
+            async move {
+                let msg = future.await;
+                let is_latest = LATEST_GENERATIONS.with(|generations| {
+                    generations.borrow().get(&tag) == Some(&generation)
+                });
+                if is_latest {
+                    program.dispatch(msg);
+                }
+            }
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which spawns the fallible future and dispatch
+    /// the MSG created from the `Result` of the future, once the future completes.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// Cmd::try_from_async(fetch_content(url), Msg::ContentReceived)
+    /// ```
+    pub fn try_from_async<F, T, E, MSG, CB>(future: F, cb: CB) -> Self
+    where
+        F: Future<Output = Result<T, E>> + 'static,
+        CB: FnOnce(Result<T, E>) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        Cmd::from_async(async move { cb(future.await) })
+    }
 }
 
 impl<DSP, MSG> From<Effects<MSG, ()>> for Cmd<DSP>
@@ -1,13 +1,24 @@
+use crate::dom::animate;
+use crate::dom::canvas_draw;
+use crate::dom::click_outside;
+use crate::dom::event_delegation::{self, DelegatedHandler};
+use crate::dom::event_timing;
+use crate::dom::focus_trap;
+use crate::dom::resize;
+use crate::dom::template::{self, Skeletons};
+use crate::dom::tooltip;
+use crate::dom::viewport;
 use crate::events::MountEvent;
 use crate::Listener;
 use crate::{
-    dom::Dispatch,
+    dom::{Dispatch, NodeRef},
     html,
     html::attributes::{AttributeValue, SegregatedAttributes, Special},
     Attribute, Event,
 };
 use std::cell::Cell;
 use std::collections::HashMap;
+use std::rc::Rc;
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 use web_sys::{
     self, Element, EventTarget, HtmlDetailsElement, HtmlElement,
@@ -66,11 +77,32 @@ impl CreatedNode {
 
     /// Create and return a `CreatedNode` instance (containing a DOM `Node`
     /// together with potentially related closures) for this virtual node.
+    ///
+    /// The subtrees which have the same skeleton as the subtrees created before
+    /// are imported from a `<template>` instead of created node by node.
     pub fn create_dom_node<DSP, MSG>(
         program: &DSP,
         vnode: &crate::Node<MSG>,
         focused_node: &mut Option<Node>,
-    ) -> CreatedNode
+    ) -> Result<CreatedNode, JsValue>
+    where
+        MSG: 'static,
+        DSP: Clone + Dispatch<MSG> + 'static,
+    {
+        Self::create_dom_node_with_skeletons(
+            program,
+            vnode,
+            focused_node,
+            &Skeletons::new(),
+        )
+    }
+
+    fn create_dom_node_with_skeletons<DSP, MSG>(
+        program: &DSP,
+        vnode: &crate::Node<MSG>,
+        focused_node: &mut Option<Node>,
+        skeletons: &Skeletons<MSG>,
+    ) -> Result<CreatedNode, JsValue>
     where
         MSG: 'static,
         DSP: Clone + Dispatch<MSG> + 'static,
@@ -78,22 +110,56 @@ impl CreatedNode {
         match vnode {
             crate::Node::Text(txt) => {
                 let text_node = Self::create_text_node(&txt.text);
-                CreatedNode::without_closures(text_node.unchecked_into())
+                Ok(CreatedNode::without_closures(text_node.unchecked_into()))
             }
             crate::Node::Comment(comment) => {
                 let comment_node = crate::document().create_comment(comment);
-                CreatedNode::without_closures(comment_node.unchecked_into())
-            }
-            crate::Node::Element(element_node) => {
-                Self::create_element_node(program, element_node, focused_node)
+                Ok(CreatedNode::without_closures(comment_node.unchecked_into()))
             }
+            crate::Node::Element(element_node) => Self::create_element_node(
+                program,
+                element_node,
+                focused_node,
+                skeletons,
+            ),
+        }
+    }
+
+    /// Create the DOM nodes of the `vnodes` into a `DocumentFragment`, so they are
+    /// added into the document with a single append, instead of causing a reflow for each of the nodes.
+    pub fn create_dom_nodes<'a, DSP, MSG>(
+        program: &DSP,
+        vnodes: impl IntoIterator<Item = &'a crate::Node<MSG>>,
+        focused_node: &mut Option<Node>,
+    ) -> Result<CreatedNode, JsValue>
+    where
+        MSG: 'static,
+        DSP: Clone + Dispatch<MSG> + 'static,
+    {
+        let fragment = crate::document().create_document_fragment();
+        let mut closures = ActiveClosure::new();
+        // one cache of the skeletons for all of the vnodes
+        let skeletons = Skeletons::new();
+        for vnode in vnodes {
+            let created_node = Self::create_dom_node_with_skeletons(
+                program,
+                vnode,
+                focused_node,
+                &skeletons,
+            )?;
+            closures.extend(created_node.closures);
+            fragment.append_child(&created_node.node)?;
         }
+        Ok(CreatedNode {
+            node: fragment.unchecked_into(),
+            closures,
+        })
     }
 
     /// dispatch the mount event,
     /// call the listener since browser don't allow asynchronous execution of
     /// dispatching custom events (non-native browser events)
-    fn dispatch_mount_event<DSP, MSG>(
+    pub(crate) fn dispatch_mount_event<DSP, MSG>(
         program: &DSP,
         velem: &crate::Element<MSG>,
         element: &Element,
@@ -121,60 +187,132 @@ impl CreatedNode {
         program: &DSP,
         velem: &crate::Element<MSG>,
         focused_node: &mut Option<Node>,
-    ) -> CreatedNode
+        skeletons: &Skeletons<MSG>,
+    ) -> Result<CreatedNode, JsValue>
     where
         MSG: 'static,
         DSP: Clone + Dispatch<MSG> + 'static,
     {
-        let document = crate::document();
-
-        let element = if let Some(namespace) = velem.namespace() {
-            document
-                .create_element_ns(Some(namespace), velem.tag())
-                .expect("Unable to create element")
-        } else {
-            document
-                .create_element(velem.tag())
-                .expect("Unable to create element")
-        };
-
-        Self::dispatch_mount_event(program, velem, &element);
+        if let Some(adopted_element) = Self::find_adopted_element(velem) {
+            return Ok(CreatedNode::without_closures(
+                adopted_element.unchecked_into(),
+            ));
+        }
 
-        if velem.is_focused() {
-            *focused_node = Some(element.clone().unchecked_into());
-            log::trace!("element is focused..{:?}", focused_node);
-            Self::set_element_focus(&element);
+        if let Some(created_node) =
+            template::import_skeleton(program, velem, focused_node, skeletons)?
+        {
+            return Ok(created_node);
         }
 
-        let mut closures = ActiveClosure::new();
+        let element = Self::create_element(&crate::document(), velem)?;
 
-        Self::set_element_attributes(
+        let mut closures = ActiveClosure::new();
+        Self::set_up_element(
             program,
             &mut closures,
+            velem,
             &element,
-            &velem.get_attributes().iter().collect::<Vec<_>>(),
-        );
+            focused_node,
+            |_attr| true,
+        )?;
 
         for child in velem.get_children().iter() {
             if child.is_safe_html() {
                 let child_text = child.unwrap_text();
                 // https://developer.mozilla.org/en-US/docs/Web/API/Element/insertAdjacentHTML
-                element
-                    .insert_adjacent_html("beforeend", &child_text.text)
-                    .expect("must not error");
+                element.insert_adjacent_html("beforeend", &child_text.text)?;
             } else {
-                let created_child =
-                    Self::create_dom_node(program, child, focused_node);
+                let created_child = Self::create_dom_node_with_skeletons(
+                    program,
+                    child,
+                    focused_node,
+                    skeletons,
+                )?;
 
                 closures.extend(created_child.closures);
-                element
-                    .append_child(&created_child.node)
-                    .expect("Unable to append element node");
+                element.append_child(&created_child.node)?;
             }
         }
 
+        // the attributes and the children are set, so the listener can initialize the element
+        Self::dispatch_mount_event(program, velem, &element);
+
         let node: Node = element.unchecked_into();
-        CreatedNode { node, closures }
+        Ok(CreatedNode { node, closures })
+    }
+
+    /// create the element of the tag of `velem` in the `document`, without its attributes and children
+    pub(crate) fn create_element<MSG>(
+        document: &web_sys::Document,
+        velem: &crate::Element<MSG>,
+    ) -> Result<Element, JsValue> {
+        let namespace = velem
+            .namespace()
+            .copied()
+            .or_else(|| crate::svg::implied_namespace(velem.tag()));
+        if let Some(namespace) = namespace {
+            document.create_element_ns(Some(namespace), velem.tag())
+        } else {
+            document.create_element(velem.tag())
+        }
+    }
+
+    /// Set up the element of `velem`, whether it is created node by node or imported from
+    /// a template: the element is checked in the debug builds, focused if it has the focus,
+    /// then the attributes which pass the `filter` are set.
+    pub(crate) fn set_up_element<DSP, MSG>(
+        program: &DSP,
+        closures: &mut ActiveClosure,
+        velem: &crate::Element<MSG>,
+        element: &Element,
+        focused_node: &mut Option<Node>,
+        filter: impl Fn(&Attribute<MSG>) -> bool,
+    ) -> Result<(), JsValue>
+    where
+        MSG: 'static,
+        DSP: Clone + Dispatch<MSG> + 'static,
+    {
+        #[cfg(debug_assertions)]
+        if html::attributes::is_missing_rel_protection(velem) {
+            log::warn!(
+                "link with target=\"_blank\" is missing rel=\"noopener noreferrer\": {:?}",
+                velem
+            );
+        }
+
+        if velem.is_focused() {
+            *focused_node = Some(element.clone().unchecked_into());
+            log::trace!("element is focused..{:?}", focused_node);
+            Self::set_element_focus(element)?;
+        }
+
+        let attrs: Vec<&Attribute<MSG>> =
+            velem.get_attributes().iter().collect();
+        for attr in mt_dom::merge_attributes_of_same_name(&attrs)
+            .iter()
+            .filter(|attr| filter(attr))
+        {
+            Self::set_element_attribute(program, closures, element, attr)?;
+        }
+        Ok(())
+    }
+
+    /// the existing element adopted into the view with [`adopt_existing`](crate::html::adopt_existing)
+    fn find_adopted_element<MSG>(
+        velem: &crate::Element<MSG>,
+    ) -> Option<Element> {
+        let selector = velem.get_value(html::ADOPT_ATTR)?.to_string();
+        match crate::document().query_selector(&selector) {
+            Ok(Some(element)) => Some(element),
+            _ => {
+                log::warn!(
+                    "unable to find the existing element to adopt: {}",
+                    selector
+                );
+                None
+            }
+        }
     }
 
     /// set the element attribute
@@ -183,32 +321,52 @@ impl CreatedNode {
         closures: &mut ActiveClosure,
         element: &Element,
         attrs: &[&Attribute<MSG>],
-    ) where
+    ) -> Result<(), JsValue>
+    where
         MSG: 'static,
         DSP: Clone + Dispatch<MSG> + 'static,
     {
         let attrs = mt_dom::merge_attributes_of_same_name(attrs);
         for att in attrs {
-            Self::set_element_attribute(program, closures, element, &att);
+            Self::set_element_attribute(program, closures, element, &att)?;
         }
+        Ok(())
     }
 
     /// set the element attribute
-    #[track_caller]
     pub fn set_element_attribute<DSP, MSG>(
         program: &DSP,
         closures: &mut ActiveClosure,
         element: &Element,
         attr: &Attribute<MSG>,
-    ) where
+    ) -> Result<(), JsValue>
+    where
         MSG: 'static,
         DSP: Clone + Dispatch<MSG> + 'static,
     {
+        if *attr.name() == canvas_draw::DRAW_ATTR {
+            canvas_draw::draw_later(element, attr);
+        }
+        // only the elements which are being created are not in the document yet,
+        // the enter animation is not played again when the attribute is patched
+        if *attr.name() == animate::ENTER_ATTR && !element.is_connected() {
+            animate::play_enter(element, attr);
+        }
+        if *attr.name() == animate::EXIT_ATTR {
+            animate::set_exit(element, attr);
+        }
+        if *attr.name() == tooltip::TOOLTIP_ATTR {
+            tooltip::init();
+        }
+        if *attr.name() == focus_trap::FOCUS_TRAP_ATTR {
+            focus_trap::init();
+        }
         let SegregatedAttributes {
             listeners,
             plain_values,
             styles,
             function_calls,
+            properties,
         } =
             html::attributes::partition_callbacks_from_plain_styles_and_func_calls(
                 attr,
@@ -218,23 +376,26 @@ impl CreatedNode {
         if let Some(merged_plain_values) =
             html::attributes::merge_plain_attributes_values(&plain_values)
         {
+            let merged_plain_values = if *attr.name() == "class" {
+                html::attributes::transform_classes(&merged_plain_values)
+            } else {
+                merged_plain_values
+            };
+            if *attr.name() == "class"
+                && merged_plain_values.contains(html::skeleton::CLASS)
+            {
+                html::skeleton::inject_style();
+            }
             if let Some(namespace) = attr.namespace() {
                 // Warning NOTE: set_attribute_ns should only be called
                 // when you meant to use a namespace
                 // using this with None will error in the browser with:
                 // NamespaceError: An attempt was made to create or change an object in a way which is incorrect with regard to namespaces
-                element
-                    .set_attribute_ns(
-                        Some(namespace),
-                        attr.name(),
-                        &merged_plain_values,
-                    )
-                    .unwrap_or_else(|_| {
-                        panic!(
-                            "Error setting an attribute_ns for {:?}",
-                            element
-                        )
-                    });
+                element.set_attribute_ns(
+                    Some(namespace),
+                    attr.name(),
+                    &merged_plain_values,
+                )?;
             } else {
                 match *attr.name() {
                     // we explicitly call the `set_value` function in the html element
@@ -289,13 +450,7 @@ impl CreatedNode {
                     }
                     _ => {
                         element
-                            .set_attribute(attr.name(), &merged_plain_values)
-                            .unwrap_or_else(|_| {
-                                panic!(
-                                    "Error setting an attribute for {:?}",
-                                    element
-                                )
-                            });
+                            .set_attribute(attr.name(), &merged_plain_values)?;
                     }
                 }
             }
@@ -303,17 +458,18 @@ impl CreatedNode {
             html::attributes::merge_styles_attributes_values(&styles)
         {
             // set the styles
-            element
-                .set_attribute(attr.name(), &merged_styles)
-                .unwrap_or_else(|_| {
-                    panic!("Error setting an attribute_ns for {:?}", element)
-                });
-        } else {
+            element.set_attribute(attr.name(), &merged_styles)?;
+        } else if properties.is_empty() {
             //if the merged attribute is blank of empty when string is trimmed
             //remove the attribute
-            element
-                .remove_attribute(attr.name())
-                .expect("must remove attribute");
+            element.remove_attribute(attr.name())?;
+        }
+
+        // populate the node refs of this element
+        for node_ref in
+            attr.value().iter().filter_map(|v| v.as_custom::<NodeRef>())
+        {
+            node_ref.set(element);
         }
 
         // do function calls such as set_inner_html
@@ -325,44 +481,85 @@ impl CreatedNode {
             }
         }
 
+        // set the properties of the element, these are not reflected in the attributes
+        for property in properties.iter().filter_map(|v| v.get_property()) {
+            js_sys::Reflect::set(
+                element,
+                &JsValue::from_str(attr.name()),
+                &JsValue::from(property),
+            )?;
+        }
+
+        if listeners.is_empty() {
+            return Ok(());
+        }
+        // the msgs of all the listeners of this attribute are debounced or throttled together
+        let dispatch =
+            event_timing::dispatcher(program, event_timing::timing_of(attr));
+
         // add listeners using add_event_listener
         for listener in listeners {
-            let unique_id = create_unique_identifier();
-
-            // set the data-sauron_vdom-id this will be read later on
-            // when it's time to remove this element and its closures and event listeners
-            element
-                .set_attribute(DATA_VDOM_ID, &unique_id.to_string())
-                .expect("Could not set attribute on element");
+            // reuse the data-vdom-id if this element already has one,
+            // so all the closures of this element can be found and removed later on
+            let vdom_id = element
+                .get_attribute(DATA_VDOM_ID)
+                .and_then(|vdom_id| vdom_id.parse::<usize>().ok());
+            let unique_id = match vdom_id {
+                Some(vdom_id) => vdom_id,
+                None => {
+                    let unique_id = create_unique_identifier();
+                    // set the data-sauron_vdom-id this will be read later on
+                    // when it's time to remove this element and its closures and event listeners
+                    element
+                        .set_attribute(DATA_VDOM_ID, &unique_id.to_string())?;
+                    unique_id
+                }
+            };
 
-            closures.insert(unique_id, vec![]);
+            closures.entry(unique_id).or_insert_with(Vec::new);
 
             let event_str = attr.name();
-            let current_elm: &EventTarget =
-                element.dyn_ref().expect("unable to cast to event targe");
+
+            if viewport::is_viewport_event(event_str) {
+                viewport::observe(unique_id, element);
+            } else if *event_str == resize::RESIZE_ELEMENT {
+                resize::observe(unique_id, element);
+            } else if *event_str == click_outside::CLICK_OUTSIDE {
+                click_outside::register(unique_id, element);
+            }
+
+            if event_delegation::is_delegating()
+                && event_delegation::is_delegatable(event_str)
+            {
+                // the listener is not attached to the element, instead it is registered
+                // and will be called by the listener attached at the root node
+                let (event_type, handler) =
+                    create_delegated_handler(&dispatch, event_str, listener);
+                event_delegation::register(
+                    unique_id, event_str, event_type, handler,
+                );
+                continue;
+            }
+
+            let current_elm: &EventTarget = element;
 
             // a custom enter event which triggers the listener
             // when the enter key is pressed
             if *event_str == "enter" {
-                let program_clone = program.clone();
+                let dispatch = Rc::clone(&dispatch);
                 let listener_clone = listener.clone();
                 let key_press_func: Closure<dyn FnMut(web_sys::Event)> =
                     Closure::wrap(Box::new(move |event: web_sys::Event| {
-                        let ke: &web_sys::KeyboardEvent = event
-                            .dyn_ref()
-                            .expect("should be a keyboard event");
-                        if ke.key() == "Enter" {
+                        if is_enter_key(&event) {
                             let msg = listener_clone.emit(Event::from(event));
-                            program_clone.dispatch(msg);
+                            dispatch(msg);
                         }
                     }));
 
-                current_elm
-                    .add_event_listener_with_callback(
-                        "keypress",
-                        key_press_func.as_ref().unchecked_ref(),
-                    )
-                    .expect("unable to attach enter event listener");
+                current_elm.add_event_listener_with_callback(
+                    "keypress",
+                    key_press_func.as_ref().unchecked_ref(),
+                )?;
 
                 key_press_func.forget();
             } else {
@@ -371,25 +568,24 @@ impl CreatedNode {
                 // The callback to this listener emits an Msg which is then \
                 // dispatched to the `program` which then triggers update view cycle.
                 let callback_wrapped: Closure<dyn FnMut(web_sys::Event)> =
-                    create_closure_wrap(program, listener);
-                current_elm
-                    .add_event_listener_with_callback(
-                        event_str,
-                        callback_wrapped.as_ref().unchecked_ref(),
-                    )
-                    .expect("Unable to attached event listener");
+                    create_dispatching_closure(&dispatch, listener);
+                current_elm.add_event_listener_with_callback(
+                    event_str,
+                    callback_wrapped.as_ref().unchecked_ref(),
+                )?;
                 closures
-                    .get_mut(&unique_id)
-                    .expect("Unable to get closure")
+                    .entry(unique_id)
+                    .or_insert_with(Vec::new)
                     .push((event_str, callback_wrapped));
             }
         }
+        Ok(())
     }
 
     /// set focus to this element
-    pub(crate) fn set_element_focus(element: &Element) {
+    pub(crate) fn set_element_focus(element: &Element) -> Result<(), JsValue> {
         let html_element: &HtmlElement = element.unchecked_ref();
-        html_element.focus().expect("must focus")
+        html_element.focus()
     }
 
     /// remove element attribute,
@@ -419,6 +615,57 @@ impl CreatedNode {
     }
 }
 
+/// merge the closures into the existing active closures,
+/// closures of the same element are appended instead of replacing the existing ones.
+pub(crate) fn merge_closures(
+    active_closures: &mut ActiveClosure,
+    closures: ActiveClosure,
+) {
+    for (vdom_id, element_closures) in closures {
+        active_closures
+            .entry(vdom_id)
+            .or_insert_with(Vec::new)
+            .extend(element_closures);
+    }
+}
+
+/// create the handler for the delegated listener, this also returns the actual event type
+/// in which the handler will be triggered.
+fn create_delegated_handler<MSG>(
+    dispatch: &Rc<dyn Fn(MSG)>,
+    event_str: &'static str,
+    listener: &Listener<MSG>,
+) -> (&'static str, DelegatedHandler)
+where
+    MSG: 'static,
+{
+    let dispatch = Rc::clone(dispatch);
+    let listener_clone = listener.clone();
+    if event_str == "enter" {
+        let handler = move |event: web_sys::Event| {
+            if is_enter_key(&event) {
+                let msg = listener_clone.emit(Event::from(event));
+                dispatch(msg);
+            }
+        };
+        ("keypress", Rc::new(handler))
+    } else {
+        let handler = move |event: web_sys::Event| {
+            let msg = listener_clone.emit(Event::from(event));
+            dispatch(msg);
+        };
+        (event_str, Rc::new(handler))
+    }
+}
+
+/// whether the `event` is a keyboard event of the enter key
+fn is_enter_key(event: &web_sys::Event) -> bool {
+    event
+        .dyn_ref::<web_sys::KeyboardEvent>()
+        .map(|ke| ke.key() == "Enter")
+        .unwrap_or(false)
+}
+
 /// This wrap into a closure the function that is dispatched when the event is triggered.
 pub(crate) fn create_closure_wrap<DSP, MSG>(
     program: &DSP,
@@ -427,12 +674,29 @@ pub(crate) fn create_closure_wrap<DSP, MSG>(
 where
     MSG: 'static,
     DSP: Clone + Dispatch<MSG> + 'static,
+{
+    create_dispatching_closure(
+        &event_timing::dispatcher(program, None),
+        listener,
+    )
+}
+
+/// wrap the listener into a closure which dispatch its msg with the `dispatch` function
+fn create_dispatching_closure<MSG>(
+    dispatch: &Rc<dyn Fn(MSG)>,
+    listener: &Listener<MSG>,
+) -> Closure<dyn FnMut(web_sys::Event)>
+where
+    MSG: 'static,
 {
     let listener_clone = listener.clone();
-    let program_clone = program.clone();
+    let dispatch = Rc::clone(dispatch);
 
     Closure::wrap(Box::new(move |event: web_sys::Event| {
+        if let Some(current_target) = event.current_target() {
+            event_delegation::record_handled(&event, &current_target);
+        }
         let msg = listener_clone.emit(Event::from(event));
-        program_clone.dispatch(msg);
+        dispatch(msg);
     }))
 }
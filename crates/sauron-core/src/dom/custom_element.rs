@@ -0,0 +1,439 @@
+//! provides the reflection of the attributes and the properties of a custom element
+//! into the MSG of the app, and the custom events dispatched from the app to the host page,
+//! so an app wrapped as a custom element behaves like an idiomatic web component.
+//!
+//! The complex values are passed as json, which is deserialized with serde.
+//!
+//! The app is exported as a custom element with [`register`], so it can be used from the
+//! pages and the frameworks which are not written in rust.
+//!
+//! # Examples
+//! ```rust,ignore
+//! let reflection = Reflection::new()
+//!     .attribute("label", |value| Msg::SetLabel(value.unwrap_or_default()))
+//!     .attribute_json("items", Msg::SetItems)
+//!     .property_json("config", Msg::SetConfig);
+//!
+//! // in the update of the app, notify the host page
+//! Cmd::emit_custom_event("change", &self.selected)
+//!
+//! // then in the entry point of the wasm module
+//! custom_element::register::<App, Msg>("my-widget", reflection)?;
+//! ```
+use crate::{dom::cmd::Cmd, Application, Dispatch, Program};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::HtmlElement;
+
+/// the property of the element which holds its id, to look up the program mounted in it
+const ELEMENT_ID: &str = "__sauron_element_id";
+
+/// the body of the function which creates the class of the custom element,
+/// the callbacks of the element are calling into the closures in the arguments
+const DEFINE_CLASS: &str = r#"
+    class SauronElement extends HTMLElement {
+        static get observedAttributes() { return observed; }
+        connectedCallback() { connected(this); }
+        disconnectedCallback() { disconnected(this); }
+        attributeChangedCallback(name, oldValue, newValue) {
+            if (oldValue !== newValue) { attributeChanged(this, name, newValue); }
+        }
+    }
+    for (const property of properties) {
+        const field = "__sauron_" + property;
+        Object.defineProperty(SauronElement.prototype, property, {
+            get() { return this[field]; },
+            set(value) { this[field] = value; propertyChanged(this, property, value); },
+        });
+    }
+    return SauronElement;
+"#;
+
+thread_local! {
+    static NEXT_ELEMENT_ID: Cell<u32> = Cell::new(0);
+}
+
+type AttributeMapper<MSG> = Rc<dyn Fn(Option<String>) -> Option<MSG>>;
+type PropertyMapper<MSG> = Rc<dyn Fn(JsValue) -> Option<MSG>>;
+
+/// The declarative mapping of the observed attributes and the properties of the custom element
+/// into the MSG which is dispatched into the app when they are changed by the host page
+pub struct Reflection<MSG> {
+    attributes: Vec<(&'static str, AttributeMapper<MSG>)>,
+    properties: Vec<(&'static str, PropertyMapper<MSG>)>,
+}
+
+impl<MSG> Reflection<MSG>
+where
+    MSG: 'static,
+{
+    /// a reflection which has no attributes and properties
+    pub fn new() -> Self {
+        Reflection {
+            attributes: vec![],
+            properties: vec![],
+        }
+    }
+
+    /// observe the attribute, `f` is called with the new value of the attribute,
+    /// which is `None` when the attribute is removed
+    pub fn attribute<F>(mut self, name: &'static str, f: F) -> Self
+    where
+        F: Fn(Option<String>) -> MSG + 'static,
+    {
+        self.attributes
+            .push((name, Rc::new(move |value| Some(f(value)))));
+        self
+    }
+
+    /// observe the attribute which holds a json value, the values which can not be
+    /// deserialized are logged and not dispatched
+    pub fn attribute_json<T, F>(mut self, name: &'static str, f: F) -> Self
+    where
+        T: DeserializeOwned,
+        F: Fn(T) -> MSG + 'static,
+    {
+        self.attributes.push((
+            name,
+            Rc::new(move |value| {
+                let value = value?;
+                match serde_json::from_str(&value) {
+                    Ok(value) => Some(f(value)),
+                    Err(e) => {
+                        log::warn!("invalid value of {}: {}", name, e);
+                        None
+                    }
+                }
+            }),
+        ));
+        self
+    }
+
+    /// map the property, `f` is called with the value assigned into the property of the element
+    pub fn property<F>(mut self, name: &'static str, f: F) -> Self
+    where
+        F: Fn(JsValue) -> MSG + 'static,
+    {
+        self.properties
+            .push((name, Rc::new(move |value| Some(f(value)))));
+        self
+    }
+
+    /// map the property which holds a complex value, which is deserialized from its json.
+    /// The values which can not be deserialized are logged and not dispatched
+    pub fn property_json<T, F>(mut self, name: &'static str, f: F) -> Self
+    where
+        T: DeserializeOwned,
+        F: Fn(T) -> MSG + 'static,
+    {
+        self.properties.push((
+            name,
+            Rc::new(move |value| {
+                let json = js_sys::JSON::stringify(&value)
+                    .ok()
+                    .and_then(|json| json.as_string())?;
+                match serde_json::from_str(&json) {
+                    Ok(value) => Some(f(value)),
+                    Err(e) => {
+                        log::warn!("invalid value of {}: {}", name, e);
+                        None
+                    }
+                }
+            }),
+        ));
+        self
+    }
+}
+
+impl<MSG> Default for Reflection<MSG>
+where
+    MSG: 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<MSG> Clone for Reflection<MSG> {
+    fn clone(&self) -> Self {
+        Reflection {
+            attributes: self.attributes.clone(),
+            properties: self.properties.clone(),
+        }
+    }
+}
+
+impl<MSG> fmt::Debug for Reflection<MSG> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Reflection")
+            .field("attributes", &self.observed_attributes())
+            .field("properties", &self.properties())
+            .finish()
+    }
+}
+
+impl<MSG> Reflection<MSG> {
+    /// the names of the observed attributes, to be returned in `observedAttributes`
+    pub fn observed_attributes(&self) -> Vec<&'static str> {
+        self.attributes.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// the names of the mapped properties
+    pub fn properties(&self) -> Vec<&'static str> {
+        self.properties.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// the MSG when the attribute is changed, to be called in `attributeChangedCallback`
+    pub fn attribute_changed(
+        &self,
+        name: &str,
+        value: Option<String>,
+    ) -> Option<MSG> {
+        let (_, mapper) = self
+            .attributes
+            .iter()
+            .find(|(attribute, _)| *attribute == name)?;
+        mapper(value)
+    }
+
+    /// the MSG when the property is assigned
+    pub fn property_changed(&self, name: &str, value: JsValue) -> Option<MSG> {
+        let (_, mapper) = self
+            .properties
+            .iter()
+            .find(|(property, _)| *property == name)?;
+        mapper(value)
+    }
+}
+
+impl<APP, MSG> Cmd<Program<APP, MSG>>
+where
+    MSG: 'static,
+    APP: Application<MSG> + 'static,
+{
+    /// Creates a Cmd which dispatch a `CustomEvent` with the `detail` serialized as json
+    /// from the root element of the app. The event bubbles and crosses the shadow root,
+    /// so the host page can listen to it on the custom element.
+    pub fn emit_custom_event<T>(event_name: &str, detail: &T) -> Self
+    where
+        T: Serialize,
+    {
+        let event_name = event_name.to_string();
+        let detail = serde_json::to_string(detail)
+            .ok()
+            .and_then(|json| js_sys::JSON::parse(&json).ok());
+        Cmd::new(move |program: Program<APP, MSG>| {
+            let mut event_init = web_sys::CustomEventInit::new();
+            event_init.bubbles(true).composed(true);
+            if let Some(detail) = detail.as_ref() {
+                event_init.detail(detail);
+            }
+            let event = match web_sys::CustomEvent::new_with_event_init_dict(
+                &event_name,
+                &event_init,
+            ) {
+                Ok(event) => event,
+                Err(e) => {
+                    log::warn!("unable to create the event: {:?}", e);
+                    return;
+                }
+            };
+            let root_node = program.dom_updater.borrow().root_node();
+            if let Err(e) = root_node.dispatch_event(&event) {
+                log::warn!("unable to dispatch the event: {:?}", e);
+            }
+        })
+        .no_render()
+    }
+}
+
+/// Define the custom element `name` for the app, which is created with `APP::default()`.
+///
+/// The app is mounted into the shadow root of the element when it is connected to the document,
+/// and unmounted when it is disconnected, dropping the closures of its view.
+/// See [`register_with`] for the details.
+pub fn register<APP, MSG>(
+    name: &str,
+    reflection: Reflection<MSG>,
+) -> Result<(), JsValue>
+where
+    APP: Application<MSG> + Default + 'static,
+    MSG: 'static,
+{
+    register_with(name, APP::default, reflection)
+}
+
+/// Define the custom element `name`, each of the element mounts its own app created
+/// with `app_fn` into its shadow root when it is connected to the document.
+///
+/// The changes of the observed attributes and the properties of the element are dispatched
+/// into the app as the MSG from the `reflection`, the attributes and the properties which are
+/// set before the element is connected are dispatched right after the app is mounted.
+///
+/// When the element is disconnected, the app is unmounted, its subscriptions are stopped
+/// and the closures of its view are dropped. Moving the element in the document mounts
+/// a new app, the state of the previous one is not kept.
+///
+/// This returns an error if the custom element is already defined or the name is not valid,
+/// ie: it doesn't have a dash.
+///
+/// Note: the class of the custom element is created with `new Function`,
+/// which needs the `unsafe-eval` in the Content Security Policy of the page.
+pub fn register_with<APP, MSG, F>(
+    name: &str,
+    app_fn: F,
+    reflection: Reflection<MSG>,
+) -> Result<(), JsValue>
+where
+    APP: Application<MSG> + 'static,
+    MSG: 'static,
+    F: Fn() -> APP + 'static,
+{
+    let programs: Rc<RefCell<HashMap<u32, Program<APP, MSG>>>> =
+        Rc::new(RefCell::new(HashMap::new()));
+    let reflection = Rc::new(reflection);
+
+    let connected: Closure<dyn FnMut(HtmlElement)> = Closure::wrap(Box::new({
+        let programs = Rc::clone(&programs);
+        let reflection = Rc::clone(&reflection);
+        move |element: HtmlElement| {
+            let id = element_id(&element);
+            if programs.borrow().contains_key(&id) {
+                return;
+            }
+            let program = Program::mount_to_shadow(app_fn(), &element);
+            let msgs = initial_msgs(&reflection, &element);
+            if !msgs.is_empty() {
+                program.dispatch_multiple(msgs);
+            }
+            programs.borrow_mut().insert(id, program);
+        }
+    }));
+
+    let disconnected: Closure<dyn FnMut(HtmlElement)> =
+        Closure::wrap(Box::new({
+            let programs = Rc::clone(&programs);
+            move |element: HtmlElement| {
+                let program =
+                    programs.borrow_mut().remove(&element_id(&element));
+                if let Some(program) = program {
+                    program.unmount();
+                    program.dom_updater.borrow_mut().active_closures.clear();
+                }
+            }
+        }));
+
+    let attribute_changed: Closure<
+        dyn FnMut(HtmlElement, String, Option<String>),
+    > = Closure::wrap(Box::new({
+        let programs = Rc::clone(&programs);
+        let reflection = Rc::clone(&reflection);
+        move |element: HtmlElement, name: String, value: Option<String>| {
+            // the attributes before the element is connected are dispatched once it is mounted
+            let program = programs.borrow().get(&element_id(&element)).cloned();
+            if let Some(program) = program {
+                if let Some(msg) = reflection.attribute_changed(&name, value) {
+                    program.dispatch(msg);
+                }
+            }
+        }
+    }));
+
+    let property_changed: Closure<dyn FnMut(HtmlElement, String, JsValue)> =
+        Closure::wrap(Box::new({
+            let reflection = Rc::clone(&reflection);
+            move |element: HtmlElement, name: String, value: JsValue| {
+                let program =
+                    programs.borrow().get(&element_id(&element)).cloned();
+                if let Some(program) = program {
+                    if let Some(msg) = reflection.property_changed(&name, value)
+                    {
+                        program.dispatch(msg);
+                    }
+                }
+            }
+        }));
+
+    let observed: js_sys::Array = reflection
+        .observed_attributes()
+        .into_iter()
+        .map(JsValue::from)
+        .collect();
+    let properties: js_sys::Array = reflection
+        .properties()
+        .into_iter()
+        .map(JsValue::from)
+        .collect();
+
+    let define_class = js_sys::Function::new_with_args(
+        "connected, disconnected, attributeChanged, propertyChanged, observed, properties",
+        DEFINE_CLASS,
+    );
+    let args = js_sys::Array::of4(
+        connected.as_ref(),
+        disconnected.as_ref(),
+        attribute_changed.as_ref(),
+        property_changed.as_ref(),
+    );
+    args.push(&observed);
+    args.push(&properties);
+    let class: js_sys::Function =
+        define_class.apply(&JsValue::NULL, &args)?.unchecked_into();
+    crate::window().custom_elements().define(name, &class)?;
+
+    // the custom element is defined for the rest of the lifetime of the page
+    connected.forget();
+    disconnected.forget();
+    attribute_changed.forget();
+    property_changed.forget();
+    Ok(())
+}
+
+/// the id of the element, which is assigned the first time it is connected
+fn element_id(element: &HtmlElement) -> u32 {
+    let key = JsValue::from_str(ELEMENT_ID);
+    if let Some(id) = js_sys::Reflect::get(element, &key)
+        .ok()
+        .and_then(|id| id.as_f64())
+    {
+        return id as u32;
+    }
+    let id = NEXT_ELEMENT_ID.with(|next_id| {
+        let id = next_id.get();
+        next_id.set(id + 1);
+        id
+    });
+    js_sys::Reflect::set(element, &key, &JsValue::from(id))
+        .expect("must set the id of the element");
+    id
+}
+
+/// the MSG of the attributes and properties which are set before the element is connected
+fn initial_msgs<MSG>(
+    reflection: &Reflection<MSG>,
+    element: &HtmlElement,
+) -> Vec<MSG> {
+    let attribute_msgs = reflection
+        .observed_attributes()
+        .into_iter()
+        .filter_map(|name| {
+            let value = element.get_attribute(name)?;
+            reflection.attribute_changed(name, Some(value))
+        });
+    let property_msgs =
+        reflection.properties().into_iter().filter_map(|name| {
+            let value =
+                js_sys::Reflect::get(element, &JsValue::from_str(name)).ok()?;
+            if value.is_undefined() {
+                return None;
+            }
+            reflection.property_changed(name, value)
+        });
+    attribute_msgs.chain(property_msgs).collect()
+}
@@ -0,0 +1,218 @@
+//! provides the hook for browser devtools, which is registered as `window.__SAURON_DEVTOOLS__`
+//! when the `with-devtools` feature is enabled.
+//!
+//! The hook has the following methods, where `index` is the order in which the programs
+//! are created, defaulting to the first program:
+//! - `programs()` the number of programs created in the page
+//! - `vdom(index)` the current vdom of the program as a json object
+//! - `closures(index)` the event names of the active closures for each `data-vdom-id` of the elements
+//! - `subscribe(callback)` calls the callback with the patches applied into the DOM,
+//!     returns the subscription id
+//! - `unsubscribe(id)` stops calling the callback of the subscription
+use crate::{
+    dom::dom_updater::DomUpdater, html::attributes::AttributeValue,
+    vdom::patch::PatchKind, Attribute, Node, Patch,
+};
+use serde_json::{json, Value};
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+/// the name of the global variable where the hook is registered
+pub const HOOK_NAME: &str = "__SAURON_DEVTOOLS__";
+
+thread_local!(static PROGRAMS: RefCell<Vec<Box<dyn Inspect>>> = RefCell::new(vec![]));
+thread_local!(static SUBSCRIBERS: RefCell<Vec<(u32, js_sys::Function)>> = RefCell::new(vec![]));
+thread_local!(static SUBSCRIPTION_ID: Cell<u32> = Cell::new(0));
+
+/// the program which can be inspected by the devtools,
+/// this is implemented for the dom updater of the program regardless of the type of its MSG
+trait Inspect {
+    /// the current vdom as json, None when the program is already dropped
+    fn vdom(&self) -> Option<Value>;
+    /// the event names of the active closures for each element
+    fn closures(&self) -> Option<Value>;
+}
+
+impl<MSG> Inspect for Weak<RefCell<DomUpdater<MSG>>> {
+    fn vdom(&self) -> Option<Value> {
+        let dom_updater = self.upgrade()?;
+        let dom_updater = dom_updater.borrow();
+        Some(node_to_json(&dom_updater.current_vdom))
+    }
+
+    fn closures(&self) -> Option<Value> {
+        let dom_updater = self.upgrade()?;
+        let dom_updater = dom_updater.borrow();
+        let closures: serde_json::Map<String, Value> = dom_updater
+            .active_closures
+            .iter()
+            .map(|(vdom_id, closures)| {
+                let event_names: Vec<&str> = closures
+                    .iter()
+                    .map(|(event_name, _closure)| *event_name)
+                    .collect();
+                (vdom_id.to_string(), json!(event_names))
+            })
+            .collect();
+        Some(Value::Object(closures))
+    }
+}
+
+/// register the dom updater of a program to be inspected by the devtools,
+/// the hook is installed into the window on the first program
+pub(crate) fn register<MSG>(dom_updater: &Rc<RefCell<DomUpdater<MSG>>>)
+where
+    MSG: 'static,
+{
+    let is_first = PROGRAMS.with(|programs| {
+        let mut programs = programs.borrow_mut();
+        programs.push(Box::new(Rc::downgrade(dom_updater)));
+        programs.len() == 1
+    });
+    if is_first {
+        install_hook();
+    }
+}
+
+/// call the subscribers with the patches which are about to be applied into the DOM
+pub(crate) fn publish_patches<MSG>(patches: &[Patch<MSG>]) {
+    let subscribers =
+        SUBSCRIBERS.with(|subscribers| subscribers.borrow().clone());
+    if subscribers.is_empty() {
+        return;
+    }
+    let patches: Vec<Value> = patches
+        .iter()
+        .map(|patch| {
+            json!({
+                "type": PatchKind::from(patch).name(),
+                "path": patch.path(),
+                "tag": patch.tag(),
+            })
+        })
+        .collect();
+    let patches = to_js_value(&json!(patches));
+    for (_id, callback) in subscribers {
+        if let Err(e) = callback.call1(&JsValue::NULL, &patches) {
+            log::warn!("devtools subscriber failed: {:?}", e);
+        }
+    }
+}
+
+fn install_hook() {
+    let hook = js_sys::Object::new();
+    add_method(&hook, "programs", |_arg| {
+        JsValue::from(PROGRAMS.with(|programs| programs.borrow().len() as u32))
+    });
+    add_method(&hook, "vdom", |arg| inspect(arg, |program| program.vdom()));
+    add_method(&hook, "closures", |arg| {
+        inspect(arg, |program| program.closures())
+    });
+    add_method(&hook, "subscribe", |arg| {
+        let callback: js_sys::Function = match arg.dyn_into() {
+            Ok(callback) => callback,
+            Err(_) => return JsValue::UNDEFINED,
+        };
+        let id = SUBSCRIPTION_ID.with(|id| {
+            id.set(id.get() + 1);
+            id.get()
+        });
+        SUBSCRIBERS
+            .with(|subscribers| subscribers.borrow_mut().push((id, callback)));
+        JsValue::from(id)
+    });
+    add_method(&hook, "unsubscribe", |arg| {
+        if let Some(id) = arg.as_f64() {
+            SUBSCRIBERS.with(|subscribers| {
+                subscribers.borrow_mut().retain(|(subscription_id, _)| {
+                    f64::from(*subscription_id) != id
+                })
+            });
+        }
+        JsValue::UNDEFINED
+    });
+    js_sys::Reflect::set(
+        &crate::window(),
+        &JsValue::from_str(HOOK_NAME),
+        &hook,
+    )
+    .expect("must register the devtools hook");
+}
+
+fn add_method<F>(hook: &js_sys::Object, name: &str, f: F)
+where
+    F: Fn(JsValue) -> JsValue + 'static,
+{
+    let method: Closure<dyn Fn(JsValue) -> JsValue> =
+        Closure::wrap(Box::new(f));
+    js_sys::Reflect::set(hook, &JsValue::from_str(name), method.as_ref())
+        .expect("must add the devtools method");
+    // the hook lives for the rest of the page lifetime
+    method.forget();
+}
+
+/// inspect the program at the index in the `arg`, which defaults to the first program
+fn inspect<F>(arg: JsValue, f: F) -> JsValue
+where
+    F: Fn(&dyn Inspect) -> Option<Value>,
+{
+    let index = arg.as_f64().map(|index| index as usize).unwrap_or(0);
+    PROGRAMS
+        .with(|programs| {
+            programs
+                .borrow()
+                .get(index)
+                .and_then(|program| f(program.as_ref()))
+        })
+        .map(|value| to_js_value(&value))
+        .unwrap_or(JsValue::UNDEFINED)
+}
+
+fn to_js_value(value: &Value) -> JsValue {
+    js_sys::JSON::parse(&value.to_string()).expect("must be a valid json")
+}
+
+/// convert the vdom into json
+fn node_to_json<MSG>(node: &Node<MSG>) -> Value {
+    match node {
+        Node::Element(element) => json!({
+            "tag": element.tag(),
+            "namespace": element.namespace(),
+            "attributes": element
+                .get_attributes()
+                .iter()
+                .map(attribute_to_json)
+                .collect::<Vec<_>>(),
+            "children": element
+                .get_children()
+                .iter()
+                .map(node_to_json)
+                .collect::<Vec<_>>(),
+        }),
+        Node::Text(text) => json!({ "text": text.text }),
+        Node::Comment(comment) => json!({ "comment": comment }),
+    }
+}
+
+fn attribute_to_json<MSG>(attr: &Attribute<MSG>) -> Value {
+    let values: Vec<Value> = attr
+        .value()
+        .iter()
+        .map(|value| match value {
+            AttributeValue::Simple(value)
+            | AttributeValue::FunctionCall(value)
+            | AttributeValue::Property(value) => json!(value.to_string()),
+            AttributeValue::Style(styles) => json!(styles
+                .iter()
+                .map(|style| style.to_string())
+                .collect::<String>()),
+            AttributeValue::EventListener(_) => json!("<listener>"),
+            AttributeValue::Custom(custom) => json!(format!("{:?}", custom)),
+            AttributeValue::Empty => Value::Null,
+        })
+        .collect();
+    json!({ "name": attr.name(), "values": values })
+}
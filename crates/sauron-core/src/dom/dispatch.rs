@@ -15,4 +15,20 @@ pub trait Dispatch<MSG> {
 
     /// dispatch multiple msg
     fn dispatch_multiple(&self, msgs: Vec<MSG>);
+
+    /// dispatch the msg right away, ahead of the msgs which are waiting for the next frame,
+    /// ie: the keystrokes of an input which should not lag behind the typing.
+    ///
+    /// This is the same as `dispatch`, unless it is overridden such as in the Program.
+    fn dispatch_urgent(&self, msg: MSG) {
+        self.dispatch(msg)
+    }
+
+    /// dispatch the msg once the browser is idle, after the msgs of the user interactions,
+    /// ie: the polling ticks and the analytics which can wait.
+    ///
+    /// This is the same as `dispatch`, unless it is overridden such as in the Program.
+    fn dispatch_idle(&self, msg: MSG) {
+        self.dispatch(msg)
+    }
 }
@@ -1,14 +1,42 @@
 use crate::{
     diff,
     dom::{
-        apply_patches::patch,
-        created_node::{ActiveClosure, CreatedNode},
-        Dispatch,
+        apply_patches::{self, patch, patch_with_old_vdom, PatchOperations},
+        created_node::{self, ActiveClosure, CreatedNode},
+        event_delegation::{self, DelegatedListeners},
+        scheduler, transition_group, Dispatch, MountOptions,
     },
+    vdom::{patch::TreePath, DiffStrategy},
     Patch,
 };
-use wasm_bindgen::JsCast;
-use web_sys::{self, Element, Node};
+use std::{cell::RefCell, rc::Rc};
+use thiserror::Error;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{
+    self, Element, EventTarget, HtmlInputElement, HtmlTextAreaElement, Node,
+};
+
+/// Errors when the view can not be mounted or patched into the DOM
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DomError {
+    /// the view could not be mounted into the root node
+    #[error("unable to mount the view: {0}")]
+    Mount(String),
+    /// the patches could not be applied into the DOM,
+    /// the DOM may be left partially patched
+    #[error("unable to patch the dom: {0}")]
+    Patch(String),
+}
+
+impl DomError {
+    fn mount(js_value: JsValue) -> Self {
+        DomError::Mount(format!("{:?}", js_value))
+    }
+
+    fn patch(js_value: JsValue) -> Self {
+        DomError::Patch(format!("{:?}", js_value))
+    }
+}
 
 /// Used for keeping a real DOM node up to date based on the current Node
 /// and a new incoming Node that represents our latest DOM state.
@@ -25,8 +53,32 @@ pub struct DomUpdater<MSG> {
     pub active_closures: ActiveClosure,
     /// after mounting or update dispatch call, the element will be focused
     pub focused_node: Option<Node>,
+    /// when enabled, the event listeners of the elements are not attached to each element,
+    /// instead one listener per event type is attached to the root node
+    /// which then routes the event to the listeners of the element using its vdom-id.
+    event_delegation: bool,
+    /// the listeners of the elements of this view which are delegated to the root node
+    delegated_listeners: event_delegation::Registry,
+    /// the node where the delegated listeners are attached, which is the root node
+    /// unless it has been replaced since
+    delegation_root: Option<Node>,
+    /// the listeners attached to the root node when event delegation is enabled
+    delegated_root_listeners:
+        Vec<(&'static str, Closure<dyn FnMut(web_sys::Event)>)>,
+    /// the algorithm used to diff the current vdom and the new vdom,
+    /// the default [`diff`] is used when not set
+    diff_strategy: Option<Box<dyn DiffStrategy<MSG>>>,
+    /// the views with at least this many nodes are updated in slices across multiple frames
+    time_slicing: Option<usize>,
+    /// an update of the DOM is being applied in slices
+    is_updating_in_slices: bool,
+    /// the view has changed while the update of the DOM is being applied in slices
+    is_stale: bool,
 }
 
+/// the time spent applying the patches in one slice, before yielding to the browser
+const SLICE_BUDGET: f64 = 8.0;
+
 impl<MSG> DomUpdater<MSG> {
     /// Creates and instance of this DOM updater, but doesn't mount the current_vdom to the DOM just yet.
     pub fn new(
@@ -38,6 +90,89 @@ impl<MSG> DomUpdater<MSG> {
             root_node: mount.clone(),
             active_closures: ActiveClosure::new(),
             focused_node: None,
+            event_delegation: false,
+            delegated_listeners: Rc::new(RefCell::new(
+                DelegatedListeners::default(),
+            )),
+            delegation_root: None,
+            delegated_root_listeners: vec![],
+            diff_strategy: None,
+            time_slicing: None,
+            is_updating_in_slices: false,
+            is_stale: false,
+        }
+    }
+
+    /// Set the algorithm used to diff the current vdom and the new vdom
+    /// when the DOM is updated, ie: [`Positional`](crate::vdom::Positional) for huge flat lists
+    pub fn set_diff_strategy(
+        &mut self,
+        diff_strategy: impl DiffStrategy<MSG> + 'static,
+    ) {
+        self.diff_strategy = Some(Box::new(diff_strategy));
+    }
+
+    /// Update the DOM of the views with at least `node_count` nodes in slices across multiple frames,
+    /// yielding to the browser in between so a huge view doesn't block the main thread.
+    ///
+    /// This is used by the [`Program`](crate::Program), the `update_dom` of the DomUpdater
+    /// always updates the DOM at once.
+    pub fn set_time_slicing(&mut self, node_count: usize) {
+        self.time_slicing = Some(node_count);
+    }
+
+    /// returns true if an update of the DOM is being applied in slices
+    pub fn is_updating_in_slices(&self) -> bool {
+        self.is_updating_in_slices
+    }
+
+    /// Enable or disable event delegation.
+    ///
+    /// This should be set before mounting, since elements that are already created
+    /// will keep their listeners attached to themselves.
+    pub fn set_event_delegation(&mut self, event_delegation: bool) {
+        self.event_delegation = event_delegation;
+    }
+
+    /// returns true if event delegation is enabled
+    pub fn is_event_delegation(&self) -> bool {
+        self.event_delegation
+    }
+
+    /// count the total number of elements which has listeners delegated to the root node
+    pub fn delegated_listener_len(&self) -> usize {
+        self.delegated_listeners.borrow().len()
+    }
+
+    /// the registry where the listeners of the created elements are delegated into,
+    /// if event delegation is enabled
+    fn delegation_registry(&self) -> Option<event_delegation::Registry> {
+        if self.event_delegation {
+            Some(Rc::clone(&self.delegated_listeners))
+        } else {
+            None
+        }
+    }
+
+    /// remove the listeners attached to the root node for the delegated listeners
+    fn remove_delegated_root_listeners(&mut self) {
+        let delegation_root = match self.delegation_root.take() {
+            Some(delegation_root) => delegation_root,
+            None => return,
+        };
+        let target: &EventTarget = delegation_root.unchecked_ref();
+        for (event_type, root_listener) in
+            self.delegated_root_listeners.drain(..)
+        {
+            if let Err(e) = target.remove_event_listener_with_callback(
+                event_type,
+                root_listener.as_ref().unchecked_ref(),
+            ) {
+                log::warn!(
+                    "unable to remove the delegated event listener: {:?}",
+                    e
+                );
+            }
         }
     }
 
@@ -58,54 +193,147 @@ where
     /// Mount the current_vdom appending to the actual browser DOM specified in the root_node
     /// This also gets the closures that was created when mounting the vdom to their
     /// actual DOM counterparts.
-    pub fn append_to_mount<DSP>(&mut self, program: &DSP)
+    pub fn append_to_mount<DSP>(
+        &mut self,
+        program: &DSP,
+    ) -> Result<(), DomError>
+    where
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        self.mount(program, false)
+    }
+
+    /// Mount the current_vdom appending to the root_node, after the existing children
+    /// of the root_node are cleared according to the `options`
+    pub fn append_to_mount_with_options<DSP>(
+        &mut self,
+        program: &DSP,
+        options: &MountOptions,
+    ) -> Result<(), DomError>
     where
         DSP: Dispatch<MSG> + Clone + 'static,
     {
-        self.mount(program, false);
+        options.prepare(&self.root_node).map_err(DomError::mount)?;
+        self.mount(program, false)
     }
 
     /// each element and it's descendant in the vdom is created into
     /// an actual DOM node.
-    fn mount<DSP>(&mut self, program: &DSP, replace: bool)
+    fn mount<DSP>(
+        &mut self,
+        program: &DSP,
+        replace: bool,
+    ) -> Result<(), DomError>
     where
         DSP: Dispatch<MSG> + Clone + 'static,
     {
-        let created_node = CreatedNode::create_dom_node(
-            program,
-            &self.current_vdom,
-            &mut self.focused_node,
-        );
+        let registry = self.delegation_registry();
+        let current_vdom = &self.current_vdom;
+        let focused_node = &mut self.focused_node;
+        let created_node = event_delegation::with_delegation(registry, || {
+            CreatedNode::create_dom_node(program, current_vdom, focused_node)
+        })
+        .map_err(DomError::mount)?;
         if replace {
             let root_element: &Element = self.root_node.unchecked_ref();
             root_element
                 .replace_with_with_node_1(&created_node.node)
-                .expect("Could not append child to mount");
+                .map_err(DomError::mount)?;
         } else {
             self.root_node
                 .append_child(&created_node.node)
-                .expect("Could not append child to mount");
+                .map_err(DomError::mount)?;
         }
         self.root_node = created_node.node;
         self.active_closures = created_node.closures;
         self.set_focus_element();
+        self.attach_delegated_root_listeners();
+        Ok(())
+    }
+
+    /// Discard the DOM of the current vdom and create the DOM of the `new_vdom` from scratch,
+    /// replacing the root node.
+    ///
+    /// This is used to recover when the DOM is out of sync with the current vdom,
+    /// ie: after a failed [`update_dom`](Self::update_dom).
+    pub fn rebuild_dom<DSP>(
+        &mut self,
+        program: &DSP,
+        new_vdom: crate::Node<MSG>,
+    ) -> Result<(), DomError>
+    where
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        self.current_vdom = new_vdom;
+        self.focused_node = None;
+        self.mount(program, true)
+    }
+
+    /// attach a listener on the root node for each of the event type
+    /// that has been delegated and has not been attached yet.
+    ///
+    /// When the root node has been replaced, the listeners are moved from the old root node.
+    fn attach_delegated_root_listeners(&mut self) {
+        if !self.event_delegation {
+            return;
+        }
+        let is_same_root = self
+            .delegation_root
+            .as_ref()
+            .map(|delegation_root| {
+                delegation_root.is_same_node(Some(&self.root_node))
+            })
+            .unwrap_or(false);
+        if !is_same_root {
+            self.remove_delegated_root_listeners();
+            self.delegation_root = Some(self.root_node.clone());
+        }
+        let event_types = self.delegated_listeners.borrow().event_types();
+        for event_type in event_types {
+            let is_attached = self
+                .delegated_root_listeners
+                .iter()
+                .any(|(attached, _)| *attached == event_type);
+            if is_attached {
+                continue;
+            }
+            let root_node = self.root_node.clone();
+            let registry = Rc::clone(&self.delegated_listeners);
+            let root_listener: Closure<dyn FnMut(web_sys::Event)> =
+                Closure::wrap(Box::new(move |event: web_sys::Event| {
+                    event_delegation::dispatch_event(
+                        &root_node, &registry, &event,
+                    );
+                }));
+            let target: &EventTarget = self.root_node.unchecked_ref();
+            target
+                .add_event_listener_with_callback(
+                    event_type,
+                    root_listener.as_ref().unchecked_ref(),
+                )
+                .expect("Unable to attach delegated event listener");
+            self.delegated_root_listeners
+                .push((event_type, root_listener));
+        }
     }
 
     fn set_focus_element(&self) {
         if let Some(focused_node) = &self.focused_node {
             let focused_element: &Element = focused_node.unchecked_ref();
-            CreatedNode::set_element_focus(focused_element);
+            if let Err(e) = CreatedNode::set_element_focus(focused_element) {
+                log::warn!("unable to focus the element: {:?}", e);
+            }
         }
     }
 
     /// Mount the current_vdom replacing the actual browser DOM specified in the root_node
     /// This also gets the closures that was created when mounting the vdom to their
     /// actual DOM counterparts.
-    pub fn replace_mount<DSP>(&mut self, program: &DSP)
+    pub fn replace_mount<DSP>(&mut self, program: &DSP) -> Result<(), DomError>
     where
         DSP: Dispatch<MSG> + Clone + 'static,
     {
-        self.mount(program, true);
+        self.mount(program, true)
     }
 
     /// Create a new `DomUpdater`.
@@ -116,13 +344,13 @@ where
         program: &DSP,
         current_vdom: crate::Node<MSG>,
         mount: &Element,
-    ) -> DomUpdater<MSG>
+    ) -> Result<DomUpdater<MSG>, DomError>
     where
         DSP: Dispatch<MSG> + Clone + 'static,
     {
         let mut dom_updater = Self::new(current_vdom, mount);
-        dom_updater.append_to_mount(program);
-        dom_updater
+        dom_updater.append_to_mount(program)?;
+        Ok(dom_updater)
     }
 
     /// Create a new `DomUpdater`.
@@ -133,13 +361,73 @@ where
         program: &DSP,
         current_vdom: crate::Node<MSG>,
         mount: Element,
-    ) -> DomUpdater<MSG>
+    ) -> Result<DomUpdater<MSG>, DomError>
     where
         DSP: Dispatch<MSG> + Clone + 'static,
     {
         let mut dom_updater = Self::new(current_vdom, &mount);
-        dom_updater.replace_mount(program);
-        dom_updater
+        dom_updater.replace_mount(program)?;
+        Ok(dom_updater)
+    }
+
+    /// the patches to turn the `old` vdom into the `new` vdom, using the diff strategy
+    fn diff<'a>(
+        &self,
+        old: &'a crate::Node<MSG>,
+        new: &'a crate::Node<MSG>,
+    ) -> Vec<Patch<'a, MSG>> {
+        match &self.diff_strategy {
+            Some(diff_strategy) => diff_strategy.diff(old, new),
+            None => diff(old, new),
+        }
+    }
+
+    /// returns true if the DOM of the `new_vdom` is to be updated in slices
+    pub(crate) fn should_slice(&self, new_vdom: &crate::Node<MSG>) -> bool {
+        match self.time_slicing {
+            Some(node_count) => count_nodes(new_vdom, node_count) >= node_count,
+            None => false,
+        }
+    }
+
+    /// mark the view as changed while the update of the DOM is being applied in slices,
+    /// so the view is updated again once it is done
+    pub(crate) fn mark_stale(&mut self) {
+        self.is_stale = true;
+    }
+
+    /// returns true if the view has changed while the DOM is updated in slices,
+    /// then reset it
+    pub(crate) fn take_stale(&mut self) -> bool {
+        std::mem::replace(&mut self.is_stale, false)
+    }
+
+    /// take out the current vdom and the state of the DOM for the update in slices,
+    /// which owns them until it is done
+    fn start_slicing(&mut self) -> (crate::Node<MSG>, SlicedState) {
+        self.is_updating_in_slices = true;
+        let old_vdom = std::mem::replace(
+            &mut self.current_vdom,
+            crate::Node::Comment(String::new()),
+        );
+        let state = SlicedState {
+            root_node: self.root_node.clone(),
+            active_closures: std::mem::take(&mut self.active_closures),
+            focused_node: self.focused_node.take(),
+            delegation_registry: self.delegation_registry(),
+        };
+        (old_vdom, state)
+    }
+
+    /// put back the state of the DOM after the update in slices, along with its `vdom`
+    fn finish_slicing(&mut self, state: SlicedState, vdom: crate::Node<MSG>) {
+        self.current_vdom = vdom;
+        self.root_node = state.root_node;
+        self.active_closures = state.active_closures;
+        self.focused_node = state.focused_node;
+        self.is_updating_in_slices = false;
+        self.set_focus_element();
+        self.attach_delegated_root_listeners();
     }
 
     /// Diff the current virtual dom with the new virtual dom that is being passed in.
@@ -147,54 +435,95 @@ where
     /// Then use that diff to patch the real DOM in the user's browser so that they are
     /// seeing the latest state of the application.
     ///
-    /// Return the total number of patches applied
+    /// Return the total number of patches applied.
+    ///
+    /// When a patch fails, the current vdom is kept and the DOM may be left partially patched,
+    /// which can be recovered with [`rebuild_dom`](Self::rebuild_dom).
     pub fn update_dom<DSP>(
         &mut self,
         program: &DSP,
         new_vdom: crate::Node<MSG>,
-    ) -> usize
+    ) -> Result<usize, DomError>
     where
         DSP: Dispatch<MSG> + Clone + 'static,
     {
-        let patches = diff(&self.current_vdom, &new_vdom);
+        let patches = self.diff(&self.current_vdom, &new_vdom);
         let total_patches = patches.len();
 
         #[cfg(feature = "with-debug")]
         log::debug!("patches: {:#?}", patches);
 
-        let active_closures = patch(
-            program,
-            &mut self.root_node,
+        #[cfg(feature = "with-debug")]
+        crate::html::a11y::log_warnings(&new_vdom);
+
+        #[cfg(feature = "with-devtools")]
+        crate::dom::devtools::publish_patches(&patches);
+
+        // the caret position is lost when the value of the focused input is patched
+        let saved_selection = SavedSelection::save();
+        let group_positions = transition_group::record(&self.root_node);
+
+        let registry = self.delegation_registry();
+        let current_vdom = &self.current_vdom;
+        let root_node = &mut self.root_node;
+        let old_closures = &mut self.active_closures;
+        let focused_node = &mut self.focused_node;
+        let active_closures =
+            event_delegation::with_delegation(registry, || {
+                patch_with_old_vdom(
+                    program,
+                    current_vdom,
+                    root_node,
+                    old_closures,
+                    focused_node,
+                    patches,
+                )
+            })
+            .map_err(DomError::patch)?;
+
+        created_node::merge_closures(
             &mut self.active_closures,
-            &mut self.focused_node,
-            patches,
-        )
-        .expect("Error in patching the dom");
+            active_closures,
+        );
 
-        self.active_closures.extend(active_closures);
+        if let Some(saved_selection) = saved_selection {
+            saved_selection.restore();
+        }
+        transition_group::play(group_positions);
 
         self.current_vdom = new_vdom;
         self.set_focus_element();
+        self.attach_delegated_root_listeners();
         //return the total number of patches
-        total_patches
+        Ok(total_patches)
     }
 
     /// Apply patches blindly to the `root_node` in this DomUpdater.
     ///
     /// Warning: only used this for debugging purposes
-    pub fn patch_dom<DSP>(&mut self, program: &DSP, patches: Vec<Patch<MSG>>)
+    pub fn patch_dom<DSP>(
+        &mut self,
+        program: &DSP,
+        patches: Vec<Patch<MSG>>,
+    ) -> Result<(), DomError>
     where
         DSP: Dispatch<MSG> + Clone + 'static,
     {
-        let active_closures = patch(
-            program,
-            &mut self.root_node,
+        let registry = self.delegation_registry();
+        let root_node = &mut self.root_node;
+        let old_closures = &mut self.active_closures;
+        let focused_node = &mut self.focused_node;
+        let active_closures =
+            event_delegation::with_delegation(registry, || {
+                patch(program, root_node, old_closures, focused_node, patches)
+            })
+            .map_err(DomError::patch)?;
+        created_node::merge_closures(
             &mut self.active_closures,
-            &mut self.focused_node,
-            patches,
-        )
-        .expect("Error in patching the dom");
-        self.active_closures.extend(active_closures);
+            active_closures,
+        );
+        self.attach_delegated_root_listeners();
+        Ok(())
     }
 
     /// Return the root node of your application, the highest ancestor of all other nodes in
@@ -204,4 +533,231 @@ where
         // So we're effectively cloning a pointer here, which is fast.
         self.root_node.clone()
     }
+
+    /// dispatch the `unmount` event to the elements of this view which listen to it,
+    /// before the view is removed from the document
+    pub(crate) fn dispatch_unmount_events(&self) {
+        if self.root_node.node_type() != Node::ELEMENT_NODE {
+            return;
+        }
+        if let Err(e) = apply_patches::dispatch_unmount_events(
+            self.root_node.unchecked_ref(),
+            &self.active_closures,
+        ) {
+            log::error!("unable to dispatch the unmount events: {:?}", e);
+        }
+    }
+
+    /// The path of the nodes of this view whose listeners handled the `event`, in the order
+    /// the listeners are called, which is from the inner to the outer nodes as the event bubbles.
+    ///
+    /// This is both for the delegated listeners and the listeners attached to the elements,
+    /// the nodes outside of this view are not included.
+    pub fn handled_by(&self, event: &web_sys::Event) -> Vec<TreePath> {
+        let mut paths: Vec<TreePath> = vec![];
+        for node in event_delegation::handled_nodes(event) {
+            if let Some(path) =
+                event_delegation::tree_path(&self.root_node, &node)
+            {
+                let path = TreePath::new(path);
+                // an element with multiple listeners of the same event is recorded once
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths
+    }
+}
+
+impl<MSG> Drop for DomUpdater<MSG> {
+    /// the listeners of the delegated events are attached to the root node which outlives
+    /// this DomUpdater, so they are removed along with it
+    fn drop(&mut self) {
+        self.remove_delegated_root_listeners();
+    }
+}
+
+/// the state of the DOM which is owned by the update in slices while it is in progress
+struct SlicedState {
+    root_node: Node,
+    active_closures: ActiveClosure,
+    focused_node: Option<Node>,
+    delegation_registry: Option<event_delegation::Registry>,
+}
+
+/// Diff the current vdom with the `new_vdom`, then apply the patches in slices of
+/// [`SLICE_BUDGET`] milliseconds, yielding to the browser until it is idle in between.
+///
+/// The current vdom is swapped with the `new_vdom` only after all of the patches are applied.
+/// When a patch fails the current vdom is kept, the same as [`DomUpdater::update_dom`].
+///
+/// The diff itself is computed at once before the first slice, only the patches are sliced,
+/// since creating and patching the DOM nodes is where most of the time goes.
+pub(crate) async fn update_dom_in_slices<DSP, MSG>(
+    dom_updater: &RefCell<DomUpdater<MSG>>,
+    program: &DSP,
+    new_vdom: crate::Node<MSG>,
+) -> Result<usize, DomError>
+where
+    MSG: 'static,
+    DSP: Dispatch<MSG> + Clone + 'static,
+{
+    let (old_vdom, mut state) = dom_updater.borrow_mut().start_slicing();
+    // the writes queued by the cmds are executed after the last slice of the patches
+    scheduler::hold_writes();
+    let patches = dom_updater.borrow().diff(&old_vdom, &new_vdom);
+    let total_patches = patches.len();
+
+    #[cfg(feature = "with-debug")]
+    crate::html::a11y::log_warnings(&new_vdom);
+
+    #[cfg(feature = "with-devtools")]
+    crate::dom::devtools::publish_patches(&patches);
+
+    let group_positions = transition_group::record(&state.root_node);
+    let mut operations =
+        match PatchOperations::new(Some(&old_vdom), &state.root_node, &patches)
+        {
+            Ok(operations) => operations,
+            Err(e) => {
+                drop(patches);
+                dom_updater.borrow_mut().finish_slicing(state, old_vdom);
+                scheduler::release_writes();
+                return Err(DomError::patch(e));
+            }
+        };
+    let mut active_closures = ActiveClosure::new();
+    let result = 'slices: loop {
+        let slice_start = crate::now();
+        let saved_selection = SavedSelection::save();
+        let mut is_done = false;
+        while crate::now() - slice_start < SLICE_BUDGET {
+            let applied = event_delegation::with_delegation(
+                state.delegation_registry.clone(),
+                || {
+                    operations.apply_next(
+                        program,
+                        &mut state.root_node,
+                        &mut state.active_closures,
+                        &mut state.focused_node,
+                    )
+                },
+            );
+            match applied {
+                Some(Ok(new_closures)) => created_node::merge_closures(
+                    &mut active_closures,
+                    new_closures,
+                ),
+                Some(Err(e)) => break 'slices Err(DomError::patch(e)),
+                None => {
+                    is_done = true;
+                    break;
+                }
+            }
+        }
+        if let Some(saved_selection) = saved_selection {
+            saved_selection.restore();
+        }
+        if is_done {
+            break Ok(total_patches);
+        }
+        crate::dom::util::idle().await;
+    };
+    drop(operations);
+    drop(patches);
+    if result.is_ok() {
+        transition_group::play(group_positions);
+    }
+
+    created_node::merge_closures(&mut state.active_closures, active_closures);
+    let vdom = if result.is_ok() { new_vdom } else { old_vdom };
+    dom_updater.borrow_mut().finish_slicing(state, vdom);
+    scheduler::release_writes();
+    result
+}
+
+/// count the nodes of the `node`, stopping once it reaches the `limit`
+fn count_nodes<MSG>(node: &crate::Node<MSG>, limit: usize) -> usize {
+    let mut count = 0;
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        count += 1;
+        if count >= limit {
+            break;
+        }
+        if let crate::Node::Element(element) = node {
+            stack.extend(element.children.iter());
+        }
+    }
+    count
+}
+
+/// The selection of the focused input or textarea
+struct SavedSelection {
+    element: Element,
+    start: u32,
+    end: u32,
+}
+
+impl SavedSelection {
+    /// save the selection of the focused element, if it is an input or textarea.
+    fn save() -> Option<Self> {
+        let element = crate::document().active_element()?;
+        let (start, end) = if let Some(input) =
+            element.dyn_ref::<HtmlInputElement>()
+        {
+            // input types such as `number` and `email` doesn't support selection
+            (
+                input.selection_start().ok().flatten()?,
+                input.selection_end().ok().flatten()?,
+            )
+        } else if let Some(textarea) = element.dyn_ref::<HtmlTextAreaElement>()
+        {
+            (
+                textarea.selection_start().ok().flatten()?,
+                textarea.selection_end().ok().flatten()?,
+            )
+        } else {
+            return None;
+        };
+        Some(SavedSelection {
+            element,
+            start,
+            end,
+        })
+    }
+
+    /// restore the selection if the element still has the focus
+    /// and the selection has been moved by the patch.
+    fn restore(self) {
+        let is_still_focused = crate::document()
+            .active_element()
+            .map(|active| active == self.element)
+            .unwrap_or(false);
+        if !is_still_focused {
+            return;
+        }
+        if let Some(input) = self.element.dyn_ref::<HtmlInputElement>() {
+            let len = input.value().encode_utf16().count() as u32;
+            if input.selection_start().ok().flatten() != Some(self.start)
+                || input.selection_end().ok().flatten() != Some(self.end)
+            {
+                input
+                    .set_selection_range(self.start.min(len), self.end.min(len))
+                    .ok();
+            }
+        } else if let Some(textarea) =
+            self.element.dyn_ref::<HtmlTextAreaElement>()
+        {
+            let len = textarea.value().encode_utf16().count() as u32;
+            if textarea.selection_start().ok().flatten() != Some(self.start)
+                || textarea.selection_end().ok().flatten() != Some(self.end)
+            {
+                textarea
+                    .set_selection_range(self.start.min(len), self.end.min(len))
+                    .ok();
+            }
+        }
+    }
 }
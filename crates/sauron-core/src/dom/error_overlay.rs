@@ -0,0 +1,113 @@
+//! provides the error overlay which is shown when the app panics, enabled with the
+//! `with-error-overlay` feature and installed with
+//! [`Program::install_error_overlay`](crate::Program::install_error_overlay).
+//!
+//! The overlay shows the panic message and the last few msgs dispatched into the app,
+//! instead of leaving the page frozen with no feedback. This is meant for development only,
+//! the feature should not be enabled in the release builds.
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    panic::{self, PanicInfo},
+};
+
+/// the id of the overlay element
+pub const OVERLAY_ID: &str = "sauron-error-overlay";
+
+/// the number of the recent msgs shown in the overlay
+pub const MAX_RECENT_MSGS: usize = 10;
+
+thread_local!(static IS_INSTALLED: Cell<bool> = Cell::new(false));
+thread_local!(static RECENT_MSGS: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new()));
+
+/// install the panic hook which shows the overlay, this is done only once.
+/// The previous panic hook is still called after the overlay is shown.
+pub(crate) fn install() {
+    if IS_INSTALLED.with(|is_installed| is_installed.replace(true)) {
+        return;
+    }
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        show_overlay(info);
+        previous_hook(info);
+    }));
+}
+
+/// record the msg dispatched into the app, only the last [`MAX_RECENT_MSGS`] are kept
+pub(crate) fn record_msg(msg: String) {
+    RECENT_MSGS.with(|recent_msgs| {
+        // the panic could have happened while the msgs are being recorded
+        if let Ok(mut recent_msgs) = recent_msgs.try_borrow_mut() {
+            if recent_msgs.len() == MAX_RECENT_MSGS {
+                recent_msgs.pop_front();
+            }
+            recent_msgs.push_back(msg);
+        }
+    });
+}
+
+/// the recent msgs dispatched into the app, the latest msg is last
+pub fn recent_msgs() -> Vec<String> {
+    RECENT_MSGS.with(|recent_msgs| {
+        recent_msgs
+            .try_borrow()
+            .map(|recent_msgs| recent_msgs.iter().cloned().collect())
+            .unwrap_or_default()
+    })
+}
+
+/// render the overlay using the DOM directly, since the state of the app
+/// can not be trusted after the panic
+fn show_overlay(info: &PanicInfo) {
+    let document = match web_sys::window().and_then(|window| window.document())
+    {
+        Some(document) => document,
+        None => return,
+    };
+    let body = match document.body() {
+        Some(body) => body,
+        None => return,
+    };
+    if let Some(overlay) = document.get_element_by_id(OVERLAY_ID) {
+        overlay.remove();
+    }
+    let overlay = match document.create_element("div") {
+        Ok(overlay) => overlay,
+        Err(_) => return,
+    };
+    overlay.set_id(OVERLAY_ID);
+    let _ = overlay.set_attribute("role", "alert");
+    let _ = overlay.set_attribute(
+        "style",
+        "position:fixed;inset:0;z-index:2147483647;overflow:auto;padding:2em;\
+         background:rgba(24,24,24,0.95);color:#e8e8e8;font-family:monospace;",
+    );
+
+    let heading =
+        "<h2 style=\"color:#ff6b6b;margin-top:0\">The app panicked</h2>";
+    let message = format!(
+        "<pre style=\"white-space:pre-wrap\">{}</pre>",
+        escape(&info.to_string())
+    );
+    let msgs: String = recent_msgs()
+        .iter()
+        .map(|msg| format!("<li><pre>{}</pre></li>", escape(msg)))
+        .collect();
+    let recent = if msgs.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "<h3>Last dispatched msgs, the latest is last</h3><ol>{}</ol>",
+            msgs
+        )
+    };
+    overlay.set_inner_html(&format!("{}{}{}", heading, message, recent));
+    let _ = body.append_child(&overlay);
+}
+
+/// escape the text to be included in the html of the overlay
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,287 @@
+//! Event delegation, where only one listener per event type is attached to the root node
+//! of the application instead of one closure per element per event.
+//!
+//! The listeners of each element are stored in the registry of its view, keyed by the element's
+//! `data-vdom-id`. When an event is triggered, the root listener walks from the event target
+//! up to the root, calling the listeners of each element it passes through.
+//!
+//! The ordering is the same as the native bubbling of the event:
+//! - the listeners of the inner elements are called before the listeners of the outer elements.
+//! - the listeners of the same element are called in the order they are declared in the view.
+//! - calling `stop_propagation` in a listener stops the walk after the listeners of its element,
+//!   and the event doesn't propagate further from the root.
+//!
+//! Note: the events which don't bubble, such as `focus`, are still listened directly on
+//! the element. When the delegation is enabled, all the listeners of the bubbling events of
+//! the created elements are delegated, so the delegated and the direct listeners of
+//! the same event are not interleaved.
+use crate::dom::created_node::DATA_VDOM_ID;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Element, Node};
+
+/// the property of the event which holds the elements whose listeners handled the event
+const HANDLED_BY: &str = "__sauron_handled_by";
+
+/// A type erased handler which already contains the listener and the program
+/// it will dispatch the msg into.
+pub(crate) type DelegatedHandler = Rc<dyn Fn(web_sys::Event)>;
+
+/// These events don't bubble, so they can not be delegated to the root node.
+/// Listeners to these events are still attached directly to the element.
+const NON_BUBBLING_EVENTS: [&str; 15] = [
+    "focus",
+    "blur",
+    "mouseenter",
+    "mouseleave",
+    "scroll",
+    "load",
+    "error",
+    "toggle",
+    "mount",
+    "unmount",
+    "resize",
+    "enterviewport",
+    "leaveviewport",
+    "resizeelement",
+    "clickoutside",
+];
+
+struct DelegatedListener {
+    /// the name of the event as declared in the attribute, ie: `enter`
+    name: &'static str,
+    /// the actual dom event type this listener is triggered, ie: `keypress`
+    event_type: &'static str,
+    handler: DelegatedHandler,
+}
+
+/// The listeners delegated to the root node of one view, keyed by the vdom-id of their element.
+///
+/// Each `DomUpdater` has its own registry, so the views mounted into the same parent
+/// only dispatch the events into the listeners of their own elements.
+#[derive(Default)]
+pub(crate) struct DelegatedListeners {
+    listeners: HashMap<usize, Vec<DelegatedListener>>,
+    /// the event types that has been delegated so far
+    event_types: BTreeSet<&'static str>,
+}
+
+/// the registry of the delegated listeners, shared with the listeners attached to the root node
+pub(crate) type Registry = Rc<RefCell<DelegatedListeners>>;
+
+thread_local!(static CURRENT_REGISTRY: RefCell<Option<Registry>> = RefCell::new(None));
+
+impl DelegatedListeners {
+    fn register(
+        &mut self,
+        vdom_id: usize,
+        name: &'static str,
+        event_type: &'static str,
+        handler: DelegatedHandler,
+    ) {
+        self.event_types.insert(event_type);
+        self.listeners.entry(vdom_id).or_insert_with(Vec::new).push(
+            DelegatedListener {
+                name,
+                event_type,
+                handler,
+            },
+        );
+    }
+
+    fn unregister(&mut self, vdom_id: usize) {
+        self.listeners.remove(&vdom_id);
+    }
+
+    fn unregister_event(&mut self, vdom_id: usize, name: &'static str) {
+        if let Some(element_listeners) = self.listeners.get_mut(&vdom_id) {
+            element_listeners.retain(|listener| listener.name != name);
+            if element_listeners.is_empty() {
+                self.listeners.remove(&vdom_id);
+            }
+        }
+    }
+
+    /// the event types that has been delegated so far
+    pub(crate) fn event_types(&self) -> Vec<&'static str> {
+        self.event_types.iter().copied().collect()
+    }
+
+    /// the total number of elements that has delegated listeners
+    pub(crate) fn len(&self) -> usize {
+        self.listeners.len()
+    }
+
+    fn get_handlers(
+        &self,
+        vdom_id: usize,
+        event_type: &str,
+    ) -> Vec<DelegatedHandler> {
+        self.listeners
+            .get(&vdom_id)
+            .map(|element_listeners| {
+                element_listeners
+                    .iter()
+                    .filter(|listener| listener.event_type == event_type)
+                    .map(|listener| Rc::clone(&listener.handler))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// returns true if the nodes that are currently being created should have their
+/// listeners delegated to the root node
+pub(crate) fn is_delegating() -> bool {
+    CURRENT_REGISTRY.with(|registry| registry.borrow().is_some())
+}
+
+/// execute `f` with the listeners of the created nodes delegated into the `registry`,
+/// or attached to the elements when there is none. The previous registry is restored after.
+pub(crate) fn with_delegation<F, R>(registry: Option<Registry>, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let prev = CURRENT_REGISTRY.with(|current| current.replace(registry));
+    let ret = f();
+    CURRENT_REGISTRY.with(|current| current.replace(prev));
+    ret
+}
+
+/// execute `f` with the registry the listeners are currently delegated into, if any
+fn with_current_registry(f: impl FnOnce(&mut DelegatedListeners)) {
+    CURRENT_REGISTRY.with(|current| {
+        if let Some(registry) = current.borrow().as_ref() {
+            f(&mut registry.borrow_mut());
+        }
+    });
+}
+
+/// returns true if the listener for this event can be attached to the root node
+pub(crate) fn is_delegatable(event_name: &str) -> bool {
+    !NON_BUBBLING_EVENTS.contains(&event_name)
+}
+
+/// register the handler of the element with `vdom_id` into the current registry
+pub(crate) fn register(
+    vdom_id: usize,
+    name: &'static str,
+    event_type: &'static str,
+    handler: DelegatedHandler,
+) {
+    with_current_registry(|registry| {
+        registry.register(vdom_id, name, event_type, handler)
+    });
+}
+
+/// remove all the delegated listeners of the element with `vdom_id` from the current registry
+pub(crate) fn unregister(vdom_id: usize) {
+    with_current_registry(|registry| registry.unregister(vdom_id));
+}
+
+/// remove the delegated listener with the event name of the element with `vdom_id`
+/// from the current registry
+pub(crate) fn unregister_event(vdom_id: usize, name: &'static str) {
+    with_current_registry(|registry| registry.unregister_event(vdom_id, name));
+}
+
+/// This is called by the root listener.
+/// Walk from the event target up to the `root`, calling the handlers in the `registry`
+/// of the elements along the way, simulating the bubbling of the event.
+pub(crate) fn dispatch_event(
+    root: &Node,
+    registry: &Registry,
+    event: &web_sys::Event,
+) {
+    let event_type = event.type_();
+    let mut current: Option<Element> = event
+        .target()
+        .and_then(|target| target.dyn_into::<Node>().ok())
+        .and_then(|node| {
+            if node.node_type() == Node::ELEMENT_NODE {
+                Some(node.unchecked_into())
+            } else {
+                node.parent_element()
+            }
+        });
+
+    while let Some(element) = current {
+        if let Some(vdom_id) = element
+            .get_attribute(DATA_VDOM_ID)
+            .and_then(|vdom_id| vdom_id.parse::<usize>().ok())
+        {
+            // the handlers are cloned out of the registry before calling them,
+            // since a dispatch could add or remove listeners of the registry
+            let handlers = registry.borrow().get_handlers(vdom_id, &event_type);
+            if !handlers.is_empty() {
+                record_handled(event, &element);
+            }
+            for handler in handlers {
+                handler(event.clone());
+            }
+            if event.cancel_bubble() {
+                break;
+            }
+        }
+        let node: &Node = element.unchecked_ref();
+        if node.is_same_node(Some(root)) {
+            break;
+        }
+        current = element.parent_element();
+    }
+}
+
+/// record the element whose listener is handling the event
+pub(crate) fn record_handled(event: &web_sys::Event, element: &JsValue) {
+    let key = JsValue::from_str(HANDLED_BY);
+    let handled_by = match js_sys::Reflect::get(event, &key) {
+        Ok(handled_by) if handled_by.is_instance_of::<js_sys::Array>() => {
+            handled_by.unchecked_into()
+        }
+        _ => {
+            let handled_by = js_sys::Array::new();
+            if let Err(e) = js_sys::Reflect::set(event, &key, &handled_by) {
+                log::warn!("unable to record the handled event: {:?}", e);
+            }
+            handled_by
+        }
+    };
+    handled_by.push(element);
+}
+
+/// the nodes whose listeners handled the event, in the order they are called
+pub(crate) fn handled_nodes(event: &web_sys::Event) -> Vec<Node> {
+    js_sys::Reflect::get(event, &JsValue::from_str(HANDLED_BY))
+        .ok()
+        .filter(|handled_by| handled_by.is_instance_of::<js_sys::Array>())
+        .map(|handled_by| {
+            js_sys::Array::from(&handled_by)
+                .iter()
+                .filter_map(|node| node.dyn_into::<Node>().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// the path of the `node` from the `root`, which starts with the `0` of the root itself
+/// followed by the index of the child nodes.
+/// Returns `None` if the node is not inside the root.
+pub(crate) fn tree_path(root: &Node, node: &Node) -> Option<Vec<usize>> {
+    let mut path = vec![];
+    let mut current = node.clone();
+    while !current.is_same_node(Some(root)) {
+        let mut index = 0;
+        let mut sibling = current.previous_sibling();
+        while let Some(prev) = sibling {
+            index += 1;
+            sibling = prev.previous_sibling();
+        }
+        path.push(index);
+        current = current.parent_node()?;
+    }
+    path.push(0);
+    path.reverse();
+    Some(path)
+}
@@ -0,0 +1,204 @@
+//! provides the debounce and the throttle of the event listeners, so the events which fires
+//! in quick succession such as the keystrokes of a search input don't flood the `update`
+//! and diff the view on every event, and the urgent listeners which are processed ahead of the other msgs.
+//!
+//! # Examples
+//! ```rust
+//! use sauron::prelude::*;
+//!
+//! enum Msg {
+//!     Search(String),
+//!     Scrolled(i32),
+//!     KeyPressed,
+//! }
+//!
+//! let search: Node<Msg> = input(
+//!     vec![
+//!         r#type("search"),
+//!         on_input(|event: InputEvent| Msg::Search(event.value)).debounce(300),
+//!         on_keydown(|_| Msg::KeyPressed).urgent(),
+//!     ],
+//!     vec![],
+//! );
+//! let feed: Node<Msg> = div(
+//!     vec![on_scroll(|(scroll_top, _)| Msg::Scrolled(scroll_top)).throttle(100)],
+//!     vec![],
+//! );
+//! ```
+use crate::{
+    dom::{timer, Dispatch},
+    html::attributes::AttributeValue,
+    Attribute,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// how the msgs of the listeners of an attribute are dispatched
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Timing {
+    /// dispatch only the last msg, once no event has fired for this many milliseconds
+    Debounce(i32),
+    /// dispatch the msg right away, then at most one msg for each of this many milliseconds,
+    /// which is the last msg of that period
+    Throttle(i32),
+    /// dispatch the msg right away with [`Dispatch::dispatch_urgent`]
+    Urgent,
+}
+
+/// Limit how often the msgs of the event listeners of this attribute are dispatched.
+///
+/// The msg is created from the event as soon as the event fires, so the values read from the event
+/// are of that moment, only the dispatch is delayed. The timers are kept per element,
+/// and are cancelled when the element is removed.
+pub trait EventTiming {
+    /// dispatch only the last msg, once no event has fired for `ms` milliseconds
+    fn debounce(self, ms: i32) -> Self;
+
+    /// dispatch the first msg right away, then at most one msg every `ms` milliseconds,
+    /// the last msg is dispatched at the end of the period so it is never lost
+    fn throttle(self, ms: i32) -> Self;
+
+    /// dispatch the msg with [`Dispatch::dispatch_urgent`], so it is processed in the current frame
+    /// ahead of the other msgs, ie: the keystrokes of a text input
+    fn urgent(self) -> Self;
+}
+
+impl<MSG> EventTiming for Attribute<MSG> {
+    fn debounce(mut self, ms: i32) -> Self {
+        self.value
+            .push(AttributeValue::from_custom(Timing::Debounce(ms.max(0))));
+        self
+    }
+
+    fn throttle(mut self, ms: i32) -> Self {
+        self.value
+            .push(AttributeValue::from_custom(Timing::Throttle(ms.max(0))));
+        self
+    }
+
+    fn urgent(mut self) -> Self {
+        self.value.push(AttributeValue::from_custom(Timing::Urgent));
+        self
+    }
+}
+
+/// the timing of the listeners of the attribute, if it is debounced or throttled
+pub(crate) fn timing_of<MSG>(attr: &Attribute<MSG>) -> Option<Timing> {
+    attr.value()
+        .iter()
+        .find_map(|value| value.as_custom::<Timing>())
+        .copied()
+}
+
+/// A function which dispatch the msgs into the `program` with the `timing`.
+///
+/// Each of the returned functions has its own timer, which is cancelled when the function is dropped.
+pub(crate) fn dispatcher<DSP, MSG>(
+    program: &DSP,
+    timing: Option<Timing>,
+) -> Rc<dyn Fn(MSG)>
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let program = program.clone();
+    let timing = match timing {
+        Some(Timing::Urgent) => {
+            return Rc::new(move |msg| program.dispatch_urgent(msg))
+        }
+        Some(timing) => timing,
+        None => return Rc::new(move |msg| program.dispatch(msg)),
+    };
+    let limiter = Rc::new(Limiter {
+        timing,
+        pending: RefCell::new(None),
+        timeout_id: Cell::new(None),
+        last_dispatched: Cell::new(None),
+        callback: RefCell::new(None),
+    });
+    let weak_limiter: Weak<Limiter<MSG>> = Rc::downgrade(&limiter);
+    let program_clone = program.clone();
+    *limiter.callback.borrow_mut() = Some(Closure::wrap(Box::new(move || {
+        if let Some(limiter) = weak_limiter.upgrade() {
+            limiter.timeout_id.set(None);
+            let pending = limiter.pending.borrow_mut().take();
+            if let Some(msg) = pending {
+                limiter.last_dispatched.set(Some(timer::now()));
+                program_clone.dispatch(msg);
+            }
+        }
+    })));
+    Rc::new(move |msg| {
+        if let Some(msg) = limiter.limit(msg) {
+            program.dispatch(msg);
+        }
+    })
+}
+
+/// the state of the timer of a debounced or throttled dispatcher
+struct Limiter<MSG> {
+    timing: Timing,
+    /// the msg which is dispatched when the timeout fires
+    pending: RefCell<Option<MSG>>,
+    timeout_id: Cell<Option<i32>>,
+    /// the time the last msg is dispatched, used in the throttle
+    last_dispatched: Cell<Option<f64>>,
+    /// dispatch the pending msg, this is called by the timeout
+    callback: RefCell<Option<Closure<dyn FnMut()>>>,
+}
+
+impl<MSG> Limiter<MSG> {
+    /// returns the msg if it is to be dispatched right away,
+    /// otherwise it is kept until the timeout fires
+    fn limit(&self, msg: MSG) -> Option<MSG> {
+        match self.timing {
+            Timing::Debounce(ms) => {
+                *self.pending.borrow_mut() = Some(msg);
+                if let Some(timeout_id) = self.timeout_id.take() {
+                    timer::clear_timeout(timeout_id);
+                }
+                self.start_timeout(ms);
+                None
+            }
+            Timing::Throttle(ms) => {
+                let now = timer::now();
+                let elapsed = self.last_dispatched.get().map(|last| now - last);
+                match elapsed {
+                    Some(elapsed) if elapsed < f64::from(ms) => {
+                        *self.pending.borrow_mut() = Some(msg);
+                        if self.timeout_id.get().is_none() {
+                            let remaining = f64::from(ms) - elapsed;
+                            self.start_timeout(remaining.ceil() as i32);
+                        }
+                        None
+                    }
+                    _ => {
+                        self.last_dispatched.set(Some(now));
+                        Some(msg)
+                    }
+                }
+            }
+            Timing::Urgent => Some(msg),
+        }
+    }
+
+    fn start_timeout(&self, ms: i32) {
+        if let Some(callback) = self.callback.borrow().as_ref() {
+            let timeout_id =
+                timer::set_timeout(callback.as_ref().unchecked_ref(), ms);
+            self.timeout_id.set(Some(timeout_id));
+        }
+    }
+}
+
+impl<MSG> Drop for Limiter<MSG> {
+    /// the pending msg is discarded along with the element of the listener
+    fn drop(&mut self) {
+        if let Some(timeout_id) = self.timeout_id.take() {
+            timer::clear_timeout(timeout_id);
+        }
+    }
+}
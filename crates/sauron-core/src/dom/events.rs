@@ -1,13 +1,32 @@
 //! Create [events][0] Object
 //!
+//! The typed events are split into groups which are enabled with their features,
+//! so apps only pay for the events they use:
+//! - `with-pointer-events` the mouse events, ie: `on_mousedown`, except for [`on_click`]
+//! - `with-keyboard-events` the key events, ie: `on_keydown`, except for [`on_enter`]
+//! - `with-media-events` the events of the audio and video elements, ie: `on_play`
+//! - `with-drag-events` the drag and drop events, ie: `on_drop`
+//! - `with-clipboard-events` the clipboard events, ie: `on_paste`,
+//!   which also needs the `web_sys_unstable_apis` cfg flag
+//!
+//! All of the groups except the clipboard events are enabled with the `with-events` feature,
+//! which is enabled by default. The clipboard events are opt-in, since they use unstable
+//! web-sys APIs: enable the `with-clipboard-events` feature and build with
+//! `RUSTFLAGS=--cfg=web_sys_unstable_apis`.
+//!
 //! [0]: https://developer.mozilla.org/en-US/docs/Web/Events
+use crate::dom::file::{self, FileHandle};
+use crate::dom::{click_outside, resize, viewport};
 use crate::html::attributes::AttributeValue;
 use crate::{Attribute, Listener};
-use wasm_bindgen::JsCast;
-#[cfg(web_sys_unstable_apis)]
+use wasm_bindgen::{JsCast, JsValue};
+#[cfg(all(web_sys_unstable_apis, feature = "with-clipboard-events"))]
 pub use web_sys::ClipboardEvent;
+#[cfg(feature = "with-drag-events")]
+pub use web_sys::DragEvent;
 pub use web_sys::{
-    AnimationEvent, HashChangeEvent, KeyboardEvent, MouseEvent, TransitionEvent,
+    AnimationEvent, HashChangeEvent, IntersectionObserverEntry, KeyboardEvent,
+    MouseEvent, TransitionEvent,
 };
 use web_sys::{EventTarget, HtmlInputElement, HtmlTextAreaElement};
 
@@ -101,6 +120,82 @@ where
     })
 }
 
+/// attach a change event to a file input, emitting the files selected by the user
+pub fn on_file_input<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(Vec<FileHandle>) -> MSG + 'static,
+    MSG: 'static,
+{
+    on("change", move |event: Event| {
+        let web_event = event.as_web().expect("must be a web event");
+        let target = web_event.target().expect("must have a target");
+        let files = target
+            .dyn_ref::<HtmlInputElement>()
+            .and_then(|input| input.files());
+        f(file::file_handles(files))
+    })
+}
+
+/// custom event which is triggered when the element becomes visible in the viewport,
+/// ie: to lazy load an image or to load the next page of an infinite scroll
+pub fn on_enter_viewport<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(IntersectionObserverEntry) -> MSG + 'static,
+    MSG: 'static,
+{
+    on(viewport::ENTER_VIEWPORT, move |event: Event| {
+        f(to_intersection_entry(event))
+    })
+}
+
+/// custom event which is triggered when the element which was visible
+/// is no longer visible in the viewport
+pub fn on_leave_viewport<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(IntersectionObserverEntry) -> MSG + 'static,
+    MSG: 'static,
+{
+    on(viewport::LEAVE_VIEWPORT, move |event: Event| {
+        f(to_intersection_entry(event))
+    })
+}
+
+/// custom event which is triggered when a click lands outside of the element and its descendants,
+/// ie: to close a dropdown or a popover
+pub fn on_click_outside<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(MouseEvent) -> MSG + 'static,
+    MSG: 'static,
+{
+    on(click_outside::CLICK_OUTSIDE, move |event: Event| {
+        let web_event = event.as_web().expect("must be a web_sys event");
+        let custom_event: &web_sys::CustomEvent =
+            web_event.dyn_ref().expect("unable to cast to custom event");
+        f(custom_event.detail().unchecked_into())
+    })
+}
+
+/// the size of the content box of an element, in css pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Size {
+    /// the width of the content box
+    pub width: f64,
+    /// the height of the content box
+    pub height: f64,
+}
+
+/// custom event which is triggered with the new size of the element whenever it changes,
+/// ie: to redraw a chart with the size of its container instead of the size of the window
+pub fn on_resize_element<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(Size) -> MSG + 'static,
+    MSG: 'static,
+{
+    on(resize::RESIZE_ELEMENT, move |event: Event| {
+        f(to_size(event))
+    })
+}
+
 /// an event when a virtual Node is mounted the field node is the actual
 /// dom node where the virtual Node is created in the actual dom
 #[derive(Debug, Clone, PartialEq)]
@@ -109,7 +204,8 @@ pub struct MountEvent {
     pub target_node: web_sys::Node,
 }
 
-/// custom mount event
+/// custom mount event, which is triggered when the element is created,
+/// ie: to initialize a chart, a map or an editor into the element
 pub fn on_mount<F, MSG>(f: F) -> Attribute<MSG>
 where
     F: Fn(MountEvent) -> MSG + 'static,
@@ -124,6 +220,23 @@ where
     })
 }
 
+/// custom unmount event, which is triggered right before the element is removed from the dom,
+/// including when one of its ancestors is removed,
+/// ie: to destroy the chart, the map or the editor initialized in [`on_mount`]
+pub fn on_unmount<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(MountEvent) -> MSG + 'static,
+    MSG: 'static,
+{
+    on("unmount", move |event: Event| {
+        let web_event = event.as_web().expect("must be a web event");
+        let target = web_event.target().expect("must have a target");
+        f(MountEvent {
+            target_node: target.unchecked_into(),
+        })
+    })
+}
+
 macro_rules! declare_events {
 
     ( $(
@@ -150,7 +263,9 @@ macro_rules! declare_events {
 }
 
 macro_rules! declare_html_events{
-    ( $(
+    ( $(#[$group_attr:meta])*
+      $group:ident;
+      $(
          $(#[$attr:meta])*
          $name:ident => $event:ident => $mapper:ident => $ret:ty;
        )*
@@ -162,8 +277,8 @@ macro_rules! declare_html_events{
             )*
         }
 
-        /// html events
-        pub const HTML_EVENTS: [&'static str; 34] = [$(stringify!($event),)*];
+        $(#[$group_attr])*
+        pub const $group: &[&str] = &[$(stringify!($event),)*];
     }
 }
 
@@ -194,6 +309,32 @@ fn to_transition_event(event: Event) -> TransitionEvent {
         .expect("unable to cast to transition event")
 }
 
+/// the entry of the observer is passed in the detail of the viewport events
+fn to_intersection_entry(event: Event) -> IntersectionObserverEntry {
+    let web_event = event.as_web().expect("must be a web_sys event");
+    let custom_event: &web_sys::CustomEvent =
+        web_event.dyn_ref().expect("unable to cast to custom event");
+    custom_event.detail().unchecked_into()
+}
+
+/// the content rect of the observer entry is passed in the detail of the resize event
+fn to_size(event: Event) -> Size {
+    let web_event = event.as_web().expect("must be a web_sys event");
+    let custom_event: &web_sys::CustomEvent =
+        web_event.dyn_ref().expect("unable to cast to custom event");
+    let content_rect = custom_event.detail();
+    let get = |name: &str| {
+        js_sys::Reflect::get(&content_rect, &JsValue::from_str(name))
+            .ok()
+            .and_then(|value| value.as_f64())
+            .unwrap_or(0.0)
+    };
+    Size {
+        width: get("width"),
+        height: get("height"),
+    }
+}
+
 fn to_webevent(event: Event) -> web_sys::Event {
     match event {
         Event::WebEvent(event) => event,
@@ -249,10 +390,19 @@ fn to_checked(event: Event) -> bool {
     }
 }
 
+#[cfg(feature = "with-drag-events")]
+fn to_drag_event(event: Event) -> DragEvent {
+    event
+        .as_web()
+        .expect("must be a web event")
+        .dyn_into()
+        .expect("unable to cast to drag event")
+}
+
 /// Note: paste event happens before the data is inserted into the target element
 /// therefore trying to access the data on the target element triggered from paste will get an
 /// empty text
-#[cfg(web_sys_unstable_apis)]
+#[cfg(all(web_sys_unstable_apis, feature = "with-clipboard-events"))]
 fn to_clipboard_event(event: Event) -> ClipboardEvent {
     event
         .as_web()
@@ -261,11 +411,60 @@ fn to_clipboard_event(event: Event) -> ClipboardEvent {
         .expect("unable to cast to clipboard event")
 }
 
-// Mouse events
+/// the content pasted by the user
+#[cfg(all(web_sys_unstable_apis, feature = "with-clipboard-events"))]
+#[derive(Debug)]
+pub struct PasteEvent {
+    /// the pasted text, `None` if there is no text, ie: only an image is pasted
+    pub text: Option<String>,
+    /// the pasted files, ie: the screenshot or the files copied from the file manager
+    pub files: Vec<FileHandle>,
+    /// the actual dom event
+    pub event: ClipboardEvent,
+}
+
+/// Note: the data is read from the event,
+/// since the paste event happens before the data is inserted into the target element
+#[cfg(all(web_sys_unstable_apis, feature = "with-clipboard-events"))]
+fn to_paste_event(event: Event) -> PasteEvent {
+    let event = to_clipboard_event(event);
+    let data = event.clipboard_data();
+    PasteEvent {
+        text: data
+            .as_ref()
+            .and_then(|data| data.get_data("text/plain").ok())
+            .filter(|text| !text.is_empty()),
+        files: file::file_handles(data.and_then(|data| data.files())),
+        event,
+    }
+}
+
 declare_html_events! {
-    on_auxclick => auxclick => to_mouse_event => MouseEvent;
+    /// the names of the html events which are always available
+    HTML_EVENTS;
     on_animationend => animationend => to_animation_event => AnimationEvent;
     on_transitionend => transitionend => to_transition_event => TransitionEvent;
+    on_popstate => popstate => to_webevent => web_sys::Event;
+    on_select => select => to_webevent => web_sys::Event;
+    on_toggle => toggle => to_webevent => web_sys::Event;
+    on_focus => focus => to_webevent => web_sys::Event;
+    on_blur => blur => to_webevent => web_sys::Event;
+    on_reset => reset => to_webevent => web_sys::Event;
+    on_submit => submit => to_webevent => web_sys::Event;
+    on_input => input => to_input_event => InputEvent;
+    on_checked => input => to_checked => bool;
+    on_change => change => to_input_event => InputEvent;
+    on_broadcast => broadcast => to_input_event => InputEvent;
+    on_hashchange => hashchange => to_hashchange_event => HashChangeEvent;
+    on_readystatechange => readystatechange => to_webevent => web_sys::Event;
+}
+
+// Mouse events
+#[cfg(feature = "with-pointer-events")]
+declare_html_events! {
+    /// the names of the mouse events
+    POINTER_EVENTS;
+    on_auxclick => auxclick => to_mouse_event => MouseEvent;
     on_contextmenu => contextmenu => to_mouse_event => MouseEvent;
     on_dblclick  => dblclick => to_mouse_event => MouseEvent;
     on_mousedown => mousedown => to_mouse_event => MouseEvent;
@@ -277,26 +476,62 @@ declare_html_events! {
     on_mouseup => mouseup => to_mouse_event => MouseEvent;
     on_pointerlockchange => pointerlockchange => to_mouse_event => MouseEvent;
     on_pointerlockerror => pointerlockerror => to_mouse_event => MouseEvent;
-    on_popstate => popstate => to_webevent => web_sys::Event;
-    on_select => select => to_webevent => web_sys::Event;
     on_wheel => wheel => to_mouse_event => MouseEvent;
     on_doubleclick => dblclick => to_mouse_event => MouseEvent;
+}
+
+// Keyboard events
+#[cfg(feature = "with-keyboard-events")]
+declare_html_events! {
+    /// the names of the keyboard events
+    KEYBOARD_EVENTS;
     on_keydown => keydown => to_keyboard_event => KeyboardEvent;
     on_keypress => keypress => to_keyboard_event => KeyboardEvent;
     on_keyup => keyup => to_keyboard_event => KeyboardEvent;
-    on_toggle => toggle => to_webevent => web_sys::Event;
-    on_focus => focus => to_webevent => web_sys::Event;
-    on_blur => blur => to_webevent => web_sys::Event;
-    on_reset => reset => to_webevent => web_sys::Event;
-    on_submit => submit => to_webevent => web_sys::Event;
-    on_input => input => to_input_event => InputEvent;
-    on_checked => input => to_checked => bool;
-    #[cfg(web_sys_unstable_apis)]
-    on_paste => paste => to_clipboard_event => ClipboardEvent;
-    #[cfg(web_sys_unstable_apis)]
+}
+
+// Media events
+#[cfg(feature = "with-media-events")]
+declare_html_events! {
+    /// the names of the events of the audio and video elements
+    MEDIA_EVENTS;
+    on_play => play => to_webevent => web_sys::Event;
+    on_playing => playing => to_webevent => web_sys::Event;
+    on_pause => pause => to_webevent => web_sys::Event;
+    on_ended => ended => to_webevent => web_sys::Event;
+    on_waiting => waiting => to_webevent => web_sys::Event;
+    on_seeking => seeking => to_webevent => web_sys::Event;
+    on_seeked => seeked => to_webevent => web_sys::Event;
+    on_timeupdate => timeupdate => to_webevent => web_sys::Event;
+    on_durationchange => durationchange => to_webevent => web_sys::Event;
+    on_ratechange => ratechange => to_webevent => web_sys::Event;
+    on_volumechange => volumechange => to_webevent => web_sys::Event;
+    on_loadedmetadata => loadedmetadata => to_webevent => web_sys::Event;
+    on_loadeddata => loadeddata => to_webevent => web_sys::Event;
+    on_canplay => canplay => to_webevent => web_sys::Event;
+    on_canplaythrough => canplaythrough => to_webevent => web_sys::Event;
+}
+
+// Drag and drop events
+#[cfg(feature = "with-drag-events")]
+declare_html_events! {
+    /// the names of the drag and drop events
+    DRAG_EVENTS;
+    on_drag => drag => to_drag_event => DragEvent;
+    on_dragstart => dragstart => to_drag_event => DragEvent;
+    on_dragend => dragend => to_drag_event => DragEvent;
+    on_dragenter => dragenter => to_drag_event => DragEvent;
+    on_dragleave => dragleave => to_drag_event => DragEvent;
+    on_dragover => dragover => to_drag_event => DragEvent;
+    on_drop => drop => to_drag_event => DragEvent;
+}
+
+// Clipboard events
+#[cfg(all(web_sys_unstable_apis, feature = "with-clipboard-events"))]
+declare_html_events! {
+    /// the names of the clipboard events
+    CLIPBOARD_EVENTS;
+    on_paste => paste => to_paste_event => PasteEvent;
     on_copy => copy => to_clipboard_event => ClipboardEvent;
-    on_change => change => to_input_event => InputEvent;
-    on_broadcast => broadcast => to_input_event => InputEvent;
-    on_hashchange => hashchange => to_hashchange_event => HashChangeEvent;
-    on_readystatechange => readystatechange => to_webevent => web_sys::Event;
+    on_cut => cut => to_clipboard_event => ClipboardEvent;
 }
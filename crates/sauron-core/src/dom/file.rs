@@ -0,0 +1,228 @@
+//! provides the [`FileHandle`] of the files selected by the user with [`on_file_input`],
+//! which are read or uploaded with the Cmds in this module.
+//!
+//! # Examples
+//! ```rust,ignore
+//! // in the view
+//! input(vec![r#type("file"), multiple(true), on_file_input(Msg::FilesSelected)], vec![])
+//!
+//! // in the update
+//! Msg::FilesSelected(files) => Cmd::upload_multipart("/api/upload", &files, Msg::Upload),
+//! Msg::Upload(UploadProgress::Sending(percent)) => {
+//!     self.progress = percent;
+//!     Cmd::none()
+//! }
+//! ```
+//!
+//! [`on_file_input`]: crate::dom::events::on_file_input
+use crate::dom::{cmd::Cmd, Dispatch};
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{FormData, ProgressEvent, XmlHttpRequest};
+
+/// the name of the multipart field of the files in [`Cmd::upload_multipart`]
+pub const UPLOAD_FIELD: &str = "files";
+
+/// Errors when reading or uploading the files
+#[derive(Debug, Error)]
+pub enum FileError {
+    /// the file can not be read, ie: it was deleted after it was selected
+    #[error("unable to read the file: {0}")]
+    Read(String),
+}
+
+impl From<JsValue> for FileError {
+    fn from(js_value: JsValue) -> Self {
+        FileError::Read(format!("{:?}", js_value))
+    }
+}
+
+/// A file selected by the user
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileHandle {
+    file: web_sys::File,
+}
+
+impl FileHandle {
+    /// the name of the file, without the path
+    pub fn name(&self) -> String {
+        self.file.name()
+    }
+
+    /// the size of the file in bytes
+    pub fn size(&self) -> f64 {
+        self.file.size()
+    }
+
+    /// the media type of the file, ie: `image/png`, empty if it can not be determined
+    pub fn mime_type(&self) -> String {
+        self.file.type_()
+    }
+
+    /// the last modified time of the file, in milliseconds since the unix epoch
+    pub fn last_modified(&self) -> f64 {
+        self.file.last_modified()
+    }
+
+    /// the underlying file
+    pub fn as_file(&self) -> &web_sys::File {
+        &self.file
+    }
+
+    async fn text(&self) -> Result<String, FileError> {
+        let text = JsFuture::from(self.file.text()).await?;
+        Ok(text.as_string().unwrap_or_default())
+    }
+
+    async fn bytes(&self) -> Result<Vec<u8>, FileError> {
+        let buffer = JsFuture::from(self.file.array_buffer()).await?;
+        Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+    }
+}
+
+impl From<web_sys::File> for FileHandle {
+    fn from(file: web_sys::File) -> Self {
+        FileHandle { file }
+    }
+}
+
+/// the files in the file list of an input element
+pub(crate) fn file_handles(
+    files: Option<web_sys::FileList>,
+) -> Vec<FileHandle> {
+    match files {
+        Some(files) => (0..files.length())
+            .filter_map(|index| files.item(index))
+            .map(FileHandle::from)
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// The progress of [`Cmd::upload_multipart`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum UploadProgress {
+    /// the percentage of the bytes sent so far, from `0.0` to `100.0`
+    Sending(f64),
+    /// the server responded, which may not be a successful status
+    Done {
+        /// the http status of the response
+        status: u16,
+        /// the body of the response
+        body: String,
+    },
+    /// the upload failed, in cases where a network is down, server is dead, etc.
+    Failed,
+}
+
+impl<DSP> Cmd<DSP>
+where
+    DSP: 'static,
+{
+    /// Creates a Cmd which reads the content of the file as text
+    pub fn read_file_text<F, MSG>(file: &FileHandle, f: F) -> Self
+    where
+        F: FnOnce(Result<String, FileError>) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        let file = file.clone();
+        Cmd::try_from_async(async move { file.text().await }, f)
+    }
+
+    /// Creates a Cmd which reads the content of the file as bytes
+    pub fn read_file_bytes<F, MSG>(file: &FileHandle, f: F) -> Self
+    where
+        F: FnOnce(Result<Vec<u8>, FileError>) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        let file = file.clone();
+        Cmd::try_from_async(async move { file.bytes().await }, f)
+    }
+
+    /// Creates a Cmd which posts the `files` to the `url` as `multipart/form-data`
+    /// in the [`UPLOAD_FIELD`], emitting the MSG as the upload progresses.
+    ///
+    /// This uses an `XMLHttpRequest` since the fetch api doesn't report the progress of the upload.
+    pub fn upload_multipart<F, MSG>(
+        url: &str,
+        files: &[FileHandle],
+        f: F,
+    ) -> Self
+    where
+        F: Fn(UploadProgress) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        let url = url.to_string();
+        let files = files.to_vec();
+        Cmd::new(move |program: DSP| {
+            let notify = Rc::new(move |progress: UploadProgress| {
+                program.dispatch(f(progress))
+            });
+            if let Err(e) = upload(&url, &files, Rc::clone(&notify)) {
+                log::error!("unable to upload the files: {:?}", e);
+                notify(UploadProgress::Failed);
+            }
+        })
+        .no_render()
+    }
+}
+
+fn upload(
+    url: &str,
+    files: &[FileHandle],
+    notify: Rc<dyn Fn(UploadProgress)>,
+) -> Result<(), JsValue> {
+    let form = FormData::new()?;
+    for file in files {
+        form.append_with_blob_and_filename(
+            UPLOAD_FIELD,
+            &file.file,
+            &file.name(),
+        )?;
+    }
+    let request = XmlHttpRequest::new()?;
+    request.open_with_async("POST", url, true)?;
+
+    let notify_progress = Rc::clone(&notify);
+    let on_progress: Closure<dyn FnMut(ProgressEvent)> =
+        Closure::wrap(Box::new(move |event: ProgressEvent| {
+            if event.length_computable() && event.total() > 0.0 {
+                notify_progress(UploadProgress::Sending(
+                    event.loaded() / event.total() * 100.0,
+                ));
+            }
+        }));
+    let loaded_request = request.clone();
+    let notify_load = Rc::clone(&notify);
+    let on_load: Closure<dyn FnMut(ProgressEvent)> =
+        Closure::wrap(Box::new(move |_event: ProgressEvent| {
+            notify_load(UploadProgress::Done {
+                status: loaded_request.status().unwrap_or(0),
+                body: loaded_request
+                    .response_text()
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default(),
+            });
+        }));
+    let on_error: Closure<dyn FnMut(ProgressEvent)> =
+        Closure::wrap(Box::new(move |_event: ProgressEvent| {
+            notify(UploadProgress::Failed);
+        }));
+    request
+        .upload()?
+        .set_onprogress(Some(on_progress.as_ref().unchecked_ref()));
+    request.set_onload(Some(on_load.as_ref().unchecked_ref()));
+    request.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+    request.send_with_opt_form_data(Some(&form))?;
+    // the closures are called until the request is done
+    on_progress.forget();
+    on_load.forget();
+    on_error.forget();
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! provides the [`focus_trap`] attribute which keeps the focus inside of an element,
+//! ie: a modal dialog, while it is in the document.
+//!
+//! The tab key of all the traps are handled by one listener on the document,
+//! which is installed when the first trap is created.
+//! When the focus is outside of the traps, ie: right after the modal is opened,
+//! the tab key moves the focus into the trap which is last in the document.
+use crate::{html::attributes::attr, Attribute};
+use std::cell::Cell;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Element, HtmlElement, KeyboardEvent};
+
+/// the attribute which marks the element as a focus trap
+pub(crate) const FOCUS_TRAP_ATTR: &str = "data-sauron-focus-trap";
+
+/// the elements which can be focused with the tab key
+const FOCUSABLE: &str = "a[href], area[href], button:not([disabled]), \
+    input:not([disabled]):not([type=\"hidden\"]), select:not([disabled]), \
+    textarea:not([disabled]), iframe, [contenteditable], \
+    [tabindex]:not([tabindex=\"-1\"])";
+
+thread_local!(static IS_INSTALLED: Cell<bool> = Cell::new(false));
+
+/// Keep the focus inside of the element, the tab key on the last focusable element
+/// moves the focus back to the first, and shift+tab on the first moves it to the last.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+///
+/// let modal: Node<()> = div(
+///     vec![class("modal"), attr("role", "dialog"), focus_trap()],
+///     vec![input(vec![autofocus(true)], vec![]), button(vec![], vec![text("Close")])],
+/// );
+/// ```
+pub fn focus_trap<MSG>() -> Attribute<MSG> {
+    attr(FOCUS_TRAP_ATTR, true)
+}
+
+/// install the listener which handles the tab key of the traps, this is done only once
+pub(crate) fn init() {
+    if IS_INSTALLED.with(|is_installed| is_installed.replace(true)) {
+        return;
+    }
+    let listener: Closure<dyn FnMut(KeyboardEvent)> =
+        Closure::wrap(Box::new(on_keydown));
+    crate::document()
+        .add_event_listener_with_callback_and_bool(
+            "keydown",
+            listener.as_ref().unchecked_ref(),
+            true,
+        )
+        .expect("must add the focus trap listener");
+    // the listener lives for the rest of the page lifetime
+    listener.forget();
+}
+
+fn on_keydown(event: KeyboardEvent) {
+    if event.key() != "Tab" {
+        return;
+    }
+    let trap = match active_trap() {
+        Some(trap) => trap,
+        None => return,
+    };
+    let focusables = focusable_elements(&trap);
+    let (first, last) = match (focusables.first(), focusables.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => {
+            // there is nothing to focus, so the focus stays where it is
+            event.prevent_default();
+            return;
+        }
+    };
+    let active = crate::document().active_element();
+    let index = active.and_then(|active| {
+        focusables
+            .iter()
+            .position(|focusable| **focusable == active)
+    });
+    let next = match (index, event.shift_key()) {
+        (None, false) => Some(first),
+        (None, true) => Some(last),
+        (Some(0), true) => Some(last),
+        (Some(index), false) if index == focusables.len() - 1 => Some(first),
+        // the browser moves the focus to the element next to it, which is inside of the trap
+        _ => None,
+    };
+    if let Some(next) = next {
+        event.prevent_default();
+        next.focus().expect("must focus");
+    }
+}
+
+/// the trap of the focused element, otherwise the trap which is last in the document,
+/// which is usually the modal opened last
+fn active_trap() -> Option<Element> {
+    let selector = format!("[{}]", FOCUS_TRAP_ATTR);
+    let document = crate::document();
+    document
+        .active_element()
+        .and_then(|active| active.closest(&selector).ok().flatten())
+        .or_else(|| {
+            let traps = document.query_selector_all(&selector).ok()?;
+            traps
+                .item(traps.length().checked_sub(1)?)
+                .map(|trap| trap.unchecked_into())
+        })
+}
+
+/// the visible elements inside of the trap which can be focused, in the tab order of the document
+fn focusable_elements(trap: &Element) -> Vec<HtmlElement> {
+    let elements = match trap.query_selector_all(FOCUSABLE) {
+        Ok(elements) => elements,
+        Err(_) => return vec![],
+    };
+    (0..elements.length())
+        .filter_map(|index| elements.item(index))
+        .map(|element| element.unchecked_into::<HtmlElement>())
+        .filter(|element| element.get_client_rects().length() > 0)
+        .collect()
+}
@@ -0,0 +1,249 @@
+//! provides a canvas node for WebGL/WebGPU rendering which coexists with the vdom lifecycle.
+//!
+use crate::{
+    dom::{
+        capabilities,
+        events::{on, on_mount},
+        sub::AnimationFrameLoop,
+    },
+    html::tags::canvas,
+    Attribute, Event, Node,
+};
+use js_sys::{Array, Function, Reflect};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::HtmlCanvasElement;
+
+/// the name of the event dispatched on the canvas when its drawing buffer is resized
+const RESIZE_EVENT: &str = "gpucanvasresize";
+
+/// Renders into the canvas created with [`gpu_canvas`] using WebGL or WebGPU (ie: wgpu, three-d)
+pub trait GpuRenderer {
+    /// called when the canvas is mounted, this is where the rendering context is created
+    fn init(&mut self, canvas: &HtmlCanvasElement);
+
+    /// render the frame, called on every animation frame while the context is not lost
+    fn render(&mut self, timestamp: f64);
+
+    /// the drawing buffer of the canvas has been resized to `width` and `height` in physical pixels
+    fn resize(&mut self, _width: u32, _height: u32) {}
+
+    /// the rendering context is lost, the gpu resources should be dropped
+    fn context_lost(&mut self) {}
+
+    /// the rendering context is restored, the gpu resources should be recreated
+    fn context_restored(&mut self, canvas: &HtmlCanvasElement) {
+        self.init(canvas);
+    }
+}
+
+/// The lifecycle events of the [`gpu_canvas`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GpuCanvasEvent {
+    /// the canvas is mounted and the renderer has been initialized
+    Mounted,
+    /// the drawing buffer of the canvas has been resized, in physical pixels
+    Resized {
+        /// the width of the drawing buffer
+        width: u32,
+        /// the height of the drawing buffer
+        height: u32,
+    },
+    /// the rendering context is lost
+    ContextLost,
+    /// the rendering context is restored
+    ContextRestored,
+}
+
+/// Creates a canvas which is rendered by the `renderer` on every animation frame,
+/// sharing the frames with the rendering of the view.
+///
+/// The drawing buffer of the canvas follows the size of the canvas multiplied by the device pixel ratio.
+/// The lifecycle events of the canvas are dispatched as the MSG created with `on_event`.
+/// The render loop stops when the canvas is removed from the document.
+pub fn gpu_canvas<MSG, R, F>(
+    attrs: impl IntoIterator<Item = Attribute<MSG>>,
+    renderer: R,
+    on_event: F,
+) -> Node<MSG>
+where
+    MSG: 'static,
+    R: GpuRenderer + 'static,
+    F: Fn(GpuCanvasEvent) -> MSG + 'static,
+{
+    let renderer = Rc::new(RefCell::new(renderer));
+    let on_event = Rc::new(on_event);
+    let is_context_lost = Rc::new(Cell::new(false));
+
+    let mount_listener = {
+        let renderer = Rc::clone(&renderer);
+        let on_event = Rc::clone(&on_event);
+        let is_context_lost = Rc::clone(&is_context_lost);
+        on_mount(move |me| {
+            let canvas: HtmlCanvasElement = me.target_node.unchecked_into();
+            resize_drawing_buffer(&canvas);
+            renderer.borrow_mut().init(&canvas);
+            start(&canvas, &renderer, &is_context_lost);
+            on_event(GpuCanvasEvent::Mounted)
+        })
+    };
+
+    let resize_listener = {
+        let renderer = Rc::clone(&renderer);
+        let on_event = Rc::clone(&on_event);
+        on(RESIZE_EVENT, move |event: Event| {
+            let canvas = event_canvas(event);
+            let (width, height) = (canvas.width(), canvas.height());
+            renderer.borrow_mut().resize(width, height);
+            on_event(GpuCanvasEvent::Resized { width, height })
+        })
+    };
+
+    let context_lost_listener = {
+        let renderer = Rc::clone(&renderer);
+        let on_event = Rc::clone(&on_event);
+        let is_context_lost = Rc::clone(&is_context_lost);
+        on("webglcontextlost", move |event: Event| {
+            // tell the browser that the context is expected to be restored
+            if let Some(web_event) = event.as_web() {
+                web_event.prevent_default();
+            }
+            is_context_lost.set(true);
+            renderer.borrow_mut().context_lost();
+            on_event(GpuCanvasEvent::ContextLost)
+        })
+    };
+
+    let context_restored_listener =
+        on("webglcontextrestored", move |event: Event| {
+            let canvas = event_canvas(event);
+            renderer.borrow_mut().context_restored(&canvas);
+            is_context_lost.set(false);
+            on_event(GpuCanvasEvent::ContextRestored)
+        });
+
+    canvas(
+        attrs.into_iter().chain(vec![
+            mount_listener,
+            resize_listener,
+            context_lost_listener,
+            context_restored_listener,
+        ]),
+        vec![],
+    )
+}
+
+/// the canvas which is the target of the event
+fn event_canvas(event: Event) -> HtmlCanvasElement {
+    event
+        .as_web()
+        .and_then(|web_event| web_event.target())
+        .expect("must have a target")
+        .unchecked_into()
+}
+
+/// set the size of the drawing buffer to the displayed size of the canvas in physical pixels,
+/// returns true if the size has changed
+pub(crate) fn resize_drawing_buffer(canvas: &HtmlCanvasElement) -> bool {
+    let device_pixel_ratio = crate::window().device_pixel_ratio();
+    let width = (f64::from(canvas.client_width()) * device_pixel_ratio) as u32;
+    let height =
+        (f64::from(canvas.client_height()) * device_pixel_ratio) as u32;
+    if canvas.width() == width && canvas.height() == height {
+        return false;
+    }
+    canvas.set_width(width);
+    canvas.set_height(height);
+    true
+}
+
+/// start the render loop and observe the size of the canvas
+fn start<R>(
+    canvas: &HtmlCanvasElement,
+    renderer: &Rc<RefCell<R>>,
+    is_context_lost: &Rc<Cell<bool>>,
+) where
+    R: GpuRenderer + 'static,
+{
+    let on_resize: Closure<dyn FnMut()> = Closure::wrap(Box::new({
+        let canvas = canvas.clone();
+        move || {
+            if resize_drawing_buffer(&canvas) {
+                let event = web_sys::Event::new(RESIZE_EVENT)
+                    .expect("must create the resize event");
+                canvas
+                    .dispatch_event(&event)
+                    .expect("must dispatch the resize event");
+            }
+        }
+    }));
+    let stop_observing = observe_size(canvas, &on_resize);
+    let mut stop_observing = Some(move || {
+        stop_observing();
+        drop(on_resize);
+    });
+
+    let canvas = canvas.clone();
+    let renderer = Rc::clone(renderer);
+    let is_context_lost = Rc::clone(is_context_lost);
+    let frame_loop = AnimationFrameLoop::new(move |timestamp| {
+        if !canvas.is_connected() {
+            // the canvas has been removed from the document
+            if let Some(stop_observing) = stop_observing.take() {
+                stop_observing();
+            }
+            return false;
+        }
+        if !is_context_lost.get() {
+            renderer.borrow_mut().render(timestamp);
+        }
+        true
+    });
+    frame_loop.start();
+}
+
+/// call `on_resize` whenever the size of the canvas changes,
+/// returns the function to stop observing the size.
+///
+/// This uses the `ResizeObserver` if available, otherwise it listens to the resize of the window.
+fn observe_size(
+    canvas: &HtmlCanvasElement,
+    on_resize: &Closure<dyn FnMut()>,
+) -> Box<dyn FnOnce()> {
+    let callback: Function = on_resize.as_ref().clone().unchecked_into();
+    if capabilities().resize_observer {
+        let window = crate::window();
+        let constructor: Function =
+            Reflect::get(&window, &JsValue::from_str("ResizeObserver"))
+                .expect("must have a ResizeObserver")
+                .unchecked_into();
+        let observer = Reflect::construct(&constructor, &Array::of1(&callback))
+            .expect("must create a ResizeObserver");
+        call_method(&observer, "observe", Some(canvas.as_ref()));
+        Box::new(move || call_method(&observer, "disconnect", None))
+    } else {
+        let window = crate::window();
+        window
+            .add_event_listener_with_callback("resize", &callback)
+            .expect("must add a resize listener");
+        Box::new(move || {
+            window
+                .remove_event_listener_with_callback("resize", &callback)
+                .expect("must remove the resize listener");
+        })
+    }
+}
+
+fn call_method(target: &JsValue, name: &str, arg: Option<&JsValue>) {
+    let method: Function = Reflect::get(target, &JsValue::from_str(name))
+        .expect("must have the method")
+        .unchecked_into();
+    match arg {
+        Some(arg) => method.call1(target, arg),
+        None => method.call0(target),
+    }
+    .expect("must call the method");
+}
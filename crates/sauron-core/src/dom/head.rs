@@ -0,0 +1,111 @@
+//! applies the [`Head`] of the apps into the head of the document.
+//!
+//! The last applied head of each type of the app is kept, so the document is only patched
+//! when the head has changed, and only the tags which were added by the app are removed.
+use crate::{
+    dom::cmd::Cmd,
+    html::head::{Head, HeadTag, HEAD_ATTR},
+};
+use std::{any::TypeId, cell::RefCell, collections::HashMap};
+use wasm_bindgen::JsCast;
+use web_sys::Element;
+
+thread_local! {
+    /// the last applied head, for each type of the app
+    static APPLIED_HEADS: RefCell<HashMap<TypeId, Head>> = RefCell::new(HashMap::new());
+}
+
+/// apply the `head` of the app into the head of the document,
+/// the tags which are no longer in the `head` are removed, while the new tags are added.
+/// The tags rendered by the server with the same key are reused.
+pub(crate) fn apply(type_id: TypeId, head: Head) {
+    APPLIED_HEADS.with(|applied_heads| {
+        let mut applied_heads = applied_heads.borrow_mut();
+        let last_head = applied_heads.get(&type_id);
+        if last_head == Some(&head) || (last_head.is_none() && head.is_empty())
+        {
+            return;
+        }
+        let document = crate::document();
+        if let Some(title) = head.title.as_ref() {
+            if document.title() != *title {
+                document.set_title(title);
+            }
+        }
+        let mut managed = managed_tags();
+        if let Some(last_head) = last_head {
+            for last_tag in last_head.tags.iter() {
+                let is_removed =
+                    !head.tags.iter().any(|tag| tag.key == last_tag.key);
+                if is_removed {
+                    if let Some(element) = managed.remove(&last_tag.key) {
+                        element.remove();
+                    }
+                }
+            }
+        }
+        for tag in head.tags.iter() {
+            match managed.get(&tag.key) {
+                Some(element) => set_attributes(element, tag),
+                None => add_tag(tag),
+            }
+        }
+        applied_heads.insert(type_id, head);
+    });
+}
+
+/// the tags in the head of the document which are managed by the apps, by their key
+fn managed_tags() -> HashMap<String, Element> {
+    let html_head = crate::document().head().expect("must have a head");
+    let found = html_head
+        .query_selector_all(&format!("[{}]", HEAD_ATTR))
+        .expect("must query the head");
+    (0..found.length())
+        .filter_map(|i| found.item(i))
+        .map(|node| node.unchecked_into::<Element>())
+        .filter_map(|element| {
+            element.get_attribute(HEAD_ATTR).map(|key| (key, element))
+        })
+        .collect()
+}
+
+fn add_tag(tag: &HeadTag) {
+    let element = crate::document()
+        .create_element(tag.tag)
+        .expect("must create the head tag");
+    element
+        .set_attribute(HEAD_ATTR, &tag.key)
+        .expect("must set the key of the head tag");
+    set_attributes(&element, tag);
+    crate::document()
+        .head()
+        .expect("must have a head")
+        .append_child(&element)
+        .expect("must append the head tag");
+}
+
+fn set_attributes(element: &Element, tag: &HeadTag) {
+    for (name, value) in tag.attrs.iter() {
+        if element.get_attribute(name).as_ref() != Some(value) {
+            element
+                .set_attribute(name, value)
+                .expect("must set the attribute of the head tag");
+        }
+    }
+}
+
+impl<DSP> Cmd<DSP>
+where
+    DSP: 'static,
+{
+    /// Creates a Cmd which sets the title of the document.
+    ///
+    /// Note: the title from [`Application::head`](crate::Application::head), if any,
+    /// takes over again when the head of the app has changed and is updated
+    /// with [`Cmd::update_head`].
+    pub fn set_title(title: impl ToString) -> Self {
+        let title = title.to_string();
+        Cmd::new(move |_program| crate::document().set_title(&title))
+            .no_render()
+    }
+}
@@ -0,0 +1,238 @@
+//! provides the [`KeyCombo`] parsed from strings such as `ctrl+k` or `shift+alt+arrowup`,
+//! which are matched against the keydown events in [`Sub::hotkeys`].
+//!
+//! The combo is the modifiers and the key joined with `+`, ignoring the case:
+//! - the modifiers are `ctrl`, `alt`, `shift` and `meta`,
+//!   with the aliases `control`, `option`, `cmd` and `command`
+//! - the key is the [`KeyboardEvent.key`][0] of the key, ie: `k`, `enter`, `arrowup`, `f2`,
+//!   with the aliases `esc`, `space`, `plus`, `up`, `down`, `left`, `right` and `del`
+//!
+//! # Examples
+//! ```rust,ignore
+//! // in the subscriptions of the app
+//! Sub::hotkeys([("ctrl+k", Msg::OpenPalette), ("esc", Msg::Close)])
+//! ```
+//!
+//! [0]: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key
+use crate::dom::{
+    sub::{Sub, Subscription},
+    Dispatch,
+};
+use std::str::FromStr;
+use thiserror::Error;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{HtmlElement, KeyboardEvent};
+
+/// Errors when parsing a key combo
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum KeyComboError {
+    /// the combo has only modifiers, or is empty
+    #[error("the key combo {0:?} has no key")]
+    MissingKey(String),
+    /// the combo has more than one key which is not a modifier
+    #[error("the key combo {0:?} has more than one key")]
+    MultipleKeys(String),
+}
+
+/// A key pressed together with the modifiers
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    /// the control key is pressed
+    pub ctrl: bool,
+    /// the alt key, or the option key in mac is pressed
+    pub alt: bool,
+    /// the shift key is pressed
+    pub shift: bool,
+    /// the meta key, which is the command key in mac or the windows key is pressed
+    pub meta: bool,
+    /// the lowercased `key` of the keyboard event
+    pub key: String,
+}
+
+impl KeyCombo {
+    /// returns true if the key and exactly the same modifiers are pressed in the `event`
+    pub fn matches(&self, event: &KeyboardEvent) -> bool {
+        self.ctrl == event.ctrl_key()
+            && self.alt == event.alt_key()
+            && self.shift == event.shift_key()
+            && self.meta == event.meta_key()
+            && self.key == event.key().to_lowercase()
+    }
+
+    /// returns true if the combo has a modifier other than shift,
+    /// which is not typed as text
+    fn has_command_modifier(&self) -> bool {
+        self.ctrl || self.alt || self.meta
+    }
+}
+
+impl FromStr for KeyCombo {
+    type Err = KeyComboError;
+
+    fn from_str(combo: &str) -> Result<Self, Self::Err> {
+        let mut key_combo = KeyCombo::default();
+        let mut key = None;
+        for part in combo.split('+').map(|part| part.trim().to_lowercase()) {
+            match part.as_str() {
+                "ctrl" | "control" => key_combo.ctrl = true,
+                "alt" | "option" => key_combo.alt = true,
+                "shift" => key_combo.shift = true,
+                "meta" | "cmd" | "command" => key_combo.meta = true,
+                "" => return Err(KeyComboError::MissingKey(combo.to_string())),
+                _ if key.is_some() => {
+                    return Err(KeyComboError::MultipleKeys(combo.to_string()))
+                }
+                _ => key = Some(key_alias(&part).unwrap_or(&part).to_string()),
+            }
+        }
+        key_combo.key =
+            key.ok_or_else(|| KeyComboError::MissingKey(combo.to_string()))?;
+        Ok(key_combo)
+    }
+}
+
+/// the lowercased `key` of the keyboard event of the short names of the keys
+fn key_alias(name: &str) -> Option<&'static str> {
+    let key = match name {
+        "esc" => "escape",
+        "space" => " ",
+        "plus" => "+",
+        "up" => "arrowup",
+        "down" => "arrowdown",
+        "left" => "arrowleft",
+        "right" => "arrowright",
+        "del" => "delete",
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// returns true if the user is typing into the target of the event
+fn is_typing(event: &KeyboardEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+        .map(|element| {
+            let tag = element.tag_name().to_lowercase();
+            tag == "input"
+                || tag == "textarea"
+                || tag == "select"
+                || element.is_content_editable()
+        })
+        .unwrap_or(false)
+}
+
+/// parse the combos of the bindings, the invalid combos are logged and skipped
+fn parse_bindings<S, MSG>(
+    bindings: impl IntoIterator<Item = (S, MSG)>,
+) -> Vec<(KeyCombo, MSG)>
+where
+    S: AsRef<str>,
+{
+    bindings
+        .into_iter()
+        .filter_map(|(combo, msg)| match combo.as_ref().parse::<KeyCombo>() {
+            Ok(key_combo) => Some((key_combo, msg)),
+            Err(e) => {
+                log::error!("invalid hotkey: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+impl<DSP> Sub<DSP>
+where
+    DSP: 'static,
+{
+    /// Creates a Sub which emits the MSG of the first key combo which matches the keydown events
+    /// in the document, the default action of the matched events is prevented.
+    ///
+    /// The combos without the `ctrl`, `alt` or `meta` modifier are ignored while the user
+    /// is typing into an input, a textarea or an editable element, except for `esc`.
+    pub fn hotkeys<S, MSG>(bindings: impl IntoIterator<Item = (S, MSG)>) -> Self
+    where
+        S: AsRef<str>,
+        MSG: Clone + 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        let bindings = parse_bindings(bindings);
+        Sub::new(move |program: DSP| {
+            listen_keydown(move |event: &KeyboardEvent| {
+                let typing = is_typing(event);
+                let msg = bindings
+                    .iter()
+                    .filter(|(combo, _)| {
+                        !typing
+                            || combo.has_command_modifier()
+                            || combo.key == "escape"
+                    })
+                    .find(|(combo, _)| combo.matches(event))
+                    .map(|(_, msg)| msg.clone());
+                if let Some(msg) = msg {
+                    event.prevent_default();
+                    program.dispatch(msg);
+                }
+            })
+        })
+    }
+
+    /// Creates a Sub which emits the MSG of the first key combo which matches the keydown events,
+    /// only when the focus is inside of the element matching the `selector`,
+    /// ie: the shortcuts of an editor which don't apply to the rest of the page.
+    pub fn hotkeys_within<S, MSG>(
+        selector: &str,
+        bindings: impl IntoIterator<Item = (S, MSG)>,
+    ) -> Self
+    where
+        S: AsRef<str>,
+        MSG: Clone + 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        let selector = selector.to_string();
+        let bindings = parse_bindings(bindings);
+        Sub::new(move |program: DSP| {
+            listen_keydown(move |event: &KeyboardEvent| {
+                let is_within = crate::document()
+                    .active_element()
+                    .and_then(|active| active.closest(&selector).ok().flatten())
+                    .is_some();
+                if !is_within {
+                    return;
+                }
+                let msg = bindings
+                    .iter()
+                    .find(|(combo, _)| combo.matches(event))
+                    .map(|(_, msg)| msg.clone());
+                if let Some(msg) = msg {
+                    event.prevent_default();
+                    program.dispatch(msg);
+                }
+            })
+        })
+    }
+}
+
+/// call `f` on every keydown event in the document until unsubscribed
+fn listen_keydown<F>(f: F) -> Subscription
+where
+    F: Fn(&KeyboardEvent) + 'static,
+{
+    let listener: Closure<dyn FnMut(KeyboardEvent)> =
+        Closure::wrap(Box::new(move |event: KeyboardEvent| f(&event)));
+    let document = crate::document();
+    document
+        .add_event_listener_with_callback(
+            "keydown",
+            listener.as_ref().unchecked_ref(),
+        )
+        .expect("must add the keydown listener");
+    Subscription::new(move || {
+        document
+            .remove_event_listener_with_callback(
+                "keydown",
+                listener.as_ref().unchecked_ref(),
+            )
+            .expect("must remove the keydown listener");
+    })
+}
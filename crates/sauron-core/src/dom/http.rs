@@ -1,12 +1,43 @@
 //! provides functions for retrieving data using http network request
 use crate::dom::Callback;
+#[cfg(feature = "with-persist")]
+use crate::remote_data::RemoteData;
 use crate::{Application, Cmd, Dispatch, Program};
 use js_sys::TypeError;
+#[cfg(feature = "with-persist")]
+use serde::de::DeserializeOwned;
 use std::fmt::Debug;
+use thiserror::Error;
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+#[cfg(feature = "with-persist")]
+use wasm_bindgen_futures::JsFuture;
 use web_sys::RequestInit;
 use web_sys::Response;
 
+#[cfg(feature = "with-persist")]
+pub mod auth;
+
+/// Errors when fetching the data from the url
+#[derive(Debug, Error)]
+pub enum FetchError {
+    /// the request failed, in cases where a network is down, server is dead, etc.
+    #[error("network error: {0}")]
+    Network(String),
+    /// the server responded with a status which is not successful
+    #[error("http status: {0}")]
+    Status(u16),
+    /// the response can not be deserialized
+    #[cfg(feature = "with-persist")]
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl From<JsValue> for FetchError {
+    fn from(js_value: JsValue) -> Self {
+        FetchError::Network(format!("{:?}", js_value))
+    }
+}
+
 /// Provides functions for doing http network request
 #[derive(Copy, Clone, Debug)]
 pub struct Http;
@@ -109,4 +140,45 @@ impl Http {
             error_cb_closure.forget();
         })
     }
+
+    /// fetch the json from the url and deserialize it into `T`,
+    /// dispatching the MSG created from [`RemoteData::Loading`] when the request starts,
+    /// then from either [`RemoteData::Success`] or [`RemoteData::Failure`] when it is done.
+    #[cfg(feature = "with-persist")]
+    pub fn fetch_remote_data<APP, MSG, T, F>(url: &str, f: F) -> Cmd<APP, MSG>
+    where
+        APP: Application<MSG> + 'static,
+        MSG: 'static,
+        T: DeserializeOwned + 'static,
+        F: Fn(RemoteData<T, FetchError>) -> MSG + 'static,
+    {
+        let url = url.to_string();
+        Cmd::new_async(move |program: Program<APP, MSG>| {
+            program.dispatch(f(RemoteData::Loading));
+            async move {
+                let remote_data = RemoteData::from(fetch_json(&url).await);
+                program.dispatch(f(remote_data));
+            }
+        })
+        .no_render()
+    }
+}
+
+#[cfg(feature = "with-persist")]
+async fn fetch_json<T>(url: &str) -> Result<T, FetchError>
+where
+    T: DeserializeOwned,
+{
+    let response: Response =
+        JsFuture::from(crate::window().fetch_with_str(url))
+            .await?
+            .unchecked_into();
+    if !response.ok() {
+        return Err(FetchError::Status(response.status()));
+    }
+    let text = JsFuture::from(response.text()?)
+        .await?
+        .as_string()
+        .unwrap_or_default();
+    Ok(serde_json::from_str(&text)?)
 }
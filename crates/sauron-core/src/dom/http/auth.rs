@@ -0,0 +1,421 @@
+//! provides the [`Session`] which holds the tokens of the authenticated user,
+//! attaches them into the requests and schedules their refresh before they expire.
+//!
+//! # Examples
+//! ```rust,ignore
+//! let session = Session::new("my-app-tokens", TokenStorage::Local).refresh_ahead(60_000.0);
+//!
+//! // in the update, once the user is logged in or the tokens are refreshed
+//! session.set_tokens(Tokens::new(access_token).refresh_token(refresh_token).expires_in(3_600_000.0));
+//!
+//! // fetch with the `Authorization: Bearer <access_token>` header
+//! session.fetch_remote_data("/api/orders", Msg::OrdersReceived)
+//!
+//! // in the subscriptions of the app
+//! self.session.subscribe(Msg::RefreshTokens, || Msg::SessionExpired)
+//! ```
+use super::FetchError;
+use crate::dom::{
+    cmd::Cmd,
+    storage::{local, session, StorageError},
+    sub::{Sub, Subscription},
+    timer, Dispatch,
+};
+use crate::remote_data::RemoteData;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Headers, RequestInit, Response};
+
+/// The tokens of the authenticated user
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tokens {
+    /// the token sent in the `Authorization` header
+    pub access_token: String,
+    /// the token used to get a new access token before it expires
+    pub refresh_token: Option<String>,
+    /// the time when the access token expires, in milliseconds since the unix epoch
+    pub expires_at: Option<f64>,
+}
+
+impl Tokens {
+    /// tokens which has only the access token, which doesn't expire
+    pub fn new(access_token: impl ToString) -> Self {
+        Tokens {
+            access_token: access_token.to_string(),
+            refresh_token: None,
+            expires_at: None,
+        }
+    }
+
+    /// set the refresh token
+    pub fn refresh_token(mut self, refresh_token: impl ToString) -> Self {
+        self.refresh_token = Some(refresh_token.to_string());
+        self
+    }
+
+    /// the access token expires `ms` milliseconds from now
+    pub fn expires_in(mut self, ms: f64) -> Self {
+        self.expires_at = Some(js_sys::Date::now() + ms);
+        self
+    }
+
+    /// returns true if the access token has expired
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .map(|expires_at| expires_at <= js_sys::Date::now())
+            .unwrap_or(false)
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "access_token": self.access_token,
+            "refresh_token": self.refresh_token,
+            "expires_at": self.expires_at,
+        })
+    }
+
+    fn from_json(json: &serde_json::Value) -> Option<Self> {
+        Some(Tokens {
+            access_token: json.get("access_token")?.as_str()?.to_string(),
+            refresh_token: json
+                .get("refresh_token")
+                .and_then(|v| v.as_str())
+                .map(ToString::to_string),
+            expires_at: json.get("expires_at").and_then(|v| v.as_f64()),
+        })
+    }
+}
+
+/// Where the tokens are stored
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenStorage {
+    /// the localStorage, the user stays logged in across the browser sessions
+    Local,
+    /// the sessionStorage, the user is logged out when the browser tab is closed
+    Session,
+    /// only in memory, the user is logged out when the page is reloaded
+    Memory,
+}
+
+impl TokenStorage {
+    fn load(&self, key: &str) -> Option<Tokens> {
+        let json: Result<Option<serde_json::Value>, StorageError> = match self {
+            TokenStorage::Local => local::get(key),
+            TokenStorage::Session => session::get(key),
+            TokenStorage::Memory => Ok(None),
+        };
+        json.map_err(|e| log::warn!("unable to load the tokens: {}", e))
+            .ok()
+            .flatten()
+            .and_then(|json| Tokens::from_json(&json))
+    }
+
+    fn store(&self, key: &str, tokens: Option<&Tokens>) {
+        let result = match (self, tokens) {
+            (TokenStorage::Local, Some(tokens)) => {
+                local::set(key, &tokens.to_json())
+            }
+            (TokenStorage::Session, Some(tokens)) => {
+                session::set(key, &tokens.to_json())
+            }
+            (TokenStorage::Local, None) => local::remove(key),
+            (TokenStorage::Session, None) => session::remove(key),
+            (TokenStorage::Memory, _) => Ok(()),
+        };
+        if let Err(e) = result {
+            log::error!("unable to store the tokens: {}", e);
+        }
+    }
+}
+
+/// what happened to the session, which the subscriptions are notified of
+#[derive(Clone, Copy, PartialEq)]
+enum SessionEvent {
+    /// the tokens are set or cleared
+    Changed,
+    /// the tokens are expired or rejected by the server
+    Expired,
+}
+
+struct Inner {
+    key: String,
+    storage: TokenStorage,
+    tokens: RefCell<Option<Tokens>>,
+    /// how long before the expiry the tokens are refreshed, in milliseconds
+    refresh_ahead: Cell<f64>,
+    listeners: RefCell<Vec<(usize, Rc<dyn Fn(SessionEvent)>)>>,
+    next_listener_id: RefCell<usize>,
+}
+
+/// The session of the authenticated user, which holds its tokens.
+///
+/// This is cheap to clone, the clones share the same tokens.
+#[derive(Clone)]
+pub struct Session {
+    inner: Rc<Inner>,
+}
+
+impl Session {
+    /// create a session with the tokens stored under the `key` in the `storage`,
+    /// the previously stored tokens are loaded right away
+    pub fn new(key: &str, storage: TokenStorage) -> Self {
+        Session {
+            inner: Rc::new(Inner {
+                key: key.to_string(),
+                storage,
+                tokens: RefCell::new(storage.load(key)),
+                refresh_ahead: Cell::new(30_000.0),
+                listeners: RefCell::new(vec![]),
+                next_listener_id: RefCell::new(0),
+            }),
+        }
+    }
+
+    /// refresh the tokens `ms` milliseconds before they expire, the default is 30 seconds
+    pub fn refresh_ahead(self, ms: f64) -> Self {
+        self.inner.refresh_ahead.set(ms);
+        self
+    }
+
+    /// the current tokens
+    pub fn tokens(&self) -> Option<Tokens> {
+        self.inner.tokens.borrow().clone()
+    }
+
+    /// returns true if there are tokens which has not expired yet
+    pub fn is_authenticated(&self) -> bool {
+        self.inner
+            .tokens
+            .borrow()
+            .as_ref()
+            .map(|tokens| !tokens.is_expired())
+            .unwrap_or(false)
+    }
+
+    /// the value of the `Authorization` header
+    pub fn authorization(&self) -> Option<String> {
+        self.inner
+            .tokens
+            .borrow()
+            .as_ref()
+            .map(|tokens| format!("Bearer {}", tokens.access_token))
+    }
+
+    /// store the tokens, ie: after the user logged in or the tokens are refreshed,
+    /// the refresh is scheduled again with the expiry of the new tokens
+    pub fn set_tokens(&self, tokens: Tokens) {
+        self.inner.storage.store(&self.inner.key, Some(&tokens));
+        *self.inner.tokens.borrow_mut() = Some(tokens);
+        self.notify(SessionEvent::Changed);
+    }
+
+    /// remove the tokens, ie: when the user logged out
+    pub fn clear(&self) {
+        self.inner.storage.store(&self.inner.key, None);
+        *self.inner.tokens.borrow_mut() = None;
+        self.notify(SessionEvent::Changed);
+    }
+
+    /// remove the tokens and notify the subscriptions that the session has expired
+    pub fn expire(&self) {
+        self.clear();
+        self.notify(SessionEvent::Expired);
+    }
+
+    fn notify(&self, event: SessionEvent) {
+        let listeners: Vec<_> = self
+            .inner
+            .listeners
+            .borrow()
+            .iter()
+            .map(|(_, listener)| Rc::clone(listener))
+            .collect();
+        for listener in listeners {
+            listener(event);
+        }
+    }
+
+    fn add_listener(&self, listener: Rc<dyn Fn(SessionEvent)>) -> usize {
+        let mut next_listener_id = self.inner.next_listener_id.borrow_mut();
+        let id = *next_listener_id;
+        *next_listener_id += 1;
+        self.inner.listeners.borrow_mut().push((id, listener));
+        id
+    }
+
+    fn remove_listener(&self, id: usize) {
+        self.inner
+            .listeners
+            .borrow_mut()
+            .retain(|(listener_id, _)| *listener_id != id);
+    }
+
+    /// fetch the url with the `Authorization` header of this session.
+    ///
+    /// When the server responded with `401 Unauthorized`, the session is expired
+    /// and the subscriptions dispatch their `on_expired` MSG.
+    pub async fn fetch(
+        &self,
+        url: &str,
+        request_init: Option<RequestInit>,
+    ) -> Result<Response, FetchError> {
+        let mut request_init = request_init.unwrap_or_else(RequestInit::new);
+        if let Some(authorization) = self.authorization() {
+            let headers = Headers::new()?;
+            headers.set("Authorization", &authorization)?;
+            request_init.headers(&headers);
+        }
+        let response: Response = JsFuture::from(
+            crate::window().fetch_with_str_and_init(url, &request_init),
+        )
+        .await?
+        .unchecked_into();
+        if response.status() == 401 {
+            self.expire();
+        }
+        if !response.ok() {
+            return Err(FetchError::Status(response.status()));
+        }
+        Ok(response)
+    }
+
+    /// fetch the json from the url with the `Authorization` header of this session
+    /// and deserialize it into `T`, the same way as
+    /// [`Http::fetch_remote_data`](crate::dom::Http::fetch_remote_data)
+    pub fn fetch_remote_data<DSP, MSG, T, F>(&self, url: &str, f: F) -> Cmd<DSP>
+    where
+        DSP: Dispatch<MSG> + Clone + 'static,
+        MSG: 'static,
+        T: DeserializeOwned + 'static,
+        F: Fn(RemoteData<T, FetchError>) -> MSG + 'static,
+    {
+        let session = self.clone();
+        let url = url.to_string();
+        Cmd::new_async(move |program: DSP| {
+            program.dispatch(f(RemoteData::Loading));
+            async move {
+                let remote_data =
+                    RemoteData::from(fetch_json(&session, &url).await);
+                program.dispatch(f(remote_data));
+            }
+        })
+        .no_render()
+    }
+
+    /// Creates a Sub which dispatch the MSG from `on_refresh` with the refresh token,
+    /// `refresh_ahead` milliseconds before the access token expires.
+    /// The app then requests new tokens and stores them with [`set_tokens`](Self::set_tokens),
+    /// which schedules the next refresh.
+    ///
+    /// The MSG from `on_expired` is dispatched when the tokens have expired without a refresh
+    /// token, or the server rejected the access token.
+    pub fn subscribe<DSP, MSG, R, E>(
+        &self,
+        on_refresh: R,
+        on_expired: E,
+    ) -> Sub<DSP>
+    where
+        DSP: Dispatch<MSG> + Clone + 'static,
+        MSG: 'static,
+        R: Fn(String) -> MSG + 'static,
+        E: Fn() -> MSG + 'static,
+    {
+        let session = self.clone();
+        let on_refresh = Rc::new(on_refresh);
+        let on_expired = Rc::new(on_expired);
+        Sub::new(move |program: DSP| {
+            let timer = Rc::new(RefreshTimer::default());
+            let schedule = Rc::new({
+                let session = session.clone();
+                let timer = Rc::clone(&timer);
+                let program = program.clone();
+                move || {
+                    timer.cancel();
+                    let tokens = match session.tokens() {
+                        Some(tokens) => tokens,
+                        None => return,
+                    };
+                    let expires_at = match tokens.expires_at {
+                        Some(expires_at) => expires_at,
+                        None => return,
+                    };
+                    let refresh_at = match tokens.refresh_token {
+                        Some(_) => {
+                            expires_at - session.inner.refresh_ahead.get()
+                        }
+                        None => expires_at,
+                    };
+                    let delay = (refresh_at - js_sys::Date::now()).max(0.0);
+                    let session = session.clone();
+                    let program = program.clone();
+                    let on_refresh = Rc::clone(&on_refresh);
+                    timer.start(delay, move || match session.tokens() {
+                        Some(Tokens {
+                            refresh_token: Some(refresh_token),
+                            ..
+                        }) => program.dispatch(on_refresh(refresh_token)),
+                        Some(_) => session.expire(),
+                        None => (),
+                    });
+                }
+            });
+            let listener_id = session.add_listener(Rc::new({
+                let schedule = Rc::clone(&schedule);
+                move |event| match event {
+                    SessionEvent::Changed => schedule(),
+                    SessionEvent::Expired => program.dispatch(on_expired()),
+                }
+            }));
+            schedule();
+            Subscription::new(move || {
+                session.remove_listener(listener_id);
+                timer.cancel();
+            })
+        })
+    }
+}
+
+async fn fetch_json<T>(session: &Session, url: &str) -> Result<T, FetchError>
+where
+    T: DeserializeOwned,
+{
+    let response = session.fetch(url, None).await?;
+    let text = JsFuture::from(response.text()?)
+        .await?
+        .as_string()
+        .unwrap_or_default();
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// the timeout of the next refresh of the tokens
+#[derive(Default)]
+struct RefreshTimer {
+    timeout: RefCell<Option<(i32, Closure<dyn FnMut()>)>>,
+}
+
+impl RefreshTimer {
+    fn start<F>(&self, delay: f64, f: F)
+    where
+        F: FnOnce() + 'static,
+    {
+        let closure: Closure<dyn FnMut()> = Closure::once(f);
+        let timeout_id = timer::set_timeout(
+            closure.as_ref().unchecked_ref(),
+            // clamped to the max delay of setTimeout, which is about 24 days
+            delay.min(f64::from(i32::MAX)) as i32,
+        );
+        *self.timeout.borrow_mut() = Some((timeout_id, closure));
+    }
+
+    fn cancel(&self) {
+        if let Some((timeout_id, _closure)) = self.timeout.borrow_mut().take() {
+            timer::clear_timeout(timeout_id);
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! injects the styles of the apps, either into the head of the document
+//! or into the shadow root the app is mounted to.
+//!
+//! The hash of the last injected style is kept, so the style is only set again
+//! when it has changed, ie: when the app switched its theme.
+use js_sys::{Array, Function, Object, Reflect};
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::{hash_map::DefaultHasher, HashMap};
+use std::hash::{Hash, Hasher};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::ShadowRoot;
+
+/// which of the styles of the app is injected
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum StyleSlot {
+    /// the style from `Application::style`
+    Style,
+    /// the style from `Application::style_scoped`
+    Scoped,
+    /// the style from `Application::style_print`
+    Print,
+}
+
+thread_local! {
+    /// the hash of the last injected style and its style element, for each type of the app
+    static INJECTED_STYLES: RefCell<HashMap<(TypeId, StyleSlot), (u64, web_sys::Node)>> =
+        RefCell::new(HashMap::new());
+}
+
+fn style_hash(style: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    style.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// inject the style into the head of the document,
+/// the `media` is set when the style only applies to that media, ie: `print`.
+///
+/// The style element is only created once for each type of the app,
+/// and its content is only set again when the style has changed.
+pub(crate) fn inject_into_head(
+    type_id: TypeId,
+    slot: StyleSlot,
+    style: &str,
+    media: Option<&str>,
+) {
+    let hash = style_hash(style);
+    INJECTED_STYLES.with(|injected_styles| {
+        let mut injected_styles = injected_styles.borrow_mut();
+        if let Some((last_hash, html_style)) =
+            injected_styles.get_mut(&(type_id, slot))
+        {
+            if *last_hash != hash {
+                html_style.set_text_content(Some(style));
+                *last_hash = hash;
+            }
+            return;
+        }
+        // there is no need for an empty style element, until the app has a style
+        if style.trim().is_empty() {
+            return;
+        }
+        let html_style = create_style_element(style, media);
+        html_style.set_class_name(&format!("{:?}", type_id));
+        crate::document()
+            .head()
+            .expect("must have a head")
+            .append_child(&html_style)
+            .expect("must append style");
+        injected_styles.insert((type_id, slot), (hash, html_style.into()));
+    });
+}
+
+fn create_style_element(style: &str, media: Option<&str>) -> web_sys::Element {
+    let html_style = crate::document()
+        .create_element("style")
+        .expect("must be able to create style element");
+    if let Some(media) = media {
+        html_style
+            .set_attribute("media", media)
+            .expect("must set attribute");
+    }
+    html_style.set_text_content(Some(style));
+    html_style
+}
+
+/// the injected style in the shadow root
+enum ShadowStyle {
+    /// a constructed `CSSStyleSheet` in the `adoptedStyleSheets` of the shadow root
+    Adopted(JsValue),
+    /// a `<style>` element in the shadow root,
+    /// when the browser doesn't support the constructable stylesheets
+    Element(web_sys::Element),
+}
+
+/// The styles injected into the shadow root the app is mounted to.
+///
+/// The styles are set using the `adoptedStyleSheets` of the shadow root,
+/// falling back to a `<style>` element when the browser doesn't support it.
+pub(crate) struct ShadowStyles {
+    shadow_root: ShadowRoot,
+    styles: HashMap<StyleSlot, (u64, ShadowStyle)>,
+}
+
+impl ShadowStyles {
+    pub(crate) fn new(shadow_root: ShadowRoot) -> Self {
+        ShadowStyles {
+            shadow_root,
+            styles: HashMap::new(),
+        }
+    }
+
+    /// inject the style into the shadow root, or replace the injected style when it has changed
+    pub(crate) fn inject(
+        &mut self,
+        slot: StyleSlot,
+        style: &str,
+        media: Option<&str>,
+    ) {
+        let hash = style_hash(style);
+        if let Some((last_hash, shadow_style)) = self.styles.get_mut(&slot) {
+            if *last_hash != hash {
+                match shadow_style {
+                    ShadowStyle::Adopted(sheet) => {
+                        if let Err(e) = replace_sync(sheet, style) {
+                            log::error!("unable to replace the style: {:?}", e);
+                        }
+                    }
+                    ShadowStyle::Element(html_style) => {
+                        html_style.set_text_content(Some(style))
+                    }
+                }
+                *last_hash = hash;
+            }
+            return;
+        }
+        if style.trim().is_empty() {
+            return;
+        }
+        let shadow_style = match self.adopt_style_sheet(style, media) {
+            Some(sheet) => ShadowStyle::Adopted(sheet),
+            None => {
+                let html_style = create_style_element(style, media);
+                self.shadow_root
+                    .prepend_with_node_1(&html_style)
+                    .expect("must prepend style");
+                ShadowStyle::Element(html_style)
+            }
+        };
+        self.styles.insert(slot, (hash, shadow_style));
+    }
+
+    /// construct a stylesheet and add it into the `adoptedStyleSheets` of the shadow root,
+    /// returns `None` when the browser doesn't support the constructable stylesheets
+    fn adopt_style_sheet(
+        &self,
+        style: &str,
+        media: Option<&str>,
+    ) -> Option<JsValue> {
+        let adopted_key = JsValue::from_str("adoptedStyleSheets");
+        if !Reflect::has(&self.shadow_root, &adopted_key).unwrap_or(false) {
+            return None;
+        }
+        let constructor: Function = Reflect::get(
+            &js_sys::global(),
+            &JsValue::from_str("CSSStyleSheet"),
+        )
+        .ok()?
+        .dyn_into()
+        .ok()?;
+        let options = Object::new();
+        if let Some(media) = media {
+            Reflect::set(&options, &JsValue::from_str("media"), &media.into())
+                .ok()?;
+        }
+        let sheet =
+            Reflect::construct(&constructor, &Array::of1(&options)).ok()?;
+        replace_sync(&sheet, style).ok()?;
+        let adopted = Reflect::get(&self.shadow_root, &adopted_key).ok()?;
+        let sheets = Array::from(&adopted);
+        sheets.push(&sheet);
+        Reflect::set(&self.shadow_root, &adopted_key, &sheets).ok()?;
+        Some(sheet)
+    }
+}
+
+/// set the content of the constructed stylesheet
+fn replace_sync(sheet: &JsValue, style: &str) -> Result<(), JsValue> {
+    let replace_sync: Function =
+        Reflect::get(sheet, &JsValue::from_str("replaceSync"))?.dyn_into()?;
+    replace_sync.call1(sheet, &JsValue::from_str(style))?;
+    Ok(())
+}
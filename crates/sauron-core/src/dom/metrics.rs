@@ -0,0 +1,189 @@
+//! provides the [`Metrics`] of a program, which can be exported in the OpenMetrics text format
+//! using [`Program::metrics_text`](crate::Program::metrics_text), to be scraped or posted
+//! to a metrics collector.
+use std::fmt::Write;
+use wasm_bindgen::JsValue;
+
+/// the time in milliseconds of a frame at 60fps,
+/// a dispatch which takes longer than this causes the frame to be dropped
+pub const FRAME_BUDGET: f64 = 16.0;
+
+/// The counters of the dispatches of a program since it was created
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Metrics {
+    /// the number of the dispatches processed in the update loop
+    pub dispatch_count: u64,
+    /// the number of msgs processed in the update loop
+    pub msg_count: u64,
+    /// the number of times the view is rebuilt and patched into the DOM
+    pub render_count: u64,
+    /// the total number of patches applied into the DOM
+    pub patch_count: u64,
+    /// the number of dispatches which took longer than the [`FRAME_BUDGET`]
+    pub frames_over_budget: u64,
+    /// the total time in milliseconds spent in the dispatches
+    pub dispatch_time: f64,
+}
+
+/// The memory usage of a program at the time it is collected
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MemoryStats {
+    /// the number of closures attached to the elements of the view
+    pub active_closures: usize,
+    /// the number of elements observed for their visibility in the viewport
+    pub observed_elements: usize,
+    /// the size of the memory of the wasm module in bytes
+    pub wasm_memory_bytes: Option<f64>,
+    /// the size of the used javascript heap in bytes, only available in chromium browsers
+    pub js_heap_used_bytes: Option<f64>,
+    /// the size of the allocated javascript heap in bytes, only available in chromium browsers
+    pub js_heap_total_bytes: Option<f64>,
+}
+
+impl Metrics {
+    /// record a dispatch of `msg_count` msgs, which took `duration` milliseconds
+    pub(crate) fn record_dispatch(
+        &mut self,
+        msg_count: usize,
+        is_rendered: bool,
+        total_patches: usize,
+        duration: f64,
+    ) {
+        self.dispatch_count += 1;
+        self.msg_count += msg_count as u64;
+        if is_rendered {
+            self.render_count += 1;
+        }
+        self.patch_count += total_patches as u64;
+        if duration > FRAME_BUDGET {
+            self.frames_over_budget += 1;
+        }
+        self.dispatch_time += duration;
+    }
+
+    /// format the metrics and the `memory_stats` in the OpenMetrics text format
+    pub fn to_text(&self, memory_stats: &MemoryStats) -> String {
+        let mut buffer = String::new();
+        let counters = [
+            (
+                "sauron_dispatches",
+                "Number of dispatches processed in the update loop.",
+                self.dispatch_count,
+            ),
+            (
+                "sauron_msgs",
+                "Number of msgs processed in the update loop.",
+                self.msg_count,
+            ),
+            (
+                "sauron_renders",
+                "Number of times the view is patched into the DOM.",
+                self.render_count,
+            ),
+            (
+                "sauron_patches",
+                "Number of patches applied into the DOM.",
+                self.patch_count,
+            ),
+            (
+                "sauron_frames_over_budget",
+                "Number of dispatches which took longer than a frame.",
+                self.frames_over_budget,
+            ),
+        ];
+        for (name, help, value) in counters.iter() {
+            write_header(&mut buffer, name, "counter", help);
+            writeln!(buffer, "{}_total {}", name, value).expect("must write");
+        }
+
+        let name = "sauron_dispatch_duration_milliseconds";
+        write_header(
+            &mut buffer,
+            name,
+            "summary",
+            "Time spent in the dispatches.",
+        );
+        writeln!(buffer, "{}_sum {}", name, self.dispatch_time)
+            .expect("must write");
+        writeln!(buffer, "{}_count {}", name, self.dispatch_count)
+            .expect("must write");
+
+        let gauges = [
+            (
+                "sauron_active_closures",
+                "Number of closures attached to the elements of the view.",
+                Some(memory_stats.active_closures as f64),
+            ),
+            (
+                "sauron_observed_elements",
+                "Number of elements observed for their visibility in the viewport.",
+                Some(memory_stats.observed_elements as f64),
+            ),
+            (
+                "sauron_wasm_memory_bytes",
+                "Size of the memory of the wasm module.",
+                memory_stats.wasm_memory_bytes,
+            ),
+            (
+                "sauron_js_heap_used_bytes",
+                "Size of the used javascript heap.",
+                memory_stats.js_heap_used_bytes,
+            ),
+            (
+                "sauron_js_heap_total_bytes",
+                "Size of the allocated javascript heap.",
+                memory_stats.js_heap_total_bytes,
+            ),
+        ];
+        for (name, help, value) in gauges.iter() {
+            // the stats which are not supported by the browser are omitted
+            if let Some(value) = value {
+                write_header(&mut buffer, name, "gauge", help);
+                writeln!(buffer, "{} {}", name, value).expect("must write");
+            }
+        }
+        buffer.push_str("# EOF\n");
+        buffer
+    }
+}
+
+impl MemoryStats {
+    /// collect the memory usage from the browser
+    pub(crate) fn collect(active_closures: usize) -> Self {
+        let wasm_memory_bytes = get_number(
+            &js_sys::Reflect::get(
+                &wasm_bindgen::memory(),
+                &JsValue::from_str("buffer"),
+            )
+            .unwrap_or(JsValue::UNDEFINED),
+            "byteLength",
+        );
+        let memory = js_sys::Reflect::get(
+            &crate::performance(),
+            &JsValue::from_str("memory"),
+        )
+        .unwrap_or(JsValue::UNDEFINED);
+        MemoryStats {
+            active_closures,
+            observed_elements: crate::dom::viewport::observed_count(),
+            wasm_memory_bytes,
+            js_heap_used_bytes: get_number(&memory, "usedJSHeapSize"),
+            js_heap_total_bytes: get_number(&memory, "totalJSHeapSize"),
+        }
+    }
+}
+
+fn write_header(buffer: &mut String, name: &str, kind: &str, help: &str) {
+    writeln!(buffer, "# TYPE {} {}", name, kind).expect("must write");
+    writeln!(buffer, "# HELP {} {}", name, help).expect("must write");
+}
+
+/// get the number value of the `property` of the object
+fn get_number(object: &JsValue, property: &str) -> Option<f64> {
+    if object.is_undefined() {
+        return None;
+    }
+    js_sys::Reflect::get(object, &JsValue::from_str(property))
+        .ok()?
+        .as_f64()
+}
@@ -0,0 +1,70 @@
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Element, Node};
+
+/// Options on how the existing children of the mount node are treated
+/// when the app is appended into it, ie: with [`Program::append_to_mount_with_options`](crate::Program::append_to_mount_with_options)
+///
+/// # Examples
+/// ```rust
+/// use sauron::dom::MountOptions;
+///
+/// // remove the loading spinner, but keep the noscript message
+/// let options = MountOptions::clear_children().preserve("noscript");
+/// assert!(options.clear_children);
+/// assert_eq!(options.preserve, vec!["noscript".to_string()]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MountOptions {
+    /// remove the existing children of the mount node before the app is appended
+    pub clear_children: bool,
+    /// the css selectors of the children which are kept when the children are cleared
+    pub preserve: Vec<String>,
+}
+
+impl MountOptions {
+    /// options which remove the existing children of the mount node
+    pub fn clear_children() -> Self {
+        MountOptions {
+            clear_children: true,
+            preserve: vec![],
+        }
+    }
+
+    /// keep the children which matches the css `selector` when the children are cleared
+    pub fn preserve(mut self, selector: impl ToString) -> Self {
+        self.preserve.push(selector.to_string());
+        self
+    }
+
+    /// remove the children of the `mount` node according to these options
+    pub(crate) fn prepare(&self, mount: &Node) -> Result<(), JsValue> {
+        if !self.clear_children {
+            return Ok(());
+        }
+        let child_nodes = mount.child_nodes();
+        let children: Vec<Node> = (0..child_nodes.length())
+            .filter_map(|index| child_nodes.item(index))
+            .collect();
+        for child in children {
+            if !self.is_preserved(&child)? {
+                mount.remove_child(&child)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// whether the node matches any of the preserve selectors, only elements can be preserved
+    fn is_preserved(&self, node: &Node) -> Result<bool, JsValue> {
+        match node.dyn_ref::<Element>() {
+            Some(element) => {
+                for selector in self.preserve.iter() {
+                    if element.matches(selector)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            None => Ok(false),
+        }
+    }
+}
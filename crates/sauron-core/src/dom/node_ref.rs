@@ -0,0 +1,172 @@
+//! provides [`NodeRef`], a handle to the real DOM element of a node in the view,
+//! which is populated when the element is created.
+//!
+//! The element can also be targeted by a css selector with [`ElementTarget`],
+//! ie: to measure it with [`Cmd::measure_element`](crate::dom::cmd::Cmd::measure_element).
+use crate::{html::attributes::custom_attr, Attribute};
+use std::{cell::RefCell, fmt, rc::Rc};
+use wasm_bindgen::JsCast;
+use web_sys::Element;
+
+/// the name of the attribute which carries the node ref
+pub(crate) const NODE_REF_ATTR: &str = "node_ref";
+
+/// A handle to the real DOM element of a node in the view.
+///
+/// The handle is populated when the element is created in the DOM, so the `update` function
+/// can use it to measure or focus the element, or hand it to a third party javascript library.
+/// The clones of the handle refer to the same element.
+///
+/// # Examples
+/// ```rust,ignore
+/// struct App {
+///     canvas: NodeRef,
+/// }
+///
+/// impl Application<Msg> for App {
+///     fn view(&self) -> Node<Msg> {
+///         div(vec![], vec![canvas(vec![node_ref(&self.canvas)], vec![])])
+///     }
+///
+///     fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+///         if let Some(canvas) = self.canvas.get() {
+///             let rect = canvas.get_bounding_client_rect();
+///             log::info!("the canvas is {}px wide", rect.width());
+///         }
+///         Cmd::none()
+///     }
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct NodeRef(Rc<RefCell<Option<Element>>>);
+
+impl NodeRef {
+    /// create a handle which is not attached to an element yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the element this handle is attached to,
+    /// `None` when the element is not created yet or is removed from the view
+    pub fn get(&self) -> Option<Element> {
+        self.0.borrow().clone()
+    }
+
+    /// the element this handle is attached to, casted into a more specific type
+    /// such as `web_sys::HtmlInputElement`, `None` if the element is not of that type
+    pub fn cast<T: JsCast>(&self) -> Option<T> {
+        self.get().and_then(|element| element.dyn_into().ok())
+    }
+
+    pub(crate) fn set(&self, element: &Element) {
+        *self.0.borrow_mut() = Some(element.clone());
+    }
+
+    /// detach this handle from the `element`, unless it is already attached to another element
+    pub(crate) fn detach(&self, element: &Element) {
+        let mut attached = self.0.borrow_mut();
+        if attached.as_ref() == Some(element) {
+            *attached = None;
+        }
+    }
+}
+
+/// the handles are equal when they refer to the same element,
+/// so the node ref doesn't produce a patch as long as the same handle is used in the view
+impl PartialEq for NodeRef {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for NodeRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NodeRef").field(&self.0.borrow()).finish()
+    }
+}
+
+/// attach the `node_ref` to this element, the handle is populated when the element is created
+pub fn node_ref<MSG>(node_ref: &NodeRef) -> Attribute<MSG> {
+    custom_attr(NODE_REF_ATTR, node_ref.clone())
+}
+
+/// The element targeted by a [`NodeRef`] or a css selector
+#[derive(Clone, Debug, PartialEq)]
+pub enum ElementTarget {
+    /// the element attached to the node ref
+    NodeRef(NodeRef),
+    /// the first element which matches the css selector
+    Selector(String),
+}
+
+impl ElementTarget {
+    /// the targeted element, `None` if it is not in the document
+    pub fn element(&self) -> Option<Element> {
+        match self {
+            ElementTarget::NodeRef(node_ref) => node_ref.get(),
+            ElementTarget::Selector(selector) => {
+                crate::document().query_selector(selector).ok().flatten()
+            }
+        }
+    }
+}
+
+impl From<NodeRef> for ElementTarget {
+    fn from(node_ref: NodeRef) -> Self {
+        ElementTarget::NodeRef(node_ref)
+    }
+}
+
+impl From<&NodeRef> for ElementTarget {
+    fn from(node_ref: &NodeRef) -> Self {
+        ElementTarget::NodeRef(node_ref.clone())
+    }
+}
+
+impl From<&str> for ElementTarget {
+    fn from(selector: &str) -> Self {
+        ElementTarget::Selector(selector.to_string())
+    }
+}
+
+impl From<String> for ElementTarget {
+    fn from(selector: String) -> Self {
+        ElementTarget::Selector(selector)
+    }
+}
+
+/// The size and position of an element relative to the viewport, in css pixels
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rect {
+    /// the left of the element
+    pub x: f64,
+    /// the top of the element
+    pub y: f64,
+    /// the width of the element, including its padding and border
+    pub width: f64,
+    /// the height of the element, including its padding and border
+    pub height: f64,
+}
+
+impl Rect {
+    /// the right of the element
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    /// the bottom of the element
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+}
+
+impl From<web_sys::DomRect> for Rect {
+    fn from(rect: web_sys::DomRect) -> Self {
+        Rect {
+            x: rect.x(),
+            y: rect.y(),
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+}
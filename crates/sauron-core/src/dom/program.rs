@@ -1,13 +1,34 @@
+use crate::dom::injected_style::{self, ShadowStyles, StyleSlot};
+use crate::dom::metrics::{MemoryStats, Metrics};
+use crate::dom::view_transition;
 #[cfg(feature = "with-measure")]
 use crate::dom::Measurements;
+use crate::dom::MountOptions;
+use crate::dom::Subscription;
+use crate::html::{attributes::class, scoped_style};
+use crate::vdom::patch::TreePath;
 use crate::Cmd;
-use crate::{dom::dom_updater::DomUpdater, Application, Dispatch};
+use crate::{
+    dom::dom_updater::{self, DomError, DomUpdater},
+    Application, Dispatch,
+};
+#[cfg(feature = "with-persist")]
+use serde::de::DeserializeOwned;
 use std::any::TypeId;
 use std::{cell::RefCell, rc::Rc};
 #[cfg(feature = "with-request-animation-frame")]
 use wasm_bindgen::closure::Closure;
 use web_sys::Node;
 
+/// the performance marks at the start of the dispatch and at the end of each of its phases
+#[cfg(feature = "with-measure")]
+const DISPATCH_MARKS: [&str; 4] = [
+    "sauron-dispatch-start",
+    "sauron-update-end",
+    "sauron-view-end",
+    "sauron-patch-end",
+];
+
 /// Holds the user App and the dom updater
 /// This is passed into the event listener and the dispatch program
 /// will be called after the event is triggered.
@@ -21,6 +42,22 @@ where
     pub app: Rc<RefCell<APP>>,
     /// The dom_updater responsible to updating the actual document in the browser
     pub dom_updater: Rc<RefCell<DomUpdater<MSG>>>,
+    /// the active subscriptions of the app
+    subscriptions: Rc<RefCell<Vec<Subscription>>>,
+    /// the counters of the dispatches of the app
+    metrics: Rc<RefCell<Metrics>>,
+    /// the msgs dispatched with `dispatch_idle`, which are waiting for the browser to be idle
+    idle_msgs: Rc<RefCell<Vec<MSG>>>,
+    /// the class added into the root element of the view, when the app has a scoped style
+    scope_class: Rc<Option<String>>,
+    /// the styles injected into the shadow root, when the app is mounted to a shadow root
+    shadow_styles: Rc<RefCell<Option<ShadowStyles>>>,
+    /// the output of `style`, `style_scoped` and `style_print` of the app when they were last
+    /// injected, so the styles are only injected again when the output has changed
+    injected_styles: Rc<RefCell<Option<[String; 3]>>>,
+    /// formats the dispatched msgs to be shown in the error overlay, once it is installed
+    #[cfg(feature = "with-error-overlay")]
+    msg_formatter: Rc<std::cell::Cell<Option<fn(&MSG) -> String>>>,
 }
 
 impl<APP, MSG> Clone for Program<APP, MSG>
@@ -31,6 +68,14 @@ where
         Program {
             app: Rc::clone(&self.app),
             dom_updater: Rc::clone(&self.dom_updater),
+            subscriptions: Rc::clone(&self.subscriptions),
+            metrics: Rc::clone(&self.metrics),
+            idle_msgs: Rc::clone(&self.idle_msgs),
+            scope_class: Rc::clone(&self.scope_class),
+            shadow_styles: Rc::clone(&self.shadow_styles),
+            injected_styles: Rc::clone(&self.injected_styles),
+            #[cfg(feature = "with-error-overlay")]
+            msg_formatter: Rc::clone(&self.msg_formatter),
         }
     }
 }
@@ -43,11 +88,27 @@ where
     /// Create an Rc wrapped instance of program, initializing DomUpdater with the initial view
     /// and root node, but doesn't mount it yet.
     pub fn new(app: APP, root_node: &Node) -> Self {
-        let dom_updater: DomUpdater<MSG> =
-            DomUpdater::new(app.view(), root_node);
+        let scope_class = if app.style_scoped().trim().is_empty() {
+            None
+        } else {
+            Some(scoped_style::scope_class(TypeId::of::<APP>()))
+        };
+        let view = Self::scoped_view(&app, &scope_class);
+        let dom_updater: DomUpdater<MSG> = DomUpdater::new(view, root_node);
+        let dom_updater = Rc::new(RefCell::new(dom_updater));
+        #[cfg(feature = "with-devtools")]
+        crate::dom::devtools::register(&dom_updater);
         Program {
             app: Rc::new(RefCell::new(app)),
-            dom_updater: Rc::new(RefCell::new(dom_updater)),
+            dom_updater,
+            subscriptions: Rc::new(RefCell::new(vec![])),
+            metrics: Rc::new(RefCell::new(Metrics::default())),
+            idle_msgs: Rc::new(RefCell::new(vec![])),
+            scope_class: Rc::new(scope_class),
+            shadow_styles: Rc::new(RefCell::new(None)),
+            injected_styles: Rc::new(RefCell::new(None)),
+            #[cfg(feature = "with-error-overlay")]
+            msg_formatter: Rc::new(std::cell::Cell::new(None)),
         }
     }
 
@@ -59,17 +120,114 @@ where
         // listening to events (resize, hashchange)
         cmds.emit(self);
 
+        // start listening to the subscriptions of the app
+        let subscriptions = self.app.borrow().subscriptions().subscribe(self);
+        self.subscriptions.borrow_mut().extend(subscriptions);
+
         // inject the style style after call the init of the app as
         // it may be modifying the app state including the style
-        let style = self.app.borrow().style();
-        if !style.trim().is_empty() {
-            let type_id = TypeId::of::<APP>();
-            Self::inject_style(type_id, &style);
+        self.inject_styles();
+        self.update_head();
+    }
+
+    /// stop listening to all the subscriptions of the app
+    pub fn unsubscribe_all(&self) {
+        for subscription in self.subscriptions.borrow_mut().drain(..) {
+            subscription.unsubscribe();
         }
     }
 
+    /// remove the view of the app from the document
+    /// and stop listening to all the subscriptions of the app
+    pub fn unmount(&self) {
+        self.unsubscribe_all();
+        self.dom_updater.borrow().dispatch_unmount_events();
+        let root_node = self.root_node();
+        if let Some(parent_node) = root_node.parent_node() {
+            if let Err(e) = parent_node.remove_child(&root_node) {
+                self.handle_error(DomError::mount(e));
+            }
+        }
+    }
+
+    /// Show an overlay with the panic message and the last few msgs dispatched into the app
+    /// when the app panics, instead of leaving the page frozen with no feedback.
+    ///
+    /// This is only available with the `with-error-overlay` feature,
+    /// which is meant for development and should not be enabled in the release builds.
+    #[cfg(feature = "with-error-overlay")]
+    pub fn install_error_overlay(&self)
+    where
+        MSG: std::fmt::Debug,
+    {
+        self.msg_formatter
+            .set(Some(|msg: &MSG| format!("{:#?}", msg)));
+        crate::dom::error_overlay::install();
+    }
+
+    /// the counters of the dispatches of the app since the program is created
+    pub fn metrics(&self) -> Metrics {
+        *self.metrics.borrow()
+    }
+
+    /// Export the metrics of the app and the memory usage in the OpenMetrics text format,
+    /// which the app can post periodically to a metrics collector, ie: Prometheus.
+    ///
+    /// # Examples
+    /// ```text
+    /// # TYPE sauron_dispatches counter
+    /// # HELP sauron_dispatches Number of dispatches processed in the update loop.
+    /// sauron_dispatches_total 42
+    /// ...
+    /// # EOF
+    /// ```
+    pub fn metrics_text(&self) -> String {
+        let active_closures = self.dom_updater.borrow().active_closure_len();
+        self.metrics()
+            .to_text(&MemoryStats::collect(active_closures))
+    }
+
+    /// The path of the nodes of the view whose listeners handled the `event`,
+    /// see [`DomUpdater::handled_by`]
+    pub fn handled_by(&self, event: &web_sys::Event) -> Vec<TreePath> {
+        self.dom_updater.borrow().handled_by(event)
+    }
+
+    /// Set the algorithm used to diff the old view and the new view of the app,
+    /// the default is [`Keyed`](crate::vdom::Keyed)
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// let program = Program::mount_to_body(App::default());
+    /// // the app is a huge flat list which is only appended
+    /// program.set_diff_strategy(Positional);
+    /// ```
+    pub fn set_diff_strategy(
+        &self,
+        diff_strategy: impl crate::vdom::DiffStrategy<MSG> + 'static,
+    ) {
+        self.dom_updater
+            .borrow_mut()
+            .set_diff_strategy(diff_strategy);
+    }
+
+    /// Update the DOM of the views with at least `node_count` nodes in slices across multiple frames,
+    /// so a huge view such as a table of 100k cells doesn't block the main thread for the whole update.
+    ///
+    /// The patches are applied in slices of a few milliseconds, yielding to the browser until it is
+    /// idle in between, so the events are still handled in the meantime. The view is updated again
+    /// with the latest state of the app once the patches are all applied.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// let program = Program::mount_to_body(App::default());
+    /// program.set_time_slicing(10_000);
+    /// ```
+    pub fn set_time_slicing(&self, node_count: usize) {
+        self.dom_updater.borrow_mut().set_time_slicing(node_count);
+    }
+
     /// get the real DOM node where this app is mounted to.
-    #[allow(unused)]
     fn root_node(&self) -> web_sys::Node {
         self.dom_updater.borrow().root_node()
     }
@@ -90,21 +248,132 @@ where
         program
     }
 
+    /// Instantiate an app and append the view to the root_node, the existing children of the
+    /// root_node are cleared or preserved according to the `options`
+    pub fn append_to_mount_with_options(
+        app: APP,
+        root_node: &Node,
+        options: MountOptions,
+    ) -> Self {
+        let program = Self::new(app, root_node);
+        let result = program
+            .dom_updater
+            .borrow_mut()
+            .append_to_mount_with_options(&program, &options);
+        if let Err(e) = result {
+            program.handle_error(e);
+        }
+        program.after_mounted();
+        program
+    }
+
+    /// Instantiate an app and append the view to the root_node, with event delegation enabled.
+    ///
+    /// Instead of attaching a closure for each event of each element, only one listener per event
+    /// type is attached to the root node of the view, which then routes the event to the listener
+    /// of the target element.
+    pub fn append_to_mount_with_event_delegation(
+        app: APP,
+        root_node: &Node,
+    ) -> Self {
+        let program = Self::new(app, root_node);
+        program.dom_updater.borrow_mut().set_event_delegation(true);
+        program.start_append_to_mount();
+        program.after_mounted();
+        program
+    }
+
     /// Instantiate the app and then append it to the document body
     pub fn mount_to_body(app: APP) -> Self {
         Self::append_to_mount(app, &crate::body())
     }
 
+    /// Attach a shadow root to the `host` element and mount the app inside it,
+    /// the existing shadow root of the `host` is used when it already has one.
+    ///
+    /// The styles of the app are injected into the shadow root using the constructable
+    /// stylesheets, falling back to a `<style>` element when the browser doesn't support it.
+    /// This isolates the app from the styles of the page, ie: when it is embedded
+    /// as a widget into a third-party page.
+    ///
+    /// # Panics
+    /// if a shadow root can not be attached to the `host` element, ie: an `<input>`
+    pub fn mount_to_shadow(app: APP, host: &web_sys::Element) -> Self {
+        let shadow_root = host.shadow_root().unwrap_or_else(|| {
+            host.attach_shadow(&web_sys::ShadowRootInit::new(
+                web_sys::ShadowRootMode::Open,
+            ))
+            .expect("must be able to attach a shadow root")
+        });
+        let program = Self::new(app, &shadow_root);
+        *program.shadow_styles.borrow_mut() =
+            Some(ShadowStyles::new(shadow_root));
+        program.start_append_to_mount();
+        program.after_mounted();
+        program
+    }
+
     /// Replace the body of the document with the app
     pub fn replace_body(app: APP) -> Self {
         Self::replace_mount(app, &crate::body())
     }
+
+    /// Create the app from the `json` state, which is persisted by the server using
+    /// [`Application::persist`], then replace the server rendered body with the view of the app.
+    ///
+    /// The `app_fn` is called with `None` if the state can not be deserialized,
+    /// in which case the app should start with its default state.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// let json = sauron::embedded_state().unwrap_or_default();
+    /// Program::mount_with_serialized_state(|state: Option<App>| state.unwrap_or_default(), &json);
+    /// ```
+    #[cfg(feature = "with-persist")]
+    pub fn mount_with_serialized_state<F, S>(app_fn: F, json: &str) -> Self
+    where
+        F: FnOnce(Option<S>) -> APP,
+        S: DeserializeOwned,
+    {
+        let state = serde_json::from_str(json)
+            .map_err(|e| {
+                log::warn!("unable to deserialize the serialized state: {}", e)
+            })
+            .ok();
+        Self::replace_body(app_fn(state))
+    }
+
     fn start_append_to_mount(&self) {
-        self.dom_updater.borrow_mut().append_to_mount(self)
+        let result = self.dom_updater.borrow_mut().append_to_mount(self);
+        if let Err(e) = result {
+            self.handle_error(e);
+        }
     }
 
     fn start_replace_mount(&self) {
-        self.dom_updater.borrow_mut().replace_mount(self)
+        let result = self.dom_updater.borrow_mut().replace_mount(self);
+        if let Err(e) = result {
+            self.handle_error(e);
+        }
+    }
+
+    /// let the app handle the error, then emit the cmd it returns
+    fn handle_error(&self, error: DomError) {
+        let cmd = self.app.borrow_mut().on_error(error);
+        cmd.emit(self);
+    }
+
+    /// Discard the DOM of the app and create it from scratch with the current view of the app.
+    ///
+    /// This is used to recover when the DOM is out of sync with the view,
+    /// ie: in [`Application::on_error`] when patching the DOM failed.
+    pub fn rebuild_view(&self) {
+        let view = self.view();
+        let result = self.dom_updater.borrow_mut().rebuild_dom(self, view);
+        // the error is not passed to the app, which could rebuild the view again
+        if let Err(e) = result {
+            log::error!("unable to rebuild the view: {}", e);
+        }
     }
 
     /// This is called when an event is triggered in the html DOM.
@@ -119,54 +388,95 @@ where
     /// - update the app with msgs (use a request_idle_callback)
     /// - compute the view and update the dom (use request_animation_frame )
     fn dispatch_inner(&self, msgs: Vec<MSG>) {
-        #[cfg(feature = "with-measure")]
         let t1 = crate::now();
         #[cfg(feature = "with-measure")]
+        performance_mark(DISPATCH_MARKS[0]);
         let msg_count = msgs.len();
+        #[cfg(feature = "with-error-overlay")]
+        if let Some(msg_formatter) = self.msg_formatter.get() {
+            for msg in msgs.iter() {
+                crate::dom::error_overlay::record_msg(msg_formatter(msg));
+            }
+        }
         // update the app and emit the cmd returned from the update
         let all_cmd = msgs
             .into_iter()
             .map(|msg| self.app.borrow_mut().update(msg));
-        let cmd = Cmd::batch(all_cmd);
+        let mut cmd = Cmd::batch(all_cmd);
 
+        let mut total_patches = 0;
+        let mut error = None;
         if cmd.modifier.should_update_view {
             #[cfg(feature = "with-measure")]
             let t2 = crate::now();
+            #[cfg(feature = "with-measure")]
+            performance_mark(DISPATCH_MARKS[1]);
 
             // a new view is created due to the app update
-            let view = self.app.borrow().view();
+            let view = self.view();
 
             #[cfg(feature = "with-measure")]
             let node_count = view.node_count();
             #[cfg(feature = "with-measure")]
             let t3 = crate::now();
+            #[cfg(feature = "with-measure")]
+            performance_mark(DISPATCH_MARKS[2]);
+            #[cfg(feature = "with-measure")]
+            let _ = crate::dom::apply_patches::take_patch_timings();
 
+            // the queued reads, including the reads of the cmd, are done before the DOM
+            // is patched and the writes after, so they are not interleaved with the patches
+            cmd.emit_reads(self);
+            crate::dom::scheduler::flush_reads();
             // update the last DOM node tree with this new view
-            let _total_patches =
-                self.dom_updater.borrow_mut().update_dom(self, view);
+            let result = if cmd.modifier.with_view_transition
+                && view_transition::is_supported()
+            {
+                // the view is created again when the browser has captured the old view,
+                // since other updates may be dispatched in the meantime
+                let program = self.clone();
+                view_transition::start(move || program.update_view());
+                Ok(0)
+            } else {
+                self.update_dom(view)
+            };
+            match result {
+                Ok(patches) => total_patches = patches,
+                Err(e) => error = Some(e),
+            }
+            // the writes are held instead when the patches are applied in slices,
+            // they are executed after the last slice is applied
+            crate::dom::scheduler::flush_writes();
+            // the style may depend on the state of the app, ie: switching the theme
+            self.inject_styles();
             #[cfg(feature = "with-measure")]
             let t4 = crate::now();
+            #[cfg(feature = "with-measure")]
+            performance_mark(DISPATCH_MARKS[3]);
+            #[cfg(feature = "with-measure")]
+            let patch_timings = crate::dom::apply_patches::take_patch_timings();
 
             #[cfg(feature = "with-measure")]
             {
                 let dispatch_duration = t4 - t1;
-                // 60fps is 16.667 ms per frame.
-                if dispatch_duration > 16.0 {
+                if dispatch_duration > crate::dom::metrics::FRAME_BUDGET {
                     log::warn!("dispatch took: {}ms", dispatch_duration);
                 }
             }
 
             #[cfg(feature = "with-measure")]
-            if cmd.modifier.log_measurements && _total_patches > 0 {
+            if cmd.modifier.log_measurements && total_patches > 0 {
+                performance_measure(&cmd.modifier.measurement_name);
                 let measurements = Measurements {
                     name: cmd.modifier.measurement_name.clone(),
                     msg_count,
                     view_node_count: node_count,
                     update_dispatch_took: t2 - t1,
                     build_view_took: t3 - t2,
-                    total_patches: _total_patches,
+                    total_patches,
                     dom_update_took: t4 - t3,
                     total_time: t4 - t1,
+                    patch_timings,
                 };
                 // tell the app on app performance measurements
                 let cmd_measurement =
@@ -174,25 +484,168 @@ where
                 cmd_measurement.emit(self);
             }
         }
+        if cmd.modifier.should_update_head {
+            self.update_head();
+        }
+        #[cfg(feature = "with-measure")]
+        for mark_name in DISPATCH_MARKS.iter() {
+            crate::performance().clear_marks_with_mark_name(mark_name);
+        }
+        self.metrics.borrow_mut().record_dispatch(
+            msg_count,
+            cmd.modifier.should_update_view,
+            total_patches,
+            crate::now() - t1,
+        );
         cmd.emit(self);
+        // the reads and writes queued by the cmd are done in the same frame as the patches
+        crate::dom::scheduler::flush();
+        if let Some(error) = error {
+            self.handle_error(error);
+        }
+    }
+
+    /// Update the DOM with the `view`, returns the number of patches applied.
+    ///
+    /// The views above the time slicing threshold are updated in slices, returning right away,
+    /// while the views in the meantime are deferred until the update in slices is done.
+    fn update_dom(&self, view: crate::Node<MSG>) -> Result<usize, DomError> {
+        let mut dom_updater = self.dom_updater.borrow_mut();
+        if dom_updater.is_updating_in_slices() {
+            dom_updater.mark_stale();
+            return Ok(0);
+        }
+        if !dom_updater.should_slice(&view) {
+            return dom_updater.update_dom(self, view);
+        }
+        drop(dom_updater);
+        let program = self.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = dom_updater::update_dom_in_slices(
+                &program.dom_updater,
+                &program,
+                view,
+            )
+            .await;
+            if let Err(e) = result {
+                program.handle_error(e);
+            }
+            let is_stale = program.dom_updater.borrow_mut().take_stale();
+            if is_stale {
+                program.update_view();
+            }
+        });
+        Ok(0)
+    }
+
+    /// update the DOM with the current view of the app
+    fn update_view(&self) {
+        let view = self.view();
+        if let Err(e) = self.update_dom(view) {
+            self.handle_error(e);
+        }
+        self.inject_styles();
+        crate::dom::scheduler::flush();
+    }
+
+    /// the view of the app, with the scope class added into the root element
+    /// when the app has a scoped style
+    fn scoped_view(
+        app: &APP,
+        scope_class: &Option<String>,
+    ) -> crate::Node<MSG> {
+        let mut view = app.view();
+        if let (Some(scope_class), crate::Node::Element(element)) =
+            (scope_class, &mut view)
+        {
+            element.attrs.push(class(scope_class));
+        }
+        view
+    }
+
+    /// the view of the app, see [`scoped_view`](Self::scoped_view)
+    fn view(&self) -> crate::Node<MSG> {
+        Self::scoped_view(&self.app.borrow(), &self.scope_class)
+    }
+
+    /// apply the title and the tags of the head of the app into the head of the document
+    fn update_head(&self) {
+        crate::dom::head::apply(TypeId::of::<APP>(), self.app.borrow().head());
+    }
+
+    /// inject the styles of the app into the head of the document, or into the shadow root
+    /// when the app is mounted with [`mount_to_shadow`](Self::mount_to_shadow).
+    /// The styles are injected once when the app is mounted, then only when the output of the
+    /// style functions of the app has changed, ie: when the app switched its theme.
+    fn inject_styles(&self) {
+        let output = {
+            let app = self.app.borrow();
+            [app.style(), app.style_scoped(), app.style_print()]
+        };
+        let mut injected_styles = self.injected_styles.borrow_mut();
+        if injected_styles.as_ref() == Some(&output) {
+            return;
+        }
+        let [style, style_scoped, style_print] = &output;
+        let mut styles = vec![(StyleSlot::Style, style.to_string(), None)];
+        if let Some(scope_class) = self.scope_class.as_ref() {
+            let style_scoped =
+                scoped_style::scope_style(scope_class, style_scoped);
+            styles.push((StyleSlot::Scoped, style_scoped, None));
+        }
+        styles.push((StyleSlot::Print, style_print.to_string(), Some("print")));
+
+        let mut shadow_styles = self.shadow_styles.borrow_mut();
+        for (slot, style, media) in styles {
+            match shadow_styles.as_mut() {
+                Some(shadow_styles) => {
+                    shadow_styles.inject(slot, &style, media)
+                }
+                None => injected_style::inject_into_head(
+                    TypeId::of::<APP>(),
+                    slot,
+                    &style,
+                    media,
+                ),
+            }
+        }
+        *injected_styles = Some(output);
     }
+}
 
-    fn inject_style(type_id: TypeId, style: &str) {
-        use wasm_bindgen::JsCast;
-        dbg!(&type_id);
-        let type_id = format!("{:?}", type_id);
+/// add a mark into the timeline of the Performance panel of the browser
+#[cfg(feature = "with-measure")]
+fn performance_mark(mark_name: &str) {
+    if let Err(e) = crate::performance().mark(mark_name) {
+        log::warn!("unable to add the performance mark: {:?}", e);
+    }
+}
 
-        let document = crate::document();
-        let html_style = document
-            .create_element("style")
-            .expect("must be able to create style element");
-        html_style
-            .set_attribute("class", &type_id)
-            .expect("must set attribute");
-        let html_style: web_sys::Node = html_style.unchecked_into();
-        html_style.set_text_content(Some(style));
-        let head = document.head().expect("must have a head");
-        head.append_child(&html_style).expect("must append style");
+/// add the measures of the dispatch and each of its phases, named after the `measurement_name`,
+/// into the timeline of the Performance panel of the browser
+#[cfg(feature = "with-measure")]
+fn performance_measure(measurement_name: &str) {
+    let name = if measurement_name.is_empty() {
+        "dispatch"
+    } else {
+        measurement_name
+    };
+    let [start, update_end, view_end, patch_end] = DISPATCH_MARKS;
+    let measures = [
+        (name.to_string(), start, patch_end),
+        (format!("{} update", name), start, update_end),
+        (format!("{} view", name), update_end, view_end),
+        (format!("{} patch", name), view_end, patch_end),
+    ];
+    let performance = crate::performance();
+    for (measure_name, start_mark, end_mark) in measures.iter() {
+        if let Err(e) = performance.measure_with_start_mark_and_end_mark(
+            measure_name,
+            start_mark,
+            end_mark,
+        ) {
+            log::warn!("unable to add the performance measure: {:?}", e);
+        }
     }
 }
 
@@ -205,6 +658,12 @@ where
 {
     #[cfg(feature = "with-request-animation-frame")]
     fn dispatch_multiple(&self, msgs: Vec<MSG>) {
+        // msgs dispatched from an animation frame callback such as `Sub::on_animation_frame`
+        // are rendered in the same frame
+        if crate::dom::util::is_in_animation_frame() {
+            self.dispatch_inner(msgs);
+            return;
+        }
         let program_clone = self.clone();
         let closure_raf: Closure<dyn FnMut() + 'static> =
             Closure::once(move || {
@@ -222,4 +681,38 @@ where
     fn dispatch(&self, msg: MSG) {
         self.dispatch_multiple(vec![msg])
     }
+
+    /// the msg is processed in the current frame, before the msgs which are waiting
+    /// for the next animation frame and the idle msgs.
+    ///
+    /// When the app is being updated or the DOM is being patched, ie: a blur event fired by the
+    /// removal of the focused element, the msg is dispatched as a normal msg instead.
+    fn dispatch_urgent(&self, msg: MSG) {
+        if self.app.try_borrow_mut().is_err()
+            || self.dom_updater.try_borrow_mut().is_err()
+        {
+            self.dispatch(msg);
+            return;
+        }
+        self.dispatch_inner(vec![msg]);
+    }
+
+    /// the idle msgs are queued, then processed together in one update
+    /// when the browser is idle
+    fn dispatch_idle(&self, msg: MSG) {
+        let is_scheduled = {
+            let mut idle_msgs = self.idle_msgs.borrow_mut();
+            idle_msgs.push(msg);
+            idle_msgs.len() > 1
+        };
+        if is_scheduled {
+            return;
+        }
+        let program = self.clone();
+        crate::dom::util::request_idle_callback(move || {
+            let msgs: Vec<MSG> =
+                program.idle_msgs.borrow_mut().drain(..).collect();
+            program.dispatch_inner(msgs);
+        });
+    }
 }
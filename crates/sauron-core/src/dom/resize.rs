@@ -0,0 +1,110 @@
+//! The elements with the [`on_resize_element`] listener are observed by one `ResizeObserver`
+//! shared by all the programs, which dispatches the `resizeelement` event to the element
+//! when the size of its content box changes.
+//!
+//! The element is unobserved when its listener is removed, which is also when
+//! the element is removed by a patch, the same as the closures of its other listeners.
+//!
+//! The `ResizeObserver` is called dynamically, since it is still an unstable api in web-sys.
+//!
+//! [`on_resize_element`]: crate::dom::events::on_resize_element
+use crate::dom::created_node::DATA_VDOM_ID;
+use js_sys::{Array, Function, Reflect};
+use std::{cell::RefCell, collections::HashMap};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::Element;
+
+/// the event dispatched when the size of the element changes
+pub(crate) const RESIZE_ELEMENT: &str = "resizeelement";
+
+/// the callback of the observer, which is kept alive as long as the observer
+type ObserverCallback = Closure<dyn FnMut(Array)>;
+
+thread_local!(static OBSERVER: Option<(JsValue, ObserverCallback)> = create_observer());
+thread_local!(static OBSERVED: RefCell<HashMap<usize, Element>> = RefCell::new(HashMap::new()));
+
+/// `None` if the browser doesn't support the `ResizeObserver`
+fn create_observer() -> Option<(JsValue, ObserverCallback)> {
+    let window = crate::window();
+    let constructor: Function =
+        Reflect::get(&window, &JsValue::from_str("ResizeObserver"))
+            .ok()
+            .filter(|constructor| constructor.is_function())?
+            .unchecked_into();
+    let callback: ObserverCallback = Closure::wrap(Box::new(notify));
+    let observer = Reflect::construct(
+        &constructor,
+        &Array::of1(callback.as_ref().unchecked_ref()),
+    )
+    .expect("must create a ResizeObserver");
+    Some((observer, callback))
+}
+
+/// call the `method` of the observer with the element
+fn call_observer(method: &str, element: &Element) {
+    OBSERVER.with(|observer| match observer {
+        Some((observer, _)) => {
+            let function: Function =
+                Reflect::get(observer, &JsValue::from_str(method))
+                    .expect("must have the method")
+                    .unchecked_into();
+            function
+                .call1(observer, element)
+                .expect("must call the method");
+        }
+        None => log::warn!("ResizeObserver is not supported"),
+    });
+}
+
+/// start observing the element, if it's not observed yet
+pub(crate) fn observe(vdom_id: usize, element: &Element) {
+    let is_observed = OBSERVED.with(|observed| {
+        observed.borrow_mut().insert(vdom_id, element.clone())
+    });
+    if is_observed.is_none() {
+        call_observer("observe", element);
+    }
+}
+
+/// stop observing the element with the `vdom_id`, if it is observed
+pub(crate) fn unobserve(vdom_id: usize) {
+    let element =
+        OBSERVED.with(|observed| observed.borrow_mut().remove(&vdom_id));
+    if let Some(element) = element {
+        call_observer("unobserve", &element);
+    }
+}
+
+/// dispatch the event with the content rect of the entry to its element
+fn notify(entries: Array) {
+    for entry in entries.iter() {
+        let target: Element =
+            Reflect::get(&entry, &JsValue::from_str("target"))
+                .expect("must have a target")
+                .unchecked_into();
+        let is_observed = target
+            .get_attribute(DATA_VDOM_ID)
+            .and_then(|vdom_id| vdom_id.parse::<usize>().ok())
+            .map(|vdom_id| {
+                OBSERVED
+                    .with(|observed| observed.borrow().contains_key(&vdom_id))
+            })
+            .unwrap_or(false);
+        if !is_observed {
+            continue;
+        }
+        let content_rect =
+            Reflect::get(&entry, &JsValue::from_str("contentRect"))
+                .expect("must have a content rect");
+        let mut event_init = web_sys::CustomEventInit::new();
+        event_init.detail(&content_rect);
+        let event = web_sys::CustomEvent::new_with_event_init_dict(
+            RESIZE_ELEMENT,
+            &event_init,
+        )
+        .expect("must create the resize event");
+        if let Err(e) = target.dispatch_event(&event) {
+            log::error!("unable to dispatch {}: {:?}", RESIZE_ELEMENT, e);
+        }
+    }
+}
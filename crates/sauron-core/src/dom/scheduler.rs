@@ -0,0 +1,104 @@
+//! batches the reads and the writes of the DOM which are queued with
+//! [`Cmd::read_phase`](crate::dom::cmd::Cmd::read_phase) and
+//! [`Cmd::write_phase`](crate::dom::cmd::Cmd::write_phase), such as
+//! [`Cmd::dom_read`](crate::dom::cmd::Cmd::dom_read) and
+//! [`Cmd::dom_write`](crate::dom::cmd::Cmd::dom_write).
+//!
+//! All the queued reads are executed before all the queued writes, so the layout is only computed
+//! once instead of on every read which follows a write. The queues are flushed by the program
+//! around the patching of the DOM: the reads before the patches are applied and the writes
+//! after. Anything queued outside of the update loop is flushed on the next animation frame.
+//!
+//! While the patches are applied in slices across multiple frames, the writes are held
+//! and only executed after the last slice is applied.
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+};
+
+type Task = Box<dyn FnOnce()>;
+
+thread_local!(static READS: RefCell<VecDeque<Task>> = RefCell::new(VecDeque::new()));
+thread_local!(static WRITES: RefCell<VecDeque<Task>> = RefCell::new(VecDeque::new()));
+thread_local!(static IS_FLUSH_SCHEDULED: Cell<bool> = Cell::new(false));
+thread_local!(static IS_HOLDING_WRITES: Cell<bool> = Cell::new(false));
+
+/// queue the function which reads from the DOM, ie: the size of an element
+pub(crate) fn read<F>(f: F)
+where
+    F: FnOnce() + 'static,
+{
+    READS.with(|reads| reads.borrow_mut().push_back(Box::new(f)));
+    schedule_flush();
+}
+
+/// queue the function which writes into the DOM, ie: the style of an element
+pub(crate) fn write<F>(f: F)
+where
+    F: FnOnce() + 'static,
+{
+    WRITES.with(|writes| writes.borrow_mut().push_back(Box::new(f)));
+    schedule_flush();
+}
+
+/// execute the queued reads, including the reads queued while executing them
+pub(crate) fn flush_reads() {
+    while let Some(task) = READS.with(|reads| reads.borrow_mut().pop_front()) {
+        task();
+    }
+}
+
+/// execute the queued writes, including the writes queued while executing them,
+/// unless the writes are held while the patches are applied in slices
+pub(crate) fn flush_writes() {
+    if is_holding_writes() {
+        return;
+    }
+    while let Some(task) = WRITES.with(|writes| writes.borrow_mut().pop_front())
+    {
+        task();
+    }
+}
+
+/// execute all the queued reads and then all the queued writes,
+/// until there are no more reads and writes queued
+pub(crate) fn flush() {
+    while has_pending() {
+        flush_reads();
+        flush_writes();
+    }
+}
+
+/// hold the queued writes until [`release_writes`] is called,
+/// so they are not executed before the patches which are applied in slices
+pub(crate) fn hold_writes() {
+    IS_HOLDING_WRITES.with(|is_holding| is_holding.set(true));
+}
+
+/// release the writes held with [`hold_writes`] and execute them,
+/// this is called after the last slice of the patches is applied
+pub(crate) fn release_writes() {
+    IS_HOLDING_WRITES.with(|is_holding| is_holding.set(false));
+    flush();
+}
+
+fn is_holding_writes() -> bool {
+    IS_HOLDING_WRITES.with(|is_holding| is_holding.get())
+}
+
+fn has_pending() -> bool {
+    READS.with(|reads| !reads.borrow().is_empty())
+        || (!is_holding_writes()
+            && WRITES.with(|writes| !writes.borrow().is_empty()))
+}
+
+/// flush the queues on the next animation frame, if it is not scheduled yet
+fn schedule_flush() {
+    if IS_FLUSH_SCHEDULED.with(|is_scheduled| is_scheduled.replace(true)) {
+        return;
+    }
+    crate::dom::util::request_animation_frame(|| {
+        IS_FLUSH_SCHEDULED.with(|is_scheduled| is_scheduled.set(false));
+        flush();
+    });
+}
@@ -0,0 +1,214 @@
+//! provides the registration of the service worker of a progressive web app,
+//! and the notifications when a new version of the app is available.
+//!
+//! # Examples
+//! ```rust,ignore
+//! // in the init of the app
+//! Cmd::register_service_worker("/sw.js", Msg::ServiceWorker)
+//!
+//! // in the update
+//! Msg::ServiceWorker(ServiceWorkerEvent::UpdateReady) => {
+//!     self.is_update_ready = true;
+//!     Cmd::none()
+//! }
+//! Msg::Reload => Cmd::reload_for_update(),
+//!
+//! // in the view
+//! if self.is_update_ready {
+//!     update_prompt("A new version is available", "Reload", Msg::Reload)
+//! }
+//! ```
+//!
+//! The service worker script needs to skip waiting when it receives the [`SKIP_WAITING`] message,
+//! so the new version takes over the page when it is reloaded:
+//! ```js
+//! self.addEventListener("message", (event) => {
+//!     if (event.data === "sauron-skip-waiting") {
+//!         self.skipWaiting();
+//!     }
+//! });
+//! ```
+use crate::{
+    dom::{cmd::Cmd, Dispatch},
+    html::{
+        attributes::{attr, class},
+        button, div,
+        events::on_click,
+        span, text,
+    },
+    Node,
+};
+use std::rc::Rc;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    ServiceWorkerContainer, ServiceWorkerRegistration, ServiceWorkerState,
+};
+
+/// the message posted to the waiting service worker to make it take over the page
+pub const SKIP_WAITING: &str = "sauron-skip-waiting";
+
+/// The progress of the registration of the service worker
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ServiceWorkerEvent {
+    /// the browser doesn't support service workers, or the page is not served over https
+    Unsupported,
+    /// the service worker is registered
+    Registered,
+    /// the registration failed, ie: the script can not be fetched or has an error
+    Failed(String),
+    /// a new service worker is being installed, this is also emitted on the first install
+    UpdateFound,
+    /// a new version is installed and is waiting for the page to be reloaded,
+    /// this is not emitted on the first install
+    UpdateReady,
+}
+
+impl<DSP> Cmd<DSP>
+where
+    DSP: 'static,
+{
+    /// Creates a Cmd which registers the service worker script at `url`,
+    /// emitting the MSG for every [`ServiceWorkerEvent`] of the registration.
+    pub fn register_service_worker<F, MSG>(url: &str, f: F) -> Self
+    where
+        F: Fn(ServiceWorkerEvent) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        let url = url.to_string();
+        Cmd::new_async(move |program: DSP| async move {
+            register(&url, move |event| program.dispatch(f(event))).await
+        })
+        .no_render()
+    }
+
+    /// Creates a Cmd which activates the waiting service worker and reloads the page
+    /// once it takes over, the page is reloaded right away if there is no waiting service worker.
+    pub fn reload_for_update() -> Self {
+        Cmd::new_async(|_program| skip_waiting()).no_render()
+    }
+}
+
+/// A prompt with the `message` and a button with the `label` which emits the `reload` MSG,
+/// shown when a new version of the app is ready.
+pub fn update_prompt<MSG>(message: &str, label: &str, reload: MSG) -> Node<MSG>
+where
+    MSG: Clone + 'static,
+{
+    div(
+        vec![class("sauron-update-prompt"), attr("role", "alert")],
+        vec![
+            span(vec![], vec![text(message)]),
+            button(vec![on_click(move |_| reload.clone())], vec![text(label)]),
+        ],
+    )
+}
+
+/// the service worker container of the browser,
+/// `None` if not supported or not in a secure context
+fn container() -> Option<ServiceWorkerContainer> {
+    let navigator = crate::window().navigator();
+    let is_supported =
+        js_sys::Reflect::has(&navigator, &JsValue::from_str("serviceWorker"))
+            .unwrap_or(false);
+    if is_supported {
+        Some(navigator.service_worker())
+    } else {
+        None
+    }
+}
+
+/// a page which is not yet controlled by a service worker is on its first install
+fn is_controlled() -> bool {
+    container()
+        .map(|container| container.controller().is_some())
+        .unwrap_or(false)
+}
+
+async fn register<F>(url: &str, notify: F)
+where
+    F: Fn(ServiceWorkerEvent) + 'static,
+{
+    let container = match container() {
+        Some(container) => container,
+        None => {
+            notify(ServiceWorkerEvent::Unsupported);
+            return;
+        }
+    };
+    let registration: ServiceWorkerRegistration =
+        match JsFuture::from(container.register(url)).await {
+            Ok(registration) => registration.unchecked_into(),
+            Err(e) => {
+                notify(ServiceWorkerEvent::Failed(format!("{:?}", e)));
+                return;
+            }
+        };
+    notify(ServiceWorkerEvent::Registered);
+    // the update was installed while the app was not open
+    if registration.waiting().is_some() && is_controlled() {
+        notify(ServiceWorkerEvent::UpdateReady);
+    }
+
+    let notify = Rc::new(notify);
+    let installing_registration = registration.clone();
+    // the registration lives as long as the page, so are the listeners
+    let on_update_found: Closure<dyn FnMut()> =
+        Closure::wrap(Box::new(move || {
+            notify(ServiceWorkerEvent::UpdateFound);
+            if let Some(installing) = installing_registration.installing() {
+                let worker = installing.clone();
+                let notify = Rc::clone(&notify);
+                let on_state_change: Closure<dyn FnMut()> =
+                    Closure::wrap(Box::new(move || {
+                        if worker.state() == ServiceWorkerState::Installed
+                            && is_controlled()
+                        {
+                            notify(ServiceWorkerEvent::UpdateReady);
+                        }
+                    }));
+                installing.set_onstatechange(Some(
+                    on_state_change.as_ref().unchecked_ref(),
+                ));
+                on_state_change.forget();
+            }
+        }));
+    registration
+        .set_onupdatefound(Some(on_update_found.as_ref().unchecked_ref()));
+    on_update_found.forget();
+}
+
+async fn skip_waiting() {
+    let reload = || {
+        if let Err(e) = crate::window().location().reload() {
+            log::error!("unable to reload the page: {:?}", e);
+        }
+    };
+    let container = match container() {
+        Some(container) => container,
+        None => return reload(),
+    };
+    let waiting = JsFuture::from(container.get_registration())
+        .await
+        .ok()
+        .filter(|registration| !registration.is_undefined())
+        .and_then(|registration| {
+            registration
+                .unchecked_into::<ServiceWorkerRegistration>()
+                .waiting()
+        });
+    let waiting = match waiting {
+        Some(waiting) => waiting,
+        None => return reload(),
+    };
+    let on_controller_change: Closure<dyn FnMut()> = Closure::once(reload);
+    container.set_oncontrollerchange(Some(
+        on_controller_change.as_ref().unchecked_ref(),
+    ));
+    on_controller_change.forget();
+    if let Err(e) = waiting.post_message(&JsValue::from_str(SKIP_WAITING)) {
+        log::error!("unable to activate the service worker: {:?}", e);
+        reload();
+    }
+}
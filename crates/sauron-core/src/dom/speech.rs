@@ -0,0 +1,197 @@
+//! provides wrappers to the speech synthesis and speech recognition of the browser.
+//!
+use crate::dom::{
+    cmd::Cmd,
+    sub::{Sub, Subscription},
+    Dispatch,
+};
+use js_sys::{Function, Reflect};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{
+    SpeechRecognition, SpeechRecognitionEvent, SpeechSynthesisUtterance,
+};
+
+/// The voice used in [`Cmd::speak`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoiceOptions {
+    /// the language of the text, ie: `en-US`, defaults to the language of the document
+    pub lang: Option<String>,
+    /// the name of the voice to use, the default voice is used if there is no voice of this name
+    pub voice_name: Option<String>,
+    /// the speed of the speech, from `0.1` to `10.0`
+    pub rate: f32,
+    /// the pitch of the speech, from `0.0` to `2.0`
+    pub pitch: f32,
+    /// the volume of the speech, from `0.0` to `1.0`
+    pub volume: f32,
+}
+
+impl Default for VoiceOptions {
+    fn default() -> Self {
+        VoiceOptions {
+            lang: None,
+            voice_name: None,
+            rate: 1.0,
+            pitch: 1.0,
+            volume: 1.0,
+        }
+    }
+}
+
+/// The text recognized from the speech of the user
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpeechResult {
+    /// the recognized text
+    pub transcript: String,
+    /// how confident the recognizer is with the transcript, from `0.0` to `1.0`
+    pub confidence: f32,
+    /// false if this is an interim result, which may still change as the user speaks
+    pub is_final: bool,
+}
+
+/// returns true if the browser supports speech synthesis
+pub fn is_synthesis_available() -> bool {
+    crate::window().speech_synthesis().is_ok()
+}
+
+/// returns true if the browser supports speech recognition
+pub fn is_recognition_available() -> bool {
+    recognition_constructor().is_some()
+}
+
+/// the constructor of speech recognition, which is still prefixed in some browsers
+fn recognition_constructor() -> Option<Function> {
+    let window = crate::window();
+    ["SpeechRecognition", "webkitSpeechRecognition"]
+        .iter()
+        .filter_map(|name| Reflect::get(&window, &JsValue::from_str(name)).ok())
+        .find(|constructor| constructor.is_function())
+        .map(|constructor| constructor.unchecked_into())
+}
+
+impl<DSP> Cmd<DSP>
+where
+    DSP: 'static,
+{
+    /// Creates a Cmd which speaks the `text` using the speech synthesis of the browser
+    pub fn speak(text: &str, options: VoiceOptions) -> Self {
+        let text = text.to_string();
+        Cmd::new(move |_program| {
+            let synthesis = match crate::window().speech_synthesis() {
+                Ok(synthesis) => synthesis,
+                Err(_) => {
+                    log::warn!("speech synthesis is not available");
+                    return;
+                }
+            };
+            let utterance = SpeechSynthesisUtterance::new_with_text(&text)
+                .expect("must create an utterance");
+            if let Some(lang) = &options.lang {
+                utterance.set_lang(lang);
+            }
+            if let Some(voice_name) = &options.voice_name {
+                let voice = synthesis
+                    .get_voices()
+                    .iter()
+                    .map(|voice| {
+                        voice.unchecked_into::<web_sys::SpeechSynthesisVoice>()
+                    })
+                    .find(|voice| voice.name() == *voice_name);
+                utterance.set_voice(voice.as_ref());
+            }
+            utterance.set_rate(options.rate);
+            utterance.set_pitch(options.pitch);
+            utterance.set_volume(options.volume);
+            synthesis.speak(&utterance);
+        })
+        .no_render()
+    }
+}
+
+impl<DSP> Sub<DSP>
+where
+    DSP: 'static,
+{
+    /// Creates a Sub which continuously recognize the speech of the user in the language `lang`,
+    /// ie: `en-US`, emitting the MSG for every interim and final result.
+    ///
+    /// The browser asks the user for the permission to use the microphone when subscribed.
+    pub fn on_speech_result<F, MSG>(lang: &str, f: F) -> Self
+    where
+        F: Fn(SpeechResult) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        let lang = lang.to_string();
+        Sub::new(move |program: DSP| {
+            let constructor = match recognition_constructor() {
+                Some(constructor) => constructor,
+                None => {
+                    log::warn!("speech recognition is not available");
+                    return Subscription::none();
+                }
+            };
+            let recognition: SpeechRecognition =
+                Reflect::construct(&constructor, &js_sys::Array::new())
+                    .expect("must create a speech recognition")
+                    .unchecked_into();
+            recognition.set_lang(&lang);
+            recognition.set_continuous(true);
+            recognition.set_interim_results(true);
+
+            let on_result: Closure<dyn FnMut(SpeechRecognitionEvent)> =
+                Closure::wrap(Box::new(
+                    move |event: SpeechRecognitionEvent| {
+                        let results = match event.results() {
+                            Some(results) => results,
+                            None => return,
+                        };
+                        for i in event.result_index()..results.length() {
+                            let result = match results.get(i) {
+                                Some(result) => result,
+                                None => continue,
+                            };
+                            if let Some(alternative) = result.get(0) {
+                                program.dispatch(f(SpeechResult {
+                                    transcript: alternative.transcript(),
+                                    confidence: alternative.confidence(),
+                                    is_final: result.is_final(),
+                                }));
+                            }
+                        }
+                    },
+                ));
+            recognition.set_onresult(Some(on_result.as_ref().unchecked_ref()));
+
+            // the recognition stops by itself after a period of silence,
+            // so it is restarted until unsubscribed
+            let is_subscribed = Rc::new(Cell::new(true));
+            let on_end: Closure<dyn FnMut()> = Closure::wrap(Box::new({
+                let recognition = recognition.clone();
+                let is_subscribed = Rc::clone(&is_subscribed);
+                move || {
+                    if is_subscribed.get() {
+                        if let Err(e) = recognition.start() {
+                            log::warn!("unable to restart the speech recognition: {:?}", e);
+                        }
+                    }
+                }
+            }));
+            recognition.set_onend(Some(on_end.as_ref().unchecked_ref()));
+
+            if let Err(e) = recognition.start() {
+                log::warn!("unable to start the speech recognition: {:?}", e);
+            }
+
+            Subscription::new(move || {
+                is_subscribed.set(false);
+                recognition.set_onresult(None);
+                recognition.set_onend(None);
+                recognition.abort();
+                drop(on_result);
+                drop(on_end);
+            })
+        })
+    }
+}
@@ -0,0 +1,202 @@
+//! provides typed access to the localStorage and sessionStorage of the browser,
+//! the values are stored as json.
+//!
+//! Use [`indexeddb`] for the larger data, ie: the records of an offline capable app.
+//!
+use crate::dom::{
+    cmd::Cmd,
+    sub::{Sub, Subscription},
+    Dispatch,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+pub mod indexeddb;
+
+/// Errors when accessing the storage
+#[derive(Debug, Error)]
+pub enum StorageError {
+    /// the storage is not available, ie: disabled in private browsing mode
+    #[error("storage is not available")]
+    Unavailable,
+    /// the storage rejected the operation, ie: the quota has been exceeded
+    #[error("storage error: {0}")]
+    Js(String),
+    /// the value can not be serialized or deserialized
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl From<JsValue> for StorageError {
+    fn from(js_value: JsValue) -> Self {
+        StorageError::Js(format!("{:?}", js_value))
+    }
+}
+
+/// The storage where the value is stored
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Area {
+    Local,
+    Session,
+}
+
+impl Area {
+    fn storage(&self) -> Result<web_sys::Storage, StorageError> {
+        let window = crate::window();
+        let storage = match self {
+            Area::Local => window.local_storage(),
+            Area::Session => window.session_storage(),
+        };
+        storage.ok().flatten().ok_or(StorageError::Unavailable)
+    }
+
+    fn get<T>(&self, key: &str) -> Result<Option<T>, StorageError>
+    where
+        T: DeserializeOwned,
+    {
+        match self.storage()?.get_item(key)? {
+            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn set<T>(&self, key: &str, value: &T) -> Result<(), StorageError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let json = serde_json::to_string(value)?;
+        self.storage()?.set_item(key, &json)?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), StorageError> {
+        self.storage()?.remove_item(key)?;
+        Ok(())
+    }
+
+    fn save<T, DSP>(&self, key: &str, value: &T) -> Cmd<DSP>
+    where
+        T: Serialize + ?Sized,
+        DSP: 'static,
+    {
+        let area = *self;
+        let key = key.to_string();
+        let json = serde_json::to_string(value);
+        Cmd::new(move |_program| {
+            let saved = json.map_err(StorageError::from).and_then(|json| {
+                area.storage()?.set_item(&key, &json)?;
+                Ok(())
+            });
+            if let Err(e) = saved {
+                log::error!("unable to save {}: {}", key, e);
+            }
+        })
+        .no_render()
+    }
+}
+
+macro_rules! declare_area {
+    ($area:ident) => {
+        /// returns the value of the `key` deserialized from json,
+        /// `None` if there is no value stored
+        pub fn get<T>(key: &str) -> Result<Option<T>, StorageError>
+        where
+            T: DeserializeOwned,
+        {
+            Area::$area.get(key)
+        }
+
+        /// store the `value` serialized into json
+        pub fn set<T>(key: &str, value: &T) -> Result<(), StorageError>
+        where
+            T: Serialize + ?Sized,
+        {
+            Area::$area.set(key, value)
+        }
+
+        /// remove the value of the `key`
+        pub fn remove(key: &str) -> Result<(), StorageError> {
+            Area::$area.remove(key)
+        }
+
+        /// Creates a Cmd which stores the `value` when emitted,
+        /// the value is serialized right away so it captures the current state of the app.
+        pub fn save<T, DSP>(key: &str, value: &T) -> Cmd<DSP>
+        where
+            T: Serialize + ?Sized,
+            DSP: 'static,
+        {
+            Area::$area.save(key, value)
+        }
+    };
+}
+
+/// typed access to the localStorage, which persists across browser sessions
+pub mod local {
+    use super::*;
+
+    declare_area!(Local);
+
+    /// Creates a Sub which emits the MSG with the new value of the `key`
+    /// whenever it is changed from another tab or window of the same origin,
+    /// the value is `None` when it is removed or can not be deserialized.
+    pub fn subscribe_changes<T, F, DSP, MSG>(key: &str, f: F) -> Sub<DSP>
+    where
+        T: DeserializeOwned,
+        F: Fn(Option<T>) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let key = key.to_string();
+        Sub::new(move |program: DSP| {
+            let listener: Closure<dyn FnMut(web_sys::StorageEvent)> =
+                Closure::wrap(Box::new(move |event: web_sys::StorageEvent| {
+                    // the key is `None` when the whole storage is cleared
+                    let is_key = event
+                        .key()
+                        .map(|event_key| event_key == key)
+                        .unwrap_or(true);
+                    let is_local =
+                        event.storage_area() == Area::Local.storage().ok();
+                    if !is_key || !is_local {
+                        return;
+                    }
+                    let value = event.new_value().and_then(|json| {
+                        serde_json::from_str(&json)
+                            .map_err(|e| {
+                                log::warn!(
+                                    "unable to deserialize {}: {}",
+                                    key,
+                                    e
+                                )
+                            })
+                            .ok()
+                    });
+                    program.dispatch(f(value));
+                }));
+            let window = crate::window();
+            window
+                .add_event_listener_with_callback(
+                    "storage",
+                    listener.as_ref().unchecked_ref(),
+                )
+                .expect("must add the storage listener");
+            Subscription::new(move || {
+                window
+                    .remove_event_listener_with_callback(
+                        "storage",
+                        listener.as_ref().unchecked_ref(),
+                    )
+                    .expect("must remove the storage listener");
+            })
+        })
+    }
+}
+
+/// typed access to the sessionStorage, which is cleared when the browser tab is closed
+pub mod session {
+    use super::*;
+
+    declare_area!(Session);
+}
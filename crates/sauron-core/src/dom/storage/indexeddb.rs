@@ -0,0 +1,291 @@
+//! provides typed access to the IndexedDB of the browser, which can hold a lot more data
+//! than the localStorage, ie: the records of an offline capable app.
+//!
+//! The values are stored as json with string keys, and every operation is a Cmd which
+//! dispatch the MSG created from the result once the operation is done.
+//!
+//! # Examples
+//! ```rust,ignore
+//! let db = Database::new("my-app", 1).with_store("drafts");
+//!
+//! // in the update
+//! Msg::SaveDraft(draft) => db.put("drafts", &draft.id, &draft, Msg::DraftSaved),
+//! Msg::LoadDrafts => db.iterate("drafts", Msg::DraftsLoaded),
+//! ```
+use super::StorageError;
+use crate::dom::{cmd::Cmd, Dispatch};
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbObjectStore, IdbRequest, IdbTransactionMode};
+
+/// An IndexedDB database and the object stores it has,
+/// the object stores are created when the database is opened with a newer version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Database {
+    name: String,
+    version: u32,
+    stores: Vec<String>,
+}
+
+impl Database {
+    /// the database with `name`, bump the `version` when adding object stores
+    pub fn new(name: &str, version: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            version,
+            stores: vec![],
+        }
+    }
+
+    /// add an object store which is created when it doesn't exist yet
+    pub fn with_store(mut self, store: &str) -> Self {
+        self.stores.push(store.to_string());
+        self
+    }
+
+    /// Creates a Cmd which opens the database and creates the missing object stores,
+    /// the operations open the database themselves, this is for checking the
+    /// availability of the IndexedDB when the app starts.
+    pub fn open<DSP, MSG, CB>(&self, cb: CB) -> Cmd<DSP>
+    where
+        CB: FnOnce(Result<(), StorageError>) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let db = self.clone();
+        Cmd::try_from_async(
+            async move { db.connect().await.map(|db| db.close()) },
+            cb,
+        )
+    }
+
+    /// Creates a Cmd which stores the `value` at the `key`,
+    /// the value is serialized right away so it captures the current state of the app.
+    pub fn put<T, DSP, MSG, CB>(
+        &self,
+        store: &str,
+        key: &str,
+        value: &T,
+        cb: CB,
+    ) -> Cmd<DSP>
+    where
+        T: Serialize + ?Sized,
+        CB: FnOnce(Result<(), StorageError>) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let db = self.clone();
+        let store = store.to_string();
+        let key = JsValue::from_str(key);
+        let json = serde_json::to_string(value);
+        Cmd::try_from_async(
+            async move {
+                match json {
+                    Ok(json) => db
+                        .with_store(
+                            &store,
+                            IdbTransactionMode::Readwrite,
+                            |store| {
+                                store.put_with_key(
+                                    &JsValue::from_str(&json),
+                                    &key,
+                                )
+                            },
+                        )
+                        .await
+                        .map(|_| ()),
+                    Err(e) => Err(StorageError::from(e)),
+                }
+            },
+            cb,
+        )
+    }
+
+    /// Creates a Cmd which retrieves the value of the `key` deserialized from json,
+    /// `None` if there is no value stored.
+    pub fn get<T, DSP, MSG, CB>(
+        &self,
+        store: &str,
+        key: &str,
+        cb: CB,
+    ) -> Cmd<DSP>
+    where
+        T: DeserializeOwned + 'static,
+        CB: FnOnce(Result<Option<T>, StorageError>) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let db = self.clone();
+        let store = store.to_string();
+        let key = JsValue::from_str(key);
+        Cmd::try_from_async(async move { db.get_value(&store, &key).await }, cb)
+    }
+
+    /// Creates a Cmd which removes the value of the `key`
+    pub fn delete<DSP, MSG, CB>(
+        &self,
+        store: &str,
+        key: &str,
+        cb: CB,
+    ) -> Cmd<DSP>
+    where
+        CB: FnOnce(Result<(), StorageError>) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let db = self.clone();
+        let store = store.to_string();
+        let key = JsValue::from_str(key);
+        Cmd::try_from_async(
+            async move {
+                db.with_store(&store, IdbTransactionMode::Readwrite, |store| {
+                    store.delete(&key)
+                })
+                .await
+                .map(|_| ())
+            },
+            cb,
+        )
+    }
+
+    /// Creates a Cmd which retrieves all the keys and values of the object store,
+    /// ordered by their key.
+    pub fn iterate<T, DSP, MSG, CB>(&self, store: &str, cb: CB) -> Cmd<DSP>
+    where
+        T: DeserializeOwned + 'static,
+        CB: FnOnce(Result<Vec<(String, T)>, StorageError>) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let db = self.clone();
+        let store = store.to_string();
+        Cmd::try_from_async(async move { db.get_all(&store).await }, cb)
+    }
+
+    /// retrieve the value of the `key` deserialized from json
+    async fn get_value<T>(
+        &self,
+        store: &str,
+        key: &JsValue,
+    ) -> Result<Option<T>, StorageError>
+    where
+        T: DeserializeOwned,
+    {
+        let value = self
+            .with_store(store, IdbTransactionMode::Readonly, |store| {
+                store.get(key)
+            })
+            .await?;
+        match value.as_string() {
+            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// retrieve all the keys and their values deserialized from json
+    async fn get_all<T>(
+        &self,
+        store: &str,
+    ) -> Result<Vec<(String, T)>, StorageError>
+    where
+        T: DeserializeOwned,
+    {
+        let keys: js_sys::Array = self
+            .with_store(store, IdbTransactionMode::Readonly, |store| {
+                store.get_all_keys()
+            })
+            .await?
+            .unchecked_into();
+        let values: js_sys::Array = self
+            .with_store(store, IdbTransactionMode::Readonly, |store| {
+                store.get_all()
+            })
+            .await?
+            .unchecked_into();
+        let mut entries = vec![];
+        for (key, value) in keys.iter().zip(values.iter()) {
+            let json = value.as_string().unwrap_or_default();
+            entries.push((
+                key.as_string().unwrap_or_default(),
+                serde_json::from_str(&json)?,
+            ));
+        }
+        Ok(entries)
+    }
+
+    /// open the database, creating the missing object stores on upgrade
+    async fn connect(&self) -> Result<IdbDatabase, StorageError> {
+        let factory = crate::window()
+            .indexed_db()
+            .ok()
+            .flatten()
+            .ok_or(StorageError::Unavailable)?;
+        let request = factory.open_with_u32(&self.name, self.version)?;
+        let stores = self.stores.clone();
+        let upgrade_request = request.clone();
+        let on_upgrade: Closure<dyn FnMut(web_sys::Event)> =
+            Closure::wrap(Box::new(move |_event: web_sys::Event| {
+                let db: IdbDatabase = upgrade_request
+                    .result()
+                    .expect("must have the database")
+                    .unchecked_into();
+                for store in stores.iter() {
+                    if !db.object_store_names().contains(store) {
+                        if let Err(e) = db.create_object_store(store) {
+                            log::error!("unable to create {}: {:?}", store, e);
+                        }
+                    }
+                }
+            }));
+        request.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+        let db = wait_for(&request).await;
+        request.set_onupgradeneeded(None);
+        Ok(db?.unchecked_into())
+    }
+
+    /// run the request created from the object store in its own transaction
+    async fn with_store<F>(
+        &self,
+        store: &str,
+        mode: IdbTransactionMode,
+        f: F,
+    ) -> Result<JsValue, StorageError>
+    where
+        F: FnOnce(&IdbObjectStore) -> Result<IdbRequest, JsValue>,
+    {
+        let db = self.connect().await?;
+        let result = match db
+            .transaction_with_str_and_mode(store, mode)
+            .and_then(|transaction| transaction.object_store(store))
+            .and_then(|store| f(&store))
+        {
+            Ok(request) => wait_for(&request).await,
+            Err(e) => Err(e.into()),
+        };
+        db.close();
+        result
+    }
+}
+
+/// wait for the request to succeed and return its result
+async fn wait_for(request: &IdbRequest) -> Result<JsValue, StorageError> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+    let done = JsFuture::from(promise).await;
+    request.set_onsuccess(None);
+    request.set_onerror(None);
+    match done {
+        Ok(_) => Ok(request.result()?),
+        Err(_) => Err(StorageError::Js(
+            request
+                .error()
+                .ok()
+                .flatten()
+                .map(|e| e.message())
+                .unwrap_or_else(|| "request failed".to_string()),
+        )),
+    }
+}
@@ -0,0 +1,706 @@
+//! provides functionalities for subscriptions, which listens to events outside of the view
+//! such as scrolling of the page, and dispatch the MSG into the program whenever
+//! the event happens.
+//!
+#[cfg(feature = "with-persist")]
+use crate::dom::broadcast;
+use crate::dom::{capabilities, cmd::Cmd, timer, util, Dispatch, Window};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{IntersectionObserver, IntersectionObserverEntry};
+
+/// Sub is a subscription to events that happens outside of the view.
+/// Subscriptions are started using the program, which returns the [`Subscription`]
+/// handles that can be used to stop listening to the events.
+pub struct Sub<DSP> {
+    /// the functions which starts listening to the events when this Sub is subscribed
+    pub subscribers: Vec<Box<dyn FnOnce(DSP) -> Subscription>>,
+    /// whether each of the timers in this Sub is paused while the document is hidden
+    pause_when_hidden: Vec<Rc<Cell<bool>>>,
+}
+
+/// A handle to an active subscription,
+/// calling `unsubscribe` stops listening to the events
+pub struct Subscription {
+    unsubscribe: Option<Box<dyn FnOnce()>>,
+}
+
+impl Subscription {
+    /// creates a subscription which calls the function `f` when unsubscribed
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnOnce() + 'static,
+    {
+        Self {
+            unsubscribe: Some(Box::new(f)),
+        }
+    }
+
+    /// A subscription which has nothing to clean up
+    pub fn none() -> Self {
+        Self { unsubscribe: None }
+    }
+
+    /// stop listening to the events of this subscription
+    pub fn unsubscribe(mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.take() {
+            unsubscribe();
+        }
+    }
+
+    /// keep listening to the events for the rest of the program's lifetime.
+    ///
+    /// Note: dropping the subscription without calling this will free the closures
+    /// which are still attached to the events.
+    pub fn forget(self) {
+        std::mem::forget(self.unsubscribe);
+    }
+}
+
+impl<DSP> Sub<DSP>
+where
+    DSP: 'static,
+{
+    /// creates a new Sub from a function which starts listening to the events
+    /// and returns the Subscription to stop it
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnOnce(DSP) -> Subscription + 'static,
+    {
+        Self {
+            subscribers: vec![Box::new(f)],
+            pause_when_hidden: vec![],
+        }
+    }
+
+    /// creates a Sub of a timer which can be paused while the document is hidden,
+    /// the function `f` is called with the flag which tells whether it should pause.
+    fn new_pausable<F>(pause_when_hidden: bool, f: F) -> Self
+    where
+        F: FnOnce(DSP, bool) -> Subscription + 'static,
+    {
+        let flag = Rc::new(Cell::new(pause_when_hidden));
+        let flag_clone = Rc::clone(&flag);
+        Self {
+            subscribers: vec![Box::new(move |program| {
+                f(program, flag_clone.get())
+            })],
+            pause_when_hidden: vec![flag],
+        }
+    }
+
+    /// creates a unified Sub which batches all the other Subs in one.
+    pub fn batch(subs: impl IntoIterator<Item = Self>) -> Self {
+        let mut subscribers = vec![];
+        let mut pause_when_hidden = vec![];
+        for sub in subs {
+            subscribers.extend(sub.subscribers);
+            pause_when_hidden.extend(sub.pause_when_hidden);
+        }
+        Self {
+            subscribers,
+            pause_when_hidden,
+        }
+    }
+
+    /// A Sub which doesn't listen to anything
+    pub fn none() -> Self {
+        Self {
+            subscribers: vec![],
+            pause_when_hidden: vec![],
+        }
+    }
+
+    /// Stop the timers and the animation frames of this Sub while the document is hidden,
+    /// ie: the tab is in the background, and resume them when the document is visible again.
+    ///
+    /// This is the default for [`Sub::every`], which dispatch one catch-up MSG when resumed
+    /// instead of a burst of the missed ticks.
+    /// The other subscriptions which listens to the events are not affected.
+    pub fn pause_when_hidden(self) -> Self {
+        self.set_pause_when_hidden(true)
+    }
+
+    /// Keep the timers and the animation frames of this Sub running while the document is hidden
+    pub fn keep_running_when_hidden(self) -> Self {
+        self.set_pause_when_hidden(false)
+    }
+
+    fn set_pause_when_hidden(self, pause_when_hidden: bool) -> Self {
+        for flag in self.pause_when_hidden.iter() {
+            flag.set(pause_when_hidden);
+        }
+        self
+    }
+}
+
+impl<DSP> Sub<DSP>
+where
+    DSP: Clone + 'static,
+{
+    /// start listening to the events, returns the subscriptions
+    /// which can be used to stop listening to the events
+    pub fn subscribe(self, program: &DSP) -> Vec<Subscription> {
+        self.subscribers
+            .into_iter()
+            .map(|subscriber| subscriber(program.clone()))
+            .collect()
+    }
+}
+
+impl<DSP> Sub<DSP>
+where
+    DSP: 'static,
+{
+    /// Creates a Sub which emits the MSG with the scroll progress of the element
+    /// matching the `selector` as it passes through the viewport.
+    ///
+    /// The progress is `0.0` when the top of the element enters the bottom of the viewport and
+    /// `1.0` when the bottom of the element leaves the top of the viewport.
+    /// The progress is only sampled on every animation frame while the element is visible.
+    pub fn on_scroll_progress<F, MSG>(selector: &str, f: F) -> Self
+    where
+        F: Fn(f64) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        let selector = selector.to_string();
+        Sub::new(move |program: DSP| {
+            if !crate::dom::capabilities().intersection_observer {
+                log::warn!("IntersectionObserver is not supported");
+                return Subscription::none();
+            }
+            let element = match crate::document()
+                .query_selector(&selector)
+                .ok()
+                .flatten()
+            {
+                Some(element) => element,
+                None => {
+                    log::warn!("no element matches: {}", selector);
+                    return Subscription::none();
+                }
+            };
+            let is_visible = Rc::new(Cell::new(false));
+            let last_progress: Rc<Cell<Option<f64>>> = Rc::new(Cell::new(None));
+            let sampler = AnimationFrameLoop::new({
+                let element = element.clone();
+                let is_visible = Rc::clone(&is_visible);
+                move |_timestamp| {
+                    let progress = scroll_progress(&element);
+                    if last_progress.get() != Some(progress) {
+                        last_progress.set(Some(progress));
+                        program.dispatch(f(progress));
+                    }
+                    // keep on sampling while the element is visible
+                    is_visible.get()
+                }
+            });
+
+            let sampler_clone = sampler.clone();
+            let observer_callback: Closure<
+                dyn FnMut(js_sys::Array, IntersectionObserver),
+            > = Closure::wrap(Box::new(
+                move |entries: js_sys::Array, _observer| {
+                    for entry in entries.iter() {
+                        let entry: IntersectionObserverEntry =
+                            entry.unchecked_into();
+                        is_visible.set(entry.is_intersecting());
+                    }
+                    // sample at least once more, so the final progress is emitted
+                    // when the element leaves the viewport
+                    sampler_clone.start();
+                },
+            ));
+            let observer = IntersectionObserver::new(
+                observer_callback.as_ref().unchecked_ref(),
+            )
+            .expect("must create an intersection observer");
+            observer.observe(&element);
+
+            Subscription::new(move || {
+                observer.disconnect();
+                sampler.stop();
+                drop(observer_callback);
+            })
+        })
+    }
+}
+
+impl<DSP> Sub<DSP>
+where
+    DSP: 'static,
+{
+    /// Creates a Sub which emits the MSG whenever the user changes
+    /// the `prefers-reduced-motion` preference,
+    /// the argument is true when the user prefers reduced motion.
+    pub fn on_reduced_motion_change<F, MSG>(f: F) -> Self
+    where
+        F: Fn(bool) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        Sub::new(move |program: DSP| {
+            let media_query = match crate::window()
+                .match_media("(prefers-reduced-motion: reduce)")
+                .ok()
+                .flatten()
+            {
+                Some(media_query) => media_query,
+                None => return Subscription::none(),
+            };
+            let media_query_clone = media_query.clone();
+            let listener: Closure<dyn FnMut(web_sys::Event)> =
+                Closure::wrap(Box::new(move |_event: web_sys::Event| {
+                    let prefers_reduced_motion = media_query_clone.matches();
+                    capabilities::update(|capabilities| {
+                        capabilities.prefers_reduced_motion =
+                            prefers_reduced_motion
+                    });
+                    program.dispatch(f(prefers_reduced_motion));
+                }));
+            media_query
+                .add_event_listener_with_callback(
+                    "change",
+                    listener.as_ref().unchecked_ref(),
+                )
+                .expect("must add a change listener");
+            Subscription::new(move || {
+                media_query
+                    .remove_event_listener_with_callback(
+                        "change",
+                        listener.as_ref().unchecked_ref(),
+                    )
+                    .expect("must remove the change listener");
+            })
+        })
+    }
+}
+
+#[cfg(feature = "with-persist")]
+impl<DSP> Sub<DSP>
+where
+    DSP: 'static,
+{
+    /// Creates a Sub which dispatch the msgs sent with [`Cmd::broadcast`] from the other tabs
+    /// and windows of the same origin into the channel `channel_name`,
+    /// so a change such as a logout or of the settings propagates to all of the tabs.
+    ///
+    /// The channel is closed when unsubscribed, ie: when the program is unmounted.
+    pub fn broadcast_channel<MSG>(channel_name: &str) -> Self
+    where
+        MSG: serde::de::DeserializeOwned + 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        let channel_name = channel_name.to_string();
+        Sub::new(move |program: DSP| {
+            broadcast::listen(&channel_name, move |msg: MSG| {
+                program.dispatch(msg)
+            })
+        })
+    }
+}
+
+impl<DSP> Sub<DSP>
+where
+    DSP: 'static,
+{
+    /// Creates a Sub which emits the MSG with the timestamp of the frame on every animation frame,
+    /// useful for driving games and animations.
+    ///
+    /// The MSG is processed in the same frame, so the view is updated along with it.
+    /// Use [`Sub::pause_when_hidden`] to also stop requesting the frames while the document is hidden.
+    pub fn on_animation_frame<F, MSG>(f: F) -> Self
+    where
+        F: Fn(f64) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        Sub::new_pausable(false, move |program: DSP, pause_when_hidden| {
+            let frame_loop = AnimationFrameLoop::new(move |timestamp| {
+                program.dispatch(f(timestamp));
+                true
+            });
+            let visibility = if pause_when_hidden {
+                let frame_loop = frame_loop.clone();
+                on_visibility_change(move |is_hidden| {
+                    if is_hidden {
+                        frame_loop.pause();
+                    } else {
+                        frame_loop.start();
+                    }
+                })
+            } else {
+                Subscription::none()
+            };
+            if !(pause_when_hidden && is_document_hidden()) {
+                frame_loop.start();
+            }
+            Subscription::new(move || {
+                visibility.unsubscribe();
+                frame_loop.stop();
+            })
+        })
+    }
+
+    /// Creates a Sub which dispatch the MSG created from the tick count every `ms` milliseconds,
+    /// the tick count starts at 1.
+    ///
+    /// The interval is stopped while the document is hidden. When the document is visible again,
+    /// the ticks missed while it was hidden are added to the tick count and one catch-up MSG
+    /// is dispatched, use [`Sub::keep_running_when_hidden`] to keep the interval running instead.
+    pub fn every<F, MSG>(ms: i32, f: F) -> Self
+    where
+        F: Fn(u32) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        Sub::new_pausable(true, move |program: DSP, pause_when_hidden| {
+            let tick = Rc::new(Cell::new(0));
+            let f = Rc::new(f);
+            let dispatch_tick = Rc::new({
+                let tick = Rc::clone(&tick);
+                move |ticks: u32| {
+                    tick.set(tick.get() + ticks);
+                    program.dispatch(f(tick.get()));
+                }
+            });
+            let interval = Interval::new(ms, {
+                let dispatch_tick = Rc::clone(&dispatch_tick);
+                move || dispatch_tick(1)
+            });
+            let visibility = if pause_when_hidden {
+                let interval = interval.clone();
+                let hidden_at: Cell<Option<f64>> = Cell::new(None);
+                on_visibility_change(move |is_hidden| {
+                    if is_hidden {
+                        interval.stop();
+                        hidden_at.set(Some(js_sys::Date::now()));
+                    } else {
+                        if let Some(hidden_at) = hidden_at.take() {
+                            let elapsed = js_sys::Date::now() - hidden_at;
+                            let missed =
+                                (elapsed / f64::from(ms.max(1))) as u32;
+                            if missed > 0 {
+                                dispatch_tick(missed);
+                            }
+                        }
+                        interval.start();
+                    }
+                })
+            } else {
+                Subscription::none()
+            };
+            if !(pause_when_hidden && is_document_hidden()) {
+                interval.start();
+            }
+            Subscription::new(move || {
+                visibility.unsubscribe();
+                interval.stop();
+            })
+        })
+    }
+}
+
+impl<DSP> Sub<DSP>
+where
+    DSP: Clone + 'static,
+{
+    /// Sample the MSG of the high frequency `source`, such as the scroll or the mousemove,
+    /// so at most one MSG is dispatched every `ms` milliseconds.
+    ///
+    /// The first MSG starts the sampling period and the latest MSG is dispatched at the end
+    /// of it, the MSG in between are dropped.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// // the source dispatch into the `Sampler`, so it is created with the generic `sub::Sub`
+    /// Sub::sampled(sub::Sub::on_scroll_progress("#article", Msg::ReadProgress), 100)
+    /// ```
+    pub fn sampled<MSG>(source: Sub<Sampler<DSP, MSG>>, ms: i32) -> Self
+    where
+        MSG: 'static,
+        DSP: Dispatch<MSG>,
+    {
+        let Sub {
+            subscribers,
+            pause_when_hidden,
+        } = source;
+        let subscribers = subscribers
+            .into_iter()
+            .map(|subscriber| {
+                Box::new(move |program: DSP| {
+                    let sampler = Sampler::new(program, ms);
+                    let subscription = subscriber(sampler.clone());
+                    Subscription::new(move || {
+                        subscription.unsubscribe();
+                        sampler.cancel();
+                    })
+                }) as Box<dyn FnOnce(DSP) -> Subscription>
+            })
+            .collect();
+        Self {
+            subscribers,
+            pause_when_hidden,
+        }
+    }
+}
+
+/// The dispatcher of the source of [`Sub::sampled`],
+/// which keeps only the latest MSG until the end of the sampling period.
+pub struct Sampler<DSP, MSG> {
+    program: DSP,
+    ms: i32,
+    latest: Rc<RefCell<Option<MSG>>>,
+    /// the id of the timeout at the end of the sampling period, while it is pending
+    timeout_id: Rc<Cell<Option<i32>>>,
+}
+
+impl<DSP, MSG> Clone for Sampler<DSP, MSG>
+where
+    DSP: Clone,
+{
+    fn clone(&self) -> Self {
+        Sampler {
+            program: self.program.clone(),
+            ms: self.ms,
+            latest: Rc::clone(&self.latest),
+            timeout_id: Rc::clone(&self.timeout_id),
+        }
+    }
+}
+
+impl<DSP, MSG> Sampler<DSP, MSG>
+where
+    DSP: Dispatch<MSG> + Clone + 'static,
+    MSG: 'static,
+{
+    fn new(program: DSP, ms: i32) -> Self {
+        Sampler {
+            program,
+            ms,
+            latest: Rc::new(RefCell::new(None)),
+            timeout_id: Rc::new(Cell::new(None)),
+        }
+    }
+
+    /// start the sampling period, unless it is already started
+    fn start(&self) {
+        if self.timeout_id.get().is_some() {
+            return;
+        }
+        let sampler = self.clone();
+        // the closure is freed once it is called
+        let closure = Closure::once_into_js(move || {
+            sampler.timeout_id.set(None);
+            let latest = sampler.latest.borrow_mut().take();
+            if let Some(msg) = latest {
+                sampler.program.dispatch(msg);
+            }
+        });
+        let timeout_id = timer::set_timeout(closure.unchecked_ref(), self.ms);
+        self.timeout_id.set(Some(timeout_id));
+    }
+
+    /// stop the pending sampling period, the latest MSG is dropped
+    fn cancel(&self) {
+        if let Some(timeout_id) = self.timeout_id.take() {
+            timer::clear_timeout(timeout_id);
+        }
+        self.latest.borrow_mut().take();
+    }
+}
+
+impl<DSP, MSG> Dispatch<MSG> for Sampler<DSP, MSG>
+where
+    DSP: Dispatch<MSG> + Clone + 'static,
+    MSG: 'static,
+{
+    fn dispatch(&self, msg: MSG) {
+        *self.latest.borrow_mut() = Some(msg);
+        self.start();
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<MSG>) {
+        if let Some(msg) = msgs.into_iter().last() {
+            self.dispatch(msg);
+        }
+    }
+}
+
+/// whether the document is hidden, ie: the tab is in the background
+fn is_document_hidden() -> bool {
+    crate::document().hidden()
+}
+
+/// calls the function with whether the document is hidden, whenever its visibility changes
+fn on_visibility_change<F>(f: F) -> Subscription
+where
+    F: Fn(bool) + 'static,
+{
+    let listener: Closure<dyn FnMut(web_sys::Event)> =
+        Closure::wrap(Box::new(move |_event: web_sys::Event| {
+            f(is_document_hidden())
+        }));
+    crate::document()
+        .add_event_listener_with_callback(
+            "visibilitychange",
+            listener.as_ref().unchecked_ref(),
+        )
+        .expect("must add a visibilitychange listener");
+    Subscription::new(move || {
+        crate::document()
+            .remove_event_listener_with_callback(
+                "visibilitychange",
+                listener.as_ref().unchecked_ref(),
+            )
+            .expect("must remove the visibilitychange listener");
+    })
+}
+
+/// An interval which calls the function every `ms` milliseconds while it is started
+#[derive(Clone)]
+struct Interval {
+    ms: i32,
+    closure: Rc<Closure<dyn FnMut()>>,
+    /// the id returned by `setInterval`, while the interval is started
+    interval_id: Rc<Cell<Option<i32>>>,
+}
+
+impl Interval {
+    /// create the interval, this doesn't start the interval yet
+    fn new<F>(ms: i32, f: F) -> Self
+    where
+        F: Fn() + 'static,
+    {
+        Interval {
+            ms,
+            closure: Rc::new(Closure::wrap(Box::new(f))),
+            interval_id: Rc::new(Cell::new(None)),
+        }
+    }
+
+    /// start the interval, if it is not started yet
+    fn start(&self) {
+        if self.interval_id.get().is_some() {
+            return;
+        }
+        let interval_id = timer::set_interval(
+            self.closure.as_ref().as_ref().unchecked_ref(),
+            self.ms,
+        );
+        self.interval_id.set(Some(interval_id));
+    }
+
+    /// stop the interval, it can be started again
+    fn stop(&self) {
+        if let Some(interval_id) = self.interval_id.take() {
+            timer::clear_interval(interval_id);
+        }
+    }
+}
+
+impl<DSP> From<Sub<DSP>> for Cmd<DSP>
+where
+    DSP: Clone + 'static,
+{
+    /// Convert the Sub into a Cmd which subscribes when the Cmd is emitted,
+    /// the subscriptions are kept for the rest of the program's lifetime.
+    fn from(sub: Sub<DSP>) -> Self {
+        Cmd::new(move |program: DSP| {
+            for subscription in sub.subscribe(&program) {
+                subscription.forget();
+            }
+        })
+        .no_render()
+    }
+}
+
+/// the scroll progress of the element through the viewport, from 0.0 to 1.0
+fn scroll_progress(element: &web_sys::Element) -> f64 {
+    let rect = element.get_bounding_client_rect();
+    let (_, viewport_height) = Window::get_size();
+    let viewport_height = f64::from(viewport_height);
+    let total = viewport_height + rect.height();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    ((viewport_height - rect.top()) / total).max(0.0).min(1.0)
+}
+
+/// Calls the function with the timestamp of the frame on every animation frame,
+/// until the function returns false or the loop is stopped.
+#[derive(Clone)]
+pub(crate) struct AnimationFrameLoop {
+    closure: Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>>,
+    /// the id of the requested animation frame, if there is one pending
+    request_id: Rc<Cell<Option<i32>>>,
+    /// the closure is being called, so it can not be dropped yet
+    is_running: Rc<Cell<bool>>,
+}
+
+impl AnimationFrameLoop {
+    /// create the loop, this doesn't start the loop yet
+    pub(crate) fn new<F>(mut f: F) -> Self
+    where
+        F: FnMut(f64) -> bool + 'static,
+    {
+        let this = AnimationFrameLoop {
+            closure: Rc::new(RefCell::new(None)),
+            request_id: Rc::new(Cell::new(None)),
+            is_running: Rc::new(Cell::new(false)),
+        };
+        let this_clone = this.clone();
+        let closure: Closure<dyn FnMut(f64)> =
+            Closure::wrap(Box::new(move |timestamp: f64| {
+                this_clone.request_id.set(None);
+                this_clone.is_running.set(true);
+                let is_continued = util::with_animation_frame(|| f(timestamp));
+                this_clone.is_running.set(false);
+                if is_continued {
+                    this_clone.start();
+                }
+            }));
+        *this.closure.borrow_mut() = Some(closure);
+        this
+    }
+
+    /// request an animation frame, if there is none pending
+    pub(crate) fn start(&self) {
+        if self.request_id.get().is_some() {
+            return;
+        }
+        if let Some(closure) = self.closure.borrow().as_ref() {
+            let request_id = crate::window()
+                .request_animation_frame(closure.as_ref().unchecked_ref())
+                .expect("should register `requestAnimationFrame` OK");
+            self.request_id.set(Some(request_id));
+        }
+    }
+
+    /// cancel the pending animation frame, the loop can be started again
+    pub(crate) fn pause(&self) {
+        if let Some(request_id) = self.request_id.take() {
+            crate::window()
+                .cancel_animation_frame(request_id)
+                .expect("must cancel animation frame");
+        }
+    }
+
+    /// cancel the pending animation frame and drop the closure,
+    /// the loop can not be started again after this.
+    ///
+    /// When the loop is stopped from within its own frame, the closure is dropped
+    /// after the frame, since the closure can not be dropped while it is being called.
+    pub(crate) fn stop(&self) {
+        self.pause();
+        // this also breaks the reference cycle of the closure to this loop
+        let closure = self.closure.borrow_mut().take();
+        if self.is_running.get() {
+            wasm_bindgen_futures::spawn_local(async move { drop(closure) });
+        }
+    }
+}
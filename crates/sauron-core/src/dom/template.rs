@@ -0,0 +1,378 @@
+//! Clones the static skeleton of the repeated subtrees from a `<template>`,
+//! instead of creating each of their elements with `create_element`.
+//!
+//! The skeleton of a subtree is its elements with only their attributes of literal values,
+//! where the texts and the comments are left empty.
+//! The skeletons are detected at runtime by hashing them: the first time a skeleton is seen
+//! its subtree is created node by node, the second time it is created into the inert document
+//! of a `<template>`, then the subtree is imported from it on this and every next time.
+//! Since different skeletons may have the same hash, the full skeleton is compared
+//! before the template is used.
+//! After the import, only the texts, the listeners and the other dynamic attributes are set.
+//!
+//! This makes the mount of the rows of the big lists, and of the pages which are mounted again
+//! such as the pages of a router, cost a deep clone plus their dynamic parts.
+use crate::{
+    dom::{
+        created_node::{ActiveClosure, CreatedNode},
+        Dispatch,
+    },
+    html::{
+        self,
+        attributes::{AttributeValue, Special, Value},
+    },
+    vdom::ContentHash,
+    Attribute,
+};
+use std::{
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Document, Element, HtmlTemplateElement, Node};
+
+/// the subtrees with less nodes than this are created node by node,
+/// since hashing and importing them costs more than creating them
+const MIN_TEMPLATE_NODES: usize = 8;
+
+/// the skeletons which are remembered, the skeletons which are seen after this
+/// is reached are always created node by node
+const MAX_TEMPLATES: usize = 1024;
+
+thread_local! {
+    /// the skeletons by their hash, which is `None` when it is only seen once
+    static TEMPLATES: RefCell<HashMap<u64, Option<Template>>> = RefCell::new(HashMap::new());
+}
+
+/// the skeleton in the `<template>`, with the full skeleton it is created from
+struct Template {
+    skeleton: Node,
+    full_skeleton: Vec<u8>,
+}
+
+/// The hash and the number of nodes of the skeleton of the elements of a view.
+///
+/// The skeleton of a subtree is hashed once, the first time it is looked up,
+/// and cached together with the skeletons of its elements, so the elements of a subtree
+/// which is created node by node reuse the hashes computed for the subtree.
+pub(crate) struct Skeletons<MSG> {
+    skeletons:
+        RefCell<HashMap<*const crate::Element<MSG>, Option<(u64, usize)>>>,
+}
+
+impl<MSG> Skeletons<MSG> {
+    /// the skeletons of the elements of a view, which are hashed as they are looked up
+    pub(crate) fn new() -> Self {
+        Skeletons {
+            skeletons: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// the hash and the number of nodes of the skeleton of the element,
+    /// `None` if the subtree can not be cloned.
+    ///
+    /// The adopted elements and the safe html can not be cloned, since they are not created
+    /// from the view.
+    fn skeleton_of(&self, velem: &crate::Element<MSG>) -> Option<(u64, usize)> {
+        let address: *const crate::Element<MSG> = velem;
+        if let Some(skeleton) = self.skeletons.borrow().get(&address) {
+            return *skeleton;
+        }
+        let mut cloneable = velem.get_value(html::ADOPT_ATTR).is_none();
+        let mut hasher = DefaultHasher::new();
+        let mut node_count = 1;
+        hash_element_parts(velem, &mut hasher);
+        for child in velem.children.iter() {
+            if child.is_safe_html() {
+                cloneable = false;
+            }
+            match child {
+                crate::Node::Element(child_elem) => {
+                    match self.skeleton_of(child_elem) {
+                        Some((child_hash, child_count)) => {
+                            child_hash.hash(&mut hasher);
+                            node_count += child_count;
+                        }
+                        None => cloneable = false,
+                    }
+                }
+                _ => node_count += 1,
+            }
+        }
+        let skeleton = if cloneable {
+            Some((hasher.finish(), node_count))
+        } else {
+            None
+        };
+        self.skeletons.borrow_mut().insert(address, skeleton);
+        skeleton
+    }
+
+    /// the hash of the skeleton of this element, if it is big enough to be cloned
+    fn hash_of(&self, velem: &crate::Element<MSG>) -> Option<u64> {
+        self.skeleton_of(velem)
+            .filter(|(_hash, node_count)| *node_count >= MIN_TEMPLATE_NODES)
+            .map(|(hash, _node_count)| *hash)
+    }
+}
+
+/// feed the parts of the skeleton of the element itself into the `state`,
+/// which are its tag, its attributes and the kinds of its children
+fn hash_element_parts<MSG, H: Hasher>(
+    velem: &crate::Element<MSG>,
+    state: &mut H,
+) {
+    velem.namespace.hash(state);
+    velem.tag.hash(state);
+    velem.attrs.len().hash(state);
+    for attr in velem.attrs.iter() {
+        let is_static = is_static_attribute(attr);
+        is_static.hash(state);
+        if is_static {
+            attr.hash_content(state);
+        } else {
+            attr.namespace.hash(state);
+            attr.name.hash(state);
+        }
+    }
+    velem.children.len().hash(state);
+    for child in velem.children.iter() {
+        std::mem::discriminant(child).hash(state);
+    }
+}
+
+/// the full skeleton of the element and its descendants, which is compared
+/// when the hash of the skeleton matches a template
+fn full_skeleton<MSG>(velem: &crate::Element<MSG>) -> Vec<u8> {
+    fn write_skeleton<MSG>(
+        velem: &crate::Element<MSG>,
+        recorder: &mut Recorder,
+    ) {
+        hash_element_parts(velem, recorder);
+        for child in velem.children.iter() {
+            if let crate::Node::Element(child_elem) = child {
+                write_skeleton(child_elem, recorder);
+            }
+        }
+    }
+    let mut recorder = Recorder(vec![]);
+    write_skeleton(velem, &mut recorder);
+    recorder.0
+}
+
+/// records the bytes which are hashed, instead of hashing them
+struct Recorder(Vec<u8>);
+
+impl Hasher for Recorder {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        0
+    }
+}
+
+/// the attributes which are set the same way into every element they are in,
+/// these are the attributes with only literal values.
+///
+/// The attributes with the formatted values such as the keys and the ids, the listeners,
+/// the properties, the function calls and the custom values are set after the skeleton is imported,
+/// so the elements which differ only in those have the same skeleton.
+fn is_static_attribute<MSG>(attr: &Attribute<MSG>) -> bool {
+    is_plain_attribute(attr)
+        && attr.value().iter().all(|value| match value {
+            AttributeValue::Simple(value) => matches!(value, Value::Str(_)),
+            AttributeValue::Style(styles) => styles
+                .iter()
+                .all(|style| matches!(style.value, Value::Str(_))),
+            _ => false,
+        })
+}
+
+/// the attributes which are set as the attributes of the element, rather than as properties,
+/// listeners, function calls or custom values
+fn is_plain_attribute<MSG>(attr: &Attribute<MSG>) -> bool {
+    !matches!(*attr.name(), "value" | "checked" | "open")
+        && attr.value().iter().all(|value| {
+            matches!(
+                value,
+                AttributeValue::Simple(_) | AttributeValue::Style(_)
+            )
+        })
+}
+
+/// Create the element by importing its skeleton, then setting its dynamic parts.
+///
+/// Returns `None` if the skeleton of the element is seen the first time, is too small,
+/// or can not be cloned, in which case the element is to be created node by node.
+pub(crate) fn import_skeleton<DSP, MSG>(
+    program: &DSP,
+    velem: &crate::Element<MSG>,
+    focused_node: &mut Option<Node>,
+    skeletons: &Skeletons<MSG>,
+) -> Result<Option<CreatedNode>, JsValue>
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let hash = match skeletons.hash_of(velem) {
+        Some(hash) => hash,
+        None => return Ok(None),
+    };
+    let skeleton = TEMPLATES.with(|templates| -> Result<_, JsValue> {
+        let mut templates = templates.borrow_mut();
+        match templates.get_mut(&hash) {
+            // a different skeleton with the same hash is created node by node
+            Some(Some(template)) => Ok((template.full_skeleton
+                == full_skeleton(velem))
+            .then(|| template.skeleton.clone())),
+            Some(seen_once) => {
+                let skeleton = create_skeleton(program, velem)?;
+                *seen_once = Some(Template {
+                    skeleton: skeleton.clone(),
+                    full_skeleton: full_skeleton(velem),
+                });
+                Ok(Some(skeleton))
+            }
+            None => {
+                if templates.len() < MAX_TEMPLATES {
+                    templates.insert(hash, None);
+                }
+                Ok(None)
+            }
+        }
+    })?;
+    let skeleton = match skeleton {
+        Some(skeleton) => skeleton,
+        None => return Ok(None),
+    };
+
+    let node = crate::document().import_node_with_deep(&skeleton, true)?;
+    let mut closures = ActiveClosure::new();
+    set_dynamic_parts(
+        program,
+        &mut closures,
+        velem,
+        node.unchecked_ref(),
+        focused_node,
+    )?;
+    Ok(Some(CreatedNode { node, closures }))
+}
+
+/// create the skeleton of the element into the inert document of a new `<template>`
+fn create_skeleton<DSP, MSG>(
+    program: &DSP,
+    velem: &crate::Element<MSG>,
+) -> Result<Node, JsValue>
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let template: HtmlTemplateElement = crate::document()
+        .create_element("template")?
+        .unchecked_into();
+    let content = template.content();
+    let inert_document = content.owner_document().ok_or_else(|| {
+        js_sys::Error::new("the template content has no owner document")
+    })?;
+    let skeleton = create_skeleton_element(program, &inert_document, velem)?;
+    content.append_child(&skeleton)?;
+    Ok(skeleton)
+}
+
+fn create_skeleton_element<DSP, MSG>(
+    program: &DSP,
+    document: &Document,
+    velem: &crate::Element<MSG>,
+) -> Result<Node, JsValue>
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let element = CreatedNode::create_element(document, velem)?;
+    // the static attributes have no closures
+    let mut closures = ActiveClosure::new();
+    let attrs: Vec<&Attribute<MSG>> = velem.attrs.iter().collect();
+    for attr in mt_dom::merge_attributes_of_same_name(&attrs).iter() {
+        if is_static_attribute(attr) {
+            CreatedNode::set_element_attribute(
+                program,
+                &mut closures,
+                &element,
+                attr,
+            )?;
+        } else if is_plain_attribute(attr) {
+            // keep the place of the attribute which is set after the import,
+            // so the attributes are in the same order as when created node by node
+            match attr.namespace() {
+                Some(namespace) => element.set_attribute_ns(
+                    Some(namespace),
+                    attr.name(),
+                    "",
+                )?,
+                None => element.set_attribute(attr.name(), "")?,
+            }
+        }
+    }
+    for child in velem.children.iter() {
+        let child_node: Node = match child {
+            crate::Node::Element(child_elem) => {
+                create_skeleton_element(program, document, child_elem)?
+            }
+            crate::Node::Text(_) => document.create_text_node("").into(),
+            crate::Node::Comment(_) => document.create_comment("").into(),
+        };
+        element.append_child(&child_node)?;
+    }
+    Ok(element.into())
+}
+
+/// set the texts, the comments and the dynamic attributes of the imported skeleton,
+/// in the same order as the element is created node by node
+fn set_dynamic_parts<DSP, MSG>(
+    program: &DSP,
+    closures: &mut ActiveClosure,
+    velem: &crate::Element<MSG>,
+    element: &Element,
+    focused_node: &mut Option<Node>,
+) -> Result<(), JsValue>
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    CreatedNode::set_up_element(
+        program,
+        closures,
+        velem,
+        element,
+        focused_node,
+        |attr| !is_static_attribute(attr),
+    )?;
+
+    let mut dom_child = element.first_child();
+    for child in velem.children.iter() {
+        // the full skeleton is compared before the import, so the children are the same
+        let node = dom_child.ok_or_else(|| {
+            js_sys::Error::new(
+                "the skeleton has less children than the element",
+            )
+        })?;
+        match child {
+            crate::Node::Element(child_elem) => set_dynamic_parts(
+                program,
+                closures,
+                child_elem,
+                node.unchecked_ref(),
+                focused_node,
+            )?,
+            crate::Node::Text(txt) => node.set_node_value(Some(&txt.text)),
+            crate::Node::Comment(comment) => node.set_node_value(Some(comment)),
+        }
+        dom_child = node.next_sibling();
+    }
+
+    CreatedNode::dispatch_mount_event(program, velem, element);
+    Ok(())
+}
@@ -0,0 +1,197 @@
+//! provides the functions to simulate the events on the nodes of the view in the tests,
+//! so the `update` of the app can be driven without a user triggering the actual events.
+//!
+//! The listeners of the node are found in the vdom and the msgs they return are dispatched
+//! into the program. The event is dispatched on a detached element with the same tag as the node,
+//! so the listeners which reads the target of the event, ie: `on_input`, still works.
+//!
+//! The node is located by its path, which are the indexes of the children from the root node,
+//! the root node itself is the empty path `&[]`.
+//!
+//! # Examples
+//! ```rust,ignore
+//! let view = app.view();
+//! // type into the input which is the second child of the root node
+//! test_util::simulate_input(&program, &view, &[1], "hello")?;
+//! // click the first child of the third child of the root node
+//! test_util::simulate_click(&program, &view, &[2, 0])?;
+//! ```
+//!
+//! The timers of the program can be controlled with the [`TestClock`].
+pub use crate::dom::timer::TestClock;
+use crate::{
+    dom::{created_node::create_closure_wrap, Dispatch},
+    Node,
+};
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Element, EventTarget};
+
+/// Errors when the event can not be simulated
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SimulateError {
+    /// there is no node at the path
+    #[error("there is no node at the path: {0:?}")]
+    NodeNotFound(Vec<usize>),
+    /// the node at the path is a text or comment node
+    #[error("the node at the path: {0:?} is not an element")]
+    NotAnElement(Vec<usize>),
+    /// the element at the path has no listener to the event
+    #[error("the element at the path: {path:?} has no listener to the {event} event")]
+    NoListener {
+        /// the path of the element
+        path: Vec<usize>,
+        /// the name of the event
+        event: String,
+    },
+}
+
+/// simulate a click on the element at the `path`
+pub fn simulate_click<DSP, MSG>(
+    program: &DSP,
+    view: &Node<MSG>,
+    path: &[usize],
+) -> Result<usize, SimulateError>
+where
+    MSG: 'static,
+    DSP: Dispatch<MSG> + Clone + 'static,
+{
+    let event =
+        web_sys::MouseEvent::new("click").expect("must create a mouse event");
+    simulate(program, view, path, event.unchecked_into(), |_| ())
+}
+
+/// simulate typing the `value` into the input or textarea at the `path`,
+/// this triggers the `on_input` listeners of the element
+pub fn simulate_input<DSP, MSG>(
+    program: &DSP,
+    view: &Node<MSG>,
+    path: &[usize],
+    value: &str,
+) -> Result<usize, SimulateError>
+where
+    MSG: 'static,
+    DSP: Dispatch<MSG> + Clone + 'static,
+{
+    let event = web_sys::Event::new("input").expect("must create an event");
+    simulate(program, view, path, event, |element| {
+        set_property(element, "value", &JsValue::from_str(value))
+    })
+}
+
+/// simulate checking or unchecking the checkbox at the `path`,
+/// this triggers the `on_checked` listeners of the element
+pub fn simulate_checked<DSP, MSG>(
+    program: &DSP,
+    view: &Node<MSG>,
+    path: &[usize],
+    checked: bool,
+) -> Result<usize, SimulateError>
+where
+    MSG: 'static,
+    DSP: Dispatch<MSG> + Clone + 'static,
+{
+    let event = web_sys::Event::new("input").expect("must create an event");
+    simulate(program, view, path, event, |element| {
+        set_property(element, "checked", &JsValue::from_bool(checked))
+    })
+}
+
+/// simulate the event with the `event_name` on the element at the `path`
+pub fn simulate_event<DSP, MSG>(
+    program: &DSP,
+    view: &Node<MSG>,
+    path: &[usize],
+    event_name: &str,
+) -> Result<usize, SimulateError>
+where
+    MSG: 'static,
+    DSP: Dispatch<MSG> + Clone + 'static,
+{
+    let event = web_sys::Event::new(event_name).expect("must create an event");
+    simulate(program, view, path, event, |_| ())
+}
+
+/// dispatch the `event` into the listeners of the element at the `path`,
+/// the `prepare` function sets up the detached element before the event is dispatched.
+///
+/// Returns the number of the listeners which are called
+pub fn simulate<DSP, MSG, F>(
+    program: &DSP,
+    view: &Node<MSG>,
+    path: &[usize],
+    event: web_sys::Event,
+    prepare: F,
+) -> Result<usize, SimulateError>
+where
+    MSG: 'static,
+    DSP: Dispatch<MSG> + Clone + 'static,
+    F: FnOnce(&Element),
+{
+    let node = find_node(view, path)
+        .ok_or_else(|| SimulateError::NodeNotFound(path.to_vec()))?;
+    let velem = node
+        .as_element_ref()
+        .ok_or_else(|| SimulateError::NotAnElement(path.to_vec()))?;
+
+    let event_name = event.type_();
+    let listeners: Vec<_> = velem
+        .get_attributes()
+        .iter()
+        .filter(|attr| *attr.name() == event_name)
+        .flat_map(|attr| attr.value().iter())
+        .filter_map(|value| value.as_event_listener())
+        .collect();
+    if listeners.is_empty() {
+        return Err(SimulateError::NoListener {
+            path: path.to_vec(),
+            event: event_name,
+        });
+    }
+
+    let element = crate::document()
+        .create_element(velem.tag())
+        .expect("must create the element");
+    prepare(&element);
+    let target: &EventTarget = element.unchecked_ref();
+    let closures: Vec<_> = listeners
+        .iter()
+        .map(|listener| {
+            let closure = create_closure_wrap(program, listener);
+            target
+                .add_event_listener_with_callback(
+                    &event_name,
+                    closure.as_ref().unchecked_ref(),
+                )
+                .expect("must attach the listener");
+            closure
+        })
+        .collect();
+    target
+        .dispatch_event(&event)
+        .expect("must dispatch the event");
+    for closure in closures.iter() {
+        target
+            .remove_event_listener_with_callback(
+                &event_name,
+                closure.as_ref().unchecked_ref(),
+            )
+            .expect("must detach the listener");
+    }
+    Ok(closures.len())
+}
+
+/// find the node at the path, which are the indexes of the children from the `root`
+pub fn find_node<'a, MSG>(
+    root: &'a Node<MSG>,
+    path: &[usize],
+) -> Option<&'a Node<MSG>> {
+    path.iter().try_fold(root, |node, idx| {
+        node.as_element_ref()?.get_children().get(*idx)
+    })
+}
+
+fn set_property(element: &Element, name: &str, value: &JsValue) {
+    js_sys::Reflect::set(element, &JsValue::from_str(name), value)
+        .expect("must set the property");
+}
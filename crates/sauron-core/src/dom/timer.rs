@@ -0,0 +1,316 @@
+//! provides a handle to the timers started by the program,
+//! and the [`TestClock`] which replaces the timers with a fake clock in the tests.
+//!
+use js_sys::Function;
+#[cfg(any(test, feature = "test-util"))]
+use std::collections::BTreeMap;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::closure::Closure;
+#[cfg(any(test, feature = "test-util"))]
+use wasm_bindgen::JsValue;
+
+#[cfg(any(test, feature = "test-util"))]
+thread_local!(static FAKE_TIMERS: RefCell<Option<FakeTimers>> = RefCell::new(None));
+
+/// A handle to the interval started with [`Cmd::every`](crate::dom::cmd::Cmd::every),
+/// this can be stored in the app and cancelled in the `update` function.
+#[derive(Clone, Default)]
+pub struct TimerHandle {
+    inner: Rc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// the id returned by `setInterval`, set when the Cmd is emitted
+    interval_id: Cell<Option<i32>>,
+    /// the timer is cancelled before the Cmd is emitted
+    is_cancelled: Cell<bool>,
+    /// the closure called by the timer, kept alive until the timer is cancelled
+    closure: RefCell<Option<Closure<dyn FnMut()>>>,
+}
+
+impl TimerHandle {
+    /// stop the timer, the MSG will no longer be dispatched
+    pub fn cancel(&self) {
+        self.inner.is_cancelled.set(true);
+        if let Some(interval_id) = self.inner.interval_id.take() {
+            clear_interval(interval_id);
+        }
+        self.inner.closure.borrow_mut().take();
+    }
+
+    /// returns true if the timer has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled.get()
+    }
+
+    /// record the started interval, if it has been cancelled already it is stopped right away
+    pub(crate) fn started(
+        &self,
+        interval_id: i32,
+        closure: Closure<dyn FnMut()>,
+    ) {
+        if self.is_cancelled() {
+            clear_interval(interval_id);
+            return;
+        }
+        self.inner.interval_id.set(Some(interval_id));
+        *self.inner.closure.borrow_mut() = Some(closure);
+    }
+}
+
+impl std::fmt::Debug for TimerHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("TimerHandle")
+            .field("interval_id", &self.inner.interval_id.get())
+            .field("is_cancelled", &self.inner.is_cancelled.get())
+            .finish()
+    }
+}
+
+/// call the `callback` once after `ms` milliseconds, returns the id to clear the timeout.
+/// The timeout is scheduled in the [`TestClock`] instead, while it is installed.
+pub(crate) fn set_timeout(callback: &Function, ms: i32) -> i32 {
+    schedule(callback, ms, false).unwrap_or_else(|| {
+        crate::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(callback, ms)
+            .expect("must set a timeout")
+    })
+}
+
+/// call the `callback` every `ms` milliseconds, returns the id to clear the interval.
+/// The interval is scheduled in the [`TestClock`] instead, while it is installed.
+pub(crate) fn set_interval(callback: &Function, ms: i32) -> i32 {
+    schedule(callback, ms, true).unwrap_or_else(|| {
+        crate::window()
+            .set_interval_with_callback_and_timeout_and_arguments_0(
+                callback, ms,
+            )
+            .expect("must set an interval")
+    })
+}
+
+/// cancel the timeout started with [`set_timeout`]
+pub(crate) fn clear_timeout(timeout_id: i32) {
+    if !clear_fake(timeout_id) {
+        crate::window().clear_timeout_with_handle(timeout_id);
+    }
+}
+
+/// cancel the interval started with [`set_interval`]
+pub(crate) fn clear_interval(interval_id: i32) {
+    if !clear_fake(interval_id) {
+        crate::window().clear_interval_with_handle(interval_id);
+    }
+}
+
+/// the current time in milliseconds, which is the time of the [`TestClock`] while it is installed
+#[cfg(any(test, feature = "test-util"))]
+pub(crate) fn now() -> f64 {
+    FAKE_TIMERS
+        .with(|fake_timers| fake_timers.borrow().as_ref().map(|t| t.now))
+        .unwrap_or_else(js_sys::Date::now)
+}
+
+/// the current time in milliseconds
+#[cfg(not(any(test, feature = "test-util")))]
+pub(crate) fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+/// the test clock is only available in the tests, so the timers are never faked
+#[cfg(not(any(test, feature = "test-util")))]
+fn schedule(_callback: &Function, _ms: i32, _is_interval: bool) -> Option<i32> {
+    None
+}
+
+/// the test clock is only available in the tests, so there are no fake timers
+#[cfg(not(any(test, feature = "test-util")))]
+fn clear_fake(_id: i32) -> bool {
+    false
+}
+
+/// schedule the callback in the fake timers, `None` if the test clock is not installed
+#[cfg(any(test, feature = "test-util"))]
+fn schedule(callback: &Function, ms: i32, is_interval: bool) -> Option<i32> {
+    FAKE_TIMERS.with(|fake_timers| {
+        let mut fake_timers = fake_timers.borrow_mut();
+        let fake_timers = fake_timers.as_mut()?;
+        // the ids of the fake timers are negative, so they are never mistaken
+        // for the ids of the browser timers
+        fake_timers.last_id -= 1;
+        let id = fake_timers.last_id;
+        let ms = f64::from(ms.max(0));
+        fake_timers.timers.insert(
+            id,
+            FakeTimer {
+                due: fake_timers.now + ms,
+                interval: if is_interval { Some(ms) } else { None },
+                callback: callback.clone(),
+            },
+        );
+        Some(id)
+    })
+}
+
+/// remove the fake timer, returns true if the id is of a fake timer
+#[cfg(any(test, feature = "test-util"))]
+fn clear_fake(id: i32) -> bool {
+    if id >= 0 {
+        return false;
+    }
+    FAKE_TIMERS.with(|fake_timers| {
+        if let Some(fake_timers) = fake_timers.borrow_mut().as_mut() {
+            fake_timers.timers.remove(&id);
+        }
+    });
+    true
+}
+
+#[cfg(any(test, feature = "test-util"))]
+struct FakeTimers {
+    /// the elapsed milliseconds since the clock is installed
+    now: f64,
+    last_id: i32,
+    timers: BTreeMap<i32, FakeTimer>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+struct FakeTimer {
+    /// the time when the timer is called next
+    due: f64,
+    /// the period of the interval, `None` for a timeout
+    interval: Option<f64>,
+    callback: Function,
+}
+
+/// A fake clock which replaces the timers of [`Cmd::delay`], [`Cmd::every`], [`Sub::every`]
+/// and [`Sub::sampled`] while it is installed, so the logic which depends on the time
+/// can be tested deterministically without waiting for real timeouts.
+///
+/// The timers only fire when the clock is advanced, in the order they are due.
+/// The clock is uninstalled when it is dropped, the timers which are still pending are discarded.
+///
+/// # Examples
+/// ```rust,ignore
+/// let clock = TestClock::install();
+/// program.dispatch(Msg::ShowToast("saved".into()));
+/// // the toast hides itself with `Cmd::delay(3000, Msg::HideToast)`
+/// clock.advance(2999);
+/// assert!(program.app.borrow().toast.is_some());
+/// clock.advance(1);
+/// assert!(program.app.borrow().toast.is_none());
+/// ```
+///
+/// [`Cmd::delay`]: crate::dom::cmd::Cmd::delay
+/// [`Cmd::every`]: crate::dom::cmd::Cmd::every
+/// [`Sub::every`]: crate::dom::sub::Sub::every
+/// [`Sub::sampled`]: crate::dom::sub::Sub::sampled
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug)]
+pub struct TestClock {
+    _private: (),
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl TestClock {
+    /// install the fake clock, starting at 0
+    ///
+    /// # Panics
+    /// panics if there is already a test clock installed
+    pub fn install() -> Self {
+        FAKE_TIMERS.with(|fake_timers| {
+            let mut fake_timers = fake_timers.borrow_mut();
+            assert!(
+                fake_timers.is_none(),
+                "there is already a test clock installed"
+            );
+            *fake_timers = Some(FakeTimers {
+                now: 0.0,
+                last_id: 0,
+                timers: BTreeMap::new(),
+            });
+        });
+        TestClock { _private: () }
+    }
+
+    /// the elapsed milliseconds since the clock is installed
+    pub fn now(&self) -> f64 {
+        FAKE_TIMERS.with(|fake_timers| {
+            fake_timers.borrow().as_ref().map(|t| t.now).unwrap_or(0.0)
+        })
+    }
+
+    /// the number of the timeouts and intervals which are not cancelled yet
+    pub fn pending(&self) -> usize {
+        FAKE_TIMERS.with(|fake_timers| {
+            fake_timers
+                .borrow()
+                .as_ref()
+                .map(|t| t.timers.len())
+                .unwrap_or(0)
+        })
+    }
+
+    /// move the clock forward by `ms` milliseconds, calling the timers which are due in the order
+    /// of their due time, the timers started by the callbacks are also called if they are due.
+    pub fn advance(&self, ms: i32) {
+        let target = self.now() + f64::from(ms.max(0));
+        while let Some(callback) = Self::take_next_due(target) {
+            if let Err(e) = callback.call0(&JsValue::NULL) {
+                log::error!("the timer callback failed: {:?}", e);
+            }
+        }
+        FAKE_TIMERS.with(|fake_timers| {
+            if let Some(fake_timers) = fake_timers.borrow_mut().as_mut() {
+                fake_timers.now = target;
+            }
+        });
+    }
+
+    /// move the clock to the next timer which is due before the `target` time,
+    /// the timeout is removed and the interval is scheduled again.
+    /// The callback is called after the timers are released, so it can start or clear the timers.
+    fn take_next_due(target: f64) -> Option<Function> {
+        FAKE_TIMERS.with(|fake_timers| {
+            let mut fake_timers = fake_timers.borrow_mut();
+            let fake_timers = fake_timers.as_mut()?;
+            // the earlier started timer goes first when they are due at the same time,
+            // which is the one with the greater id since the ids are decreasing
+            let (id, due) = fake_timers
+                .timers
+                .iter()
+                .filter(|(_, timer)| timer.due <= target)
+                .min_by(|(a_id, a), (b_id, b)| {
+                    a.due
+                        .partial_cmp(&b.due)
+                        .expect("must not be NaN")
+                        .then(b_id.cmp(a_id))
+                })
+                .map(|(id, timer)| (*id, timer.due))?;
+            fake_timers.now = due;
+            let timer = fake_timers.timers.get_mut(&id)?;
+            let callback = timer.callback.clone();
+            let interval = timer.interval;
+            match interval {
+                Some(interval) if interval > 0.0 => timer.due += interval,
+                // an interval of 0 is called once per advance, instead of looping forever
+                Some(_) => timer.due = target + 1.0,
+                None => {
+                    fake_timers.timers.remove(&id);
+                }
+            }
+            Some(callback)
+        })
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Drop for TestClock {
+    fn drop(&mut self) {
+        FAKE_TIMERS.with(|fake_timers| fake_timers.borrow_mut().take());
+    }
+}
@@ -0,0 +1,258 @@
+//! provides the [`tooltip`] directive which shows a rich tooltip when hovering or focusing an element.
+//!
+//! The tooltips of all the elements are handled by listeners on the document,
+//! which are installed when the first element with a tooltip is created.
+//! The tooltip is rendered into a shared element appended to the body, so it is not clipped
+//! by the `overflow` of the ancestors of the element.
+use crate::{html::attributes::attr, Attribute, Node, Render};
+use std::cell::{Cell, RefCell};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Element, HtmlElement};
+
+/// the attribute which holds the html content of the tooltip
+pub(crate) const TOOLTIP_ATTR: &str = "data-sauron-tooltip";
+
+/// the id of the shared element where the tooltip is rendered
+const TOOLTIP_ID: &str = "sauron-tooltip";
+
+/// the space between the tooltip, the element and the edges of the viewport
+const MARGIN: f64 = 8.0;
+
+thread_local!(static IS_INSTALLED: Cell<bool> = Cell::new(false));
+thread_local!(static DELAY: Cell<i32> = Cell::new(400));
+thread_local!(static STATE: RefCell<State> = RefCell::new(State::default()));
+thread_local!(static SHOW_PENDING: Closure<dyn FnMut()> = Closure::wrap(Box::new(show_pending)));
+
+#[derive(Default)]
+struct State {
+    /// the element where the tooltip is shown
+    anchor: Option<Element>,
+    /// the element waiting for the delay to show its tooltip,
+    /// the timeout is cancelled when the pointer leaves before the delay
+    pending: Option<(Element, i32)>,
+}
+
+/// Show the `content` in a tooltip when the element is hovered or focused.
+///
+/// The tooltip is shown after the pointer rests on the element for the [`delay`],
+/// or right away when the element is focused with the keyboard.
+/// It is placed above the element, or below it if there is no room,
+/// and kept within the viewport. The element is described by the tooltip with `aria-describedby`
+/// while it is shown, and the tooltip is hidden with the `Escape` key.
+///
+/// The content is rendered into html, so the event listeners in the content are not attached.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+///
+/// let view: Node<()> = button(
+///     vec![tooltip(span(vec![], vec![text("Save the "), b(vec![], vec![text("draft")])]))],
+///     vec![text("Save")],
+/// );
+/// ```
+pub fn tooltip<MSG>(content: Node<MSG>) -> Attribute<MSG> {
+    attr(TOOLTIP_ATTR, content.render_to_string())
+}
+
+/// set the delay in milliseconds before the tooltip is shown when hovering an element
+pub fn set_delay(delay_ms: i32) {
+    DELAY.with(|delay| delay.set(delay_ms.max(0)));
+}
+
+/// the delay in milliseconds before the tooltip is shown when hovering an element
+pub fn delay() -> i32 {
+    DELAY.with(|delay| delay.get())
+}
+
+/// install the listeners which handles the tooltips in the document, this is done only once
+pub(crate) fn init() {
+    if IS_INSTALLED.with(|is_installed| is_installed.replace(true)) {
+        return;
+    }
+    let document = crate::document();
+    add_listener(&document, "mouseover", |event| {
+        if let Some(anchor) = anchor_of(event.target()) {
+            enter(anchor, delay());
+        }
+    });
+    add_listener(&document, "focusin", |event| {
+        if let Some(anchor) = anchor_of(event.target()) {
+            enter(anchor, 0);
+        }
+    });
+    let leave = |event: web_sys::Event| {
+        let anchor = match anchor_of(event.target()) {
+            Some(anchor) => anchor,
+            None => return,
+        };
+        // moving between the descendants of the element doesn't leave it
+        let related_target = js_sys::Reflect::get(
+            &event,
+            &wasm_bindgen::JsValue::from_str("relatedTarget"),
+        )
+        .ok()
+        .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+        if anchor.contains(related_target.as_ref()) {
+            return;
+        }
+        hide();
+    };
+    add_listener(&document, "mouseout", leave);
+    add_listener(&document, "focusout", leave);
+    add_listener(&document, "keydown", |event| {
+        if let Some(event) = event.dyn_ref::<web_sys::KeyboardEvent>() {
+            if event.key() == "Escape" {
+                hide();
+            }
+        }
+    });
+    // the tooltip would be detached from the element when the page is scrolled
+    add_listener(&crate::window(), "scroll", |_event| hide());
+}
+
+fn add_listener<F>(target: &web_sys::EventTarget, event_name: &str, f: F)
+where
+    F: FnMut(web_sys::Event) + 'static,
+{
+    let listener: Closure<dyn FnMut(web_sys::Event)> =
+        Closure::wrap(Box::new(f));
+    target
+        .add_event_listener_with_callback_and_bool(
+            event_name,
+            listener.as_ref().unchecked_ref(),
+            true,
+        )
+        .expect("must add the tooltip listener");
+    // the listeners lives for the rest of the page lifetime
+    listener.forget();
+}
+
+/// the closest element which has a tooltip
+fn anchor_of(target: Option<web_sys::EventTarget>) -> Option<Element> {
+    target?
+        .dyn_into::<Element>()
+        .ok()?
+        .closest(&format!("[{}]", TOOLTIP_ATTR))
+        .ok()?
+}
+
+/// show the tooltip of the anchor after the delay
+fn enter(anchor: Element, delay_ms: i32) {
+    let is_current = STATE.with(|state| {
+        let state = state.borrow();
+        state.anchor.as_ref() == Some(&anchor)
+            || state.pending.as_ref().map(|(pending, _)| pending)
+                == Some(&anchor)
+    });
+    if is_current {
+        return;
+    }
+    cancel_pending();
+    if delay_ms == 0 {
+        show(&anchor);
+        return;
+    }
+    let timeout_id = SHOW_PENDING.with(|show_pending| {
+        crate::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                show_pending.as_ref().unchecked_ref(),
+                delay_ms,
+            )
+            .expect("must set the tooltip timeout")
+    });
+    STATE.with(|state| state.borrow_mut().pending = Some((anchor, timeout_id)));
+}
+
+/// show the tooltip of the element which has waited for the delay
+fn show_pending() {
+    if let Some((anchor, _timeout_id)) =
+        STATE.with(|state| state.borrow_mut().pending.take())
+    {
+        show(&anchor);
+    }
+}
+
+fn cancel_pending() {
+    if let Some((_anchor, timeout_id)) =
+        STATE.with(|state| state.borrow_mut().pending.take())
+    {
+        crate::window().clear_timeout_with_handle(timeout_id);
+    }
+}
+
+fn show(anchor: &Element) {
+    hide();
+    // the element could have been removed while waiting for the delay
+    if !anchor.is_connected() {
+        return;
+    }
+    let content = match anchor.get_attribute(TOOLTIP_ATTR) {
+        Some(content) if !content.trim().is_empty() => content,
+        _ => return,
+    };
+    let document = crate::document();
+    let tooltip: HtmlElement = document
+        .create_element("div")
+        .expect("must create the tooltip")
+        .unchecked_into();
+    tooltip.set_id(TOOLTIP_ID);
+    tooltip
+        .set_attribute("role", "tooltip")
+        .expect("must set the role");
+    tooltip.set_inner_html(&content);
+    let style = tooltip.style();
+    style
+        .set_css_text("position:fixed;z-index:2147483647;pointer-events:none;");
+    crate::body()
+        .append_child(&tooltip)
+        .expect("must append the tooltip");
+    position(&tooltip, anchor);
+
+    anchor
+        .set_attribute("aria-describedby", TOOLTIP_ID)
+        .expect("must set aria-describedby");
+    STATE.with(|state| state.borrow_mut().anchor = Some(anchor.clone()));
+}
+
+/// place the tooltip above the anchor, or below it when there is no room above,
+/// keeping it within the viewport
+fn position(tooltip: &HtmlElement, anchor: &Element) {
+    let anchor_rect = anchor.get_bounding_client_rect();
+    let tooltip_rect = tooltip.get_bounding_client_rect();
+    let viewport_width = crate::document()
+        .document_element()
+        .map(|root| f64::from(root.client_width()))
+        .unwrap_or_default();
+
+    let above = anchor_rect.top() - tooltip_rect.height() - MARGIN;
+    let top = if above >= MARGIN {
+        above
+    } else {
+        anchor_rect.bottom() + MARGIN
+    };
+    let centered =
+        anchor_rect.left() + (anchor_rect.width() - tooltip_rect.width()) / 2.0;
+    let left = centered
+        .min(viewport_width - tooltip_rect.width() - MARGIN)
+        .max(MARGIN);
+
+    let style = tooltip.style();
+    style
+        .set_property("top", &format!("{}px", top))
+        .expect("must set top");
+    style
+        .set_property("left", &format!("{}px", left))
+        .expect("must set left");
+}
+
+/// hide the tooltip, including the tooltip which is waiting for the delay
+fn hide() {
+    cancel_pending();
+    if let Some(anchor) = STATE.with(|state| state.borrow_mut().anchor.take()) {
+        let _ = anchor.remove_attribute("aria-describedby");
+    }
+    if let Some(tooltip) = crate::document().get_element_by_id(TOOLTIP_ID) {
+        tooltip.remove();
+    }
+}
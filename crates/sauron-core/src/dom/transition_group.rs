@@ -0,0 +1,131 @@
+//! provides a container which animates its keyed children to their new positions
+//! when they are reordered, using the [FLIP](https://aerotwist.com/blog/flip-your-animations/) technique.
+//!
+//! The positions of the children are recorded before the patches are applied (First),
+//! then after the patches the children are measured again (Last), moved back to
+//! their old positions (Invert) and animated to their new positions (Play).
+//! The children are matched by their `key`, since the reordered children may be
+//! removed and inserted again by the patches.
+use crate::{
+    dom::{animate, AnimationTiming, EXITING_ATTR},
+    html::{attributes::attr, div},
+    Attribute, Node,
+};
+use std::collections::HashMap;
+use wasm_bindgen::JsCast;
+use web_sys::Element;
+
+/// the attribute of the transition group, which is the duration of the animations
+const GROUP_ATTR: &str = "data-sauron-transition-group";
+/// the attribute of the transition group, which is the easing of the animations
+const EASING_ATTR: &str = "data-sauron-transition-easing";
+/// the duration of the animations when the attribute of the group is not a number
+const DEFAULT_DURATION: f64 = 250.0;
+
+/// Creates a `div` of keyed `children`, which are animated from their old positions
+/// to their new positions when they are reordered.
+///
+/// The children must have a [`key`](crate::html::attributes::key).
+/// Combine it with [`animate_enter`](crate::dom::animate_enter) and [`animate_exit`](crate::dom::animate_exit)
+/// to also animate the children which are added and removed.
+pub fn transition_group<MSG>(
+    attrs: impl IntoIterator<Item = Attribute<MSG>>,
+    children: impl IntoIterator<Item = Node<MSG>>,
+    timing: AnimationTiming,
+) -> Node<MSG> {
+    div(
+        attrs.into_iter().chain(vec![
+            attr(GROUP_ATTR, timing.duration),
+            attr(EASING_ATTR, timing.easing),
+        ]),
+        children,
+    )
+}
+
+/// the positions of the keyed children of a transition group, before the patches are applied
+pub(crate) struct GroupPositions {
+    group: Element,
+    positions: HashMap<String, (f64, f64)>,
+}
+
+/// record the positions of the children of the transition groups in the `root_node`
+pub(crate) fn record(root_node: &web_sys::Node) -> Vec<GroupPositions> {
+    let root: &Element = match root_node.dyn_ref() {
+        Some(root) => root,
+        None => return vec![],
+    };
+    let selector = format!("[{}]", GROUP_ATTR);
+    let mut groups = vec![];
+    if root.has_attribute(GROUP_ATTR) {
+        groups.push(root.clone());
+    }
+    if let Ok(found) = root.query_selector_all(&selector) {
+        groups.extend(
+            (0..found.length())
+                .filter_map(|i| found.item(i))
+                .map(|node| node.unchecked_into::<Element>()),
+        );
+    }
+    groups
+        .into_iter()
+        .map(|group| {
+            let positions = keyed_children(&group)
+                .map(|(key, child)| (key, position(&child)))
+                .collect();
+            GroupPositions { group, positions }
+        })
+        .collect()
+}
+
+/// animate the children of the transition groups from their recorded positions to their current positions
+pub(crate) fn play(recorded: Vec<GroupPositions>) {
+    for GroupPositions { group, positions } in recorded {
+        if !group.is_connected() {
+            continue;
+        }
+        let duration = group
+            .get_attribute(GROUP_ATTR)
+            .and_then(|duration| duration.parse().ok())
+            .unwrap_or(DEFAULT_DURATION);
+        let easing = group
+            .get_attribute(EASING_ATTR)
+            .unwrap_or_else(|| "ease".to_string());
+        for (key, child) in keyed_children(&group) {
+            let (old_left, old_top) = match positions.get(&key) {
+                Some(old) => *old,
+                None => continue,
+            };
+            let (left, top) = position(&child);
+            let (dx, dy) = (old_left - left, old_top - top);
+            if dx.abs() < 0.5 && dy.abs() < 0.5 {
+                continue;
+            }
+            animate::play_keyframes(
+                &child,
+                &[
+                    vec![(
+                        "transform".to_string(),
+                        format!("translate({}px, {}px)", dx, dy),
+                    )],
+                    vec![("transform".to_string(), "none".to_string())],
+                ],
+                duration,
+                &easing,
+            );
+        }
+    }
+}
+
+/// the children of the group which have a key and are not playing their exit animation
+fn keyed_children(group: &Element) -> impl Iterator<Item = (String, Element)> {
+    let children = group.children();
+    (0..children.length())
+        .filter_map(move |i| children.item(i))
+        .filter(|child| !child.has_attribute(EXITING_ATTR))
+        .filter_map(|child| child.get_attribute("key").map(|key| (key, child)))
+}
+
+fn position(element: &Element) -> (f64, f64) {
+    let rect = element.get_bounding_client_rect();
+    (rect.left(), rect.top())
+}
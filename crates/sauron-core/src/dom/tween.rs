@@ -0,0 +1,289 @@
+//! provides the Cmds which animate a number on every animation frame, such as the width
+//! of a progress bar or the height of a panel, dispatching the MSG created from the value.
+//!
+use crate::dom::{capabilities, cmd::Cmd, sub::AnimationFrameLoop, Dispatch};
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::Rc,
+};
+
+/// The [easing](https://easings.net/) of a tween, which maps the elapsed portion of the
+/// duration into the portion of the distance travelled
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    /// constant speed
+    Linear,
+    /// starts slow and speeds up
+    EaseInQuad,
+    /// starts fast and slows down
+    EaseOutQuad,
+    /// starts slow, speeds up, then slows down
+    EaseInOutQuad,
+    /// starts fast and slows down, more pronounced than [`Easing::EaseOutQuad`]
+    EaseOutCubic,
+}
+
+impl Easing {
+    /// the portion of the distance travelled at `t`, which is the elapsed portion of the duration
+    /// clamped to `0.0..=1.0`
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = t.max(0.0).min(1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInQuad => t * t,
+            Easing::EaseOutQuad => t * (2.0 - t),
+            Easing::EaseInOutQuad => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+            Easing::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+        }
+    }
+}
+
+/// The physical properties of a spring animation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spring {
+    /// how strong the spring pulls the value to the target
+    pub stiffness: f64,
+    /// how much the motion is slowed down, a lower damping makes the spring bounce more
+    pub damping: f64,
+    /// the mass of the moving value, a heavier mass makes the motion slower
+    pub mass: f64,
+    /// the spring is at rest when both the distance to the target and the velocity are below this
+    pub precision: f64,
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Spring {
+            stiffness: 170.0,
+            damping: 26.0,
+            mass: 1.0,
+            precision: 0.01,
+        }
+    }
+}
+
+impl Spring {
+    /// a slow spring without bouncing
+    pub fn gentle() -> Self {
+        Spring {
+            stiffness: 120.0,
+            damping: 14.0,
+            ..Default::default()
+        }
+    }
+
+    /// a fast spring which bounces around the target
+    pub fn wobbly() -> Self {
+        Spring {
+            stiffness: 180.0,
+            damping: 12.0,
+            ..Default::default()
+        }
+    }
+
+    /// move the `position` and the `velocity` toward the `target` by `dt` seconds,
+    /// in steps small enough to keep the motion stable
+    fn step(
+        &self,
+        mut position: f64,
+        mut velocity: f64,
+        target: f64,
+        dt: f64,
+    ) -> (f64, f64) {
+        const MAX_STEP: f64 = 1.0 / 240.0;
+        let mut remaining = dt;
+        while remaining > 0.0 {
+            let dt = remaining.min(MAX_STEP);
+            let force =
+                -self.stiffness * (position - target) - self.damping * velocity;
+            velocity += force / self.mass * dt;
+            position += velocity * dt;
+            remaining -= dt;
+        }
+        (position, velocity)
+    }
+
+    fn is_at_rest(&self, position: f64, velocity: f64, target: f64) -> bool {
+        (position - target).abs() < self.precision
+            && velocity.abs() < self.precision
+    }
+}
+
+/// A handle to the animation started with [`Cmd::tween`] or [`Cmd::spring`],
+/// this can be stored in the app to stop the animation, ie: when a new animation of the
+/// same value is started.
+#[derive(Clone, Default)]
+pub struct AnimationHandle {
+    inner: Rc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// the animation is cancelled, set also before the Cmd is emitted
+    is_cancelled: Cell<bool>,
+    /// the animation frame loop, set when the Cmd is emitted
+    frame_loop: RefCell<Option<AnimationFrameLoop>>,
+}
+
+impl AnimationHandle {
+    /// stop the animation, the MSG will no longer be dispatched
+    pub fn cancel(&self) {
+        self.inner.is_cancelled.set(true);
+        self.stop();
+    }
+
+    /// returns true if the animation has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled.get()
+    }
+
+    /// start the frame loop, unless the animation has been cancelled already
+    fn start(&self, frame_loop: AnimationFrameLoop) {
+        if self.is_cancelled() {
+            frame_loop.stop();
+            return;
+        }
+        frame_loop.start();
+        *self.inner.frame_loop.borrow_mut() = Some(frame_loop);
+    }
+
+    /// stop the frame loop, this is also called from within the frame of the loop
+    fn stop(&self) {
+        if let Some(frame_loop) = self.inner.frame_loop.borrow_mut().take() {
+            frame_loop.stop();
+        }
+    }
+}
+
+impl fmt::Debug for AnimationHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AnimationHandle")
+            .field("is_cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+impl<DSP> Cmd<DSP>
+where
+    DSP: 'static,
+{
+    /// Creates a Cmd which animates a value from `from` to `to` in `duration` milliseconds,
+    /// dispatching the MSG created from the value on every animation frame.
+    /// The last MSG is always created from `to`.
+    ///
+    /// When the user prefers reduced motion, only the MSG of `to` is dispatched.
+    /// # Example
+    /// ```rust,ignore
+    /// Msg::Expand => {
+    ///     let (cmd, animation) = Cmd::tween(self.height, 240.0, 300.0, Easing::EaseOutCubic, Msg::SetHeight);
+    ///     if let Some(previous) = self.animation.replace(animation) {
+    ///         previous.cancel();
+    ///     }
+    ///     cmd
+    /// }
+    /// Msg::SetHeight(height) => {
+    ///     self.height = height;
+    ///     Cmd::none()
+    /// }
+    /// ```
+    pub fn tween<F, MSG>(
+        from: f64,
+        to: f64,
+        duration: f64,
+        easing: Easing,
+        f: F,
+    ) -> (Self, AnimationHandle)
+    where
+        F: Fn(f64) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        let handle = AnimationHandle::default();
+        let handle_clone = handle.clone();
+        let cmd = Cmd::new(move |program: DSP| {
+            if capabilities().prefers_reduced_motion || duration <= 0.0 {
+                program.dispatch(f(to));
+                return;
+            }
+            let mut start = None;
+            let frame_handle = handle_clone.clone();
+            let frame_loop = AnimationFrameLoop::new(move |timestamp| {
+                if frame_handle.is_cancelled() {
+                    return false;
+                }
+                let start = *start.get_or_insert(timestamp);
+                let t = (timestamp - start) / duration;
+                if t >= 1.0 {
+                    program.dispatch(f(to));
+                    frame_handle.stop();
+                    return false;
+                }
+                program.dispatch(f(from + (to - from) * easing.apply(t)));
+                true
+            });
+            handle_clone.start(frame_loop);
+        })
+        .no_render();
+        (cmd, handle)
+    }
+
+    /// Creates a Cmd which animates a value from `from` to `to` with the physics of a `spring`,
+    /// dispatching the MSG created from the value on every animation frame until the spring is at rest.
+    /// The last MSG is always created from `to`.
+    ///
+    /// When the user prefers reduced motion, only the MSG of `to` is dispatched.
+    pub fn spring<F, MSG>(
+        from: f64,
+        to: f64,
+        spring: Spring,
+        f: F,
+    ) -> (Self, AnimationHandle)
+    where
+        F: Fn(f64) -> MSG + 'static,
+        MSG: 'static,
+        DSP: Dispatch<MSG> + Clone,
+    {
+        let handle = AnimationHandle::default();
+        let handle_clone = handle.clone();
+        let cmd = Cmd::new(move |program: DSP| {
+            if capabilities().prefers_reduced_motion {
+                program.dispatch(f(to));
+                return;
+            }
+            let mut last_timestamp: Option<f64> = None;
+            let (mut position, mut velocity) = (from, 0.0);
+            let frame_handle = handle_clone.clone();
+            let frame_loop = AnimationFrameLoop::new(move |timestamp| {
+                if frame_handle.is_cancelled() {
+                    return false;
+                }
+                // a frame which comes late, ie: from a hidden tab, is not simulated at once
+                let dt = last_timestamp
+                    .map(|last| ((timestamp - last) / 1000.0).min(0.1))
+                    .unwrap_or(0.0);
+                last_timestamp = Some(timestamp);
+                let (next_position, next_velocity) =
+                    spring.step(position, velocity, to, dt);
+                position = next_position;
+                velocity = next_velocity;
+                if spring.is_at_rest(position, velocity, to) {
+                    program.dispatch(f(to));
+                    frame_handle.stop();
+                    return false;
+                }
+                program.dispatch(f(position));
+                true
+            });
+            handle_clone.start(frame_loop);
+        })
+        .no_render();
+        (cmd, handle)
+    }
+}
@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use wasm_bindgen::{closure::Closure, JsCast};
 
 thread_local!(static WINDOW: web_sys::Window = web_sys::window().expect("no global `window` exists"));
@@ -30,6 +31,64 @@ pub(crate) fn request_animation_frame_for_closure(f: &Closure<dyn FnMut()>) {
         .expect("should register `requestAnimationFrame` OK");
 }
 
+/// execute the closure when the browser is idle,
+/// or in the next task when the browser doesn't support `requestIdleCallback`, ie: Safari
+pub(crate) fn request_idle_callback<F>(f: F)
+where
+    F: FnOnce() + 'static,
+{
+    let closure: Closure<dyn FnMut() + 'static> = Closure::once(f);
+    let window = window();
+    let has_idle_callback = js_sys::Reflect::has(
+        &window,
+        &wasm_bindgen::JsValue::from_str("requestIdleCallback"),
+    )
+    .unwrap_or(false);
+    if has_idle_callback {
+        window
+            .request_idle_callback(closure.as_ref().unchecked_ref())
+            .expect("should register `requestIdleCallback` OK");
+    } else {
+        crate::dom::timer::set_timeout(closure.as_ref().unchecked_ref(), 1);
+    }
+    closure.forget();
+}
+
+/// resolves when the browser is idle, so a long running work can yield to the browser
+pub(crate) async fn idle() {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        request_idle_callback(move || {
+            resolve
+                .call0(&wasm_bindgen::JsValue::NULL)
+                .expect("must resolve");
+        });
+    });
+    wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .expect("must not be rejected");
+}
+
+thread_local!(static IN_ANIMATION_FRAME: Cell<bool> = Cell::new(false));
+
+/// execute the function while marking that the code is running inside an animation frame callback,
+/// so the msgs dispatched in it are processed in the same frame instead of the next one.
+pub(crate) fn with_animation_frame<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let was_in_animation_frame =
+        IN_ANIMATION_FRAME.with(|cell| cell.replace(true));
+    let result = f();
+    IN_ANIMATION_FRAME.with(|cell| cell.set(was_in_animation_frame));
+    result
+}
+
+/// returns true if the code is running inside an animation frame callback
+#[allow(unused)]
+pub(crate) fn is_in_animation_frame() -> bool {
+    IN_ANIMATION_FRAME.with(|cell| cell.get())
+}
+
 thread_local!(static DOCUMENT: web_sys::Document = window().document().expect("should have a document on window"));
 /// provides access to the document element
 pub fn document() -> web_sys::Document {
@@ -41,6 +100,13 @@ pub fn body() -> web_sys::HtmlElement {
     document().body().expect("document should have a body")
 }
 
+/// the state embedded in the page by the server with [`serialized_state`](crate::html::serialized_state)
+pub fn embedded_state() -> Option<String> {
+    document()
+        .get_element_by_id(crate::html::SERIALIZED_STATE_ID)
+        .and_then(|script| script.text_content())
+}
+
 /// provides access to the window Performance api
 pub fn performance() -> web_sys::Performance {
     window()
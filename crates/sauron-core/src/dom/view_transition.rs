@@ -0,0 +1,38 @@
+//! wraps the update of the DOM in a [view transition](https://developer.mozilla.org/en-US/docs/Web/API/View_Transitions_API),
+//! which cross-fades the old and the new view, or morphs the elements with the same
+//! `view-transition-name` style, ie: a thumbnail into the header image of the detail page.
+use js_sys::{Function, Reflect};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+/// returns true if the browser supports `document.startViewTransition`
+pub(crate) fn is_supported() -> bool {
+    start_view_transition().is_some()
+}
+
+/// call `update` in a view transition, the browser captures the old view before calling it.
+/// The `update` is called right away if the browser doesn't support view transitions.
+pub(crate) fn start<F>(update: F)
+where
+    F: FnOnce() + 'static,
+{
+    match start_view_transition() {
+        Some(start_view_transition) => {
+            let callback = Closure::once_into_js(update);
+            if let Err(e) =
+                start_view_transition.call1(&crate::document(), &callback)
+            {
+                log::error!("unable to start the view transition: {:?}", e);
+            }
+        }
+        None => update(),
+    }
+}
+
+fn start_view_transition() -> Option<Function> {
+    Reflect::get(
+        &crate::document(),
+        &JsValue::from_str("startViewTransition"),
+    )
+    .ok()
+    .and_then(|start| start.dyn_into().ok())
+}
@@ -0,0 +1,112 @@
+//! The elements with the [`on_enter_viewport`] and [`on_leave_viewport`] listeners are observed
+//! by one `IntersectionObserver` shared by all the programs, instead of one observer per element.
+//!
+//! The observer dispatches the `enterviewport` and `leaveviewport` events to the element
+//! when its visibility changes. The element is unobserved when its listeners are removed,
+//! which is also when the element is removed by a patch.
+//!
+//! [`on_enter_viewport`]: crate::dom::events::on_enter_viewport
+//! [`on_leave_viewport`]: crate::dom::events::on_leave_viewport
+use crate::dom::created_node::DATA_VDOM_ID;
+use std::{cell::RefCell, collections::HashMap};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry};
+
+/// the event dispatched when the element becomes visible in the viewport
+pub(crate) const ENTER_VIEWPORT: &str = "enterviewport";
+/// the event dispatched when the element is no longer visible in the viewport
+pub(crate) const LEAVE_VIEWPORT: &str = "leaveviewport";
+
+/// the callback of the observer, which is kept alive as long as the observer
+type ObserverCallback = Closure<dyn FnMut(js_sys::Array)>;
+
+struct Observed {
+    element: Element,
+    /// the element starts as not visible,
+    /// so the first notification of an element outside of the viewport is not a leave event
+    is_visible: bool,
+}
+
+thread_local!(static OBSERVER: (IntersectionObserver, ObserverCallback) = create_observer());
+thread_local!(static OBSERVED: RefCell<HashMap<usize, Observed>> = RefCell::new(HashMap::new()));
+
+fn create_observer() -> (IntersectionObserver, ObserverCallback) {
+    let callback: ObserverCallback = Closure::wrap(Box::new(notify));
+    let observer = IntersectionObserver::new(callback.as_ref().unchecked_ref())
+        .expect("must create an intersection observer");
+    (observer, callback)
+}
+
+/// returns true if the event is dispatched by the shared observer
+pub(crate) fn is_viewport_event(event: &str) -> bool {
+    event == ENTER_VIEWPORT || event == LEAVE_VIEWPORT
+}
+
+/// start observing the element, if it's not observed yet
+pub(crate) fn observe(vdom_id: usize, element: &Element) {
+    let is_observed = OBSERVED.with(|observed| {
+        observed.borrow_mut().insert(
+            vdom_id,
+            Observed {
+                element: element.clone(),
+                is_visible: false,
+            },
+        )
+    });
+    if is_observed.is_none() {
+        OBSERVER.with(|(observer, _)| observer.observe(element));
+    }
+}
+
+/// stop observing the element with the `vdom_id`, if it is observed
+pub(crate) fn unobserve(vdom_id: usize) {
+    let observed =
+        OBSERVED.with(|observed| observed.borrow_mut().remove(&vdom_id));
+    if let Some(observed) = observed {
+        OBSERVER.with(|(observer, _)| observer.unobserve(&observed.element));
+    }
+}
+
+/// the number of the elements being observed
+pub(crate) fn observed_count() -> usize {
+    OBSERVED.with(|observed| observed.borrow().len())
+}
+
+/// dispatch the events to the elements whose visibility changed
+fn notify(entries: js_sys::Array) {
+    for entry in entries.iter() {
+        let entry: IntersectionObserverEntry = entry.unchecked_into();
+        let is_visible = entry.is_intersecting();
+        let target = entry.target();
+        let is_changed = target
+            .get_attribute(DATA_VDOM_ID)
+            .and_then(|vdom_id| vdom_id.parse::<usize>().ok())
+            .and_then(|vdom_id| {
+                OBSERVED.with(|observed| {
+                    observed.borrow_mut().get_mut(&vdom_id).map(|observed| {
+                        std::mem::replace(&mut observed.is_visible, is_visible)
+                            != is_visible
+                    })
+                })
+            })
+            .unwrap_or(false);
+        if !is_changed {
+            continue;
+        }
+        let event_name = if is_visible {
+            ENTER_VIEWPORT
+        } else {
+            LEAVE_VIEWPORT
+        };
+        let mut event_init = web_sys::CustomEventInit::new();
+        event_init.detail(&entry);
+        let event = web_sys::CustomEvent::new_with_event_init_dict(
+            event_name,
+            &event_init,
+        )
+        .expect("must create the viewport event");
+        if let Err(e) = target.dispatch_event(&event) {
+            log::error!("unable to dispatch {}: {:?}", event_name, e);
+        }
+    }
+}
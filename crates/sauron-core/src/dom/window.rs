@@ -1,6 +1,11 @@
+//! provides access to the browser window and the typed window-level listeners
+//!
 use crate::{
-    dom::created_node::create_closure_wrap, Application, Attribute, Cmd,
-    Dispatch,
+    dom::{
+        created_node::create_closure_wrap,
+        sub::{Sub, Subscription},
+    },
+    Application, Attribute, Cmd, Dispatch,
 };
 use std::fmt::Debug;
 use wasm_bindgen::{self, prelude::*, JsCast};
@@ -55,6 +60,9 @@ impl Window {
 
     /// Creates a Cmd in which the MSG will be emitted
     /// whenever the browser is resized
+    ///
+    /// Note: the listener is kept for the rest of the page lifetime,
+    /// use the [`on_resize`] subscription to have it removed when the program is unmounted.
     pub fn on_resize<F, APP, MSG>(cb: F) -> Cmd<APP, MSG>
     where
         F: Fn(i32, i32) -> MSG + Clone + 'static,
@@ -78,6 +86,9 @@ impl Window {
 
     /// attached a callback and will be triggered when the hash portion of the window location
     /// url is changed
+    ///
+    /// Note: the listener is kept for the rest of the page lifetime,
+    /// use the [`on_hashchange`] subscription to have it removed when the program is unmounted.
     pub fn on_hashchange<F, APP, MSG>(cb: F) -> Cmd<APP, MSG>
     where
         F: Fn(String) -> MSG + Clone + 'static,
@@ -144,3 +155,74 @@ impl Window {
         location.set_hash(hash).expect("must set the location hash");
     }
 }
+
+/// Creates a Sub which emits the MSG with the new width and height of the window
+/// whenever the window is resized
+pub fn on_resize<F, DSP, MSG>(f: F) -> Sub<DSP>
+where
+    F: Fn(i32, i32) -> MSG + 'static,
+    MSG: 'static,
+    DSP: Dispatch<MSG> + Clone + 'static,
+{
+    listen("resize", move |_event| {
+        let (width, height) = Window::get_size();
+        f(width, height)
+    })
+}
+
+/// Creates a Sub which emits the MSG with the horizontal and vertical scroll offset of the page
+/// whenever the page is scrolled
+pub fn on_scroll<F, DSP, MSG>(f: F) -> Sub<DSP>
+where
+    F: Fn(i32, i32) -> MSG + 'static,
+    MSG: 'static,
+    DSP: Dispatch<MSG> + Clone + 'static,
+{
+    listen("scroll", move |_event| {
+        let window = crate::window();
+        let scroll_x = window.scroll_x().expect("must get the scroll x");
+        let scroll_y = window.scroll_y().expect("must get the scroll y");
+        f(scroll_x as i32, scroll_y as i32)
+    })
+}
+
+/// Creates a Sub which emits the MSG with the new hash of the location url
+/// whenever the hash is changed
+pub fn on_hashchange<F, DSP, MSG>(f: F) -> Sub<DSP>
+where
+    F: Fn(String) -> MSG + 'static,
+    MSG: 'static,
+    DSP: Dispatch<MSG> + Clone + 'static,
+{
+    listen("hashchange", move |_event| f(Window::get_hash()))
+}
+
+/// listen to the event of the window, the listener is removed when unsubscribed
+fn listen<F, DSP, MSG>(event_name: &'static str, f: F) -> Sub<DSP>
+where
+    F: Fn(web_sys::Event) -> MSG + 'static,
+    MSG: 'static,
+    DSP: Dispatch<MSG> + Clone + 'static,
+{
+    Sub::new(move |program: DSP| {
+        let listener: Closure<dyn FnMut(web_sys::Event)> =
+            Closure::wrap(Box::new(move |event: web_sys::Event| {
+                program.dispatch(f(event));
+            }));
+        let window = crate::window();
+        window
+            .add_event_listener_with_callback(
+                event_name,
+                listener.as_ref().unchecked_ref(),
+            )
+            .expect("must add the window listener");
+        Subscription::new(move || {
+            window
+                .remove_event_listener_with_callback(
+                    event_name,
+                    listener.as_ref().unchecked_ref(),
+                )
+                .expect("must remove the window listener");
+        })
+    })
+}
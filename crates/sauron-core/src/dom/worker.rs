@@ -0,0 +1,982 @@
+//! Runs the `update` and the `view` of an app inside a dedicated Web Worker,
+//! so the CPU heavy update logic such as parsers and simulations don't block the UI thread.
+//!
+//! The worker diffs the views and posts the patches serialized into json to the main thread,
+//! where the [`WorkerHost`] applies them into the DOM. The events of the view are serialized
+//! into a [`RemoteEvent`] and posted back to the worker, which maps them into the MSG
+//! of the app with the listeners created by [`on_remote`].
+//!
+//! The closures can not be sent across the threads, so the listeners of the views which run
+//! in the worker are created with [`on_remote`] instead of the typed listeners such as `on_click`,
+//! and the msgs dispatched from the main thread with [`WorkerHost::dispatch`] are serialized.
+//!
+//! # Examples
+//! The worker script loads the wasm module built with `wasm-pack build --target no-modules`,
+//! then calls the entry point which starts the app:
+//! ```rust,ignore
+//! #[wasm_bindgen]
+//! pub fn start_worker() {
+//!     WorkerProgram::start(Simulation::default());
+//! }
+//!
+//! impl WorkerApplication<Msg> for Simulation {
+//!     fn update(&mut self, msg: Msg) -> Cmd<WorkerProgram<Self, Msg>> {
+//!         match msg {
+//!             Msg::Step(steps) => self.run(steps),
+//!         }
+//!         Cmd::none()
+//!     }
+//!
+//!     fn view(&self) -> Node<Msg> {
+//!         button(
+//!             vec![on_remote("click", |_event| Msg::Step(1000))],
+//!             vec![text(self.generation)],
+//!         )
+//!     }
+//! }
+//! ```
+//! while the main thread mounts the host, which starts the worker:
+//! ```rust,ignore
+//! #[wasm_bindgen(start)]
+//! pub fn main() {
+//!     WorkerHost::<Msg>::mount("./worker.js", &sauron::body());
+//! }
+//! ```
+use crate::{
+    dom::{cmd::Cmd, event_delegation, Dispatch, DomUpdater, Event},
+    html::attributes::{AttributeValue, Style, Value},
+    vdom::patch::{
+        AddAttributes, AppendChildren, ChangeComment, ChangeText, InsertNode,
+        MoveNode, RemoveAttributes, RemoveChildrenFrom, RemoveNode,
+        ReplaceNode, TreePath,
+    },
+    Attribute, Node, Patch, Text,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    cell::RefCell, collections::HashSet, fmt, marker::PhantomData, rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{
+    DedicatedWorkerGlobalScope, HtmlInputElement, HtmlTextAreaElement,
+    KeyboardEvent, MessageEvent, MouseEvent, Worker,
+};
+
+thread_local! {
+    /// the tags and the attribute names received from the worker,
+    /// which are leaked once since the vdom uses `&'static str` for them
+    static INTERNED: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+}
+
+/// The node of a view which is serialized to be sent across the threads
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum RemoteNode {
+    /// an element with its attributes and children
+    Element {
+        /// the namespace of the element, ie: svg
+        namespace: Option<String>,
+        /// the tag of the element
+        tag: String,
+        /// the attributes of the element
+        attrs: Vec<RemoteAttribute>,
+        /// the child nodes of the element
+        children: Vec<RemoteNode>,
+    },
+    /// a text node
+    Text(String),
+    /// a comment node
+    Comment(String),
+}
+
+/// The attribute of an element which is serialized to be sent across the threads
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RemoteAttribute {
+    /// the namespace of the attribute, ie: xlink
+    pub namespace: Option<String>,
+    /// the name of the attribute, which is the event name for the listeners
+    pub name: String,
+    /// the values of the attribute
+    pub values: Vec<RemoteValue>,
+}
+
+/// The value of an attribute which is serialized to be sent across the threads
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum RemoteValue {
+    /// a bool attribute such as `checked`
+    Bool(bool),
+    /// the text of any other attribute
+    Text(String),
+    /// the name and the value of the styles
+    Style(Vec<(String, String)>),
+    /// a property of the element such as `value`
+    Property(String),
+    /// the element listens to the event of the attribute name, which is posted to the worker
+    Listener,
+}
+
+/// The patch of a view which is serialized to be sent across the threads
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum RemotePatch {
+    /// insert the node before the node at the path
+    InsertNode {
+        /// the tag of the node at the path
+        tag: Option<String>,
+        /// the path of the node
+        path: Vec<usize>,
+        /// the node to be inserted
+        node: RemoteNode,
+    },
+    /// move the node at the path before the node at the before path,
+    /// or to the end of its parent when there is none
+    MoveNode {
+        /// the tag of the node at the path
+        tag: Option<String>,
+        /// the path of the node
+        path: Vec<usize>,
+        /// the path of the node it is moved before
+        before_path: Option<Vec<usize>>,
+    },
+    /// add or change the attributes of the element at the path
+    AddAttributes {
+        /// the tag of the element
+        tag: String,
+        /// the path of the element
+        path: Vec<usize>,
+        /// the attributes to be set
+        attrs: Vec<RemoteAttribute>,
+    },
+    /// remove the attributes of the element at the path
+    RemoveAttributes {
+        /// the tag of the element
+        tag: String,
+        /// the path of the element
+        path: Vec<usize>,
+        /// the attributes to be removed
+        attrs: Vec<RemoteAttribute>,
+    },
+    /// replace the node at the path
+    ReplaceNode {
+        /// the tag of the node at the path
+        tag: Option<String>,
+        /// the path of the node
+        path: Vec<usize>,
+        /// the node which replaces it
+        replacement: RemoteNode,
+    },
+    /// remove the node at the path
+    RemoveNode {
+        /// the tag of the node at the path
+        tag: Option<String>,
+        /// the path of the node
+        path: Vec<usize>,
+    },
+    /// remove the children of the element at the path, from the child at the start index
+    RemoveChildrenFrom {
+        /// the tag of the element
+        tag: String,
+        /// the path of the element
+        path: Vec<usize>,
+        /// the index of the first child to be removed
+        start_index: usize,
+    },
+    /// append the children into the element at the path
+    AppendChildren {
+        /// the tag of the element
+        tag: String,
+        /// the path of the element
+        path: Vec<usize>,
+        /// the nodes to be appended
+        children: Vec<RemoteNode>,
+    },
+    /// change the text of the text node at the path
+    ChangeText {
+        /// the path of the text node
+        path: Vec<usize>,
+        /// the new text
+        text: String,
+    },
+    /// change the comment at the path
+    ChangeComment {
+        /// the path of the comment
+        path: Vec<usize>,
+        /// the new comment
+        comment: String,
+    },
+}
+
+/// The event of the view which is posted from the main thread to the worker,
+/// with the values which are read from the event and its target.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct RemoteEvent {
+    /// the path of the element which listens to the event
+    pub path: Vec<usize>,
+    /// the name of the event, ie: `click`
+    pub name: String,
+    /// the value of the input or textarea which is the target of the event
+    pub value: Option<String>,
+    /// whether the checkbox which is the target of the event is checked
+    pub checked: Option<bool>,
+    /// the key of the keyboard events
+    pub key: Option<String>,
+    /// the position of the mouse events, relative to the viewport
+    pub client_x: Option<i32>,
+    /// the position of the mouse events, relative to the viewport
+    pub client_y: Option<i32>,
+}
+
+/// the messages posted by the worker to the main thread
+#[derive(Serialize, Deserialize)]
+enum FromWorker {
+    /// the initial view of the app
+    Mount(RemoteNode),
+    /// the patches of the updated view
+    Patch(Vec<RemotePatch>),
+}
+
+/// the messages posted by the main thread to the worker
+#[derive(Serialize, Deserialize)]
+enum ToWorker<MSG> {
+    Event(RemoteEvent),
+    Msg(MSG),
+}
+
+/// Creates the MSG from the [`RemoteEvent`] of the listeners created with [`on_remote`]
+pub struct RemoteListener<MSG>(Rc<dyn Fn(&RemoteEvent) -> MSG>);
+
+impl<MSG> PartialEq for RemoteListener<MSG> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<MSG> fmt::Debug for RemoteListener<MSG> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RemoteListener")
+    }
+}
+
+/// Listen to the event `event_name` of an element in the view of a [`WorkerApplication`].
+///
+/// The event is read into a [`RemoteEvent`] in the main thread,
+/// which is then mapped into the MSG in the worker.
+pub fn on_remote<F, MSG>(event_name: &'static str, f: F) -> Attribute<MSG>
+where
+    F: Fn(&RemoteEvent) -> MSG + 'static,
+    MSG: 'static,
+{
+    mt_dom::attr(
+        event_name,
+        AttributeValue::from_custom(RemoteListener(Rc::new(f))),
+    )
+}
+
+/// An app whose `update` and `view` run inside a dedicated Web Worker,
+/// which is started with [`WorkerProgram::start`]
+pub trait WorkerApplication<MSG>: Sized + 'static
+where
+    MSG: 'static,
+{
+    /// called right after the initial view is posted to the main thread
+    fn init(&mut self) -> Cmd<WorkerProgram<Self, MSG>> {
+        Cmd::none()
+    }
+
+    /// called for each of the msgs, including the msgs mapped from the events of the view
+    fn update(&mut self, msg: MSG) -> Cmd<WorkerProgram<Self, MSG>>;
+
+    /// the view of the app, its listeners are created with [`on_remote`]
+    fn view(&self) -> Node<MSG>;
+}
+
+/// Runs the [`WorkerApplication`] inside the worker, posting the patches of its view
+/// to the [`WorkerHost`] of the main thread.
+pub struct WorkerProgram<APP, MSG>
+where
+    MSG: 'static,
+{
+    app: Rc<RefCell<APP>>,
+    current_vdom: Rc<RefCell<Node<MSG>>>,
+    scope: DedicatedWorkerGlobalScope,
+}
+
+impl<APP, MSG> Clone for WorkerProgram<APP, MSG>
+where
+    MSG: 'static,
+{
+    fn clone(&self) -> Self {
+        WorkerProgram {
+            app: Rc::clone(&self.app),
+            current_vdom: Rc::clone(&self.current_vdom),
+            scope: self.scope.clone(),
+        }
+    }
+}
+
+impl<APP, MSG> WorkerProgram<APP, MSG>
+where
+    MSG: DeserializeOwned + 'static,
+    APP: WorkerApplication<MSG>,
+{
+    /// Start the app in this worker, posting its initial view to the main thread
+    /// and listening to the events and the msgs posted by the main thread.
+    ///
+    /// # Panics
+    /// if this is not called inside a dedicated worker
+    pub fn start(app: APP) -> Self {
+        let scope: DedicatedWorkerGlobalScope = js_sys::global()
+            .dyn_into()
+            .expect("must be started inside a dedicated worker");
+        let view = app.view();
+        let program = WorkerProgram {
+            app: Rc::new(RefCell::new(app)),
+            current_vdom: Rc::new(RefCell::new(view)),
+            scope,
+        };
+        let program_clone = program.clone();
+        let on_message: Closure<dyn FnMut(MessageEvent)> =
+            Closure::wrap(Box::new(move |message: MessageEvent| {
+                program_clone.receive(message)
+            }));
+        program
+            .scope
+            .set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        // the worker listens for as long as it lives
+        on_message.forget();
+
+        let mount =
+            FromWorker::Mount(to_remote_node(&program.current_vdom.borrow()));
+        program.post(&mount);
+        let cmd = program.app.borrow_mut().init();
+        cmd.emit(&program);
+        program
+    }
+
+    /// map the event or the msg posted by the main thread, then dispatch it
+    fn receive(&self, message: MessageEvent) {
+        let json = match message.data().as_string() {
+            Some(json) => json,
+            None => return,
+        };
+        match serde_json::from_str::<ToWorker<MSG>>(&json) {
+            Ok(ToWorker::Msg(msg)) => self.dispatch(msg),
+            Ok(ToWorker::Event(event)) => {
+                let msg =
+                    find_remote_listener(&self.current_vdom.borrow(), &event)
+                        .map(|listener| (listener.0)(&event));
+                if let Some(msg) = msg {
+                    self.dispatch(msg);
+                }
+            }
+            Err(e) => {
+                log::error!("unable to deserialize the worker message: {}", e)
+            }
+        }
+    }
+
+    /// update the app with the msgs, then post the patches of its view to the main thread
+    fn dispatch_inner(&self, msgs: Vec<MSG>) {
+        let all_cmd = msgs
+            .into_iter()
+            .map(|msg| self.app.borrow_mut().update(msg));
+        let cmd = Cmd::batch(all_cmd);
+        if cmd.modifier.should_update_view {
+            let new_vdom = self.app.borrow().view();
+            let remote_patches: Vec<RemotePatch> = {
+                let current_vdom = self.current_vdom.borrow();
+                crate::diff(&current_vdom, &new_vdom)
+                    .iter()
+                    .map(to_remote_patch)
+                    .collect()
+            };
+            *self.current_vdom.borrow_mut() = new_vdom;
+            if !remote_patches.is_empty() {
+                self.post(&FromWorker::Patch(remote_patches));
+            }
+        }
+        cmd.emit(self);
+    }
+
+    fn post(&self, message: &FromWorker) {
+        let json = serde_json::to_string(message)
+            .expect("must serialize the worker message");
+        if let Err(e) = self.scope.post_message(&JsValue::from_str(&json)) {
+            log::error!("unable to post the worker message: {:?}", e);
+        }
+    }
+}
+
+impl<APP, MSG> Dispatch<MSG> for WorkerProgram<APP, MSG>
+where
+    MSG: DeserializeOwned + 'static,
+    APP: WorkerApplication<MSG>,
+{
+    fn dispatch_multiple(&self, msgs: Vec<MSG>) {
+        self.dispatch_inner(msgs)
+    }
+
+    fn dispatch(&self, msg: MSG) {
+        self.dispatch_inner(vec![msg])
+    }
+}
+
+/// The event of an element in the view of the [`WorkerHost`],
+/// which is read into a [`RemoteEvent`] before it is posted to the worker
+pub struct HostEvent {
+    node: web_sys::Node,
+    event: RemoteEvent,
+}
+
+/// Mounts the view of a [`WorkerApplication`] which runs inside a worker, applying the patches
+/// posted by the worker into the DOM and posting the events of the view back to the worker.
+pub struct WorkerHost<MSG> {
+    worker: Worker,
+    mount_node: web_sys::Node,
+    dom_updater: Rc<RefCell<Option<DomUpdater<HostEvent>>>>,
+    on_message: Rc<RefCell<Option<Closure<dyn FnMut(MessageEvent)>>>>,
+    _msg: PhantomData<MSG>,
+}
+
+impl<MSG> Clone for WorkerHost<MSG> {
+    fn clone(&self) -> Self {
+        WorkerHost {
+            worker: self.worker.clone(),
+            mount_node: self.mount_node.clone(),
+            dom_updater: Rc::clone(&self.dom_updater),
+            on_message: Rc::clone(&self.on_message),
+            _msg: PhantomData,
+        }
+    }
+}
+
+impl<MSG> WorkerHost<MSG>
+where
+    MSG: Serialize + 'static,
+{
+    /// Start the worker from the `script_url`, then append the view of its app
+    /// into the `mount_node` once the worker has posted it.
+    pub fn mount(script_url: &str, mount_node: &web_sys::Node) -> Self {
+        let worker = Worker::new(script_url).expect("must start the worker");
+        let host = WorkerHost {
+            worker,
+            mount_node: mount_node.clone(),
+            dom_updater: Rc::new(RefCell::new(None)),
+            on_message: Rc::new(RefCell::new(None)),
+            _msg: PhantomData,
+        };
+        let host_clone = host.clone();
+        let on_message: Closure<dyn FnMut(MessageEvent)> =
+            Closure::wrap(Box::new(move |message: MessageEvent| {
+                host_clone.receive(message)
+            }));
+        host.worker
+            .set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        *host.on_message.borrow_mut() = Some(on_message);
+        host
+    }
+
+    /// Dispatch the `msg` into the app in the worker,
+    /// ie: the msgs of the subscriptions which listen to the window of the main thread
+    pub fn dispatch(&self, msg: MSG) {
+        self.post(&ToWorker::Msg(msg));
+    }
+
+    /// stop the worker, the view is left as it is
+    pub fn terminate(&self) {
+        self.worker.terminate();
+        self.worker.set_onmessage(None);
+        self.on_message.borrow_mut().take();
+    }
+
+    /// mount the view or apply the patches posted by the worker
+    fn receive(&self, message: MessageEvent) {
+        let json = match message.data().as_string() {
+            Some(json) => json,
+            None => return,
+        };
+        match serde_json::from_str::<FromWorker>(&json) {
+            Ok(FromWorker::Mount(remote_node)) => {
+                let mut dom_updater = DomUpdater::new(
+                    from_remote_node(remote_node),
+                    &self.mount_node,
+                );
+                if let Err(e) = dom_updater.append_to_mount(self) {
+                    log::error!("unable to mount the worker view: {}", e);
+                }
+                *self.dom_updater.borrow_mut() = Some(dom_updater);
+            }
+            Ok(FromWorker::Patch(remote_patches)) => {
+                let host_patches: Vec<HostPatch> =
+                    remote_patches.into_iter().map(HostPatch::from).collect();
+                let patches: Vec<Patch<HostEvent>> =
+                    host_patches.iter().map(HostPatch::as_patch).collect();
+                if let Some(dom_updater) =
+                    self.dom_updater.borrow_mut().as_mut()
+                {
+                    if let Err(e) = dom_updater.patch_dom(self, patches) {
+                        log::error!("unable to patch the worker view: {}", e);
+                    }
+                }
+            }
+            Err(e) => {
+                log::error!("unable to deserialize the worker message: {}", e)
+            }
+        }
+    }
+
+    fn post(&self, message: &ToWorker<MSG>) {
+        let json = serde_json::to_string(message)
+            .expect("must serialize the worker message");
+        if let Err(e) = self.worker.post_message(&JsValue::from_str(&json)) {
+            log::error!("unable to post the worker message: {:?}", e);
+        }
+    }
+}
+
+impl<MSG> Dispatch<HostEvent> for WorkerHost<MSG>
+where
+    MSG: Serialize + 'static,
+{
+    /// post the event to the worker, along with the path of its element
+    fn dispatch(&self, host_event: HostEvent) {
+        let root_node = match self.dom_updater.try_borrow() {
+            Ok(dom_updater) => dom_updater
+                .as_ref()
+                .map(|dom_updater| dom_updater.root_node()),
+            // the events fired while the DOM is patched such as the blur of a removed element
+            Err(_) => None,
+        };
+        let path = root_node.and_then(|root_node| {
+            event_delegation::tree_path(&root_node, &host_event.node)
+        });
+        if let Some(path) = path {
+            let event = RemoteEvent {
+                path,
+                ..host_event.event
+            };
+            self.post(&ToWorker::Event(event));
+        }
+    }
+
+    fn dispatch_multiple(&self, host_events: Vec<HostEvent>) {
+        for host_event in host_events {
+            self.dispatch(host_event);
+        }
+    }
+}
+
+impl RemoteEvent {
+    /// read the event and its target, the path is set by the host
+    fn from_event(name: &str, event: &web_sys::Event) -> Self {
+        let target = event.target();
+        let input = target
+            .as_ref()
+            .and_then(|target| target.dyn_ref::<HtmlInputElement>());
+        let textarea = target
+            .as_ref()
+            .and_then(|target| target.dyn_ref::<HtmlTextAreaElement>());
+        let mouse_event = event.dyn_ref::<MouseEvent>();
+        RemoteEvent {
+            path: vec![],
+            name: name.to_string(),
+            value: input
+                .map(|input| input.value())
+                .or_else(|| textarea.map(|textarea| textarea.value())),
+            checked: input.map(|input| input.checked()),
+            key: event
+                .dyn_ref::<KeyboardEvent>()
+                .map(|keyboard_event| keyboard_event.key()),
+            client_x: mouse_event.map(|mouse_event| mouse_event.client_x()),
+            client_y: mouse_event.map(|mouse_event| mouse_event.client_y()),
+        }
+    }
+}
+
+/// the listener of the element at the path of the event
+fn find_remote_listener<'a, MSG>(
+    vdom: &'a Node<MSG>,
+    event: &RemoteEvent,
+) -> Option<&'a RemoteListener<MSG>>
+where
+    MSG: 'static,
+{
+    let (_root_idx, path) = event.path.split_first()?;
+    let mut node = vdom;
+    for idx in path {
+        node = node.as_element_ref()?.get_children().get(*idx)?;
+    }
+    node.as_element_ref()?
+        .attrs
+        .iter()
+        .filter(|attr| *attr.name() == event.name)
+        .flat_map(|attr| attr.value().iter())
+        .find_map(|value| value.as_custom::<RemoteListener<MSG>>())
+}
+
+fn to_remote_node<MSG>(node: &Node<MSG>) -> RemoteNode
+where
+    MSG: 'static,
+{
+    match node {
+        Node::Element(element) => RemoteNode::Element {
+            namespace: element.namespace.map(ToString::to_string),
+            tag: element.tag.to_string(),
+            attrs: element.attrs.iter().map(to_remote_attribute).collect(),
+            children: element.children.iter().map(to_remote_node).collect(),
+        },
+        Node::Text(txt) => RemoteNode::Text(txt.text.to_string()),
+        Node::Comment(comment) => RemoteNode::Comment(comment.to_string()),
+    }
+}
+
+/// the values which can not be sent across the threads such as the node refs are left out
+fn to_remote_attribute<MSG>(attr: &Attribute<MSG>) -> RemoteAttribute
+where
+    MSG: 'static,
+{
+    let values = attr
+        .value()
+        .iter()
+        .filter_map(|value| match value {
+            AttributeValue::Simple(value) => Some(match value.as_bool() {
+                Some(flag) => RemoteValue::Bool(flag),
+                None => RemoteValue::Text(value.to_string()),
+            }),
+            AttributeValue::Style(styles) => Some(RemoteValue::Style(
+                styles
+                    .iter()
+                    .map(|style| {
+                        (style.name.to_string(), style.value.to_string())
+                    })
+                    .collect(),
+            )),
+            AttributeValue::Property(value) => {
+                Some(RemoteValue::Property(value.to_string()))
+            }
+            AttributeValue::Custom(custom)
+                if custom.as_any().is::<RemoteListener<MSG>>() =>
+            {
+                Some(RemoteValue::Listener)
+            }
+            _ => None,
+        })
+        .collect();
+    RemoteAttribute {
+        namespace: attr.namespace().map(ToString::to_string),
+        name: attr.name().to_string(),
+        values,
+    }
+}
+
+fn to_remote_patch<MSG>(patch: &Patch<MSG>) -> RemotePatch
+where
+    MSG: 'static,
+{
+    let path = patch.path().to_vec();
+    let tag = patch.tag().map(ToString::to_string);
+    match patch {
+        Patch::InsertNode(InsertNode { node, .. }) => RemotePatch::InsertNode {
+            tag,
+            path,
+            node: to_remote_node(node),
+        },
+        Patch::MoveNode(MoveNode { before_path, .. }) => {
+            RemotePatch::MoveNode {
+                tag,
+                path,
+                before_path: before_path
+                    .as_ref()
+                    .map(|before_path| before_path.path.clone()),
+            }
+        }
+        Patch::AddAttributes(AddAttributes { attrs, .. }) => {
+            RemotePatch::AddAttributes {
+                tag: tag.unwrap_or_default(),
+                path,
+                attrs: attrs
+                    .iter()
+                    .map(|attr| to_remote_attribute(attr))
+                    .collect(),
+            }
+        }
+        Patch::RemoveAttributes(RemoveAttributes { attrs, .. }) => {
+            RemotePatch::RemoveAttributes {
+                tag: tag.unwrap_or_default(),
+                path,
+                attrs: attrs
+                    .iter()
+                    .map(|attr| to_remote_attribute(attr))
+                    .collect(),
+            }
+        }
+        Patch::ReplaceNode(ReplaceNode { replacement, .. }) => {
+            RemotePatch::ReplaceNode {
+                tag,
+                path,
+                replacement: to_remote_node(replacement),
+            }
+        }
+        Patch::RemoveNode(RemoveNode { .. }) => {
+            RemotePatch::RemoveNode { tag, path }
+        }
+        Patch::RemoveChildrenFrom(RemoveChildrenFrom {
+            start_index, ..
+        }) => RemotePatch::RemoveChildrenFrom {
+            tag: tag.unwrap_or_default(),
+            path,
+            start_index: *start_index,
+        },
+        Patch::AppendChildren(AppendChildren { children, .. }) => {
+            RemotePatch::AppendChildren {
+                tag: tag.unwrap_or_default(),
+                path,
+                children: children
+                    .iter()
+                    .map(|child| to_remote_node(child))
+                    .collect(),
+            }
+        }
+        Patch::ChangeText(ct) => RemotePatch::ChangeText {
+            path,
+            text: ct.new.text.to_string(),
+        },
+        Patch::ChangeComment(cm) => RemotePatch::ChangeComment {
+            path,
+            comment: cm.new.to_string(),
+        },
+    }
+}
+
+/// the `&'static str` of the tag or the attribute name received from the worker
+fn intern(name: String) -> &'static str {
+    INTERNED.with(|interned| {
+        let mut interned = interned.borrow_mut();
+        match interned.get(name.as_str()) {
+            Some(name) => *name,
+            None => {
+                let name: &'static str = Box::leak(name.into_boxed_str());
+                interned.insert(name);
+                name
+            }
+        }
+    })
+}
+
+fn from_remote_node(remote_node: RemoteNode) -> Node<HostEvent> {
+    match remote_node {
+        RemoteNode::Element {
+            namespace,
+            tag,
+            attrs,
+            children,
+        } => crate::html::element_ns(
+            namespace.map(intern),
+            intern(tag),
+            attrs.into_iter().map(from_remote_attribute),
+            children.into_iter().map(from_remote_node),
+            false,
+        ),
+        RemoteNode::Text(txt) => crate::html::text(txt),
+        RemoteNode::Comment(comment) => crate::html::comment(comment),
+    }
+}
+
+/// the listeners read the event into a [`HostEvent`], which is posted to the worker by the host
+fn from_remote_attribute(remote_attr: RemoteAttribute) -> Attribute<HostEvent> {
+    let name = intern(remote_attr.name);
+    let values: Vec<AttributeValue<HostEvent>> = remote_attr
+        .values
+        .into_iter()
+        .map(|value| match value {
+            RemoteValue::Bool(flag) => {
+                AttributeValue::Simple(Value::Bool(flag))
+            }
+            RemoteValue::Text(text) => {
+                AttributeValue::Simple(Value::String(text))
+            }
+            RemoteValue::Style(styles) => AttributeValue::Style(
+                styles
+                    .into_iter()
+                    .map(|(name, value)| Style {
+                        name,
+                        value: Value::String(value),
+                    })
+                    .collect(),
+            ),
+            RemoteValue::Property(value) => {
+                AttributeValue::Property(Value::String(value))
+            }
+            RemoteValue::Listener => {
+                let listener = crate::dom::events::on(
+                    name,
+                    move |event: Event| match event {
+                        Event::WebEvent(web_event) => HostEvent {
+                            node: web_event
+                                .current_target()
+                                .expect("must have a current target")
+                                .unchecked_into(),
+                            event: RemoteEvent::from_event(name, &web_event),
+                        },
+                        Event::MountEvent(mount_event) => HostEvent {
+                            node: mount_event.target_node,
+                            event: RemoteEvent {
+                                name: name.to_string(),
+                                ..Default::default()
+                            },
+                        },
+                    },
+                );
+                listener
+                    .value
+                    .into_iter()
+                    .next()
+                    .expect("must have the listener")
+            }
+        })
+        .collect();
+    let mut attr = mt_dom::attr(name, AttributeValue::Empty);
+    attr.namespace = remote_attr.namespace.map(intern);
+    attr.value = values;
+    attr
+}
+
+/// the patch posted by the worker, which owns the nodes and attributes its [`Patch`] borrows
+enum HostPatch {
+    InsertNode(Option<&'static str>, TreePath, Node<HostEvent>),
+    MoveNode(Option<&'static str>, TreePath, Option<TreePath>),
+    AddAttributes(&'static str, TreePath, Vec<Attribute<HostEvent>>),
+    RemoveAttributes(&'static str, TreePath, Vec<Attribute<HostEvent>>),
+    ReplaceNode(Option<&'static str>, TreePath, Node<HostEvent>),
+    RemoveNode(Option<&'static str>, TreePath),
+    RemoveChildrenFrom(&'static str, TreePath, usize),
+    AppendChildren(&'static str, TreePath, Vec<Node<HostEvent>>),
+    ChangeText(TreePath, Text, Text),
+    ChangeComment(TreePath, String, String),
+}
+
+impl From<RemotePatch> for HostPatch {
+    fn from(remote_patch: RemotePatch) -> Self {
+        let attributes = |attrs: Vec<RemoteAttribute>| {
+            attrs.into_iter().map(from_remote_attribute).collect()
+        };
+        match remote_patch {
+            RemotePatch::InsertNode { tag, path, node } => {
+                HostPatch::InsertNode(
+                    tag.map(intern),
+                    TreePath::new(path),
+                    from_remote_node(node),
+                )
+            }
+            RemotePatch::MoveNode {
+                tag,
+                path,
+                before_path,
+            } => HostPatch::MoveNode(
+                tag.map(intern),
+                TreePath::new(path),
+                before_path.map(TreePath::new),
+            ),
+            RemotePatch::AddAttributes { tag, path, attrs } => {
+                HostPatch::AddAttributes(
+                    intern(tag),
+                    TreePath::new(path),
+                    attributes(attrs),
+                )
+            }
+            RemotePatch::RemoveAttributes { tag, path, attrs } => {
+                HostPatch::RemoveAttributes(
+                    intern(tag),
+                    TreePath::new(path),
+                    attributes(attrs),
+                )
+            }
+            RemotePatch::ReplaceNode {
+                tag,
+                path,
+                replacement,
+            } => HostPatch::ReplaceNode(
+                tag.map(intern),
+                TreePath::new(path),
+                from_remote_node(replacement),
+            ),
+            RemotePatch::RemoveNode { tag, path } => {
+                HostPatch::RemoveNode(tag.map(intern), TreePath::new(path))
+            }
+            RemotePatch::RemoveChildrenFrom {
+                tag,
+                path,
+                start_index,
+            } => HostPatch::RemoveChildrenFrom(
+                intern(tag),
+                TreePath::new(path),
+                start_index,
+            ),
+            RemotePatch::AppendChildren {
+                tag,
+                path,
+                children,
+            } => HostPatch::AppendChildren(
+                intern(tag),
+                TreePath::new(path),
+                children.into_iter().map(from_remote_node).collect(),
+            ),
+            // the old text is not used when the text is patched
+            RemotePatch::ChangeText { path, text } => HostPatch::ChangeText(
+                TreePath::new(path),
+                Text::new(""),
+                Text::new(text),
+            ),
+            RemotePatch::ChangeComment { path, comment } => {
+                HostPatch::ChangeComment(
+                    TreePath::new(path),
+                    String::new(),
+                    comment,
+                )
+            }
+        }
+    }
+}
+
+impl HostPatch {
+    fn as_patch(&self) -> Patch<HostEvent> {
+        match self {
+            HostPatch::InsertNode(tag, path, node) => {
+                InsertNode::new(tag.as_ref(), path.clone(), node).into()
+            }
+            HostPatch::MoveNode(tag, path, before_path) => {
+                MoveNode::new(tag.as_ref(), path.clone(), before_path.clone())
+                    .into()
+            }
+            HostPatch::AddAttributes(tag, path, attrs) => {
+                AddAttributes::new(tag, path.clone(), attrs.iter().collect())
+                    .into()
+            }
+            HostPatch::RemoveAttributes(tag, path, attrs) => {
+                RemoveAttributes::new(tag, path.clone(), attrs.iter().collect())
+                    .into()
+            }
+            HostPatch::ReplaceNode(tag, path, replacement) => {
+                ReplaceNode::new(tag.as_ref(), path.clone(), replacement).into()
+            }
+            HostPatch::RemoveNode(tag, path) => {
+                RemoveNode::new(tag.as_ref(), path.clone()).into()
+            }
+            HostPatch::RemoveChildrenFrom(tag, path, start_index) => {
+                RemoveChildrenFrom::new(tag, path.clone(), *start_index).into()
+            }
+            HostPatch::AppendChildren(tag, path, children) => {
+                AppendChildren::new(
+                    tag,
+                    path.clone(),
+                    children.iter().collect(),
+                )
+                .into()
+            }
+            HostPatch::ChangeText(path, old, new) => {
+                ChangeText::new(old, path.clone(), new).into()
+            }
+            HostPatch::ChangeComment(path, old, new) => {
+                ChangeComment::new(old, path.clone(), new).into()
+            }
+        }
+    }
+}
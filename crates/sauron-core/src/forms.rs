@@ -0,0 +1,389 @@
+//! provides [`FieldArray`] for the fields of a form which holds a list of values,
+//! ie: the line items of an invoice or the tags of a post.
+//!
+//! Each row has a stable key which doesn't change when the rows are added, removed or moved,
+//! so the rows map onto the keyed diffing and the state of their inputs, ie: the focus,
+//! stays with the row. Each row is validated when it is added or updated,
+//! and the field array is valid when all of its rows and the number of rows are valid.
+//!
+//! # Examples
+//! ```rust
+//! use sauron::forms::FieldArray;
+//!
+//! let mut items = FieldArray::new()
+//!     .validator(|item: &String| {
+//!         if item.is_empty() { vec!["required".to_string()] } else { vec![] }
+//!     })
+//!     .min_rows(1);
+//! let milk = items.push("milk".to_string());
+//! let eggs = items.push(String::new());
+//! assert!(!items.is_valid());
+//! assert_eq!(items.errors(eggs), ["required"]);
+//!
+//! items.update(eggs, |item| *item = "eggs".to_string());
+//! items.move_row(eggs, 0);
+//! assert!(items.is_valid());
+//! assert_eq!(items.keys(), vec![eggs, milk]);
+//! ```
+use crate::{html::attributes::key, validate::Validator, Node};
+use std::{fmt, rc::Rc};
+
+/// the stable key of a row in the [`FieldArray`]
+pub type RowKey = usize;
+
+/// The error of the number of the rows in the [`FieldArray`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthError {
+    /// there are fewer rows than the minimum
+    TooFew {
+        /// the minimum number of the rows
+        min: usize,
+    },
+    /// there are more rows than the maximum
+    TooMany {
+        /// the maximum number of the rows
+        max: usize,
+    },
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LengthError::TooFew { min } => {
+                write!(f, "must have at least {} rows", min)
+            }
+            LengthError::TooMany { max } => {
+                write!(f, "must have at most {} rows", max)
+            }
+        }
+    }
+}
+
+/// A row of the [`FieldArray`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row<T> {
+    key: RowKey,
+    value: T,
+    errors: Vec<String>,
+}
+
+impl<T> Row<T> {
+    /// the stable key of the row
+    pub fn key(&self) -> RowKey {
+        self.key
+    }
+
+    /// the value of the row
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// the validation errors of the row
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// returns true if the row has no validation errors
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// The field of a form which holds a list of values, where each value is a row
+/// with its own stable key and validation errors
+pub struct FieldArray<T> {
+    rows: Vec<Row<T>>,
+    next_key: RowKey,
+    validator: Option<Rc<dyn Fn(&T) -> Vec<String>>>,
+    min_rows: Option<usize>,
+    max_rows: Option<usize>,
+}
+
+impl<T> FieldArray<T> {
+    /// an empty field array, which has no validation
+    pub fn new() -> Self {
+        FieldArray {
+            rows: vec![],
+            next_key: 0,
+            validator: None,
+            min_rows: None,
+            max_rows: None,
+        }
+    }
+
+    /// validate each row using the function `f`, which returns the errors of the value.
+    /// The existing rows are validated right away
+    pub fn validator<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&T) -> Vec<String> + 'static,
+    {
+        self.validator = Some(Rc::new(f));
+        self.validate_all();
+        self
+    }
+
+    /// validate each row with the rules of the `validator`
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sauron::forms::FieldArray;
+    /// use sauron::validate::{email, required, Validator};
+    ///
+    /// let mut emails = FieldArray::new()
+    ///     .validate_with(Validator::new().rule(required()).rule(email()));
+    /// let row = emails.push("ivan".to_string());
+    /// assert_eq!(emails.errors(row), ["must be a valid email address"]);
+    /// ```
+    pub fn validate_with(self, validator: Validator) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.validator(move |value: &T| validator.validate(value.as_ref()))
+    }
+
+    /// the minimum number of the rows for the field array to be valid
+    pub fn min_rows(mut self, min: usize) -> Self {
+        self.min_rows = Some(min);
+        self
+    }
+
+    /// the maximum number of the rows for the field array to be valid,
+    /// rows can still be added beyond the maximum, see [`can_add`](Self::can_add)
+    pub fn max_rows(mut self, max: usize) -> Self {
+        self.max_rows = Some(max);
+        self
+    }
+
+    /// add the value as the last row, returns the key of the new row
+    pub fn push(&mut self, value: T) -> RowKey {
+        let index = self.rows.len();
+        self.insert(index, value)
+    }
+
+    /// insert the value as a row at the `index`, returns the key of the new row
+    ///
+    /// # Panics
+    /// if the `index` is greater than the number of the rows
+    pub fn insert(&mut self, index: usize, value: T) -> RowKey {
+        let key = self.next_key;
+        self.next_key += 1;
+        let errors = self.validate(&value);
+        self.rows.insert(index, Row { key, value, errors });
+        key
+    }
+
+    /// remove the row, returns its value
+    pub fn remove(&mut self, key: RowKey) -> Option<T> {
+        let index = self.index_of(key)?;
+        Some(self.rows.remove(index).value)
+    }
+
+    /// move the row to the `index`, which is clamped to the last row.
+    /// Returns false if there is no row with the `key`
+    pub fn move_row(&mut self, key: RowKey, index: usize) -> bool {
+        match self.index_of(key) {
+            Some(current) => {
+                let row = self.rows.remove(current);
+                let index = index.min(self.rows.len());
+                self.rows.insert(index, row);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// move the row one position up, returns false if it is already the first row
+    pub fn move_up(&mut self, key: RowKey) -> bool {
+        match self.index_of(key) {
+            Some(index) if index > 0 => {
+                self.rows.swap(index, index - 1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// move the row one position down, returns false if it is already the last row
+    pub fn move_down(&mut self, key: RowKey) -> bool {
+        match self.index_of(key) {
+            Some(index) if index + 1 < self.rows.len() => {
+                self.rows.swap(index, index + 1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// update the value of the row, which is then validated again.
+    /// Returns false if there is no row with the `key`
+    pub fn update<F>(&mut self, key: RowKey, f: F) -> bool
+    where
+        F: FnOnce(&mut T),
+    {
+        let index = match self.index_of(key) {
+            Some(index) => index,
+            None => return false,
+        };
+        f(&mut self.rows[index].value);
+        self.rows[index].errors = self.validate(&self.rows[index].value);
+        true
+    }
+
+    /// the value of the row
+    pub fn get(&self, key: RowKey) -> Option<&T> {
+        self.row(key).map(Row::value)
+    }
+
+    /// the row with the `key`
+    pub fn row(&self, key: RowKey) -> Option<&Row<T>> {
+        self.rows.iter().find(|row| row.key == key)
+    }
+
+    /// the rows in their order
+    pub fn rows(&self) -> &[Row<T>] {
+        &self.rows
+    }
+
+    /// the keys of the rows in their order
+    pub fn keys(&self) -> Vec<RowKey> {
+        self.rows.iter().map(Row::key).collect()
+    }
+
+    /// the values of the rows in their order
+    pub fn values(&self) -> Vec<&T> {
+        self.rows.iter().map(Row::value).collect()
+    }
+
+    /// take the values of the rows in their order
+    pub fn into_values(self) -> Vec<T> {
+        self.rows.into_iter().map(|row| row.value).collect()
+    }
+
+    /// the number of the rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// returns true if there are no rows
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// returns true if a row can be added without exceeding the maximum number of the rows
+    pub fn can_add(&self) -> bool {
+        self.max_rows
+            .map(|max| self.rows.len() < max)
+            .unwrap_or(true)
+    }
+
+    /// returns true if a row can be removed without going below the minimum number of the rows
+    pub fn can_remove(&self) -> bool {
+        self.min_rows
+            .map(|min| self.rows.len() > min)
+            .unwrap_or(true)
+    }
+
+    /// the validation errors of the row, empty if the row is valid or doesn't exist
+    pub fn errors(&self, key: RowKey) -> &[String] {
+        self.row(key).map(Row::errors).unwrap_or(&[])
+    }
+
+    /// the error of the number of the rows
+    pub fn length_error(&self) -> Option<LengthError> {
+        match (self.min_rows, self.max_rows) {
+            (Some(min), _) if self.rows.len() < min => {
+                Some(LengthError::TooFew { min })
+            }
+            (_, Some(max)) if self.rows.len() > max => {
+                Some(LengthError::TooMany { max })
+            }
+            _ => None,
+        }
+    }
+
+    /// returns true if all of the rows and the number of the rows are valid
+    pub fn is_valid(&self) -> bool {
+        self.length_error().is_none() && self.rows.iter().all(Row::is_valid)
+    }
+
+    /// the keys of the rows which have validation errors
+    pub fn invalid_rows(&self) -> Vec<RowKey> {
+        self.rows
+            .iter()
+            .filter(|row| !row.is_valid())
+            .map(Row::key)
+            .collect()
+    }
+
+    /// create the view of each row, the key of the row is added into the view,
+    /// so the rows are diffed by their keys when they are added, removed or moved
+    pub fn view<MSG, F>(&self, f: F) -> Vec<Node<MSG>>
+    where
+        F: Fn(&Row<T>) -> Node<MSG>,
+    {
+        self.rows
+            .iter()
+            .map(|row| {
+                let mut node = f(row);
+                if let Node::Element(element) = &mut node {
+                    element.attrs.push(key(row.key));
+                }
+                node
+            })
+            .collect()
+    }
+
+    /// validate all the rows again
+    fn validate_all(&mut self) {
+        if let Some(validator) = self.validator.as_ref() {
+            for row in self.rows.iter_mut() {
+                row.errors = validator(&row.value);
+            }
+        }
+    }
+
+    fn validate(&self, value: &T) -> Vec<String> {
+        self.validator
+            .as_ref()
+            .map(|validator| validator(value))
+            .unwrap_or_default()
+    }
+
+    fn index_of(&self, key: RowKey) -> Option<usize> {
+        self.rows.iter().position(|row| row.key == key)
+    }
+}
+
+impl<T> Default for FieldArray<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for FieldArray<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        FieldArray {
+            rows: self.rows.clone(),
+            next_key: self.next_key,
+            validator: self.validator.clone(),
+            min_rows: self.min_rows,
+            max_rows: self.max_rows,
+        }
+    }
+}
+
+impl<T> fmt::Debug for FieldArray<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FieldArray")
+            .field("rows", &self.rows)
+            .field("min_rows", &self.min_rows)
+            .field("max_rows", &self.max_rows)
+            .finish()
+    }
+}
@@ -0,0 +1,184 @@
+//! The entry points of the fuzz targets in the `fuzz` directory, which are run with `cargo fuzz`.
+//!
+//! Each function takes the raw input of the fuzzer and panics when an invariant is broken:
+//! - [`parse_html`] the html is tokenized and built into a vdom which is rendered back into html.
+//! - [`sanitize`] the cleaned html only has the allowed tags and attributes,
+//!   and cleaning it again doesn't change it.
+//! - [`diff_html`] the patches of the diff of two parsed html turns the old vdom into the new one.
+//!
+//! This module is only compiled with `--cfg fuzzing`, which is set by `cargo fuzz`.
+//! The seeds of each target are in `fuzz/corpus/<target>`.
+//! ```sh
+//! cargo +nightly fuzz run sanitize
+//! ```
+use crate::{
+    diff,
+    html::{attributes::attr, comment, html_element_self_closing, text},
+    sanitize::{self, tokenize, Policy, Token, VOID_ELEMENTS},
+    vdom::apply_patch_to_vdom,
+    Attribute, Node, Render,
+};
+use std::{cell::RefCell, collections::HashSet};
+
+/// the maximum number of the distinct tag and attribute names interned
+const MAX_INTERNED: usize = 1024;
+
+/// the attributes which changes how the nodes are diffed, these are left out of the parsed vdom,
+/// since the keys could be duplicated and the skipped nodes are not patched
+const SPECIAL_ATTRIBUTES: [&str; 3] = ["key", "skip", "replace"];
+
+thread_local!(static INTERNED: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new()));
+
+/// tokenize the html and build it into a vdom, which is then rendered back into html
+pub fn parse_html(data: &[u8]) {
+    let html = match std::str::from_utf8(data) {
+        Ok(html) => html,
+        Err(_) => return,
+    };
+    let node = parse(html);
+    let _ = node.render_to_string();
+}
+
+/// clean the html with the default policy and check the cleaned html
+pub fn sanitize(data: &[u8]) {
+    let html = match std::str::from_utf8(data) {
+        Ok(html) => html,
+        Err(_) => return,
+    };
+    let policy = Policy::default();
+    let cleaned = sanitize::clean(html, &policy);
+    for token in tokenize(&cleaned) {
+        if let Token::StartTag { name, attrs, .. } = token {
+            assert!(policy.is_tag_allowed(&name), "tag: {:?}", name);
+            for (att, _value) in attrs {
+                let is_link_rel = name == "a" && att == "rel";
+                assert!(
+                    is_link_rel || policy.is_attribute_allowed(&name, &att),
+                    "attribute: {:?} of tag: {:?}",
+                    att,
+                    name
+                );
+                assert!(!att.starts_with("on"), "event handler: {:?}", att);
+            }
+        }
+    }
+    assert_eq!(
+        sanitize::clean(&cleaned, &policy),
+        cleaned,
+        "cleaning is not idempotent"
+    );
+}
+
+/// split the input into the old and the new html, at the first `0` byte or at the middle,
+/// and check the patches of the diff of their vdom
+pub fn diff_html(data: &[u8]) {
+    let split = data
+        .iter()
+        .position(|b| *b == 0)
+        .unwrap_or_else(|| data.len() / 2);
+    let (old, new) = (&data[..split], &data[split..]);
+    let new = new.strip_prefix(&[0]).unwrap_or(new);
+    let (old, new) = match (std::str::from_utf8(old), std::str::from_utf8(new))
+    {
+        (Ok(old), Ok(new)) => (parse(old), parse(new)),
+        _ => return,
+    };
+    let patches = diff(&old, &new);
+    let patched =
+        apply_patch_to_vdom(&old, &patches).expect("must apply the patches");
+    assert_eq!(patched, new, "patches: {:#?}", patches);
+}
+
+/// build the html into a vdom, the nodes are wrapped in a `div`.
+///
+/// Unclosed tags are closed at the end of the input and stray closing tags are ignored,
+/// the [`SPECIAL_ATTRIBUTES`] are left out.
+fn parse(html: &str) -> Node<()> {
+    // the open elements, with their tag, attributes and children
+    let mut stack: Vec<(&'static str, Vec<Attribute<()>>, Vec<Node<()>>)> =
+        vec![("div", vec![], vec![])];
+    for token in tokenize(html) {
+        match token {
+            Token::Text(content) => children(&mut stack).push(text(content)),
+            Token::Comment(content) => {
+                children(&mut stack).push(comment(content))
+            }
+            Token::StartTag {
+                name,
+                attrs,
+                self_closing,
+            } => {
+                let is_void = VOID_ELEMENTS.contains(&name.as_str());
+                let tag = intern(&name, "div");
+                let attrs = attrs
+                    .into_iter()
+                    .filter(|(att, _)| {
+                        !SPECIAL_ATTRIBUTES.contains(&att.as_str())
+                    })
+                    .map(|(att, value)| attr(intern(&att, "data-att"), value))
+                    .collect();
+                if self_closing || is_void {
+                    children(&mut stack).push(html_element_self_closing(
+                        tag,
+                        attrs,
+                        vec![],
+                        is_void,
+                    ));
+                } else {
+                    stack.push((tag, attrs, vec![]));
+                }
+            }
+            Token::EndTag(name) => {
+                // the root element is never closed
+                if let Some(pos) =
+                    stack.iter().skip(1).rposition(|(tag, ..)| *tag == name)
+                {
+                    while stack.len() > pos + 1 {
+                        close(&mut stack);
+                    }
+                }
+            }
+        }
+    }
+    while stack.len() > 1 {
+        close(&mut stack);
+    }
+    let (tag, attrs, children) = stack.pop().expect("must have the root");
+    html_element_self_closing(tag, attrs, children, false)
+}
+
+fn children<'a>(
+    stack: &'a mut [(&'static str, Vec<Attribute<()>>, Vec<Node<()>>)],
+) -> &'a mut Vec<Node<()>> {
+    &mut stack.last_mut().expect("must have the root").2
+}
+
+/// close the last open element, adding it into the children of its parent
+fn close(stack: &mut Vec<(&'static str, Vec<Attribute<()>>, Vec<Node<()>>)>) {
+    let (tag, attrs, element_children) =
+        stack.pop().expect("must have an open element");
+    children(stack).push(html_element_self_closing(
+        tag,
+        attrs,
+        element_children,
+        false,
+    ));
+}
+
+/// the tags and attribute names of the vdom are `&'static str`,
+/// the names are leaked once and reused, up to the `MAX_INTERNED` names
+/// and the `fallback` is used after that
+fn intern(name: &str, fallback: &'static str) -> &'static str {
+    INTERNED.with(|interned| {
+        let mut interned = interned.borrow_mut();
+        if let Some(existing) = interned.get(name) {
+            return *existing;
+        }
+        if interned.len() >= MAX_INTERNED {
+            return fallback;
+        }
+        let leaked: &'static str = Box::leak(name.to_string().into_boxed_str());
+        interned.insert(leaked);
+        leaked
+    })
+}
@@ -2,16 +2,30 @@
 use crate::{Attribute, Node};
 pub use mt_dom::{comment, element, element_ns, safe_html, text};
 
+pub mod a11y;
 #[macro_use]
 pub mod attributes;
+pub mod css;
+pub mod elem;
+pub mod head;
+pub mod scoped_style;
+pub mod skeleton;
 pub mod tags;
 pub mod units;
 
 #[cfg(feature = "with-dom")]
 pub use crate::dom::events;
 
+pub use elem::Elem;
+pub use head::Head;
 pub use tags::{commons::*, self_closing::*};
 
+/// the id of the script element created with [`serialized_state`]
+pub const SERIALIZED_STATE_ID: &str = "sauron-serialized-state";
+
+/// the attribute which holds the selector of the element adopted with [`adopt_existing`]
+pub const ADOPT_ATTR: &str = "data-sauron-adopt";
+
 /// A help function which render the view when the condition is met, otherwise
 /// just display a `span(vec![], vec![])`
 ///
@@ -32,6 +46,88 @@ pub fn view_if<MSG>(flag: bool, node: Node<MSG>) -> Node<MSG> {
     }
 }
 
+/// Creates a link which opens the `href` in a new tab,
+/// with `rel="noopener noreferrer"` so the opened page can not access this page.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+///
+/// let link: Node<()> = safe_external_link("https://example.com", vec![text("example")]);
+/// assert_eq!(
+///     node!{<a href="https://example.com" target="_blank" rel="noopener noreferrer">"example"</a>},
+///     link
+/// );
+/// ```
+pub fn safe_external_link<MSG>(
+    href: impl Into<attributes::Value>,
+    children: impl IntoIterator<Item = Node<MSG>>,
+) -> Node<MSG> {
+    a(
+        [
+            attributes::href(href),
+            attributes::target("_blank"),
+            attributes::rel_noopener_noreferrer(),
+        ],
+        children,
+    )
+}
+
+/// Adopt the existing element in the page matching the `selector` into the view,
+/// so pages with legacy widgets can be migrated into sauron gradually.
+///
+/// The existing element is moved to where this node is in the view, but the diffing
+/// never goes inside the element, so the legacy code can keep on modifying it.
+/// If the element is not found, an empty `div` is created in its place.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+///
+/// let view: Node<()> = main(vec![], vec![
+///     h1(vec![], vec![text("Dashboard")]),
+///     adopt_existing("#legacy-chart"),
+/// ]);
+/// ```
+pub fn adopt_existing<MSG>(selector: &str) -> Node<MSG> {
+    div(
+        [
+            attributes::attr(ADOPT_ATTR, selector.to_string()),
+            attributes::skip(true),
+        ],
+        [],
+    )
+}
+
+/// Creates a script element which embeds the `json` state of the app into the server rendered page,
+/// so the client can start with the same state using `Program::mount_with_serialized_state`.
+///
+/// The `<`, `>` and `&` are escaped in the json, so it can not close the script element.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+///
+/// let state: Node<()> = serialized_state(r#"{"name":"</script>"}"#);
+/// assert_eq!(
+///     state.render_to_string(),
+///     r#"<script type="application/json" id="sauron-serialized-state">{"name":"\u003c/script\u003e"}</script>"#
+/// );
+/// ```
+pub fn serialized_state<MSG>(json: &str) -> Node<MSG> {
+    let escaped = json
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026");
+    script(
+        [
+            attributes::r#type("application/json"),
+            attributes::id(SERIALIZED_STATE_ID),
+        ],
+        [text(escaped)],
+    )
+}
+
 /// Creates an html element
 ///
 /// # Examples
@@ -0,0 +1,145 @@
+//! Provides an audit of the accessibility of the view, such as images without alt and
+//! buttons without accessible names.
+//!
+//! With the `with-debug` feature, the view is audited after each diff and the warnings
+//! are logged whenever they change.
+use crate::{
+    html::attributes::{merge_plain_attributes_values, AttributeValue},
+    Element, Node,
+};
+use std::{collections::BTreeSet, fmt};
+
+/// A warning about the accessibility of the view
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum A11yWarning {
+    /// an `img` without the `alt` attribute, use `alt("")` for decorative images
+    ImageWithoutAlt {
+        /// the src of the image, if any
+        src: Option<String>,
+    },
+    /// a button without any text, `aria-label`, `aria-labelledby` or `title`
+    ButtonWithoutName,
+    /// the same id is used by more than one element
+    DuplicateId(String),
+}
+
+impl fmt::Display for A11yWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            A11yWarning::ImageWithoutAlt { src: Some(src) } => {
+                write!(f, "image without alt: {}", src)
+            }
+            A11yWarning::ImageWithoutAlt { src: None } => {
+                write!(f, "image without alt")
+            }
+            A11yWarning::ButtonWithoutName => {
+                write!(f, "button without an accessible name")
+            }
+            A11yWarning::DuplicateId(id) => write!(f, "duplicate id: {}", id),
+        }
+    }
+}
+
+/// Walk the `node` and return the accessibility warnings found, in document order
+///
+/// # Examples
+/// ```rust
+/// use sauron::{html::a11y::{audit, A11yWarning}, prelude::*};
+///
+/// let view: Node<()> = div(vec![], vec![
+///     img(vec![src("logo.png")], vec![]),
+///     button(vec![aria_label("close")], vec![]),
+/// ]);
+/// assert_eq!(
+///     audit(&view),
+///     vec![A11yWarning::ImageWithoutAlt { src: Some("logo.png".to_string()) }]
+/// );
+/// ```
+pub fn audit<MSG>(node: &Node<MSG>) -> Vec<A11yWarning> {
+    let mut warnings = vec![];
+    let mut ids = BTreeSet::new();
+    let mut duplicates = BTreeSet::new();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        if let Node::Element(element) = node {
+            if let Some(id) = value_of(element, "id") {
+                if !ids.insert(id.clone()) && duplicates.insert(id.clone()) {
+                    warnings.push(A11yWarning::DuplicateId(id));
+                }
+            }
+            if *element.tag() == "img" && value_of(element, "alt").is_none() {
+                warnings.push(A11yWarning::ImageWithoutAlt {
+                    src: value_of(element, "src"),
+                });
+            }
+            if is_button(element) && !has_accessible_name(element) {
+                warnings.push(A11yWarning::ButtonWithoutName);
+            }
+            stack.extend(element.get_children().iter().rev());
+        }
+    }
+    warnings
+}
+
+/// the merged plain value of the attribute `name` of the element
+fn value_of<MSG>(element: &Element<MSG>, name: &str) -> Option<String> {
+    let values: Vec<&AttributeValue<MSG>> = element
+        .get_attributes()
+        .iter()
+        .filter(|att| *att.name() == name)
+        .flat_map(|att| att.value())
+        .collect();
+    merge_plain_attributes_values(&values)
+}
+
+fn is_button<MSG>(element: &Element<MSG>) -> bool {
+    *element.tag() == "button"
+        || value_of(element, "role").as_deref() == Some("button")
+}
+
+/// whether the element is labelled by its attributes or has text or an image with alt in it
+fn has_accessible_name<MSG>(element: &Element<MSG>) -> bool {
+    let is_labelled =
+        ["aria-label", "aria-labelledby", "title"]
+            .iter()
+            .any(|name| {
+                value_of(element, name)
+                    .map(|v| !v.trim().is_empty())
+                    .unwrap_or(false)
+            });
+    is_labelled
+        || element.get_children().iter().any(|child| match child {
+            Node::Text(text) => !text.text.trim().is_empty(),
+            Node::Element(child) => {
+                if *child.tag() == "img" {
+                    value_of(child, "alt")
+                        .map(|alt| !alt.trim().is_empty())
+                        .unwrap_or(false)
+                } else {
+                    value_of(child, "aria-hidden").as_deref() != Some("true")
+                        && has_accessible_name(child)
+                }
+            }
+            Node::Comment(_) => false,
+        })
+}
+
+#[cfg(feature = "with-debug")]
+thread_local! {
+    static LAST_WARNINGS: std::cell::RefCell<Vec<A11yWarning>> = std::cell::RefCell::new(vec![]);
+}
+
+/// audit the view and log the warnings, only when they differ from the last audit
+#[cfg(feature = "with-debug")]
+pub(crate) fn log_warnings<MSG>(node: &Node<MSG>) {
+    let warnings = audit(node);
+    LAST_WARNINGS.with(|last| {
+        let mut last = last.borrow_mut();
+        if *last != warnings {
+            for warning in warnings.iter() {
+                log::warn!("a11y: {}", warning);
+            }
+            *last = warnings;
+        }
+    });
+}
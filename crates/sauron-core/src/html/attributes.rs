@@ -1,18 +1,25 @@
 //! Create html [attributes][0]
 //!
 //! [0]: https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes
-use crate::Attribute;
 use crate::Event;
+use crate::{Attribute, Element};
 pub use attribute_macros::*;
 pub use attribute_value::AttributeValue;
+pub use class_transformer::{
+    clear_class_transformer, set_class_transformer, transform_classes,
+};
+pub use custom_value::CustomValue;
 pub use listener::Listener;
-pub use special::{key, replace, skip, Special};
-pub use style::Style;
+pub use special::{key, replace, skip, static_node, Special};
+use std::borrow::Cow;
+pub use style::{Style, UpdateStyles};
 pub use value::Value;
 
 #[macro_use]
 mod attribute_macros;
 mod attribute_value;
+mod class_transformer;
+mod custom_value;
 mod listener;
 mod special;
 mod style;
@@ -147,6 +154,49 @@ pub fn classes<MSG>(
     Attribute::with_multiple_values(None, "class", class_values)
 }
 
+/// the same as [`classes_flag`], except that the class names are kept as is
+/// instead of being converted into a `String`, so the literal class names are not allocated
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+/// let is_hidden = true;
+///
+/// let line: Attribute<()> = classes_flag_cow([("dashed", is_hidden), ("error", false)]);
+/// ```
+pub fn classes_flag_cow<MSG>(
+    pair: impl IntoIterator<Item = (impl Into<Cow<'static, str>>, bool)>,
+) -> Attribute<MSG> {
+    let class_list =
+        pair.into_iter()
+            .filter_map(|(class, flag)| if flag { Some(class) } else { None });
+
+    classes_cow(class_list)
+}
+
+/// the same as [`classes`], except that the class names are kept as is
+/// instead of being converted into a `String`, so the literal class names are not allocated
+/// # Examples
+///
+/// ```rust
+/// use sauron::prelude::*;
+///
+/// let html: Node<()> =
+///    div(vec![classes_cow(["dashed", "error"])], vec![]);
+/// ```
+pub fn classes_cow<MSG>(
+    class_list: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+) -> Attribute<MSG> {
+    let class_values = class_list.into_iter().map(|class| {
+        let value = match class.into() {
+            Cow::Borrowed(class) => Value::Str(class),
+            Cow::Owned(class) => Value::String(class),
+        };
+        AttributeValue::from_value(value)
+    });
+
+    Attribute::with_multiple_values(None, "class", class_values)
+}
+
 /// return a class attribute where the classnames are transformed with
 /// namespace
 /// # Example:
@@ -334,6 +384,63 @@ pub fn attr<MSG, V: Into<Value>>(att: &'static str, v: V) -> Attribute<MSG> {
     mt_dom::attr(att, AttributeValue::from_value(v.into()))
 }
 
+/// create an attribute which carries a user defined value.
+/// The value is not set into the actual DOM, but a change in its value
+/// will be detected when diffing using the value's own equality.
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+/// use sauron::html::attributes::custom_attr;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Token {
+///     Primary,
+///     Secondary,
+/// }
+///
+/// let old: Node<()> = div(vec![custom_attr("token", Token::Primary)], vec![]);
+/// let same: Node<()> = div(vec![custom_attr("token", Token::Primary)], vec![]);
+/// let new: Node<()> = div(vec![custom_attr("token", Token::Secondary)], vec![]);
+/// assert!(diff(&old, &same).is_empty());
+/// assert_eq!(diff(&old, &new).len(), 1);
+/// ```
+pub fn custom_attr<MSG>(
+    att: &'static str,
+    v: impl CustomValue + 'static,
+) -> Attribute<MSG> {
+    mt_dom::attr(att, AttributeValue::from_custom(v))
+}
+
+/// Creates an attribute which is set as a property of the DOM element instead of an attribute.
+///
+/// Properties such as `value` and `checked` reflects the current state of the element
+/// which the attribute no longer controls after the user has interacted with it.
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+/// use sauron::html::attributes::prop;
+///
+/// let input: Node<()> = input(vec![r#type("text"), prop("value", "hello")], vec![]);
+/// ```
+pub fn prop<MSG, V: Into<Value>>(name: &'static str, v: V) -> Attribute<MSG> {
+    mt_dom::attr(name, AttributeValue::from_property(v.into()))
+}
+
+/// set the `value` property of form controls such as input, textarea and select
+pub fn prop_value<MSG>(v: impl Into<Value>) -> Attribute<MSG> {
+    prop("value", v)
+}
+
+/// set the `checked` property of checkbox and radio inputs
+pub fn prop_checked<MSG>(is_checked: bool) -> Attribute<MSG> {
+    prop("checked", is_checked)
+}
+
+/// set the `selected` property of an option element
+pub fn prop_selected<MSG>(is_selected: bool) -> Attribute<MSG> {
+    prop("selected", is_selected)
+}
+
 /// a utility function to return create an empty attr, useful for cases where branch expression
 /// need to return an attribute which otherwise it can not produce
 /// example:
@@ -354,6 +461,59 @@ pub fn empty_attr<MSG>() -> Attribute<MSG> {
     mt_dom::attr("", AttributeValue::Empty)
 }
 
+/// `rel="noopener noreferrer"`, which prevents the page opened by a link with `target="_blank"`
+/// from accessing `window.opener` and from receiving the referrer of this page.
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+///
+/// let link: Node<()> = a(
+///     vec![href("https://example.com"), target("_blank"), rel_noopener_noreferrer()],
+///     vec![text("example")],
+/// );
+/// assert_eq!(
+///     node!{<a href="https://example.com" target="_blank" rel="noopener noreferrer">"example"</a>},
+///     link
+/// );
+/// ```
+pub fn rel_noopener_noreferrer<MSG>() -> Attribute<MSG> {
+    rel("noopener noreferrer")
+}
+
+/// returns true if this element is an anchor with `target="_blank"` which is missing
+/// `noopener` or `noreferrer` in its `rel` attribute.
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+/// use sauron::html::attributes::is_missing_rel_protection;
+///
+/// let unsafe_link: Node<()> = a(vec![href("https://example.com"), target("_blank")], vec![]);
+/// assert!(is_missing_rel_protection(unsafe_link.as_element_ref().unwrap()));
+///
+/// let safe_link: Node<()> = safe_external_link("https://example.com", vec![]);
+/// assert!(!is_missing_rel_protection(safe_link.as_element_ref().unwrap()));
+/// ```
+pub fn is_missing_rel_protection<MSG>(element: &Element<MSG>) -> bool {
+    if *element.tag() != "a" {
+        return false;
+    }
+    let value_of = |name: &str| {
+        let values: Vec<&AttributeValue<MSG>> = element
+            .get_attributes()
+            .iter()
+            .filter(|att| *att.name() == name)
+            .flat_map(|att| att.value())
+            .collect();
+        merge_plain_attributes_values(&values)
+    };
+    let is_blank = value_of("target")
+        .map(|target| target.trim().eq_ignore_ascii_case("_blank"))
+        .unwrap_or(false);
+    let rel = value_of("rel").unwrap_or_default().to_lowercase();
+    let rels: Vec<&str> = rel.split_whitespace().collect();
+    is_blank && !(rels.contains(&"noopener") && rels.contains(&"noreferrer"))
+}
+
 /// merge the plain values
 #[doc(hidden)]
 pub(crate) fn merge_plain_attributes_values<MSG>(
@@ -412,6 +572,8 @@ pub struct SegregatedAttributes<'a, MSG> {
     pub styles: Vec<&'a AttributeValue<MSG>>,
     /// function calls
     pub function_calls: Vec<&'a AttributeValue<MSG>>,
+    /// values set as property of the DOM element
+    pub properties: Vec<&'a AttributeValue<MSG>>,
 }
 
 /// returns (listeners, plain_attribtues, function_calls)
@@ -423,6 +585,7 @@ pub(crate) fn partition_callbacks_from_plain_styles_and_func_calls<MSG>(
     let mut plain_values = vec![];
     let mut styles = vec![];
     let mut function_calls = vec![];
+    let mut properties = vec![];
     for av in attr.value() {
         match av {
             AttributeValue::Simple(_plain) => {
@@ -437,6 +600,9 @@ pub(crate) fn partition_callbacks_from_plain_styles_and_func_calls<MSG>(
             AttributeValue::EventListener(cb) => {
                 listeners.push(cb);
             }
+            AttributeValue::Property(_) => {
+                properties.push(av);
+            }
             _ => (),
         }
     }
@@ -445,5 +611,6 @@ pub(crate) fn partition_callbacks_from_plain_styles_and_func_calls<MSG>(
         plain_values,
         styles,
         function_calls,
+        properties,
     }
 }
@@ -63,7 +63,7 @@ macro_rules! declare_html_attributes{
 
         #[cfg(feature = "with-lookup")]
         /// These are most commonly used html attributes such as class, id, etc
-        pub const HTML_ATTRS:[&'static str; 115] = [$(stringify!($name),)*];
+        pub const HTML_ATTRS:[&'static str; 116] = [$(stringify!($name),)*];
     }
 }
 
@@ -177,6 +177,7 @@ declare_html_attributes! {
     rel;
     required;
     reversed;
+    role;
     rows;
     rowspan;
     sandbox;
@@ -221,3 +222,79 @@ declare_html_attributes_special! {
 
     r#type => "type";
 }
+
+/// declare the [aria][0] attributes with the type of their values,
+/// which describe the role and the state of the elements to the assistive technologies
+///
+/// [0]: https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes
+macro_rules! declare_aria_attributes {
+    ( $(
+         $(#[$attr:meta])*
+         $name:ident => $attribute:tt : $ty:ty;
+       )*
+     ) => {
+        $(
+            doc_comment!{
+                concat!("Creates the [",$attribute,"](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/",$attribute,") attribute"),
+                $(#[$attr])*
+                #[inline]
+                pub fn $name<MSG>(v: $ty) -> crate::Attribute<MSG> {
+                    attr($attribute, AttributeValue::from_value(v.into()))
+                }
+            }
+         )*
+    }
+}
+
+declare_aria_attributes! {
+    aria_activedescendant => "aria-activedescendant": impl Into<Value>;
+    aria_atomic => "aria-atomic": bool;
+    aria_autocomplete => "aria-autocomplete": &'static str;
+    aria_busy => "aria-busy": bool;
+    /// the value is `"true"`, `"false"` or `"mixed"`
+    aria_checked => "aria-checked": &'static str;
+    aria_colcount => "aria-colcount": i32;
+    aria_colindex => "aria-colindex": i32;
+    aria_controls => "aria-controls": impl Into<Value>;
+    /// the value is `"page"`, `"step"`, `"location"`, `"date"`, `"time"`, `"true"` or `"false"`
+    aria_current => "aria-current": &'static str;
+    aria_describedby => "aria-describedby": impl Into<Value>;
+    aria_description => "aria-description": impl Into<Value>;
+    aria_disabled => "aria-disabled": bool;
+    aria_errormessage => "aria-errormessage": impl Into<Value>;
+    aria_expanded => "aria-expanded": bool;
+    /// the value is `"true"`, `"false"`, `"menu"`, `"listbox"`, `"tree"`, `"grid"` or `"dialog"`
+    aria_haspopup => "aria-haspopup": &'static str;
+    aria_hidden => "aria-hidden": bool;
+    aria_invalid => "aria-invalid": bool;
+    aria_keyshortcuts => "aria-keyshortcuts": impl Into<Value>;
+    aria_label => "aria-label": impl Into<Value>;
+    aria_labelledby => "aria-labelledby": impl Into<Value>;
+    aria_level => "aria-level": i32;
+    /// the value is `"off"`, `"polite"` or `"assertive"`
+    aria_live => "aria-live": &'static str;
+    aria_modal => "aria-modal": bool;
+    aria_multiline => "aria-multiline": bool;
+    aria_multiselectable => "aria-multiselectable": bool;
+    /// the value is `"horizontal"` or `"vertical"`
+    aria_orientation => "aria-orientation": &'static str;
+    aria_owns => "aria-owns": impl Into<Value>;
+    aria_placeholder => "aria-placeholder": impl Into<Value>;
+    aria_posinset => "aria-posinset": i32;
+    /// the value is `"true"`, `"false"` or `"mixed"`
+    aria_pressed => "aria-pressed": &'static str;
+    aria_readonly => "aria-readonly": bool;
+    aria_relevant => "aria-relevant": &'static str;
+    aria_required => "aria-required": bool;
+    aria_roledescription => "aria-roledescription": impl Into<Value>;
+    aria_rowcount => "aria-rowcount": i32;
+    aria_rowindex => "aria-rowindex": i32;
+    aria_selected => "aria-selected": bool;
+    aria_setsize => "aria-setsize": i32;
+    /// the value is `"ascending"`, `"descending"`, `"other"` or `"none"`
+    aria_sort => "aria-sort": &'static str;
+    aria_valuemax => "aria-valuemax": f64;
+    aria_valuemin => "aria-valuemin": f64;
+    aria_valuenow => "aria-valuenow": f64;
+    aria_valuetext => "aria-valuetext": impl Into<Value>;
+}
@@ -1,6 +1,9 @@
+use super::CustomValue;
 use crate::prelude::{Style, Value};
 use crate::Listener;
 use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
 
 /// Values of an attribute can be in these variants
 pub enum AttributeValue<MSG> {
@@ -10,8 +13,14 @@ pub enum AttributeValue<MSG> {
     Simple(Value),
     /// style values
     Style(Vec<Style>),
+    /// a value which is set as a property of the DOM element instead of an attribute,
+    /// ie: `value`, `checked`, `selected` of form controls
+    Property(Value),
     /// Event Listener
     EventListener(Listener<MSG>),
+    /// user defined value, which is compared using its own equality
+    /// and is not set into the actual DOM
+    Custom(Rc<dyn CustomValue>),
     /// no value
     Empty,
 }
@@ -28,9 +37,15 @@ impl<MSG> Clone for AttributeValue<MSG> {
                 AttributeValue::Simple(this.clone())
             }
             AttributeValue::Style(this) => AttributeValue::Style(this.clone()),
+            AttributeValue::Property(this) => {
+                AttributeValue::Property(this.clone())
+            }
             AttributeValue::EventListener(this) => {
                 AttributeValue::EventListener(this.clone())
             }
+            AttributeValue::Custom(this) => {
+                AttributeValue::Custom(Rc::clone(this))
+            }
             AttributeValue::Empty => AttributeValue::Empty,
         }
     }
@@ -44,7 +59,9 @@ impl<MSG> Debug for AttributeValue<MSG> {
             AttributeValue::FunctionCall(this) => this.fmt(f),
             AttributeValue::Simple(this) => this.fmt(f),
             AttributeValue::Style(this) => this.fmt(f),
+            AttributeValue::Property(this) => this.fmt(f),
             AttributeValue::EventListener(this) => this.fmt(f),
+            AttributeValue::Custom(this) => this.fmt(f),
             AttributeValue::Empty => write!(f, "Empty"),
         }
     }
@@ -65,16 +82,42 @@ impl<MSG> PartialEq for AttributeValue<MSG> {
             (AttributeValue::Style(this), AttributeValue::Style(other)) => {
                 this == other
             }
+            (
+                AttributeValue::Property(this),
+                AttributeValue::Property(other),
+            ) => this == other,
             (
                 AttributeValue::EventListener(this),
                 AttributeValue::EventListener(other),
             ) => this == other,
+            (AttributeValue::Custom(this), AttributeValue::Custom(other)) => {
+                this.eq_custom(other.as_ref())
+            }
             (AttributeValue::Empty, AttributeValue::Empty) => true,
             (_, _) => false,
         }
     }
 }
 
+/// This is written manually, so we don't push
+/// constraint on MSG to be Hash.
+///
+/// The custom values are compared with their own equality, which can't be hashed,
+/// so only their variant is hashed.
+impl<MSG> Hash for AttributeValue<MSG> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            AttributeValue::FunctionCall(this) => this.hash(state),
+            AttributeValue::Simple(this) => this.hash(state),
+            AttributeValue::Style(this) => this.hash(state),
+            AttributeValue::Property(this) => this.hash(state),
+            AttributeValue::EventListener(this) => this.hash(state),
+            AttributeValue::Custom(_) | AttributeValue::Empty => (),
+        }
+    }
+}
+
 impl<MSG> AttributeValue<MSG> {
     /// create an attribute from Vec<Style>
     pub fn from_styles(styles: impl IntoIterator<Item = Style>) -> Self {
@@ -86,11 +129,21 @@ impl<MSG> AttributeValue<MSG> {
         AttributeValue::Simple(value)
     }
 
+    /// create an attribute value which is set as a property of the DOM element
+    pub fn from_property(value: Value) -> Self {
+        AttributeValue::Property(value)
+    }
+
     /// create an attribute from a function `name` with arguments `value`
     pub fn function_call(value: Value) -> Self {
         AttributeValue::FunctionCall(value)
     }
 
+    /// create an attribute value from a user defined value
+    pub fn from_custom(value: impl CustomValue + 'static) -> Self {
+        AttributeValue::Custom(Rc::new(value))
+    }
+
     /// return the value if it is a Simple variant
     pub fn get_simple(&self) -> Option<&Value> {
         match self {
@@ -99,6 +152,19 @@ impl<MSG> AttributeValue<MSG> {
         }
     }
 
+    /// return the value if it is a Property variant
+    pub fn get_property(&self) -> Option<&Value> {
+        match self {
+            AttributeValue::Property(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// returns true if this is a value set as a property of the DOM element
+    pub fn is_property(&self) -> bool {
+        matches!(self, AttributeValue::Property(_))
+    }
+
     /// return the function call argument value if it is a FunctionCall variant
     pub fn get_function_call_value(&self) -> Option<&Value> {
         match self {
@@ -133,6 +199,20 @@ impl<MSG> AttributeValue<MSG> {
         matches!(self, AttributeValue::FunctionCall(_))
     }
 
+    /// return the custom value downcasted to type `T`,
+    /// None if this is not a Custom variant or the type doesn't match
+    pub fn as_custom<T: 'static>(&self) -> Option<&T> {
+        match self {
+            AttributeValue::Custom(custom) => custom.as_any().downcast_ref(),
+            _ => None,
+        }
+    }
+
+    /// returns true if this is a custom value
+    pub fn is_custom(&self) -> bool {
+        matches!(self, AttributeValue::Custom(_))
+    }
+
     /// returns true if this attribute value is empty
     pub fn is_empty(&self) -> bool {
         matches!(self, AttributeValue::Empty)
@@ -0,0 +1,59 @@
+use std::{cell::RefCell, rc::Rc};
+
+thread_local!(static CLASS_TRANSFORMER: RefCell<Option<Rc<dyn Fn(&str) -> String>>> = RefCell::new(None));
+
+/// Set the transformer of the class names, which is applied to each of the class names
+/// when the elements are created, patched and rendered into html.
+///
+/// The transformer can return multiple class names separated by spaces, ie: to expand
+/// a shorthand into utility classes, or an empty string to drop the class name.
+/// This lets a design system enforce its conventions centrally, such as prefixing the classes,
+/// instead of wrapping every call to `class`.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+/// use sauron::html::attributes::set_class_transformer;
+///
+/// set_class_transformer(|class_name| match class_name {
+///     "card" => "ds-card ds-shadow".to_string(),
+///     _ => format!("ds-{}", class_name),
+/// });
+/// let view: Node<()> = div(vec![class("card active")], vec![]);
+/// assert_eq!(view.render_to_string(), r#"<div class="ds-card ds-shadow ds-active"></div>"#);
+/// ```
+pub fn set_class_transformer<F>(transformer: F)
+where
+    F: Fn(&str) -> String + 'static,
+{
+    CLASS_TRANSFORMER.with(|class_transformer| {
+        *class_transformer.borrow_mut() = Some(Rc::new(transformer))
+    });
+}
+
+/// remove the transformer of the class names
+pub fn clear_class_transformer() {
+    CLASS_TRANSFORMER
+        .with(|class_transformer| *class_transformer.borrow_mut() = None);
+}
+
+/// apply the transformer to each of the class names in the `classes`,
+/// the `classes` is returned as is when there is no transformer.
+pub fn transform_classes(classes: &str) -> String {
+    let transformer = CLASS_TRANSFORMER
+        .with(|class_transformer| class_transformer.borrow().clone());
+    match transformer {
+        Some(transformer) => {
+            let mut class_names: Vec<String> = vec![];
+            for class_name in classes.split_whitespace() {
+                for transformed in transformer(class_name).split_whitespace() {
+                    if !class_names.iter().any(|c| c == transformed) {
+                        class_names.push(transformed.to_string());
+                    }
+                }
+            }
+            class_names.join(" ")
+        }
+        None => classes.to_string(),
+    }
+}
@@ -0,0 +1,36 @@
+//! Custom values allows users to attach their own typed data into an attribute.
+//!
+//! These values are never stringified into the DOM, but they participate in diffing
+//! using the equality of the user supplied type.
+use std::any::Any;
+use std::fmt;
+
+/// A user defined value that can be carried by an attribute.
+///
+/// This is automatically implemented for any type that is `PartialEq + Debug + 'static`,
+/// so users don't have to implement this manually.
+pub trait CustomValue: fmt::Debug {
+    /// compare this value to another custom value.
+    /// Values of different types are never equal.
+    fn eq_custom(&self, other: &dyn CustomValue) -> bool;
+
+    /// return this value as `Any` so it can be downcasted into its concrete type
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T> CustomValue for T
+where
+    T: PartialEq + fmt::Debug + 'static,
+{
+    fn eq_custom(&self, other: &dyn CustomValue) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<T>()
+            .map(|other| self == other)
+            .unwrap_or(false)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
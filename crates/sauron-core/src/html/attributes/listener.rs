@@ -1,7 +1,11 @@
 //! Callbacks contains function that can be called at a later time.
 //! This is used in containing an event listener attached to an DOM element.
 use std::any::TypeId;
-use std::{fmt, rc::Rc};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
 
 /// A generic sized representation of a function that can be
 /// attached to a Node. The callback will essentially be owned by the element
@@ -124,3 +128,12 @@ impl<IN, OUT> PartialEq for Listener<IN, OUT> {
             && self.func_type_id == other.func_type_id
     }
 }
+
+/// hashed with the same type_ids which are compared in the `PartialEq`
+impl<IN, OUT> Hash for Listener<IN, OUT> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.event_type_id.hash(state);
+        self.msg_type_id.hash(state);
+        self.func_type_id.hash(state);
+    }
+}
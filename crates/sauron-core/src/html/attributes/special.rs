@@ -13,9 +13,10 @@ pub trait Special {
         self.get_value("key")
     }
 
-    /// return the boolean value of the "focus" attribute of this node
+    /// return the boolean value of the "focus" or the "autofocus" attribute of this node
     fn is_focused(&self) -> bool {
         self.get_value("focus")
+            .or_else(|| self.get_value("autofocus"))
             .map(|v| v.as_bool())
             .flatten()
             .unwrap_or(false)
@@ -60,6 +61,18 @@ pub fn skip<MSG>(v: bool) -> Attribute<MSG> {
     attr("skip", v)
 }
 
+/// mark the node as static, the diffing of this node and its descendants is skipped entirely.
+/// Use this for subtrees which never changes such as icons, footers and large svg charts,
+/// so they are not traversed on every update.
+///
+/// Text and comment nodes are returned as is, since they can't have attributes.
+pub fn static_node<MSG>(node: Node<MSG>) -> Node<MSG> {
+    match node {
+        Node::Element(_) => node.add_attributes(vec![skip(true)]),
+        Node::Text(_) | Node::Comment(_) => node,
+    }
+}
+
 /// if the value is true, then this node is made to replace the old
 /// node it matches
 pub fn replace<MSG>(v: bool) -> Attribute<MSG> {
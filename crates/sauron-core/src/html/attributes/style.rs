@@ -11,7 +11,7 @@ macro_rules! style {
 
 /// css styles
 /// style can be converted into an attribute
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Hash)]
 pub struct Style {
     /// style name such as border, width, etc
     pub name: String,
@@ -34,3 +34,68 @@ impl fmt::Display for Style {
         write!(f, "{}:{}", self.name, self.value)
     }
 }
+
+/// The changes on the style properties from the old styles to the new styles,
+/// this is used to update only the properties that changed, leaving the properties
+/// set by other means (ie: animation libraries) intact.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UpdateStyles {
+    /// the style properties which are added or has changed its value
+    pub set: Vec<Style>,
+    /// the name of the style properties that are removed
+    pub remove: Vec<String>,
+}
+
+impl UpdateStyles {
+    /// diff the old styles and the new styles property by property.
+    /// When a property is declared more than once, the last one is used.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sauron::html::attributes::{Style, UpdateStyles};
+    ///
+    /// let old = [Style::new("display", "block"), Style::new("color", "red")];
+    /// let new = [Style::new("display", "none")];
+    /// assert_eq!(
+    ///     UpdateStyles::diff(&old, &new),
+    ///     UpdateStyles {
+    ///         set: vec![Style::new("display", "none")],
+    ///         remove: vec!["color".to_string()],
+    ///     }
+    /// );
+    /// ```
+    pub fn diff(old: &[Style], new: &[Style]) -> Self {
+        let old = last_declared(old);
+        let new = last_declared(new);
+        let set = new
+            .iter()
+            .filter(|new_style| {
+                !old.iter().any(|old_style| old_style == *new_style)
+            })
+            .map(|new_style| (*new_style).clone())
+            .collect();
+        let remove = old
+            .iter()
+            .filter(|old_style| {
+                !new.iter().any(|new_style| new_style.name == old_style.name)
+            })
+            .map(|old_style| old_style.name.clone())
+            .collect();
+        UpdateStyles { set, remove }
+    }
+
+    /// returns true if there are no changes in the styles
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty() && self.remove.is_empty()
+    }
+}
+
+/// only the last declared style of the same name is in effect
+fn last_declared(styles: &[Style]) -> Vec<&Style> {
+    let mut declared: Vec<&Style> = vec![];
+    for style in styles {
+        declared.retain(|existing| existing.name != style.name);
+        declared.push(style);
+    }
+    declared
+}
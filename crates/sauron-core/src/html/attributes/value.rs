@@ -1,14 +1,19 @@
-use std::fmt;
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
 
 ///TODO: merge this with AttributeValue
 /// Value is an abstraction of the values used in the actual
 /// backend. Html and gtk-rs have different set of compatible values
 /// therefore a need for a storage of these intermediate value is needed
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
 pub enum Value {
     /// bool value
     Bool(bool),
-    /// &'static str value
+    /// &'static str value, the literals in the view are kept as is instead of being allocated.
+    /// The text nodes are the `Text` of mt-dom which owns a `String`,
+    /// so their literals can not be borrowed the same way from this crate.
     Str(&'static str),
     /// String value
     String(String),
@@ -114,6 +119,35 @@ impl Value {
     }
 }
 
+/// The floats are hashed by their bits, with `-0.0` hashed the same as `0.0` since they are equal.
+/// The hash is stable for the same build of the app, it should not be persisted.
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::Bool(v) => v.hash(state),
+            Value::Str(v) => v.hash(state),
+            Value::String(v) => v.hash(state),
+            Value::Vec(v) => v.hash(state),
+            Value::U8(v) => v.hash(state),
+            Value::U16(v) => v.hash(state),
+            Value::U32(v) => v.hash(state),
+            Value::U64(v) => v.hash(state),
+            Value::Usize(v) => v.hash(state),
+            Value::U128(v) => v.hash(state),
+            Value::I8(v) => v.hash(state),
+            Value::I16(v) => v.hash(state),
+            Value::I32(v) => v.hash(state),
+            Value::I64(v) => v.hash(state),
+            Value::I128(v) => v.hash(state),
+            Value::Isize(v) => v.hash(state),
+            Value::F32(v) => (v + 0.0).to_bits().hash(state),
+            Value::F64(v) => (v + 0.0).to_bits().hash(state),
+            Value::Bytes(v) => v.hash(state),
+        }
+    }
+}
+
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -149,6 +183,26 @@ impl fmt::Display for Value {
     }
 }
 
+#[cfg(feature = "with-dom")]
+impl From<&Value> for wasm_bindgen::JsValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Bool(v) => wasm_bindgen::JsValue::from_bool(*v),
+            Value::String(v) => wasm_bindgen::JsValue::from_str(v),
+            Value::Str(v) => wasm_bindgen::JsValue::from_str(v),
+            Value::Vec(v) => v
+                .iter()
+                .map(wasm_bindgen::JsValue::from)
+                .collect::<js_sys::Array>()
+                .into(),
+            Value::Bytes(v) => js_sys::Uint8Array::from(v.as_slice()).into(),
+            _ => wasm_bindgen::JsValue::from_f64(
+                value.as_f64().expect("must be a numeric value"),
+            ),
+        }
+    }
+}
+
 impl From<&String> for Value {
     fn from(v: &String) -> Self {
         Value::String(v.to_string())
@@ -0,0 +1,485 @@
+//! A typed builder for stylesheets, so the css returned from `Application::style`
+//! doesn't have to be concatenated by hand.
+//!
+//! The properties only accept the values of their type, a length property can not be
+//! given a color and vice versa, which would otherwise only be noticed in the browser.
+//!
+//! # Example
+//! ```rust
+//! use sauron::html::css::{percent, px, rgba, Declarations, StyleSheet};
+//!
+//! let css = StyleSheet::new()
+//!     .rule(
+//!         ".btn",
+//!         Declarations::new()
+//!             .width(px(120))
+//!             .border_radius(percent(50))
+//!             .color(rgba(0, 45, 52, 0.3))
+//!             .nest("&:hover", Declarations::new().opacity(0.8)),
+//!     )
+//!     .to_string();
+//!
+//! assert_eq!(
+//!     css,
+//!     ".btn{width:120px;border-radius:50%;color:rgba(0,45,52,0.3);}\n.btn:hover{opacity:0.8;}\n"
+//! );
+//! ```
+use crate::html::attributes::Value;
+use std::fmt;
+
+/// A css length
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length {
+    /// zero, which doesn't need a unit
+    Zero,
+    /// the `auto` keyword
+    Auto,
+    /// pixels
+    Px(f64),
+    /// percentage of the parent
+    Percent(f64),
+    /// relative to the font-size of the element
+    Em(f64),
+    /// relative to the font-size of the root element
+    Rem(f64),
+    /// 1% of the width of the viewport
+    Vw(f64),
+    /// 1% of the height of the viewport
+    Vh(f64),
+}
+
+/// A css color
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    /// the `transparent` keyword
+    Transparent,
+    /// the `currentcolor` keyword
+    CurrentColor,
+    /// red, green and blue channels
+    Rgb(u8, u8, u8),
+    /// red, green, blue and the alpha from 0.0 to 1.0
+    Rgba(u8, u8, u8, f64),
+    /// a hex color such as `0x0c0e0e`
+    Hex(u32),
+}
+
+/// The value of the `display` property
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Display {
+    /// `display: none`
+    None,
+    /// `display: block`
+    Block,
+    /// `display: inline`
+    Inline,
+    /// `display: inline-block`
+    InlineBlock,
+    /// `display: flex`
+    Flex,
+    /// `display: inline-flex`
+    InlineFlex,
+    /// `display: grid`
+    Grid,
+    /// `display: contents`
+    Contents,
+}
+
+/// The value of the `position` property
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Position {
+    /// `position: static`
+    Static,
+    /// `position: relative`
+    Relative,
+    /// `position: absolute`
+    Absolute,
+    /// `position: fixed`
+    Fixed,
+    /// `position: sticky`
+    Sticky,
+}
+
+/// a length in pixels
+pub fn px(v: impl Into<f64>) -> Length {
+    Length::Px(v.into())
+}
+
+/// a length in percent
+pub fn percent(v: impl Into<f64>) -> Length {
+    Length::Percent(v.into())
+}
+
+/// a length relative to the font-size of the element
+pub fn em(v: impl Into<f64>) -> Length {
+    Length::Em(v.into())
+}
+
+/// a length relative to the font-size of the root element
+pub fn rem(v: impl Into<f64>) -> Length {
+    Length::Rem(v.into())
+}
+
+/// a length relative to the width of the viewport
+pub fn vw(v: impl Into<f64>) -> Length {
+    Length::Vw(v.into())
+}
+
+/// a length relative to the height of the viewport
+pub fn vh(v: impl Into<f64>) -> Length {
+    Length::Vh(v.into())
+}
+
+/// a color from its red, green and blue channels
+pub fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::Rgb(r, g, b)
+}
+
+/// a color from its red, green and blue channels and an alpha from 0.0 to 1.0
+pub fn rgba(r: u8, g: u8, b: u8, a: f64) -> Color {
+    Color::Rgba(r, g, b, a.max(0.0).min(1.0))
+}
+
+/// a color from its hex value, ie: `hex(0x0c0e0e)`
+pub fn hex(v: u32) -> Color {
+    Color::Hex(v & 0xff_ff_ff)
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Length::Zero => write!(f, "0"),
+            Length::Auto => write!(f, "auto"),
+            Length::Px(v) => write!(f, "{}px", v),
+            Length::Percent(v) => write!(f, "{}%", v),
+            Length::Em(v) => write!(f, "{}em", v),
+            Length::Rem(v) => write!(f, "{}rem", v),
+            Length::Vw(v) => write!(f, "{}vw", v),
+            Length::Vh(v) => write!(f, "{}vh", v),
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Color::Transparent => write!(f, "transparent"),
+            Color::CurrentColor => write!(f, "currentcolor"),
+            Color::Rgb(r, g, b) => write!(f, "rgb({},{},{})", r, g, b),
+            Color::Rgba(r, g, b, a) => {
+                write!(f, "rgba({},{},{},{})", r, g, b, a)
+            }
+            Color::Hex(v) => write!(f, "#{:06x}", v),
+        }
+    }
+}
+
+impl fmt::Display for Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let keyword = match self {
+            Display::None => "none",
+            Display::Block => "block",
+            Display::Inline => "inline",
+            Display::InlineBlock => "inline-block",
+            Display::Flex => "flex",
+            Display::InlineFlex => "inline-flex",
+            Display::Grid => "grid",
+            Display::Contents => "contents",
+        };
+        write!(f, "{}", keyword)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let keyword = match self {
+            Position::Static => "static",
+            Position::Relative => "relative",
+            Position::Absolute => "absolute",
+            Position::Fixed => "fixed",
+            Position::Sticky => "sticky",
+        };
+        write!(f, "{}", keyword)
+    }
+}
+
+macro_rules! impl_into_value {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(v: $ty) -> Self {
+                    Value::String(v.to_string())
+                }
+            }
+        )*
+    };
+}
+
+// so the typed values can also be used in the inline `style` attributes
+impl_into_value!(Length, Color, Display, Position);
+
+macro_rules! declare_properties {
+    ( $(
+        $(#[$attr:meta])*
+        $name:ident => $property:tt : $ty:ty;
+    )* ) => {
+        $(
+            $(#[$attr])*
+            pub fn $name(self, value: impl Into<$ty>) -> Self {
+                self.declare($property, value.into())
+            }
+        )*
+    };
+}
+
+/// The declarations of a rule, and the rules nested in it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Declarations {
+    properties: Vec<(&'static str, String)>,
+    nested: Vec<(String, Declarations)>,
+}
+
+impl Declarations {
+    /// create an empty declaration block
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn declare(
+        mut self,
+        property: &'static str,
+        value: impl fmt::Display,
+    ) -> Self {
+        self.properties.push((property, value.to_string()));
+        self
+    }
+
+    declare_properties! {
+        /// the `width` property
+        width => "width": Length;
+        /// the `height` property
+        height => "height": Length;
+        /// the `min-width` property
+        min_width => "min-width": Length;
+        /// the `min-height` property
+        min_height => "min-height": Length;
+        /// the `max-width` property
+        max_width => "max-width": Length;
+        /// the `max-height` property
+        max_height => "max-height": Length;
+        /// the `top` property
+        top => "top": Length;
+        /// the `right` property
+        right => "right": Length;
+        /// the `bottom` property
+        bottom => "bottom": Length;
+        /// the `left` property
+        left => "left": Length;
+        /// the `margin` property
+        margin => "margin": Length;
+        /// the `padding` property
+        padding => "padding": Length;
+        /// the `gap` property
+        gap => "gap": Length;
+        /// the `font-size` property
+        font_size => "font-size": Length;
+        /// the `line-height` property
+        line_height => "line-height": Length;
+        /// the `border-width` property
+        border_width => "border-width": Length;
+        /// the `border-radius` property
+        border_radius => "border-radius": Length;
+        /// the `color` property
+        color => "color": Color;
+        /// the `background-color` property
+        background_color => "background-color": Color;
+        /// the `border-color` property
+        border_color => "border-color": Color;
+        /// the `display` property
+        display => "display": Display;
+        /// the `position` property
+        position => "position": Position;
+    }
+
+    /// the `opacity` property, clamped from 0.0 to 1.0
+    pub fn opacity(self, value: f64) -> Self {
+        self.declare("opacity", value.max(0.0).min(1.0))
+    }
+
+    /// the `z-index` property
+    pub fn z_index(self, value: i32) -> Self {
+        self.declare("z-index", value)
+    }
+
+    /// the `margin` property with a value for each of the sides,
+    /// in the order of top, right, bottom and left
+    pub fn margins(self, sides: [Length; 4]) -> Self {
+        self.declare("margin", join(&sides))
+    }
+
+    /// the `padding` property with a value for each of the sides,
+    /// in the order of top, right, bottom and left
+    pub fn paddings(self, sides: [Length; 4]) -> Self {
+        self.declare("padding", join(&sides))
+    }
+
+    /// a property which has no typed method yet, the value is used as is
+    pub fn raw(self, property: &'static str, value: impl fmt::Display) -> Self {
+        self.declare(property, value)
+    }
+
+    /// nest a rule in this one, an `&` in the selector is replaced with the selector
+    /// of this rule, otherwise the selector is used as a descendant of this rule
+    pub fn nest(
+        mut self,
+        selector: impl ToString,
+        declarations: Declarations,
+    ) -> Self {
+        self.nested.push((selector.to_string(), declarations));
+        self
+    }
+
+    /// returns true if there is no property declared
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty() && self.nested.is_empty()
+    }
+
+    /// the properties declared, in the form used in the inline `style` attribute
+    pub fn inline(&self) -> String {
+        self.properties
+            .iter()
+            .map(|(property, value)| format!("{}:{};", property, value))
+            .collect()
+    }
+
+    fn write_rules(
+        &self,
+        selector: &str,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        if !self.properties.is_empty() {
+            writeln!(f, "{}{{{}}}", selector, self.inline())?;
+        }
+        for (nested, declarations) in self.nested.iter() {
+            let nested_selector = nest_selector(selector, nested);
+            declarations.write_rules(&nested_selector, f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A list of rules
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyleSheet {
+    rules: Vec<(String, Declarations)>,
+}
+
+impl StyleSheet {
+    /// create an empty stylesheet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add a rule for the selector
+    pub fn rule(
+        mut self,
+        selector: impl ToString,
+        declarations: Declarations,
+    ) -> Self {
+        self.rules.push((selector.to_string(), declarations));
+        self
+    }
+
+    /// append the rules of the other stylesheet into this one
+    pub fn extend(mut self, other: StyleSheet) -> Self {
+        self.rules.extend(other.rules);
+        self
+    }
+}
+
+impl fmt::Display for StyleSheet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (selector, declarations) in self.rules.iter() {
+            declarations.write_rules(selector, f)?;
+        }
+        Ok(())
+    }
+}
+
+fn join(lengths: &[Length]) -> String {
+    lengths
+        .iter()
+        .map(|length| length.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// each of the comma separated parent selectors is combined with each of the nested ones
+fn nest_selector(parent: &str, nested: &str) -> String {
+    parent
+        .split(',')
+        .flat_map(|parent| {
+            let parent = parent.trim();
+            nested.split(',').map(move |nested| {
+                let nested = nested.trim();
+                if nested.contains('&') {
+                    nested.replace('&', parent)
+                } else {
+                    format!("{} {}", parent, nested)
+                }
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_values() {
+        assert_eq!(px(10).to_string(), "10px");
+        assert_eq!(percent(12.5).to_string(), "12.5%");
+        assert_eq!(Length::Zero.to_string(), "0");
+        assert_eq!(rgba(0, 45, 52, 1.5).to_string(), "rgba(0,45,52,1)");
+        assert_eq!(hex(0x0c0e0e).to_string(), "#0c0e0e");
+        assert_eq!(Value::from(px(3)), Value::String("3px".to_string()));
+    }
+
+    #[test]
+    fn test_nested_rules() {
+        let css = StyleSheet::new()
+            .rule(
+                ".menu, .bar",
+                Declarations::new()
+                    .display(Display::Flex)
+                    .margins([Length::Zero, px(4), Length::Zero, px(4)])
+                    .nest(
+                        "&.open",
+                        Declarations::new().background_color(hex(0xffffff)),
+                    )
+                    .nest("li", Declarations::new().padding(em(1))),
+            )
+            .to_string();
+        let expected = [
+            ".menu, .bar{display:flex;margin:0 4px 0 4px;}",
+            ".menu.open,.bar.open{background-color:#ffffff;}",
+            ".menu li,.bar li{padding:1em;}",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(css, expected);
+    }
+
+    #[test]
+    fn test_empty_rules_are_skipped() {
+        let css = StyleSheet::new()
+            .rule(".a", Declarations::new())
+            .rule(
+                ".b",
+                Declarations::new().nest("&:hover", Declarations::new()),
+            )
+            .to_string();
+        assert_eq!(css, "");
+    }
+}
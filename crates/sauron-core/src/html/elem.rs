@@ -0,0 +1,170 @@
+//! provides [`Elem`], a fluent builder of elements which lowers into the same [`Node`]
+//! created by the tag functions and the `node!` macro, so they can be mixed freely.
+use crate::{
+    html::{
+        attributes::{self, Value},
+        element_ns, text,
+    },
+    Attribute, Node,
+};
+
+/// A fluent builder of an element, an alternative to the tag functions
+/// which take the attributes and the children as 2 separate lists.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+///
+/// let greeting: Node<()> = Elem::new("div")
+///     .class("greeting")
+///     .child(text("hi"))
+///     .child(span(vec![], vec![text("there")]))
+///     .build();
+///
+/// assert_eq!(
+///     node! {<div class="greeting">"hi"<span>"there"</span></div>},
+///     greeting
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Elem<MSG> {
+    tag: &'static str,
+    namespace: Option<&'static str>,
+    attrs: Vec<Attribute<MSG>>,
+    children: Vec<Node<MSG>>,
+    self_closing: bool,
+}
+
+impl<MSG> Elem<MSG> {
+    /// start building an html element with the `tag`
+    pub fn new(tag: &'static str) -> Self {
+        Elem {
+            tag,
+            namespace: None,
+            attrs: vec![],
+            children: vec![],
+            self_closing: false,
+        }
+    }
+
+    /// start building an element with the `tag` in the `namespace`, ie: svg elements
+    pub fn new_ns(tag: &'static str, namespace: &'static str) -> Self {
+        Elem {
+            namespace: Some(namespace),
+            ..Elem::new(tag)
+        }
+    }
+
+    /// render the element as a self closing tag, ie: `<input/>`
+    pub fn self_closing(mut self) -> Self {
+        self.self_closing = true;
+        self
+    }
+
+    /// add an attribute to the element
+    pub fn attr(mut self, attr: Attribute<MSG>) -> Self {
+        self.attrs.push(attr);
+        self
+    }
+
+    /// add the attributes to the element
+    pub fn attrs(
+        mut self,
+        attrs: impl IntoIterator<Item = Attribute<MSG>>,
+    ) -> Self {
+        self.attrs.extend(attrs);
+        self
+    }
+
+    /// set the class of the element, multiple calls adds up the class names
+    pub fn class(self, class: impl Into<Value>) -> Self {
+        self.attr(attributes::class(class))
+    }
+
+    /// set the id of the element
+    pub fn id(self, id: impl Into<Value>) -> Self {
+        self.attr(attributes::id(id))
+    }
+
+    /// set the key of the element, used for matching the elements when diffing
+    pub fn key(self, key: impl Into<Value>) -> Self {
+        self.attr(attributes::key(key))
+    }
+
+    /// set a style of the element
+    pub fn style(self, name: impl ToString, value: impl Into<Value>) -> Self {
+        self.attr(attributes::style(name, value))
+    }
+
+    /// add a child to the element, which can also be an [`Elem`]
+    pub fn child(mut self, child: impl Into<Node<MSG>>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+
+    /// add the children to the element
+    pub fn children(
+        mut self,
+        children: impl IntoIterator<Item = Node<MSG>>,
+    ) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// add a text node to the element
+    pub fn text(self, s: impl ToString) -> Self {
+        self.child(text(s))
+    }
+
+    /// build the element into a node
+    pub fn build(self) -> Node<MSG> {
+        element_ns(
+            self.namespace,
+            self.tag,
+            self.attrs,
+            self.children,
+            self.self_closing,
+        )
+    }
+}
+
+#[cfg(feature = "with-dom")]
+mod listeners {
+    use super::Elem;
+    use crate::dom::events::{self, on, Event, InputEvent, MouseEvent};
+
+    impl<MSG> Elem<MSG>
+    where
+        MSG: 'static,
+    {
+        /// listen to the event with the `event_name`
+        pub fn on<F>(self, event_name: &'static str, f: F) -> Self
+        where
+            F: Fn(Event) -> MSG + 'static,
+        {
+            self.attr(on(event_name, f))
+        }
+
+        /// listen to the click event of the element
+        pub fn on_click<F>(self, f: F) -> Self
+        where
+            F: Fn(MouseEvent) -> MSG + 'static,
+        {
+            self.attr(events::on_click(f))
+        }
+
+        /// listen to the input event of the element
+        pub fn on_input<F>(self, f: F) -> Self
+        where
+            F: Fn(InputEvent) -> MSG + 'static,
+        {
+            self.attr(events::on_input(f))
+        }
+    }
+}
+
+impl<MSG> From<Elem<MSG>> for Node<MSG> {
+    fn from(elem: Elem<MSG>) -> Self {
+        elem.build()
+    }
+}
@@ -0,0 +1,195 @@
+//! Describes the content of the `<head>` of the document, such as the title, the meta tags
+//! and the link tags of the page.
+//!
+//! The program applies the [`Head`] returned from `Application::head` into the document when
+//! it is mounted and after the updates which return [`Cmd::update_head`](crate::Cmd::update_head),
+//! while the server renders the same [`Head`] into the page with [`Head::nodes`], so the crawlers
+//! which don't run the app still see the title and the meta tags.
+//!
+//! # Examples
+//! ```rust
+//! use sauron::prelude::*;
+//!
+//! let head = Head::new()
+//!     .title("Sauron & friends")
+//!     .meta("description", "A web framework")
+//!     .link("canonical", "https://example.com/");
+//! assert_eq!(
+//!     head.render_to_string(),
+//!     concat!(
+//!         "<title>Sauron &amp; friends</title>",
+//!         r#"<meta name="description" content="A web framework" data-sauron-head="meta:name:description"/>"#,
+//!         r#"<link rel="canonical" href="https://example.com/" data-sauron-head="link:canonical"/>"#,
+//!     )
+//! );
+//! ```
+use crate::{
+    html::{attributes::attr, html_element, html_element_self_closing, text},
+    render::Render,
+    sanitize::escape_attribute_value,
+    Node,
+};
+use std::fmt;
+
+/// the attribute of the tags in the head which are managed by the app,
+/// its value is the key which identifies the tag when the head is updated
+pub const HEAD_ATTR: &str = "data-sauron-head";
+
+/// The title and the tags of the head of the document
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Head {
+    /// the title of the document, the title is left as is when this is None
+    pub(crate) title: Option<String>,
+    pub(crate) tags: Vec<HeadTag>,
+}
+
+/// a meta or a link tag of the head
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct HeadTag {
+    /// identifies the tag, a tag with the same key replaces the earlier one
+    pub(crate) key: String,
+    pub(crate) tag: &'static str,
+    pub(crate) attrs: Vec<(&'static str, String)>,
+}
+
+impl Head {
+    /// an empty head
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// set the title of the document
+    pub fn title(mut self, title: impl ToString) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// add a `<meta name="{name}" content="{content}">`, ie: `description`
+    pub fn meta(self, name: impl ToString, content: impl ToString) -> Self {
+        let name = name.to_string();
+        self.push(HeadTag {
+            key: format!("meta:name:{}", name),
+            tag: "meta",
+            attrs: vec![("name", name), ("content", content.to_string())],
+        })
+    }
+
+    /// add a `<meta property="{property}" content="{content}">`,
+    /// which is used by the [Open Graph](https://ogp.me/) tags, ie: `og:title`
+    pub fn meta_property(
+        self,
+        property: impl ToString,
+        content: impl ToString,
+    ) -> Self {
+        let property = property.to_string();
+        self.push(HeadTag {
+            key: format!("meta:property:{}", property),
+            tag: "meta",
+            attrs: vec![
+                ("property", property),
+                ("content", content.to_string()),
+            ],
+        })
+    }
+
+    /// add a `<link rel="{rel}" href="{href}">`, ie: `canonical` or `icon`.
+    /// There is one link for each `rel`, so changing the `href` updates the same link.
+    pub fn link(self, rel: impl ToString, href: impl ToString) -> Self {
+        let (rel, href) = (rel.to_string(), href.to_string());
+        self.push(HeadTag {
+            key: format!("link:{}", rel),
+            tag: "link",
+            attrs: vec![("rel", rel), ("href", href)],
+        })
+    }
+
+    /// add a `<link rel="alternate" hreflang="{hreflang}" href="{href}">`
+    /// for the translation of the page in the language `hreflang`, ie: `en` or `x-default`
+    pub fn alternate(
+        self,
+        hreflang: impl ToString,
+        href: impl ToString,
+    ) -> Self {
+        let hreflang = hreflang.to_string();
+        self.push(HeadTag {
+            key: format!("link:alternate:{}", hreflang),
+            tag: "link",
+            attrs: vec![
+                ("rel", "alternate".to_string()),
+                ("hreflang", hreflang),
+                ("href", href.to_string()),
+            ],
+        })
+    }
+
+    /// whether there is no title and no tags
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.tags.is_empty()
+    }
+
+    /// the title and the tags as nodes, so the server can put them into the `<head>`
+    /// of the page, ie: `{for node in app.head().nodes() { node }}`
+    pub fn nodes<MSG>(&self) -> Vec<Node<MSG>> {
+        let title = self
+            .title
+            .iter()
+            .map(|title| html_element("title", vec![], vec![text(title)]));
+        let tags = self.tags.iter().map(|tag| {
+            let attrs = tag
+                .attrs
+                .iter()
+                .map(|(name, value)| attr(*name, value.clone()))
+                .chain(std::iter::once(attr(HEAD_ATTR, tag.key.clone())));
+            html_element_self_closing(tag.tag, attrs, vec![], true)
+        });
+        title.chain(tags).collect()
+    }
+
+    fn push(mut self, tag: HeadTag) -> Self {
+        match self
+            .tags
+            .iter_mut()
+            .find(|existing| existing.key == tag.key)
+        {
+            Some(existing) => *existing = tag,
+            None => self.tags.push(tag),
+        }
+        self
+    }
+}
+
+impl Render for Head {
+    fn render_with_indent(
+        &self,
+        buffer: &mut dyn fmt::Write,
+        indent: usize,
+        compressed: bool,
+    ) -> fmt::Result {
+        let title = self.title.iter().map(|title| {
+            format!("<title>{}</title>", escape_attribute_value(title))
+        });
+        let tags = self.tags.iter().map(|tag| {
+            let attrs: String = tag
+                .attrs
+                .iter()
+                .map(|(name, value)| {
+                    format!(" {}=\"{}\"", name, escape_attribute_value(value))
+                })
+                .collect();
+            format!(
+                "<{}{} {}=\"{}\"/>",
+                tag.tag,
+                attrs,
+                HEAD_ATTR,
+                escape_attribute_value(&tag.key)
+            )
+        });
+        for (i, rendered) in title.chain(tags).enumerate() {
+            if i > 0 && !compressed {
+                write!(buffer, "\n{}", "    ".repeat(indent))?;
+            }
+            write!(buffer, "{}", rendered)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+//! provides the scoping of the css of an app, so the style of multiple apps in the same page
+//! don't collide with each other.
+//!
+//! The selectors of the css are prefixed with the scope class, which is added into the root
+//! element of the app. Use `:host` to select the root element itself.
+//!
+//! # Examples
+//! ```rust
+//! use sauron::html::scoped_style::scope_style;
+//!
+//! let css = ":host { display: flex; } .btn, a:hover { color: red; }";
+//! assert_eq!(
+//!     scope_style("app", css),
+//!     ".app { display: flex; }\n.app .btn, .app a:hover { color: red; }"
+//! );
+//! ```
+use std::{
+    any::TypeId,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+/// the at-rules which contains rules that are scoped,
+/// the other at-rules such as `@keyframes` and `@font-face` are kept as is
+const NESTING_AT_RULES: [&str; 4] =
+    ["@media", "@supports", "@container", "@layer"];
+
+/// the scope class derived from the hash of the type id, ie: `sauron-1f2e3d4c5b6a7980`
+pub fn scope_class(type_id: TypeId) -> String {
+    let mut hasher = DefaultHasher::new();
+    type_id.hash(&mut hasher);
+    format!("sauron-{:016x}", hasher.finish())
+}
+
+/// prefix the selectors of the rules in the `css` with the `scope_class`
+pub fn scope_style(scope_class: &str, css: &str) -> String {
+    let mut rules = vec![];
+    let css = strip_comments(css);
+    let mut remaining = css.as_str();
+    while let Some(open) = remaining.find('{') {
+        let prelude = remaining[..open].trim();
+        let close = match matching_brace(&remaining[open..]) {
+            Some(close) => open + close,
+            None => break,
+        };
+        let body = &remaining[open + 1..close];
+        if prelude.starts_with('@') {
+            let is_nesting = NESTING_AT_RULES
+                .iter()
+                .any(|at_rule| prelude.starts_with(at_rule));
+            if is_nesting {
+                rules.push(format!(
+                    "{} {{\n{}\n}}",
+                    prelude,
+                    scope_style(scope_class, body)
+                ));
+            } else {
+                rules.push(format!("{} {{{}}}", prelude, body));
+            }
+        } else {
+            rules.push(format!(
+                "{} {{{}}}",
+                scope_selectors(scope_class, prelude),
+                body
+            ));
+        }
+        remaining = &remaining[close + 1..];
+    }
+    // the statements without a block, ie: `@import`, are kept as is
+    let trailing = remaining.trim();
+    if !trailing.is_empty() {
+        rules.push(trailing.to_string());
+    }
+    rules.join("\n")
+}
+
+/// prefix each of the selectors in the comma separated `selectors`
+fn scope_selectors(scope_class: &str, selectors: &str) -> String {
+    split_top_level(selectors)
+        .iter()
+        .map(|selector| {
+            let selector = selector.trim();
+            if selector.contains(":host") {
+                replace_host(scope_class, selector)
+            } else {
+                format!(".{} {}", scope_class, selector)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// replace the `:host` with the scope class, and `:host(.dark)` with the scope class
+/// compounded with the selector in the parenthesis, ie: `.scope.dark`
+fn replace_host(scope_class: &str, selector: &str) -> String {
+    let mut replaced = String::new();
+    let mut rest = selector;
+    while let Some(start) = rest.find(":host") {
+        replaced.push_str(&rest[..start]);
+        replaced.push('.');
+        replaced.push_str(scope_class);
+        rest = &rest[start + ":host".len()..];
+        if rest.starts_with('(') {
+            if let Some(end) = rest.find(')') {
+                replaced.push_str(rest[1..end].trim());
+                rest = &rest[end + 1..];
+            }
+        }
+    }
+    replaced.push_str(rest);
+    replaced
+}
+
+/// split the selectors at the commas which are not inside the parenthesis, ie: `:is(a, b)`
+fn split_top_level(selectors: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, ch) in selectors.char_indices() {
+        match ch {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&selectors[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    parts.push(&selectors[start..]);
+    parts
+}
+
+/// the index of the brace which closes the block, the `block` starts with the opening brace
+fn matching_brace(block: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, ch) in block.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut stripped = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    stripped.push_str(rest);
+    stripped
+}
@@ -0,0 +1,209 @@
+//! provides placeholders which are shown while the content is still loading,
+//! such as lines of text, avatars and cards with a shimmering animation.
+//!
+//! The style of the skeletons is injected into the document once when the first skeleton is created.
+//! For server side rendering, include the [`STYLE`] in the page.
+use crate::{
+    html::{
+        attributes::{attr, class, style, styles},
+        div,
+    },
+    Node,
+};
+
+/// the class of all the skeletons, the style is injected when an element with this class is created
+#[cfg(feature = "with-dom")]
+pub(crate) const CLASS: &str = "sauron-skeleton";
+
+/// the id of the style element of the skeletons injected into the document
+pub const STYLE_ID: &str = "sauron-skeleton-style";
+
+/// the css of the skeletons
+pub const STYLE: &str = r#"
+.sauron-skeleton {
+    background: linear-gradient(90deg, #e8e8e8 25%, #f5f5f5 50%, #e8e8e8 75%);
+    background-size: 200% 100%;
+    animation: sauron-skeleton-shimmer 1.5s ease-in-out infinite;
+    border-radius: 4px;
+}
+.sauron-skeleton-line {
+    height: 0.8em;
+    margin: 0.6em 0;
+}
+.sauron-skeleton-line:last-child:not(:first-child) {
+    width: 60%;
+}
+.sauron-skeleton-avatar {
+    border-radius: 50%;
+    flex-shrink: 0;
+}
+.sauron-skeleton-card {
+    display: flex;
+    flex-direction: column;
+    gap: 0.8em;
+    padding: 1em;
+    border: 1px solid #e8e8e8;
+    border-radius: 8px;
+}
+.sauron-skeleton-card-header {
+    display: flex;
+    align-items: center;
+    gap: 0.8em;
+}
+.sauron-skeleton-card-header > div {
+    flex-grow: 1;
+}
+.sauron-skeleton-card-media {
+    height: 8em;
+}
+@keyframes sauron-skeleton-shimmer {
+    0% { background-position: 200% 0; }
+    100% { background-position: -200% 0; }
+}
+@media (prefers-reduced-motion: reduce) {
+    .sauron-skeleton {
+        animation: none;
+    }
+}
+"#;
+
+/// The state of a task which loads the content of a view
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TaskState<T> {
+    /// the task is still running
+    Pending,
+    /// the task is done with the value
+    Done(T),
+}
+
+impl<T> Default for TaskState<T> {
+    fn default() -> Self {
+        TaskState::Pending
+    }
+}
+
+impl<T> From<Option<T>> for TaskState<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => TaskState::Done(value),
+            None => TaskState::Pending,
+        }
+    }
+}
+
+impl<T> TaskState<T> {
+    /// returns true if the task is still running
+    pub fn is_pending(&self) -> bool {
+        matches!(self, TaskState::Pending)
+    }
+}
+
+/// Shows the `skeleton_view` while the task is pending,
+/// then the view created from the loaded value with `loaded_view_fn`.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+/// use sauron::html::skeleton::{skeleton_lines, with_loading, TaskState};
+///
+/// let names: TaskState<Vec<String>> = TaskState::Pending;
+/// let view: Node<()> = with_loading(&names, skeleton_lines(3), |names| {
+///     ul(vec![], names.iter().map(|name| li(vec![], vec![text(name)])))
+/// });
+/// ```
+pub fn with_loading<T, MSG, F>(
+    task_state: &TaskState<T>,
+    skeleton_view: Node<MSG>,
+    loaded_view_fn: F,
+) -> Node<MSG>
+where
+    F: FnOnce(&T) -> Node<MSG>,
+{
+    match task_state {
+        TaskState::Pending => skeleton_view,
+        TaskState::Done(value) => loaded_view_fn(value),
+    }
+}
+
+/// the placeholder of a block of text with `count` lines,
+/// the last line is shorter like the end of a paragraph
+pub fn skeleton_lines<MSG>(count: usize) -> Node<MSG> {
+    placeholder(
+        "sauron-skeleton-lines",
+        (0..count).map(|_| skeleton_block("sauron-skeleton-line", None)),
+    )
+}
+
+/// the placeholder of a round avatar with the diameter of `size` in pixels
+pub fn skeleton_avatar<MSG>(size: u32) -> Node<MSG> {
+    let size = format!("{}px", size);
+    div(
+        vec![
+            class("sauron-skeleton sauron-skeleton-avatar"),
+            styles([("width", size.clone()), ("height", size)]),
+            attr("aria-hidden", "true"),
+        ],
+        vec![],
+    )
+}
+
+/// the placeholder of a card with an avatar, a title, an image and a few lines of text
+pub fn skeleton_card<MSG>() -> Node<MSG> {
+    placeholder(
+        "sauron-skeleton-card",
+        vec![
+            div(
+                vec![class("sauron-skeleton-card-header")],
+                vec![
+                    skeleton_avatar(40),
+                    skeleton_block("sauron-skeleton-line", Some("50%")),
+                ],
+            ),
+            skeleton_block("sauron-skeleton-card-media", None),
+            skeleton_lines(3),
+        ],
+    )
+}
+
+/// a shimmering block of the `class_name`
+fn skeleton_block<MSG>(class_name: &str, width: Option<&str>) -> Node<MSG> {
+    let mut attrs = vec![
+        class(format!("sauron-skeleton {}", class_name)),
+        attr("aria-hidden", "true"),
+    ];
+    if let Some(width) = width {
+        attrs.push(style("width", width));
+    }
+    div(attrs, vec![])
+}
+
+/// the container of the skeleton, which tells assistive technologies that it is loading
+fn placeholder<MSG>(
+    class_name: &'static str,
+    children: impl IntoIterator<Item = Node<MSG>>,
+) -> Node<MSG> {
+    div(
+        vec![
+            class(class_name),
+            attr("aria-busy", "true"),
+            attr("aria-label", "Loading"),
+        ],
+        children,
+    )
+}
+
+/// inject the style of the skeletons into the document, if it is not injected yet
+#[cfg(feature = "with-dom")]
+pub(crate) fn inject_style() {
+    let document = crate::document();
+    if document.get_element_by_id(STYLE_ID).is_some() {
+        return;
+    }
+    let html_style = document
+        .create_element("style")
+        .expect("must be able to create style element");
+    html_style.set_id(STYLE_ID);
+    html_style.set_text_content(Some(STYLE));
+    let head = document.head().expect("must have a head");
+    head.append_child(&html_style).expect("must append style");
+}
@@ -27,12 +27,24 @@ cfg_if! {if #[cfg(not(feature = "with-dom"))] {
     pub type Event = ();
 }}
 
+#[cfg(feature = "with-proptest")]
+pub mod arbitrary;
+pub mod backend;
+pub mod forms;
+#[cfg(fuzzing)]
+pub mod fuzz;
 #[macro_use]
 pub mod html;
 #[macro_use]
 pub mod svg;
+pub mod qr_code;
+pub mod remote_data;
 mod render;
+pub mod sanitize;
+pub mod validate;
 pub mod vdom;
+#[cfg(feature = "with-dom")]
+pub mod widgets;
 
 pub use render::Render;
 
@@ -0,0 +1,744 @@
+//! A QR code generator which renders the QR code as an svg node.
+//!
+//! The text is encoded in byte mode using the smallest version which fits the text
+//! at the chosen error correction level.
+//! The generated QR code is memoized by content, so re-rendering the same text
+//! in every view doesn't encode it again.
+use crate::{
+    html::attributes::{attr, height, width},
+    svg::{
+        attributes::{d, fill, shape_rendering, viewBox},
+        tags::commons::{path, rect, svg},
+    },
+    Attribute, Node,
+};
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
+use thiserror::Error;
+
+/// the memoized QR codes are cleared when there are more than this number of them
+const MAX_MEMOIZED: usize = 32;
+
+/// the number of light modules around the QR code
+const QUIET_ZONE: usize = 4;
+
+thread_local!(static MEMOIZED: RefCell<HashMap<(String, EcLevel), Rc<QrCode>>> = RefCell::new(HashMap::new()));
+
+/// The error correction level, the higher the level the more damage the QR code
+/// can sustain while still being readable, at the cost of a bigger QR code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EcLevel {
+    /// recovers about 7% of the data
+    Low,
+    /// recovers about 15% of the data
+    Medium,
+    /// recovers about 25% of the data
+    Quartile,
+    /// recovers about 30% of the data
+    High,
+}
+
+impl Default for EcLevel {
+    fn default() -> Self {
+        EcLevel::Medium
+    }
+}
+
+impl EcLevel {
+    fn index(self) -> usize {
+        match self {
+            EcLevel::Low => 0,
+            EcLevel::Medium => 1,
+            EcLevel::Quartile => 2,
+            EcLevel::High => 3,
+        }
+    }
+
+    /// the bits of this level as encoded in the format information
+    fn format_bits(self) -> u32 {
+        match self {
+            EcLevel::Low => 1,
+            EcLevel::Medium => 0,
+            EcLevel::Quartile => 3,
+            EcLevel::High => 2,
+        }
+    }
+}
+
+/// Errors when encoding the QR code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum QrCodeError {
+    /// the text is too long to fit in the biggest QR code
+    #[error("the data of {0} bytes is too long for a QR code")]
+    DataTooLong(usize),
+}
+
+/// the number of error correction codewords per block, indexed by level then version
+const ECC_CODEWORDS_PER_BLOCK: [[u8; 41]; 4] = [
+    [
+        0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28,
+        30, 28, 28, 28, 28, 30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+        30, 30, 30, 30, 30,
+    ],
+    [
+        0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28,
+        26, 26, 26, 26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+        28, 28, 28, 28, 28,
+    ],
+    [
+        0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28,
+        28, 26, 30, 28, 30, 30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+        30, 30, 30, 30, 30,
+    ],
+    [
+        0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28,
+        28, 26, 28, 30, 24, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+        30, 30, 30, 30, 30,
+    ],
+];
+
+/// the number of error correction blocks, indexed by level then version
+const NUM_ERROR_CORRECTION_BLOCKS: [[u8; 41]; 4] = [
+    [
+        0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9,
+        10, 12, 12, 12, 13, 14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25,
+    ],
+    [
+        0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16,
+        17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45,
+        47, 49,
+    ],
+    [
+        0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20,
+        23, 23, 25, 27, 29, 34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62,
+        65, 68,
+    ],
+    [
+        0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25,
+        25, 25, 34, 30, 32, 35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70,
+        74, 77, 81,
+    ],
+];
+
+/// A QR code, which is a square grid of dark and light modules
+#[derive(Clone, PartialEq, Eq)]
+pub struct QrCode {
+    version: usize,
+    size: usize,
+    /// the dark modules, indexed by `y * size + x`
+    modules: Vec<bool>,
+    /// the modules which are part of the function patterns, which are not masked
+    is_function: Vec<bool>,
+}
+
+impl fmt::Debug for QrCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                write!(f, "{}", if self.get(x, y) { "##" } else { "  " })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl QrCode {
+    /// encode the text into a QR code of the smallest version which fits the text
+    pub fn encode(text: &str, ec_level: EcLevel) -> Result<Self, QrCodeError> {
+        let data = text.as_bytes();
+        let version = (1..=40)
+            .find(|&version| {
+                let char_count_bits = if version <= 9 { 8 } else { 16 };
+                4 + char_count_bits + data.len() * 8
+                    <= num_data_codewords(version, ec_level) * 8
+            })
+            .ok_or(QrCodeError::DataTooLong(data.len()))?;
+
+        let mut bits = BitBuffer::default();
+        // byte mode
+        bits.append(0b0100, 4);
+        bits.append(data.len() as u32, if version <= 9 { 8 } else { 16 });
+        for byte in data {
+            bits.append(u32::from(*byte), 8);
+        }
+        let capacity = num_data_codewords(version, ec_level) * 8;
+        // terminator and padding to the byte boundary
+        let terminator = (capacity - bits.len()).min(4);
+        bits.append(0, terminator);
+        let padding = (8 - bits.len() % 8) % 8;
+        bits.append(0, padding);
+        for pad in [0xEC, 0x11].iter().cycle() {
+            if bits.len() >= capacity {
+                break;
+            }
+            bits.append(*pad, 8);
+        }
+        let codewords =
+            add_ecc_and_interleave(&bits.to_bytes(), version, ec_level);
+
+        let size = version * 4 + 17;
+        let mut qr_code = QrCode {
+            version,
+            size,
+            modules: vec![false; size * size],
+            is_function: vec![false; size * size],
+        };
+        qr_code.draw_function_patterns();
+        qr_code.draw_codewords(&codewords);
+
+        let best_mask = (0..8)
+            .min_by_key(|&mask| {
+                qr_code.apply_mask(mask);
+                qr_code.draw_format_bits(ec_level, mask);
+                let penalty = qr_code.penalty_score();
+                // masking is its own inverse
+                qr_code.apply_mask(mask);
+                penalty
+            })
+            .expect("must have a mask");
+        qr_code.apply_mask(best_mask);
+        qr_code.draw_format_bits(ec_level, best_mask);
+        Ok(qr_code)
+    }
+
+    /// the version of the QR code, from 1 to 40
+    pub fn version(&self) -> usize {
+        self.version
+    }
+
+    /// the number of modules on each side of the QR code
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// returns true if the module at column `x` and row `y` is dark
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.size && y < self.size && self.modules[y * self.size + x]
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, is_dark: bool) {
+        self.modules[y * self.size + x] = is_dark;
+        self.is_function[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self) {
+        let size = self.size;
+        // timing patterns
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+        // finder patterns, including the separators
+        for &(x, y) in [(3, 3), (size - 4, 3), (3, size - 4)].iter() {
+            for dy in -4isize..=4 {
+                for dx in -4isize..=4 {
+                    let distance = dx.abs().max(dy.abs());
+                    let xx = x as isize + dx;
+                    let yy = y as isize + dy;
+                    if xx >= 0
+                        && xx < size as isize
+                        && yy >= 0
+                        && yy < size as isize
+                    {
+                        self.set_function(
+                            xx as usize,
+                            yy as usize,
+                            distance != 2 && distance != 4,
+                        );
+                    }
+                }
+            }
+        }
+        // alignment patterns, except on the corners occupied by the finder patterns
+        let positions = alignment_pattern_positions(self.version);
+        let last = positions.len().saturating_sub(1);
+        for (i, &y) in positions.iter().enumerate() {
+            for (j, &x) in positions.iter().enumerate() {
+                if (i == 0 && j == 0)
+                    || (i == 0 && j == last)
+                    || (i == last && j == 0)
+                {
+                    continue;
+                }
+                for dy in -2isize..=2 {
+                    for dx in -2isize..=2 {
+                        self.set_function(
+                            (x as isize + dx) as usize,
+                            (y as isize + dy) as usize,
+                            dx.abs().max(dy.abs()) != 1,
+                        );
+                    }
+                }
+            }
+        }
+        // reserve the format bits, these are drawn after the mask is chosen
+        self.draw_format_bits(EcLevel::Low, 0);
+        self.draw_version_bits();
+    }
+
+    fn draw_format_bits(&mut self, ec_level: EcLevel, mask: u32) {
+        let data = ec_level.format_bits() << 3 | mask;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = (data << 10 | remainder) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+
+        // around the top left finder pattern
+        for i in 0..=5 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+        // the copy around the top right and bottom left finder patterns
+        let size = self.size;
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        // the module which is always dark
+        self.set_function(8, size - 8, true);
+    }
+
+    fn draw_version_bits(&mut self) {
+        if self.version < 7 {
+            return;
+        }
+        let version = self.version as u32;
+        let mut remainder = version;
+        for _ in 0..12 {
+            remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+        }
+        let bits = version << 12 | remainder;
+        for i in 0..18 {
+            let is_dark = (bits >> i) & 1 != 0;
+            let a = self.size - 11 + i % 3;
+            let b = i / 3;
+            self.set_function(a, b, is_dark);
+            self.set_function(b, a, is_dark);
+        }
+    }
+
+    /// draw the codewords in the zigzag order, skipping the function patterns
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size as isize;
+        let total_bits = codewords.len() * 8;
+        let mut i = 0;
+        let mut right = size - 1;
+        while right >= 1 {
+            // skip the vertical timing pattern
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..size {
+                for j in 0..2 {
+                    let x = (right - j) as usize;
+                    let is_upward = (right + 1) & 2 == 0;
+                    let y = if is_upward {
+                        size - 1 - vertical
+                    } else {
+                        vertical
+                    } as usize;
+                    let index = y * self.size + x;
+                    if !self.is_function[index] && i < total_bits {
+                        self.modules[index] =
+                            (codewords[i >> 3] >> (7 - (i & 7))) & 1 != 0;
+                        i += 1;
+                    }
+                }
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                let index = y * self.size + x;
+                if invert && !self.is_function[index] {
+                    self.modules[index] = !self.modules[index];
+                }
+            }
+        }
+    }
+
+    /// the penalty score of the QR code, the mask with the lowest score is used
+    fn penalty_score(&self) -> usize {
+        let size = self.size;
+        let mut score = 0;
+        let rows = (0..size)
+            .map(|y| (0..size).map(|x| self.get(x, y)).collect::<Vec<_>>());
+        let columns = (0..size)
+            .map(|x| (0..size).map(|y| self.get(x, y)).collect::<Vec<_>>());
+        let finder_like = [
+            [
+                true, false, true, true, true, false, true, false, false,
+                false, false,
+            ],
+            [
+                false, false, false, false, true, false, true, true, true,
+                false, true,
+            ],
+        ];
+        for line in rows.chain(columns) {
+            // runs of the same color
+            let mut run = 1;
+            for i in 1..size {
+                if line[i] == line[i - 1] {
+                    run += 1;
+                } else {
+                    if run >= 5 {
+                        score += run - 2;
+                    }
+                    run = 1;
+                }
+            }
+            if run >= 5 {
+                score += run - 2;
+            }
+            // patterns which looks like the finder pattern
+            score += line
+                .windows(11)
+                .filter(|window| {
+                    finder_like.iter().any(|pattern| window == pattern)
+                })
+                .count()
+                * 40;
+        }
+        // 2x2 blocks of the same color
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let color = self.get(x, y);
+                if color == self.get(x + 1, y)
+                    && color == self.get(x, y + 1)
+                    && color == self.get(x + 1, y + 1)
+                {
+                    score += 3;
+                }
+            }
+        }
+        // the balance of dark and light modules
+        let total = (size * size) as isize;
+        let dark =
+            self.modules.iter().filter(|&&is_dark| is_dark).count() as isize;
+        let k = ((dark * 20 - total * 10).abs() + total - 1) / total - 1;
+        score + k as usize * 10
+    }
+
+    /// the svg path of the dark modules, offset by the quiet zone
+    fn to_svg_path(&self) -> String {
+        let mut path = String::new();
+        for y in 0..self.size {
+            for x in 0..self.size {
+                if self.get(x, y) {
+                    path.push_str(&format!(
+                        "M{},{}h1v1h-1z",
+                        x + QUIET_ZONE,
+                        y + QUIET_ZONE
+                    ));
+                }
+            }
+        }
+        path
+    }
+}
+
+/// the number of modules which are used for the codewords
+fn num_raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let num_align = version / 7 + 2;
+        result -= (25 * num_align - 10) * num_align - 55;
+        if version >= 7 {
+            // the version information
+            result -= 36;
+        }
+    }
+    result
+}
+
+/// the number of codewords for the data, excluding the error correction codewords
+fn num_data_codewords(version: usize, ec_level: EcLevel) -> usize {
+    let level = ec_level.index();
+    num_raw_data_modules(version) / 8
+        - usize::from(ECC_CODEWORDS_PER_BLOCK[level][version])
+            * usize::from(NUM_ERROR_CORRECTION_BLOCKS[level][version])
+}
+
+/// the center positions of the alignment patterns, for both the x and y axis
+fn alignment_pattern_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return vec![];
+    }
+    let size = version * 4 + 17;
+    let num_align = version / 7 + 2;
+    let step = (version * 8 + num_align * 3 + 5) / (num_align * 4 - 4) * 2;
+    let mut result = vec![6];
+    for i in 0..num_align - 1 {
+        result.insert(1, size - 7 - i * step);
+    }
+    result
+}
+
+/// split the data into blocks, append the error correction codewords of each block
+/// then interleave the blocks
+fn add_ecc_and_interleave(
+    data: &[u8],
+    version: usize,
+    ec_level: EcLevel,
+) -> Vec<u8> {
+    let level = ec_level.index();
+    let num_blocks = usize::from(NUM_ERROR_CORRECTION_BLOCKS[level][version]);
+    let block_ecc_len = usize::from(ECC_CODEWORDS_PER_BLOCK[level][version]);
+    let raw_codewords = num_raw_data_modules(version) / 8;
+    let num_short_blocks = num_blocks - raw_codewords % num_blocks;
+    let short_block_len = raw_codewords / num_blocks;
+
+    let divisor = reed_solomon_divisor(block_ecc_len);
+    let mut blocks = Vec::with_capacity(num_blocks);
+    let mut k = 0;
+    for i in 0..num_blocks {
+        let data_len = short_block_len - block_ecc_len
+            + if i < num_short_blocks { 0 } else { 1 };
+        let mut block = data[k..k + data_len].to_vec();
+        k += data_len;
+        let ecc = reed_solomon_remainder(&block, &divisor);
+        if i < num_short_blocks {
+            // padding, so all the blocks has the same length, this is skipped in interleaving
+            block.push(0);
+        }
+        block.extend(ecc);
+        blocks.push(block);
+    }
+
+    let mut result = Vec::with_capacity(raw_codewords);
+    for i in 0..blocks[0].len() {
+        for (j, block) in blocks.iter().enumerate() {
+            if i != short_block_len - block_ecc_len || j >= num_short_blocks {
+                result.push(block[i]);
+            }
+        }
+    }
+    result
+}
+
+/// the generator polynomial of the Reed-Solomon code of the `degree`
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0; degree];
+    result[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+/// the error correction codewords of the data
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0; divisor.len()];
+    for byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (r, d) in result.iter_mut().zip(divisor) {
+            *r ^= gf_multiply(*d, factor);
+        }
+    }
+    result
+}
+
+/// multiplication in the galois field GF(2^8/0x11D)
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u32 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= ((u32::from(y) >> i) & 1) * u32::from(x);
+    }
+    z as u8
+}
+
+#[derive(Default)]
+struct BitBuffer {
+    bits: Vec<bool>,
+}
+
+impl BitBuffer {
+    fn append(&mut self, value: u32, len: usize) {
+        for i in (0..len).rev() {
+            self.bits.push((value >> i) & 1 != 0);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.bits
+            .chunks(8)
+            .map(|chunk| {
+                chunk.iter().fold(0, |byte, &bit| byte << 1 | u8::from(bit))
+            })
+            .collect()
+    }
+}
+
+/// Creates an svg node of the QR code which encodes the `text`.
+/// The QR code is memoized by the text and the error correction level.
+///
+/// The dark modules are drawn with the `currentColor`, on a white background.
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+/// use sauron::qr_code::{qr_code, EcLevel};
+///
+/// let code: Node<()> = qr_code("https://example.com", EcLevel::Medium, vec![]).unwrap();
+/// ```
+pub fn qr_code<MSG>(
+    text: &str,
+    ec_level: EcLevel,
+    attrs: impl IntoIterator<Item = Attribute<MSG>>,
+) -> Result<Node<MSG>, QrCodeError> {
+    let qr_code = memoized(text, ec_level)?;
+    let dimension = qr_code.size() + QUIET_ZONE * 2;
+    Ok(svg(
+        vec![
+            viewBox([0, 0, dimension, dimension]),
+            shape_rendering("crispEdges"),
+            attr("role", "img"),
+            attr("aria-label", text.to_string()),
+        ]
+        .into_iter()
+        .chain(attrs),
+        vec![
+            rect(vec![width("100%"), height("100%"), fill("white")], vec![]),
+            path(vec![d(qr_code.to_svg_path()), fill("currentColor")], vec![]),
+        ],
+    ))
+}
+
+/// encode the QR code, or return the previously encoded one
+fn memoized(text: &str, ec_level: EcLevel) -> Result<Rc<QrCode>, QrCodeError> {
+    let key = (text.to_string(), ec_level);
+    if let Some(qr_code) =
+        MEMOIZED.with(|memoized| memoized.borrow().get(&key).cloned())
+    {
+        return Ok(qr_code);
+    }
+    let qr_code = Rc::new(QrCode::encode(text, ec_level)?);
+    MEMOIZED.with(|memoized| {
+        let mut memoized = memoized.borrow_mut();
+        if memoized.len() >= MAX_MEMOIZED {
+            memoized.clear();
+        }
+        memoized.insert(key, Rc::clone(&qr_code));
+    });
+    Ok(qr_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reed_solomon_of_hello_world() {
+        // the data codewords of "HELLO WORLD" version 1-M in alphanumeric mode
+        let data = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236,
+            17,
+        ];
+        let divisor = reed_solomon_divisor(10);
+        assert_eq!(
+            reed_solomon_remainder(&data, &divisor),
+            vec![196, 35, 39, 119, 235, 215, 231, 226, 93, 23]
+        );
+    }
+
+    #[test]
+    fn codewords_of_every_version_fit_the_blocks() {
+        for version in 1..=40 {
+            let raw_codewords = num_raw_data_modules(version) / 8;
+            for ec_level in [
+                EcLevel::Low,
+                EcLevel::Medium,
+                EcLevel::Quartile,
+                EcLevel::High,
+            ]
+            .iter()
+            {
+                let data = vec![0; num_data_codewords(version, *ec_level)];
+                assert_eq!(
+                    add_ecc_and_interleave(&data, version, *ec_level).len(),
+                    raw_codewords
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn smallest_version_is_used() {
+        let qr_code = QrCode::encode("hello", EcLevel::Medium).unwrap();
+        assert_eq!(qr_code.version(), 1);
+        assert_eq!(qr_code.size(), 21);
+        // 17 bytes is the capacity of version 1-L in byte mode
+        assert_eq!(
+            QrCode::encode(&"a".repeat(17), EcLevel::Low)
+                .unwrap()
+                .version(),
+            1
+        );
+        assert_eq!(
+            QrCode::encode(&"a".repeat(18), EcLevel::Low)
+                .unwrap()
+                .version(),
+            2
+        );
+        assert_eq!(
+            QrCode::encode(&"a".repeat(3000), EcLevel::Low),
+            Err(QrCodeError::DataTooLong(3000))
+        );
+    }
+
+    #[test]
+    fn format_bits_of_known_level_and_mask() {
+        let qr_code = QrCode::encode("hello", EcLevel::Low).unwrap();
+        // read back the format bits around the top left finder pattern
+        let mut bits = 0;
+        for i in 0..=5 {
+            bits |= (qr_code.get(8, i) as u32) << i;
+        }
+        bits |= (qr_code.get(8, 7) as u32) << 6;
+        bits |= (qr_code.get(8, 8) as u32) << 7;
+        bits |= (qr_code.get(7, 8) as u32) << 8;
+        for i in 9..15 {
+            bits |= (qr_code.get(14 - i, 8) as u32) << i;
+        }
+        // the format information of level L, for each of the masks
+        let known = [
+            0x77C4, 0x72F3, 0x7DAA, 0x789D, 0x662F, 0x6318, 0x6C41, 0x6976,
+        ];
+        assert!(known.contains(&bits), "{:x}", bits);
+    }
+}
@@ -0,0 +1,141 @@
+//! provides [`RemoteData`] which represents the states of the data fetched asynchronously,
+//! and [`match_remote_data`] so the view handles all of the states exhaustively.
+use crate::Node;
+
+/// The states of the data which is fetched asynchronously, ie: from an http request
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RemoteData<T, E> {
+    /// the data is not requested yet
+    NotAsked,
+    /// the data is being requested
+    Loading,
+    /// the request failed with the error
+    Failure(E),
+    /// the request succeeded with the data
+    Success(T),
+}
+
+impl<T, E> Default for RemoteData<T, E> {
+    fn default() -> Self {
+        RemoteData::NotAsked
+    }
+}
+
+impl<T, E> From<Result<T, E>> for RemoteData<T, E> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(data) => RemoteData::Success(data),
+            Err(e) => RemoteData::Failure(e),
+        }
+    }
+}
+
+impl<T, E> RemoteData<T, E> {
+    /// returns true if the data is being requested
+    pub fn is_loading(&self) -> bool {
+        matches!(self, RemoteData::Loading)
+    }
+
+    /// the data, if the request succeeded
+    pub fn success(&self) -> Option<&T> {
+        match self {
+            RemoteData::Success(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// the error, if the request failed
+    pub fn failure(&self) -> Option<&E> {
+        match self {
+            RemoteData::Failure(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// converts from `&RemoteData<T, E>` to `RemoteData<&T, &E>`
+    pub fn as_ref(&self) -> RemoteData<&T, &E> {
+        match self {
+            RemoteData::NotAsked => RemoteData::NotAsked,
+            RemoteData::Loading => RemoteData::Loading,
+            RemoteData::Failure(e) => RemoteData::Failure(e),
+            RemoteData::Success(data) => RemoteData::Success(data),
+        }
+    }
+
+    /// map the data of the successful request
+    pub fn map<U, F>(self, f: F) -> RemoteData<U, E>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            RemoteData::NotAsked => RemoteData::NotAsked,
+            RemoteData::Loading => RemoteData::Loading,
+            RemoteData::Failure(e) => RemoteData::Failure(e),
+            RemoteData::Success(data) => RemoteData::Success(f(data)),
+        }
+    }
+
+    /// map the error of the failed request
+    pub fn map_err<F2, F>(self, f: F) -> RemoteData<T, F2>
+    where
+        F: FnOnce(E) -> F2,
+    {
+        match self {
+            RemoteData::NotAsked => RemoteData::NotAsked,
+            RemoteData::Loading => RemoteData::Loading,
+            RemoteData::Failure(e) => RemoteData::Failure(f(e)),
+            RemoteData::Success(data) => RemoteData::Success(data),
+        }
+    }
+}
+
+/// The views for each of the states of the [`RemoteData`], used in [`match_remote_data`]
+#[derive(Clone, Copy, Debug)]
+pub struct RemoteDataViews<NA, L, F, S> {
+    /// the view when the data is not requested yet
+    pub not_asked: NA,
+    /// the view while the data is being requested, ie: a skeleton
+    pub loading: L,
+    /// the view of the error when the request failed
+    pub failure: F,
+    /// the view of the data when the request succeeded
+    pub success: S,
+}
+
+/// Creates the view of the current state of the `remote_data`,
+/// all of the states has to be handled in the `views`.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+/// use sauron::html::skeleton::skeleton_lines;
+/// use sauron::remote_data::{match_remote_data, RemoteData, RemoteDataViews};
+///
+/// let users: RemoteData<Vec<String>, String> = RemoteData::Loading;
+/// let view: Node<()> = match_remote_data(
+///     &users,
+///     RemoteDataViews {
+///         not_asked: || button(vec![], vec![text("Load users")]),
+///         loading: || skeleton_lines(3),
+///         failure: |e| p(vec![class("error")], vec![text(e)]),
+///         success: |users| ul(vec![], users.iter().map(|user| li(vec![], vec![text(user)]))),
+///     },
+/// );
+/// ```
+pub fn match_remote_data<T, E, MSG, NA, L, F, S>(
+    remote_data: &RemoteData<T, E>,
+    views: RemoteDataViews<NA, L, F, S>,
+) -> Node<MSG>
+where
+    NA: FnOnce() -> Node<MSG>,
+    L: FnOnce() -> Node<MSG>,
+    F: FnOnce(&E) -> Node<MSG>,
+    S: FnOnce(&T) -> Node<MSG>,
+{
+    match remote_data {
+        RemoteData::NotAsked => (views.not_asked)(),
+        RemoteData::Loading => (views.loading)(),
+        RemoteData::Failure(e) => (views.failure)(e),
+        RemoteData::Success(data) => (views.success)(data),
+    }
+}
@@ -54,6 +54,50 @@ pub trait Render {
         self.render(&mut buffer).expect("must render");
         buffer
     }
+
+    /// render compressed html to string suitable for printing, where the interactive elements
+    /// such as buttons, form controls and navigations are left out.
+    /// Elements with the `data-no-print` attribute are also left out.
+    fn render_for_print(&self) -> String {
+        self.render_to_string()
+    }
+}
+
+/// the interactive elements which are left out when rendering for print
+const INTERACTIVE_TAGS: [&str; 8] = [
+    "button", "input", "select", "textarea", "nav", "dialog", "script",
+    "noscript",
+];
+
+/// elements with this attribute are left out when rendering for print
+const NO_PRINT: &str = "data-no-print";
+
+/// create a copy of the node without the interactive elements
+fn printable_node<MSG>(node: &Node<MSG>) -> Option<Node<MSG>> {
+    match node {
+        Node::Element(element) => {
+            let is_interactive = INTERACTIVE_TAGS.contains(element.tag());
+            let is_no_print = element
+                .get_attributes()
+                .iter()
+                .any(|att| *att.name() == NO_PRINT);
+            if is_interactive || is_no_print {
+                return None;
+            }
+            Some(Node::Element(Element {
+                namespace: element.namespace,
+                tag: element.tag,
+                attrs: element.attrs.clone(),
+                children: element
+                    .children
+                    .iter()
+                    .filter_map(printable_node)
+                    .collect(),
+                self_closing: element.self_closing,
+            }))
+        }
+        Node::Text(_) | Node::Comment(_) => Some(node.clone()),
+    }
 }
 
 impl<MSG> Render for Node<MSG> {
@@ -75,13 +119,29 @@ impl<MSG> Render for Node<MSG> {
             }
         }
     }
+
+    fn render_for_print(&self) -> String {
+        printable_node(self)
+            .map(|node| node.render_to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// properties with a false value such as `prop_checked(false)` are not rendered,
+/// since the presence of the attribute alone means it is true
+fn is_false_property<MSG>(attr: &Attribute<MSG>) -> bool {
+    attr.value().iter().all(|v| {
+        v.get_property()
+            .map(|v| v.as_bool() == Some(false))
+            .unwrap_or(false)
+    })
 }
 
 fn extract_inner_html<MSG>(merged_attributes: &[Attribute<MSG>]) -> String {
     merged_attributes
         .iter()
         .flat_map(|attr| {
-            let SegregatedAttributes{listeners:_, plain_values:_, styles:_, function_calls} =
+            let SegregatedAttributes{listeners:_, plain_values:_, styles:_, function_calls, properties:_} =
                 attributes::partition_callbacks_from_plain_styles_and_func_calls(
                     attr,
                 );
@@ -113,7 +173,15 @@ impl<MSG> Render for Element<MSG> {
         for attr in &merged_attributes {
             // dont render empty attribute
             // TODO: must check the attribute value for empty value
-            if !attr.name().is_empty() {
+            //
+            // custom values are not rendered, since they are not meant to be
+            // in the actual DOM
+            let is_custom_only = !attr.value().is_empty()
+                && attr.value().iter().all(|v| v.is_custom());
+            if !attr.name().is_empty()
+                && !is_custom_only
+                && !is_false_property(attr)
+            {
                 write!(buffer, " ")?;
                 attr.render_with_indent(buffer, indent, compressed)?;
             }
@@ -177,12 +245,18 @@ impl<MSG> Render for Attribute<MSG> {
             plain_values,
             styles,
             function_calls: _,
+            properties,
         } = attributes::partition_callbacks_from_plain_styles_and_func_calls(
             self,
         );
         if let Some(merged_plain_values) =
             attributes::merge_plain_attributes_values(&plain_values)
         {
+            let merged_plain_values = if *self.name() == "class" {
+                attributes::transform_classes(&merged_plain_values)
+            } else {
+                merged_plain_values
+            };
             write!(buffer, "{}=\"{}\"", self.name(), merged_plain_values)?;
         }
         if let Some(merged_styles) =
@@ -190,6 +264,15 @@ impl<MSG> Render for Attribute<MSG> {
         {
             write!(buffer, "{}=\"{}\"", self.name(), merged_styles)?;
         }
+        // properties are rendered as attributes, so the server side rendered page
+        // has the initial state of the form controls
+        for property in properties.iter().filter_map(|v| v.get_property()) {
+            match property.as_bool() {
+                Some(true) => write!(buffer, "{}", self.name())?,
+                Some(false) => (),
+                None => write!(buffer, "{}=\"{}\"", self.name(), property)?,
+            }
+        }
         Ok(())
     }
 }
@@ -199,6 +282,24 @@ mod test {
     use super::*;
     use crate::prelude::*;
 
+    #[test]
+    fn test_render_for_print() {
+        let view: Node<()> = div(
+            vec![],
+            vec![
+                nav(vec![], vec![text("menu")]),
+                h1(vec![], vec![text("Report")]),
+                div(vec![attr("data-no-print", "")], vec![text("ad")]),
+                p(vec![], vec![text("content"), button(vec![], vec![])]),
+            ],
+        );
+
+        assert_eq!(
+            view.render_for_print(),
+            "<div><h1>Report</h1><p>content</p></div>"
+        );
+    }
+
     #[test]
     fn test_render_comments() {
         let view: Node<()> =
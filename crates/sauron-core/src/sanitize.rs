@@ -0,0 +1,835 @@
+//! A whitelist based html sanitizer.
+//!
+//! This is used for displaying user generated content, both in server side rendering
+//! and in the browser. Only the tags, attributes and url schemes allowed in the [`Policy`]
+//! are kept, everything else is removed.
+//!
+//! # Examples
+//! ```rust
+//! use sauron::sanitize::{clean, Policy};
+//!
+//! let dirty = r#"<p onclick="steal()">Hello <b>world</b><script>alert(1)</script></p>"#;
+//! assert_eq!(clean(dirty, &Policy::default()), "<p>Hello <b>world</b></p>");
+//! ```
+use crate::{html::safe_html, Node};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// Elements which don't have a closing tag
+pub(crate) const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
+    "param", "source", "track", "wbr",
+];
+
+/// Elements in which the content is not parsed as html, but as text.
+const RAW_TEXT_ELEMENTS: [&str; 10] = [
+    "script",
+    "style",
+    "textarea",
+    "title",
+    "xmp",
+    "iframe",
+    "noembed",
+    "noframes",
+    "noscript",
+    "plaintext",
+];
+
+/// Attributes which contains url, these are checked against the allowed url schemes
+const URL_ATTRIBUTES: [&str; 9] = [
+    "href",
+    "src",
+    "cite",
+    "action",
+    "formaction",
+    "background",
+    "poster",
+    "longdesc",
+    "xlink:href",
+];
+
+/// The policy which determines which tags, attributes and url schemes are allowed
+/// when cleaning the html.
+///
+/// The default policy mirrors the default of well known sanitizers, such that only
+/// non-interactive formatting tags and links are allowed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Policy {
+    tags: HashSet<String>,
+    clean_content_tags: HashSet<String>,
+    generic_attributes: HashSet<String>,
+    tag_attributes: HashMap<String, HashSet<String>>,
+    url_schemes: HashSet<String>,
+    allow_relative_urls: bool,
+    link_rel: Option<String>,
+    strip_comments: bool,
+}
+
+impl Policy {
+    /// A policy which doesn't allow any tags, only the text content is retained
+    pub fn empty() -> Self {
+        Policy {
+            tags: HashSet::new(),
+            clean_content_tags: to_set(["script", "style"]),
+            generic_attributes: HashSet::new(),
+            tag_attributes: HashMap::new(),
+            url_schemes: HashSet::new(),
+            allow_relative_urls: true,
+            link_rel: None,
+            strip_comments: true,
+        }
+    }
+
+    /// allow these tags
+    pub fn add_tags<'a>(
+        mut self,
+        tags: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        self.tags.extend(tags.into_iter().map(str::to_lowercase));
+        self
+    }
+
+    /// disallow these tags
+    pub fn rm_tags<'a>(
+        mut self,
+        tags: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        for tag in tags {
+            self.tags.remove(&tag.to_lowercase());
+        }
+        self
+    }
+
+    /// the content of these tags are removed together with the tag
+    /// instead of being retained as text
+    pub fn add_clean_content_tags<'a>(
+        mut self,
+        tags: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        self.clean_content_tags
+            .extend(tags.into_iter().map(str::to_lowercase));
+        self
+    }
+
+    /// allow these attributes in all of the allowed tags
+    pub fn add_generic_attributes<'a>(
+        mut self,
+        attributes: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        self.generic_attributes
+            .extend(attributes.into_iter().map(str::to_lowercase));
+        self
+    }
+
+    /// allow these attributes on this specific tag
+    pub fn add_tag_attributes<'a>(
+        mut self,
+        tag: &str,
+        attributes: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        self.tag_attributes
+            .entry(tag.to_lowercase())
+            .or_insert_with(HashSet::new)
+            .extend(attributes.into_iter().map(str::to_lowercase));
+        self
+    }
+
+    /// disallow these attributes on this specific tag
+    pub fn rm_tag_attributes<'a>(
+        mut self,
+        tag: &str,
+        attributes: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        if let Some(tag_attributes) =
+            self.tag_attributes.get_mut(&tag.to_lowercase())
+        {
+            for att in attributes {
+                tag_attributes.remove(&att.to_lowercase());
+            }
+        }
+        self
+    }
+
+    /// allow urls with these schemes, ie: `https`, `mailto`
+    pub fn add_url_schemes<'a>(
+        mut self,
+        schemes: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        self.url_schemes
+            .extend(schemes.into_iter().map(str::to_lowercase));
+        self
+    }
+
+    /// disallow urls with these schemes
+    pub fn rm_url_schemes<'a>(
+        mut self,
+        schemes: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        for scheme in schemes {
+            self.url_schemes.remove(&scheme.to_lowercase());
+        }
+        self
+    }
+
+    /// whether or not urls without scheme are allowed
+    pub fn allow_relative_urls(mut self, allow: bool) -> Self {
+        self.allow_relative_urls = allow;
+        self
+    }
+
+    /// set the `rel` attribute of all the links, replacing the original value.
+    /// `None` to leave the links as is.
+    pub fn link_rel(mut self, rel: Option<&str>) -> Self {
+        self.link_rel = rel.map(ToString::to_string);
+        self
+    }
+
+    /// whether or not to remove html comments
+    pub fn strip_comments(mut self, strip: bool) -> Self {
+        self.strip_comments = strip;
+        self
+    }
+
+    pub(crate) fn is_tag_allowed(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+
+    pub(crate) fn is_attribute_allowed(&self, tag: &str, att: &str) -> bool {
+        self.generic_attributes.contains(att)
+            || self
+                .tag_attributes
+                .get(tag)
+                .map(|attributes| attributes.contains(att))
+                .unwrap_or(false)
+    }
+
+    fn is_url_allowed(&self, url: &str) -> bool {
+        match url_scheme(url) {
+            Some(scheme) => self.url_schemes.contains(&scheme),
+            None => self.allow_relative_urls,
+        }
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy::empty()
+            .add_tags([
+                "a",
+                "abbr",
+                "acronym",
+                "area",
+                "article",
+                "aside",
+                "b",
+                "bdi",
+                "bdo",
+                "blockquote",
+                "br",
+                "caption",
+                "center",
+                "cite",
+                "code",
+                "col",
+                "colgroup",
+                "data",
+                "dd",
+                "del",
+                "details",
+                "dfn",
+                "div",
+                "dl",
+                "dt",
+                "em",
+                "figcaption",
+                "figure",
+                "footer",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "header",
+                "hgroup",
+                "hr",
+                "i",
+                "img",
+                "ins",
+                "kbd",
+                "li",
+                "map",
+                "mark",
+                "nav",
+                "ol",
+                "p",
+                "pre",
+                "q",
+                "rp",
+                "rt",
+                "rtc",
+                "ruby",
+                "s",
+                "samp",
+                "small",
+                "span",
+                "strike",
+                "strong",
+                "sub",
+                "summary",
+                "sup",
+                "table",
+                "tbody",
+                "td",
+                "th",
+                "thead",
+                "time",
+                "tr",
+                "tt",
+                "u",
+                "ul",
+                "var",
+                "wbr",
+            ])
+            .add_generic_attributes(["lang", "title"])
+            .add_tag_attributes("a", ["href", "hreflang"])
+            .add_tag_attributes("bdo", ["dir"])
+            .add_tag_attributes("blockquote", ["cite"])
+            .add_tag_attributes("col", ["align", "char", "charoff", "span"])
+            .add_tag_attributes(
+                "colgroup",
+                ["align", "char", "charoff", "span"],
+            )
+            .add_tag_attributes("del", ["cite", "datetime"])
+            .add_tag_attributes("hr", ["align", "size", "width"])
+            .add_tag_attributes(
+                "img",
+                ["align", "alt", "height", "src", "width"],
+            )
+            .add_tag_attributes("ins", ["cite", "datetime"])
+            .add_tag_attributes("ol", ["start"])
+            .add_tag_attributes("q", ["cite"])
+            .add_tag_attributes(
+                "table",
+                ["align", "char", "charoff", "summary"],
+            )
+            .add_tag_attributes("tbody", ["align", "char", "charoff"])
+            .add_tag_attributes(
+                "td",
+                ["align", "char", "charoff", "colspan", "headers", "rowspan"],
+            )
+            .add_tag_attributes(
+                "th",
+                [
+                    "align", "char", "charoff", "colspan", "headers",
+                    "rowspan", "scope",
+                ],
+            )
+            .add_tag_attributes("thead", ["align", "char", "charoff"])
+            .add_tag_attributes("tr", ["align", "char", "charoff"])
+            .add_url_schemes([
+                "bitcoin",
+                "ftp",
+                "ftps",
+                "geo",
+                "http",
+                "https",
+                "im",
+                "irc",
+                "ircs",
+                "magnet",
+                "mailto",
+                "mms",
+                "mx",
+                "news",
+                "nntp",
+                "openpgp4fpr",
+                "sip",
+                "sms",
+                "smsto",
+                "ssh",
+                "tel",
+                "url",
+                "webcal",
+                "wtai",
+                "xmpp",
+            ])
+            .link_rel(Some("noopener noreferrer"))
+    }
+}
+
+fn to_set<'a>(items: impl IntoIterator<Item = &'a str>) -> HashSet<String> {
+    items.into_iter().map(ToString::to_string).collect()
+}
+
+/// A token of the html input
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    /// text content, as it appears in the input
+    Text(String),
+    /// a start tag with the lowercased tag name and attributes
+    StartTag {
+        /// the lowercased tag name
+        name: String,
+        /// the lowercased attribute name and the raw attribute value
+        attrs: Vec<(String, String)>,
+        /// whether the tag is written as `<tag/>`
+        self_closing: bool,
+    },
+    /// an end tag with the lowercased tag name
+    EndTag(String),
+    /// a comment
+    Comment(String),
+}
+
+/// A forgiving html tokenizer, malformed markup is never an error.
+/// Incomplete tags at the end of the input are discarded.
+pub(crate) fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(lt) = rest.find('<') {
+        text.push_str(&rest[..lt]);
+        let tail = &rest[lt..];
+        if let Some(comment) = tail.strip_prefix("<!--") {
+            flush_text(&mut text, &mut tokens);
+            let (content, remaining) = match comment.find("-->") {
+                Some(end) => (&comment[..end], &comment[end + 3..]),
+                None => (comment, ""),
+            };
+            tokens.push(Token::Comment(content.to_string()));
+            rest = remaining;
+        } else if tail.starts_with("<!") || tail.starts_with("<?") {
+            // doctype and processing instructions are discarded
+            flush_text(&mut text, &mut tokens);
+            rest = match tail.find('>') {
+                Some(end) => &tail[end + 1..],
+                None => "",
+            };
+        } else if tail.starts_with("</")
+            && tail[2..].starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            flush_text(&mut text, &mut tokens);
+            let name_len = tag_name_len(&tail[2..]);
+            let name = tail[2..2 + name_len].to_ascii_lowercase();
+            match tail.find('>') {
+                Some(end) => {
+                    tokens.push(Token::EndTag(name));
+                    rest = &tail[end + 1..];
+                }
+                None => rest = "",
+            }
+        } else if tail[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            flush_text(&mut text, &mut tokens);
+            match parse_start_tag(tail) {
+                Some((token, remaining)) => {
+                    let raw_text_tag = match &token {
+                        Token::StartTag {
+                            name, self_closing, ..
+                        } if !self_closing
+                            && RAW_TEXT_ELEMENTS.contains(&name.as_str()) =>
+                        {
+                            Some(name.clone())
+                        }
+                        _ => None,
+                    };
+                    tokens.push(token);
+                    rest = remaining;
+                    if let Some(raw_text_tag) = raw_text_tag {
+                        let (content, remaining) =
+                            split_raw_text(rest, &raw_text_tag);
+                        if !content.is_empty() {
+                            tokens.push(Token::Text(content.to_string()));
+                        }
+                        rest = remaining;
+                    }
+                }
+                None => rest = "",
+            }
+        } else {
+            // a literal `<`
+            text.push('<');
+            rest = &tail[1..];
+        }
+    }
+    text.push_str(rest);
+    flush_text(&mut text, &mut tokens);
+    tokens
+}
+
+fn flush_text(text: &mut String, tokens: &mut Vec<Token>) {
+    if !text.is_empty() {
+        tokens.push(Token::Text(std::mem::take(text)));
+    }
+}
+
+fn tag_name_len(s: &str) -> usize {
+    s.find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+        .unwrap_or_else(|| s.len())
+}
+
+/// split the content of a raw text element up to its closing tag,
+/// the closing tag is left in the remaining input
+fn split_raw_text<'a>(rest: &'a str, tag: &str) -> (&'a str, &'a str) {
+    let lowercased = rest.to_ascii_lowercase();
+    let closing = format!("</{}", tag);
+    let mut from = 0;
+    while let Some(pos) = lowercased[from..].find(&closing) {
+        let idx = from + pos;
+        let after = &lowercased[idx + closing.len()..];
+        if after.is_empty()
+            || after.starts_with(|c: char| {
+                c.is_ascii_whitespace() || c == '/' || c == '>'
+            })
+        {
+            return (&rest[..idx], &rest[idx..]);
+        }
+        from = idx + closing.len();
+    }
+    (rest, "")
+}
+
+/// parse the start tag, returns None if the tag is not closed
+fn parse_start_tag(tail: &str) -> Option<(Token, &str)> {
+    let name_len = tag_name_len(&tail[1..]);
+    let name = tail[1..1 + name_len].to_ascii_lowercase();
+    let mut rest = &tail[1 + name_len..];
+    let mut attrs: Vec<(String, String)> = vec![];
+    let mut self_closing = false;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace());
+        if let Some(remaining) = rest.strip_prefix('>') {
+            return Some((
+                Token::StartTag {
+                    name,
+                    attrs,
+                    self_closing,
+                },
+                remaining,
+            ));
+        }
+        if let Some(remaining) = rest.strip_prefix('/') {
+            self_closing = true;
+            rest = remaining;
+            continue;
+        }
+        if rest.is_empty() {
+            return None;
+        }
+        self_closing = false;
+        let att_len = rest[1..]
+            .find(|c: char| {
+                c.is_ascii_whitespace() || c == '=' || c == '>' || c == '/'
+            })
+            .map(|len| len + 1)
+            .unwrap_or_else(|| rest.len());
+        let att_name = rest[..att_len].to_ascii_lowercase();
+        rest = rest[att_len..]
+            .trim_start_matches(|c: char| c.is_ascii_whitespace());
+        let mut value = String::new();
+        if let Some(after_eq) = rest.strip_prefix('=') {
+            let after_eq =
+                after_eq.trim_start_matches(|c: char| c.is_ascii_whitespace());
+            if let Some(quote) =
+                after_eq.chars().next().filter(|c| *c == '"' || *c == '\'')
+            {
+                let quoted = &after_eq[1..];
+                let end = quoted.find(quote)?;
+                value = quoted[..end].to_string();
+                rest = &quoted[end + 1..];
+            } else {
+                let end = after_eq
+                    .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                    .unwrap_or_else(|| after_eq.len());
+                value = after_eq[..end].to_string();
+                rest = &after_eq[end..];
+            }
+        }
+        // the first occurence of the attribute wins
+        if !attrs.iter().any(|(existing, _)| *existing == att_name) {
+            attrs.push((att_name, value));
+        }
+    }
+}
+
+/// Clean the html using the supplied policy, returning the sanitized html.
+///
+/// - tags that are not allowed are removed, but their text content are retained
+/// except for the clean content tags (`script`, `style`) which are removed entirely.
+/// - attributes that are not allowed are removed.
+/// - urls with schemes that are not allowed are removed.
+/// - unclosed tags are closed and stray closing tags are removed.
+pub fn clean(html: &str, policy: &Policy) -> String {
+    let mut buffer = String::new();
+    let mut open_tags: Vec<String> = vec![];
+    // the depth of the clean content tags we are currently in
+    let mut clean_content_depth = 0;
+
+    for token in tokenize(html) {
+        match token {
+            Token::Text(text) => {
+                if clean_content_depth == 0 {
+                    buffer.push_str(&escape_text(&text));
+                }
+            }
+            Token::Comment(comment) => {
+                if !policy.strip_comments && clean_content_depth == 0 {
+                    let comment = comment.replace("--", "").replace('>', "");
+                    write!(buffer, "<!--{}-->", comment).expect("must write");
+                }
+            }
+            Token::StartTag { name, attrs, .. } => {
+                let is_void = VOID_ELEMENTS.contains(&name.as_str());
+                if policy.clean_content_tags.contains(&name) {
+                    if !is_void {
+                        clean_content_depth += 1;
+                        open_tags.push(name);
+                    }
+                    continue;
+                }
+                if clean_content_depth > 0 {
+                    continue;
+                }
+                if policy.is_tag_allowed(&name) {
+                    write_start_tag(&mut buffer, policy, &name, attrs);
+                    if !is_void {
+                        open_tags.push(name);
+                    }
+                }
+            }
+            Token::EndTag(name) => {
+                if let Some(pos) = open_tags.iter().rposition(|t| *t == name) {
+                    // close the tags that are left open in between
+                    for tag in open_tags.drain(pos..).rev() {
+                        if policy.clean_content_tags.contains(&tag) {
+                            clean_content_depth -= 1;
+                        } else if clean_content_depth == 0
+                            && policy.is_tag_allowed(&tag)
+                        {
+                            write!(buffer, "</{}>", tag).expect("must write");
+                        }
+                    }
+                }
+            }
+        }
+    }
+    // close all the tags left open
+    for tag in open_tags.into_iter().rev() {
+        if policy.clean_content_tags.contains(&tag) {
+            clean_content_depth -= 1;
+        } else if clean_content_depth == 0 && policy.is_tag_allowed(&tag) {
+            write!(buffer, "</{}>", tag).expect("must write");
+        }
+    }
+    buffer
+}
+
+/// Clean the html using the policy and return it as a node
+/// which is inserted as is into the DOM.
+pub fn sanitized_html<MSG>(html: &str, policy: &Policy) -> Node<MSG> {
+    safe_html(clean(html, policy))
+}
+
+fn write_start_tag(
+    buffer: &mut String,
+    policy: &Policy,
+    name: &str,
+    attrs: Vec<(String, String)>,
+) {
+    write!(buffer, "<{}", name).expect("must write");
+    for (att, value) in attrs {
+        if !policy.is_attribute_allowed(name, &att) {
+            continue;
+        }
+        if name == "a" && att == "rel" && policy.link_rel.is_some() {
+            continue;
+        }
+        let value = decode_entities(&value);
+        if URL_ATTRIBUTES.contains(&att.as_str())
+            && !policy.is_url_allowed(&value)
+        {
+            continue;
+        }
+        write!(buffer, " {}=\"{}\"", att, escape_attribute_value(&value))
+            .expect("must write");
+    }
+    if name == "a" {
+        if let Some(rel) = &policy.link_rel {
+            write!(buffer, " rel=\"{}\"", escape_attribute_value(rel))
+                .expect("must write");
+        }
+    }
+    buffer.push('>');
+}
+
+/// return the lowercased scheme of the url, None if it is a relative url.
+/// Whitespace and control characters are ignored, since browsers ignore them too.
+fn url_scheme(url: &str) -> Option<String> {
+    let url: String = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+        .collect();
+    let end = url.find(|c| c == ':' || c == '/' || c == '?' || c == '#')?;
+    if url[end..].starts_with(':') {
+        Some(url[..end].to_ascii_lowercase())
+    } else {
+        None
+    }
+}
+
+/// decode the character references in the attribute value
+fn decode_entities(value: &str) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        let tail = &rest[amp + 1..];
+        match decode_entity(tail) {
+            Some((c, consumed)) => {
+                decoded.push(c);
+                rest = &tail[consumed..];
+            }
+            None => {
+                decoded.push('&');
+                rest = tail;
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// decode a character reference right after the `&`,
+/// returns the character and the number of bytes consumed
+fn decode_entity(tail: &str) -> Option<(char, usize)> {
+    if let Some(numeric) = tail.strip_prefix('#') {
+        let (radix, digits_start) =
+            if numeric.starts_with('x') || numeric.starts_with('X') {
+                (16, 2)
+            } else {
+                (10, 1)
+            };
+        let digits = &tail[digits_start..];
+        let len = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or_else(|| digits.len());
+        if len == 0 {
+            return None;
+        }
+        let code = u32::from_str_radix(&digits[..len], radix).ok()?;
+        let c = std::char::from_u32(code).unwrap_or('\u{FFFD}');
+        let semicolon = if digits[len..].starts_with(';') { 1 } else { 0 };
+        Some((c, digits_start + len + semicolon))
+    } else {
+        let len = tail
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or_else(|| tail.len());
+        let c = match &tail[..len] {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            "nbsp" => '\u{a0}',
+            "Tab" => '\t',
+            "NewLine" => '\n',
+            "colon" => ':',
+            "lpar" => '(',
+            "rpar" => ')',
+            "sol" => '/',
+            _ => return None,
+        };
+        let semicolon = if tail[len..].starts_with(';') { 1 } else { 0 };
+        Some((c, len + semicolon))
+    }
+}
+
+/// escape the text content, well formed character references are left as is
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for (i, c) in text.char_indices() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '&' if !is_character_reference(&text[i + 1..]) => {
+                escaped.push_str("&amp;")
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn is_character_reference(tail: &str) -> bool {
+    let (body, radix) = if let Some(hex) =
+        tail.strip_prefix("#x").or_else(|| tail.strip_prefix("#X"))
+    {
+        (hex, 16)
+    } else if let Some(dec) = tail.strip_prefix('#') {
+        (dec, 10)
+    } else {
+        (tail, 36)
+    };
+    let len = body
+        .find(|c: char| {
+            !c.is_digit(radix) || (radix == 36 && !c.is_ascii_alphanumeric())
+        })
+        .unwrap_or_else(|| body.len());
+    len > 0 && body[len..].starts_with(';')
+}
+
+/// escape the value of the attribute, which is enclosed in double quotes
+pub(crate) fn escape_attribute_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_attributes() {
+        let tokens = tokenize(r#"<a HREF='x' title=hi disabled>"#);
+        assert_eq!(
+            tokens,
+            vec![Token::StartTag {
+                name: "a".to_string(),
+                attrs: vec![
+                    ("href".to_string(), "x".to_string()),
+                    ("title".to_string(), "hi".to_string()),
+                    ("disabled".to_string(), "".to_string()),
+                ],
+                self_closing: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn decode_numeric_entities() {
+        assert_eq!(decode_entities("jav&#x61;script&#58;"), "javascript:");
+        assert_eq!(decode_entities("&#106avascript"), "javascript");
+        assert_eq!(decode_entities("a&b"), "a&b");
+    }
+
+    #[test]
+    fn url_scheme_ignores_whitespace() {
+        assert_eq!(
+            url_scheme(" java\tscript:alert(1)"),
+            Some("javascript".to_string())
+        );
+        assert_eq!(url_scheme("/path:with:colon"), None);
+        assert_eq!(url_scheme("page.html"), None);
+    }
+}
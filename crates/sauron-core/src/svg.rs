@@ -1,8 +1,10 @@
 //! Provides functions and macros to build svg elements
 pub use mt_dom::{element, element_ns};
 pub mod attributes;
+pub mod path_data;
 pub mod tags;
 
+pub use path_data::PathData;
 pub use tags::commons::*;
 
 /// SVG namespace const, use this when creating an svg element dynamically in the DOM
@@ -24,3 +26,17 @@ pub fn svg_element<MSG>(
 ) -> crate::Node<MSG> {
     crate::html::html_element_ns(tag, SVG_NAMESPACE, attrs, children)
 }
+
+/// The namespace implied by the `tag` of an element which has no explicit namespace.
+///
+/// Elements such as `linearGradient` or `clipPath` created with [`html_element`](crate::html::html_element)
+/// are still created in the [`SVG_NAMESPACE`], otherwise the browser creates them as unknown html
+/// elements with lowercased names and they are not rendered.
+/// Tags which are also html tags such as `a`, `script`, `style` and `title` are left in the html namespace.
+pub(crate) fn implied_namespace(tag: &str) -> Option<&'static str> {
+    if tags::is_common_svg_tag(tag) || tag == "use" {
+        Some(SVG_NAMESPACE)
+    } else {
+        None
+    }
+}
@@ -2,7 +2,7 @@
 use crate::prelude::{AttributeValue, Value};
 use mt_dom::{attr, attr_ns};
 
-pub(in crate) const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+pub(crate) const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
 
 macro_rules! declare_xlink_attributes {
     ( $(
@@ -40,7 +40,7 @@ macro_rules! declare_svg_attributes{
 
         #[cfg(feature = "with-lookup")]
         /// These are most commonly used svg attributes
-        pub const SVG_ATTRS:[&'static str; 168] = [$(stringify!($name),)*];
+        pub const SVG_ATTRS:[&'static str; 171] = [$(stringify!($name),)*];
     }
 }
 
@@ -55,7 +55,7 @@ macro_rules! declare_svg_attributes_special{
         #[cfg(feature = "with-lookup")]
         /// These are svg attributes with names that are non proper rust identifier therefore they
         /// are handled differently. ie: (color-profile, accent-height, etc)
-        pub const SVG_ATTRS_SPECIAL:[(&'static str,&'static str); 76] = [$((stringify!($name),$attribute),)*];
+        pub const SVG_ATTRS_SPECIAL:[(&'static str,&'static str); 78] = [$((stringify!($name),$attribute),)*];
     }
 }
 
@@ -117,6 +117,8 @@ declare_svg_attributes! {
     gradientTransform;
     gradientUnits;
     hanging;
+    hatchContentUnits;
+    hatchUnits;
     hreflang;
     ideographic;
     in2;
@@ -187,6 +189,7 @@ declare_svg_attributes! {
     ry;
     scale;
     seed;
+    side;
     slope;
     spacing;
     specularConstant;
@@ -267,6 +270,7 @@ declare_svg_attributes_special! {
     marker_end => "marker-end";
     marker_mid => "marker-mid";
     marker_start => "marker-start";
+    mask_type => "mask-type";
     overline_position => "overline-position";
     overline_thickness => "overline-thickness";
     panose_1 => "panose-1";
@@ -288,6 +292,7 @@ declare_svg_attributes_special! {
     text_anchor => "text-anchor";
     text_decoration => "text-decoration";
     text_rendering => "text-rendering";
+    transform_origin => "transform-origin";
     underline_position => "underline-position";
     underline_thickness => "underline-thickness";
     unicode_bidi => "unicode-bidi";
@@ -314,6 +319,25 @@ declare_svg_attributes_special! {
     //type_ => "type";  // already defined in html tags
 }
 
+/// Creates the [viewBox](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/viewBox) attribute
+/// from the position and the dimension of the viewport
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+///
+/// let chart: Node<()> = svg(vec![view_box(0.0, 0.0, 100.0, 50.5)], vec![]);
+/// assert_eq!(chart.render_to_string(), r#"<svg viewBox="0 0 100 50.5"></svg>"#);
+/// ```
+pub fn view_box<MSG>(
+    min_x: f64,
+    min_y: f64,
+    width: f64,
+    height: f64,
+) -> crate::Attribute<MSG> {
+    viewBox(format!("{} {} {} {}", min_x, min_y, width, height))
+}
+
 declare_xlink_attributes! {
     xlink_actuate => "actuate";
     xlink_arcrole => "arcrole";
@@ -0,0 +1,146 @@
+//! Provides a builder for the [d](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/d)
+//! attribute of the svg `path`, so charts and icons can be drawn without concatenating strings.
+//!
+//! # Examples
+//! ```rust
+//! use sauron::prelude::*;
+//!
+//! let triangle = PathData::new()
+//!     .move_to(10.0, 10.0)
+//!     .line_to(20.0, 20.5)
+//!     .horizontal_to(0.0)
+//!     .close();
+//! let icon: Node<()> = path(vec![d(triangle)], vec![]);
+//! assert_eq!(icon.render_to_string(), r#"<path d="M10 10 L20 20.5 H0 Z"></path>"#);
+//! ```
+use crate::html::attributes::Value;
+use std::fmt;
+
+/// The commands of the path, which are rendered separated with spaces
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PathData {
+    commands: Vec<String>,
+}
+
+impl PathData {
+    /// create an empty path
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(mut self, command: char, params: &[f64]) -> Self {
+        let mut command = command.to_string();
+        for (i, param) in params.iter().enumerate() {
+            if i > 0 {
+                command.push(' ');
+            }
+            command.push_str(&param.to_string());
+        }
+        self.commands.push(command);
+        self
+    }
+
+    /// start a new sub path at `x`, `y`
+    pub fn move_to(self, x: f64, y: f64) -> Self {
+        self.push('M', &[x, y])
+    }
+
+    /// start a new sub path, moved by `dx`, `dy` from the current point
+    pub fn move_by(self, dx: f64, dy: f64) -> Self {
+        self.push('m', &[dx, dy])
+    }
+
+    /// draw a line to `x`, `y`
+    pub fn line_to(self, x: f64, y: f64) -> Self {
+        self.push('L', &[x, y])
+    }
+
+    /// draw a line by `dx`, `dy` from the current point
+    pub fn line_by(self, dx: f64, dy: f64) -> Self {
+        self.push('l', &[dx, dy])
+    }
+
+    /// draw a horizontal line to `x`
+    pub fn horizontal_to(self, x: f64) -> Self {
+        self.push('H', &[x])
+    }
+
+    /// draw a vertical line to `y`
+    pub fn vertical_to(self, y: f64) -> Self {
+        self.push('V', &[y])
+    }
+
+    /// draw a cubic bezier curve to `x`, `y` with the control points `(x1, y1)` and `(x2, y2)`
+    pub fn cubic_to(
+        self,
+        (x1, y1): (f64, f64),
+        (x2, y2): (f64, f64),
+        (x, y): (f64, f64),
+    ) -> Self {
+        self.push('C', &[x1, y1, x2, y2, x, y])
+    }
+
+    /// draw a cubic bezier curve to `x`, `y`, where the first control point is the
+    /// reflection of the last control point of the previous curve
+    pub fn smooth_cubic_to(
+        self,
+        (x2, y2): (f64, f64),
+        (x, y): (f64, f64),
+    ) -> Self {
+        self.push('S', &[x2, y2, x, y])
+    }
+
+    /// draw a quadratic bezier curve to `x`, `y` with the control point `(x1, y1)`
+    pub fn quadratic_to(
+        self,
+        (x1, y1): (f64, f64),
+        (x, y): (f64, f64),
+    ) -> Self {
+        self.push('Q', &[x1, y1, x, y])
+    }
+
+    /// draw a quadratic bezier curve to `x`, `y`, where the control point is the
+    /// reflection of the control point of the previous curve
+    pub fn smooth_quadratic_to(self, x: f64, y: f64) -> Self {
+        self.push('T', &[x, y])
+    }
+
+    /// draw an elliptical arc with the radii `(rx, ry)` to `x`, `y`,
+    /// `large_arc` and `sweep` pick which of the four possible arcs is drawn
+    pub fn arc(
+        self,
+        (rx, ry): (f64, f64),
+        x_axis_rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        (x, y): (f64, f64),
+    ) -> Self {
+        let flag = |b: bool| if b { 1.0 } else { 0.0 };
+        self.push(
+            'A',
+            &[rx, ry, x_axis_rotation, flag(large_arc), flag(sweep), x, y],
+        )
+    }
+
+    /// close the current sub path with a line back to its start
+    pub fn close(self) -> Self {
+        self.push('Z', &[])
+    }
+
+    /// whether no command is added yet
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+impl fmt::Display for PathData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.commands.join(" "))
+    }
+}
+
+impl From<PathData> for Value {
+    fn from(path_data: PathData) -> Self {
+        Value::from(path_data.to_string())
+    }
+}
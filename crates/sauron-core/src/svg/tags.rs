@@ -54,6 +54,11 @@ macro_rules! declare_common_svg_tags_and_macro {
             declare_svg_tags! { $($name;)* }
         }
 
+        /// whether the `tag` is one of the common svg tags, which are not html tags
+        pub(crate) fn is_common_svg_tag(tag: &str) -> bool {
+            matches!(tag, $(stringify!($name))|*)
+        }
+
 
         #[cfg(feature = "with-lookup")]
         /// These are the commonly used svg tags such as rect, circle, path, arc, ..etc.
@@ -0,0 +1,368 @@
+//! provides the reusable validation rules for the values of the inputs, which can be
+//! used in the `update` function or in the [`FieldArray`](crate::forms::FieldArray) of the forms.
+//!
+//! The rules return a [`ValidationError`], which is turned into a message by the translator
+//! set with [`set_translator`], so the messages can be localized in one place.
+//! The messages are in english when there is no translator or the translator returns `None`.
+//!
+//! Except for [`required`], the rules accept an empty value, so the optional fields
+//! are only validated when they are filled in.
+//!
+//! # Examples
+//! ```rust
+//! use sauron::validate::{email, max_length, required, Validator};
+//!
+//! let validator = Validator::new()
+//!     .rule(required())
+//!     .rule(email())
+//!     .rule(max_length(20));
+//!
+//! assert_eq!(validator.validate(""), ["this field is required"]);
+//! assert_eq!(validator.validate("ivan"), ["must be a valid email address"]);
+//! assert!(validator.validate("ivan@example.com").is_empty());
+//! ```
+use std::{cell::RefCell, fmt, future::Future, pin::Pin, rc::Rc};
+
+thread_local!(static TRANSLATOR: RefCell<Option<Rc<dyn Fn(&ValidationError) -> Option<String>>>> = RefCell::new(None));
+
+/// The reason the value failed a rule
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationError {
+    /// the value is empty
+    Required,
+    /// the value is not an email address
+    Email,
+    /// the value has fewer characters than the minimum
+    MinLength {
+        /// the minimum number of the characters
+        min: usize,
+    },
+    /// the value has more characters than the maximum
+    MaxLength {
+        /// the maximum number of the characters
+        max: usize,
+    },
+    /// the value doesn't match the pattern
+    Pattern {
+        /// the pattern the value has to match
+        pattern: String,
+    },
+    /// the value is not a number
+    NotANumber,
+    /// the number is outside of the range
+    OutOfRange {
+        /// the minimum of the range
+        min: f64,
+        /// the maximum of the range
+        max: f64,
+    },
+    /// the error message of a custom rule
+    Custom(String),
+}
+
+impl ValidationError {
+    /// the identifier of this error, which the translator can use to look up the message
+    pub fn code(&self) -> &'static str {
+        match self {
+            ValidationError::Required => "required",
+            ValidationError::Email => "email",
+            ValidationError::MinLength { .. } => "min_length",
+            ValidationError::MaxLength { .. } => "max_length",
+            ValidationError::Pattern { .. } => "pattern",
+            ValidationError::NotANumber => "not_a_number",
+            ValidationError::OutOfRange { .. } => "out_of_range",
+            ValidationError::Custom(_) => "custom",
+        }
+    }
+
+    /// the message of this error, translated by the translator when there is one
+    pub fn message(&self) -> String {
+        let translator =
+            TRANSLATOR.with(|translator| translator.borrow().clone());
+        translator
+            .and_then(|translator| translator(self))
+            .unwrap_or_else(|| self.to_string())
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::Required => write!(f, "this field is required"),
+            ValidationError::Email => {
+                write!(f, "must be a valid email address")
+            }
+            ValidationError::MinLength { min } => {
+                write!(f, "must have at least {} characters", min)
+            }
+            ValidationError::MaxLength { max } => {
+                write!(f, "must have at most {} characters", max)
+            }
+            ValidationError::Pattern { .. } => {
+                write!(f, "is not in the expected format")
+            }
+            ValidationError::NotANumber => write!(f, "must be a number"),
+            ValidationError::OutOfRange { min, max } => {
+                write!(f, "must be between {} and {}", min, max)
+            }
+            ValidationError::Custom(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Set the translator of the validation errors into the messages of the current locale.
+/// The english message is used when the translator returns `None`.
+///
+/// # Examples
+/// ```rust
+/// use sauron::validate::{clear_translator, required, set_translator, ValidationError, Validator};
+///
+/// set_translator(|error| match error {
+///     ValidationError::Required => Some("ce champ est obligatoire".to_string()),
+///     _ => None,
+/// });
+/// let validator = Validator::new().rule(required());
+/// assert_eq!(validator.validate(""), ["ce champ est obligatoire"]);
+/// clear_translator();
+/// ```
+pub fn set_translator<F>(translator: F)
+where
+    F: Fn(&ValidationError) -> Option<String> + 'static,
+{
+    TRANSLATOR.with(|t| *t.borrow_mut() = Some(Rc::new(translator)));
+}
+
+/// remove the translator, the messages will be in english
+pub fn clear_translator() {
+    TRANSLATOR.with(|t| *t.borrow_mut() = None);
+}
+
+/// A rule which checks the value
+#[derive(Clone)]
+pub struct Rule(Rc<dyn Fn(&str) -> Result<(), ValidationError>>);
+
+impl Rule {
+    /// create a rule from a function which checks the value
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), ValidationError> + 'static,
+    {
+        Rule(Rc::new(f))
+    }
+
+    /// check the value against this rule
+    pub fn check(&self, value: &str) -> Result<(), ValidationError> {
+        (self.0)(value)
+    }
+
+    /// this rule only applies when the value is not empty
+    fn optional<F>(f: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), ValidationError> + 'static,
+    {
+        Rule::new(move |value| if value.is_empty() { Ok(()) } else { f(value) })
+    }
+}
+
+impl fmt::Debug for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Rule").finish()
+    }
+}
+
+/// the value must not be empty or only whitespace
+pub fn required() -> Rule {
+    Rule::new(|value| {
+        if value.trim().is_empty() {
+            Err(ValidationError::Required)
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// the value must look like an email address, ie: `name@example.com`
+pub fn email() -> Rule {
+    Rule::optional(|value| {
+        if is_email(value) {
+            Ok(())
+        } else {
+            Err(ValidationError::Email)
+        }
+    })
+}
+
+/// the value must have at least `min` characters
+pub fn min_length(min: usize) -> Rule {
+    Rule::optional(move |value| {
+        if value.chars().count() >= min {
+            Ok(())
+        } else {
+            Err(ValidationError::MinLength { min })
+        }
+    })
+}
+
+/// the value must have at most `max` characters
+pub fn max_length(max: usize) -> Rule {
+    Rule::optional(move |value| {
+        if value.chars().count() <= max {
+            Ok(())
+        } else {
+            Err(ValidationError::MaxLength { max })
+        }
+    })
+}
+
+/// the value must be a number from `min` to `max`, inclusive
+pub fn range(min: f64, max: f64) -> Rule {
+    Rule::optional(move |value| match value.trim().parse::<f64>() {
+        Ok(number) if number.is_nan() => Err(ValidationError::NotANumber),
+        Ok(number) if number >= min && number <= max => Ok(()),
+        Ok(_) => Err(ValidationError::OutOfRange { min, max }),
+        Err(_) => Err(ValidationError::NotANumber),
+    })
+}
+
+/// the whole value must match the regular expression `pattern`
+///
+/// # Panics
+/// panics when the `pattern` is not a valid regular expression
+#[cfg(feature = "with-regex")]
+pub fn pattern(pattern: &str) -> Rule {
+    let anchored = format!("^(?:{})$", pattern);
+    let regex = regex::Regex::new(&anchored).expect("must be a valid regex");
+    let pattern = pattern.to_string();
+    Rule::optional(move |value| {
+        if regex.is_match(value) {
+            Ok(())
+        } else {
+            Err(ValidationError::Pattern {
+                pattern: pattern.clone(),
+            })
+        }
+    })
+}
+
+/// a rule with a custom check, which returns the error message of the invalid value
+pub fn custom<F>(f: F) -> Rule
+where
+    F: Fn(&str) -> Result<(), String> + 'static,
+{
+    Rule::new(move |value| f(value).map_err(ValidationError::Custom))
+}
+
+type AsyncRule =
+    Rc<dyn Fn(String) -> Pin<Box<dyn Future<Output = Result<(), String>>>>>;
+
+/// A list of rules, which checks the value against each of the rules
+#[derive(Clone, Default)]
+pub struct Validator {
+    rules: Vec<Rule>,
+    async_rules: Vec<AsyncRule>,
+}
+
+impl Validator {
+    /// a validator which has no rules yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add a rule to this validator
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// add a rule which needs to wait for something, ie: to ask the server if the
+    /// username is already taken. The async rules are only checked in [`validate_async`],
+    /// and only when the value passed the other rules.
+    ///
+    /// [`validate_async`]: Self::validate_async
+    pub fn async_rule<F, FUT>(mut self, f: F) -> Self
+    where
+        F: Fn(String) -> FUT + 'static,
+        FUT: Future<Output = Result<(), String>> + 'static,
+    {
+        self.async_rules
+            .push(Rc::new(move |value| Box::pin(f(value))));
+        self
+    }
+
+    /// the errors of the value
+    pub fn check(&self, value: &str) -> Vec<ValidationError> {
+        self.rules
+            .iter()
+            .filter_map(|rule| rule.check(value).err())
+            .collect()
+    }
+
+    /// the translated messages of the errors of the value
+    pub fn validate(&self, value: &str) -> Vec<String> {
+        self.check(value)
+            .iter()
+            .map(|error| error.message())
+            .collect()
+    }
+
+    /// returns true if the value passed all the rules, the async rules are not checked
+    pub fn is_valid(&self, value: &str) -> bool {
+        self.rules.iter().all(|rule| rule.check(value).is_ok())
+    }
+
+    /// the translated messages of the errors of the value, including the async rules.
+    /// Use this with `Cmd::from_async` to get the result back into the `update` function.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// Cmd::from_async({
+    ///     let validator = self.username_validator.clone();
+    ///     let username = self.username.clone();
+    ///     async move { Msg::UsernameChecked(validator.validate_async(username).await) }
+    /// })
+    /// ```
+    pub fn validate_async(
+        &self,
+        value: impl ToString,
+    ) -> impl Future<Output = Vec<String>> {
+        let value = value.to_string();
+        let errors = self.validate(&value);
+        let async_rules = self.async_rules.clone();
+        async move {
+            if !errors.is_empty() {
+                return errors;
+            }
+            let mut errors = vec![];
+            for rule in async_rules {
+                if let Err(message) = rule(value.clone()).await {
+                    errors.push(ValidationError::Custom(message).message());
+                }
+            }
+            errors
+        }
+    }
+}
+
+impl fmt::Debug for Validator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Validator")
+            .field("rules", &self.rules.len())
+            .field("async_rules", &self.async_rules.len())
+            .finish()
+    }
+}
+
+fn is_email(value: &str) -> bool {
+    if value.chars().any(char::is_whitespace) {
+        return false;
+    }
+    let mut parts = value.splitn(2, '@');
+    let (local, domain) = match (parts.next(), parts.next()) {
+        (Some(local), Some(domain)) => (local, domain),
+        _ => return false,
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && domain.contains('.')
+        && domain.split('.').all(|label| !label.is_empty())
+}
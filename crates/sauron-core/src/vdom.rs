@@ -1,10 +1,24 @@
 //! This module contains types that are derived from mt-dom
 //! where we assign concrete types into the generics
 //!
-use crate::html::attributes::{self, AttributeValue};
+//! These together with the [`patch`] module are the stable api of the vdom,
+//! which is kept compatible across the minor versions of sauron regardless
+//! of the changes in mt-dom.
+//!
+use crate::html::attributes::{self, AttributeValue, Special};
 use crate::Event;
+use std::collections::{HashMap, HashSet};
 
+mod apply_patch;
+mod content_hash;
+mod diff_strategy;
 pub(crate) mod map_msg;
+pub mod patch;
+
+pub use apply_patch::apply_patch_to_vdom;
+pub use content_hash::{with_content_hash, ContentHash};
+pub use diff_strategy::{DiffStrategy, HashMatched, Keyed, Positional};
+pub use patch::Patch;
 
 /// namespace type in node, which could be change to an enum
 pub type Namespace = &'static str;
@@ -23,11 +37,6 @@ pub type Node<MSG> =
 pub type Element<MSG> =
     mt_dom::Element<Namespace, Tag, AttributeName, AttributeValue<MSG>>;
 
-/// Patch as result of diffing the current_vdom and the new vdom.
-/// The tag and attribute name types is set to &'static str
-pub type Patch<'a, MSG> =
-    mt_dom::Patch<'a, Namespace, Tag, AttributeName, AttributeValue<MSG>>;
-
 /// Attribute type used in sauron where the type of the Attribute name is &'static str
 pub type Attribute<MSG> =
     mt_dom::Attribute<Namespace, AttributeName, AttributeValue<MSG>>;
@@ -45,17 +54,63 @@ pub fn diff<'a, MSG>(
 where
     MSG: 'static,
 {
-    use crate::html::attributes::Special;
+    diff_with_skip(old, new, |_old_node, _new_node| false)
+}
+
+/// diff 2 nodes, where the nodes matching the `predicate` and its descendants are not diffed,
+/// in addition to the nodes marked with [`skip`](crate::html::attributes::skip).
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+///
+/// let old: Node<()> = div(vec![], vec![svg(vec![class("chart")], vec![])]);
+/// let new: Node<()> = div(vec![], vec![svg(vec![class("chart2")], vec![])]);
+/// let is_svg = |_old: &Node<()>, new: &Node<()>| {
+///     new.as_element_ref()
+///         .map(|element| *element.tag() == "svg")
+///         .unwrap_or(false)
+/// };
+/// let patches = diff_with_skip(&old, &new, is_svg);
+/// assert!(patches.is_empty());
+/// ```
+pub fn diff_with_skip<'a, MSG, F>(
+    old: &'a Node<MSG>,
+    new: &'a Node<MSG>,
+    predicate: F,
+) -> Vec<Patch<'a, MSG>>
+where
+    MSG: 'static,
+    F: Fn(&'a Node<MSG>, &'a Node<MSG>) -> bool,
+{
+    diff_with_key(old, new, "key", predicate)
+}
+
+/// diff 2 nodes where the children are matched by the value of the `key` attribute,
+/// the children are matched by their position when none of them has the `key` attribute
+pub(crate) fn diff_with_key<'a, MSG, F>(
+    old: &'a Node<MSG>,
+    new: &'a Node<MSG>,
+    key: &'static str,
+    predicate: F,
+) -> Vec<Patch<'a, MSG>>
+where
+    MSG: 'static,
+    F: Fn(&'a Node<MSG>, &'a Node<MSG>) -> bool,
+{
     use crate::map_msg::NodeMapMsg;
 
-    // check if the skip attribute is true
+    // check if the skip attribute is true or the node matches the predicate,
     // if it is true, skip diffing and no patches is created at this dom
-    let skip = |_old_node: &'a Node<MSG>, new_node: &'a Node<MSG>| {
-        new_node
+    let skip = |old_node: &'a Node<MSG>, new_node: &'a Node<MSG>| {
+        let explicit_skip_attr = new_node
             .get_value("skip")
             .map(|v| v.as_bool())
             .flatten()
-            .unwrap_or(false)
+            .unwrap_or(false);
+        explicit_skip_attr
+            || content_hash::is_same_precomputed_hash(old_node, new_node)
+            || predicate(old_node, new_node)
     };
 
     // check if the replace attribute evaluates to true,
@@ -75,5 +130,813 @@ where
 
         explicit_replace_attr || forbid_recycle
     };
-    mt_dom::diff::diff_with_functions(old, new, &"key", &skip, &replace)
+
+    // the children inserted before the existing children of unkeyed elements
+    // are patched with `InsertNode`, and the nodes deeper than the maximum depth
+    // are replaced, these are then skipped in the diff
+    let limits = diff_limits();
+    let mut shortcuts = Shortcuts {
+        skipped: HashSet::new(),
+        patches: vec![],
+        matched: vec![],
+    };
+    find_shortcuts(
+        old,
+        new,
+        key,
+        &mut vec![0],
+        &|old_node: &'a Node<MSG>, new_node: &'a Node<MSG>| {
+            skip(old_node, new_node) || replace(old_node, new_node)
+        },
+        &limits,
+        &mut shortcuts,
+    );
+    let Shortcuts {
+        skipped,
+        mut patches,
+        matched,
+    } = shortcuts;
+    let skip = |old_node: &'a Node<MSG>, new_node: &'a Node<MSG>| {
+        let address: *const Node<MSG> = new_node;
+        skip(old_node, new_node) || skipped.contains(&address)
+    };
+    patches.extend(
+        mt_dom::diff::diff_with_functions(old, new, &key, &skip, &replace)
+            .into_iter()
+            .map(Patch::from),
+    );
+    // the children matched in the shortcuts are diffed on their own,
+    // then their patches are moved under the path of the old child
+    for (path, old_child, new_child) in matched {
+        patches.extend(
+            mt_dom::diff::diff_with_functions(
+                old_child, new_child, &key, &skip, &replace,
+            )
+            .into_iter()
+            .map(|patch| Patch::from(patch).rebase(&path)),
+        );
+    }
+    let patches = compress_patches(remove_children_from(old, patches));
+    if patches.len() > limits.max_patches {
+        log::warn!(
+            "diff created {} patches which exceeds the maximum of {}, replacing the nearest common ancestor instead",
+            patches.len(),
+            limits.max_patches
+        );
+        return replace_common_ancestor(old, new, &patches);
+    }
+    patches
+}
+
+/// Replace the `RemoveNode` patches of the trailing children of the same parent
+/// with a single `RemoveChildrenFrom` patch of the parent, which is placed where
+/// the first of these `RemoveNode` patches was.
+///
+/// The trailing children are determined from the children of the parent in the `old` node,
+/// so only the runs of removals which reach its last child are replaced.
+fn remove_children_from<'a, MSG>(
+    old: &'a Node<MSG>,
+    patches: Vec<Patch<'a, MSG>>,
+) -> Vec<Patch<'a, MSG>> {
+    use patch::{RemoveChildrenFrom, TreePath};
+
+    let mut removed_indexes: HashMap<&[usize], Vec<usize>> = HashMap::new();
+    for patch in patches.iter() {
+        if let Patch::RemoveNode(_) = patch {
+            if let Some((idx, parent_path)) = patch.path().split_last() {
+                if !parent_path.is_empty() {
+                    removed_indexes.entry(parent_path).or_default().push(*idx);
+                }
+            }
+        }
+    }
+    // the parent path and the index where its trailing children are removed
+    let mut truncated: HashMap<Vec<usize>, (&'a Tag, usize)> = HashMap::new();
+    for (parent_path, mut indexes) in removed_indexes {
+        let parent = match find_node_by_path(old, parent_path)
+            .and_then(|parent| parent.as_element_ref())
+        {
+            Some(parent) => parent,
+            None => continue,
+        };
+        indexes.sort_unstable();
+        indexes.dedup();
+        let children_len = parent.get_children().len();
+        let trailing = indexes
+            .iter()
+            .rev()
+            .zip((0..children_len).rev())
+            .take_while(|(idx, expected)| *idx == expected)
+            .count();
+        if trailing > 1 {
+            truncated.insert(
+                parent_path.to_vec(),
+                (parent.tag(), children_len - trailing),
+            );
+        }
+    }
+    if truncated.is_empty() {
+        return patches;
+    }
+
+    let mut added: HashSet<Vec<usize>> = HashSet::new();
+    let mut compacted = Vec::with_capacity(patches.len());
+    for patch in patches {
+        let truncation = match (&patch, patch.path().split_last()) {
+            (Patch::RemoveNode(_), Some((idx, parent_path))) => truncated
+                .get(parent_path)
+                .filter(|(_tag, start_index)| idx >= start_index)
+                .map(|(tag, start_index)| (parent_path, *tag, *start_index)),
+            _ => None,
+        };
+        match truncation {
+            Some((parent_path, tag, start_index)) => {
+                if added.insert(parent_path.to_vec()) {
+                    compacted.push(
+                        RemoveChildrenFrom::new(
+                            tag,
+                            TreePath::new(parent_path.to_vec()),
+                            start_index,
+                        )
+                        .into(),
+                    );
+                }
+            }
+            None => compacted.push(patch),
+        }
+    }
+    compacted
+}
+
+/// find the node in the vdom which is at this path,
+/// the first element of the path is the root node
+fn find_node_by_path<'a, MSG>(
+    node: &'a Node<MSG>,
+    path: &[usize],
+) -> Option<&'a Node<MSG>> {
+    let (_root_idx, path) = path.split_first()?;
+    path.iter().try_fold(node, |node, idx| {
+        node.as_element_ref()?.get_children().get(*idx)
+    })
+}
+
+/// Drop the patches which are made redundant by the other patches in the batch.
+///
+/// These are the patches into the descendants of a node which is itself removed or replaced,
+/// and the patches which changes the attributes, the text or the children of that node.
+/// The `InsertNode` before the node is kept, since the inserted node is not part of it.
+/// The children removed by a `RemoveChildrenFrom` are treated the same as the removed nodes.
+///
+/// The consecutive `RemoveNode` of the contiguous children of the same parent are then
+/// removed together when the patches are applied into the DOM.
+///
+/// # Examples
+/// ```rust
+/// use sauron::{vdom::compress_patches, Patch, Text};
+/// use sauron::vdom::patch::{ChangeText, RemoveNode, TreePath};
+///
+/// let patches: Vec<Patch<()>> = vec![
+///     ChangeText::new(&Text::new("hello"), TreePath::new(vec![0, 0]), &Text::new("world")).into(),
+///     RemoveNode::new(Some(&"div"), TreePath::new(vec![0])).into(),
+/// ];
+/// assert_eq!(
+///     compress_patches(patches),
+///     vec![RemoveNode::new(Some(&"div"), TreePath::new(vec![0])).into()]
+/// );
+/// ```
+pub fn compress_patches<'a, MSG>(
+    patches: Vec<Patch<'a, MSG>>,
+) -> Vec<Patch<'a, MSG>> {
+    use patch::{PatchKind, RemoveChildrenFrom};
+
+    // the parent path and the index of the first child removed by the `RemoveChildrenFrom`
+    let truncated: Vec<(&[usize], usize)> = patches
+        .iter()
+        .filter_map(|patch| match patch {
+            Patch::RemoveChildrenFrom(RemoveChildrenFrom {
+                parent_path,
+                start_index,
+                ..
+            }) => Some((&parent_path.path[..], *start_index)),
+            _ => None,
+        })
+        .collect();
+    let discarded: HashSet<&[usize]> = patches
+        .iter()
+        .filter(|patch| {
+            matches!(
+                PatchKind::from(*patch),
+                PatchKind::RemoveNode | PatchKind::ReplaceNode
+            )
+        })
+        .map(|patch| patch.path())
+        .collect();
+    if discarded.is_empty() && truncated.is_empty() {
+        return patches;
+    }
+    let is_redundant = |patch: &Patch<'a, MSG>| {
+        let path = patch.path();
+        let is_in_discarded_node =
+            (1..path.len()).any(|len| discarded.contains(&path[..len]));
+        let changes_discarded_node = discarded.contains(path)
+            && matches!(
+                PatchKind::from(patch),
+                PatchKind::AddAttributes
+                    | PatchKind::RemoveAttributes
+                    | PatchKind::AppendChildren
+                    | PatchKind::ChangeText
+                    | PatchKind::ChangeComment
+            );
+        let is_in_truncated_child =
+            truncated.iter().any(|(parent_path, start_index)| {
+                path.len() > parent_path.len()
+                    && path.starts_with(parent_path)
+                    && path[parent_path.len()] >= *start_index
+                    && !(path.len() == parent_path.len() + 1
+                        && PatchKind::from(patch) == PatchKind::InsertNode)
+            });
+        is_in_discarded_node || changes_discarded_node || is_in_truncated_child
+    };
+    let redundant: Vec<bool> = patches.iter().map(is_redundant).collect();
+    patches
+        .into_iter()
+        .zip(redundant)
+        .filter(|(_, is_redundant)| !is_redundant)
+        .map(|(patch, _)| patch)
+        .collect()
+}
+
+/// The thresholds where the diff gives up on finding the minimal patches,
+/// and replaces the nodes instead, guarding against pathological trees
+/// such as a deeply recursive data accidentally rendered into the view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DiffLimits {
+    /// the depth of the tree where the nodes below it are not diffed,
+    /// the node at this depth is replaced when it has changed
+    pub max_depth: usize,
+    /// the number of patches where the nearest common ancestor of the changes is replaced
+    /// instead of applying each of the patches
+    pub max_patches: usize,
+}
+
+impl Default for DiffLimits {
+    fn default() -> Self {
+        DiffLimits {
+            max_depth: 256,
+            max_patches: 10_000,
+        }
+    }
+}
+
+thread_local!(static DIFF_LIMITS: std::cell::Cell<DiffLimits> = std::cell::Cell::new(DiffLimits::default()));
+
+/// set the limits of the diff, used in [`diff`] and [`diff_with_skip`]
+pub fn set_diff_limits(limits: DiffLimits) {
+    DIFF_LIMITS.with(|diff_limits| diff_limits.set(limits));
+}
+
+/// the limits of the diff, used in [`diff`] and [`diff_with_skip`]
+pub fn diff_limits() -> DiffLimits {
+    DIFF_LIMITS.with(|diff_limits| diff_limits.get())
+}
+
+/// the patches found before the diff, and the new nodes which are skipped in the diff.
+/// The skipped nodes are looked up by their address for each node visited in the diff.
+///
+/// The children of the skipped nodes which are matched with an old child are diffed
+/// on their own, these are kept in `matched` along with the path of the old child.
+struct Shortcuts<'a, MSG> {
+    skipped: HashSet<*const Node<MSG>>,
+    patches: Vec<Patch<'a, MSG>>,
+    matched: Vec<(Vec<usize>, &'a Node<MSG>, &'a Node<MSG>)>,
+}
+
+/// Walk the old and new nodes which are diffed against each other, to find:
+///
+/// - the unkeyed elements where children are inserted before its existing children,
+/// creating an `InsertNode` patch for each of the inserted children, since the positional
+/// diff of the children would otherwise patch every sibling after the insertion point.
+/// Only the elements where the attributes are unchanged are considered, the existing
+/// children are matched with a shallow comparison, then diffed against their match.
+/// - the keyed elements, where the children are matched by their keys instead of in the diff,
+/// see [`match_keyed_children`].
+/// - the elements at the maximum depth, which are replaced when they have changed,
+/// instead of recursing into its descendants.
+fn find_shortcuts<'a, MSG, F>(
+    old: &'a Node<MSG>,
+    new: &'a Node<MSG>,
+    key: &'static str,
+    path: &mut Vec<usize>,
+    is_not_diffed: &F,
+    limits: &DiffLimits,
+    shortcuts: &mut Shortcuts<'a, MSG>,
+) where
+    F: Fn(&'a Node<MSG>, &'a Node<MSG>) -> bool,
+{
+    use patch::{InsertNode, ReplaceNode, TreePath};
+
+    let (old_element, new_element) =
+        match (old.as_element_ref(), new.as_element_ref()) {
+            (Some(old_element), Some(new_element)) => {
+                (old_element, new_element)
+            }
+            _ => return,
+        };
+    if old_element.tag() != new_element.tag() || is_not_diffed(old, new) {
+        return;
+    }
+    if path.len() > limits.max_depth {
+        if has_changed(old, new) {
+            log::warn!(
+                "diff exceeded the maximum depth of {} at {:?}, replacing the node instead",
+                limits.max_depth,
+                path
+            );
+            shortcuts.patches.push(
+                ReplaceNode::new(
+                    Some(old_element.tag()),
+                    TreePath::new(path.clone()),
+                    new,
+                )
+                .into(),
+            );
+        }
+        shortcuts.skipped.insert(new);
+        return;
+    }
+    let old_children = old_element.get_children();
+    let new_children = new_element.get_children();
+    let is_keyed = old_children
+        .iter()
+        .chain(new_children.iter())
+        .any(|child| child.get_value(key).is_some());
+    if !is_keyed && old_element.get_attributes() == new_element.get_attributes()
+    {
+        if let Some((index, count)) =
+            inserted_range(&old_children[..], &new_children[..])
+        {
+            path.push(index);
+            for inserted in &new_children[index..index + count] {
+                shortcuts.patches.push(
+                    InsertNode::new(
+                        Some(new_element.tag()),
+                        TreePath::new(path.clone()),
+                        inserted,
+                    )
+                    .into(),
+                );
+            }
+            path.pop();
+            shortcuts.skipped.insert(new);
+            let before =
+                old_children[..index].iter().zip(&new_children[..index]);
+            let after = old_children[index..]
+                .iter()
+                .zip(&new_children[index + count..]);
+            match_children(
+                before.chain(after).enumerate(),
+                key,
+                path,
+                is_not_diffed,
+                limits,
+                shortcuts,
+            );
+            return;
+        }
+    }
+    if is_keyed
+        && old_element.get_attributes() == new_element.get_attributes()
+        && match_keyed_children(
+            old_element,
+            new_element,
+            key,
+            path,
+            is_not_diffed,
+            limits,
+            shortcuts,
+        )
+    {
+        shortcuts.skipped.insert(new);
+        return;
+    }
+    for (idx, (old_child, new_child)) in
+        old_children.iter().zip(new_children.iter()).enumerate()
+    {
+        // keyed children are matched by their keys in the diff,
+        // only the children which stays in the same position are diffed against each other
+        if is_keyed && old_child.get_value(key) != new_child.get_value(key) {
+            continue;
+        }
+        path.push(idx);
+        find_shortcuts(
+            old_child,
+            new_child,
+            key,
+            path,
+            is_not_diffed,
+            limits,
+            shortcuts,
+        );
+        path.pop();
+    }
+}
+
+/// Match the keyed children by their keys, using a map of the keys to the index of the old children.
+///
+/// The matched children which keep their order are the longest increasing subsequence
+/// of their old indexes. The rest of the matched children are moved before the next child
+/// which is kept, or to the end when there is none, so their DOM nodes are kept along with their state.
+/// The old children which are not matched are removed, and the new children which are not matched
+/// are inserted before the next child which is kept, or appended when there is none,
+/// so matching the children takes O(n log n). All of the matched children are diffed against their match.
+///
+/// Returns `false` when some of the children has no key or the keys are not unique,
+/// these children are matched in the diff instead.
+fn match_keyed_children<'a, MSG, F>(
+    old_element: &'a Element<MSG>,
+    new_element: &'a Element<MSG>,
+    key: &'static str,
+    path: &mut Vec<usize>,
+    is_not_diffed: &F,
+    limits: &DiffLimits,
+    shortcuts: &mut Shortcuts<'a, MSG>,
+) -> bool
+where
+    F: Fn(&'a Node<MSG>, &'a Node<MSG>) -> bool,
+{
+    use patch::{AppendChildren, InsertNode, MoveNode, RemoveNode, TreePath};
+
+    let old_children = old_element.get_children();
+    let new_children = new_element.get_children();
+    let mut old_indexes = HashMap::with_capacity(old_children.len());
+    for (old_idx, old_child) in old_children.iter().enumerate() {
+        let is_unique = old_child
+            .get_value(key)
+            .map(|value| old_indexes.insert(KeyValue(value), old_idx).is_none())
+            .unwrap_or(false);
+        if !is_unique {
+            return false;
+        }
+    }
+    // the index of the old child which is matched with each of the new children
+    let mut matched_indexes = Vec::with_capacity(new_children.len());
+    let mut new_keys = HashSet::with_capacity(new_children.len());
+    for new_child in new_children.iter() {
+        let value = match new_child.get_value(key) {
+            Some(value) if new_keys.insert(KeyValue(value)) => value,
+            _ => return false,
+        };
+        matched_indexes.push(old_indexes.get(&KeyValue(value)).copied());
+    }
+
+    // the new and old index of the matched children
+    let matched: Vec<(usize, usize)> = matched_indexes
+        .iter()
+        .enumerate()
+        .filter_map(|(new_idx, old_idx)| {
+            old_idx.map(|old_idx| (new_idx, old_idx))
+        })
+        .collect();
+    let old_sequence: Vec<usize> =
+        matched.iter().map(|(_new_idx, old_idx)| *old_idx).collect();
+    let kept: Vec<(usize, usize)> =
+        longest_increasing_subsequence(&old_sequence)
+            .into_iter()
+            .map(|i| matched[i])
+            .collect();
+    let mut is_matched_old = vec![false; old_children.len()];
+    for (_new_idx, old_idx) in matched.iter() {
+        is_matched_old[*old_idx] = true;
+    }
+    let mut is_kept_new = vec![false; new_children.len()];
+    for (new_idx, _old_idx) in kept.iter() {
+        is_kept_new[*new_idx] = true;
+    }
+
+    // the children which are removed
+    for (old_idx, old_child) in old_children.iter().enumerate() {
+        if !is_matched_old[old_idx] {
+            path.push(old_idx);
+            shortcuts.patches.push(
+                RemoveNode::new(
+                    old_child.as_element_ref().map(|element| element.tag()),
+                    TreePath::new(path.clone()),
+                )
+                .into(),
+            );
+            path.pop();
+        }
+    }
+    // the children which are inserted or moved, before the next child which is kept
+    let mut next_kept = None;
+    let mut placed = Vec::with_capacity(new_children.len() - kept.len());
+    for (new_idx, new_child) in new_children.iter().enumerate().rev() {
+        if is_kept_new[new_idx] {
+            next_kept = matched_indexes[new_idx];
+        } else {
+            placed.push((next_kept, matched_indexes[new_idx], new_child));
+        }
+    }
+    let mut appended = vec![];
+    for (next_kept, moved_from, new_child) in placed.into_iter().rev() {
+        let before_path = next_kept.map(|old_idx| {
+            TreePath::new(path.iter().copied().chain(Some(old_idx)).collect())
+        });
+        match (moved_from, before_path) {
+            (Some(old_idx), before_path) => {
+                // the children appended so far are placed before the child moved to the end
+                if before_path.is_none() && !appended.is_empty() {
+                    shortcuts.patches.push(
+                        AppendChildren::new(
+                            old_element.tag(),
+                            TreePath::new(path.clone()),
+                            std::mem::take(&mut appended),
+                        )
+                        .into(),
+                    );
+                }
+                path.push(old_idx);
+                shortcuts.patches.push(
+                    MoveNode::new(
+                        old_children[old_idx]
+                            .as_element_ref()
+                            .map(|element| element.tag()),
+                        TreePath::new(path.clone()),
+                        before_path,
+                    )
+                    .into(),
+                );
+                path.pop();
+            }
+            (None, Some(before_path)) => {
+                shortcuts.patches.push(
+                    InsertNode::new(
+                        Some(new_element.tag()),
+                        before_path,
+                        new_child,
+                    )
+                    .into(),
+                );
+            }
+            (None, None) => appended.push(new_child),
+        }
+    }
+    if !appended.is_empty() {
+        shortcuts.patches.push(
+            AppendChildren::new(
+                old_element.tag(),
+                TreePath::new(path.clone()),
+                appended,
+            )
+            .into(),
+        );
+    }
+    match_children(
+        matched.into_iter().map(|(new_idx, old_idx)| {
+            (old_idx, (&old_children[old_idx], &new_children[new_idx]))
+        }),
+        key,
+        path,
+        is_not_diffed,
+        limits,
+        shortcuts,
+    );
+    true
+}
+
+/// The value of the key attribute, which the children are looked up with.
+///
+/// The keys are compared with the equality of the values, which is reflexive
+/// for the values used as keys.
+struct KeyValue<'a>(&'a attributes::Value);
+
+impl PartialEq for KeyValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for KeyValue<'_> {}
+
+impl std::hash::Hash for KeyValue<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+/// the indexes of one of the longest strictly increasing subsequences of the `sequence`,
+/// found in O(n log n) by keeping the smallest tail of the subsequences of each length
+fn longest_increasing_subsequence(sequence: &[usize]) -> Vec<usize> {
+    // the index of the smallest tail of the increasing subsequences of each length
+    let mut tails: Vec<usize> = vec![];
+    // the index of the previous item in the subsequence which ends at each index
+    let mut previous: Vec<Option<usize>> = vec![None; sequence.len()];
+    for (i, value) in sequence.iter().enumerate() {
+        let len = tails.partition_point(|tail| sequence[*tail] < *value);
+        if len > 0 {
+            previous[i] = Some(tails[len - 1]);
+        }
+        if len == tails.len() {
+            tails.push(i);
+        } else {
+            tails[len] = i;
+        }
+    }
+    let mut subsequence = vec![];
+    let mut current = tails.last().copied();
+    while let Some(i) = current {
+        subsequence.push(i);
+        current = previous[i];
+    }
+    subsequence.reverse();
+    subsequence
+}
+
+/// each old child at the index is matched with the new child in the shortcuts,
+/// so they are diffed on their own instead of in the diff of their parent
+fn match_children<'a, MSG, F>(
+    matched: impl Iterator<Item = (usize, (&'a Node<MSG>, &'a Node<MSG>))>,
+    key: &'static str,
+    path: &mut Vec<usize>,
+    is_not_diffed: &F,
+    limits: &DiffLimits,
+    shortcuts: &mut Shortcuts<'a, MSG>,
+) where
+    F: Fn(&'a Node<MSG>, &'a Node<MSG>) -> bool,
+{
+    for (idx, (old_child, new_child)) in matched {
+        path.push(idx);
+        shortcuts.matched.push((path.clone(), old_child, new_child));
+        find_shortcuts(
+            old_child,
+            new_child,
+            key,
+            path,
+            is_not_diffed,
+            limits,
+            shortcuts,
+        );
+        path.pop();
+    }
+}
+
+/// Whether the `old` node or any of its descendants is different from the `new` node.
+///
+/// The nodes are compared a pair at a time by their tag, attributes (including the key)
+/// and number of children, stopping at the first difference. The trees are walked with a stack
+/// instead of recursing, since this is used on the nodes beyond the maximum depth of the diff.
+fn has_changed<MSG>(old: &Node<MSG>, new: &Node<MSG>) -> bool {
+    let mut pending = vec![(old, new)];
+    while let Some((old, new)) = pending.pop() {
+        match (old.as_element_ref(), new.as_element_ref()) {
+            (Some(old_element), Some(new_element)) => {
+                let old_children = old_element.get_children();
+                let new_children = new_element.get_children();
+                if old_element.tag() != new_element.tag()
+                    || old_element.get_attributes()
+                        != new_element.get_attributes()
+                    || old_children.len() != new_children.len()
+                {
+                    return true;
+                }
+                pending.extend(old_children.iter().zip(new_children.iter()));
+            }
+            // the text and comment nodes have no children
+            (None, None) => {
+                if old != new {
+                    return true;
+                }
+            }
+            _ => return true,
+        }
+    }
+    false
+}
+
+/// returns the index and the number of the children inserted into `old_children`,
+/// if the rest of the `new_children` looks the same as the `old_children` in a shallow comparison.
+/// Children added at the end are not included, since they are appended in the diff.
+fn inserted_range<MSG>(
+    old_children: &[Node<MSG>],
+    new_children: &[Node<MSG>],
+) -> Option<(usize, usize)> {
+    if new_children.len() <= old_children.len() {
+        return None;
+    }
+    let count = new_children.len() - old_children.len();
+    let index = old_children
+        .iter()
+        .zip(new_children.iter())
+        .take_while(|(old_child, new_child)| {
+            is_shallow_match(old_child, new_child)
+        })
+        .count();
+    let is_shifted = old_children[index..]
+        .iter()
+        .zip(&new_children[index + count..])
+        .all(|(old_child, new_child)| is_shallow_match(old_child, new_child));
+    if index < old_children.len() && is_shifted {
+        Some((index, count))
+    } else {
+        None
+    }
+}
+
+/// Whether the nodes look the same without descending into their children:
+/// the elements have the same tag and attributes, and their children have the same tags
+/// or the same text. The matched nodes are then diffed against each other,
+/// so the changes in their descendants are still patched.
+fn is_shallow_match<MSG>(old: &Node<MSG>, new: &Node<MSG>) -> bool {
+    match (old.as_element_ref(), new.as_element_ref()) {
+        (Some(old_element), Some(new_element)) => {
+            let old_children = old_element.get_children();
+            let new_children = new_element.get_children();
+            old_element.tag() == new_element.tag()
+                && old_element.get_attributes() == new_element.get_attributes()
+                && old_children.len() == new_children.len()
+                && old_children.iter().zip(new_children.iter()).all(
+                    |(old_child, new_child)| match (
+                        old_child.as_element_ref(),
+                        new_child.as_element_ref(),
+                    ) {
+                        (Some(old_child), Some(new_child)) => {
+                            old_child.tag() == new_child.tag()
+                        }
+                        (None, None) => old_child == new_child,
+                        _ => false,
+                    },
+                )
+        }
+        (None, None) => old == new,
+        _ => false,
+    }
+}
+
+/// replace the nearest common ancestor of the nodes changed by the `patches`
+fn replace_common_ancestor<'a, MSG>(
+    old: &'a Node<MSG>,
+    new: &'a Node<MSG>,
+    patches: &[Patch<'a, MSG>],
+) -> Vec<Patch<'a, MSG>> {
+    use patch::{ReplaceNode, TreePath};
+
+    // the children inserted, moved and removed are changes of their parent
+    let changed_paths = patches.iter().map(|patch| match patch {
+        Patch::InsertNode(_) | Patch::MoveNode(_) | Patch::RemoveNode(_) => {
+            let path = patch.path();
+            &path[..path.len().saturating_sub(1)]
+        }
+        _ => patch.path(),
+    });
+    let mut common_path: Vec<usize> = vec![0];
+    if let Some(first_path) = changed_paths.clone().next() {
+        let common_len = changed_paths.fold(first_path.len(), |len, path| {
+            first_path
+                .iter()
+                .zip(path.iter())
+                .take(len)
+                .take_while(|(a, b)| a == b)
+                .count()
+        });
+        common_path = first_path[..common_len.max(1)].to_vec();
+    }
+
+    // the old and new nodes are matched by their position,
+    // unless the children are keyed
+    let mut old_node = old;
+    let mut new_node = new;
+    let mut depth = 1;
+    while depth < common_path.len() {
+        let (old_element, new_element) =
+            match (old_node.as_element_ref(), new_node.as_element_ref()) {
+                (Some(old_element), Some(new_element)) => {
+                    (old_element, new_element)
+                }
+                _ => break,
+            };
+        let old_children = old_element.get_children();
+        let new_children = new_element.get_children();
+        let is_keyed = old_children
+            .iter()
+            .chain(new_children.iter())
+            .any(|child| child.get_value("key").is_some());
+        if old_element.tag() != new_element.tag() || is_keyed {
+            break;
+        }
+        let idx = common_path[depth];
+        match (old_children.get(idx), new_children.get(idx)) {
+            (Some(old_child), Some(new_child)) => {
+                old_node = old_child;
+                new_node = new_child;
+                depth += 1;
+            }
+            _ => break,
+        }
+    }
+    vec![ReplaceNode::new(
+        old_node.as_element_ref().map(|element| element.tag()),
+        TreePath::new(common_path[..depth].to_vec()),
+        new_node,
+    )
+    .into()]
 }
@@ -0,0 +1,252 @@
+//! Applies the patches into a vdom, without touching the actual DOM.
+//!
+//! This is used to verify the patches produced by the diff,
+//! applying the `diff(old, new)` into `old` should always result to `new`.
+use crate::{
+    backend::PatchError,
+    vdom::patch::{
+        AddAttributes, AppendChildren, InsertNode, MoveNode, RemoveAttributes,
+        RemoveChildrenFrom, ReplaceNode,
+    },
+    Attribute, Node, Patch,
+};
+
+/// Apply the patches into a copy of the `old` vdom and return the resulting vdom.
+///
+/// The nodes of all the patches are found before applying any of the patches,
+/// the same way as the patches are applied into the actual DOM.
+///
+/// # Examples
+/// ```rust
+/// use sauron::{diff, html::*, html::attributes::*, vdom::apply_patch_to_vdom, Node};
+///
+/// let old: Node<()> = ul(vec![], vec![li(vec![], vec![text("1")])]);
+/// let new: Node<()> = ul(vec![class("list")], vec![li(vec![], vec![text("2")])]);
+/// let patches = diff(&old, &new);
+/// assert_eq!(apply_patch_to_vdom(&old, &patches).unwrap(), new);
+/// ```
+pub fn apply_patch_to_vdom<MSG>(
+    old: &Node<MSG>,
+    patches: &[Patch<MSG>],
+) -> Result<Node<MSG>, PatchError> {
+    let mut tree = Tree::default();
+    tree.root = tree.add(old.clone());
+
+    let mut targets = Vec::with_capacity(patches.len());
+    for patch in patches {
+        let path = patch.path();
+        let target = tree
+            .find(path)
+            .ok_or_else(|| PatchError::NodeNotFound(path.to_vec()))?;
+        // the children to be removed and the node which a node is moved before
+        // are also found before applying any of the patches
+        let children = match patch {
+            Patch::RemoveChildrenFrom(RemoveChildrenFrom {
+                start_index,
+                ..
+            }) => tree.slots[target]
+                .children
+                .get(*start_index..)
+                .map(<[usize]>::to_vec)
+                .ok_or_else(|| {
+                    PatchError::NodeNotFound([path, &[*start_index]].concat())
+                })?,
+            Patch::MoveNode(MoveNode {
+                before_path: Some(before_path),
+                ..
+            }) => vec![tree.find(&before_path.path).ok_or_else(|| {
+                PatchError::NodeNotFound(before_path.path.clone())
+            })?],
+            _ => vec![],
+        };
+        targets.push((target, children));
+    }
+    for (patch, (target, children)) in patches.iter().zip(targets) {
+        match patch {
+            Patch::InsertNode(InsertNode { node, .. }) => {
+                let inserted = tree.add((*node).clone());
+                tree.insert_before(target, inserted);
+            }
+            Patch::MoveNode(_) => {
+                let parent = tree.slots[target].parent;
+                tree.detach(target);
+                match (children.first(), parent) {
+                    (Some(before), _) => tree.insert_before(*before, target),
+                    (None, Some(parent)) => {
+                        tree.slots[target].parent = Some(parent);
+                        tree.slots[parent].children.push(target);
+                    }
+                    (None, None) => (),
+                }
+            }
+            Patch::AddAttributes(AddAttributes { attrs, .. }) => {
+                if let Node::Element(element) = &mut tree.slots[target].node {
+                    add_attributes(&mut element.attrs, attrs);
+                }
+            }
+            Patch::RemoveAttributes(RemoveAttributes { attrs, .. }) => {
+                if let Node::Element(element) = &mut tree.slots[target].node {
+                    element.attrs.retain(|existing| {
+                        attrs.iter().all(|attr| attr.name() != existing.name())
+                    });
+                }
+            }
+            Patch::ReplaceNode(ReplaceNode { replacement, .. }) => {
+                let replacement = tree.add((*replacement).clone());
+                tree.replace(target, replacement);
+            }
+            Patch::RemoveNode(_) => {
+                tree.detach(target);
+            }
+            Patch::RemoveChildrenFrom(_) => {
+                for child in children {
+                    tree.detach(child);
+                }
+            }
+            Patch::AppendChildren(AppendChildren { children, .. }) => {
+                for child in children.iter() {
+                    let child = tree.add((*child).clone());
+                    tree.slots[child].parent = Some(target);
+                    tree.slots[target].children.push(child);
+                }
+            }
+            Patch::ChangeText(ct) => {
+                tree.slots[target].node = Node::Text(ct.new.clone());
+            }
+            Patch::ChangeComment(cm) => {
+                tree.slots[target].node = Node::Comment(cm.new.clone());
+            }
+        }
+    }
+    Ok(tree.build(tree.root))
+}
+
+/// set the attributes, replacing the existing attributes with the same name in place
+fn add_attributes<MSG>(
+    existing: &mut Vec<Attribute<MSG>>,
+    attrs: &[&Attribute<MSG>],
+) {
+    let mut added: Vec<&'static str> = vec![];
+    for attr in attrs {
+        let name = *attr.name();
+        if added.contains(&name) {
+            // the attributes with the same name are kept together
+            let position = existing
+                .iter()
+                .rposition(|existing| *existing.name() == name)
+                .map(|position| position + 1)
+                .unwrap_or(existing.len());
+            existing.insert(position, (*attr).clone());
+            continue;
+        }
+        added.push(name);
+        match existing
+            .iter()
+            .position(|existing| *existing.name() == name)
+        {
+            Some(position) => {
+                existing.retain(|existing| *existing.name() != name);
+                existing.insert(position, (*attr).clone());
+            }
+            None => existing.push((*attr).clone()),
+        }
+    }
+}
+
+/// the nodes of the vdom where the children can be moved around
+/// and still be referred to by their index
+struct Tree<MSG> {
+    slots: Vec<Slot<MSG>>,
+    root: usize,
+}
+
+/// a node, where its children are taken out into the tree
+struct Slot<MSG> {
+    node: Node<MSG>,
+    children: Vec<usize>,
+    parent: Option<usize>,
+}
+
+impl<MSG> Default for Tree<MSG> {
+    fn default() -> Self {
+        Tree {
+            slots: vec![],
+            root: 0,
+        }
+    }
+}
+
+impl<MSG> Tree<MSG> {
+    /// add the node and its descendants into the tree, returns the index of the node
+    fn add(&mut self, mut node: Node<MSG>) -> usize {
+        let children = match &mut node {
+            Node::Element(element) => std::mem::take(&mut element.children),
+            _ => vec![],
+        };
+        let index = self.slots.len();
+        self.slots.push(Slot {
+            node,
+            children: vec![],
+            parent: None,
+        });
+        for child in children {
+            let child = self.add(child);
+            self.slots[child].parent = Some(index);
+            self.slots[index].children.push(child);
+        }
+        index
+    }
+
+    /// find the node at the path, the first element of the path is the root node
+    fn find(&self, path: &[usize]) -> Option<usize> {
+        let (_root_idx, path) = path.split_first()?;
+        path.iter().try_fold(self.root, |node, idx| {
+            self.slots[node].children.get(*idx).copied()
+        })
+    }
+
+    /// remove the node from its parent
+    fn detach(&mut self, node: usize) {
+        if let Some(parent) = self.slots[node].parent.take() {
+            self.slots[parent].children.retain(|child| *child != node);
+        }
+    }
+
+    /// insert the `node` before the `target`, in the parent of the `target`
+    fn insert_before(&mut self, target: usize, node: usize) {
+        if let Some(parent) = self.slots[target].parent {
+            let children = &mut self.slots[parent].children;
+            if let Some(position) =
+                children.iter().position(|child| *child == target)
+            {
+                children.insert(position, node);
+                self.slots[node].parent = Some(parent);
+            }
+        }
+    }
+
+    /// replace the `target` with the `replacement`, which could be the root node
+    fn replace(&mut self, target: usize, replacement: usize) {
+        match self.slots[target].parent {
+            Some(_) => {
+                self.insert_before(target, replacement);
+                self.detach(target);
+            }
+            None if target == self.root => self.root = replacement,
+            None => (),
+        }
+    }
+
+    /// build the node at the index with its descendants
+    fn build(&self, index: usize) -> Node<MSG> {
+        let mut node = self.slots[index].node.clone();
+        if let Node::Element(element) = &mut node {
+            element.children = self.slots[index]
+                .children
+                .iter()
+                .map(|child| self.build(*child))
+                .collect();
+        }
+        node
+    }
+}
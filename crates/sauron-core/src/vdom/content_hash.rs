@@ -0,0 +1,136 @@
+//! provides [`ContentHash`] to hash the content of the nodes and attributes,
+//! so the memoization, the template caching and the devtools can key on a subtree.
+//!
+//! The hash of a subtree can also be precomputed with [`with_content_hash`],
+//! so the diff skips the subtree when its hash is the same as in the old view.
+use crate::{html::attributes::custom_attr, Attribute, Element, Node};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+/// the name of the attribute which carries the precomputed hash of the element
+const CONTENT_HASH_ATTR: &str = "content_hash";
+
+/// the precomputed hash of an element, which is not set into the actual DOM
+#[derive(Debug, PartialEq)]
+struct PrecomputedHash(u64);
+
+/// Hash the content of a Node, Element or Attribute.
+///
+/// The nodes which are equal have the same hash: the tag, the namespace, the attributes
+/// in their order and the children are hashed. The event listeners are hashed by their types
+/// and the custom values by their variant only, the same way as they are compared.
+///
+/// The hash is stable for the same build of the app, so it can be used as a key of the caches
+/// in memory. It is not guaranteed to be the same across the versions of sauron or of the
+/// compiler, or across the targets, so it should not be persisted or sent to a server.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+///
+/// let old: Node<()> = div(vec![class("panel")], vec![text("hello")]);
+/// let new: Node<()> = div(vec![class("panel")], vec![text("hello")]);
+/// assert_eq!(old.content_hash(), new.content_hash());
+///
+/// let changed: Node<()> = div(vec![class("panel")], vec![text("world")]);
+/// assert_ne!(old.content_hash(), changed.content_hash());
+/// ```
+pub trait ContentHash {
+    /// feed the content into the `state`
+    fn hash_content<H: Hasher>(&self, state: &mut H);
+
+    /// the hash of the content
+    fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash_content(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl<MSG> ContentHash for Node<MSG> {
+    fn hash_content<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Node::Element(element) => element.hash_content(state),
+            Node::Text(text) => text.text.hash(state),
+            Node::Comment(comment) => comment.hash(state),
+        }
+    }
+}
+
+impl<MSG> ContentHash for Element<MSG> {
+    fn hash_content<H: Hasher>(&self, state: &mut H) {
+        self.namespace.hash(state);
+        self.tag.hash(state);
+        self.attrs.len().hash(state);
+        for attr in self.attrs.iter() {
+            attr.hash_content(state);
+        }
+        self.children.len().hash(state);
+        for child in self.children.iter() {
+            child.hash_content(state);
+        }
+    }
+}
+
+impl<MSG> ContentHash for Attribute<MSG> {
+    fn hash_content<H: Hasher>(&self, state: &mut H) {
+        self.namespace.hash(state);
+        self.name.hash(state);
+        self.value.hash(state);
+    }
+}
+
+/// Precompute the content hash of this node, so the diff compares the hashes
+/// instead of descending into the node when both the old and the new node have one.
+///
+/// This is a large win for the views where only one panel changes per dispatch,
+/// the unchanged panels are hashed when the view is built but not diffed.
+/// The event listeners are hashed by their types, so the listeners of an unchanged panel
+/// are not replaced even if they capture different values.
+/// Text and comment nodes are returned as is.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+/// use sauron::vdom::with_content_hash;
+///
+/// let panel = |title: &str| -> Node<()> {
+///     with_content_hash(div(vec![class("panel")], vec![text(title)]))
+/// };
+/// let old: Node<()> = main(vec![], vec![panel("sidebar"), text("1")]);
+/// let new: Node<()> = main(vec![], vec![panel("sidebar"), text("2")]);
+/// assert_eq!(diff(&old, &new).len(), 1);
+/// ```
+pub fn with_content_hash<MSG>(mut node: Node<MSG>) -> Node<MSG> {
+    let hash = node.content_hash();
+    if let Node::Element(element) = &mut node {
+        element
+            .attrs
+            .push(custom_attr(CONTENT_HASH_ATTR, PrecomputedHash(hash)));
+    }
+    node
+}
+
+/// returns true if both nodes have a precomputed hash and they are the same
+pub(crate) fn is_same_precomputed_hash<MSG>(
+    old: &Node<MSG>,
+    new: &Node<MSG>,
+) -> bool {
+    match (precomputed_hash(old), precomputed_hash(new)) {
+        (Some(old_hash), Some(new_hash)) => old_hash == new_hash,
+        _ => false,
+    }
+}
+
+fn precomputed_hash<MSG>(node: &Node<MSG>) -> Option<u64> {
+    node.as_element_ref()?
+        .attrs
+        .iter()
+        .filter(|attr| *attr.name() == CONTENT_HASH_ATTR)
+        .flat_map(|attr| attr.value().iter())
+        .find_map(|value| value.as_custom::<PrecomputedHash>())
+        .map(|precomputed| precomputed.0)
+}
@@ -0,0 +1,109 @@
+//! provides [`DiffStrategy`], the algorithm used to diff the old view and the new view
+//! of a program, so workload specific strategies can be used without forking the crate.
+//!
+//! The strategy is set per program with [`Program::set_diff_strategy`](crate::dom::Program::set_diff_strategy),
+//! [`Keyed`] is used by default.
+use crate::{
+    vdom::{content_hash::ContentHash, diff_with_key},
+    Node, Patch,
+};
+
+/// An algorithm which creates the patches to turn the `old` node into the `new` node.
+///
+/// The patches are applied into the DOM in the order they are returned,
+/// the paths of the patches are the paths of the nodes in the `old` node.
+///
+/// # Examples
+/// ```rust
+/// use sauron::prelude::*;
+/// use sauron::vdom::{DiffStrategy, Keyed};
+/// use sauron::vdom::patch::{ReplaceNode, TreePath};
+///
+/// /// replace the whole view when the root element has changed
+/// struct ReplaceRootOnChange;
+///
+/// impl<MSG: 'static> DiffStrategy<MSG> for ReplaceRootOnChange {
+///     fn diff<'a>(&self, old: &'a Node<MSG>, new: &'a Node<MSG>) -> Vec<Patch<'a, MSG>> {
+///         let old_tag = old.as_element_ref().map(|element| element.tag());
+///         let new_tag = new.as_element_ref().map(|element| element.tag());
+///         if old_tag != new_tag {
+///             vec![ReplaceNode::new(old_tag, TreePath::new(vec![0]), new).into()]
+///         } else {
+///             Keyed.diff(old, new)
+///         }
+///     }
+/// }
+/// ```
+pub trait DiffStrategy<MSG> {
+    /// the patches to turn the `old` node into the `new` node
+    fn diff<'a>(
+        &self,
+        old: &'a Node<MSG>,
+        new: &'a Node<MSG>,
+    ) -> Vec<Patch<'a, MSG>>;
+}
+
+/// The default strategy, where the children are matched by their `key` attribute if they have one,
+/// otherwise by their position. This is the same as [`diff`](crate::diff).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Keyed;
+
+/// The children are always matched by their position, ignoring the `key` attribute.
+///
+/// This suits the huge flat lists which are only appended or changed in place,
+/// where matching the keys costs more than it saves.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Positional;
+
+/// The same as [`Keyed`], except that the subtrees which have the same
+/// [`content_hash`](ContentHash::content_hash) are not descended into.
+///
+/// This suits the deep trees where only a few branches changes per dispatch,
+/// since each subtree is hashed instead of diffed node by node.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HashMatched;
+
+/// the name of the attribute which none of the elements has a value of,
+/// only the empty attribute has an empty name and it has no value
+const NO_KEY: &str = "";
+
+impl<MSG> DiffStrategy<MSG> for Keyed
+where
+    MSG: 'static,
+{
+    fn diff<'a>(
+        &self,
+        old: &'a Node<MSG>,
+        new: &'a Node<MSG>,
+    ) -> Vec<Patch<'a, MSG>> {
+        crate::diff(old, new)
+    }
+}
+
+impl<MSG> DiffStrategy<MSG> for Positional
+where
+    MSG: 'static,
+{
+    fn diff<'a>(
+        &self,
+        old: &'a Node<MSG>,
+        new: &'a Node<MSG>,
+    ) -> Vec<Patch<'a, MSG>> {
+        diff_with_key(old, new, NO_KEY, |_old_node, _new_node| false)
+    }
+}
+
+impl<MSG> DiffStrategy<MSG> for HashMatched
+where
+    MSG: 'static,
+{
+    fn diff<'a>(
+        &self,
+        old: &'a Node<MSG>,
+        new: &'a Node<MSG>,
+    ) -> Vec<Patch<'a, MSG>> {
+        crate::diff_with_skip(old, new, |old_node, new_node| {
+            old_node.content_hash() == new_node.content_hash()
+        })
+    }
+}
@@ -176,9 +176,11 @@ where
             }
             AttributeValue::Simple(this) => AttributeValue::Simple(this),
             AttributeValue::Style(this) => AttributeValue::Style(this),
+            AttributeValue::Property(this) => AttributeValue::Property(this),
             AttributeValue::EventListener(this) => {
                 AttributeValue::EventListener(this.map_callback(cb))
             }
+            AttributeValue::Custom(this) => AttributeValue::Custom(this),
             AttributeValue::Empty => AttributeValue::Empty,
         }
     }
@@ -0,0 +1,710 @@
+//! The patches created from diffing the vdom, and the path of the nodes they are applied to.
+//!
+//! [`Patch`] and the structs of its variants are owned by sauron, the patches from the diff
+//! of mt-dom are converted into them, in addition to the patches which are only created by sauron.
+//! These are the semver stable types of the patches, which widget crates should use
+//! in their assertions instead of `sauron::mt_dom`, which is an implementation detail
+//! that may change in a minor version.
+//!
+//! # Examples
+//! ```rust
+//! use sauron::prelude::*;
+//! use sauron::vdom::patch::{PatchKind, TreePath, ChangeText};
+//!
+//! let old: Node<()> = div(vec![], vec![text("hello")]);
+//! let new: Node<()> = div(vec![], vec![text("world")]);
+//! let patches = diff(&old, &new);
+//!
+//! assert_eq!(PatchKind::from(&patches[0]), PatchKind::ChangeText);
+//! assert_eq!(
+//!     patches,
+//!     vec![ChangeText::new(&Text::new("hello"), TreePath::new(vec![0, 0]), &Text::new("world")).into()]
+//! );
+//! ```
+use crate::{
+    html::attributes::AttributeValue,
+    vdom::{AttributeName, Namespace, Tag},
+    Attribute, Node, Text,
+};
+use std::fmt;
+
+/// the patch of mt-dom with the types of sauron, which is converted into [`Patch`]
+type MtPatch<'a, MSG> =
+    mt_dom::Patch<'a, Namespace, Tag, AttributeName, AttributeValue<MSG>>;
+
+/// The path of a node in the tree, which are the indexes of the children from the root node,
+/// the root node itself is `[0]`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TreePath {
+    /// the indexes of the children, starting with the root node
+    pub path: Vec<usize>,
+}
+
+impl TreePath {
+    /// create a path from the indexes of the children
+    pub fn new(path: Vec<usize>) -> Self {
+        TreePath { path }
+    }
+}
+
+impl From<mt_dom::patch::TreePath> for TreePath {
+    fn from(tree_path: mt_dom::patch::TreePath) -> Self {
+        TreePath::new(tree_path.path)
+    }
+}
+
+/// implement Clone, Debug and PartialEq by hand for the patches which hold the nodes
+/// or the attributes, since deriving them requires the MSG itself to implement them
+macro_rules! impl_patch_traits {
+    ($patch:ident { $($field:ident),+ }) => {
+        impl<MSG> Clone for $patch<'_, MSG> {
+            fn clone(&self) -> Self {
+                $patch {
+                    $($field: self.$field.clone()),+
+                }
+            }
+        }
+
+        impl<MSG> fmt::Debug for $patch<'_, MSG> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_struct(stringify!($patch))
+                    $(.field(stringify!($field), &self.$field))+
+                    .finish()
+            }
+        }
+
+        impl<MSG> PartialEq for $patch<'_, MSG> {
+            fn eq(&self, other: &Self) -> bool {
+                $(self.$field == other.$field)&&+
+            }
+        }
+    };
+}
+
+/// Insert the `node` before the node at the `patch_path`
+pub struct InsertNode<'a, MSG> {
+    /// the tag of the node the new node is inserted before, if it is an element
+    pub tag: Option<&'a Tag>,
+    /// the path of the node the new node is inserted before
+    pub patch_path: TreePath,
+    /// the node to be inserted
+    pub node: &'a Node<MSG>,
+}
+
+impl<'a, MSG> InsertNode<'a, MSG> {
+    /// create a patch which inserts the `node` before the node at the `patch_path`
+    pub fn new(
+        tag: Option<&'a Tag>,
+        patch_path: TreePath,
+        node: &'a Node<MSG>,
+    ) -> Self {
+        InsertNode {
+            tag,
+            patch_path,
+            node,
+        }
+    }
+}
+
+impl_patch_traits!(InsertNode {
+    tag,
+    patch_path,
+    node
+});
+
+/// Move the existing node at the `patch_path` before the node at the `before_path`,
+/// or to the end of its parent when there is no `before_path`.
+///
+/// The diff creates this for the keyed children which are moved to another position,
+/// so the DOM node is kept along with its state, such as the focus and the scroll position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MoveNode<'a> {
+    /// the tag of the node to be moved, if it is an element
+    pub tag: Option<&'a Tag>,
+    /// the path of the node to be moved
+    pub patch_path: TreePath,
+    /// the path of the node it is moved before, which has the same parent
+    pub before_path: Option<TreePath>,
+}
+
+impl<'a> MoveNode<'a> {
+    /// create a patch which moves the node at the `patch_path` before the node at the `before_path`,
+    /// or to the end of its parent when there is no `before_path`
+    pub fn new(
+        tag: Option<&'a Tag>,
+        patch_path: TreePath,
+        before_path: Option<TreePath>,
+    ) -> Self {
+        MoveNode {
+            tag,
+            patch_path,
+            before_path,
+        }
+    }
+}
+
+/// Add or change the `attrs` of the element at the `patch_path`
+pub struct AddAttributes<'a, MSG> {
+    /// the tag of the element
+    pub tag: &'a Tag,
+    /// the path of the element
+    pub patch_path: TreePath,
+    /// the attributes to be added or changed
+    pub attrs: Vec<&'a Attribute<MSG>>,
+}
+
+impl<'a, MSG> AddAttributes<'a, MSG> {
+    /// create a patch which adds or changes the `attrs` of the element at the `patch_path`
+    pub fn new(
+        tag: &'a Tag,
+        patch_path: TreePath,
+        attrs: Vec<&'a Attribute<MSG>>,
+    ) -> Self {
+        AddAttributes {
+            tag,
+            patch_path,
+            attrs,
+        }
+    }
+}
+
+impl_patch_traits!(AddAttributes {
+    tag,
+    patch_path,
+    attrs
+});
+
+/// Remove the `attrs` of the element at the `patch_path`
+pub struct RemoveAttributes<'a, MSG> {
+    /// the tag of the element
+    pub tag: &'a Tag,
+    /// the path of the element
+    pub patch_path: TreePath,
+    /// the attributes to be removed
+    pub attrs: Vec<&'a Attribute<MSG>>,
+}
+
+impl<'a, MSG> RemoveAttributes<'a, MSG> {
+    /// create a patch which removes the `attrs` of the element at the `patch_path`
+    pub fn new(
+        tag: &'a Tag,
+        patch_path: TreePath,
+        attrs: Vec<&'a Attribute<MSG>>,
+    ) -> Self {
+        RemoveAttributes {
+            tag,
+            patch_path,
+            attrs,
+        }
+    }
+}
+
+impl_patch_traits!(RemoveAttributes {
+    tag,
+    patch_path,
+    attrs
+});
+
+/// Replace the node at the `patch_path` with the `replacement`
+pub struct ReplaceNode<'a, MSG> {
+    /// the tag of the node to be replaced, if it is an element
+    pub tag: Option<&'a Tag>,
+    /// the path of the node to be replaced
+    pub patch_path: TreePath,
+    /// the node which replaces the old node
+    pub replacement: &'a Node<MSG>,
+}
+
+impl<'a, MSG> ReplaceNode<'a, MSG> {
+    /// create a patch which replaces the node at the `patch_path` with the `replacement`
+    pub fn new(
+        tag: Option<&'a Tag>,
+        patch_path: TreePath,
+        replacement: &'a Node<MSG>,
+    ) -> Self {
+        ReplaceNode {
+            tag,
+            patch_path,
+            replacement,
+        }
+    }
+}
+
+impl_patch_traits!(ReplaceNode {
+    tag,
+    patch_path,
+    replacement
+});
+
+/// Remove the node at the `patch_path`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RemoveNode<'a> {
+    /// the tag of the node to be removed, if it is an element
+    pub tag: Option<&'a Tag>,
+    /// the path of the node to be removed
+    pub patch_path: TreePath,
+}
+
+impl<'a> RemoveNode<'a> {
+    /// create a patch which removes the node at the `patch_path`
+    pub fn new(tag: Option<&'a Tag>, patch_path: TreePath) -> Self {
+        RemoveNode { tag, patch_path }
+    }
+}
+
+/// Append the `children` into the element at the `patch_path`
+pub struct AppendChildren<'a, MSG> {
+    /// the tag of the element
+    pub tag: &'a Tag,
+    /// the path of the element
+    pub patch_path: TreePath,
+    /// the children to be appended
+    pub children: Vec<&'a Node<MSG>>,
+}
+
+impl<'a, MSG> AppendChildren<'a, MSG> {
+    /// create a patch which appends the `children` into the element at the `patch_path`
+    pub fn new(
+        tag: &'a Tag,
+        patch_path: TreePath,
+        children: Vec<&'a Node<MSG>>,
+    ) -> Self {
+        AppendChildren {
+            tag,
+            patch_path,
+            children,
+        }
+    }
+}
+
+impl_patch_traits!(AppendChildren {
+    tag,
+    patch_path,
+    children
+});
+
+/// Change the text of the text node at the `patch_path` from `old` to `new`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeText<'a> {
+    /// the old text, which is not needed to apply the patch but shows which text is changed
+    pub old: &'a Text,
+    /// the path of the text node
+    pub patch_path: TreePath,
+    /// the new text
+    pub new: &'a Text,
+}
+
+impl<'a> ChangeText<'a> {
+    /// create a patch which changes the text of the text node at the `patch_path`
+    pub fn new(old: &'a Text, patch_path: TreePath, new: &'a Text) -> Self {
+        ChangeText {
+            old,
+            patch_path,
+            new,
+        }
+    }
+}
+
+/// Change the comment at the `patch_path` from `old` to `new`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeComment<'a> {
+    /// the old comment, which is not needed to apply the patch but shows which comment is changed
+    pub old: &'a String,
+    /// the path of the comment
+    pub patch_path: TreePath,
+    /// the new comment
+    pub new: &'a String,
+}
+
+impl<'a> ChangeComment<'a> {
+    /// create a patch which changes the comment at the `patch_path`
+    pub fn new(old: &'a String, patch_path: TreePath, new: &'a String) -> Self {
+        ChangeComment {
+            old,
+            patch_path,
+            new,
+        }
+    }
+}
+
+/// Remove the children of the element at the `parent_path`, starting at the child at
+/// `start_index` up to its last child.
+///
+/// The diff creates this in place of the `RemoveNode` of each of the trailing children,
+/// such as when a long list is cleared, so the children are removed in a single loop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RemoveChildrenFrom<'a> {
+    /// the tag of the parent element
+    pub tag: &'a Tag,
+    /// the path of the parent element
+    pub parent_path: TreePath,
+    /// the index of the first child to be removed
+    pub start_index: usize,
+}
+
+impl<'a> RemoveChildrenFrom<'a> {
+    /// create a patch which removes the children of the element at the `parent_path`,
+    /// from the child at `start_index` up to its last child
+    pub fn new(
+        tag: &'a Tag,
+        parent_path: TreePath,
+        start_index: usize,
+    ) -> Self {
+        RemoveChildrenFrom {
+            tag,
+            parent_path,
+            start_index,
+        }
+    }
+}
+
+/// Patch as result of diffing the current_vdom and the new vdom.
+///
+/// The patches of mt-dom are converted into these, while the patches which mt-dom doesn't have
+/// are added here, so the diff of sauron can emit them.
+/// More patches may be added in a minor version, so the matches on it need a wildcard arm.
+#[non_exhaustive]
+pub enum Patch<'a, MSG> {
+    /// insert the node before the node at the path
+    InsertNode(InsertNode<'a, MSG>),
+    /// move the node at the path before another node of the same parent
+    MoveNode(MoveNode<'a>),
+    /// add or change the attributes of the element at the path
+    AddAttributes(AddAttributes<'a, MSG>),
+    /// remove the attributes of the element at the path
+    RemoveAttributes(RemoveAttributes<'a, MSG>),
+    /// replace the node at the path
+    ReplaceNode(ReplaceNode<'a, MSG>),
+    /// remove the node at the path
+    RemoveNode(RemoveNode<'a>),
+    /// remove the children of the element at the path, from the child at the start index
+    RemoveChildrenFrom(RemoveChildrenFrom<'a>),
+    /// append the children into the element at the path
+    AppendChildren(AppendChildren<'a, MSG>),
+    /// change the text of the text node at the path
+    ChangeText(ChangeText<'a>),
+    /// change the comment at the path
+    ChangeComment(ChangeComment<'a>),
+}
+
+impl<'a, MSG> Patch<'a, MSG> {
+    /// the path of the node where the patch is applied to
+    pub fn path(&self) -> &[usize] {
+        &self.tree_path().path
+    }
+
+    /// the tag of the node where the patch is applied to, if it is known
+    pub fn tag(&self) -> Option<&Tag> {
+        match self {
+            Patch::InsertNode(patch) => patch.tag,
+            Patch::MoveNode(patch) => patch.tag,
+            Patch::AddAttributes(patch) => Some(patch.tag),
+            Patch::RemoveAttributes(patch) => Some(patch.tag),
+            Patch::ReplaceNode(patch) => patch.tag,
+            Patch::RemoveNode(patch) => patch.tag,
+            Patch::RemoveChildrenFrom(patch) => Some(patch.tag),
+            Patch::AppendChildren(patch) => Some(patch.tag),
+            Patch::ChangeText(_) | Patch::ChangeComment(_) => None,
+        }
+    }
+
+    /// move the patch of a node which is diffed on its own under the `path`,
+    /// which is the path of that node in the whole tree
+    pub(crate) fn rebase(mut self, path: &[usize]) -> Self {
+        let tree_path = self.tree_path_mut();
+        tree_path.path.splice(..1, path.iter().copied());
+        if let Patch::MoveNode(MoveNode {
+            before_path: Some(before_path),
+            ..
+        }) = &mut self
+        {
+            before_path.path.splice(..1, path.iter().copied());
+        }
+        self
+    }
+
+    fn tree_path(&self) -> &TreePath {
+        match self {
+            Patch::InsertNode(patch) => &patch.patch_path,
+            Patch::MoveNode(patch) => &patch.patch_path,
+            Patch::AddAttributes(patch) => &patch.patch_path,
+            Patch::RemoveAttributes(patch) => &patch.patch_path,
+            Patch::ReplaceNode(patch) => &patch.patch_path,
+            Patch::RemoveNode(patch) => &patch.patch_path,
+            Patch::RemoveChildrenFrom(patch) => &patch.parent_path,
+            Patch::AppendChildren(patch) => &patch.patch_path,
+            Patch::ChangeText(patch) => &patch.patch_path,
+            Patch::ChangeComment(patch) => &patch.patch_path,
+        }
+    }
+
+    fn tree_path_mut(&mut self) -> &mut TreePath {
+        match self {
+            Patch::InsertNode(patch) => &mut patch.patch_path,
+            Patch::MoveNode(patch) => &mut patch.patch_path,
+            Patch::AddAttributes(patch) => &mut patch.patch_path,
+            Patch::RemoveAttributes(patch) => &mut patch.patch_path,
+            Patch::ReplaceNode(patch) => &mut patch.patch_path,
+            Patch::RemoveNode(patch) => &mut patch.patch_path,
+            Patch::RemoveChildrenFrom(patch) => &mut patch.parent_path,
+            Patch::AppendChildren(patch) => &mut patch.patch_path,
+            Patch::ChangeText(patch) => &mut patch.patch_path,
+            Patch::ChangeComment(patch) => &mut patch.patch_path,
+        }
+    }
+}
+
+impl<'a, MSG> From<MtPatch<'a, MSG>> for Patch<'a, MSG> {
+    fn from(patch: MtPatch<'a, MSG>) -> Self {
+        match patch {
+            mt_dom::Patch::InsertNode(patch) => {
+                InsertNode::new(patch.tag, patch.patch_path.into(), patch.node)
+                    .into()
+            }
+            mt_dom::Patch::AddAttributes(patch) => AddAttributes::new(
+                patch.tag,
+                patch.patch_path.into(),
+                patch.attrs,
+            )
+            .into(),
+            mt_dom::Patch::RemoveAttributes(patch) => RemoveAttributes::new(
+                patch.tag,
+                patch.patch_path.into(),
+                patch.attrs,
+            )
+            .into(),
+            mt_dom::Patch::ReplaceNode(patch) => ReplaceNode::new(
+                patch.tag,
+                patch.patch_path.into(),
+                patch.replacement,
+            )
+            .into(),
+            mt_dom::Patch::RemoveNode(patch) => {
+                RemoveNode::new(patch.tag, patch.patch_path.into()).into()
+            }
+            mt_dom::Patch::AppendChildren(patch) => AppendChildren::new(
+                patch.tag,
+                patch.patch_path.into(),
+                patch.children,
+            )
+            .into(),
+            mt_dom::Patch::ChangeText(patch) => {
+                ChangeText::new(patch.old, patch.patch_path.into(), patch.new)
+                    .into()
+            }
+            mt_dom::Patch::ChangeComment(patch) => ChangeComment::new(
+                patch.old,
+                patch.patch_path.into(),
+                patch.new,
+            )
+            .into(),
+        }
+    }
+}
+
+impl<'a, MSG> From<InsertNode<'a, MSG>> for Patch<'a, MSG> {
+    fn from(patch: InsertNode<'a, MSG>) -> Self {
+        Patch::InsertNode(patch)
+    }
+}
+
+impl<'a, MSG> From<MoveNode<'a>> for Patch<'a, MSG> {
+    fn from(patch: MoveNode<'a>) -> Self {
+        Patch::MoveNode(patch)
+    }
+}
+
+impl<'a, MSG> From<AddAttributes<'a, MSG>> for Patch<'a, MSG> {
+    fn from(patch: AddAttributes<'a, MSG>) -> Self {
+        Patch::AddAttributes(patch)
+    }
+}
+
+impl<'a, MSG> From<RemoveAttributes<'a, MSG>> for Patch<'a, MSG> {
+    fn from(patch: RemoveAttributes<'a, MSG>) -> Self {
+        Patch::RemoveAttributes(patch)
+    }
+}
+
+impl<'a, MSG> From<ReplaceNode<'a, MSG>> for Patch<'a, MSG> {
+    fn from(patch: ReplaceNode<'a, MSG>) -> Self {
+        Patch::ReplaceNode(patch)
+    }
+}
+
+impl<'a, MSG> From<RemoveNode<'a>> for Patch<'a, MSG> {
+    fn from(patch: RemoveNode<'a>) -> Self {
+        Patch::RemoveNode(patch)
+    }
+}
+
+impl<'a, MSG> From<RemoveChildrenFrom<'a>> for Patch<'a, MSG> {
+    fn from(patch: RemoveChildrenFrom<'a>) -> Self {
+        Patch::RemoveChildrenFrom(patch)
+    }
+}
+
+impl<'a, MSG> From<AppendChildren<'a, MSG>> for Patch<'a, MSG> {
+    fn from(patch: AppendChildren<'a, MSG>) -> Self {
+        Patch::AppendChildren(patch)
+    }
+}
+
+impl<'a, MSG> From<ChangeText<'a>> for Patch<'a, MSG> {
+    fn from(patch: ChangeText<'a>) -> Self {
+        Patch::ChangeText(patch)
+    }
+}
+
+impl<'a, MSG> From<ChangeComment<'a>> for Patch<'a, MSG> {
+    fn from(patch: ChangeComment<'a>) -> Self {
+        Patch::ChangeComment(patch)
+    }
+}
+
+// implemented by hand, since deriving these requires the MSG itself to implement them
+impl<MSG> Clone for Patch<'_, MSG> {
+    fn clone(&self) -> Self {
+        match self {
+            Patch::InsertNode(patch) => Patch::InsertNode(patch.clone()),
+            Patch::MoveNode(patch) => Patch::MoveNode(patch.clone()),
+            Patch::AddAttributes(patch) => Patch::AddAttributes(patch.clone()),
+            Patch::RemoveAttributes(patch) => {
+                Patch::RemoveAttributes(patch.clone())
+            }
+            Patch::ReplaceNode(patch) => Patch::ReplaceNode(patch.clone()),
+            Patch::RemoveNode(patch) => Patch::RemoveNode(patch.clone()),
+            Patch::RemoveChildrenFrom(patch) => {
+                Patch::RemoveChildrenFrom(patch.clone())
+            }
+            Patch::AppendChildren(patch) => {
+                Patch::AppendChildren(patch.clone())
+            }
+            Patch::ChangeText(patch) => Patch::ChangeText(patch.clone()),
+            Patch::ChangeComment(patch) => Patch::ChangeComment(patch.clone()),
+        }
+    }
+}
+
+impl<MSG> fmt::Debug for Patch<'_, MSG> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Patch::InsertNode(patch) => {
+                f.debug_tuple("InsertNode").field(patch).finish()
+            }
+            Patch::MoveNode(patch) => {
+                f.debug_tuple("MoveNode").field(patch).finish()
+            }
+            Patch::AddAttributes(patch) => {
+                f.debug_tuple("AddAttributes").field(patch).finish()
+            }
+            Patch::RemoveAttributes(patch) => {
+                f.debug_tuple("RemoveAttributes").field(patch).finish()
+            }
+            Patch::ReplaceNode(patch) => {
+                f.debug_tuple("ReplaceNode").field(patch).finish()
+            }
+            Patch::RemoveNode(patch) => {
+                f.debug_tuple("RemoveNode").field(patch).finish()
+            }
+            Patch::RemoveChildrenFrom(patch) => {
+                f.debug_tuple("RemoveChildrenFrom").field(patch).finish()
+            }
+            Patch::AppendChildren(patch) => {
+                f.debug_tuple("AppendChildren").field(patch).finish()
+            }
+            Patch::ChangeText(patch) => {
+                f.debug_tuple("ChangeText").field(patch).finish()
+            }
+            Patch::ChangeComment(patch) => {
+                f.debug_tuple("ChangeComment").field(patch).finish()
+            }
+        }
+    }
+}
+
+impl<MSG> PartialEq for Patch<'_, MSG> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Patch::InsertNode(a), Patch::InsertNode(b)) => a == b,
+            (Patch::MoveNode(a), Patch::MoveNode(b)) => a == b,
+            (Patch::AddAttributes(a), Patch::AddAttributes(b)) => a == b,
+            (Patch::RemoveAttributes(a), Patch::RemoveAttributes(b)) => a == b,
+            (Patch::ReplaceNode(a), Patch::ReplaceNode(b)) => a == b,
+            (Patch::RemoveNode(a), Patch::RemoveNode(b)) => a == b,
+            (Patch::RemoveChildrenFrom(a), Patch::RemoveChildrenFrom(b)) => {
+                a == b
+            }
+            (Patch::AppendChildren(a), Patch::AppendChildren(b)) => a == b,
+            (Patch::ChangeText(a), Patch::ChangeText(b)) => a == b,
+            (Patch::ChangeComment(a), Patch::ChangeComment(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// The kind of the patch, which doesn't borrow the nodes of the patch
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PatchKind {
+    /// a node is inserted before the node at the path
+    InsertNode,
+    /// the node at the path is moved before another node of the same parent
+    MoveNode,
+    /// attributes are added or changed in the element at the path
+    AddAttributes,
+    /// attributes are removed from the element at the path
+    RemoveAttributes,
+    /// the node at the path is replaced
+    ReplaceNode,
+    /// the node at the path is removed
+    RemoveNode,
+    /// the children of the element at the path are removed from the start index
+    RemoveChildrenFrom,
+    /// children are appended into the element at the path
+    AppendChildren,
+    /// the text of the text node at the path is changed
+    ChangeText,
+    /// the comment at the path is changed
+    ChangeComment,
+}
+
+impl PatchKind {
+    /// the name of the patch kind, ie: `ReplaceNode`
+    pub fn name(&self) -> &'static str {
+        match self {
+            PatchKind::InsertNode => "InsertNode",
+            PatchKind::MoveNode => "MoveNode",
+            PatchKind::AddAttributes => "AddAttributes",
+            PatchKind::RemoveAttributes => "RemoveAttributes",
+            PatchKind::ReplaceNode => "ReplaceNode",
+            PatchKind::RemoveNode => "RemoveNode",
+            PatchKind::RemoveChildrenFrom => "RemoveChildrenFrom",
+            PatchKind::AppendChildren => "AppendChildren",
+            PatchKind::ChangeText => "ChangeText",
+            PatchKind::ChangeComment => "ChangeComment",
+        }
+    }
+}
+
+impl<MSG> From<&Patch<'_, MSG>> for PatchKind {
+    fn from(patch: &Patch<'_, MSG>) -> Self {
+        match patch {
+            Patch::InsertNode(_) => PatchKind::InsertNode,
+            Patch::MoveNode(_) => PatchKind::MoveNode,
+            Patch::AddAttributes(_) => PatchKind::AddAttributes,
+            Patch::RemoveAttributes(_) => PatchKind::RemoveAttributes,
+            Patch::ReplaceNode(_) => PatchKind::ReplaceNode,
+            Patch::RemoveNode(_) => PatchKind::RemoveNode,
+            Patch::RemoveChildrenFrom(_) => PatchKind::RemoveChildrenFrom,
+            Patch::AppendChildren(_) => PatchKind::AppendChildren,
+            Patch::ChangeText(_) => PatchKind::ChangeText,
+            Patch::ChangeComment(_) => PatchKind::ChangeComment,
+        }
+    }
+}
+
+impl fmt::Display for PatchKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -0,0 +1,4 @@
+//! Provides the widgets which are common to most apps, built on top of the html functions
+pub mod virtual_list;
+
+pub use virtual_list::VirtualList;
@@ -0,0 +1,155 @@
+//! provides [`VirtualList`] which renders only the rows of a large list which are visible
+//! in its viewport, so a list of 50k rows doesn't create and diff 50k nodes on every update.
+//!
+//! All the rows have the same height. The rows above and below the visible rows are replaced
+//! with spacers of the same height, so the scrollbar has the size and position
+//! as if all the rows were rendered.
+//!
+//! # Examples
+//! ```rust
+//! use sauron::prelude::*;
+//! use sauron::widgets::VirtualList;
+//!
+//! let mut list = VirtualList::new(20.0, 100.0).overscan(0);
+//! list.set_scroll_top(200.0);
+//! assert_eq!(list.visible_range(50_000), 10..15);
+//!
+//! let items: Vec<u32> = (0..50_000).collect();
+//! let view: Node<f64> = list.view(&items, |_index, item| li(vec![], vec![text(item)]), |top| top);
+//! ```
+use crate::{
+    html::{
+        attributes::{class, key, styles},
+        div,
+        events::on_scroll,
+        units::px,
+    },
+    Node,
+};
+use std::ops::Range;
+
+/// The scroll position and the dimension of a list which only renders its visible rows.
+///
+/// The app keeps the `VirtualList` in its model and updates its scroll position
+/// with the msg created in [`VirtualList::view`] when the list is scrolled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VirtualList {
+    /// the height of each row in px
+    row_height: f64,
+    /// the height of the scrollable viewport of the list in px
+    viewport_height: f64,
+    /// the number of the rows rendered above and below the visible rows
+    overscan: usize,
+    /// the scroll position of the viewport in px
+    scroll_top: f64,
+}
+
+impl VirtualList {
+    /// create a list where each row is `row_height` px high,
+    /// which is displayed in a viewport of `viewport_height` px
+    pub fn new(row_height: f64, viewport_height: f64) -> Self {
+        VirtualList {
+            row_height: row_height.max(1.0),
+            viewport_height: viewport_height.max(0.0),
+            overscan: 3,
+            scroll_top: 0.0,
+        }
+    }
+
+    /// set the number of the rows rendered above and below the visible rows,
+    /// so the rows are already there when the list is scrolled fast. Defaults to 3.
+    pub fn overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    /// set the height of the viewport, ie: when the window is resized
+    pub fn set_viewport_height(&mut self, viewport_height: f64) {
+        self.viewport_height = viewport_height.max(0.0);
+    }
+
+    /// set the scroll position of the viewport, this is called with the value
+    /// from the scroll msg of the list
+    pub fn set_scroll_top(&mut self, scroll_top: f64) {
+        self.scroll_top = scroll_top.max(0.0);
+    }
+
+    /// the scroll position of the viewport in px
+    pub fn scroll_top(&self) -> f64 {
+        self.scroll_top
+    }
+
+    /// the indexes of the rows which are rendered out of the `item_count` rows,
+    /// these are the visible rows and the overscan rows around them
+    pub fn visible_range(&self, item_count: usize) -> Range<usize> {
+        let first_visible =
+            (self.scroll_top / self.row_height).floor() as usize;
+        let visible_count =
+            (self.viewport_height / self.row_height).ceil() as usize;
+        let start = first_visible.saturating_sub(self.overscan).min(item_count);
+        let end = first_visible
+            .saturating_add(visible_count)
+            .saturating_add(self.overscan)
+            .min(item_count);
+        start..end
+    }
+
+    /// the view of the list, rendering only the rows in the [`visible_range`](Self::visible_range)
+    /// of the `items` with `render_row`, which is called with the index of the item.
+    ///
+    /// Each row is keyed with its index, so the rows which stays visible are not recreated
+    /// when the list is scrolled. The MSG created from the new scroll position with `on_scroll`
+    /// should be passed into [`set_scroll_top`](Self::set_scroll_top).
+    pub fn view<T, MSG, R, S>(
+        &self,
+        items: &[T],
+        render_row: R,
+        on_scroll_top: S,
+    ) -> Node<MSG>
+    where
+        R: Fn(usize, &T) -> Node<MSG>,
+        S: Fn(f64) -> MSG + 'static,
+        MSG: 'static,
+    {
+        let range = self.visible_range(items.len());
+        let space_above = range.start as f64 * self.row_height;
+        let space_below = (items.len() - range.end) as f64 * self.row_height;
+        let rows =
+            items[range.clone()].iter().zip(range).map(|(item, index)| {
+                div(
+                    vec![
+                        key(index),
+                        class("virtual-list__row"),
+                        styles([("height", px(self.row_height))]),
+                    ],
+                    vec![render_row(index, item)],
+                )
+            });
+        div(
+            vec![
+                class("virtual-list"),
+                styles([
+                    ("height", px(self.viewport_height)),
+                    ("overflow-y", "auto".to_string()),
+                ]),
+                on_scroll(move |(scroll_top, _scroll_left)| {
+                    on_scroll_top(f64::from(scroll_top))
+                }),
+            ],
+            std::iter::once(spacer(space_above))
+                .chain(rows)
+                .chain(std::iter::once(spacer(space_below))),
+        )
+    }
+}
+
+/// the empty element which takes the place of the rows which are not rendered
+fn spacer<MSG>(height: f64) -> Node<MSG> {
+    div(
+        vec![
+            class("virtual-list__spacer"),
+            styles([("height", px(height))]),
+        ],
+        vec![],
+    )
+}
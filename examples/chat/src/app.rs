@@ -0,0 +1,235 @@
+use sauron::{dom::events::KeyboardEvent, prelude::*};
+use serde::{Deserialize, Serialize};
+use web_sys::{MessageEvent, WebSocket};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChatMessage {
+    pub user: String,
+    pub text: String,
+}
+
+pub enum Msg {
+    Connected(WebSocket),
+    Disconnected,
+    /// the text frame received from the server
+    Received(String),
+    InputChanged(String),
+    Send,
+    NoOp,
+}
+
+pub struct App {
+    user: String,
+    server_url: String,
+    socket: Option<WebSocket>,
+    messages: Vec<ChatMessage>,
+    /// the messages which are sent while there is no connection,
+    /// these are sent once connected
+    outbox: Vec<ChatMessage>,
+    input: String,
+}
+
+impl App {
+    /// the chat of `user` into the websocket server at `server_url`
+    pub fn new(user: &str, server_url: &str) -> Self {
+        App {
+            user: user.to_string(),
+            server_url: server_url.to_string(),
+            socket: None,
+            messages: vec![],
+            outbox: vec![],
+            input: String::new(),
+        }
+    }
+
+    /// the messages which are received or sent
+    pub fn messages(&self) -> &[ChatMessage] {
+        &self.messages
+    }
+
+    /// the messages which are waiting for the connection
+    pub fn outbox(&self) -> &[ChatMessage] {
+        &self.outbox
+    }
+
+    /// open the websocket, dispatching the msgs of its events into the program
+    fn connect(&self) -> Cmd<Self, Msg> {
+        let server_url = self.server_url.clone();
+        Cmd::new(move |program: Program<Self, Msg>| {
+            let socket = match WebSocket::new(&server_url) {
+                Ok(socket) => socket,
+                Err(err) => {
+                    log::error!(
+                        "unable to connect to {}: {:?}",
+                        server_url,
+                        err
+                    );
+                    return;
+                }
+            };
+
+            let program_clone = program.clone();
+            let on_message: Closure<dyn Fn(MessageEvent)> =
+                Closure::wrap(Box::new(move |event: MessageEvent| {
+                    if let Some(frame) = event.data().as_string() {
+                        program_clone.dispatch(Msg::Received(frame));
+                    }
+                }));
+            socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+            on_message.forget();
+
+            let program_clone = program.clone();
+            let socket_clone = socket.clone();
+            let on_open: Closure<dyn Fn(web_sys::Event)> =
+                Closure::wrap(Box::new(move |_| {
+                    program_clone
+                        .dispatch(Msg::Connected(socket_clone.clone()));
+                }));
+            socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+            on_open.forget();
+
+            let on_close: Closure<dyn Fn(web_sys::Event)> =
+                Closure::wrap(Box::new(move |_| {
+                    program.dispatch(Msg::Disconnected);
+                }));
+            socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+            on_close.forget();
+        })
+        .no_render()
+    }
+
+    /// send the messages through the socket
+    fn send(socket: WebSocket, messages: Vec<ChatMessage>) -> Cmd<Self, Msg> {
+        Cmd::new(move |_program| {
+            for message in messages.iter() {
+                let frame =
+                    serde_json::to_string(message).expect("must serialize");
+                if let Err(err) = socket.send_with_str(&frame) {
+                    log::error!("unable to send the message: {:?}", err);
+                }
+            }
+        })
+        .no_render()
+    }
+
+    fn view_message(&self, message: &ChatMessage) -> Node<Msg> {
+        li(
+            [
+                class("message"),
+                classes_flag([("own", message.user == self.user)]),
+            ],
+            [
+                span([class("user")], [text(&message.user)]),
+                span([class("text")], [text(&message.text)]),
+            ],
+        )
+    }
+
+    fn view_status(&self) -> Node<Msg> {
+        let status = if self.socket.is_some() {
+            "connected"
+        } else {
+            "offline"
+        };
+        div([class("status"), class(status)], [text(status)])
+    }
+}
+
+impl Application<Msg> for App {
+    fn init(&mut self) -> Cmd<Self, Msg> {
+        self.connect()
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Connected(socket) => {
+                self.socket = Some(socket.clone());
+                let outbox = self.outbox.drain(..).collect();
+                Cmd::batch([Cmd::none(), Self::send(socket, outbox)])
+            }
+            Msg::Disconnected => {
+                self.socket = None;
+                Cmd::none()
+            }
+            Msg::Received(frame) => match serde_json::from_str(&frame) {
+                Ok(message) => {
+                    self.messages.push(message);
+                    Cmd::none()
+                }
+                Err(err) => {
+                    log::warn!(
+                        "ignoring the malformed frame {:?}: {}",
+                        frame,
+                        err
+                    );
+                    Cmd::none().no_render()
+                }
+            },
+            Msg::InputChanged(input) => {
+                self.input = input;
+                Cmd::none()
+            }
+            Msg::Send => {
+                let text = self.input.trim().to_string();
+                if text.is_empty() {
+                    return Cmd::none().no_render();
+                }
+                self.input.clear();
+                let message = ChatMessage {
+                    user: self.user.clone(),
+                    text,
+                };
+                self.messages.push(message.clone());
+                match &self.socket {
+                    Some(socket) => Cmd::batch([
+                        Cmd::none(),
+                        Self::send(socket.clone(), vec![message]),
+                    ]),
+                    None => {
+                        self.outbox.push(message);
+                        Cmd::none()
+                    }
+                }
+            }
+            Msg::NoOp => Cmd::none().no_render(),
+        }
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            [class("chat")],
+            [
+                self.view_status(),
+                ul(
+                    [class("messages")],
+                    self.messages
+                        .iter()
+                        .map(|message| self.view_message(message)),
+                ),
+                input(
+                    [
+                        class("compose"),
+                        r#type("text"),
+                        placeholder("Say something"),
+                        value(&self.input),
+                        on_input(|event: InputEvent| {
+                            Msg::InputChanged(event.value.to_string())
+                        }),
+                        on_keypress(|event: KeyboardEvent| {
+                            if event.key() == "Enter" {
+                                Msg::Send
+                            } else {
+                                Msg::NoOp
+                            }
+                        }),
+                    ],
+                    [],
+                ),
+                button(
+                    [class("send"), on_click(|_| Msg::Send)],
+                    [text("Send")],
+                ),
+            ],
+        )
+    }
+}
@@ -0,0 +1,15 @@
+#![deny(warnings)]
+use sauron::prelude::*;
+
+pub use app::{App, ChatMessage, Msg};
+
+pub mod app;
+
+const SERVER_URL: &str = "ws://localhost:4010/chat";
+
+#[wasm_bindgen(start)]
+pub fn main() {
+    console_log::init_with_level(log::Level::Trace).unwrap();
+    console_error_panic_hook::set_once();
+    Program::mount_to_body(App::new("guest", SERVER_URL));
+}
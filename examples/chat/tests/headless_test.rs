@@ -0,0 +1,94 @@
+#![deny(warnings)]
+//! The chat app as a fixture of the messages streamed from a websocket,
+//! driven with the [`HeadlessProgram`].
+//!
+//! The socket is not opened in the headless harness,
+//! the frames are dispatched as the msgs the socket would have dispatched.
+use chat::{App, ChatMessage, Msg};
+use sauron::backend::HeadlessProgram;
+
+fn frame(user: &str, text: &str) -> Msg {
+    Msg::Received(
+        serde_json::to_string(&ChatMessage {
+            user: user.to_string(),
+            text: text.to_string(),
+        })
+        .unwrap(),
+    )
+}
+
+#[test]
+fn received_frames_are_appended() {
+    let mut program =
+        HeadlessProgram::mount(App::new("alice", "ws://localhost/chat"));
+    assert!(program.html().contains(r#"<ul class="messages"></ul>"#));
+    assert!(program.html().contains(r#"<div class="status offline">"#));
+
+    program
+        .dispatch_multiple(vec![frame("bob", "hi"), frame("carol", "hello")])
+        .unwrap();
+    let html = program.html();
+    assert_eq!(html.matches(r#"<li class="message">"#).count(), 2);
+    assert!(html.contains(
+        r#"<span class="user">bob</span><span class="text">hi</span>"#
+    ));
+    assert_eq!(program.app().messages().len(), 2);
+}
+
+#[test]
+fn malformed_frames_are_ignored() {
+    let mut program =
+        HeadlessProgram::mount(App::new("alice", "ws://localhost/chat"));
+    let patches = program
+        .dispatch(Msg::Received("not json".to_string()))
+        .unwrap();
+    assert_eq!(patches, 0);
+    assert!(program.app().messages().is_empty());
+}
+
+#[test]
+fn messages_sent_offline_wait_in_the_outbox() {
+    let mut program =
+        HeadlessProgram::mount(App::new("alice", "ws://localhost/chat"));
+
+    // blank messages are not sent
+    program
+        .dispatch_multiple(vec![
+            Msg::InputChanged("   ".to_string()),
+            Msg::Send,
+        ])
+        .unwrap();
+    assert!(program.app().messages().is_empty());
+
+    program
+        .dispatch_multiple(vec![
+            Msg::InputChanged("are you there?".to_string()),
+            Msg::Send,
+        ])
+        .unwrap();
+    let html = program.html();
+    assert!(html.contains(r#"<li class="message own">"#));
+    assert!(html.contains("are you there?"));
+    assert_eq!(
+        program.app().outbox(),
+        &[ChatMessage {
+            user: "alice".to_string(),
+            text: "are you there?".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn long_conversation() {
+    let mut program =
+        HeadlessProgram::mount(App::new("alice", "ws://localhost/chat"));
+    for index in 0..500 {
+        let user = if index % 2 == 0 { "bob" } else { "carol" };
+        program
+            .dispatch(frame(user, &format!("message {}", index)))
+            .unwrap();
+    }
+    let html = program.html();
+    assert_eq!(html.matches(r#"<li class="message">"#).count(), 500);
+    assert!(html.contains("message 499"));
+}
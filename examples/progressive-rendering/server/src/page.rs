@@ -14,6 +14,7 @@ pub fn index(app: &App) -> Node<Msg> {
           <head>
             <meta http-equiv="Content-type" content="text/html; charset=utf-8"/>
             <title>"Progressive Rendering with Sauron"</title>
+            {for node in app.head().nodes() { node }}
             <style>{text(style)}</style>
             <script type="module">
                 {text!("
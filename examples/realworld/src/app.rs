@@ -0,0 +1,289 @@
+use sauron::{dom::FetchError, prelude::*, remote_data::RemoteData};
+use serde::Deserialize;
+
+const API_URL: &str = "https://api.realworld.io/api";
+
+/// the pages of the app, which are selected with the hash of the url
+#[derive(Debug, Clone, PartialEq)]
+pub enum Route {
+    Home,
+    Article(String),
+    Profile(String),
+    NotFound,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    UrlChanged(String),
+    ReceivedArticles(RemoteData<ArticleList, FetchError>),
+    ReceivedArticle(RemoteData<SingleArticle, FetchError>),
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Article {
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub tag_list: Vec<String>,
+    pub author: Author,
+    #[serde(default)]
+    pub favorites_count: u32,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Author {
+    pub username: String,
+}
+
+/// the response of the articles endpoint
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ArticleList {
+    pub articles: Vec<Article>,
+    pub articles_count: usize,
+}
+
+/// the response of the article endpoint
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SingleArticle {
+    pub article: Article,
+}
+
+pub struct App {
+    route: Route,
+    articles: RemoteData<ArticleList, FetchError>,
+    article: RemoteData<SingleArticle, FetchError>,
+}
+
+impl Route {
+    /// the route of the hash of the url, ie: `#/article/how-to-train-your-dragon`
+    pub fn from_hash(hash: &str) -> Self {
+        let path = hash.trim_start_matches('#').trim_start_matches('/');
+        let segments: Vec<&str> = path.split('/').collect();
+        match segments.as_slice() {
+            [""] => Route::Home,
+            ["article", slug] if !slug.is_empty() => {
+                Route::Article(slug.to_string())
+            }
+            ["profile", username] if !username.is_empty() => {
+                Route::Profile(username.to_string())
+            }
+            _ => Route::NotFound,
+        }
+    }
+
+    /// the hash of the url of this route
+    pub fn to_hash(&self) -> String {
+        match self {
+            Route::Home => "#/".to_string(),
+            Route::Article(slug) => format!("#/article/{}", slug),
+            Route::Profile(username) => format!("#/profile/{}", username),
+            Route::NotFound => "#/404".to_string(),
+        }
+    }
+}
+
+impl App {
+    /// the app at the page of the `hash` of the url
+    pub fn new(hash: &str) -> Self {
+        App {
+            route: Route::from_hash(hash),
+            articles: RemoteData::NotAsked,
+            article: RemoteData::NotAsked,
+        }
+    }
+
+    /// the page the app is displaying
+    pub fn route(&self) -> &Route {
+        &self.route
+    }
+
+    /// fetch the data which is displayed in the current page
+    fn fetch_route(&self) -> Cmd<Self, Msg> {
+        match &self.route {
+            Route::Home => Http::fetch_remote_data(
+                &format!("{}/articles?limit=10", API_URL),
+                Msg::ReceivedArticles,
+            ),
+            Route::Article(slug) => Http::fetch_remote_data(
+                &format!("{}/articles/{}", API_URL, slug),
+                Msg::ReceivedArticle,
+            ),
+            Route::Profile(username) => Http::fetch_remote_data(
+                &format!("{}/articles?author={}&limit=10", API_URL, username),
+                Msg::ReceivedArticles,
+            ),
+            Route::NotFound => Cmd::none(),
+        }
+    }
+
+    fn view_nav(&self) -> Node<Msg> {
+        nav(
+            [class("navbar")],
+            [
+                a(
+                    [class("navbar-brand"), href(Route::Home.to_hash())],
+                    [text("conduit")],
+                ),
+                ul(
+                    [class("nav")],
+                    [li(
+                        [class("nav-item")],
+                        [a(
+                            [
+                                class("nav-link"),
+                                classes_flag([(
+                                    "active",
+                                    self.route == Route::Home,
+                                )]),
+                                href(Route::Home.to_hash()),
+                            ],
+                            [text("Home")],
+                        )],
+                    )],
+                ),
+            ],
+        )
+    }
+
+    fn view_remote<T>(
+        data: &RemoteData<T, FetchError>,
+        view: impl FnOnce(&T) -> Node<Msg>,
+    ) -> Node<Msg> {
+        match data {
+            RemoteData::NotAsked | RemoteData::Loading => {
+                div([class("loading")], [text("Loading...")])
+            }
+            RemoteData::Failure(err) => {
+                div([class("error")], [text(err.to_string())])
+            }
+            RemoteData::Success(data) => view(data),
+        }
+    }
+
+    fn view_article_preview(article: &Article) -> Node<Msg> {
+        div(
+            [class("article-preview"), key(&article.slug)],
+            [
+                a(
+                    [
+                        class("author"),
+                        href(
+                            Route::Profile(article.author.username.clone())
+                                .to_hash(),
+                        ),
+                    ],
+                    [text(&article.author.username)],
+                ),
+                a(
+                    [
+                        class("preview-link"),
+                        href(Route::Article(article.slug.clone()).to_hash()),
+                    ],
+                    [
+                        h1([], [text(&article.title)]),
+                        p([], [text(&article.description)]),
+                    ],
+                ),
+                ul(
+                    [class("tag-list")],
+                    article.tag_list.iter().map(|tag| {
+                        li([class("tag-pill"), key(tag)], [text(tag)])
+                    }),
+                ),
+            ],
+        )
+    }
+
+    fn view_article_list(&self) -> Node<Msg> {
+        Self::view_remote(&self.articles, |list| {
+            div(
+                [class("article-list")],
+                list.articles.iter().map(Self::view_article_preview),
+            )
+        })
+    }
+
+    fn view_article(&self) -> Node<Msg> {
+        Self::view_remote(&self.article, |single| {
+            let article = &single.article;
+            div(
+                [class("article-page")],
+                [
+                    h1([], [text(&article.title)]),
+                    a(
+                        [
+                            class("author"),
+                            href(
+                                Route::Profile(article.author.username.clone())
+                                    .to_hash(),
+                            ),
+                        ],
+                        [text(&article.author.username)],
+                    ),
+                    div(
+                        [class("article-content")],
+                        [p([], [text(&article.body)])],
+                    ),
+                ],
+            )
+        })
+    }
+
+    fn view_page(&self) -> Node<Msg> {
+        match &self.route {
+            Route::Home => div(
+                [class("home-page")],
+                [
+                    h1([class("logo-font")], [text("conduit")]),
+                    self.view_article_list(),
+                ],
+            ),
+            Route::Article(_) => self.view_article(),
+            Route::Profile(username) => div(
+                [class("profile-page")],
+                [h4([], [text(username)]), self.view_article_list()],
+            ),
+            Route::NotFound => {
+                div([class("not-found")], [text("Page not found")])
+            }
+        }
+    }
+}
+
+impl Application<Msg> for App {
+    fn init(&mut self) -> Cmd<Self, Msg> {
+        Cmd::batch([Window::on_hashchange(Msg::UrlChanged), self.fetch_route()])
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::UrlChanged(hash) => {
+                let route = Route::from_hash(&hash);
+                if route == self.route {
+                    return Cmd::none().no_render();
+                }
+                self.route = route;
+                self.articles = RemoteData::Loading;
+                self.article = RemoteData::Loading;
+                self.fetch_route()
+            }
+            Msg::ReceivedArticles(articles) => {
+                self.articles = articles;
+                Cmd::none()
+            }
+            Msg::ReceivedArticle(article) => {
+                self.article = article;
+                Cmd::none()
+            }
+        }
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div([class("realworld")], [self.view_nav(), self.view_page()])
+    }
+}
@@ -0,0 +1,13 @@
+#![deny(warnings)]
+use sauron::prelude::*;
+
+pub use app::{App, Article, Author, Msg, Route};
+
+pub mod app;
+
+#[wasm_bindgen(start)]
+pub fn main() {
+    console_log::init_with_level(log::Level::Trace).unwrap();
+    console_error_panic_hook::set_once();
+    Program::mount_to_body(App::new(&Window::get_hash()));
+}
@@ -0,0 +1,144 @@
+#![deny(warnings)]
+//! The realworld app as a fixture of the routing and the http responses,
+//! driven with the [`HeadlessProgram`].
+//!
+//! The fetch commands are not executed in the headless harness,
+//! the responses are dispatched as the msgs the commands would have dispatched.
+use realworld::{App, Msg, Route};
+use sauron::{
+    backend::HeadlessProgram, dom::FetchError, remote_data::RemoteData,
+};
+
+const ARTICLES: &str = r#"{
+    "articles": [
+        {
+            "slug": "how-to-train-your-dragon",
+            "title": "How to train your dragon",
+            "description": "Ever wonder how?",
+            "tagList": ["dragons", "training"],
+            "author": { "username": "jake" },
+            "favoritesCount": 3
+        },
+        {
+            "slug": "how-to-tame-a-cat",
+            "title": "How to tame a cat",
+            "description": "You can't",
+            "tagList": [],
+            "author": { "username": "jane" },
+            "favoritesCount": 0
+        }
+    ],
+    "articlesCount": 2
+}"#;
+
+const ARTICLE: &str = r#"{
+    "article": {
+        "slug": "how-to-train-your-dragon",
+        "title": "How to train your dragon",
+        "description": "Ever wonder how?",
+        "body": "You have to believe",
+        "tagList": ["dragons", "training"],
+        "author": { "username": "jake" }
+    }
+}"#;
+
+#[test]
+fn routes_from_the_hash() {
+    assert_eq!(Route::from_hash(""), Route::Home);
+    assert_eq!(Route::from_hash("#/"), Route::Home);
+    assert_eq!(
+        Route::from_hash("#/article/how-to-train-your-dragon"),
+        Route::Article("how-to-train-your-dragon".to_string())
+    );
+    assert_eq!(
+        Route::from_hash("#/profile/jake"),
+        Route::Profile("jake".to_string())
+    );
+    assert_eq!(Route::from_hash("#/article/"), Route::NotFound);
+    assert_eq!(Route::from_hash("#/settings/extra"), Route::NotFound);
+
+    let route = Route::Article("how-to-tame-a-cat".to_string());
+    assert_eq!(Route::from_hash(&route.to_hash()), route);
+}
+
+#[test]
+fn home_page_lists_the_fetched_articles() {
+    let mut program = HeadlessProgram::mount(App::new("#/"));
+    assert!(program
+        .html()
+        .contains(r#"<div class="loading">Loading...</div>"#));
+
+    program
+        .dispatch(Msg::ReceivedArticles(RemoteData::Success(
+            serde_json::from_str(ARTICLES).unwrap(),
+        )))
+        .unwrap();
+    let html = program.html();
+    assert!(!html.contains("Loading..."));
+    assert_eq!(html.matches(r#"class="article-preview""#).count(), 2);
+    assert!(html.contains("<h1>How to train your dragon</h1>"));
+    assert!(html.contains(r##"href="#/article/how-to-tame-a-cat""##));
+    assert!(html.contains(r#"<li class="tag-pill" key="dragons">dragons</li>"#));
+}
+
+#[test]
+fn navigate_to_an_article_and_back() {
+    let mut program = HeadlessProgram::mount(App::new("#/"));
+    program
+        .dispatch(Msg::ReceivedArticles(RemoteData::Success(
+            serde_json::from_str(ARTICLES).unwrap(),
+        )))
+        .unwrap();
+
+    program
+        .dispatch(Msg::UrlChanged(
+            "#/article/how-to-train-your-dragon".to_string(),
+        ))
+        .unwrap();
+    assert_eq!(
+        program.app().route(),
+        &Route::Article("how-to-train-your-dragon".to_string())
+    );
+    assert!(program.html().contains("Loading..."));
+
+    program
+        .dispatch(Msg::ReceivedArticle(RemoteData::Success(
+            serde_json::from_str(ARTICLE).unwrap(),
+        )))
+        .unwrap();
+    let html = program.html();
+    assert!(html.contains(r#"class="article-page""#));
+    assert!(html.contains("<p>You have to believe</p>"));
+
+    // the same url doesn't refetch nor rerender
+    assert_eq!(
+        program
+            .dispatch(Msg::UrlChanged(
+                "#/article/how-to-train-your-dragon".to_string()
+            ))
+            .unwrap(),
+        0
+    );
+
+    program.dispatch(Msg::UrlChanged("#/".to_string())).unwrap();
+    assert!(program.html().contains(r#"class="home-page""#));
+}
+
+#[test]
+fn failed_request_is_displayed() {
+    let mut program = HeadlessProgram::mount(App::new("#/profile/jake"));
+    program
+        .dispatch(Msg::ReceivedArticles(RemoteData::Failure(
+            FetchError::Status(404),
+        )))
+        .unwrap();
+    let html = program.html();
+    assert!(html.contains(r#"class="profile-page""#));
+    assert!(html.contains(r#"<div class="error">http status: 404</div>"#));
+}
+
+#[test]
+fn unknown_route_is_not_found() {
+    let program = HeadlessProgram::mount(App::new("#/nowhere/at/all"));
+    assert!(program.html().contains("Page not found"));
+}
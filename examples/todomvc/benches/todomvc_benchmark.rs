@@ -0,0 +1,52 @@
+//! The dispatch, diff and patch cycle of the todomvc app in the headless harness.
+//!
+//! ```sh
+//! cargo bench -p todomvc
+//! ```
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use sauron::backend::HeadlessProgram;
+use todomvc::{Model, Msg};
+
+fn with_todos(count: usize) -> HeadlessProgram<Model, Msg> {
+    let mut program = HeadlessProgram::mount(Model::new());
+    program
+        .dispatch_multiple((0..count).flat_map(|index| {
+            vec![Msg::Update(format!("todo {}", index)), Msg::Add]
+        }))
+        .unwrap();
+    program
+}
+
+fn bench_todomvc(c: &mut Criterion) {
+    c.bench_function("add 1k todos one by one", |b| {
+        b.iter(|| {
+            let mut program = HeadlessProgram::mount(Model::new());
+            for index in 0..1_000 {
+                program
+                    .dispatch_multiple(vec![
+                        Msg::Update(format!("todo {}", index)),
+                        Msg::Add,
+                    ])
+                    .unwrap();
+            }
+            black_box(program)
+        })
+    });
+
+    let mut program = with_todos(1_000);
+    c.bench_function("toggle all of 1k todos", |b| {
+        b.iter(|| black_box(program.dispatch(Msg::ToggleAll).unwrap()))
+    });
+
+    c.bench_function("delete the first of 1k todos", |b| {
+        b.iter_batched(
+            || with_todos(1_000),
+            |mut program| black_box(program.dispatch(Msg::Delete(0)).unwrap()),
+            criterion::BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_todomvc);
+criterion_main!(benches);
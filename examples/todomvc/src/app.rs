@@ -134,7 +134,7 @@ impl Entry {
 }
 
 impl Model {
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Model {
             entries: vec![],
             visibility: Visibility::All,
@@ -1,12 +1,12 @@
 #![deny(warnings)]
-use app::Model;
+pub use app::{Model, Msg, Visibility};
 use sauron::Program;
 use wasm_bindgen::prelude::*;
 
 #[macro_use]
 extern crate log;
 
-mod app;
+pub mod app;
 
 #[cfg(feature = "wee_alloc")]
 #[global_allocator]
@@ -0,0 +1,92 @@
+#![deny(warnings)]
+//! The todomvc app as a fixture of the keyed lists, driven with the [`HeadlessProgram`].
+use sauron::backend::HeadlessProgram;
+use todomvc::{Model, Msg, Visibility};
+
+fn add_todo(program: &mut HeadlessProgram<Model, Msg>, description: &str) {
+    program
+        .dispatch_multiple(vec![Msg::Update(description.to_string()), Msg::Add])
+        .unwrap();
+}
+
+#[test]
+fn add_toggle_and_delete_todos() {
+    let mut program = HeadlessProgram::mount(Model::new());
+    assert!(program.html().contains(r#"<ul class="todo-list"></ul>"#));
+
+    add_todo(&mut program, "milk");
+    add_todo(&mut program, "eggs");
+    add_todo(&mut program, "bread");
+    let html = program.html();
+    assert_eq!(html.matches(r#"class="todo""#).count(), 3);
+    assert!(html.contains("<label>milk</label>"));
+    assert!(html.contains("<strong>3</strong>"));
+
+    program.dispatch(Msg::Toggle(1)).unwrap();
+    let html = program.html();
+    assert_eq!(html.matches(r#"class="todo completed""#).count(), 1);
+    assert!(html.contains("<strong>2</strong>"));
+    assert!(html.contains("Clear completed (1)"));
+
+    program.dispatch(Msg::Delete(0)).unwrap();
+    let html = program.html();
+    assert!(!html.contains("<label>milk</label>"));
+    assert!(html.contains("<label>eggs</label>"));
+    assert!(html.contains("<label>bread</label>"));
+}
+
+#[test]
+fn filter_and_clear_completed() {
+    let mut program = HeadlessProgram::mount(Model::new());
+    for description in ["milk", "eggs", "bread", "butter"].iter() {
+        add_todo(&mut program, description);
+    }
+    program
+        .dispatch_multiple(vec![Msg::Toggle(0), Msg::Toggle(2)])
+        .unwrap();
+
+    program
+        .dispatch(Msg::ChangeVisibility(Visibility::Active))
+        .unwrap();
+    let html = program.html();
+    assert!(!html.contains("<label>milk</label>"));
+    assert!(html.contains("<label>eggs</label>"));
+    assert!(html.contains("<label>butter</label>"));
+
+    program
+        .dispatch(Msg::ChangeVisibility(Visibility::Completed))
+        .unwrap();
+    let html = program.html();
+    assert!(html.contains("<label>milk</label>"));
+    assert!(html.contains("<label>bread</label>"));
+    assert!(!html.contains("<label>eggs</label>"));
+
+    program
+        .dispatch_multiple(vec![
+            Msg::ClearCompleted,
+            Msg::ChangeVisibility(Visibility::All),
+        ])
+        .unwrap();
+    let html = program.html();
+    assert_eq!(html.matches(r#"class="todo""#).count(), 2);
+    assert!(html.contains("<label>eggs</label>"));
+    assert!(html.contains("<label>butter</label>"));
+}
+
+#[test]
+fn keyed_rows_survive_many_dispatches() {
+    let mut program = HeadlessProgram::mount(Model::new());
+    for index in 0..50 {
+        add_todo(&mut program, &format!("todo {}", index));
+    }
+    program
+        .dispatch_multiple((0..50).step_by(3).map(Msg::Delete))
+        .unwrap();
+    program.dispatch(Msg::ToggleAll).unwrap();
+
+    let html = program.html();
+    assert_eq!(html.matches(r#"class="todo completed""#).count(), 33);
+    assert!(!html.contains(r#"key="todo-0""#));
+    assert!(html.contains(r#"key="todo-1""#));
+    assert!(html.contains("<label>todo 49</label>"));
+}
@@ -23,8 +23,9 @@ cfg_if! {if #[cfg(feature = "with-dom")] {
     pub use sauron_core::dom::*;
     pub use sauron_core::web_sys;
     pub use sauron_core::wasm_bindgen;
-    pub use sauron_core::{Component, Cmd, Program};
+    pub use sauron_core::{Component, Cmd, Program, Sub};
     pub use sauron_core::js_sys;
+    pub use sauron_core::widgets;
 }}
 
 /// reexport prelude from sauron core
@@ -33,9 +34,15 @@ pub mod prelude {
     #[cfg(feature = "with-node-macro")]
     pub use sauron_node_macro::node;
 }
+#[cfg(feature = "with-proptest")]
+pub use sauron_core::arbitrary;
 pub use sauron_core::{
-    diff, html, jss, mt_dom, svg, Attribute, Element, Listener, Node, Patch,
-    Render, Text,
+    apply_patch_to_vdom, backend, diff, diff_limits, diff_with_skip, forms,
+    html, jss, qr_code, remote_data, sanitize, set_diff_limits, svg, validate,
+    vdom, Attribute, DiffLimits, Element, Listener, Node, Patch, Render, Text,
 };
+// the underlying vdom crate is not covered by the semver guarantees, use `vdom` instead
+#[doc(hidden)]
+pub use sauron_core::mt_dom;
 #[cfg(feature = "with-node-macro")]
 pub use sauron_node_macro::node;
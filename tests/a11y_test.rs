@@ -0,0 +1,70 @@
+#![deny(warnings)]
+use sauron_core::{
+    html::a11y::{audit, A11yWarning},
+    prelude::*,
+};
+
+#[test]
+fn aria_attributes_are_typed() {
+    let view: Node<()> = div(
+        vec![
+            role("tab"),
+            aria_selected(true),
+            aria_controls("panel-1"),
+            aria_level(2),
+            aria_valuenow(0.5),
+        ],
+        vec![],
+    );
+    let mut html = String::new();
+    view.render(&mut html).unwrap();
+    assert_eq!(
+        html,
+        r#"<div role="tab" aria-selected="true" aria-controls="panel-1" aria-level="2" aria-valuenow="0.5"></div>"#
+    );
+}
+
+#[test]
+fn audit_accessible_view() {
+    let view: Node<()> = div(
+        vec![id("main")],
+        vec![
+            img(vec![src("logo.png"), alt("")], vec![]),
+            button(vec![], vec![text("save")]),
+            button(vec![aria_label("close")], vec![]),
+            button(
+                vec![],
+                vec![img(vec![src("x.png"), alt("delete")], vec![])],
+            ),
+        ],
+    );
+    assert_eq!(audit(&view), vec![]);
+}
+
+#[test]
+fn audit_warnings_in_document_order() {
+    let view: Node<()> = div(
+        vec![id("main")],
+        vec![
+            img(vec![src("logo.png")], vec![]),
+            button(vec![], vec![text("  ")]),
+            div(
+                vec![role("button")],
+                vec![span(vec![aria_hidden(true)], vec![text("x")])],
+            ),
+            p(vec![id("main")], vec![]),
+            p(vec![id("main")], vec![]),
+        ],
+    );
+    assert_eq!(
+        audit(&view),
+        vec![
+            A11yWarning::ImageWithoutAlt {
+                src: Some("logo.png".to_string())
+            },
+            A11yWarning::ButtonWithoutName,
+            A11yWarning::ButtonWithoutName,
+            A11yWarning::DuplicateId("main".to_string()),
+        ]
+    );
+}
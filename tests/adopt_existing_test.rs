@@ -0,0 +1,64 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, *},
+    DomUpdater, Node, Render,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn adopted_element_is_a_skipped_placeholder() {
+    let view: Node<()> = adopt_existing("#legacy");
+    assert_eq!(
+        view.render_to_string(),
+        r##"<div data-sauron-adopt="#legacy" skip="true"></div>"##
+    );
+}
+
+#[wasm_bindgen_test]
+fn adopts_and_moves_the_existing_element() {
+    let document = sauron::document();
+    let legacy = document.create_element("div").unwrap();
+    legacy.set_id("legacy-widget");
+    legacy.set_inner_html("<span>managed by jquery</span>");
+    sauron::body().append_child(&legacy).unwrap();
+
+    let old: Node<()> = section(
+        vec![id("adopt-container")],
+        vec![adopt_existing("#legacy-widget")],
+    );
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, old, &sauron::body())
+            .unwrap();
+    let container = document.get_element_by_id("adopt-container").unwrap();
+    assert_eq!(
+        container.inner_html(),
+        r#"<div id="legacy-widget"><span>managed by jquery</span></div>"#
+    );
+
+    // the legacy code keeps on modifying the element
+    legacy.set_inner_html("<span>updated by jquery</span>");
+
+    let new: Node<()> = section(
+        vec![id("adopt-container")],
+        vec![
+            h1(vec![], vec![text("title")]),
+            adopt_existing("#legacy-widget"),
+        ],
+    );
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+    let widget = document.get_element_by_id("legacy-widget").unwrap();
+    assert_eq!(widget, legacy);
+    assert_eq!(widget.inner_html(), "<span>updated by jquery</span>");
+    assert_eq!(
+        widget.parent_element().map(|parent| parent.id()),
+        Some("adopt-container".to_string())
+    );
+}
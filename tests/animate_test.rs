@@ -0,0 +1,135 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{
+        animate_enter, animate_exit, AnimationTiming, DomUpdater, EXITING_ATTR,
+    },
+    html::{attributes::*, li, span, text, ul},
+    js_sys,
+    wasm_bindgen::JsCast,
+    Node,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// resolves after `ms` milliseconds
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+fn view(items: &[&str]) -> Node<()> {
+    ul(
+        vec![id("animate-list")],
+        items.iter().map(|item| {
+            li(
+                vec![
+                    key(*item),
+                    animate_enter(
+                        vec![vec![("opacity", "0")], vec![("opacity", "1")]],
+                        AnimationTiming::new(50.0),
+                    ),
+                    animate_exit(
+                        vec![vec![("opacity", "1")], vec![("opacity", "0")]],
+                        AnimationTiming::new(200.0).easing("ease-out"),
+                    ),
+                ],
+                vec![text(*item)],
+            )
+        }),
+    )
+}
+
+fn rendered_items() -> String {
+    sauron::document()
+        .get_element_by_id("animate-list")
+        .expect("must have the list")
+        .inner_html()
+}
+
+#[wasm_bindgen_test]
+async fn removed_item_is_kept_until_its_exit_animation_is_finished() {
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new(view(&["a", "b", "c"]), &sauron::body());
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+
+    dom_updater
+        .update_dom(&simple_program, view(&["a", "c"]))
+        .expect("must update");
+    let exiting = sauron::document()
+        .query_selector(&format!("[{}]", EXITING_ATTR))
+        .expect("must query")
+        .expect("the removed item must still be in the document");
+    assert_eq!(exiting.text_content().as_deref(), Some("b"));
+
+    // the exiting item is skipped when the next patches look up their nodes
+    dom_updater
+        .update_dom(&simple_program, view(&["a", "d", "c"]))
+        .expect("must update");
+    let list = sauron::document()
+        .get_element_by_id("animate-list")
+        .unwrap();
+    assert_eq!(list.child_element_count(), 4);
+
+    sleep(400).await;
+    assert!(!rendered_items().contains(EXITING_ATTR));
+    assert_eq!(list.text_content().as_deref(), Some("adc"));
+}
+
+/// the number of the animations of the element, which are running or pending
+fn animation_count(element: &sauron::web_sys::Element) -> u32 {
+    let get_animations: js_sys::Function =
+        js_sys::Reflect::get(element, &"getAnimations".into())
+            .expect("must have getAnimations")
+            .unchecked_into();
+    let animations: js_sys::Array = get_animations
+        .call0(element)
+        .expect("must get the animations")
+        .unchecked_into();
+    animations.length()
+}
+
+#[wasm_bindgen_test]
+fn rows_imported_from_the_template_play_their_enter_animation() {
+    let simple_program = simple_program();
+    // the rows are big enough to be imported from a template after they are seen twice
+    let rows: Node<()> = ul(
+        vec![id("animate-rows")],
+        (0..4).map(|row| {
+            li(
+                vec![
+                    key(row),
+                    animate_enter(
+                        vec![vec![("opacity", "0")], vec![("opacity", "1")]],
+                        AnimationTiming::new(500.0),
+                    ),
+                ],
+                (0..8).map(|cell| span(vec![], vec![text(cell)])),
+            )
+        }),
+    );
+    let mut dom_updater = DomUpdater::new(rows, &sauron::body());
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+
+    let rows = sauron::document()
+        .query_selector_all("#animate-rows > li")
+        .expect("must query");
+    assert_eq!(rows.length(), 4);
+    for i in 0..rows.length() {
+        let row: sauron::web_sys::Element =
+            rows.item(i).unwrap().unchecked_into();
+        assert_eq!(animation_count(&row), 1);
+    }
+}
@@ -0,0 +1,60 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{cmd::Cmd, sub::Sub},
+    Dispatch,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// records the dispatched msgs
+#[derive(Clone, Default)]
+struct Recorder {
+    msgs: Rc<RefCell<Vec<f64>>>,
+}
+
+impl Dispatch<f64> for Recorder {
+    fn dispatch(&self, msg: f64) {
+        self.msgs.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<f64>) {
+        self.msgs.borrow_mut().extend(msgs);
+    }
+}
+
+/// resolves after `ms` milliseconds
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn animation_frame_cmd_dispatch_once() {
+    let recorder = Recorder::default();
+    Cmd::animation_frame(|timestamp| timestamp).emit(&recorder);
+    sleep(100).await;
+    assert_eq!(recorder.msgs.borrow().len(), 1);
+}
+
+#[wasm_bindgen_test]
+async fn animation_frame_sub_dispatch_every_frame_until_unsubscribed() {
+    let recorder = Recorder::default();
+    let subscriptions: Vec<_> =
+        Sub::on_animation_frame(|timestamp| timestamp).subscribe(&recorder);
+    sleep(100).await;
+    for subscription in subscriptions {
+        subscription.unsubscribe();
+    }
+    let frames = recorder.msgs.borrow().len();
+    assert!(frames >= 2);
+    // the timestamps are increasing
+    assert!(recorder.msgs.borrow().windows(2).all(|w| w[0] < w[1]));
+    sleep(100).await;
+    assert_eq!(recorder.msgs.borrow().len(), frames);
+}
@@ -0,0 +1,167 @@
+#![deny(warnings)]
+use sauron::{
+    apply_patch_to_vdom,
+    backend::PatchError,
+    diff,
+    html::{attributes::*, *},
+    vdom::patch::{PatchKind, RemoveChildrenFrom, RemoveNode, TreePath},
+    Node, Patch,
+};
+
+fn assert_patches_into_new(old: Node<()>, new: Node<()>) {
+    let patches = diff(&old, &new);
+    assert_eq!(apply_patch_to_vdom(&old, &patches).unwrap(), new);
+}
+
+#[test]
+fn text_and_attributes() {
+    assert_patches_into_new(
+        div(vec![class("a"), id("x")], vec![text("hello")]),
+        div(vec![class("b"), id("x")], vec![text("world")]),
+    );
+    assert_patches_into_new(
+        div(vec![class("a"), id("x")], vec![]),
+        div(vec![class("a")], vec![]),
+    );
+}
+
+#[test]
+fn replaced_root() {
+    assert_patches_into_new(
+        div(vec![], vec![text("1")]),
+        span(vec![], vec![text("1")]),
+    );
+}
+
+#[test]
+fn appended_and_removed_children() {
+    assert_patches_into_new(
+        ul(vec![], vec![li(vec![], vec![text("1")])]),
+        ul(
+            vec![],
+            vec![
+                li(vec![], vec![text("1")]),
+                li(vec![], vec![text("2")]),
+                li(vec![], vec![text("3")]),
+            ],
+        ),
+    );
+    assert_patches_into_new(
+        ul(
+            vec![],
+            vec![
+                li(vec![], vec![text("1")]),
+                li(vec![], vec![text("2")]),
+                li(vec![], vec![text("3")]),
+            ],
+        ),
+        ul(vec![], vec![li(vec![], vec![text("1")])]),
+    );
+}
+
+#[test]
+fn keyed_insert_and_remove() {
+    let item = |k: usize| li(vec![key(k)], vec![text(k)]);
+    assert_patches_into_new(
+        ul(vec![], vec![item(1), item(3)]),
+        ul(vec![], vec![item(1), item(2), item(3)]),
+    );
+    assert_patches_into_new(
+        ul(vec![], vec![item(1), item(2), item(3)]),
+        ul(vec![], vec![item(1), item(3)]),
+    );
+    assert_patches_into_new(
+        ul(vec![], vec![item(2), item(3)]),
+        ul(vec![], vec![item(1), item(2), item(3), item(4)]),
+    );
+}
+
+#[test]
+fn keyed_reorder() {
+    let item = |k: usize| li(vec![key(k)], vec![text(k)]);
+    // the reordered children are moved, instead of removed and created again
+    let old: Node<()> = ul(vec![], vec![item(1), item(2), item(3)]);
+    let new: Node<()> = ul(vec![], vec![item(3), item(2), item(1)]);
+    let patches = diff(&old, &new);
+    assert_eq!(patches.len(), 2);
+    assert!(patches
+        .iter()
+        .all(|patch| PatchKind::from(patch) == PatchKind::MoveNode));
+
+    assert_patches_into_new(
+        ul(vec![], vec![item(1), item(2), item(3)]),
+        ul(vec![], vec![item(3), item(2), item(1)]),
+    );
+    assert_patches_into_new(
+        ul(vec![], vec![item(1), item(2), item(3), item(4)]),
+        ul(vec![], vec![item(4), item(1), item(5), item(3), item(2)]),
+    );
+    assert_patches_into_new(
+        ul(vec![], vec![item(1), item(2), item(3)]),
+        ul(
+            vec![],
+            vec![item(2), li(vec![key(1)], vec![text("changed")]), item(3)],
+        ),
+    );
+}
+
+#[test]
+fn nested_changes() {
+    assert_patches_into_new(
+        main(
+            vec![],
+            vec![
+                header(vec![], vec![h1(vec![], vec![text("title")])]),
+                section(vec![], vec![p(vec![], vec![text("a")])]),
+            ],
+        ),
+        main(
+            vec![],
+            vec![
+                header(vec![], vec![h1(vec![], vec![text("new title")])]),
+                section(
+                    vec![class("content")],
+                    vec![
+                        p(vec![], vec![text("a")]),
+                        span(vec![], vec![text("end")]),
+                    ],
+                ),
+            ],
+        ),
+    );
+}
+
+#[test]
+fn truncated_children() {
+    let item = |i: usize| li(vec![], vec![text(i)]);
+    let old: Node<()> = ul(vec![], (1..=5).map(item).collect::<Vec<_>>());
+    let new: Node<()> = ul(vec![], (1..=2).map(item).collect::<Vec<_>>());
+    let patches = diff(&old, &new);
+    assert_eq!(
+        patches,
+        vec![RemoveChildrenFrom::new(&"ul", TreePath::new(vec![0]), 2).into()]
+    );
+    assert_eq!(apply_patch_to_vdom(&old, &patches).unwrap(), new);
+}
+
+#[test]
+fn patch_at_missing_path() {
+    let old: Node<()> = div(vec![], vec![]);
+    let patches: Vec<Patch<()>> =
+        vec![RemoveNode::new(Some(&"span"), TreePath::new(vec![0, 3])).into()];
+    assert_eq!(
+        apply_patch_to_vdom(&old, &patches),
+        Err(PatchError::NodeNotFound(vec![0, 3]))
+    );
+}
+
+#[test]
+fn remove_children_from_missing_index() {
+    let old: Node<()> = div(vec![], vec![]);
+    let patches: Vec<Patch<()>> =
+        vec![RemoveChildrenFrom::new(&"div", TreePath::new(vec![0]), 1).into()];
+    assert_eq!(
+        apply_patch_to_vdom(&old, &patches),
+        Err(PatchError::NodeNotFound(vec![0, 1]))
+    );
+}
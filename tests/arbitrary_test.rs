@@ -0,0 +1,60 @@
+#![deny(warnings)]
+#![cfg(feature = "with-proptest")]
+use proptest::prelude::*;
+use sauron::{
+    arbitrary::{arb_node, arb_node_with_size, TreeSize},
+    diff,
+    html::attributes::Special,
+    Node, Render,
+};
+
+/// the keys of the children of each element in the tree are unique
+fn has_unique_keys(node: &Node<()>) -> bool {
+    match node.as_element_ref() {
+        Some(element) => {
+            let keys: Vec<String> = element
+                .get_children()
+                .iter()
+                .filter_map(|child| child.get_key())
+                .map(|key| key.to_string())
+                .collect();
+            let mut unique = keys.clone();
+            unique.sort();
+            unique.dedup();
+            unique.len() == keys.len()
+                && element.get_children().iter().all(has_unique_keys)
+        }
+        None => true,
+    }
+}
+
+fn depth(node: &Node<()>) -> u32 {
+    match node.as_element_ref() {
+        Some(element) => {
+            1 + element.get_children().iter().map(depth).max().unwrap_or(0)
+        }
+        None => 0,
+    }
+}
+
+proptest! {
+    #[test]
+    fn generated_keys_are_unique(node in arb_node()) {
+        prop_assert!(has_unique_keys(&node));
+    }
+
+    #[test]
+    fn diff_of_the_same_node_is_empty(node in arb_node()) {
+        prop_assert!(diff(&node, &node).is_empty());
+        prop_assert!(!node.render_to_string().is_empty() || node.is_text());
+    }
+
+    #[test]
+    fn tree_size_limits_the_depth(node in arb_node_with_size(TreeSize {
+        depth: 2,
+        nodes: 16,
+        children: 3,
+    })) {
+        prop_assert!(depth(&node) <= 3);
+    }
+}
@@ -0,0 +1,114 @@
+#![deny(warnings)]
+use sauron::{dom::cmd::Cmd, Dispatch};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// records the dispatched msgs
+#[derive(Clone, Default)]
+struct Recorder {
+    msgs: Rc<RefCell<Vec<Result<i32, String>>>>,
+}
+
+impl Dispatch<Result<i32, String>> for Recorder {
+    fn dispatch(&self, msg: Result<i32, String>) {
+        self.msgs.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<Result<i32, String>>) {
+        self.msgs.borrow_mut().extend(msgs);
+    }
+}
+
+/// wait for the spawned futures to complete
+async fn next_tick() {
+    let promise =
+        sauron::js_sys::Promise::resolve(&sauron::wasm_bindgen::JsValue::NULL);
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn async_cmd_dispatch_the_result() {
+    let recorder = Recorder::default();
+    let cmd: Cmd<Recorder> = Cmd::batch([
+        Cmd::from_async(async { Ok(1) }),
+        Cmd::try_from_async(
+            async { Err::<i32, _>("failed".to_string()) },
+            |r| r,
+        ),
+    ]);
+    cmd.emit(&recorder);
+    next_tick().await;
+    assert_eq!(
+        *recorder.msgs.borrow(),
+        vec![Ok(1), Err("failed".to_string())]
+    );
+}
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn only_the_latest_of_the_same_tag_is_dispatched() {
+    let recorder = Recorder::default();
+    let slow: Cmd<Recorder> = Cmd::latest_only("search", async {
+        sleep(30).await;
+        Ok(1)
+    });
+    let fast: Cmd<Recorder> = Cmd::latest_only("search", async { Ok(2) });
+    let other: Cmd<Recorder> = Cmd::latest_only("other", async { Ok(3) });
+    slow.emit(&recorder);
+    fast.emit(&recorder);
+    other.emit(&recorder);
+    sleep(50).await;
+    assert_eq!(*recorder.msgs.borrow(), vec![Ok(2), Ok(3)]);
+}
+
+#[wasm_bindgen_test]
+async fn batch_runs_the_futures_concurrently() {
+    let recorder = Recorder::default();
+    let cmd: Cmd<Recorder> = Cmd::batch([
+        Cmd::from_async(async {
+            sleep(30).await;
+            Ok(1)
+        }),
+        Cmd::from_async(async { Ok(2) }),
+    ]);
+    cmd.emit(&recorder);
+    sleep(50).await;
+    assert_eq!(*recorder.msgs.borrow(), vec![Ok(2), Ok(1)]);
+}
+
+#[wasm_bindgen_test]
+async fn seq_awaits_each_cmd_before_the_next() {
+    let recorder = Recorder::default();
+    let cmd: Cmd<Recorder> = Cmd::seq([
+        Cmd::from_async(async {
+            sleep(30).await;
+            Ok(1)
+        }),
+        Cmd::new(|program: Recorder| program.dispatch(Ok(2))),
+        Cmd::batch([
+            Cmd::from_async(async {
+                sleep(10).await;
+                Ok(3)
+            }),
+            Cmd::from_async(async { Ok(4) }),
+        ]),
+        Cmd::from_async(async { Ok(5) }),
+    ]);
+    cmd.emit(&recorder);
+    assert!(recorder.msgs.borrow().is_empty());
+    sleep(80).await;
+    assert_eq!(
+        *recorder.msgs.borrow(),
+        vec![Ok(1), Ok(2), Ok(3), Ok(4), Ok(5)]
+    );
+}
@@ -174,3 +174,27 @@ fn should_merge_classes_flag() {
         )
     );
 }
+
+#[test]
+fn literal_classes_of_classes_cow_are_not_allocated() {
+    let attr: Attribute<()> = classes_flag_cow([
+        (std::borrow::Cow::from("class1"), true),
+        (format!("class{}", 2).into(), true),
+        ("class3".into(), false),
+    ]);
+    assert_eq!(
+        attr.value()
+            .iter()
+            .map(|value| value.get_simple())
+            .collect::<Vec<_>>(),
+        vec![
+            Some(&Value::Str("class1")),
+            Some(&Value::String("class2".to_string()))
+        ]
+    );
+    let html: Node<()> = div(vec![attr], vec![]);
+    assert_eq!(
+        html.render_to_string(),
+        r#"<div class="class1 class2"></div>"#
+    );
+}
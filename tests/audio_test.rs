@@ -0,0 +1,25 @@
+#![deny(warnings)]
+use sauron::dom::audio::{self, Sound};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn sound_from_url_and_bytes() {
+    assert_eq!(Sound::from("ding.ogg"), Sound::Url("ding.ogg".to_string()));
+    assert_eq!(Sound::from(vec![1, 2, 3]), Sound::Buffer(vec![1, 2, 3]));
+}
+
+#[wasm_bindgen_test]
+fn volume_and_mute_state() {
+    audio::set_volume(1.5);
+    assert_eq!(audio::volume(), 1.0);
+    audio::set_volume(0.25);
+    assert_eq!(audio::volume(), 0.25);
+
+    audio::set_muted(true);
+    assert!(audio::is_muted());
+    // the volume is retained when unmuted
+    audio::set_muted(false);
+    assert_eq!(audio::volume(), 0.25);
+}
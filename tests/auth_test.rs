@@ -0,0 +1,77 @@
+#![deny(warnings)]
+#![cfg(feature = "with-persist")]
+use sauron::dom::{
+    auth::{Session, TokenStorage, Tokens},
+    Dispatch,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// records the dispatched msgs
+#[derive(Clone, Default)]
+struct Recorder {
+    msgs: Rc<RefCell<Vec<String>>>,
+}
+
+impl Dispatch<String> for Recorder {
+    fn dispatch_multiple(&self, msgs: Vec<String>) {
+        self.msgs.borrow_mut().extend(msgs);
+    }
+
+    fn dispatch(&self, msg: String) {
+        self.dispatch_multiple(vec![msg])
+    }
+}
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+fn tokens_are_stored_and_loaded() {
+    let session = Session::new("auth-test-tokens", TokenStorage::Local);
+    session.set_tokens(Tokens::new("abc").refresh_token("def"));
+    assert_eq!(session.authorization(), Some("Bearer abc".to_string()));
+
+    let reloaded = Session::new("auth-test-tokens", TokenStorage::Local);
+    assert_eq!(reloaded.tokens(), session.tokens());
+    assert!(reloaded.is_authenticated());
+
+    reloaded.clear();
+    assert!(Session::new("auth-test-tokens", TokenStorage::Local)
+        .tokens()
+        .is_none());
+}
+
+#[wasm_bindgen_test]
+async fn refresh_is_dispatched_before_the_expiry() {
+    let recorder = Recorder::default();
+    let session = Session::new("auth-test-refresh", TokenStorage::Memory)
+        .refresh_ahead(80.0);
+    session
+        .set_tokens(Tokens::new("abc").refresh_token("def").expires_in(100.0));
+    let subscription = session
+        .subscribe(|refresh_token| refresh_token, || "expired".to_string())
+        .subscribers
+        .pop()
+        .unwrap()(recorder.clone());
+    sleep(50).await;
+    assert_eq!(*recorder.msgs.borrow(), vec!["def".to_string()]);
+
+    // the tokens without a refresh token expire
+    session.set_tokens(Tokens::new("ghi").expires_in(10.0));
+    sleep(50).await;
+    assert_eq!(
+        *recorder.msgs.borrow(),
+        vec!["def".to_string(), "expired".to_string()]
+    );
+    assert!(session.tokens().is_none());
+    subscription.unsubscribe();
+}
@@ -0,0 +1,80 @@
+#![deny(warnings)]
+#![cfg(feature = "with-persist")]
+use sauron::{
+    dom::{cmd::Cmd, sub::Sub},
+    wasm_bindgen::{closure::Closure, JsCast, JsValue},
+    web_sys, Dispatch,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+/// records the dispatched msgs instead of updating an app
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<u32>>>);
+
+impl Dispatch<u32> for Recorder {
+    fn dispatch(&self, msg: u32) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<u32>) {
+        self.0.borrow_mut().extend(msgs);
+    }
+}
+
+#[wasm_bindgen_test]
+async fn msgs_from_the_other_tabs_are_dispatched_until_unsubscribed() {
+    let recorder = Recorder::default();
+    let subscriptions =
+        Sub::broadcast_channel::<u32>("sync-test").subscribe(&recorder);
+
+    // the msgs of the other tabs are posted the same way as `Cmd::broadcast`
+    let other_tab = web_sys::BroadcastChannel::new("sync-test").unwrap();
+    other_tab
+        .post_message(&JsValue::from_str(r#"{"sender":"other-tab","msg":7}"#))
+        .unwrap();
+    // the msgs sent from this tab are not dispatched back
+    Cmd::broadcast("sync-test", &8).emit(&recorder);
+    sleep(100).await;
+    assert_eq!(*recorder.0.borrow(), vec![7]);
+
+    for subscription in subscriptions {
+        subscription.unsubscribe();
+    }
+    other_tab
+        .post_message(&JsValue::from_str(r#"{"sender":"other-tab","msg":9}"#))
+        .unwrap();
+    sleep(100).await;
+    assert_eq!(*recorder.0.borrow(), vec![7]);
+    other_tab.close();
+}
+
+#[wasm_bindgen_test]
+async fn broadcast_posts_the_serialized_msg() {
+    let received = Rc::new(RefCell::new(None));
+    let received_clone = Rc::clone(&received);
+    let other_tab = web_sys::BroadcastChannel::new("post-test").unwrap();
+    let listener: Closure<dyn FnMut(web_sys::MessageEvent)> =
+        Closure::wrap(Box::new(move |event: web_sys::MessageEvent| {
+            *received_clone.borrow_mut() = event.data().as_string();
+        }));
+    other_tab.set_onmessage(Some(listener.as_ref().unchecked_ref()));
+
+    Cmd::broadcast("post-test", &42u32).emit(&Recorder::default());
+    sleep(100).await;
+    let json = received.borrow().clone().expect("must receive the msg");
+    let envelope: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(envelope["msg"], 42);
+    other_tab.close();
+}
@@ -0,0 +1,77 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{canvas_draw, DomUpdater},
+    html::{attributes::*, div, text},
+    Node,
+};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// resolves after `ms` milliseconds
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+/// a chart drawn with the `value`, records the values and the sizes it is drawn with
+fn view(
+    draws: &Rc<RefCell<Vec<(u32, f64)>>>,
+    value: u32,
+    label: &str,
+) -> Node<()> {
+    let draws = Rc::clone(draws);
+    div(
+        vec![],
+        vec![
+            text(label),
+            canvas_draw(
+                vec![id("canvas-draw-1"), style("width", "80px")],
+                value,
+                move |ctx, size| {
+                    ctx.fill_rect(0.0, 0.0, f64::from(value), 1.0);
+                    draws.borrow_mut().push((value, size.width));
+                },
+            ),
+        ],
+    )
+}
+
+#[wasm_bindgen_test]
+async fn canvas_is_redrawn_only_when_the_deps_changed() {
+    let draws = Rc::new(RefCell::new(vec![]));
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new(view(&draws, 1, "a"), &sauron::body());
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+    sleep(50).await;
+    assert_eq!(*draws.borrow(), vec![(1, 80.0)]);
+
+    let canvas = sauron::document()
+        .get_element_by_id("canvas-draw-1")
+        .expect("must have the canvas");
+
+    // only the label changed
+    dom_updater
+        .update_dom(&simple_program, view(&draws, 1, "b"))
+        .expect("must update");
+    sleep(50).await;
+    assert_eq!(*draws.borrow(), vec![(1, 80.0)]);
+
+    dom_updater
+        .update_dom(&simple_program, view(&draws, 2, "b"))
+        .expect("must update");
+    sleep(50).await;
+    assert_eq!(*draws.borrow(), vec![(1, 80.0), (2, 80.0)]);
+    assert!(canvas.is_connected(), "the canvas element must be kept");
+}
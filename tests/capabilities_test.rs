@@ -0,0 +1,50 @@
+#![deny(warnings)]
+use sauron::dom::{capabilities, Capabilities, Motion};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn capabilities_is_detected_when_mounted() {
+    let _program = simple_program();
+    let detected = capabilities();
+    assert_eq!(detected, Capabilities::detect());
+    // supported by all the browsers the tests are run on
+    assert!(detected.resize_observer);
+    assert!(detected.intersection_observer);
+}
+
+#[test]
+fn slow_connection() {
+    let capabilities = Capabilities {
+        touch: false,
+        prefers_reduced_motion: false,
+        connection_type: Some("2g".to_string()),
+        resize_observer: true,
+        intersection_observer: true,
+    };
+    assert!(capabilities.is_slow_connection());
+    assert!(!Capabilities {
+        connection_type: None,
+        ..capabilities
+    }
+    .is_slow_connection());
+}
+
+#[test]
+fn motion_override() {
+    assert!(Motion::Reduced.is_reduced());
+    assert!(!Motion::Full.is_reduced());
+    assert_eq!(Motion::default(), Motion::Auto);
+}
+
+#[wasm_bindgen_test]
+fn auto_motion_follows_the_user_preference() {
+    assert_eq!(
+        Motion::Auto.is_reduced(),
+        capabilities().prefers_reduced_motion
+    );
+}
@@ -0,0 +1,14 @@
+#![deny(warnings)]
+use sauron::dom::WindowOptions;
+
+#[test]
+fn window_features() {
+    let options = WindowOptions {
+        width: Some(400),
+        height: Some(300),
+        popup: true,
+        ..Default::default()
+    };
+    assert_eq!(options.to_features(), "popup,width=400,height=300");
+    assert_eq!(WindowOptions::default().to_features(), "");
+}
@@ -0,0 +1,74 @@
+#![deny(warnings)]
+
+use sauron_core::{
+    dom::DomUpdater,
+    html::{attributes::*, *},
+    Node,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn class_added_by_third_party_is_retained() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let old: Node<()> = div(
+        vec![id("class-list-1"), classes(["active", "item"])],
+        vec![],
+    );
+    let new: Node<()> = div(
+        vec![id("class-list-1"), classes(["item", "selected"])],
+        vec![],
+    );
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(old, &body);
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+
+    let element = sauron_core::document()
+        .get_element_by_id("class-list-1")
+        .unwrap();
+    // simulate a third-party library adding its own class
+    element.class_list().add_1("tooltip-shown").unwrap();
+
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+
+    let class_list = element.class_list();
+    assert!(class_list.contains("tooltip-shown"));
+    assert!(class_list.contains("item"));
+    assert!(class_list.contains("selected"));
+    assert!(!class_list.contains("active"));
+}
+
+#[wasm_bindgen_test]
+fn removing_the_class_attribute_retains_third_party_classes() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let old: Node<()> = div(vec![id("class-list-2"), class("item")], vec![]);
+    let new: Node<()> = div(vec![id("class-list-2")], vec![]);
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(old, &body);
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+
+    let element = sauron_core::document()
+        .get_element_by_id("class-list-2")
+        .unwrap();
+    element.class_list().add_1("analytics-tracked").unwrap();
+
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+
+    assert_eq!(element.class_name(), "analytics-tracked");
+}
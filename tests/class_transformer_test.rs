@@ -0,0 +1,64 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, *},
+    DomUpdater, Node, Render,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn prefix_transformer() {
+    set_class_transformer(|class_name| match class_name {
+        "btn" => "ds-btn ds-btn-primary".to_string(),
+        "legacy" => String::new(),
+        _ => format!("ds-{}", class_name),
+    });
+}
+
+#[test]
+fn transforms_the_rendered_classes() {
+    prefix_transformer();
+    let view: Node<()> =
+        div(vec![class("btn"), class("legacy large"), id("btn")], vec![]);
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div class="ds-btn ds-btn-primary ds-large" id="btn"></div>"#
+    );
+    clear_class_transformer();
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div class="btn legacy large" id="btn"></div>"#
+    );
+}
+
+#[test]
+fn transform_classes_without_transformer() {
+    clear_class_transformer();
+    assert_eq!(transform_classes("a  b"), "a  b");
+}
+
+#[wasm_bindgen_test]
+fn transforms_the_classes_when_created_and_patched() {
+    prefix_transformer();
+    let old: Node<()> =
+        div(vec![id("class-transformer"), class("btn")], vec![]);
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, old, &sauron::body())
+            .unwrap();
+    let element = sauron::document()
+        .get_element_by_id("class-transformer")
+        .unwrap();
+    assert_eq!(element.class_name(), "ds-btn ds-btn-primary");
+
+    let new: Node<()> =
+        div(vec![id("class-transformer"), class("active")], vec![]);
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+    assert_eq!(element.class_name(), "ds-active");
+    clear_class_transformer();
+}
@@ -0,0 +1,73 @@
+#![deny(warnings)]
+use sauron::{
+    dom::DomUpdater,
+    html::{attributes::*, events::*, *},
+    web_sys, Dispatch, Node,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// records the dispatched msgs instead of updating an app
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<u32>>>);
+
+impl Dispatch<u32> for Recorder {
+    fn dispatch(&self, msg: u32) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<u32>) {
+        self.0.borrow_mut().extend(msgs);
+    }
+}
+
+impl Recorder {
+    fn take(&self) -> Vec<u32> {
+        self.0.borrow_mut().drain(..).collect()
+    }
+}
+
+fn click(element_id: &str) {
+    let element: web_sys::HtmlElement = sauron::document()
+        .get_element_by_id(element_id)
+        .expect("must have the element")
+        .unchecked_into();
+    element.click();
+}
+
+fn page(is_open: bool) -> Node<u32> {
+    div(
+        vec![],
+        vec![
+            button(vec![id("outside")], vec![]),
+            view_if(
+                is_open,
+                div(
+                    vec![id("popover"), on_click_outside(|_| 1)],
+                    vec![button(vec![id("inside")], vec![])],
+                ),
+            ),
+        ],
+    )
+}
+
+#[wasm_bindgen_test]
+fn only_the_clicks_outside_of_the_element_are_dispatched() {
+    let recorder = Recorder::default();
+    let mut dom_updater = DomUpdater::new(page(true), &sauron::body());
+    dom_updater.append_to_mount(&recorder).expect("must mount");
+
+    click("inside");
+    assert!(recorder.take().is_empty());
+    click("outside");
+    assert_eq!(recorder.take(), vec![1]);
+
+    dom_updater
+        .update_dom(&recorder, page(false))
+        .expect("must update");
+    click("outside");
+    assert!(recorder.take().is_empty());
+}
@@ -0,0 +1,47 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{cmd::Cmd, ClipboardError},
+    Dispatch,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+/// records the dispatched msgs instead of updating an app
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<Result<String, ClipboardError>>>>);
+
+impl Dispatch<Result<String, ClipboardError>> for Recorder {
+    fn dispatch(&self, msg: Result<String, ClipboardError>) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<Result<String, ClipboardError>>) {
+        self.0.borrow_mut().extend(msgs);
+    }
+}
+
+#[wasm_bindgen_test]
+async fn reading_the_clipboard_always_dispatch_the_result() {
+    let recorder = Recorder::default();
+    Cmd::copy_to_clipboard("copied").emit(&recorder);
+    Cmd::read_clipboard(|text| text).emit(&recorder);
+    sleep(300).await;
+    // the headless browser may not grant the permission to the clipboard,
+    // which is then dispatched as an error instead of being lost
+    let results = recorder.0.borrow();
+    assert_eq!(results.len(), 1);
+    if let Ok(text) = &results[0] {
+        assert_eq!(text, "copied");
+    }
+}
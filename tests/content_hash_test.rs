@@ -0,0 +1,78 @@
+#![deny(warnings)]
+use sauron::{
+    diff,
+    html::{attributes::*, events::*, *},
+    vdom::{with_content_hash, ContentHash},
+    Node, Render,
+};
+use std::collections::HashMap;
+
+#[test]
+fn equal_nodes_have_the_same_hash() {
+    let view = |label: &str| -> Node<()> {
+        div(
+            vec![
+                class("panel"),
+                styles([("width", 0.0), ("height", 20.5)]),
+                on_click(|_| ()),
+            ],
+            vec![text(label), comment("note")],
+        )
+    };
+    assert_eq!(view("hello"), view("hello"));
+    assert_eq!(view("hello").content_hash(), view("hello").content_hash());
+    assert_ne!(view("hello").content_hash(), view("world").content_hash());
+
+    // the nodes can be the keys of a cache
+    let mut cache = HashMap::new();
+    cache.insert(view("hello").content_hash(), "rendered hello");
+    assert_eq!(
+        cache.get(&view("hello").content_hash()),
+        Some(&"rendered hello")
+    );
+}
+
+#[test]
+fn the_zero_floats_have_the_same_hash() {
+    let positive: Node<()> = div(vec![styles([("left", 0.0)])], vec![]);
+    let negative: Node<()> = div(vec![styles([("left", -0.0)])], vec![]);
+    assert_eq!(positive, negative);
+    assert_eq!(positive.content_hash(), negative.content_hash());
+}
+
+#[test]
+fn the_order_of_the_attributes_and_children_is_hashed() {
+    let first: Node<()> = div(vec![id("a"), class("b")], vec![]);
+    let second: Node<()> = div(vec![class("b"), id("a")], vec![]);
+    assert_ne!(first.content_hash(), second.content_hash());
+
+    let first: Node<()> =
+        ul(vec![], vec![li(vec![], vec![]), span(vec![], vec![])]);
+    let second: Node<()> =
+        ul(vec![], vec![span(vec![], vec![]), li(vec![], vec![])]);
+    assert_ne!(first.content_hash(), second.content_hash());
+}
+
+#[test]
+fn subtrees_with_the_same_precomputed_hash_are_not_diffed() {
+    let panel = |items: &[&str]| -> Node<()> {
+        with_content_hash(ul(
+            vec![class("panel")],
+            items.iter().map(|item| li(vec![], vec![text(item)])),
+        ))
+    };
+    let old: Node<()> =
+        main(vec![], vec![panel(&["a", "b"]), panel(&["c"]), text("1")]);
+    let new: Node<()> =
+        main(vec![], vec![panel(&["a", "b"]), panel(&["d"]), text("2")]);
+    let patches = diff(&old, &new);
+    // the changed text of the second panel, its hash and the text of the main
+    assert_eq!(patches.len(), 3);
+    assert!(patches.iter().all(|patch| patch.path()[..2] != [0, 0]));
+
+    // the hash is not rendered
+    assert_eq!(
+        panel(&["a"]).render_to_string(),
+        r#"<ul class="panel"><li>a</li></ul>"#
+    );
+}
@@ -1,10 +1,10 @@
 #![deny(warnings)]
 use sauron_core::{
     dom::CreatedNode,
-    html::{attributes::*, div, events::*},
+    html::{attributes::*, div, events::*, html_element},
     svg::{
-        attributes::{cx, cy, r, xmlns},
-        circle, svg,
+        attributes::{cx, cy, gradientUnits, r, stop_color, view_box, xmlns},
+        circle, svg, SVG_NAMESPACE,
     },
     Node, *,
 };
@@ -24,6 +24,7 @@ fn nested_divs() {
         div(vec![], vec![div(vec![], vec![div(vec![], vec![])])]); // <div> <div> <div></div> </div> </div>
     let div: Element =
         CreatedNode::create_dom_node(&simple_program(), &vdiv, &mut None)
+            .unwrap()
             .node
             .unchecked_into();
 
@@ -41,6 +42,7 @@ fn svg_element() {
     );
     let div: Element =
         CreatedNode::create_dom_node(&simple_program(), &vdiv, &mut None)
+            .unwrap()
             .node
             .unchecked_into();
 
@@ -50,11 +52,37 @@ fn svg_element() {
     );
 }
 
+#[wasm_bindgen_test]
+fn svg_tags_without_namespace_are_created_in_the_svg_namespace() {
+    let vsvg: Node<()> = svg(
+        vec![view_box(0.0, 0.0, 10.0, 10.0)],
+        vec![html_element(
+            "linearGradient",
+            vec![gradientUnits("userSpaceOnUse")],
+            vec![html_element("stop", vec![stop_color("red")], vec![])],
+        )],
+    );
+    let svg: Element =
+        CreatedNode::create_dom_node(&simple_program(), &vsvg, &mut None)
+            .unwrap()
+            .node
+            .unchecked_into();
+
+    let gradient = svg.first_element_child().expect("must have the gradient");
+    assert_eq!(gradient.namespace_uri().as_deref(), Some(SVG_NAMESPACE));
+    assert_eq!(gradient.tag_name(), "linearGradient");
+    assert_eq!(
+        &svg.outer_html(),
+        r#"<svg viewBox="0 0 10 10"><linearGradient gradientUnits="userSpaceOnUse"><stop stop-color="red"></stop></linearGradient></svg>"#
+    );
+}
+
 #[wasm_bindgen_test]
 fn div_with_attributes() {
     let vdiv: Node<()> = div(vec![id("id-here"), class("two classes")], vec![]);
     let div: Element =
         CreatedNode::create_dom_node(&simple_program(), &vdiv, &mut None)
+            .unwrap()
             .node
             .unchecked_into();
 
@@ -89,7 +117,8 @@ fn click_event() {
     );
 
     let _dom_updater =
-        DomUpdater::new_append_to_mount(&simple_program(), vdiv, &body);
+        DomUpdater::new_append_to_mount(&simple_program(), vdiv, &body)
+            .unwrap();
 
     let click_event = web_sys::MouseEvent::new("click").unwrap();
 
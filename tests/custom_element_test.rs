@@ -0,0 +1,168 @@
+#![deny(warnings)]
+#![cfg(feature = "with-persist")]
+use sauron::{
+    dom::custom_element::{register, Reflection},
+    html::{attributes::*, *},
+    js_sys,
+    wasm_bindgen::{closure::Closure, JsCast, JsValue},
+    Application, Cmd, Node, Program,
+};
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    max: u32,
+}
+
+#[derive(Debug, PartialEq)]
+enum Msg {
+    SetLabel(Option<String>),
+    SetItems(Vec<String>),
+    SetConfig(Config),
+}
+
+fn reflection() -> Reflection<Msg> {
+    Reflection::new()
+        .attribute("label", Msg::SetLabel)
+        .attribute_json("items", Msg::SetItems)
+        .property_json("config", Msg::SetConfig)
+}
+
+#[test]
+fn attributes_are_mapped_into_msgs() {
+    let reflection = reflection();
+    assert_eq!(reflection.observed_attributes(), vec!["label", "items"]);
+    assert_eq!(reflection.properties(), vec!["config"]);
+    assert_eq!(
+        reflection.attribute_changed("label", Some("hello".to_string())),
+        Some(Msg::SetLabel(Some("hello".to_string())))
+    );
+    assert_eq!(
+        reflection.attribute_changed("label", None),
+        Some(Msg::SetLabel(None))
+    );
+    assert_eq!(
+        reflection
+            .attribute_changed("items", Some(r#"["a", "b"]"#.to_string())),
+        Some(Msg::SetItems(vec!["a".to_string(), "b".to_string()]))
+    );
+    // invalid json and unknown attributes are ignored
+    assert_eq!(
+        reflection.attribute_changed("items", Some("[a".to_string())),
+        None
+    );
+    assert_eq!(reflection.attribute_changed("unknown", None), None);
+}
+
+#[wasm_bindgen_test]
+fn properties_are_deserialized() {
+    let reflection = reflection();
+    let value = js_sys::JSON::parse(r#"{"max": 3}"#).unwrap();
+    assert_eq!(
+        reflection.property_changed("config", value),
+        Some(Msg::SetConfig(Config { max: 3 }))
+    );
+    assert_eq!(reflection.property_changed("config", JsValue::NULL), None);
+}
+
+struct Widget;
+
+impl Application<()> for Widget {
+    fn init(&mut self) -> Cmd<Self, ()> {
+        Cmd::emit_custom_event("ready", &Config { max: 7 })
+    }
+
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div(vec![class("widget")], vec![])
+    }
+}
+
+#[wasm_bindgen_test]
+fn custom_event_bubbles_to_the_host() {
+    let host = sauron::document().create_element("div").unwrap();
+    sauron::body().append_child(&host).unwrap();
+    let details = Rc::new(RefCell::new(vec![]));
+    let details_clone = Rc::clone(&details);
+    let listener: Closure<dyn FnMut(web_sys::CustomEvent)> =
+        Closure::wrap(Box::new(move |event: web_sys::CustomEvent| {
+            let detail = js_sys::JSON::stringify(&event.detail()).unwrap();
+            details_clone.borrow_mut().push(String::from(detail));
+        }));
+    host.add_event_listener_with_callback(
+        "ready",
+        listener.as_ref().unchecked_ref(),
+    )
+    .unwrap();
+
+    let _program = Program::append_to_mount(Widget, &host);
+    assert_eq!(*details.borrow(), vec![r#"{"max":7}"#.to_string()]);
+}
+
+async fn sleep(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[derive(Default)]
+struct Labelled {
+    label: Option<String>,
+}
+
+impl Application<Option<String>> for Labelled {
+    fn update(&mut self, label: Option<String>) -> Cmd<Self, Option<String>> {
+        self.label = label;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Option<String>> {
+        div(
+            vec![class("labelled")],
+            vec![text(self.label.as_deref().unwrap_or_default())],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+async fn registered_element_mounts_the_app_when_connected() {
+    register::<Labelled, Option<String>>(
+        "sauron-labelled",
+        Reflection::new().attribute("label", |label| label),
+    )
+    .unwrap();
+    // the custom element can only be defined once
+    assert!(register::<Labelled, Option<String>>(
+        "sauron-labelled",
+        Reflection::new()
+    )
+    .is_err());
+
+    let element = sauron::document()
+        .create_element("sauron-labelled")
+        .unwrap();
+    // the attributes set before it is connected are dispatched once it is mounted
+    element.set_attribute("label", "hello").unwrap();
+    sauron::body().append_child(&element).unwrap();
+    sleep(50).await;
+    let shadow_root = element.shadow_root().expect("must have a shadow root");
+    let labelled = shadow_root.query_selector(".labelled").unwrap().unwrap();
+    assert_eq!(labelled.text_content(), Some("hello".to_string()));
+
+    element.set_attribute("label", "world").unwrap();
+    sleep(50).await;
+    assert_eq!(labelled.text_content(), Some("world".to_string()));
+
+    element.remove();
+    assert!(shadow_root.query_selector(".labelled").unwrap().is_none());
+}
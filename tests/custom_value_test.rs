@@ -0,0 +1,84 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, *},
+    vdom::patch::*,
+    *,
+};
+
+#[derive(Debug, PartialEq)]
+struct Token {
+    name: &'static str,
+    weight: u8,
+}
+
+#[test]
+fn same_custom_value_has_no_patch() {
+    let old: Node<()> = div(
+        vec![custom_attr(
+            "token",
+            Token {
+                name: "primary",
+                weight: 1,
+            },
+        )],
+        vec![],
+    );
+    let new: Node<()> = div(
+        vec![custom_attr(
+            "token",
+            Token {
+                name: "primary",
+                weight: 1,
+            },
+        )],
+        vec![],
+    );
+    assert_eq!(diff(&old, &new), vec![]);
+}
+
+#[test]
+fn changed_custom_value_is_patched() {
+    let old: Node<()> = div(
+        vec![custom_attr(
+            "token",
+            Token {
+                name: "primary",
+                weight: 1,
+            },
+        )],
+        vec![],
+    );
+    let new_token: Attribute<()> = custom_attr(
+        "token",
+        Token {
+            name: "primary",
+            weight: 2,
+        },
+    );
+    let new: Node<()> = div(vec![new_token.clone()], vec![]);
+    assert_eq!(
+        diff(&old, &new),
+        vec![AddAttributes::new(
+            &"div",
+            TreePath::new(vec![0]),
+            vec![&new_token]
+        )
+        .into()]
+    );
+}
+
+#[test]
+fn custom_values_of_different_types_are_not_equal() {
+    let a: AttributeValue<()> = AttributeValue::from_custom(1u8);
+    let b: AttributeValue<()> = AttributeValue::from_custom(1u16);
+    assert_ne!(a, b);
+    assert_eq!(a.as_custom::<u8>(), Some(&1));
+    assert_eq!(a.as_custom::<u16>(), None);
+}
+
+#[test]
+fn custom_value_is_not_rendered() {
+    let view: Node<()> =
+        div(vec![class("box"), custom_attr("token", 42u32)], vec![]);
+    assert_eq!(view.render_to_string(), r#"<div class="box"></div>"#);
+}
@@ -0,0 +1,64 @@
+#![deny(warnings)]
+#![cfg(feature = "with-devtools")]
+use sauron::{
+    devtools::HOOK_NAME,
+    js_sys::{self, Reflect},
+    wasm_bindgen::{closure::Closure, JsCast, JsValue},
+    Dispatch,
+};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+fn call_hook(method: &str, arg: &JsValue) -> JsValue {
+    let hook = Reflect::get(&sauron::window(), &JsValue::from_str(HOOK_NAME))
+        .expect("must have the hook");
+    let method: js_sys::Function =
+        Reflect::get(&hook, &JsValue::from_str(method))
+            .expect("must have the method")
+            .unchecked_into();
+    method.call1(&hook, arg).expect("must call the method")
+}
+
+#[wasm_bindgen_test]
+async fn hook_exposes_the_vdom_and_patches() {
+    let program = simple_program();
+
+    let vdom = call_hook("vdom", &JsValue::UNDEFINED);
+    let tag = Reflect::get(&vdom, &JsValue::from_str("tag")).unwrap();
+    assert_eq!(tag.as_string(), Some("div".to_string()));
+    assert!(
+        call_hook("programs", &JsValue::UNDEFINED).as_f64().unwrap() >= 1.0
+    );
+
+    let batches = Rc::new(RefCell::new(0));
+    let batches_clone = Rc::clone(&batches);
+    let callback: Closure<dyn FnMut(JsValue)> =
+        Closure::wrap(Box::new(move |patches: JsValue| {
+            assert!(js_sys::Array::is_array(&patches));
+            *batches_clone.borrow_mut() += 1;
+        }));
+    let id = call_hook("subscribe", callback.as_ref());
+
+    program.dispatch(());
+    sleep(100).await;
+    assert_eq!(*batches.borrow(), 1);
+
+    call_hook("unsubscribe", &id);
+    program.dispatch(());
+    sleep(100).await;
+    assert_eq!(*batches.borrow(), 1);
+}
@@ -2,7 +2,7 @@
 use sauron_core::{
     diff,
     html::{attributes::*, events::*, *},
-    mt_dom::patch::*,
+    vdom::patch::*,
     Attribute, Node, *,
 };
 
@@ -95,12 +95,7 @@ fn truncate_children() {
     );
     assert_eq!(
         dbg!(diff(&old, &new)),
-        vec![
-            RemoveNode::new(Some(&"div"), TreePath::new(vec![0, 3]),).into(),
-            RemoveNode::new(Some(&"div"), TreePath::new(vec![0, 4]),).into(),
-            RemoveNode::new(Some(&"div"), TreePath::new(vec![0, 5]),).into(),
-            RemoveNode::new(Some(&"div"), TreePath::new(vec![0, 6]),).into(),
-        ],
+        vec![RemoveChildrenFrom::new(&"div", TreePath::new(vec![0]), 3).into(),],
         "Should truncate children"
     );
 }
@@ -153,10 +148,7 @@ fn truncate_children_different_attributes() {
                 vec![&class("class7")]
             )
             .into(),
-            RemoveNode::new(Some(&"div"), TreePath::new(vec![0, 3]),).into(),
-            RemoveNode::new(Some(&"div"), TreePath::new(vec![0, 4]),).into(),
-            RemoveNode::new(Some(&"div"), TreePath::new(vec![0, 5]),).into(),
-            RemoveNode::new(Some(&"div"), TreePath::new(vec![0, 6]),).into(),
+            RemoveChildrenFrom::new(&"div", TreePath::new(vec![0]), 3).into(),
         ],
         "Should truncate children"
     );
@@ -198,10 +190,7 @@ fn remove_nodes1() {
 
     assert_eq!(
         dbg!(diff(&old, &new)),
-        vec![
-            RemoveNode::new(Some(&"b"), TreePath::new(vec![0, 0]),).into(),
-            RemoveNode::new(Some(&"span"), TreePath::new(vec![0, 1]),).into(),
-        ],
+        vec![RemoveChildrenFrom::new(&"div", TreePath::new(vec![0]), 0).into()],
         "Remove all child nodes at and after child sibling index 1",
     );
 }
@@ -415,18 +404,38 @@ fn text_changed_in_keyed_elements() {
     assert_eq!(
         patch,
         vec![
+            RemoveNode::new(Some(&"article"), TreePath::new(vec![0, 0, 0]),)
+                .into(),
             ChangeText::new(
                 &Text::new("item3"),
                 TreePath::new(vec![0, 0, 2, 0]),
                 &Text::new("item3 with changes")
             )
             .into(),
-            RemoveNode::new(Some(&"article"), TreePath::new(vec![0, 0, 0]),)
-                .into(),
         ]
     );
 }
 
+#[test]
+fn keyed_children_are_moved() {
+    let item = |k: usize| li(vec![key(k)], vec![text(k)]);
+    let old: Node<()> = ul(vec![], vec![item(1), item(2), item(3), item(4)]);
+    let new: Node<()> = ul(vec![], vec![item(2), item(3), item(1), item(4)]);
+
+    // only the child which is out of order is moved
+    let patch = diff(&old, &new);
+    dbg!(&patch);
+    assert_eq!(
+        patch,
+        vec![MoveNode::new(
+            Some(&"li"),
+            TreePath::new(vec![0, 0]),
+            Some(TreePath::new(vec![0, 3]))
+        )
+        .into(),]
+    );
+}
+
 #[test]
 fn multiple_style_calls() {
     let old: Node<&'static str> = div(
@@ -458,3 +467,227 @@ fn multiple_style_calls() {
         .into()]
     );
 }
+
+#[test]
+fn static_node_is_not_diffed() {
+    let old: Node<()> = div(
+        vec![],
+        vec![
+            static_node(footer(vec![class("old")], vec![text("old")])),
+            p(vec![], vec![text("content")]),
+        ],
+    );
+    let new: Node<()> = div(
+        vec![],
+        vec![
+            static_node(footer(vec![class("new")], vec![text("new")])),
+            p(vec![], vec![text("content")]),
+        ],
+    );
+    assert_eq!(diff(&old, &new), vec![]);
+}
+
+#[test]
+fn text_node_is_left_as_is_by_static_node() {
+    let node: Node<()> = static_node(text("hello"));
+    assert_eq!(node, text("hello"));
+}
+
+#[test]
+fn nodes_matching_the_predicate_are_not_diffed() {
+    let old: Node<()> = div(
+        vec![],
+        vec![
+            svg::svg(vec![class("chart")], vec![]),
+            p(vec![], vec![text("old")]),
+        ],
+    );
+    let new: Node<()> = div(
+        vec![],
+        vec![
+            svg::svg(vec![class("updated-chart")], vec![]),
+            p(vec![], vec![text("new")]),
+        ],
+    );
+    let is_chart = |_old: &Node<()>, new: &Node<()>| {
+        new.as_element_ref()
+            .map(|element| *element.tag() == "svg")
+            .unwrap_or(false)
+    };
+    let patches = diff_with_skip(&old, &new, is_chart);
+    // only the text of the paragraph is changed
+    assert_eq!(patches.len(), 1);
+    assert_eq!(diff(&old, &new).len(), 2);
+}
+
+#[test]
+fn nodes_deeper_than_the_max_depth_are_replaced() {
+    set_diff_limits(DiffLimits {
+        max_depth: 2,
+        ..Default::default()
+    });
+    let old: Node<()> = div(
+        vec![],
+        vec![div(
+            vec![],
+            vec![div(vec![], vec![span(vec![], vec![text("a")])])],
+        )],
+    );
+    let new: Node<()> = div(
+        vec![],
+        vec![div(
+            vec![],
+            vec![div(vec![], vec![span(vec![], vec![text("b")])])],
+        )],
+    );
+    assert_eq!(
+        diff(&old, &new),
+        vec![ReplaceNode::new(
+            Some(&"div"),
+            TreePath::new(vec![0, 0, 0]),
+            &div(vec![], vec![span(vec![], vec![text("b")])])
+        )
+        .into()]
+    );
+    set_diff_limits(DiffLimits::default());
+    assert_eq!(
+        diff(&old, &new),
+        vec![ChangeText::new(
+            &Text::new("a"),
+            TreePath::new(vec![0, 0, 0, 0, 0]),
+            &Text::new("b")
+        )
+        .into()]
+    );
+}
+
+#[test]
+fn nodes_deeper_than_the_max_depth_are_compared_shallowly() {
+    set_diff_limits(DiffLimits {
+        max_depth: 1,
+        ..Default::default()
+    });
+    let nested = |class_name: &'static str| -> Node<()> {
+        div(
+            vec![],
+            vec![div(
+                vec![],
+                vec![ul(
+                    vec![],
+                    vec![li(vec![class(class_name)], vec![text("item")])],
+                )],
+            )],
+        )
+    };
+    let unchanged = diff(&nested("a"), &nested("a"));
+    let changed_attribute = diff(&nested("a"), &nested("b"));
+    set_diff_limits(DiffLimits::default());
+
+    assert!(unchanged.is_empty());
+    assert_eq!(changed_attribute.len(), 1);
+    assert_eq!(
+        PatchKind::from(&changed_attribute[0]),
+        PatchKind::ReplaceNode
+    );
+    assert_eq!(changed_attribute[0].path(), [0, 0]);
+}
+
+#[test]
+fn too_many_patches_replaces_the_common_ancestor() {
+    set_diff_limits(DiffLimits {
+        max_patches: 2,
+        ..Default::default()
+    });
+    let list = |items: [&'static str; 3]| {
+        ul(
+            vec![],
+            items.iter().map(|item| li(vec![], vec![text(item)])),
+        )
+    };
+    let old: Node<()> =
+        div(vec![], vec![h1(vec![], vec![]), list(["a", "b", "c"])]);
+    let new: Node<()> =
+        div(vec![], vec![h1(vec![], vec![]), list(["x", "y", "z"])]);
+    assert_eq!(
+        diff(&old, &new),
+        vec![ReplaceNode::new(
+            Some(&"ul"),
+            TreePath::new(vec![0, 1]),
+            &list(["x", "y", "z"])
+        )
+        .into()]
+    );
+}
+
+#[test]
+fn patches_into_the_removed_and_replaced_nodes_are_compressed() {
+    let replacement: Node<()> = p(vec![], vec![text("replaced")]);
+    let inserted: Node<()> = li(vec![], vec![text("inserted")]);
+    let patches: Vec<sauron_core::Patch<()>> = vec![
+        ChangeText::new(
+            &Text::new("one"),
+            TreePath::new(vec![0, 0, 0]),
+            &Text::new("uno"),
+        )
+        .into(),
+        InsertNode::new(Some(&"li"), TreePath::new(vec![0, 0]), &inserted)
+            .into(),
+        AddAttributes::new(&"li", TreePath::new(vec![0, 0]), vec![]).into(),
+        RemoveNode::new(Some(&"li"), TreePath::new(vec![0, 0])).into(),
+        ReplaceNode::new(Some(&"li"), TreePath::new(vec![0, 1]), &replacement)
+            .into(),
+        ChangeText::new(
+            &Text::new("three"),
+            TreePath::new(vec![0, 2, 0]),
+            &Text::new("tres"),
+        )
+        .into(),
+    ];
+    assert_eq!(
+        vdom::compress_patches(patches),
+        vec![
+            InsertNode::new(Some(&"li"), TreePath::new(vec![0, 0]), &inserted)
+                .into(),
+            RemoveNode::new(Some(&"li"), TreePath::new(vec![0, 0])).into(),
+            ReplaceNode::new(
+                Some(&"li"),
+                TreePath::new(vec![0, 1]),
+                &replacement
+            )
+            .into(),
+            ChangeText::new(
+                &Text::new("three"),
+                TreePath::new(vec![0, 2, 0]),
+                &Text::new("tres"),
+            )
+            .into(),
+        ]
+    );
+}
+
+#[test]
+fn diff_strategies() {
+    let old: Node<()> = ul(
+        vec![],
+        vec![
+            li(vec![key(1)], vec![text("a")]),
+            li(vec![key(2)], vec![text("b")]),
+        ],
+    );
+    let new: Node<()> = ul(vec![], vec![li(vec![key(2)], vec![text("b")])]);
+
+    assert_eq!(Keyed.diff(&old, &new), diff(&old, &new));
+    assert_eq!(HashMatched.diff(&old, &new), diff(&old, &new));
+    assert!(HashMatched.diff(&old, &old).is_empty());
+
+    // the keys are ignored, so the first item is changed and the last one is removed
+    let positional = Positional.diff(&old, &new);
+    assert!(positional.iter().any(|patch| {
+        PatchKind::from(patch) == PatchKind::RemoveNode
+            && patch.path() == [0, 1]
+    }));
+    assert!(positional.iter().any(|patch| {
+        PatchKind::from(patch) == PatchKind::ChangeText
+            && patch.path() == [0, 0, 0]
+    }));
+}
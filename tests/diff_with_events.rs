@@ -4,7 +4,7 @@ use sauron_core::{
     diff,
     dom::DomUpdater,
     html::{attributes::*, events::*, *},
-    mt_dom::patch::*,
+    vdom::patch::*,
     Node,
 };
 use test_fixtures::simple_program;
@@ -66,13 +66,15 @@ fn remove_event_from_replaced_node() {
         .into()],
     );
     let mut dom_updater =
-        DomUpdater::new_append_to_mount(&simple_program, old, &body);
+        DomUpdater::new_append_to_mount(&simple_program, old, &body).unwrap();
     assert_eq!(
         dom_updater.active_closure_len(),
         1,
         "There should be 1 event attached to the DomUpdater"
     );
-    dom_updater.update_dom(&simple_program, new);
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
 
     assert_eq!(
         dom_updater.active_closure_len(),
@@ -0,0 +1,105 @@
+#![deny(warnings)]
+use sauron::{
+    dom::cmd::Cmd,
+    html::{attributes::*, *},
+    Application, Dispatch, Node, Program,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// a program which doesn't dispatch anything
+#[derive(Clone, Default)]
+struct NoopProgram;
+
+impl Dispatch<()> for NoopProgram {
+    fn dispatch(&self, _msg: ()) {}
+
+    fn dispatch_multiple(&self, _msgs: Vec<()>) {}
+}
+
+/// resolves after `ms` milliseconds
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn reads_are_done_before_the_writes() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let push = |entry: &'static str| {
+        let log = Rc::clone(&log);
+        move || log.borrow_mut().push(entry)
+    };
+    let cmd: Cmd<NoopProgram> = Cmd::batch([
+        Cmd::dom_write(push("write 1")),
+        Cmd::dom_read(push("read 1")),
+        Cmd::dom_write(push("write 2")),
+        Cmd::dom_read(push("read 2")),
+    ]);
+    cmd.emit(&NoopProgram);
+    assert!(log.borrow().is_empty());
+
+    sleep(100).await;
+    assert_eq!(
+        *log.borrow(),
+        vec!["read 1", "read 2", "write 1", "write 2"]
+    );
+}
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// a counter which logs its text in the DOM, in the read and the write phase of its updates
+struct PhasedCounter {
+    count: u32,
+    log: Log,
+}
+
+fn counter_text() -> String {
+    sauron::document()
+        .get_element_by_id("phased-counter")
+        .and_then(|counter| counter.text_content())
+        .unwrap_or_default()
+}
+
+impl Application<u32> for PhasedCounter {
+    fn update(&mut self, count: u32) -> Cmd<Program<Self, u32>> {
+        self.count = count;
+        let read_log = Rc::clone(&self.log);
+        let write_log = Rc::clone(&self.log);
+        Cmd::batch([
+            Cmd::write_phase(move |_program| {
+                write_log
+                    .borrow_mut()
+                    .push(format!("write {}", counter_text()))
+            }),
+            Cmd::read_phase(move |_program| {
+                read_log
+                    .borrow_mut()
+                    .push(format!("read {}", counter_text()))
+            }),
+        ])
+        .should_update_view(true)
+    }
+
+    fn view(&self) -> Node<u32> {
+        div(vec![id("phased-counter")], vec![text(self.count)])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn reads_of_the_cmd_are_done_before_the_patches() {
+    let log: Log = Rc::new(RefCell::new(vec![]));
+    let program = Program::mount_to_body(PhasedCounter {
+        count: 0,
+        log: Rc::clone(&log),
+    });
+    program.dispatch(1);
+    sleep(100).await;
+    assert_eq!(*log.borrow(), vec!["read 0", "write 1"]);
+}
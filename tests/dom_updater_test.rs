@@ -4,9 +4,9 @@ use sauron_core::{
         attributes::{id, value},
         div,
         events::*,
-        input,
+        input, span, text, ul,
     },
-    web_sys, DomUpdater, Node,
+    web_sys, DomError, DomUpdater, Node,
 };
 use std::{cell::RefCell, rc::Rc};
 use test_fixtures::simple_program;
@@ -32,10 +32,13 @@ fn patches_dom() {
         &simple_program,
         vdom,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let new_vdom = div(vec![id("patched")], vec![]); //html! { <div id="patched"></div> };
-    dom_updater.update_dom(&simple_program, new_vdom);
+    dom_updater
+        .update_dom(&simple_program, new_vdom)
+        .expect("must patch the dom");
 
     assert_eq!(document.query_selector("#patched").unwrap().is_some(), true);
 }
@@ -52,7 +55,7 @@ fn updates_active_closure_on_replace() {
     let simple_program = simple_program();
     let old = div(vec![], vec![]);
     let mut dom_updater =
-        DomUpdater::new_append_to_mount(&simple_program, old, &body);
+        DomUpdater::new_append_to_mount(&simple_program, old, &body).unwrap();
 
     let text = Rc::new(RefCell::new("Start Text".to_string()));
     let text_clone = Rc::clone(&text);
@@ -73,7 +76,9 @@ fn updates_active_closure_on_replace() {
     // New node replaces old node.
     // We are testing that we've stored this new node's closures even though `new` will be dropped
     // at the end of this block.
-    dom_updater.update_dom(&simple_program, replace_node);
+    dom_updater
+        .update_dom(&simple_program, replace_node)
+        .expect("must patch the dom");
 
     let input_event = InputEvent::new("input").unwrap();
 
@@ -100,7 +105,7 @@ fn updates_active_closures_on_append() {
     let old = div(vec![], vec![]);
     let simple_program = simple_program();
     let mut dom_updater =
-        DomUpdater::new_append_to_mount(&simple_program, old, &body);
+        DomUpdater::new_append_to_mount(&simple_program, old, &body).unwrap();
 
     let text = Rc::new(RefCell::new("Start Text".to_string()));
     let text_clone = Rc::clone(&text);
@@ -127,7 +132,9 @@ fn updates_active_closures_on_append() {
         // New node gets appended into the DOM.
         // We are testing that we've stored this new node's closures even though `new` will be dropped
         // at the end of this block.
-        dom_updater.update_dom(&simple_program, append_node);
+        dom_updater
+            .update_dom(&simple_program, append_node)
+            .expect("must patch the dom");
     }
 
     let input_event = InputEvent::new("input").unwrap();
@@ -142,3 +149,109 @@ fn updates_active_closures_on_append() {
 
     assert_eq!(&*text.borrow(), "End Text");
 }
+
+// Appending many children at once is done with a single document fragment,
+// the closures of each of the appended nodes still needs to be stored.
+#[wasm_bindgen_test]
+fn appends_many_children_with_their_closures() {
+    console_error_panic_hook::set_once();
+
+    let body = sauron_core::body();
+    let old: Node<()> = ul(vec![id("append-many-children")], vec![]);
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, old, &body).unwrap();
+
+    let inputs = Rc::new(RefCell::new(0));
+    let inputs_clone = Rc::clone(&inputs);
+    let rows = (0..5000).map(|i| {
+        let inputs_clone = Rc::clone(&inputs_clone);
+        input(
+            vec![
+                id(format!("append-many-children-{}", i)),
+                on_input(move |_| *inputs_clone.borrow_mut() += 1),
+            ],
+            vec![],
+        )
+    });
+    dom_updater
+        .update_dom(&simple_program, ul(vec![id("append-many-children")], rows))
+        .expect("must patch the dom");
+
+    let document = sauron_core::document();
+    let container = document.get_element_by_id("append-many-children").unwrap();
+    assert_eq!(container.child_element_count(), 5000);
+
+    let last = document
+        .get_element_by_id("append-many-children-4999")
+        .unwrap();
+    web_sys::EventTarget::from(last)
+        .dispatch_event(&InputEvent::new("input").unwrap())
+        .unwrap();
+    assert_eq!(*inputs.borrow(), 1);
+}
+
+#[wasm_bindgen_test]
+fn rebuilds_the_dom_from_scratch() {
+    let document = sauron_core::document();
+    let vdom: Node<()> = div(vec![id("rebuild-dom")], vec![]);
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        vdom,
+        &sauron_core::body(),
+    )
+    .unwrap();
+
+    // the DOM is out of sync with the current vdom
+    let element = document.get_element_by_id("rebuild-dom").unwrap();
+    element.set_inner_html("<span>tampered</span>");
+
+    let new_vdom = div(vec![id("rebuild-dom")], vec![ul(vec![], vec![])]);
+    dom_updater
+        .rebuild_dom(&simple_program, new_vdom)
+        .expect("must rebuild the dom");
+
+    let element = document.get_element_by_id("rebuild-dom").unwrap();
+    assert_eq!(element.inner_html(), "<ul></ul>");
+    assert_eq!(
+        document
+            .query_selector_all("#rebuild-dom")
+            .unwrap()
+            .length(),
+        1
+    );
+}
+
+#[wasm_bindgen_test]
+fn patching_a_node_removed_behind_its_back_is_an_error() {
+    let document = sauron_core::document();
+    let vdom: Node<()> = div(
+        vec![id("removed-behind")],
+        vec![span(vec![], vec![text("a")]), span(vec![], vec![text("b")])],
+    );
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        vdom,
+        &sauron_core::body(),
+    )
+    .unwrap();
+
+    // the span is removed by a third-party javascript
+    let element = document.get_element_by_id("removed-behind").unwrap();
+    element.last_element_child().unwrap().remove();
+
+    let new_vdom = div(
+        vec![id("removed-behind")],
+        vec![span(vec![], vec![text("a")]), span(vec![], vec![text("c")])],
+    );
+    let result = dom_updater.update_dom(&simple_program, new_vdom);
+    assert!(matches!(result, Err(DomError::Patch(_))), "{:?}", result);
+}
+
+#[test]
+fn dom_error_message() {
+    let error = DomError::Patch("NotFoundError".to_string());
+    assert_eq!(error.to_string(), "unable to patch the dom: NotFoundError");
+}
@@ -1,5 +1,5 @@
 #![deny(warnings)]
-use sauron::{mt_dom::patch::*, *};
+use sauron::{vdom::patch::*, *};
 
 use test_fixtures::simple_program;
 use wasm_bindgen_test::*;
@@ -171,9 +171,12 @@ fn multiple_match_on_keyed_elements() {
         &simple_program,
         current_dom.clone(),
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
-    dom_updater.patch_dom(&simple_program, patches);
+    dom_updater
+        .patch_dom(&simple_program, patches)
+        .expect("must patch the dom");
 
     let app_node = crate::document()
         .query_selector(".app")
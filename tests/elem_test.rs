@@ -0,0 +1,70 @@
+#![deny(warnings)]
+use sauron::{html::attributes::*, html::*, node, Node, Render};
+
+#[test]
+fn builds_the_same_node_as_the_tag_functions() {
+    let built: Node<()> = Elem::new("div")
+        .class("container")
+        .id("main")
+        .style("display", "flex")
+        .child(p(vec![], vec![text("hello")]))
+        .text("world")
+        .build();
+
+    let expected: Node<()> = div(
+        vec![class("container"), id("main"), style("display", "flex")],
+        vec![p(vec![], vec![text("hello")]), text("world")],
+    );
+    assert_eq!(built, expected);
+}
+
+#[test]
+fn nested_elems_are_converted_into_nodes() {
+    let built: Node<()> = Elem::new("ul")
+        .key("list")
+        .child(Elem::new("li").text("one"))
+        .child(Elem::new("li").text("two"))
+        .into();
+
+    let expected: Node<()> = node! {
+        <ul key="list">
+            <li>"one"</li>
+            <li>"two"</li>
+        </ul>
+    };
+    assert_eq!(built, expected);
+}
+
+#[test]
+fn mixes_with_the_node_macro() {
+    let html: Node<()> = node! {
+        <main>
+            {Elem::new("span").class("badge").text("new").build()}
+        </main>
+    };
+    assert_eq!(
+        html.render_to_string(),
+        r#"<main><span class="badge">new</span></main>"#
+    );
+}
+
+#[test]
+fn self_closing_elem() {
+    let built: Node<()> = Elem::new("input")
+        .attr(r#type("text"))
+        .self_closing()
+        .build();
+    assert_eq!(built, input(vec![r#type("text")], vec![]));
+    assert_eq!(built.render_to_string(), r#"<input type="text"/>"#);
+}
+
+#[test]
+fn event_listeners_are_attached() {
+    let built: Node<&'static str> = Elem::new("button")
+        .on_click(|_| "clicked")
+        .text("click me")
+        .build();
+    let attrs = built.get_attributes().expect("must be an element");
+    assert_eq!(attrs.len(), 1);
+    assert_eq!(attrs[0].name(), &"click");
+}
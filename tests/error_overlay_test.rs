@@ -0,0 +1,38 @@
+#![deny(warnings)]
+#![cfg(feature = "with-error-overlay")]
+use sauron::{
+    error_overlay::{recent_msgs, MAX_RECENT_MSGS},
+    Dispatch,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn records_the_recent_msgs_once_installed() {
+    let program = simple_program();
+    program.dispatch(());
+    sleep(50).await;
+    assert!(recent_msgs().is_empty());
+
+    program.install_error_overlay();
+    for _ in 0..MAX_RECENT_MSGS + 5 {
+        program.dispatch(());
+    }
+    sleep(50).await;
+    let msgs = recent_msgs();
+    assert_eq!(msgs.len(), MAX_RECENT_MSGS);
+    assert!(msgs.iter().all(|msg| msg == "()"));
+}
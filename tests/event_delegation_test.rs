@@ -0,0 +1,200 @@
+#![deny(warnings)]
+
+use sauron_core::{
+    dom::DomUpdater,
+    html::{attributes::*, events::*, *},
+    vdom::patch::TreePath,
+    web_sys, Node,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn bubbling_click() -> web_sys::Event {
+    let mut event_init = web_sys::EventInit::new();
+    event_init.bubbles(true);
+    web_sys::Event::new_with_event_init_dict("click", &event_init).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn delegated_listener_is_called() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let clicks = Rc::new(Cell::new(0));
+    let clicks_clone = Rc::clone(&clicks);
+
+    let view: Node<()> = div(
+        vec![class("container")],
+        vec![button(
+            vec![
+                id("delegated-btn"),
+                on_click(move |_| clicks_clone.set(clicks_clone.get() + 1)),
+            ],
+            vec![text("click me")],
+        )],
+    );
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(view, &body);
+    dom_updater.set_event_delegation(true);
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+
+    assert_eq!(
+        dom_updater.active_closure_len(),
+        0,
+        "There should be no closure attached to the element"
+    );
+
+    let btn = sauron_core::document()
+        .get_element_by_id("delegated-btn")
+        .unwrap();
+    web_sys::EventTarget::from(btn)
+        .dispatch_event(&bubbling_click())
+        .unwrap();
+
+    assert_eq!(clicks.get(), 1);
+}
+
+#[wasm_bindgen_test]
+fn delegated_listener_is_removed_with_the_node() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let old: Node<()> = div(
+        vec![],
+        vec![button(vec![on_click(|_| log::trace!("clicked"))], vec![])],
+    );
+    let new: Node<()> = div(vec![], vec![]);
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(old, &body);
+    dom_updater.set_event_delegation(true);
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+    let delegated_before = dom_updater.delegated_listener_len();
+
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+    assert_eq!(dom_updater.delegated_listener_len(), delegated_before - 1);
+}
+
+#[wasm_bindgen_test]
+fn delegated_listeners_are_called_from_inner_to_outer() {
+    let calls = Rc::new(RefCell::new(vec![]));
+    let listener = |name: &'static str, stop: bool| {
+        let calls = Rc::clone(&calls);
+        on_click(move |event| {
+            calls.borrow_mut().push(name);
+            if stop {
+                event.stop_propagation();
+            }
+        })
+    };
+    let view: Node<()> = div(
+        vec![listener("outer", false)],
+        vec![
+            text("before"),
+            section(
+                vec![listener("middle", true), listener("middle again", false)],
+                vec![button(
+                    vec![id("ordered-btn"), listener("inner", false)],
+                    vec![],
+                )],
+            ),
+        ],
+    );
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(view, &body);
+    dom_updater.set_event_delegation(true);
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+
+    let btn = sauron_core::document()
+        .get_element_by_id("ordered-btn")
+        .unwrap();
+    let event = bubbling_click();
+    web_sys::EventTarget::from(btn)
+        .dispatch_event(&event)
+        .unwrap();
+
+    // the stop_propagation of the middle doesn't skip the other listener of the same element,
+    // but the outer listener is not called
+    assert_eq!(*calls.borrow(), vec!["inner", "middle", "middle again"]);
+    assert_eq!(
+        dom_updater.handled_by(&event),
+        vec![TreePath::new(vec![0, 1, 0]), TreePath::new(vec![0, 1])]
+    );
+}
+
+#[wasm_bindgen_test]
+fn delegated_listeners_are_kept_per_view() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let counter_view = |button_id: &'static str, clicks: &Rc<Cell<usize>>| {
+        let clicks = Rc::clone(clicks);
+        let view: Node<()> = div(
+            vec![],
+            vec![button(
+                vec![
+                    id(button_id),
+                    on_click(move |_| clicks.set(clicks.get() + 1)),
+                ],
+                vec![],
+            )],
+        );
+        view
+    };
+    let first_clicks = Rc::new(Cell::new(0));
+    let second_clicks = Rc::new(Cell::new(0));
+
+    let document = sauron_core::document();
+    let parent = document.create_element("div").unwrap();
+    sauron_core::body().append_child(&parent).unwrap();
+    let simple_program = simple_program();
+    let mut first =
+        DomUpdater::new(counter_view("first-view-btn", &first_clicks), &parent);
+    first.set_event_delegation(true);
+    first.append_to_mount(&simple_program).expect("must mount");
+    let mut second = DomUpdater::new(
+        counter_view("second-view-btn", &second_clicks),
+        &parent,
+    );
+    second.set_event_delegation(true);
+    second.append_to_mount(&simple_program).expect("must mount");
+    assert_eq!(first.delegated_listener_len(), 1);
+    assert_eq!(second.delegated_listener_len(), 1);
+
+    let click = |button_id: &str| {
+        let btn = document.get_element_by_id(button_id).unwrap();
+        web_sys::EventTarget::from(btn)
+            .dispatch_event(&bubbling_click())
+            .unwrap();
+    };
+
+    // each view only dispatch the events of its own elements
+    click("first-view-btn");
+    assert_eq!(first_clicks.get(), 1);
+    assert_eq!(second_clicks.get(), 0);
+    click("second-view-btn");
+    assert_eq!(first_clicks.get(), 1);
+    assert_eq!(second_clicks.get(), 1);
+
+    // the listeners attached to the root node are removed along with the DomUpdater
+    drop(first);
+    click("first-view-btn");
+    assert_eq!(first_clicks.get(), 1);
+    click("second-view-btn");
+    assert_eq!(second_clicks.get(), 2);
+}
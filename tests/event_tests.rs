@@ -4,7 +4,8 @@ use sauron_core::{
     diff,
     dom::DomUpdater,
     html::{attributes::*, events::*, *},
-    mt_dom::patch::*,
+    vdom::patch::RemoveChildrenFrom,
+    vdom::patch::*,
     web_sys, Node,
 };
 use std::{cell::RefCell, rc::Rc};
@@ -41,7 +42,7 @@ fn on_input_test() {
     let body = sauron_core::body();
     let simple_program = simple_program();
     let _dom_updater =
-        DomUpdater::new_append_to_mount(&simple_program, input, &body);
+        DomUpdater::new_append_to_mount(&simple_program, input, &body).unwrap();
 
     let input_element =
         sauron_core::document().get_element_by_id(&elem_id).unwrap();
@@ -90,9 +91,11 @@ fn added_event() {
     let body = sauron_core::body();
     let simple_program = simple_program();
     let mut dom_updater =
-        DomUpdater::new_append_to_mount(&simple_program, old, &body);
+        DomUpdater::new_append_to_mount(&simple_program, old, &body).unwrap();
     // update to new dom with no event attached
-    dom_updater.update_dom(&simple_program, new);
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
 
     let input_element =
         sauron_core::document().get_element_by_id(&elem_id).unwrap();
@@ -144,9 +147,11 @@ fn remove_event() {
     let body = sauron_core::body();
     let simple_program = simple_program();
     let mut dom_updater =
-        DomUpdater::new_append_to_mount(&simple_program, old, &body);
+        DomUpdater::new_append_to_mount(&simple_program, old, &body).unwrap();
     // update to new dom with no event attached
-    dom_updater.update_dom(&simple_program, new);
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
 
     let input_element =
         sauron_core::document().get_element_by_id(&elem_id).unwrap();
@@ -186,21 +191,18 @@ fn remove_event_from_truncated_children() {
     log::debug!("{:#?}", diff);
     assert_eq!(
         diff,
-        vec![
-            RemoveNode::new(Some(&"button"), TreePath::new(vec![0, 1]),).into(),
-            RemoveNode::new(Some(&"button"), TreePath::new(vec![0, 2]),).into(),
-            RemoveNode::new(Some(&"button"), TreePath::new(vec![0, 3]),).into(),
-            RemoveNode::new(Some(&"button"), TreePath::new(vec![0, 4]),).into(),
-        ],
+        vec![RemoveChildrenFrom::new(&"div", TreePath::new(vec![0]), 1).into(),],
     );
     let mut dom_updater =
-        DomUpdater::new_append_to_mount(&simple_program, old, &body);
+        DomUpdater::new_append_to_mount(&simple_program, old, &body).unwrap();
     assert_eq!(
         dom_updater.active_closure_len(),
         5,
         "There should be 5 events attached to the DomUpdater"
     );
-    dom_updater.update_dom(&simple_program, new);
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
 
     assert_eq!(
         dom_updater.active_closure_len(),
@@ -232,22 +234,19 @@ fn remove_event_from_truncated_children_some_with_no_events() {
     log::debug!("{:#?}", diff);
     assert_eq!(
         diff,
-        vec![
-            RemoveNode::new(Some(&"button"), TreePath::new(vec![0, 1]),).into(),
-            RemoveNode::new(Some(&"button"), TreePath::new(vec![0, 2]),).into(),
-            RemoveNode::new(Some(&"button"), TreePath::new(vec![0, 3]),).into(),
-            RemoveNode::new(Some(&"button"), TreePath::new(vec![0, 4]),).into(),
-        ],
+        vec![RemoveChildrenFrom::new(&"div", TreePath::new(vec![0]), 1).into(),],
         "Should be a Truncate patch"
     );
     let mut dom_updater =
-        DomUpdater::new_append_to_mount(&simple_program, old, &body);
+        DomUpdater::new_append_to_mount(&simple_program, old, &body).unwrap();
     assert_eq!(
         dom_updater.active_closure_len(),
         3,
         "There should be 3 events attached to the DomUpdater"
     );
-    dom_updater.update_dom(&simple_program, new);
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
 
     assert_eq!(
         dom_updater.active_closure_len(),
@@ -279,13 +278,15 @@ fn remove_event_from_replaced_node() {
         .into()],
     );
     let mut dom_updater =
-        DomUpdater::new_append_to_mount(&simple_program, old, &body);
+        DomUpdater::new_append_to_mount(&simple_program, old, &body).unwrap();
     assert_eq!(
         dom_updater.active_closure_len(),
         1,
         "There should be 1 event attached to the DomUpdater"
     );
-    dom_updater.update_dom(&simple_program, new);
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
 
     assert_eq!(
         dom_updater.active_closure_len(),
@@ -293,3 +294,36 @@ fn remove_event_from_replaced_node() {
         "There should only be 0 left after replacing it with a different tag"
     );
 }
+
+#[wasm_bindgen_test]
+fn media_event_group_test() {
+    let played = Rc::new(RefCell::new(false));
+    let played_clone = Rc::clone(&played);
+
+    let elem_id = "media-event-group";
+    let audio: Node<()> = audio(
+        vec![
+            id(elem_id),
+            on_play(move |_event: web_sys::Event| {
+                *played_clone.borrow_mut() = true;
+            }),
+        ],
+        vec![],
+    );
+
+    let simple_program = simple_program();
+    let _dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        audio,
+        &sauron_core::body(),
+    )
+    .unwrap();
+
+    let audio_element =
+        sauron_core::document().get_element_by_id(elem_id).unwrap();
+    web_sys::EventTarget::from(audio_element)
+        .dispatch_event(&web_sys::Event::new("play").unwrap())
+        .unwrap();
+
+    assert!(*played.borrow());
+}
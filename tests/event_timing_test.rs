@@ -0,0 +1,114 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{cmd::Cmd, test_util::TestClock, DomUpdater},
+    html::{attributes::*, events::*, *},
+    web_sys, Attribute, Dispatch, EventTiming, Node,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// records the dispatched msgs instead of updating an app
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<u32>>>);
+
+impl Dispatch<u32> for Recorder {
+    fn dispatch(&self, msg: u32) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<u32>) {
+        self.0.borrow_mut().extend(msgs);
+    }
+}
+
+impl Recorder {
+    fn take(&self) -> Vec<u32> {
+        self.0.borrow_mut().drain(..).collect()
+    }
+}
+
+/// mount the button, then returns a function which clicks it with the msg
+fn mount_button(
+    recorder: &Recorder,
+    button_id: &'static str,
+    listener: impl Fn(Attribute<u32>) -> Attribute<u32>,
+) -> impl Fn(u32) {
+    let clicked = Rc::new(RefCell::new(0));
+    let clicked_clone = Rc::clone(&clicked);
+    let view: Node<u32> = button(
+        vec![
+            id(button_id),
+            listener(on_click(move |_| *clicked_clone.borrow())),
+        ],
+        vec![],
+    );
+    let mut dom_updater = DomUpdater::new(view, &sauron::body());
+    dom_updater.append_to_mount(recorder).expect("must mount");
+    std::mem::forget(dom_updater);
+
+    let btn: web_sys::HtmlElement = sauron::document()
+        .get_element_by_id(button_id)
+        .expect("must have the button")
+        .unchecked_into();
+    move |msg| {
+        *clicked.borrow_mut() = msg;
+        btn.click();
+    }
+}
+
+#[wasm_bindgen_test]
+fn debounced_listener_dispatch_only_the_last_msg() {
+    let clock = TestClock::install();
+    let recorder = Recorder::default();
+    let click =
+        mount_button(&recorder, "debounced-btn", |attr| attr.debounce(300));
+
+    click(1);
+    clock.advance(100);
+    click(2);
+    clock.advance(100);
+    click(3);
+    clock.advance(299);
+    assert!(recorder.take().is_empty());
+    clock.advance(1);
+    assert_eq!(recorder.take(), vec![3]);
+    assert_eq!(clock.pending(), 0);
+}
+
+#[wasm_bindgen_test]
+fn throttled_listener_dispatch_the_first_and_the_last_msg_of_the_period() {
+    let clock = TestClock::install();
+    let recorder = Recorder::default();
+    let click =
+        mount_button(&recorder, "throttled-btn", |attr| attr.throttle(100));
+
+    click(1);
+    assert_eq!(recorder.take(), vec![1]);
+    clock.advance(50);
+    click(2);
+    click(3);
+    assert!(recorder.take().is_empty());
+    clock.advance(50);
+    assert_eq!(recorder.take(), vec![3]);
+    clock.advance(100);
+    click(4);
+    assert_eq!(recorder.take(), vec![4]);
+}
+
+#[wasm_bindgen_test]
+fn debounced_cmd_dispatch_only_the_last_msg_of_the_key() {
+    let clock = TestClock::install();
+    let recorder = Recorder::default();
+    Cmd::debounced("search", 300, 1).emit(&recorder);
+    Cmd::debounced("other", 300, 10).emit(&recorder);
+    clock.advance(200);
+    Cmd::debounced("search", 300, 2).emit(&recorder);
+    clock.advance(100);
+    assert_eq!(recorder.take(), vec![10]);
+    clock.advance(200);
+    assert_eq!(recorder.take(), vec![2]);
+    assert_eq!(clock.pending(), 0);
+}
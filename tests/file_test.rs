@@ -0,0 +1,66 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{cmd::Cmd, FileError, FileHandle},
+    js_sys, web_sys, Dispatch,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[derive(Debug)]
+enum Msg {
+    Text(Result<String, FileError>),
+    Bytes(Result<Vec<u8>, FileError>),
+}
+
+/// records the dispatched msgs instead of updating an app
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<Msg>>>);
+
+impl Dispatch<Msg> for Recorder {
+    fn dispatch(&self, msg: Msg) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<Msg>) {
+        self.0.borrow_mut().extend(msgs);
+    }
+}
+
+fn text_file(name: &str, content: &str) -> FileHandle {
+    let parts = js_sys::Array::of1(&content.into());
+    FileHandle::from(
+        web_sys::File::new_with_str_sequence(&parts, name).unwrap(),
+    )
+}
+
+#[wasm_bindgen_test]
+async fn file_content_is_read_as_text_and_bytes() {
+    let recorder = Recorder::default();
+    let file = text_file("notes.txt", "hello");
+    assert_eq!(file.name(), "notes.txt");
+    assert_eq!(file.size(), 5.0);
+
+    Cmd::read_file_text(&file, Msg::Text).emit(&recorder);
+    Cmd::read_file_bytes(&file, Msg::Bytes).emit(&recorder);
+    sleep(100).await;
+    let msgs = recorder.0.borrow();
+    assert_eq!(msgs.len(), 2);
+    for msg in msgs.iter() {
+        match msg {
+            Msg::Text(Ok(text)) => assert_eq!(text, "hello"),
+            Msg::Bytes(Ok(bytes)) => assert_eq!(bytes, b"hello"),
+            msg => panic!("unexpected msg: {:?}", msg),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+#![deny(warnings)]
+use sauron::{
+    dom::cmd::Cmd,
+    html::{attributes::*, *},
+    web_sys, Application, Dispatch, Node, Program,
+};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Msg {
+    Open,
+    FocusClose,
+}
+
+/// a page with a modal which traps the focus
+struct Page {
+    is_open: bool,
+}
+
+impl Application<Msg> for Page {
+    fn update(&mut self, msg: Msg) -> Cmd<Program<Self, Msg>> {
+        match msg {
+            Msg::Open => {
+                self.is_open = true;
+                Cmd::none()
+            }
+            Msg::FocusClose => Cmd::focus("#modal-close"),
+        }
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            vec![],
+            vec![
+                button(vec![id("open")], vec![text("Open")]),
+                div(
+                    vec![class("modal"), focus_trap()],
+                    vec![
+                        input(
+                            vec![id("modal-name"), autofocus(self.is_open)],
+                            vec![],
+                        ),
+                        button(vec![id("modal-close")], vec![text("Close")]),
+                    ],
+                ),
+            ],
+        )
+    }
+}
+
+fn active_id() -> String {
+    sauron::document()
+        .active_element()
+        .map(|element| element.id())
+        .unwrap_or_default()
+}
+
+fn press_tab(shift: bool) {
+    let mut init = web_sys::KeyboardEventInit::new();
+    init.key("Tab")
+        .shift_key(shift)
+        .bubbles(true)
+        .cancelable(true);
+    let event = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict(
+        "keydown", &init,
+    )
+    .unwrap();
+    let target = sauron::document()
+        .active_element()
+        .map(|element| element.unchecked_into::<web_sys::EventTarget>())
+        .unwrap_or_else(|| sauron::body().unchecked_into());
+    target.dispatch_event(&event).unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn focus_is_set_on_patch_moved_by_cmd_and_trapped_in_the_modal() {
+    let program = Program::mount_to_body(Page { is_open: false });
+    assert_ne!(active_id(), "modal-name");
+
+    program.dispatch(Msg::Open);
+    sleep(100).await;
+    assert_eq!(active_id(), "modal-name");
+
+    program.dispatch(Msg::FocusClose);
+    sleep(100).await;
+    assert_eq!(active_id(), "modal-close");
+
+    // tab on the last element moves back to the first element of the trap
+    press_tab(false);
+    assert_eq!(active_id(), "modal-name");
+    // shift+tab on the first element moves to the last element of the trap
+    press_tab(true);
+    assert_eq!(active_id(), "modal-close");
+}
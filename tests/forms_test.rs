@@ -0,0 +1,121 @@
+#![deny(warnings)]
+use sauron::{
+    diff,
+    forms::{FieldArray, LengthError},
+    html::{attributes::*, *},
+    vdom::patch::PatchKind,
+    Node, Render,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+struct LineItem {
+    name: String,
+    quantity: u32,
+}
+
+fn item(name: &str, quantity: u32) -> LineItem {
+    LineItem {
+        name: name.to_string(),
+        quantity,
+    }
+}
+
+fn line_items() -> FieldArray<LineItem> {
+    FieldArray::new()
+        .validator(|item: &LineItem| {
+            let mut errors = vec![];
+            if item.name.is_empty() {
+                errors.push("name is required".to_string());
+            }
+            if item.quantity == 0 {
+                errors.push("quantity must be positive".to_string());
+            }
+            errors
+        })
+        .min_rows(1)
+        .max_rows(3)
+}
+
+fn view(items: &FieldArray<LineItem>) -> Node<()> {
+    ul(
+        vec![],
+        items.view(|row| {
+            let attrs = if row.is_valid() {
+                vec![]
+            } else {
+                vec![class("invalid")]
+            };
+            li(attrs, vec![text(&row.value().name)])
+        }),
+    )
+}
+
+#[test]
+fn rows_are_validated() {
+    let mut items = line_items();
+    assert_eq!(items.length_error(), Some(LengthError::TooFew { min: 1 }));
+    assert!(!items.is_valid());
+
+    let pen = items.push(item("pen", 0));
+    assert_eq!(items.errors(pen), ["quantity must be positive"]);
+    assert_eq!(items.invalid_rows(), vec![pen]);
+
+    assert!(items.update(pen, |pen| pen.quantity = 2));
+    assert!(items.errors(pen).is_empty());
+    assert!(items.is_valid());
+
+    items.push(item("ink", 1));
+    items.push(item("paper", 1));
+    assert!(!items.can_add());
+    items.push(item("", 1));
+    assert_eq!(items.length_error(), Some(LengthError::TooMany { max: 3 }));
+    assert_eq!(
+        LengthError::TooMany { max: 3 }.to_string(),
+        "must have at most 3 rows"
+    );
+}
+
+#[test]
+fn keys_are_stable_when_rows_are_moved_and_removed() {
+    let mut items = line_items();
+    let pen = items.push(item("pen", 1));
+    let ink = items.push(item("ink", 1));
+    let paper = items.insert(0, item("paper", 1));
+    assert_eq!(items.keys(), vec![paper, pen, ink]);
+
+    assert!(items.move_row(paper, 10));
+    assert_eq!(items.keys(), vec![pen, ink, paper]);
+    assert!(items.move_up(paper));
+    assert!(!items.move_up(pen));
+    assert!(items.move_down(pen));
+    assert_eq!(items.keys(), vec![paper, pen, ink]);
+
+    assert_eq!(items.remove(pen), Some(item("pen", 1)));
+    assert_eq!(items.remove(pen), None);
+    let new = items.push(item("pen", 1));
+    assert_ne!(new, pen);
+    assert_eq!(
+        items.into_values(),
+        vec![item("paper", 1), item("ink", 1), item("pen", 1)]
+    );
+}
+
+#[test]
+fn rows_are_keyed_in_the_view() {
+    let mut items = line_items();
+    let pen = items.push(item("pen", 1));
+    items.push(item("ink", 1));
+    let old = view(&items);
+    assert_eq!(
+        old.render_to_string(),
+        r#"<ul><li key="0">pen</li><li key="1">ink</li></ul>"#
+    );
+
+    items.remove(pen);
+    let new = view(&items);
+    let patches = diff(&old, &new);
+    assert_eq!(
+        patches.iter().map(PatchKind::from).collect::<Vec<_>>(),
+        vec![PatchKind::RemoveNode]
+    );
+}
@@ -0,0 +1,76 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{gpu_canvas, DomUpdater, GpuRenderer},
+    html::attributes::*,
+    web_sys::HtmlCanvasElement,
+    Node,
+};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Calls {
+    init: usize,
+    frames: usize,
+}
+
+struct CountingRenderer(Rc<RefCell<Calls>>);
+
+impl GpuRenderer for CountingRenderer {
+    fn init(&mut self, _canvas: &HtmlCanvasElement) {
+        self.0.borrow_mut().init += 1;
+    }
+
+    fn render(&mut self, _timestamp: f64) {
+        self.0.borrow_mut().frames += 1;
+    }
+}
+
+/// resolves after `ms` milliseconds
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn renderer_is_called_until_the_canvas_is_removed() {
+    let calls = Rc::new(RefCell::new(Calls::default()));
+    let view: Node<()> = gpu_canvas(
+        vec![id("gpu-canvas-1"), style("width", "100px")],
+        CountingRenderer(Rc::clone(&calls)),
+        |_event| (),
+    );
+
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(view, &body);
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+    sleep(100).await;
+
+    assert_eq!(calls.borrow().init, 1);
+    assert!(calls.borrow().frames > 0);
+    let canvas: HtmlCanvasElement = wasm_bindgen::JsCast::unchecked_into(
+        sauron::document()
+            .get_element_by_id("gpu-canvas-1")
+            .unwrap(),
+    );
+    let device_pixel_ratio = sauron::window().device_pixel_ratio();
+    assert_eq!(canvas.width(), (100.0 * device_pixel_ratio) as u32);
+
+    canvas.remove();
+    sleep(50).await;
+    let frames = calls.borrow().frames;
+    sleep(100).await;
+    assert_eq!(calls.borrow().frames, frames);
+}
@@ -0,0 +1,160 @@
+#![deny(warnings)]
+use sauron::{
+    html::{head::HEAD_ATTR, *},
+    Application, Cmd, Dispatch, Node, Program, Render,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[test]
+fn render_the_head_for_the_server() {
+    let head = Head::new()
+        .title("<Home>")
+        .meta("description", "first")
+        .meta_property("og:title", "Home")
+        // the later meta with the same name replaces the earlier one
+        .meta("description", "\"quoted\"");
+    assert_eq!(
+        head.render_to_string_pretty(),
+        concat!(
+            "<title>&lt;Home&gt;</title>\n",
+            r#"<meta name="description" content="&quot;quoted&quot;" data-sauron-head="meta:name:description"/>"#,
+            "\n",
+            r#"<meta property="og:title" content="Home" data-sauron-head="meta:property:og:title"/>"#,
+        )
+    );
+    assert!(Head::new().is_empty());
+    let head = Head::new()
+        .link("canonical", "https://example.com/")
+        .link("canonical", "https://example.com/home")
+        .alternate("en", "https://example.com/en")
+        .alternate("fr", "https://example.com/fr");
+    assert_eq!(
+        head.render_to_string(),
+        concat!(
+            r#"<link rel="canonical" href="https://example.com/home" data-sauron-head="link:canonical"/>"#,
+            r#"<link rel="alternate" hreflang="en" href="https://example.com/en" data-sauron-head="link:alternate:en"/>"#,
+            r#"<link rel="alternate" hreflang="fr" href="https://example.com/fr" data-sauron-head="link:alternate:fr"/>"#,
+        )
+    );
+    assert_eq!(Head::new().render_to_string(), "");
+}
+
+#[test]
+fn head_nodes_are_rendered_in_the_page() {
+    let page_head = Head::new()
+        .title("Home")
+        .meta("description", "the home page");
+    // the server puts the head of the app into the page
+    let page: Node<()> = html(vec![], vec![head(vec![], page_head.nodes())]);
+    assert_eq!(
+        page.render_to_string(),
+        concat!(
+            "<html><head><title>Home</title>",
+            r#"<meta name="description" content="the home page" data-sauron-head="meta:name:description"/>"#,
+            "</head></html>",
+        )
+    );
+}
+
+enum Msg {
+    Open(&'static str),
+    Rename(&'static str),
+}
+
+struct Page {
+    name: &'static str,
+}
+
+impl Application<Msg> for Page {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Open(name) => {
+                self.name = name;
+                Cmd::none().update_head()
+            }
+            Msg::Rename(title) => Cmd::set_title(title),
+        }
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(vec![], vec![text(self.name)])
+    }
+
+    fn head(&self) -> Head {
+        let head = Head::new()
+            .title(format!("{} page", self.name))
+            .meta("description", format!("the {} page", self.name));
+        match self.name {
+            "home" => head.link("canonical", "https://example.com/"),
+            "news" => head.link("canonical", "https://example.com/news"),
+            _ => head,
+        }
+    }
+}
+
+fn head_tags() -> Vec<String> {
+    let found = sauron::document()
+        .head()
+        .unwrap()
+        .query_selector_all(&format!("[{}]", HEAD_ATTR))
+        .unwrap();
+    (0..found.length())
+        .filter_map(|i| found.item(i))
+        .map(|node| {
+            use sauron::wasm_bindgen::JsCast;
+            node.unchecked_into::<sauron::web_sys::Element>()
+                .outer_html()
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test]
+async fn head_of_the_app_is_applied_into_the_document() {
+    let program = Program::mount_to_body(Page { name: "home" });
+    assert_eq!(sauron::document().title(), "home page");
+    assert_eq!(
+        head_tags(),
+        vec![
+            r#"<meta data-sauron-head="meta:name:description" name="description" content="the home page">"#,
+            r#"<link data-sauron-head="link:canonical" rel="canonical" href="https://example.com/">"#,
+        ]
+    );
+
+    program.dispatch(Msg::Open("news"));
+    sleep(50).await;
+    // the link with the same rel is updated in place
+    assert_eq!(
+        head_tags(),
+        vec![
+            r#"<meta data-sauron-head="meta:name:description" name="description" content="the news page">"#,
+            r#"<link data-sauron-head="link:canonical" rel="canonical" href="https://example.com/news">"#,
+        ]
+    );
+
+    program.dispatch(Msg::Open("about"));
+    sleep(50).await;
+    assert_eq!(sauron::document().title(), "about page");
+    // the meta tag is updated in place, while the link is removed
+    assert_eq!(
+        head_tags(),
+        vec![
+            r#"<meta data-sauron-head="meta:name:description" name="description" content="the about page">"#,
+        ]
+    );
+
+    program.dispatch(Msg::Rename("renamed"));
+    sleep(50).await;
+    assert_eq!(sauron::document().title(), "renamed");
+    program.unmount();
+}
@@ -0,0 +1,97 @@
+#![deny(warnings)]
+#![cfg(feature = "with-dom")]
+use sauron::{
+    backend::HeadlessProgram,
+    html::{attributes::*, *},
+    Application, Cmd, Node, Render,
+};
+
+enum Msg {
+    Add(&'static str),
+    Remove(usize),
+    Toggle(usize),
+    Rename(&'static str),
+    Noop,
+}
+
+struct Todos {
+    title: &'static str,
+    items: Vec<(&'static str, bool)>,
+}
+
+impl Application<Msg> for Todos {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Add(item) => self.items.push((item, false)),
+            Msg::Remove(index) => {
+                self.items.remove(index);
+            }
+            Msg::Toggle(index) => self.items[index].1 = !self.items[index].1,
+            Msg::Rename(title) => self.title = title,
+            Msg::Noop => return Cmd::none().no_render(),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        main(
+            vec![class("todos")],
+            vec![
+                h1(vec![], vec![text(self.title)]),
+                ul(
+                    vec![],
+                    self.items.iter().map(|(item, done)| {
+                        let attrs =
+                            if *done { vec![class("done")] } else { vec![] };
+                        li(attrs, vec![text(item)])
+                    }),
+                ),
+            ],
+        )
+    }
+}
+
+#[test]
+fn mount_dispatch_and_patch() {
+    let mut program = HeadlessProgram::mount(Todos {
+        title: "todos",
+        items: vec![],
+    });
+    assert_eq!(
+        program.html(),
+        r#"<main class="todos"><h1>todos</h1><ul></ul></main>"#
+    );
+
+    program
+        .dispatch_multiple(vec![Msg::Add("milk"), Msg::Add("eggs")])
+        .unwrap();
+    assert_eq!(
+        program.html(),
+        r#"<main class="todos"><h1>todos</h1><ul><li>milk</li><li>eggs</li></ul></main>"#
+    );
+
+    program.dispatch(Msg::Toggle(1)).unwrap();
+    assert_eq!(
+        program.html(),
+        r#"<main class="todos"><h1>todos</h1><ul><li>milk</li><li class="done">eggs</li></ul></main>"#
+    );
+
+    program.dispatch(Msg::Remove(0)).unwrap();
+    program.dispatch(Msg::Rename("groceries")).unwrap();
+    assert_eq!(
+        program.html(),
+        r#"<main class="todos"><h1>groceries</h1><ul><li class="done">eggs</li></ul></main>"#
+    );
+    assert_eq!(program.app().items.len(), 1);
+    // the patches applied into the vdom result to the view of the app
+    assert_eq!(program.current_vdom(), &program.app().view());
+}
+
+#[test]
+fn no_render_cmd_does_not_patch() {
+    let mut program = HeadlessProgram::mount(Todos {
+        title: "todos",
+        items: vec![("milk", false)],
+    });
+    assert_eq!(program.dispatch(Msg::Noop), Ok(0));
+}
@@ -0,0 +1,85 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{
+        hotkey::{KeyCombo, KeyComboError},
+        sub::Sub,
+    },
+    web_sys, Dispatch,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// records the dispatched msgs instead of updating an app
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<u32>>>);
+
+impl Dispatch<u32> for Recorder {
+    fn dispatch(&self, msg: u32) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<u32>) {
+        self.0.borrow_mut().extend(msgs);
+    }
+}
+
+impl Recorder {
+    fn take(&self) -> Vec<u32> {
+        self.0.borrow_mut().drain(..).collect()
+    }
+}
+
+fn press(key: &str, ctrl: bool) {
+    let mut init = web_sys::KeyboardEventInit::new();
+    init.key(key).ctrl_key(ctrl).bubbles(true).cancelable(true);
+    let event = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict(
+        "keydown", &init,
+    )
+    .unwrap();
+    sauron::body().dispatch_event(&event).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn key_combos_are_parsed() {
+    assert_eq!(
+        "Ctrl+Shift+K".parse::<KeyCombo>(),
+        Ok(KeyCombo {
+            ctrl: true,
+            shift: true,
+            key: "k".to_string(),
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        "esc".parse::<KeyCombo>().map(|combo| combo.key),
+        Ok("escape".to_string())
+    );
+    assert_eq!(
+        "ctrl+alt".parse::<KeyCombo>(),
+        Err(KeyComboError::MissingKey("ctrl+alt".to_string()))
+    );
+    assert_eq!(
+        "a+b".parse::<KeyCombo>(),
+        Err(KeyComboError::MultipleKeys("a+b".to_string()))
+    );
+}
+
+#[wasm_bindgen_test]
+fn hotkeys_dispatch_the_msg_of_the_matched_combo() {
+    let recorder = Recorder::default();
+    let subscriptions =
+        Sub::hotkeys([("ctrl+k", 1), ("esc", 2)]).subscribe(&recorder);
+
+    press("k", true);
+    press("k", false);
+    press("Escape", false);
+    assert_eq!(recorder.take(), vec![1, 2]);
+
+    for subscription in subscriptions {
+        subscription.unsubscribe();
+    }
+    press("k", true);
+    assert!(recorder.take().is_empty());
+}
@@ -1,5 +1,5 @@
 #![deny(warnings)]
-use sauron_core::{mt_dom::patch::*, prelude::*};
+use sauron_core::{prelude::*, vdom::patch::*};
 
 #[test]
 fn test_macros() {
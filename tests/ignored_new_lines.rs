@@ -1,8 +1,9 @@
 #![deny(warnings)]
 use sauron::{
     html::{attributes::*, *},
-    mt_dom::patch::*,
-    node, *,
+    node,
+    vdom::patch::*,
+    *,
 };
 
 #[test]
@@ -0,0 +1,119 @@
+#![deny(warnings)]
+#![cfg(feature = "with-persist")]
+use sauron::{
+    dom::storage::{indexeddb::Database, StorageError},
+    Dispatch,
+};
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Draft {
+    title: String,
+    words: u32,
+}
+
+#[derive(Debug)]
+enum Msg {
+    Saved(Result<(), StorageError>),
+    Loaded(Result<Option<Draft>, StorageError>),
+    Listed(Result<Vec<(String, Draft)>, StorageError>),
+    Deleted(Result<(), StorageError>),
+}
+
+/// records the dispatched msgs instead of updating an app
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<Msg>>>);
+
+impl Recorder {
+    fn take(&self) -> Vec<Msg> {
+        self.0.borrow_mut().drain(..).collect()
+    }
+}
+
+impl Dispatch<Msg> for Recorder {
+    fn dispatch(&self, msg: Msg) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<Msg>) {
+        self.0.borrow_mut().extend(msgs);
+    }
+}
+
+fn draft(title: &str, words: u32) -> Draft {
+    Draft {
+        title: title.to_string(),
+        words,
+    }
+}
+
+#[wasm_bindgen_test]
+async fn values_are_stored_listed_and_deleted() {
+    let recorder = Recorder::default();
+    let db = Database::new("indexeddb-test", 1).with_store("drafts");
+
+    db.put("drafts", "b", &draft("second", 20), Msg::Saved)
+        .emit(&recorder);
+    db.put("drafts", "a", &draft("first", 10), Msg::Saved)
+        .emit(&recorder);
+    sleep(200).await;
+    let msgs = recorder.take();
+    assert_eq!(msgs.len(), 2);
+    assert!(msgs.iter().all(|msg| matches!(msg, Msg::Saved(Ok(())))));
+
+    db.get("drafts", "a", Msg::Loaded).emit(&recorder);
+    sleep(200).await;
+    match recorder.take().as_slice() {
+        [Msg::Loaded(Ok(Some(loaded)))] => {
+            assert_eq!(*loaded, draft("first", 10))
+        }
+        msgs => panic!("unexpected msgs: {:?}", msgs),
+    }
+
+    db.iterate("drafts", Msg::Listed).emit(&recorder);
+    sleep(200).await;
+    match recorder.take().as_slice() {
+        [Msg::Listed(Ok(entries))] => assert_eq!(
+            *entries,
+            vec![
+                ("a".to_string(), draft("first", 10)),
+                ("b".to_string(), draft("second", 20))
+            ]
+        ),
+        msgs => panic!("unexpected msgs: {:?}", msgs),
+    }
+
+    db.delete("drafts", "a", Msg::Deleted).emit(&recorder);
+    sleep(200).await;
+    db.get("drafts", "a", Msg::Loaded).emit(&recorder);
+    sleep(200).await;
+    match recorder.take().as_slice() {
+        [Msg::Deleted(Ok(())), Msg::Loaded(Ok(None))] => (),
+        msgs => panic!("unexpected msgs: {:?}", msgs),
+    }
+}
+
+#[wasm_bindgen_test]
+async fn missing_store_is_an_error() {
+    let recorder = Recorder::default();
+    let db = Database::new("indexeddb-missing-test", 1);
+    db.get("drafts", "a", Msg::Loaded).emit(&recorder);
+    sleep(200).await;
+    match recorder.take().as_slice() {
+        [Msg::Loaded(Err(StorageError::Js(_)))] => (),
+        msgs => panic!("unexpected msgs: {:?}", msgs),
+    }
+}
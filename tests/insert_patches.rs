@@ -1,7 +1,7 @@
 #![deny(warnings)]
 use sauron_core::{
     html::{attributes::*, *},
-    mt_dom::patch::*,
+    vdom::patch::*,
     *,
 };
 
@@ -64,7 +64,8 @@ fn test_patch_insert_node() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let container = document
         .query_selector(".test1")
@@ -75,7 +76,9 @@ fn test_patch_insert_node() {
 
     assert_eq!(expected, container.outer_html());
 
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".test1")
@@ -140,7 +143,8 @@ fn test_patch_insert_node_in_the_middle() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let container = document
         .query_selector(".test_middle")
@@ -151,7 +155,9 @@ fn test_patch_insert_node_in_the_middle() {
 
     assert_eq!(expected, container.outer_html());
 
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".test_middle")
@@ -239,7 +245,8 @@ fn multiple_insert_should_work() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let container = document
         .query_selector(".test5")
@@ -250,7 +257,9 @@ fn multiple_insert_should_work() {
 
     assert_eq!(expected, container.outer_html());
 
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".test5")
@@ -261,3 +270,145 @@ fn multiple_insert_should_work() {
 
     assert_eq!(expected1, container.outer_html());
 }
+
+#[test]
+fn unkeyed_insertion_in_the_middle_is_an_insert_node() {
+    let old: Node<()> = ul(
+        vec![class("todo")],
+        vec![
+            li(vec![], vec![text("item1")]),
+            li(vec![], vec![text("item2")]),
+        ],
+    );
+    let new: Node<()> = ul(
+        vec![class("todo")],
+        vec![
+            li(vec![], vec![text("item1")]),
+            li(vec![], vec![text("item1.5")]),
+            li(vec![], vec![text("item2")]),
+        ],
+    );
+    assert_eq!(
+        diff(&old, &new),
+        vec![InsertNode::new(
+            Some(&"ul"),
+            TreePath::new(vec![0, 1]),
+            &li(vec![], vec![text("item1.5")])
+        )
+        .into()]
+    );
+}
+
+#[test]
+fn changes_in_the_shifted_unkeyed_children_are_patched() {
+    let item = |label: &'static str, count: &'static str| {
+        li(vec![], vec![text(label), span(vec![], vec![text(count)])])
+    };
+    let old: Node<()> = ul(
+        vec![class("todo")],
+        vec![item("item1", "1"), item("item2", "2")],
+    );
+    let new: Node<()> = ul(
+        vec![class("todo")],
+        vec![item("item0", "0"), item("item1", "1"), item("item2", "3")],
+    );
+    assert_eq!(
+        diff(&old, &new),
+        vec![
+            InsertNode::new(
+                Some(&"ul"),
+                TreePath::new(vec![0, 0]),
+                &item("item0", "0")
+            )
+            .into(),
+            ChangeText::new(
+                &Text::new("2"),
+                TreePath::new(vec![0, 1, 1, 0]),
+                &Text::new("3")
+            )
+            .into(),
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+fn prepending_unkeyed_children_should_not_recreate_the_siblings() {
+    console_error_panic_hook::set_once();
+
+    let document = web_sys::window().unwrap().document().unwrap();
+
+    let old: Node<()> = main(
+        vec![class("test_unkeyed_prepend")],
+        vec![ul(
+            vec![class("todo")],
+            vec![
+                li(vec![], vec![text("item1")]),
+                li(vec![], vec![text("item2")]),
+            ],
+        )],
+    );
+
+    let update1: Node<()> = main(
+        vec![class("test_unkeyed_prepend")],
+        vec![ul(
+            vec![class("todo")],
+            vec![
+                li(vec![], vec![text("itemb")]),
+                li(vec![], vec![text("itema")]),
+                li(vec![], vec![text("item1")]),
+                li(vec![], vec![text("item2")]),
+            ],
+        )],
+    );
+
+    let patches = diff(&old, &update1);
+    assert_eq!(
+        patches,
+        vec![
+            InsertNode::new(
+                Some(&"ul"),
+                TreePath::new(vec![0, 0, 0]),
+                &li(vec![], vec![text("itemb")])
+            )
+            .into(),
+            InsertNode::new(
+                Some(&"ul"),
+                TreePath::new(vec![0, 0, 0]),
+                &li(vec![], vec![text("itema")])
+            )
+            .into(),
+        ]
+    );
+
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        old,
+        &sauron_core::body(),
+    )
+    .unwrap();
+
+    let first_item = document
+        .query_selector(".test_unkeyed_prepend li")
+        .expect("must not error")
+        .expect("must exist");
+
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
+
+    let container = document
+        .query_selector(".test_unkeyed_prepend")
+        .expect("must not error")
+        .expect("must exist");
+
+    let expected = "<main class=\"test_unkeyed_prepend\"><ul class=\"todo\"><li>itemb</li><li>itema</li><li>item1</li><li>item2</li></ul></main>";
+    assert_eq!(expected, container.outer_html());
+
+    // the existing item is kept in the DOM, and only moved by the inserted items
+    let item1 = document
+        .query_selector(".test_unkeyed_prepend li:nth-child(3)")
+        .expect("must not error")
+        .expect("must exist");
+    assert_eq!(first_item, item1);
+}
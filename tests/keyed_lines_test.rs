@@ -1,8 +1,9 @@
 #![deny(warnings)]
 use sauron::{
     html::{attributes::*, *},
-    mt_dom::patch::*,
-    node, *,
+    node,
+    vdom::patch::*,
+    *,
 };
 
 use test_fixtures::simple_program;
@@ -164,53 +165,56 @@ fn test_lines() {
         &simple_program,
         view0.clone(),
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
-    dom_updater.patch_dom(
-        &simple_program,
-        vec![
-            ChangeText::new(
-                &Text::new("0"),
-                TreePath::new(vec![0, 1, 0, 0, 0, 0, 0]),
-                &Text::new("1"),
-            )
-            .into(),
-            ChangeText::new(
-                &Text::new("1"),
-                TreePath::new(vec![0, 1, 0, 0, 1, 0, 0]),
-                &Text::new("2"),
-            )
-            .into(),
-            ChangeText::new(
-                &Text::new("2"),
-                TreePath::new(vec![0, 1, 0, 0, 2, 0, 0]),
-                &Text::new("3"),
-            )
-            .into(),
-            ChangeText::new(
-                &Text::new("3"),
-                TreePath::new(vec![0, 1, 0, 0, 3, 0, 0]),
-                &Text::new("4"),
-            )
-            .into(),
-            ChangeText::new(
-                &Text::new("4"),
-                TreePath::new(vec![0, 1, 0, 0, 4, 0, 0]),
-                &Text::new("5"),
-            )
-            .into(),
-            InsertNode::new(
-                Some(&"div"),
-                TreePath::new(vec![0, 1, 0, 0, 0]),
-                &inserted,
-            )
-            .into(),
-            ChangeText::new(
-                &Text::new("line: 0, column: 0"),
-                TreePath::new(vec![0, 1, 0, 1, 0]),
-                &Text::new("line: 1, column: 0"),
-            )
-            .into(),
-        ],
-    );
+    dom_updater
+        .patch_dom(
+            &simple_program,
+            vec![
+                ChangeText::new(
+                    &Text::new("0"),
+                    TreePath::new(vec![0, 1, 0, 0, 0, 0, 0]),
+                    &Text::new("1"),
+                )
+                .into(),
+                ChangeText::new(
+                    &Text::new("1"),
+                    TreePath::new(vec![0, 1, 0, 0, 1, 0, 0]),
+                    &Text::new("2"),
+                )
+                .into(),
+                ChangeText::new(
+                    &Text::new("2"),
+                    TreePath::new(vec![0, 1, 0, 0, 2, 0, 0]),
+                    &Text::new("3"),
+                )
+                .into(),
+                ChangeText::new(
+                    &Text::new("3"),
+                    TreePath::new(vec![0, 1, 0, 0, 3, 0, 0]),
+                    &Text::new("4"),
+                )
+                .into(),
+                ChangeText::new(
+                    &Text::new("4"),
+                    TreePath::new(vec![0, 1, 0, 0, 4, 0, 0]),
+                    &Text::new("5"),
+                )
+                .into(),
+                InsertNode::new(
+                    Some(&"div"),
+                    TreePath::new(vec![0, 1, 0, 0, 0]),
+                    &inserted,
+                )
+                .into(),
+                ChangeText::new(
+                    &Text::new("line: 0, column: 0"),
+                    TreePath::new(vec![0, 1, 0, 1, 0]),
+                    &Text::new("line: 1, column: 0"),
+                )
+                .into(),
+            ],
+        )
+        .expect("must patch the dom");
 }
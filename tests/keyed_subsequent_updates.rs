@@ -1,7 +1,7 @@
 #![deny(warnings)]
 use sauron_core::{
     html::{attributes::*, *},
-    mt_dom::patch::*,
+    vdom::patch::*,
     *,
 };
 
@@ -119,6 +119,18 @@ fn subsequent_updates() {
     assert_eq!(
         patches1,
         vec![
+            InsertNode::new(
+                Some(&"section"),
+                TreePath::new(vec![0, 0, 0]),
+                &div(
+                    vec![key("hashXXX")],
+                    vec![
+                        div(vec![], vec![text("0")]),
+                        div(vec![], vec![text("lineXXX")]),
+                    ],
+                )
+            )
+            .into(),
             ChangeText::new(
                 &Text::new("0"),
                 TreePath::new(vec![0, 0, 0, 0, 0,]),
@@ -142,18 +154,6 @@ fn subsequent_updates() {
                 TreePath::new(vec![0, 0, 3, 0, 0,]),
                 &Text::new("4")
             )
-            .into(),
-            InsertNode::new(
-                Some(&"section"),
-                TreePath::new(vec![0, 0, 0]),
-                &div(
-                    vec![key("hashXXX")],
-                    vec![
-                        div(vec![], vec![text("0")]),
-                        div(vec![], vec![text("lineXXX")]),
-                    ],
-                )
-            )
             .into()
         ]
     );
@@ -190,7 +190,8 @@ fn subsequent_updates() {
         &simple_program,
         old.clone(),
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let container = document
         .query_selector(".editor")
@@ -223,7 +224,9 @@ fn subsequent_updates() {
 
     assert_eq!(expected, container.outer_html());
 
-    dom_updater.update_dom(&simple_program, update1.clone());
+    dom_updater
+        .update_dom(&simple_program, update1.clone())
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".editor")
@@ -320,6 +323,18 @@ fn subsequent_updates() {
     assert_eq!(
         patches2,
         vec![
+            InsertNode::new(
+                Some(&"section"),
+                TreePath::new(vec![0, 0, 0,]),
+                &div(
+                    vec![key("hashYYY")],
+                    vec![
+                        div(vec![], vec![text("0")]),
+                        div(vec![], vec![text("lineYYY")]),
+                    ],
+                )
+            )
+            .into(),
             ChangeText::new(
                 &Text::new("0"),
                 TreePath::new(vec![0, 0, 0, 0, 0,]),
@@ -349,23 +364,13 @@ fn subsequent_updates() {
                 TreePath::new(vec![0, 0, 4, 0, 0,]),
                 &Text::new("5")
             )
-            .into(),
-            InsertNode::new(
-                Some(&"section"),
-                TreePath::new(vec![0, 0, 0,]),
-                &div(
-                    vec![key("hashYYY")],
-                    vec![
-                        div(vec![], vec![text("0")]),
-                        div(vec![], vec![text("lineYYY")]),
-                    ],
-                )
-            )
             .into()
         ]
     );
 
-    dom_updater.update_dom(&simple_program, update2.clone());
+    dom_updater
+        .update_dom(&simple_program, update2.clone())
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".editor")
@@ -471,6 +476,18 @@ fn subsequent_updates() {
     assert_eq!(
         patches3,
         vec![
+            InsertNode::new(
+                Some(&"section"),
+                TreePath::new(vec![0, 0, 0,]),
+                &div(
+                    vec![key("hashZZZ")],
+                    vec![
+                        div(vec![], vec![text("0")]),
+                        div(vec![], vec![text("\n")]),
+                    ],
+                )
+            )
+            .into(),
             ChangeText::new(
                 &Text::new("0"),
                 TreePath::new(vec![0, 0, 0, 0, 0,]),
@@ -506,23 +523,13 @@ fn subsequent_updates() {
                 TreePath::new(vec![0, 0, 5, 0, 0,]),
                 &Text::new("6")
             )
-            .into(),
-            InsertNode::new(
-                Some(&"section"),
-                TreePath::new(vec![0, 0, 0,]),
-                &div(
-                    vec![key("hashZZZ")],
-                    vec![
-                        div(vec![], vec![text("0")]),
-                        div(vec![], vec![text("\n")]),
-                    ],
-                )
-            )
             .into()
         ]
     );
 
-    dom_updater.update_dom(&simple_program, update3.clone());
+    dom_updater
+        .update_dom(&simple_program, update3.clone())
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".editor")
@@ -0,0 +1,102 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, *},
+    on_mount, on_unmount, web_sys, DomUpdater, Node,
+};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// an element which logs when it is mounted and unmounted
+fn tracked(log: &Log, name: &str, children: Vec<Node<()>>) -> Node<()> {
+    let mount_log = Rc::clone(log);
+    let unmount_log = Rc::clone(log);
+    let mount_name = name.to_string();
+    let unmount_name = name.to_string();
+    div(
+        vec![
+            id(name),
+            on_mount(move |me| {
+                let element: &web_sys::Element = me.target_node.unchecked_ref();
+                mount_log.borrow_mut().push(format!(
+                    "mount {} #{}",
+                    mount_name,
+                    element.id()
+                ));
+            }),
+            on_unmount(move |me| {
+                let element: &web_sys::Element = me.target_node.unchecked_ref();
+                // the element is still in the document
+                let is_attached = sauron::document()
+                    .get_element_by_id(&element.id())
+                    .is_some();
+                unmount_log.borrow_mut().push(format!(
+                    "unmount {} attached: {}",
+                    unmount_name, is_attached
+                ));
+            }),
+        ],
+        children,
+    )
+}
+
+#[wasm_bindgen_test]
+fn unmount_is_fired_before_the_elements_are_removed() {
+    let log: Log = Rc::new(RefCell::new(vec![]));
+    let old: Node<()> = main(
+        vec![],
+        vec![
+            tracked(&log, "lifecycle-chart", vec![]),
+            tracked(
+                &log,
+                "lifecycle-map",
+                vec![tracked(&log, "lifecycle-marker", vec![])],
+            ),
+        ],
+    );
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, old, &sauron::body())
+            .unwrap();
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "mount lifecycle-chart #lifecycle-chart",
+            "mount lifecycle-map #lifecycle-map",
+            "mount lifecycle-marker #lifecycle-marker",
+        ]
+    );
+    log.borrow_mut().clear();
+
+    // the map is removed along with its marker
+    let new: Node<()> =
+        main(vec![], vec![tracked(&log, "lifecycle-chart", vec![])]);
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "unmount lifecycle-map attached: true",
+            "unmount lifecycle-marker attached: true",
+        ]
+    );
+    log.borrow_mut().clear();
+
+    // the chart is replaced with a different element
+    let new: Node<()> = main(vec![], vec![span(vec![], vec![])]);
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+    assert_eq!(
+        *log.borrow(),
+        vec!["unmount lifecycle-chart attached: true"]
+    );
+}
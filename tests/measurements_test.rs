@@ -0,0 +1,65 @@
+#![deny(warnings)]
+#![cfg(feature = "with-measure")]
+use sauron::{html::*, Application, Cmd, Measurements, Node, Program};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+struct Counter {
+    count: i32,
+    measurements: Rc<RefCell<Vec<Measurements>>>,
+}
+
+impl Application<()> for Counter {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        self.count += 1;
+        Cmd::none().measure_with_name("increment")
+    }
+
+    fn view(&self) -> Node<()> {
+        div(vec![], vec![text(self.count)])
+    }
+
+    fn measurements(&self, measurements: Measurements) -> Cmd<Self, ()> {
+        self.measurements.borrow_mut().push(measurements);
+        Cmd::none().no_render()
+    }
+}
+
+#[wasm_bindgen_test]
+async fn measurements_has_the_patch_timings() {
+    let measurements = Rc::new(RefCell::new(vec![]));
+    let program = Program::mount_to_body(Counter {
+        count: 0,
+        measurements: Rc::clone(&measurements),
+    });
+    sauron::Dispatch::dispatch(&program, ());
+    sleep(100).await;
+
+    let measurements = measurements.borrow();
+    assert_eq!(measurements.len(), 1);
+    let patch_timing = measurements[0].patch_timings["ChangeText"];
+    assert_eq!(patch_timing.count, 1);
+
+    // the dispatch and its phases are in the timeline of the Performance panel
+    for name in [
+        "increment",
+        "increment update",
+        "increment view",
+        "increment patch",
+    ]
+    .iter()
+    {
+        assert_eq!(sauron::performance().get_entries_by_name(name).length(), 1);
+    }
+}
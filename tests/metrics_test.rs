@@ -0,0 +1,51 @@
+#![deny(warnings)]
+use sauron::{metrics::Metrics, Dispatch};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn dispatches_are_counted() {
+    let program = simple_program();
+    assert_eq!(program.metrics(), Metrics::default());
+
+    program.dispatch_multiple(vec![(), ()]);
+    program.dispatch(());
+    sleep(100).await;
+
+    let metrics = program.metrics();
+    assert_eq!(metrics.dispatch_count, 2);
+    assert_eq!(metrics.msg_count, 3);
+    assert_eq!(metrics.render_count, 2);
+    assert_eq!(metrics.patch_count, 0);
+}
+
+#[wasm_bindgen_test]
+async fn metrics_text_is_in_openmetrics_format() {
+    let program = simple_program();
+    program.dispatch(());
+    sleep(100).await;
+
+    let text = program.metrics_text();
+    assert!(text.contains(
+        "# TYPE sauron_dispatches counter\n\
+         # HELP sauron_dispatches Number of dispatches processed in the update loop.\n\
+         sauron_dispatches_total 1\n"
+    ));
+    assert!(text.contains("sauron_msgs_total 1\n"));
+    assert!(text.contains("sauron_dispatch_duration_milliseconds_count 1\n"));
+    assert!(text.contains("# TYPE sauron_wasm_memory_bytes gauge\n"));
+    assert!(text.ends_with("# EOF\n"));
+}
@@ -0,0 +1,68 @@
+#![deny(warnings)]
+use sauron::{
+    dom::MountOptions,
+    html::{attributes::*, *},
+    DomUpdater, Node,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn mount_node(inner_html: &str) -> web_sys::Element {
+    let mount = sauron::document().create_element("div").unwrap();
+    mount.set_inner_html(inner_html);
+    sauron::body().append_child(&mount).unwrap();
+    mount
+}
+
+fn view() -> Node<()> {
+    main(vec![class("app")], vec![text("loaded")])
+}
+
+#[wasm_bindgen_test]
+fn append_keeps_the_existing_children_by_default() {
+    let mount = mount_node(r#"<div class="spinner"></div>"#);
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(view(), &mount);
+    dom_updater
+        .append_to_mount_with_options(&simple_program, &MountOptions::default())
+        .expect("must mount");
+    assert_eq!(
+        mount.inner_html(),
+        r#"<div class="spinner"></div><main class="app">loaded</main>"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn clears_the_children_except_the_preserved() {
+    let mount = mount_node(
+        r#"<div class="spinner"></div>loading<noscript>enable js</noscript>"#,
+    );
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(view(), &mount);
+    dom_updater
+        .append_to_mount_with_options(
+            &simple_program,
+            &MountOptions::clear_children().preserve("noscript"),
+        )
+        .expect("must mount");
+    assert_eq!(
+        mount.inner_html(),
+        r#"<noscript>enable js</noscript><main class="app">loaded</main>"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn invalid_preserve_selector_is_a_mount_error() {
+    let mount = mount_node(r#"<div class="spinner"></div>"#);
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(view(), &mount);
+    let result = dom_updater.append_to_mount_with_options(
+        &simple_program,
+        &MountOptions::clear_children().preserve("[invalid"),
+    );
+    assert!(matches!(result, Err(sauron::dom::DomError::Mount(_))));
+}
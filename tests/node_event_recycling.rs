@@ -3,7 +3,7 @@ use sauron_core::{
     diff,
     dom::DomUpdater,
     html::{attributes::*, events::*, *},
-    mt_dom::patch::*,
+    vdom::patch::*,
     web_sys,
 };
 use std::{cell::RefCell, rc::Rc};
@@ -63,10 +63,13 @@ fn elements_with_different_event_should_not_be_recycle() {
     let body = sauron_core::body();
     let simple_program = simple_program();
     let mut dom_updater =
-        DomUpdater::new_append_to_mount(&simple_program, old.clone(), &body);
+        DomUpdater::new_append_to_mount(&simple_program, old.clone(), &body)
+            .unwrap();
 
     // update to new dom with no event attached
-    dom_updater.patch_dom(&simple_program, patches);
+    dom_updater
+        .patch_dom(&simple_program, patches)
+        .expect("must patch the dom");
 
     let input_element =
         sauron_core::document().get_element_by_id(&elem_id).unwrap();
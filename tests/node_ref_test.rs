@@ -0,0 +1,139 @@
+#![deny(warnings)]
+use sauron::{
+    dom::cmd::Cmd,
+    html::{attributes::*, *},
+    node_ref, web_sys, Dispatch, DomUpdater, Node, NodeRef, Rect,
+};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn clones_of_the_node_ref_are_equal() {
+    let r = NodeRef::new();
+    assert_eq!(r, r.clone());
+    assert_ne!(r, NodeRef::new());
+    assert!(r.get().is_none());
+}
+
+#[wasm_bindgen_test]
+fn node_ref_is_populated_when_the_element_is_created() {
+    let r = NodeRef::new();
+    let old: Node<()> = main(
+        vec![id("node-ref-container")],
+        vec![input(vec![id("node-ref-input"), node_ref(&r)], vec![])],
+    );
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, old, &sauron::body())
+            .unwrap();
+
+    let input = sauron::document()
+        .get_element_by_id("node-ref-input")
+        .unwrap();
+    assert_eq!(r.get(), Some(input));
+    assert!(r.cast::<web_sys::HtmlInputElement>().is_some());
+    assert!(r.cast::<web_sys::HtmlCanvasElement>().is_none());
+
+    // the node ref is moved into a new element
+    let new: Node<()> = main(
+        vec![id("node-ref-container")],
+        vec![
+            input(vec![id("node-ref-input")], vec![]),
+            textarea(vec![id("node-ref-textarea"), node_ref(&r)], vec![]),
+        ],
+    );
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+    let textarea = sauron::document()
+        .get_element_by_id("node-ref-textarea")
+        .unwrap();
+    assert_eq!(r.get(), Some(textarea));
+
+    // the node ref is cleared when it is removed from the element
+    let new: Node<()> = main(
+        vec![id("node-ref-container")],
+        vec![
+            input(vec![id("node-ref-input")], vec![]),
+            textarea(vec![id("node-ref-textarea")], vec![]),
+        ],
+    );
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+    assert!(r.get().is_none());
+}
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+/// records the measured rects instead of updating an app
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<(&'static str, Rect)>>>);
+
+impl Dispatch<(&'static str, Rect)> for Recorder {
+    fn dispatch(&self, msg: (&'static str, Rect)) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<(&'static str, Rect)>) {
+        self.0.borrow_mut().extend(msgs);
+    }
+}
+
+#[wasm_bindgen_test]
+async fn measure_element_by_node_ref_or_selector() {
+    let r = NodeRef::new();
+    let view: Node<()> = div(
+        vec![],
+        vec![
+            div(
+                vec![node_ref(&r), attr("style", "width: 120px; height: 30px")],
+                vec![],
+            ),
+            div(
+                vec![
+                    id("measured-by-selector"),
+                    attr("style", "width: 60px; height: 40px"),
+                ],
+                vec![],
+            ),
+        ],
+    );
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, view, &sauron::body())
+            .unwrap();
+
+    let recorder = Recorder::default();
+    Cmd::batch([
+        Cmd::measure_element(&r, |rect| ("node_ref", rect)),
+        Cmd::measure_element("#measured-by-selector", |rect| {
+            ("selector", rect)
+        }),
+        Cmd::measure_element("#not-in-the-document", |rect| ("missing", rect)),
+    ])
+    .emit(&recorder);
+    // the measurements are done in the next animation frame
+    assert!(recorder.0.borrow().is_empty());
+    sleep(50).await;
+
+    let measured = recorder.0.borrow();
+    assert_eq!(measured.len(), 2);
+    assert_eq!(measured[0].0, "node_ref");
+    assert_eq!((measured[0].1.width, measured[0].1.height), (120.0, 30.0));
+    assert_eq!(measured[1].0, "selector");
+    assert_eq!((measured[1].1.width, measured[1].1.height), (60.0, 40.0));
+    assert_eq!(measured[1].1.y, measured[0].1.bottom());
+}
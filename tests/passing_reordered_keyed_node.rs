@@ -50,10 +50,13 @@ fn reordered_keys2() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let update1_html = update1.render_to_string();
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".reordered2")
@@ -108,10 +111,13 @@ fn reordered_keys3() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let update1_html = update1.render_to_string();
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".reordered3")
@@ -1,7 +1,7 @@
 #![deny(warnings)]
 use sauron_core::{
     html::{attributes::*, *},
-    mt_dom::patch::*,
+    vdom::patch::*,
     Patch, *,
 };
 
@@ -38,7 +38,8 @@ fn node_mounted_properly() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let container = document
         .query_selector(".container1")
@@ -100,7 +101,8 @@ fn node_patched_properly() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let container = document
         .query_selector(".container2")
@@ -116,7 +118,9 @@ fn node_patched_properly() {
 
     assert_eq!(expected, container.outer_html());
 
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".container2")
@@ -182,7 +186,8 @@ fn node_patched_properly_remove_from_start() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let container = document
         .query_selector(".test3")
@@ -199,7 +204,9 @@ fn node_patched_properly_remove_from_start() {
 
     assert_eq!(expected, container.outer_html());
 
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".test3")
@@ -252,13 +259,13 @@ fn node_patched_properly_text_changed() {
     assert_eq!(
         patches,
         vec![
+            RemoveNode::new(Some(&"article"), TreePath::new(vec![0, 0, 0]))
+                .into(),
             Patch::ChangeText(ChangeText::new(
                 &Text::new("item3"),
                 TreePath::new(vec![0, 0, 2, 0]),
                 &Text::new("item3 with changes")
-            )),
-            RemoveNode::new(Some(&"article"), TreePath::new(vec![0, 0, 0]))
-                .into()
+            ))
         ]
     );
 
@@ -270,7 +277,8 @@ fn node_patched_properly_text_changed() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let container = document
         .query_selector(".test4")
@@ -287,7 +295,9 @@ fn node_patched_properly_text_changed() {
 
     assert_eq!(expected, container.outer_html());
 
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".test4")
@@ -346,17 +356,17 @@ fn mixed_keyed_and_non_keyed_elements() {
     assert_eq!(
         patches,
         vec![
-            Patch::ChangeText(ChangeText::new(
-                &Text::new("item3"),
-                TreePath::new(vec![0, 0, 2, 0,]),
-                &Text::new("item3 with changes")
-            )),
             RemoveNode::new(Some(&"article"), TreePath::new(vec![0, 0, 0]),)
                 .into(),
             Patch::ChangeText(ChangeText::new(
                 &Text::new("3 items left"),
                 TreePath::new(vec![0, 1, 0]),
                 &Text::new("2 items left")
+            )),
+            Patch::ChangeText(ChangeText::new(
+                &Text::new("item3"),
+                TreePath::new(vec![0, 0, 2, 0,]),
+                &Text::new("item3 with changes")
             ))
         ]
     );
@@ -369,7 +379,8 @@ fn mixed_keyed_and_non_keyed_elements() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let container = document
         .query_selector(".test5")
@@ -387,7 +398,9 @@ fn mixed_keyed_and_non_keyed_elements() {
 
     assert_eq!(expected, container.outer_html());
 
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".test5")
@@ -0,0 +1,27 @@
+#![deny(warnings)]
+use sauron_core::svg::PathData;
+
+#[test]
+fn empty_path() {
+    let path = PathData::new();
+    assert!(path.is_empty());
+    assert_eq!(path.to_string(), "");
+}
+
+#[test]
+fn curves_and_arcs() {
+    let path = PathData::new()
+        .move_to(0.0, 0.0)
+        .cubic_to((1.0, 2.0), (3.0, 4.0), (5.0, 6.0))
+        .smooth_cubic_to((7.0, 8.0), (9.0, 10.0))
+        .quadratic_to((1.5, 2.5), (3.0, 0.0))
+        .smooth_quadratic_to(6.0, 0.0)
+        .arc((5.0, 5.0), 45.0, true, false, (10.0, 10.0))
+        .line_by(-1.0, 1.0)
+        .vertical_to(0.0)
+        .close();
+    assert_eq!(
+        path.to_string(),
+        "M0 0 C1 2 3 4 5 6 S7 8 9 10 Q1.5 2.5 3 0 T6 0 A5 5 45 1 0 10 10 l-1 1 V0 Z"
+    );
+}
@@ -0,0 +1,67 @@
+#![deny(warnings)]
+use sauron::{
+    dom::cmd::Cmd,
+    html::{attributes::*, *},
+    Application, Dispatch, Node, Program,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// resolves after `ms` milliseconds
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+/// logs the msgs in the order they are updated
+struct MsgLog {
+    log: Rc<RefCell<Vec<u32>>>,
+}
+
+impl Application<u32> for MsgLog {
+    fn update(&mut self, msg: u32) -> Cmd<Program<Self, u32>> {
+        self.log.borrow_mut().push(msg);
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<u32> {
+        div(vec![class("msg-log")], vec![text(self.log.borrow().len())])
+    }
+}
+
+#[wasm_bindgen_test]
+async fn urgent_msgs_are_processed_before_the_normal_and_the_idle_msgs() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let program = Program::mount_to_body(MsgLog {
+        log: Rc::clone(&log),
+    });
+    program.dispatch_idle(1);
+    program.dispatch(2);
+    program.dispatch_idle(3);
+    program.dispatch_urgent(4);
+    assert_eq!(*log.borrow(), vec![4]);
+
+    sleep(300).await;
+    assert_eq!(*log.borrow(), vec![4, 2, 1, 3]);
+}
+
+#[wasm_bindgen_test]
+async fn idle_msgs_are_processed_in_one_update() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let program = Program::mount_to_body(MsgLog {
+        log: Rc::clone(&log),
+    });
+    let dispatch_count = program.metrics().dispatch_count;
+    program.dispatch_idle(1);
+    program.dispatch_idle(2);
+
+    sleep(300).await;
+    assert_eq!(*log.borrow(), vec![1, 2]);
+    assert_eq!(program.metrics().dispatch_count, dispatch_count + 1);
+}
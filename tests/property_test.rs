@@ -0,0 +1,90 @@
+#![deny(warnings)]
+
+use sauron_core::{
+    dom::DomUpdater,
+    html::{attributes::*, *},
+    web_sys::HtmlInputElement,
+    Node, Render,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn properties_are_rendered_as_attributes() {
+    let view: Node<()> = div(
+        vec![],
+        vec![
+            input(vec![r#type("text"), prop_value("hello")], vec![]),
+            input(vec![r#type("checkbox"), prop_checked(true)], vec![]),
+            input(vec![r#type("checkbox"), prop_checked(false)], vec![]),
+        ],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div><input type="text" value="hello"/><input type="checkbox" checked/><input type="checkbox"/></div>"#
+    );
+}
+
+fn get_input(element_id: &str) -> HtmlInputElement {
+    sauron_core::document()
+        .get_element_by_id(element_id)
+        .expect("must have the input")
+        .unchecked_into()
+}
+
+#[wasm_bindgen_test]
+fn value_property_overrides_the_user_input() {
+    let old: Node<()> =
+        input(vec![id("prop-value-1"), prop_value("first")], vec![]);
+    let new: Node<()> =
+        input(vec![id("prop-value-1"), prop_value("second")], vec![]);
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(old, &body);
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+
+    let element = get_input("prop-value-1");
+    assert_eq!(element.value(), "first");
+    // simulate the user typing into the input
+    element.set_value("typed by the user");
+
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+    assert_eq!(element.value(), "second");
+}
+
+#[wasm_bindgen_test]
+fn checked_property_is_set_and_removed() {
+    let old: Node<()> = input(
+        vec![id("prop-checked-1"), r#type("checkbox"), prop_checked(true)],
+        vec![],
+    );
+    let new: Node<()> =
+        input(vec![id("prop-checked-1"), r#type("checkbox")], vec![]);
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(old, &body);
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+
+    let element = get_input("prop-checked-1");
+    assert!(element.checked());
+    // the property is not reflected to the attribute
+    assert!(!element.has_attribute("checked"));
+
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+    assert!(!element.checked());
+}
@@ -0,0 +1,44 @@
+#![deny(warnings)]
+use sauron::{
+    qr_code::{qr_code, EcLevel, QrCode, QrCodeError},
+    Node, Render,
+};
+
+#[test]
+fn qr_code_node_has_the_quiet_zone() {
+    let node: Node<()> =
+        qr_code("https://example.com", EcLevel::Medium, vec![]).unwrap();
+    let size = QrCode::encode("https://example.com", EcLevel::Medium)
+        .unwrap()
+        .size();
+    let html = node.render_to_string();
+    let view_box = format!(r#"viewBox="0 0 {} {}""#, size + 8, size + 8);
+    assert!(html.starts_with("<svg"), "{}", html);
+    assert!(html.contains(&view_box), "{}", html);
+    assert!(html.contains(r#"aria-label="https://example.com""#));
+}
+
+#[test]
+fn higher_ec_level_needs_bigger_qr_code() {
+    let text = "the quick brown fox jumps over the lazy dog";
+    let low = QrCode::encode(text, EcLevel::Low).unwrap();
+    let high = QrCode::encode(text, EcLevel::High).unwrap();
+    assert!(high.size() > low.size());
+}
+
+#[test]
+fn same_content_renders_the_same_node() {
+    let first: Node<()> = qr_code("sauron", EcLevel::High, vec![]).unwrap();
+    let second: Node<()> = qr_code("sauron", EcLevel::High, vec![]).unwrap();
+    assert_eq!(first.render_to_string(), second.render_to_string());
+    let other: Node<()> = qr_code("sauron", EcLevel::Low, vec![]).unwrap();
+    assert_ne!(first.render_to_string(), other.render_to_string());
+}
+
+#[test]
+fn too_long_text_is_an_error() {
+    let text = "x".repeat(3000);
+    let result: Result<Node<()>, QrCodeError> =
+        qr_code(&text, EcLevel::Low, vec![]);
+    assert_eq!(result.err(), Some(QrCodeError::DataTooLong(3000)));
+}
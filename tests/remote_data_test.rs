@@ -0,0 +1,69 @@
+#![deny(warnings)]
+use sauron::{
+    html::*,
+    remote_data::{match_remote_data, RemoteData, RemoteDataViews},
+    Node, Render,
+};
+
+fn users_view(users: &RemoteData<Vec<&'static str>, String>) -> Node<()> {
+    match_remote_data(
+        users,
+        RemoteDataViews {
+            not_asked: || button(vec![], vec![text("Load users")]),
+            loading: || p(vec![], vec![text("Loading...")]),
+            failure: |e| p(vec![class("error")], vec![text(e)]),
+            success: |users| {
+                ul(
+                    vec![],
+                    users.iter().map(|user| li(vec![], vec![text(user)])),
+                )
+            },
+        },
+    )
+}
+
+#[test]
+fn each_state_has_its_own_view() {
+    assert_eq!(
+        users_view(&RemoteData::NotAsked).render_to_string(),
+        "<button>Load users</button>"
+    );
+    assert_eq!(
+        users_view(&RemoteData::Loading).render_to_string(),
+        "<p>Loading...</p>"
+    );
+    assert_eq!(
+        users_view(&RemoteData::Failure("http status: 500".to_string()))
+            .render_to_string(),
+        r#"<p class="error">http status: 500</p>"#
+    );
+    assert_eq!(
+        users_view(&RemoteData::Success(vec!["alice", "bob"]))
+            .render_to_string(),
+        "<ul><li>alice</li><li>bob</li></ul>"
+    );
+}
+
+#[test]
+fn remote_data_from_result() {
+    let ok: Result<i32, String> = Ok(1);
+    let err: Result<i32, String> = Err("failed".to_string());
+    assert_eq!(RemoteData::from(ok), RemoteData::Success(1));
+    assert_eq!(
+        RemoteData::from(err),
+        RemoteData::Failure("failed".to_string())
+    );
+    assert_eq!(RemoteData::<i32, String>::default(), RemoteData::NotAsked);
+}
+
+#[test]
+fn remote_data_map() {
+    let data: RemoteData<i32, String> = RemoteData::Success(2);
+    assert_eq!(data.clone().map(|n| n * 10).success(), Some(&20));
+    assert_eq!(data.as_ref().map_err(|e| e.len()), RemoteData::Success(&2));
+    let loading: RemoteData<i32, String> = RemoteData::Loading;
+    assert!(loading.is_loading());
+    assert_eq!(loading.map(|n| n * 10), RemoteData::Loading);
+    let failure: RemoteData<i32, String> = RemoteData::Failure("e".into());
+    assert_eq!(failure.map_err(|e| e.len()).failure(), Some(&1));
+}
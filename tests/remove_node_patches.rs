@@ -1,7 +1,7 @@
 #![deny(warnings)]
 use sauron_core::{
-    html::{attributes::*, *},
-    mt_dom::patch::*,
+    html::{attributes::*, events::*, *},
+    vdom::patch::*,
     *,
 };
 
@@ -59,7 +59,8 @@ fn test_remove_nodes() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let container = document
         .query_selector(".test5")
@@ -70,7 +71,9 @@ fn test_remove_nodes() {
 
     assert_eq!(expected, container.outer_html());
 
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".test5")
@@ -81,3 +84,42 @@ fn test_remove_nodes() {
 
     assert_eq!(expected1, container.outer_html());
 }
+
+fn rows(class_name: &'static str, count: usize) -> Node<()> {
+    ul(
+        vec![class(class_name)],
+        (0..count).map(|i| {
+            li(vec![on_click(|_| ())], vec![text(format!("row{}", i))])
+        }),
+    )
+}
+
+#[wasm_bindgen_test]
+fn tail_of_children_is_truncated() {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        rows("truncated-rows", 1000),
+        &sauron_core::body(),
+    )
+    .unwrap();
+
+    dom_updater
+        .update_dom(&simple_program, rows("truncated-rows", 10))
+        .expect("must patch the dom");
+    let container = document
+        .query_selector(".truncated-rows")
+        .expect("must not error")
+        .expect("must exist");
+    assert_eq!(container.child_element_count(), 10);
+    assert_eq!(
+        container.last_element_child().unwrap().text_content(),
+        Some("row9".to_string())
+    );
+
+    dom_updater
+        .update_dom(&simple_program, rows("truncated-rows", 0))
+        .expect("must patch the dom");
+    assert_eq!(container.child_element_count(), 0);
+}
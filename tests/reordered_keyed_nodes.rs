@@ -12,7 +12,7 @@ mod test_fixtures;
 wasm_bindgen_test_configure!(run_in_browser);
 
 #[wasm_bindgen_test]
-fn failing_reordered_keys() {
+fn reordered_keys() {
     console_log::init_with_level(log::Level::Trace).ok();
     console_error_panic_hook::set_once();
 
@@ -42,9 +42,6 @@ fn failing_reordered_keys() {
         )],
     );
 
-    // The patch was:
-    //  append key1, item1
-    //  remove nodeidx: 2, [0,0,0]
     let patches = diff(&old, &update1);
     log::debug!("patches: {:#?}", patches);
 
@@ -56,9 +53,17 @@ fn failing_reordered_keys() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
+
+    let item1 = document
+        .query_selector(".reordered li[key='1']")
+        .expect("must not error")
+        .expect("must exist");
 
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".reordered")
@@ -66,7 +71,12 @@ fn failing_reordered_keys() {
         .expect("must exist");
 
     let expected1 = "<main class=\"reordered\"><ul class=\"todo\"><li key=\"3\">item3</li><li key=\"2\">item2</li><li key=\"1\">item1</li></ul></main>";
-    //FIXME: it reflect the new update
-    //assert_eq!(expected1, container.outer_html());
-    assert_ne!(expected1, container.outer_html());
+    assert_eq!(expected1, container.outer_html());
+
+    // the moved child is the same DOM node, it is not re-created
+    let moved_item1 = document
+        .query_selector(".reordered li[key='1']")
+        .expect("must not error")
+        .expect("must exist");
+    assert!(item1.is_same_node(Some(&moved_item1)));
 }
@@ -1,7 +1,8 @@
 #![deny(warnings)]
 use sauron_core::{
     html::{attributes::*, *},
-    mt_dom::patch::*,
+    vdom::patch::RemoveChildrenFrom,
+    vdom::patch::*,
     *,
 };
 
@@ -48,22 +49,15 @@ fn test_multiple_replace() {
     assert_eq!(
         patches,
         vec![
-            ReplaceNode::new(
-                Some(&"li"),
-                TreePath::new(vec![0, 0, 0]),
-                &li(vec![key(10)], vec![text("item10")]),
-            )
-            .into(),
-            ReplaceNode::new(
-                Some(&"li"),
-                TreePath::new(vec![0, 0, 1]),
-                &li(vec![key(20)], vec![text("item20")]),
-            )
-            .into(),
-            ReplaceNode::new(
-                Some(&"li"),
-                TreePath::new(vec![0, 0, 2]),
-                &li(vec![key(30)], vec![text("item30")]),
+            RemoveChildrenFrom::new(&"ul", TreePath::new(vec![0, 0]), 0).into(),
+            AppendChildren::new(
+                &"ul",
+                TreePath::new(vec![0, 0]),
+                vec![
+                    &li(vec![key(10)], vec![text("item10")]),
+                    &li(vec![key(20)], vec![text("item20")]),
+                    &li(vec![key(30)], vec![text("item30")]),
+                ],
             )
             .into(),
         ]
@@ -77,7 +71,8 @@ fn test_multiple_replace() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let container = document
         .query_selector(".multi_replace")
@@ -88,7 +83,9 @@ fn test_multiple_replace() {
 
     assert_eq!(expected, container.outer_html());
 
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".multi_replace")
@@ -162,7 +159,8 @@ fn test_multiple_replace_and_parent_is_replaced_too() {
         &simple_program,
         old,
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
     let container = document
         .query_selector(".parent_replaced")
@@ -173,7 +171,9 @@ fn test_multiple_replace_and_parent_is_replaced_too() {
 
     assert_eq!(expected, container.outer_html());
 
-    dom_updater.update_dom(&simple_program, update1);
+    dom_updater
+        .update_dom(&simple_program, update1)
+        .expect("must patch the dom");
 
     let container = document
         .query_selector(".parent_replaced")
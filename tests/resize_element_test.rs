@@ -0,0 +1,77 @@
+#![deny(warnings)]
+use sauron::{
+    dom::DomUpdater,
+    html::{attributes::*, events::*, *},
+    web_sys, Dispatch, Node,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+/// records the dispatched msgs instead of updating an app
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<u32>>>);
+
+impl Dispatch<u32> for Recorder {
+    fn dispatch(&self, msg: u32) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<u32>) {
+        self.0.borrow_mut().extend(msgs);
+    }
+}
+
+impl Recorder {
+    fn take(&self) -> Vec<u32> {
+        self.0.borrow_mut().drain(..).collect()
+    }
+}
+
+fn set_width(width: &str) {
+    let chart: web_sys::HtmlElement = sauron::document()
+        .get_element_by_id("chart")
+        .expect("must have the chart")
+        .unchecked_into();
+    chart.style().set_property("width", width).unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn the_new_size_is_dispatched_until_the_listener_is_removed() {
+    let recorder = Recorder::default();
+    let view: Node<u32> = div(
+        vec![
+            id("chart"),
+            styles([("width", "100px"), ("height", "20px")]),
+            on_resize_element(|size| size.width as u32),
+        ],
+        vec![],
+    );
+    let mut dom_updater = DomUpdater::new(view, &sauron::body());
+    dom_updater.append_to_mount(&recorder).expect("must mount");
+    sleep(100).await;
+    assert_eq!(recorder.take(), vec![100]);
+
+    set_width("200px");
+    sleep(100).await;
+    assert_eq!(recorder.take(), vec![200]);
+
+    let view: Node<u32> = div(vec![id("chart")], vec![]);
+    dom_updater
+        .update_dom(&recorder, view)
+        .expect("must update");
+    set_width("300px");
+    sleep(100).await;
+    assert!(recorder.take().is_empty());
+}
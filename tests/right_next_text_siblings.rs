@@ -2,7 +2,7 @@ use sauron_core::{
     diff,
     html::{attributes::*, events::*, *},
     mt_dom::comment,
-    mt_dom::patch::*,
+    vdom::patch::*,
     Attribute, Node, *,
 };
 
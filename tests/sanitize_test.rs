@@ -0,0 +1,248 @@
+#![deny(warnings)]
+use regex::Regex;
+use sauron::sanitize::{clean, Policy};
+
+/// well known xss vectors, mostly from the OWASP filter evasion cheat sheet
+const BYPASS_CORPUS: &[&str] = &[
+    "<script>alert(1)</script>",
+    "<SCRIPT SRC=http://xss.rocks/xss.js></SCRIPT>",
+    "<IMG SRC=\"javascript:alert('XSS');\">",
+    "<IMG SRC=javascript:alert('XSS')>",
+    "<IMG SRC=JaVaScRiPt:alert('XSS')>",
+    "<IMG SRC=`javascript:alert(\"RSnake says, 'XSS'\")`>",
+    "<a onmouseover=\"alert(document.cookie)\">xxs link</a>",
+    "<IMG \"\"\"><SCRIPT>alert(\"XSS\")</SCRIPT>\"\\>",
+    "<img src=x onerror=alert(1)>",
+    "<IMG SRC=&#106;&#97;&#118;&#97;&#115;&#99;&#114;&#105;&#112;&#116;&#58;&#97;&#108;&#101;&#114;&#116;&#40;&#39;&#88;&#83;&#83;&#39;&#41;>",
+    "<IMG SRC=&#0000106&#0000097&#0000118&#0000097&#0000115&#0000099&#0000114&#0000105&#0000112&#0000116&#0000058&#0000097>",
+    "<IMG SRC=&#x6A&#x61&#x76&#x61&#x73&#x63&#x72&#x69&#x70&#x74&#x3A&#x61>",
+    "<IMG SRC=\"jav\tascript:alert('XSS');\">",
+    "<IMG SRC=\"jav&#x09;ascript:alert('XSS');\">",
+    "<IMG SRC=\"jav&#x0A;ascript:alert('XSS');\">",
+    "<IMG SRC=\" &#14;  javascript:alert('XSS');\">",
+    "<a href=\"javascript&colon;alert(1)\">x</a>",
+    "<a href=\"  JAVASCRIPT:alert(1)\">x</a>",
+    "<a href=\"vbscript:msgbox(1)\">x</a>",
+    "<a href=\"data:text/html;base64,PHNjcmlwdD5hbGVydCgxKTwvc2NyaXB0Pg==\">x</a>",
+    "<SCRIPT/XSS SRC=\"http://xss.rocks/xss.js\"></SCRIPT>",
+    "<BODY onload!#$%&()*~+-_.,:;?@[/|\\]^`=alert(\"XSS\")>",
+    "<<SCRIPT>alert(\"XSS\");//\\<</SCRIPT>",
+    "<SCRIPT SRC=http://xss.rocks/xss.js?< B >",
+    "<iframe src=http://xss.rocks/scriptlet.html <",
+    "<svg/onload=alert('XSS')>",
+    "<svg><script>alert(1)</script></svg>",
+    "<math><mtext><table><mglyph><style><img src=x onerror=alert(1)>",
+    "<noscript><p title=\"</noscript><img src=x onerror=alert(1)>\">",
+    "<style><img src=x onerror=alert(1)></style>",
+    "<TABLE BACKGROUND=\"javascript:alert('XSS')\">",
+    "<DIV STYLE=\"background-image: url(javascript:alert('XSS'))\">",
+    "<IMG STYLE=\"xss:expr/*XSS*/ession(alert('XSS'))\">",
+    "<META HTTP-EQUIV=\"refresh\" CONTENT=\"0;url=javascript:alert('XSS');\">",
+    "<OBJECT TYPE=\"text/x-scriptlet\" DATA=\"http://xss.rocks/scriptlet.html\"></OBJECT>",
+    "<EMBED SRC=\"data:image/svg+xml;base64,PHN2ZyB4bWxuczpzdmc9Imh0dH A6Ly93d3cudzMub3JnLzIwMDAvc3ZnIiB4bWxucz0iaHR0cDovL3d3dy53My5vcmcv\" AllowScriptAccess=\"always\"></EMBED>",
+    "<a href=\"#\" onclick=\"alert(1)\" onclick=\"x\">x</a>",
+    "<p title=\"&quot; onmouseover=&quot;alert(1)\">x</p>",
+    "<!--<img src=x onerror=alert(1)>-->",
+    "<!--[if gte IE 4]><SCRIPT>alert('XSS');</SCRIPT><![endif]-->",
+    "<form><button formaction=javascript:alert(1)>x</button></form>",
+    "<details open ontoggle=alert(1)>",
+    "<a href=\"ja&#x0000000076;ascript:alert(1)\">x</a>",
+    "<scr<script>ipt>alert(1)</script>",
+    "<textarea></textarea><img src=x onerror=alert(1)>",
+];
+
+/// these tags must never appear in the output
+const FORBIDDEN_TAGS: &[&str] = &[
+    "<script",
+    "<svg",
+    "<math",
+    "<iframe",
+    "<style",
+    "<object",
+    "<embed",
+    "<meta",
+    "<form",
+    "<button",
+    "<body",
+    "<noscript",
+    "<textarea",
+];
+
+/// urls of these schemes must never appear in the output
+const FORBIDDEN_SCHEMES: &[&str] = &["javascript:", "vbscript:", "data:"];
+
+fn assert_safe(input: &str, output: &str) {
+    let lowercased = output.to_lowercase();
+    for tag in FORBIDDEN_TAGS {
+        assert!(
+            !lowercased.contains(tag),
+            "{:?} was found: {:?} -> {:?}",
+            tag,
+            input,
+            output
+        );
+    }
+    let url_attribute = Regex::new(
+        r#"\s(?:href|src|cite|action|formaction|background)="([^"]*)""#,
+    )
+    .unwrap();
+    for captures in url_attribute.captures_iter(&lowercased) {
+        let url: String = captures[1]
+            .chars()
+            .filter(|c| !c.is_whitespace() && !c.is_control())
+            .collect();
+        for scheme in FORBIDDEN_SCHEMES {
+            assert!(
+                !url.starts_with(scheme),
+                "{:?} url was found: {:?} -> {:?}",
+                scheme,
+                input,
+                output
+            );
+        }
+    }
+    // attribute values are always quoted and escaped, so they are removed
+    // before looking for the attribute names
+    let attribute_value = Regex::new(r#"="[^"]*""#).unwrap();
+    let without_values = attribute_value.replace_all(&lowercased, "");
+    let dangerous_attribute =
+        Regex::new(r#"<[a-z][^>]*\s(?:on[a-z]+|style)\b"#).unwrap();
+    assert!(
+        !dangerous_attribute.is_match(&without_values),
+        "dangerous attribute was found: {:?} -> {:?}",
+        input,
+        output
+    );
+}
+
+#[test]
+fn bypass_corpus_is_sanitized() {
+    let policy = Policy::default();
+    for input in BYPASS_CORPUS {
+        let output = clean(input, &policy);
+        println!("{:?} -> {:?}", input, output);
+        assert_safe(input, &output);
+        assert_eq!(clean(&output, &policy), output, "must be idempotent");
+    }
+}
+
+/// a simple xorshift generator, so the fuzz test is deterministic
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 as usize
+    }
+}
+
+#[test]
+fn fuzz_combinations_of_corpus() {
+    let policy = Policy::default();
+    let fragments: Vec<&str> = BYPASS_CORPUS
+        .iter()
+        .copied()
+        .chain(vec![
+            "<",
+            ">",
+            "\"",
+            "'",
+            "&",
+            "&#",
+            "=",
+            "/",
+            "<a ",
+            "<p>",
+            "</p>",
+            "<b",
+            "href=",
+            "javascript:",
+            "-->",
+            "<!--",
+            "</",
+            "x",
+        ])
+        .collect();
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..2000 {
+        let mut input = String::new();
+        for _ in 0..(rng.next() % 6 + 1) {
+            let fragment = fragments[rng.next() % fragments.len()];
+            // also cut the fragments at random char boundary
+            let cut = fragment
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain(Some(fragment.len()))
+                .nth(rng.next() % (fragment.chars().count() + 1))
+                .unwrap_or(fragment.len());
+            input.push_str(&fragment[..cut]);
+        }
+        let output = clean(&input, &policy);
+        assert_safe(&input, &output);
+        assert_eq!(clean(&output, &policy), output, "must be idempotent");
+    }
+}
+
+#[test]
+fn allowed_tags_are_retained() {
+    let html = r#"<p class="x">Hello <b>world</b> <a href="https://example.com" target="_blank">link</a></p>"#;
+    assert_eq!(
+        clean(html, &Policy::default()),
+        r#"<p>Hello <b>world</b> <a href="https://example.com" rel="noopener noreferrer">link</a></p>"#
+    );
+}
+
+#[test]
+fn text_of_disallowed_tags_are_retained() {
+    assert_eq!(
+        clean("<custom-tag>text</custom-tag>", &Policy::default()),
+        "text"
+    );
+    assert_eq!(clean("a<script>x</script>b", &Policy::default()), "ab");
+}
+
+#[test]
+fn unclosed_tags_are_closed() {
+    assert_eq!(
+        clean("<div><p>text</div></span>", &Policy::default()),
+        "<div><p>text</p></div>"
+    );
+    assert_eq!(
+        clean("<ul><li>one", &Policy::default()),
+        "<ul><li>one</li></ul>"
+    );
+}
+
+#[test]
+fn text_is_escaped() {
+    assert_eq!(
+        clean("1 < 2 && 3 > 2 &amp; &#169;", &Policy::default()),
+        "1 &lt; 2 &amp;&amp; 3 &gt; 2 &amp; &#169;"
+    );
+}
+
+#[test]
+fn custom_policy() {
+    let policy = Policy::empty()
+        .add_tags(["span", "a"])
+        .add_tag_attributes("span", ["class"])
+        .add_tag_attributes("a", ["href"])
+        .add_url_schemes(["https"])
+        .allow_relative_urls(false);
+    assert_eq!(
+        clean(
+            r#"<span class="a" id="b"><a href="/relative">x</a><a href="https://x.y">y</a><b>z</b></span>"#,
+            &policy
+        ),
+        r#"<span class="a"><a>x</a><a href="https://x.y">y</a>z</span>"#
+    );
+}
+
+#[test]
+fn comments_can_be_retained() {
+    let policy = Policy::default().strip_comments(false);
+    assert_eq!(clean("a<!-- note -->b", &policy), "a<!-- note -->b");
+    assert_eq!(clean("a<!-- note -->b", &Policy::default()), "ab");
+}
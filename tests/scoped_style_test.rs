@@ -0,0 +1,86 @@
+#![deny(warnings)]
+use sauron::{
+    html::scoped_style::{scope_class, scope_style},
+    prelude::*,
+};
+use std::any::TypeId;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn prefix_the_selectors() {
+    assert_eq!(
+        scope_style("s", ".btn { color: red; } ul > li:first-child {}"),
+        ".s .btn { color: red; }\n.s ul > li:first-child {}"
+    );
+    assert_eq!(
+        scope_style("s", ":host, :host(.dark) p, :is(h1, h2) { margin: 0; }"),
+        ".s, .s.dark p, .s :is(h1, h2) { margin: 0; }"
+    );
+}
+
+#[test]
+fn scope_the_rules_inside_media() {
+    let css = r#"
+        /* the buttons */
+        @media (max-width: 600px) {
+            .btn { display: none; }
+        }
+        @keyframes spin {
+            from { transform: rotate(0deg); }
+            to { transform: rotate(360deg); }
+        }
+    "#;
+    assert_eq!(
+        scope_style("s", css),
+        "@media (max-width: 600px) {\n.s .btn { display: none; }\n}\n\
+         @keyframes spin {\n            from { transform: rotate(0deg); }\n            to { transform: rotate(360deg); }\n        }"
+    );
+}
+
+#[test]
+fn scope_class_is_derived_from_the_type() {
+    assert_eq!(
+        scope_class(TypeId::of::<u8>()),
+        scope_class(TypeId::of::<u8>())
+    );
+    assert_ne!(
+        scope_class(TypeId::of::<u8>()),
+        scope_class(TypeId::of::<u16>())
+    );
+    assert!(scope_class(TypeId::of::<u8>()).starts_with("sauron-"));
+}
+
+struct ScopedApp;
+
+impl Application<()> for ScopedApp {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div(
+            vec![class("scoped-app")],
+            vec![button(vec![], vec![text("ok")])],
+        )
+    }
+
+    fn style_scoped(&self) -> String {
+        "button { color: red; }".to_string()
+    }
+}
+
+#[wasm_bindgen_test]
+fn root_element_has_the_scope_class() {
+    let _program = Program::mount_to_body(ScopedApp);
+    let scope = scope_class(TypeId::of::<ScopedApp>());
+    let root = sauron::document()
+        .query_selector(".scoped-app")
+        .unwrap()
+        .expect("must be mounted");
+    assert!(root.class_list().contains(&scope));
+
+    let styles = sauron::document().head().unwrap().inner_html();
+    assert!(styles.contains(&format!(".{} button {{ color: red; }}", scope)));
+}
@@ -0,0 +1,46 @@
+#![deny(warnings)]
+
+use sauron_core::{
+    dom::DomUpdater,
+    html::{attributes::*, *},
+    web_sys::HtmlInputElement,
+    Node,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn caret_is_retained_when_patching_the_focused_input() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let old: Node<()> =
+        input(vec![id("selection-1"), prop_value("hello world")], vec![]);
+    let new: Node<()> =
+        input(vec![id("selection-1"), prop_value("hello, world")], vec![]);
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(old, &body);
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+
+    let input: HtmlInputElement = sauron_core::document()
+        .get_element_by_id("selection-1")
+        .expect("must have the input")
+        .unchecked_into();
+    input.focus().unwrap();
+    input.set_selection_range(5, 5).unwrap();
+
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+
+    assert_eq!(input.value(), "hello, world");
+    assert_eq!(input.selection_start().unwrap(), Some(5));
+    assert_eq!(input.selection_end().unwrap(), Some(5));
+}
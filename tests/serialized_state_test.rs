@@ -0,0 +1,75 @@
+#![deny(warnings)]
+#![cfg(feature = "with-persist")]
+use sauron::prelude::*;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct App {
+    count: i32,
+    name: String,
+}
+
+impl Application<()> for App {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div(vec![class("counter")], vec![text(self.count)])
+    }
+
+    fn persist(&self) -> Option<String> {
+        serde_json::to_string(self).ok()
+    }
+}
+
+#[test]
+fn persisted_state_is_embedded_in_a_script() {
+    let app = App {
+        count: 3,
+        name: "</script><script>alert(1)</script>".to_string(),
+    };
+    let json = app.persist().unwrap();
+    let script: Node<()> = serialized_state(&json);
+    let html = script.render_to_string();
+    assert_eq!(html.matches("</script>").count(), 1, "{}", html);
+    let embedded = html
+        .trim_start_matches(
+            r#"<script type="application/json" id="sauron-serialized-state">"#,
+        )
+        .trim_end_matches("</script>");
+    let restored: App = serde_json::from_str(embedded).unwrap();
+    assert_eq!(restored, app);
+}
+
+#[wasm_bindgen_test]
+fn app_is_mounted_with_the_embedded_state() {
+    let state: Node<()> = serialized_state(r#"{"count":42,"name":"restored"}"#);
+    let head = sauron::document().head().expect("must have a head");
+    head.insert_adjacent_html("beforeend", &state.render_to_string())
+        .expect("must insert the state");
+
+    let json = sauron::embedded_state().expect("must have the embedded state");
+    let program = Program::mount_with_serialized_state(
+        |state: Option<App>| state.unwrap_or_default(),
+        &json,
+    );
+    assert_eq!(program.app.borrow().count, 42);
+    let counter = sauron::document()
+        .query_selector(".counter")
+        .unwrap()
+        .expect("must have the counter");
+    assert_eq!(counter.text_content(), Some("42".to_string()));
+}
+
+#[wasm_bindgen_test]
+fn invalid_state_falls_back_to_the_default() {
+    let program = Program::mount_with_serialized_state(
+        |state: Option<App>| state.unwrap_or_default(),
+        "not json",
+    );
+    assert_eq!(*program.app.borrow(), App::default());
+}
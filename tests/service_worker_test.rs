@@ -0,0 +1,59 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{
+        cmd::Cmd,
+        service_worker::{update_prompt, ServiceWorkerEvent},
+    },
+    Dispatch, Node, Render,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+/// records the dispatched msgs instead of updating an app
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<ServiceWorkerEvent>>>);
+
+impl Dispatch<ServiceWorkerEvent> for Recorder {
+    fn dispatch(&self, msg: ServiceWorkerEvent) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<ServiceWorkerEvent>) {
+        self.0.borrow_mut().extend(msgs);
+    }
+}
+
+#[wasm_bindgen_test]
+async fn missing_script_fails_the_registration() {
+    let recorder = Recorder::default();
+    Cmd::register_service_worker("/no-such-service-worker.js", |event| event)
+        .emit(&recorder);
+    sleep(500).await;
+    let events = recorder.0.borrow();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(
+        events[0],
+        ServiceWorkerEvent::Failed(_) | ServiceWorkerEvent::Unsupported
+    ));
+}
+
+#[wasm_bindgen_test]
+fn update_prompt_is_an_alert_with_a_reload_button() {
+    let prompt: Node<()> =
+        update_prompt("A new version is available", "Reload", ());
+    assert_eq!(
+        prompt.render_to_string(),
+        r#"<div class="sauron-update-prompt" role="alert"><span>A new version is available</span><button>Reload</button></div>"#
+    );
+}
@@ -0,0 +1,42 @@
+#![deny(warnings)]
+use sauron::prelude::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Widget;
+
+impl Application<()> for Widget {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div(vec![class("widget")], vec![text("embedded")])
+    }
+
+    fn style(&self) -> String {
+        ".widget { color: rebeccapurple; }".to_string()
+    }
+}
+
+#[wasm_bindgen_test]
+fn view_is_mounted_inside_the_shadow_root() {
+    let document = sauron::document();
+    let host = document.create_element("div").unwrap();
+    sauron::body().append_child(&host).unwrap();
+
+    let _program = Program::mount_to_shadow(Widget, &host);
+
+    let shadow_root = host.shadow_root().expect("must have a shadow root");
+    let widget = shadow_root
+        .query_selector(".widget")
+        .unwrap()
+        .expect("must be mounted in the shadow root");
+    assert_eq!(widget.text_content(), Some("embedded".to_string()));
+    // the view is isolated from the page
+    assert!(document.query_selector(".widget").unwrap().is_none());
+    // the style is not leaked into the head of the page
+    let head = document.head().unwrap().inner_html();
+    assert!(!head.contains("rebeccapurple"));
+}
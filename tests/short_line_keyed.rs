@@ -1,5 +1,5 @@
 #![deny(warnings)]
-use sauron::{mt_dom::patch::*, *};
+use sauron::{vdom::patch::*, *};
 use sauron_core::Node;
 
 #[test]
@@ -79,9 +79,10 @@ fn test_unmatched_old_key() {
     assert_eq!(
         patches,
         vec![
-            ReplaceNode::new(
+            RemoveNode::new(Some(&"div"), TreePath::new(vec![0, 0])).into(),
+            InsertNode::new(
                 Some(&"div"),
-                TreePath::new(vec![0, 0]),
+                TreePath::new(vec![0, 1]),
                 &node!(
                 <div class="grid__number__line" key="keyxxx">
                     <div class="grid__number">"xxx"</div>
@@ -0,0 +1,81 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        skeleton::{
+            skeleton_avatar, skeleton_card, skeleton_lines, with_loading,
+            TaskState, STYLE_ID,
+        },
+        *,
+    },
+    Application, Cmd, Node, Program, Render,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn names_view(names: &TaskState<Vec<&'static str>>) -> Node<()> {
+    with_loading(names, skeleton_lines(2), |names| {
+        ul(
+            vec![],
+            names.iter().map(|name| li(vec![], vec![text(name)])),
+        )
+    })
+}
+
+#[test]
+fn skeleton_is_shown_while_pending() {
+    let view = names_view(&TaskState::Pending);
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div class="sauron-skeleton-lines" aria-busy="true" aria-label="Loading"><div class="sauron-skeleton sauron-skeleton-line" aria-hidden="true"></div><div class="sauron-skeleton sauron-skeleton-line" aria-hidden="true"></div></div>"#
+    );
+}
+
+#[test]
+fn loaded_view_is_shown_when_done() {
+    let view = names_view(&TaskState::Done(vec!["alice", "bob"]));
+    assert_eq!(
+        view.render_to_string(),
+        "<ul><li>alice</li><li>bob</li></ul>"
+    );
+    assert_eq!(TaskState::from(Some(1)), TaskState::Done(1));
+    assert!(TaskState::<i32>::from(None).is_pending());
+}
+
+#[test]
+fn avatar_and_card_skeletons() {
+    let avatar: Node<()> = skeleton_avatar(32);
+    assert_eq!(
+        avatar.render_to_string(),
+        r#"<div class="sauron-skeleton sauron-skeleton-avatar" style="width:32px;height:32px;" aria-hidden="true"></div>"#
+    );
+    let card: Node<()> = skeleton_card();
+    assert_eq!(
+        card.render_to_string()
+            .matches(r#"sauron-skeleton-line""#)
+            .count(),
+        4
+    );
+}
+
+struct Loading;
+
+impl Application<()> for Loading {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        skeleton_lines(2)
+    }
+}
+
+#[wasm_bindgen_test]
+fn style_is_injected_once() {
+    let _first = Program::mount_to_body(Loading);
+    let _second = Program::mount_to_body(Loading);
+    let styles = sauron::document()
+        .query_selector_all(&format!("#{}", STYLE_ID))
+        .unwrap();
+    assert_eq!(styles.length(), 1);
+}
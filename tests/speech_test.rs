@@ -0,0 +1,22 @@
+#![deny(warnings)]
+use sauron::dom::speech::{self, VoiceOptions};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn default_voice_options() {
+    let options = VoiceOptions::default();
+    assert_eq!(options.lang, None);
+    assert_eq!(options.rate, 1.0);
+    assert_eq!(options.pitch, 1.0);
+    assert_eq!(options.volume, 1.0);
+}
+
+#[wasm_bindgen_test]
+fn synthesis_availability_is_detected() {
+    assert_eq!(
+        speech::is_synthesis_available(),
+        sauron::window().speech_synthesis().is_ok()
+    );
+}
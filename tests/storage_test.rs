@@ -0,0 +1,39 @@
+#![deny(warnings)]
+#![cfg(feature = "with-persist")]
+use sauron::dom::storage::{local, session};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    theme: String,
+    font_size: u8,
+}
+
+#[wasm_bindgen_test]
+fn typed_values_are_stored_and_removed() {
+    let settings = Settings {
+        theme: "dark".to_string(),
+        font_size: 14,
+    };
+    local::set("storage-test-settings", &settings).unwrap();
+    assert_eq!(
+        local::get::<Settings>("storage-test-settings").unwrap(),
+        Some(settings)
+    );
+    local::remove("storage-test-settings").unwrap();
+    assert_eq!(
+        local::get::<Settings>("storage-test-settings").unwrap(),
+        None
+    );
+}
+
+#[wasm_bindgen_test]
+fn mismatched_type_is_an_error() {
+    session::set("storage-test-count", &42).unwrap();
+    assert!(session::get::<Settings>("storage-test-count").is_err());
+    assert_eq!(session::get::<i32>("storage-test-count").unwrap(), Some(42));
+    session::remove("storage-test-count").unwrap();
+}
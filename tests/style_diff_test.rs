@@ -0,0 +1,79 @@
+#![deny(warnings)]
+
+use sauron_core::{
+    dom::DomUpdater,
+    html::{attributes::*, *},
+    web_sys, Node,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn diff_changed_style_property() {
+    let old = [
+        Style::new("display", "block"),
+        Style::new("position", "absolute"),
+    ];
+    let new = [
+        Style::new("display", "none"),
+        Style::new("position", "absolute"),
+    ];
+    assert_eq!(
+        UpdateStyles::diff(&old, &new),
+        UpdateStyles {
+            set: vec![Style::new("display", "none")],
+            remove: vec![],
+        }
+    );
+}
+
+#[test]
+fn diff_last_declared_style_wins() {
+    let old = [Style::new("color", "red"), Style::new("color", "blue")];
+    let new = [Style::new("color", "blue")];
+    assert!(UpdateStyles::diff(&old, &new).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn external_style_property_is_retained() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let old: Node<()> = div(
+        vec![
+            id("style-diff-1"),
+            styles([("display", "block"), ("color", "red")]),
+        ],
+        vec![],
+    );
+    let new: Node<()> = div(
+        vec![id("style-diff-1"), styles([("display", "flex")])],
+        vec![],
+    );
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(old, &body);
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+
+    let element: web_sys::HtmlElement = sauron_core::document()
+        .get_element_by_id("style-diff-1")
+        .unwrap()
+        .unchecked_into();
+    // simulate an animation library setting its own style property
+    element.style().set_property("opacity", "0.5").unwrap();
+
+    dom_updater
+        .update_dom(&simple_program, new)
+        .expect("must patch the dom");
+
+    let style = element.style();
+    assert_eq!(style.get_property_value("opacity").unwrap(), "0.5");
+    assert_eq!(style.get_property_value("display").unwrap(), "flex");
+    assert_eq!(style.get_property_value("color").unwrap(), "");
+}
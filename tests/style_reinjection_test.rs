@@ -0,0 +1,55 @@
+#![deny(warnings)]
+use sauron::prelude::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+struct ThemedApp {
+    dark: bool,
+}
+
+impl Application<bool> for ThemedApp {
+    fn update(&mut self, dark: bool) -> Cmd<Self, bool> {
+        self.dark = dark;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<bool> {
+        div(vec![class("themed-app")], vec![])
+    }
+
+    fn style(&self) -> String {
+        if self.dark {
+            ".themed-app { background: black; }".to_string()
+        } else {
+            ".themed-app { background: white; }".to_string()
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+async fn style_is_replaced_when_it_changed() {
+    let program = Program::mount_to_body(ThemedApp { dark: false });
+    let head = sauron::document().head().unwrap();
+    let style_count = head.query_selector_all("style").unwrap().length();
+    assert!(head.inner_html().contains("background: white"));
+
+    program.dispatch(true);
+    sleep(50).await;
+    assert!(head.inner_html().contains("background: black"));
+    assert!(!head.inner_html().contains("background: white"));
+    // the content of the same style element is replaced
+    assert_eq!(
+        head.query_selector_all("style").unwrap().length(),
+        style_count
+    );
+}
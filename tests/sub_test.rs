@@ -0,0 +1,122 @@
+#![deny(warnings)]
+use sauron::prelude::*;
+use sauron::{dom::sub, Sub};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::{simple_program, SimpleComponent};
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn batch_subs() {
+    let subs: Sub<SimpleComponent, ()> = Sub::batch([
+        Sub::on_scroll_progress("#section1", |_| ()),
+        Sub::none(),
+        Sub::on_scroll_progress("#section2", |_| ()),
+    ]);
+    assert_eq!(subs.subscribers.len(), 2);
+}
+
+#[test]
+fn pause_when_hidden_keeps_the_subscribers() {
+    let subs: Sub<SimpleComponent, ()> = Sub::batch([
+        Sub::every(1000, |_| ()),
+        Sub::on_animation_frame(|_| ()).pause_when_hidden(),
+        Sub::on_scroll_progress("#section1", |_| ()),
+    ])
+    .keep_running_when_hidden();
+    assert_eq!(subs.subscribers.len(), 3);
+}
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+/// records the dispatched ticks instead of updating an app
+#[derive(Clone, Default)]
+struct Ticks(Rc<RefCell<Vec<u32>>>);
+
+impl Dispatch<u32> for Ticks {
+    fn dispatch(&self, tick: u32) {
+        self.0.borrow_mut().push(tick);
+    }
+
+    fn dispatch_multiple(&self, ticks: Vec<u32>) {
+        self.0.borrow_mut().extend(ticks);
+    }
+}
+
+#[wasm_bindgen_test]
+async fn every_dispatch_the_ticks_until_unsubscribed() {
+    let ticks = Ticks::default();
+    let sub: sub::Sub<Ticks> = sub::Sub::every(10, |tick| tick);
+    let subscriptions = sub.subscribe(&ticks);
+    sleep(55).await;
+    for subscription in subscriptions {
+        subscription.unsubscribe();
+    }
+    let dispatched = ticks.0.borrow().clone();
+    assert!(dispatched.len() >= 2);
+    assert_eq!(dispatched[0..2], [1, 2]);
+
+    sleep(30).await;
+    assert_eq!(ticks.0.borrow().len(), dispatched.len());
+}
+
+#[wasm_bindgen_test]
+async fn sampled_dispatch_the_latest_msg_of_each_period() {
+    let ticks = Ticks::default();
+    let sub: sub::Sub<Ticks> =
+        sub::Sub::sampled(sub::Sub::every(5, |tick| tick), 40);
+    let subscriptions = sub.subscribe(&ticks);
+    sleep(100).await;
+    for subscription in subscriptions {
+        subscription.unsubscribe();
+    }
+    let dispatched = ticks.0.borrow().clone();
+    assert!(!dispatched.is_empty() && dispatched.len() <= 3);
+    // only the latest tick of each period is dispatched
+    assert!(dispatched[0] > 1);
+    assert!(dispatched.windows(2).all(|w| w[0] < w[1]));
+
+    sleep(60).await;
+    assert_eq!(ticks.0.borrow().len(), dispatched.len());
+}
+
+#[wasm_bindgen_test]
+async fn scroll_progress_of_missing_element_is_a_noop() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let ticks = Ticks::default();
+    let sub: sub::Sub<Ticks> =
+        sub::Sub::on_scroll_progress("#not-in-the-page", |_| 1);
+    let subscriptions = sub.subscribe(&ticks);
+    sleep(50).await;
+    assert!(ticks.0.borrow().is_empty());
+    for subscription in subscriptions {
+        subscription.unsubscribe();
+    }
+}
+
+#[wasm_bindgen_test]
+fn unsubscribe_scroll_progress() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let program = simple_program();
+    let section = sauron::document().create_element("div").unwrap();
+    section.set_id("scroll-section");
+    sauron::body().append_child(&section).unwrap();
+
+    let sub: Sub<SimpleComponent, ()> =
+        Sub::on_scroll_progress("#scroll-section", |_| ());
+    let subscriptions = sub.subscribe(&program);
+    assert_eq!(subscriptions.len(), 1);
+    for subscription in subscriptions {
+        subscription.unsubscribe();
+    }
+}
@@ -0,0 +1,109 @@
+#![deny(warnings)]
+use sauron_core::{
+    dom::CreatedNode,
+    html::{attributes::*, events::*, *},
+    Node,
+};
+use std::{cell::Cell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+use web_sys::{Element, HtmlElement};
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn row<MSG>(index: usize) -> Node<MSG> {
+    li(
+        vec![class("template-row"), id(format!("template-row-{}", index))],
+        vec![
+            span(vec![class("label")], vec![text(format!("item {}", index))]),
+            div(
+                vec![class("actions"), styles([("display", "flex")])],
+                vec![
+                    button(vec![class("edit")], vec![text("edit")]),
+                    button(vec![class("remove")], vec![text("remove")]),
+                ],
+            ),
+            span(vec![class("count")], vec![text(index * 10)]),
+        ],
+    )
+}
+
+fn expected_row(index: usize) -> String {
+    format!(
+        r#"<li class="template-row" id="template-row-{index}"><span class="label">item {index}</span><div class="actions" style="display:flex;"><button class="edit">edit</button><button class="remove">remove</button></div><span class="count">{count}</span></li>"#,
+        index = index,
+        count = index * 10
+    )
+}
+
+#[wasm_bindgen_test]
+fn imported_rows_are_the_same_as_created_rows() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let list: Node<()> = ul(vec![], (0..5).map(row));
+    let created: Element =
+        CreatedNode::create_dom_node(&simple_program(), &list, &mut None)
+            .unwrap()
+            .node
+            .unchecked_into();
+    let expected: String = (0..5).map(expected_row).collect();
+    assert_eq!(created.inner_html(), expected);
+
+    // the skeleton is imported for all the rows of the same list created again
+    let created: Element =
+        CreatedNode::create_dom_node(&simple_program(), &list, &mut None)
+            .unwrap()
+            .node
+            .unchecked_into();
+    assert_eq!(created.inner_html(), expected);
+}
+
+#[wasm_bindgen_test]
+fn imported_rows_have_their_listeners() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let clicks = Rc::new(Cell::new(0));
+    let row_with_listener = |index: usize| -> Node<()> {
+        let clicks = Rc::clone(&clicks);
+        article(
+            vec![class("template-card")],
+            vec![
+                h2(vec![], vec![text(format!("card {}", index))]),
+                p(vec![class("body")], vec![text("lorem ipsum")]),
+                footer(
+                    vec![],
+                    vec![
+                        span(vec![], vec![text(index)]),
+                        button(
+                            vec![
+                                id(format!("template-card-{}", index)),
+                                on_click(move |_| {
+                                    clicks.set(clicks.get() + index)
+                                }),
+                            ],
+                            vec![text("like")],
+                        ),
+                    ],
+                ),
+            ],
+        )
+    };
+    let cards: Node<()> = div(vec![], (1..=4).map(row_with_listener));
+    let created =
+        CreatedNode::create_dom_node(&simple_program(), &cards, &mut None)
+            .unwrap();
+    let container: Element = created.node.clone().unchecked_into();
+    sauron_core::body()
+        .append_child(&container)
+        .expect("must append");
+
+    for index in 1..=4 {
+        let button: HtmlElement = sauron_core::document()
+            .get_element_by_id(&format!("template-card-{}", index))
+            .expect("must have the button")
+            .unchecked_into();
+        button.click();
+    }
+    assert_eq!(clicks.get(), 1 + 2 + 3 + 4);
+}
@@ -0,0 +1,81 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{cmd::Cmd, sub::Sub, test_util::TestClock},
+    Dispatch,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// records the dispatched msgs instead of updating an app
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<u32>>>);
+
+impl Dispatch<u32> for Recorder {
+    fn dispatch(&self, msg: u32) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<u32>) {
+        self.0.borrow_mut().extend(msgs);
+    }
+}
+
+impl Recorder {
+    fn take(&self) -> Vec<u32> {
+        self.0.borrow_mut().drain(..).collect()
+    }
+}
+
+#[wasm_bindgen_test]
+fn delayed_msgs_are_dispatched_when_the_clock_is_advanced() {
+    let clock = TestClock::install();
+    let recorder = Recorder::default();
+    Cmd::batch([Cmd::delay(300, 3), Cmd::delay(100, 1), Cmd::delay(200, 2)])
+        .emit(&recorder);
+    assert_eq!(clock.pending(), 3);
+
+    clock.advance(99);
+    assert!(recorder.take().is_empty());
+    clock.advance(150);
+    assert_eq!(recorder.take(), vec![1, 2]);
+    assert_eq!(clock.now(), 249.0);
+    clock.advance(1000);
+    assert_eq!(recorder.take(), vec![3]);
+    assert_eq!(clock.pending(), 0);
+}
+
+#[wasm_bindgen_test]
+fn intervals_tick_until_cancelled() {
+    let clock = TestClock::install();
+    let recorder = Recorder::default();
+    let (cmd, timer) = Cmd::every(100, |tick| tick);
+    cmd.emit(&recorder);
+    let subscriptions = Sub::every(250, |tick| tick * 10).subscribe(&recorder);
+
+    clock.advance(500);
+    assert_eq!(recorder.take(), vec![1, 2, 10, 3, 4, 5, 20]);
+
+    timer.cancel();
+    for subscription in subscriptions {
+        subscription.unsubscribe();
+    }
+    clock.advance(1000);
+    assert!(recorder.take().is_empty());
+    assert_eq!(clock.pending(), 0);
+}
+
+#[wasm_bindgen_test]
+fn pending_timers_are_discarded_when_the_clock_is_dropped() {
+    let recorder = Recorder::default();
+    {
+        let clock = TestClock::install();
+        Cmd::delay(100, 1).emit(&recorder);
+        assert_eq!(clock.pending(), 1);
+    }
+    let clock = TestClock::install();
+    assert_eq!(clock.pending(), 0);
+    clock.advance(1000);
+    assert!(recorder.take().is_empty());
+}
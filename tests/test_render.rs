@@ -1,7 +1,7 @@
 #![deny(warnings)]
 use sauron::{
     html::{attributes::*, *},
-    mt_dom::patch::*,
+    vdom::patch::*,
     *,
 };
 
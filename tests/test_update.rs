@@ -1,7 +1,7 @@
 #![deny(warnings)]
 use sauron::{
     html::{attributes::*, *},
-    mt_dom::patch::*,
+    vdom::patch::*,
     *,
 };
 
@@ -181,9 +181,12 @@ fn test1() {
         &simple_program,
         current_dom.clone(),
         &sauron_core::body(),
-    );
+    )
+    .unwrap();
 
-    dom_updater.patch_dom(&simple_program, patch);
+    dom_updater
+        .patch_dom(&simple_program, patch)
+        .expect("must patch the dom");
 
     let app_node = crate::document()
         .query_selector(".app")
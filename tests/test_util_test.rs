@@ -0,0 +1,111 @@
+#![deny(warnings)]
+use sauron::{
+    dom::test_util::{self, SimulateError},
+    html::{attributes::*, events::*, *},
+    Dispatch, Node,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, PartialEq)]
+enum Msg {
+    Clicked,
+    Input(String),
+    Checked(bool),
+}
+
+/// records the dispatched msgs instead of updating an app
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<Msg>>>);
+
+impl Dispatch<Msg> for Recorder {
+    fn dispatch(&self, msg: Msg) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<Msg>) {
+        self.0.borrow_mut().extend(msgs);
+    }
+}
+
+fn view() -> Node<Msg> {
+    div(
+        vec![],
+        vec![
+            button(vec![on_click(|_| Msg::Clicked)], vec![text("click me")]),
+            input(
+                vec![
+                    r#type("text"),
+                    on_input(|event: InputEvent| Msg::Input(event.value)),
+                ],
+                vec![],
+            ),
+            p(
+                vec![],
+                vec![input(
+                    vec![r#type("checkbox"), on_checked(Msg::Checked)],
+                    vec![],
+                )],
+            ),
+        ],
+    )
+}
+
+#[wasm_bindgen_test]
+fn simulates_the_events_into_the_listeners() {
+    let recorder = Recorder::default();
+    let view = view();
+    assert_eq!(test_util::simulate_click(&recorder, &view, &[0]), Ok(1));
+    assert_eq!(
+        test_util::simulate_input(&recorder, &view, &[1], "hello"),
+        Ok(1)
+    );
+    assert_eq!(
+        test_util::simulate_checked(&recorder, &view, &[2, 0], true),
+        Ok(1)
+    );
+    assert_eq!(
+        *recorder.0.borrow(),
+        vec![
+            Msg::Clicked,
+            Msg::Input("hello".to_string()),
+            Msg::Checked(true)
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+fn reports_the_missing_nodes_and_listeners() {
+    let recorder = Recorder::default();
+    let view = view();
+    assert_eq!(
+        test_util::simulate_click(&recorder, &view, &[5]),
+        Err(SimulateError::NodeNotFound(vec![5]))
+    );
+    assert_eq!(
+        test_util::simulate_click(&recorder, &view, &[0, 0]),
+        Err(SimulateError::NotAnElement(vec![0, 0]))
+    );
+    assert_eq!(
+        test_util::simulate_event(&recorder, &view, &[], "scroll"),
+        Err(SimulateError::NoListener {
+            path: vec![],
+            event: "scroll".to_string()
+        })
+    );
+    assert!(recorder.0.borrow().is_empty());
+}
+
+#[test]
+fn finds_the_node_by_path() {
+    let view = view();
+    let checkbox = test_util::find_node(&view, &[2, 0]).unwrap();
+    assert_eq!(
+        checkbox.as_element_ref().map(|element| *element.tag()),
+        Some("input")
+    );
+    assert!(test_util::find_node(&view, &[]).is_some());
+    assert!(test_util::find_node(&view, &[1, 0]).is_none());
+}
@@ -0,0 +1,106 @@
+#![deny(warnings)]
+use sauron::{
+    dom::cmd::Cmd,
+    html::{attributes::*, *},
+    Application, Dispatch, Node, Program,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// resolves after `ms` milliseconds
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+/// a big table whose cells all show the generation,
+/// logging the text of the last cell in the write phase of its updates
+#[derive(Default)]
+struct BigTable {
+    generation: u32,
+    writes: Rc<RefCell<Vec<String>>>,
+}
+
+impl Application<u32> for BigTable {
+    fn update(&mut self, generation: u32) -> Cmd<Program<Self, u32>> {
+        self.generation = generation;
+        let writes = Rc::clone(&self.writes);
+        Cmd::dom_write(move || {
+            let last_cell = cell_texts().pop().unwrap_or_default();
+            writes.borrow_mut().push(last_cell);
+        })
+    }
+
+    fn view(&self) -> Node<u32> {
+        table(
+            vec![id("big-table")],
+            (0..1000).map(|row| {
+                tr(
+                    vec![key(row)],
+                    (0..5).map(|_| td(vec![], vec![text(self.generation)])),
+                )
+            }),
+        )
+    }
+}
+
+fn cell_texts() -> Vec<String> {
+    let cells = sauron::document()
+        .query_selector_all("#big-table td")
+        .unwrap();
+    (0..cells.length())
+        .map(|i| cells.item(i).unwrap().text_content().unwrap_or_default())
+        .collect()
+}
+
+#[wasm_bindgen_test]
+async fn the_view_is_updated_in_slices_until_the_latest_view() {
+    let program = Program::mount_to_body(BigTable::default());
+    program.set_time_slicing(1000);
+    program.dispatch(1);
+    sleep(50).await;
+    // the view is updated again with the latest state
+    // when it changes while the update in slices is in progress
+    program.dispatch(2);
+
+    let mut waited = 0;
+    while program.dom_updater.borrow().is_updating_in_slices() && waited < 5000
+    {
+        sleep(50).await;
+        waited += 50;
+    }
+    sleep(100).await;
+    assert!(!program.dom_updater.borrow().is_updating_in_slices());
+    let texts = cell_texts();
+    assert_eq!(texts.len(), 5000);
+    assert!(texts.iter().all(|text| text == "2"));
+
+    program.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn the_writes_are_done_after_the_last_slice() {
+    let program = Program::mount_to_body(BigTable::default());
+    let writes = Rc::clone(&program.app.borrow().writes);
+    program.set_time_slicing(1000);
+    program.dispatch(1);
+
+    let mut waited = 0;
+    while program.dom_updater.borrow().is_updating_in_slices() && waited < 5000
+    {
+        // the write is held until the patches of all the slices are applied
+        assert!(writes.borrow().is_empty());
+        sleep(10).await;
+        waited += 10;
+    }
+    sleep(100).await;
+    assert_eq!(*writes.borrow(), vec!["1".to_string()]);
+
+    program.unmount();
+}
@@ -0,0 +1,69 @@
+#![deny(warnings)]
+use sauron::{dom::cmd::Cmd, Dispatch};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// records the dispatched msgs
+#[derive(Clone, Default)]
+struct Recorder {
+    msgs: Rc<RefCell<Vec<u32>>>,
+}
+
+impl Dispatch<u32> for Recorder {
+    fn dispatch(&self, msg: u32) {
+        self.msgs.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<u32>) {
+        self.msgs.borrow_mut().extend(msgs);
+    }
+}
+
+/// resolves after `ms` milliseconds
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn delayed_msg_is_dispatched() {
+    let recorder = Recorder::default();
+    Cmd::delay(10, 42).emit(&recorder);
+    assert!(recorder.msgs.borrow().is_empty());
+    sleep(50).await;
+    assert_eq!(*recorder.msgs.borrow(), vec![42]);
+}
+
+#[wasm_bindgen_test]
+async fn interval_stops_when_cancelled() {
+    let recorder = Recorder::default();
+    let (cmd, timer): (Cmd<Recorder>, _) = Cmd::every(10, |tick| tick);
+    cmd.emit(&recorder);
+    sleep(55).await;
+    timer.cancel();
+    assert!(timer.is_cancelled());
+    let ticks = recorder.msgs.borrow().len();
+    assert!(ticks >= 2);
+    assert_eq!(
+        *recorder.msgs.borrow(),
+        (1..=ticks as u32).collect::<Vec<_>>()
+    );
+    sleep(50).await;
+    assert_eq!(recorder.msgs.borrow().len(), ticks);
+}
+
+#[wasm_bindgen_test]
+async fn interval_cancelled_before_emit_never_starts() {
+    let recorder = Recorder::default();
+    let (cmd, timer): (Cmd<Recorder>, _) = Cmd::every(10, |tick| tick);
+    timer.cancel();
+    cmd.emit(&recorder);
+    sleep(50).await;
+    assert!(recorder.msgs.borrow().is_empty());
+}
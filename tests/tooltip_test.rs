@@ -0,0 +1,111 @@
+#![deny(warnings)]
+use sauron::{
+    dom::tooltip::{set_delay, tooltip},
+    html::{attributes::*, *},
+    web_sys, Application, Cmd, Node, Program, Render,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct App;
+
+impl Application<()> for App {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        button(
+            vec![
+                id("tooltip-anchor"),
+                tooltip(span(
+                    vec![],
+                    vec![text("Save the "), b(vec![], vec![text("draft")])],
+                )),
+            ],
+            vec![span(vec![id("tooltip-anchor-label")], vec![text("Save")])],
+        )
+    }
+}
+
+fn bubbling(event_name: &str) -> web_sys::Event {
+    let mut event_init = web_sys::EventInit::new();
+    event_init.bubbles(true);
+    web_sys::Event::new_with_event_init_dict(event_name, &event_init).unwrap()
+}
+
+fn element(id: &str) -> Option<web_sys::Element> {
+    sauron::document().get_element_by_id(id)
+}
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[test]
+fn tooltip_content_is_rendered_into_the_attribute() {
+    let view: Node<()> = App.view();
+    assert_eq!(
+        view.render_to_string(),
+        r#"<button id="tooltip-anchor" data-sauron-tooltip="<span>Save the <b>draft</b></span>"><span id="tooltip-anchor-label">Save</span></button>"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn tooltip_is_shown_on_focus_and_hidden_with_escape() {
+    let program = Program::mount_to_body(App);
+    let anchor = element("tooltip-anchor").expect("must have the anchor");
+    anchor.dispatch_event(&bubbling("focusin")).unwrap();
+
+    let tooltip = element("sauron-tooltip").expect("must show the tooltip");
+    assert_eq!(tooltip.inner_html(), "<span>Save the <b>draft</b></span>");
+    assert_eq!(tooltip.get_attribute("role"), Some("tooltip".to_string()));
+    assert_eq!(
+        anchor.get_attribute("aria-describedby"),
+        Some("sauron-tooltip".to_string())
+    );
+
+    let mut key_init = web_sys::KeyboardEventInit::new();
+    key_init.key("Escape").bubbles(true);
+    let escape = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict(
+        "keydown", &key_init,
+    )
+    .unwrap();
+    anchor.dispatch_event(&escape).unwrap();
+    assert!(element("sauron-tooltip").is_none());
+    assert_eq!(anchor.get_attribute("aria-describedby"), None);
+    program.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn tooltip_is_shown_after_hovering_for_the_delay() {
+    set_delay(20);
+    let program = Program::mount_to_body(App);
+    let label = element("tooltip-anchor-label").expect("must have the label");
+    label.dispatch_event(&bubbling("mouseover")).unwrap();
+    assert!(element("sauron-tooltip").is_none());
+    sleep(50).await;
+    assert!(element("sauron-tooltip").is_some());
+
+    label.dispatch_event(&bubbling("mouseout")).unwrap();
+    assert!(element("sauron-tooltip").is_none());
+    program.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn leaving_before_the_delay_cancels_the_tooltip() {
+    set_delay(20);
+    let program = Program::mount_to_body(App);
+    let anchor = element("tooltip-anchor").expect("must have the anchor");
+    anchor.dispatch_event(&bubbling("mouseover")).unwrap();
+    anchor.dispatch_event(&bubbling("mouseout")).unwrap();
+    sleep(50).await;
+    assert!(element("sauron-tooltip").is_none());
+    program.unmount();
+}
@@ -0,0 +1,56 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{transition_group, AnimationTiming, DomUpdater},
+    html::{attributes::*, div, text},
+    js_sys::{Array, Function, Reflect},
+    Node,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn view(items: &[&str]) -> Node<()> {
+    transition_group(
+        vec![id("transition-group-1")],
+        items.iter().map(|item| {
+            div(
+                vec![key(*item), id(format!("item-{}", item))],
+                vec![text(*item)],
+            )
+        }),
+        AnimationTiming::new(300.0).easing("ease-out"),
+    )
+}
+
+/// the number of the animations running on the element
+fn animation_count(id: &str) -> u32 {
+    let element = sauron::document().get_element_by_id(id).unwrap();
+    let get_animations: Function =
+        Reflect::get(&element, &JsValue::from_str("getAnimations"))
+            .unwrap()
+            .unchecked_into();
+    let animations: Array =
+        get_animations.call0(&element).unwrap().unchecked_into();
+    animations.length()
+}
+
+#[wasm_bindgen_test]
+fn reordered_children_are_animated_to_their_new_positions() {
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new(view(&["a", "b", "c"]), &sauron::body());
+    dom_updater
+        .append_to_mount(&simple_program)
+        .expect("must mount");
+    assert_eq!(animation_count("item-a"), 0);
+
+    dom_updater
+        .update_dom(&simple_program, view(&["c", "a", "b"]))
+        .expect("must update");
+    assert_eq!(animation_count("item-a"), 1);
+    assert_eq!(animation_count("item-c"), 1);
+}
@@ -0,0 +1,99 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{cmd::Cmd, Easing, Spring},
+    Dispatch,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// records the dispatched msgs
+#[derive(Clone, Default)]
+struct Recorder {
+    msgs: Rc<RefCell<Vec<f64>>>,
+}
+
+impl Dispatch<f64> for Recorder {
+    fn dispatch(&self, msg: f64) {
+        self.msgs.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<f64>) {
+        self.msgs.borrow_mut().extend(msgs);
+    }
+}
+
+/// resolves after `ms` milliseconds
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[test]
+fn easing_starts_at_zero_and_ends_at_one() {
+    for easing in [
+        Easing::Linear,
+        Easing::EaseInQuad,
+        Easing::EaseOutQuad,
+        Easing::EaseInOutQuad,
+        Easing::EaseOutCubic,
+    ]
+    .iter()
+    {
+        assert_eq!(easing.apply(0.0), 0.0);
+        assert_eq!(easing.apply(1.0), 1.0);
+        // out of range is clamped
+        assert_eq!(easing.apply(2.0), 1.0);
+    }
+    assert_eq!(Easing::EaseInOutQuad.apply(0.5), 0.5);
+    assert!(Easing::EaseInQuad.apply(0.5) < Easing::EaseOutQuad.apply(0.5));
+}
+
+#[wasm_bindgen_test]
+async fn tween_dispatch_the_values_and_ends_at_the_target() {
+    let recorder = Recorder::default();
+    let (cmd, handle): (Cmd<Recorder>, _) =
+        Cmd::tween(0.0, 100.0, 100.0, Easing::Linear, |value| value);
+    cmd.emit(&recorder);
+    sleep(300).await;
+    let msgs = recorder.msgs.borrow().clone();
+    assert!(msgs.len() >= 2);
+    assert!(msgs.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(msgs.last(), Some(&100.0));
+    assert!(!handle.is_cancelled());
+    // no more msgs after the tween is done
+    sleep(100).await;
+    assert_eq!(recorder.msgs.borrow().len(), msgs.len());
+}
+
+#[wasm_bindgen_test]
+async fn cancelled_tween_no_longer_dispatch() {
+    let recorder = Recorder::default();
+    let (cmd, handle): (Cmd<Recorder>, _) =
+        Cmd::tween(0.0, 100.0, 1000.0, Easing::EaseOutCubic, |value| value);
+    cmd.emit(&recorder);
+    sleep(100).await;
+    handle.cancel();
+    assert!(handle.is_cancelled());
+    let dispatched = recorder.msgs.borrow().len();
+    sleep(100).await;
+    assert_eq!(recorder.msgs.borrow().len(), dispatched);
+    assert!(recorder.msgs.borrow().iter().all(|value| *value < 100.0));
+}
+
+#[wasm_bindgen_test]
+async fn spring_settles_at_the_target() {
+    let recorder = Recorder::default();
+    let (cmd, _handle): (Cmd<Recorder>, _) =
+        Cmd::spring(0.0, 10.0, Spring::default(), |value| value);
+    cmd.emit(&recorder);
+    sleep(2000).await;
+    let msgs = recorder.msgs.borrow().clone();
+    assert!(msgs.len() >= 2);
+    assert_eq!(msgs.last(), Some(&10.0));
+}
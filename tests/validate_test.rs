@@ -0,0 +1,75 @@
+#![deny(warnings)]
+use sauron::validate::{
+    custom, email, max_length, min_length, range, required, ValidationError,
+    Validator,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn optional_fields_are_only_validated_when_filled() {
+    let validator = Validator::new().rule(email()).rule(min_length(6));
+    assert!(validator.is_valid(""));
+    assert_eq!(
+        validator.check("a@b"),
+        vec![
+            ValidationError::Email,
+            ValidationError::MinLength { min: 6 }
+        ]
+    );
+    assert!(validator.is_valid("a@b.io"));
+}
+
+#[test]
+fn numbers_are_checked_against_the_range() {
+    let validator = Validator::new().rule(required()).rule(range(1.0, 10.0));
+    assert_eq!(validator.check("   "), vec![ValidationError::Required]);
+    assert_eq!(validator.check("ten"), vec![ValidationError::NotANumber]);
+    assert_eq!(
+        validator.validate("11"),
+        vec!["must be between 1 and 10".to_string()]
+    );
+    assert!(validator.is_valid(" 2.5 "));
+}
+
+#[test]
+fn custom_rules_return_their_message() {
+    let validator =
+        Validator::new().rule(max_length(8)).rule(custom(|value| {
+            if value.chars().all(|c| c.is_ascii_alphanumeric()) {
+                Ok(())
+            } else {
+                Err("only letters and digits".to_string())
+            }
+        }));
+    assert_eq!(
+        validator.validate("hello world"),
+        vec![
+            "must have at most 8 characters".to_string(),
+            "only letters and digits".to_string()
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn async_rules_run_after_the_other_rules_passed() {
+    let validator = Validator::new().rule(required()).async_rule(
+        |username: String| async move {
+            if username == "admin" {
+                Err("is already taken".to_string())
+            } else {
+                Ok(())
+            }
+        },
+    );
+    assert_eq!(
+        validator.validate_async("").await,
+        vec!["this field is required".to_string()]
+    );
+    assert_eq!(
+        validator.validate_async("admin").await,
+        vec!["is already taken".to_string()]
+    );
+    assert!(validator.validate_async("ivan").await.is_empty());
+}
@@ -0,0 +1,53 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, *},
+    vdom::patch::{PatchKind, RemoveNode, TreePath},
+    *,
+};
+
+#[test]
+fn patch_kind_of_the_patches() {
+    let old: Node<()> = div(
+        vec![class("old")],
+        vec![text("hello"), span(vec![], vec![])],
+    );
+    let new: Node<()> = div(vec![class("new")], vec![text("world")]);
+    let patches = diff(&old, &new);
+    let mut kinds: Vec<PatchKind> =
+        patches.iter().map(PatchKind::from).collect();
+    kinds.sort();
+    assert_eq!(
+        kinds,
+        vec![
+            PatchKind::AddAttributes,
+            PatchKind::RemoveNode,
+            PatchKind::ChangeText
+        ]
+    );
+}
+
+#[test]
+fn patches_from_the_facade() {
+    let old: Node<()> = div(vec![], vec![span(vec![], vec![])]);
+    let new: Node<()> = div(vec![], vec![]);
+    assert_eq!(
+        diff(&old, &new),
+        vec![RemoveNode::new(Some(&"span"), TreePath::new(vec![0, 0])).into()]
+    );
+}
+
+#[test]
+fn patch_kind_name() {
+    assert_eq!(PatchKind::ReplaceNode.name(), "ReplaceNode");
+    assert_eq!(PatchKind::InsertNode.to_string(), "InsertNode");
+}
+
+#[test]
+fn path_and_tag_of_the_patch() {
+    let old: Node<()> = div(vec![], vec![span(vec![], vec![]), text("hello")]);
+    let new: Node<()> = div(vec![], vec![span(vec![], vec![]), text("world")]);
+    let patches = diff(&old, &new);
+    assert_eq!(patches.len(), 1);
+    assert_eq!(patches[0].path(), &[0, 1]);
+    assert_eq!(patches[0].tag(), None);
+}
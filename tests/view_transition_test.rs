@@ -0,0 +1,64 @@
+#![deny(warnings)]
+use sauron::{
+    dom::cmd::Cmd,
+    html::{attributes::*, *},
+    Application, Dispatch, Node, Program,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Msg {
+    GoTo(&'static str),
+}
+
+struct Router {
+    page: &'static str,
+}
+
+impl Application<Msg> for Router {
+    fn update(&mut self, msg: Msg) -> Cmd<Program<Self, Msg>> {
+        match msg {
+            Msg::GoTo(page) => {
+                self.page = page;
+                Cmd::none().with_view_transition()
+            }
+        }
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(vec![id("view-transition-page")], vec![text(self.page)])
+    }
+}
+
+fn rendered_page() -> Option<String> {
+    sauron::document()
+        .get_element_by_id("view-transition-page")
+        .and_then(|page| page.text_content())
+}
+
+#[wasm_bindgen_test]
+async fn view_is_updated_with_a_view_transition() {
+    let program = Program::mount_to_body(Router { page: "home" });
+    assert_eq!(rendered_page().as_deref(), Some("home"));
+
+    // the browsers without view transitions update the view right away
+    program.dispatch(Msg::GoTo("about"));
+    sleep(300).await;
+    assert_eq!(rendered_page().as_deref(), Some("about"));
+
+    program.dispatch(Msg::GoTo("home"));
+    program.dispatch(Msg::GoTo("contact"));
+    sleep(300).await;
+    assert_eq!(rendered_page().as_deref(), Some("contact"));
+}
@@ -0,0 +1,96 @@
+#![deny(warnings)]
+use sauron::{
+    dom::cmd::Cmd,
+    html::{attributes::*, events::*, *},
+    Application, Dispatch, Node, Program,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Msg {
+    Entered,
+    Left,
+    MoveAway,
+    Remove,
+}
+
+/// an element which is moved out of the viewport, then removed
+struct Banner {
+    top: i32,
+    is_shown: bool,
+    log: Rc<RefCell<Vec<Msg>>>,
+}
+
+impl Application<Msg> for Banner {
+    fn update(&mut self, msg: Msg) -> Cmd<Program<Self, Msg>> {
+        match msg {
+            Msg::MoveAway => self.top = 100_000,
+            Msg::Remove => self.is_shown = false,
+            Msg::Entered | Msg::Left => self.log.borrow_mut().push(msg),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            vec![],
+            vec![view_if(
+                self.is_shown,
+                div(
+                    vec![
+                        class("banner"),
+                        styles([
+                            ("margin-top", format!("{}px", self.top)),
+                            ("height", "50px".to_string()),
+                        ]),
+                        on_enter_viewport(|_entry| Msg::Entered),
+                        on_leave_viewport(|_entry| Msg::Left),
+                    ],
+                    vec![],
+                ),
+            )],
+        )
+    }
+}
+
+fn observed_elements(program: &Program<Banner, Msg>) -> String {
+    program
+        .metrics_text()
+        .lines()
+        .find(|line| line.starts_with("sauron_observed_elements "))
+        .expect("must have the observed elements")
+        .to_string()
+}
+
+#[wasm_bindgen_test]
+async fn visibility_changes_are_dispatched_until_the_element_is_removed() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let program = Program::mount_to_body(Banner {
+        top: 0,
+        is_shown: true,
+        log: Rc::clone(&log),
+    });
+    sleep(200).await;
+    assert_eq!(*log.borrow(), vec![Msg::Entered]);
+    assert_eq!(observed_elements(&program), "sauron_observed_elements 1");
+
+    program.dispatch(Msg::MoveAway);
+    sleep(200).await;
+    assert_eq!(*log.borrow(), vec![Msg::Entered, Msg::Left]);
+
+    program.dispatch(Msg::Remove);
+    sleep(200).await;
+    assert_eq!(observed_elements(&program), "sauron_observed_elements 0");
+}
@@ -0,0 +1,36 @@
+#![deny(warnings)]
+use sauron::{html::*, widgets::VirtualList, Node, Render};
+
+#[test]
+fn only_the_visible_rows_are_rendered() {
+    let items: Vec<usize> = (0..50_000).collect();
+    let mut list = VirtualList::new(20.0, 100.0);
+    assert_eq!(list.visible_range(items.len()), 0..8);
+
+    list.set_scroll_top(20_000.0);
+    assert_eq!(list.visible_range(items.len()), 997..1008);
+
+    let view: Node<f64> = list.view(
+        &items,
+        |_index, item| span(vec![], vec![text(item)]),
+        |top| top,
+    );
+    let html = view.render_to_string();
+    assert_eq!(html.matches("virtual-list__row").count(), 11);
+    assert!(html.contains("<span>997</span>"));
+    assert!(html.contains("<span>1007</span>"));
+    assert!(!html.contains("<span>1008</span>"));
+    // the spacers have the height of the rows which are not rendered
+    assert!(html.contains("height:19940px;"));
+    assert!(html.contains(&format!("height:{}px;", (50_000 - 1008) * 20)));
+}
+
+#[test]
+fn the_range_is_clamped_at_the_end_of_the_list() {
+    let mut list = VirtualList::new(10.0, 50.0).overscan(0);
+    list.set_scroll_top(1_000.0);
+    assert_eq!(list.visible_range(20), 20..20);
+    list.set_scroll_top(170.0);
+    assert_eq!(list.visible_range(20), 17..20);
+    assert_eq!(list.visible_range(0), 0..0);
+}
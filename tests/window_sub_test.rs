@@ -0,0 +1,45 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{sub::Sub, window},
+    Dispatch,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// records the dispatched msgs
+#[derive(Clone, Default)]
+struct Recorder {
+    msgs: Rc<RefCell<Vec<(i32, i32)>>>,
+}
+
+impl Dispatch<(i32, i32)> for Recorder {
+    fn dispatch(&self, msg: (i32, i32)) {
+        self.msgs.borrow_mut().push(msg);
+    }
+
+    fn dispatch_multiple(&self, msgs: Vec<(i32, i32)>) {
+        self.msgs.borrow_mut().extend(msgs);
+    }
+}
+
+#[wasm_bindgen_test]
+fn resize_listener_is_removed_when_unsubscribed() {
+    let recorder = Recorder::default();
+    let sub: Sub<Recorder> = window::on_resize(|width, height| (width, height));
+    let subscriptions = sub.subscribe(&recorder);
+
+    let resize = sauron::web_sys::Event::new("resize").unwrap();
+    sauron::window().dispatch_event(&resize).unwrap();
+    assert_eq!(
+        *recorder.msgs.borrow(),
+        vec![sauron::dom::Window::get_size()]
+    );
+
+    for subscription in subscriptions {
+        subscription.unsubscribe();
+    }
+    sauron::window().dispatch_event(&resize).unwrap();
+    assert_eq!(recorder.msgs.borrow().len(), 1);
+}
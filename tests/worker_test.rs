@@ -0,0 +1,107 @@
+#![deny(warnings)]
+#![cfg(feature = "with-worker")]
+use sauron::{
+    js_sys,
+    wasm_bindgen::{JsCast, JsValue},
+    web_sys,
+    worker::WorkerHost,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn sleep(ms: i32) {
+    let promise = sauron::js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+/// a worker which posts a counter button, then increments it on each of its clicks,
+/// in place of a worker which runs a `WorkerProgram`
+const COUNTER_WORKER: &str = r#"
+let count = 0;
+postMessage(JSON.stringify({ Mount: { Element: {
+    namespace: null,
+    tag: "button",
+    attrs: [
+        { namespace: null, name: "id", values: [{ Text: "worker-counter" }] },
+        { namespace: null, name: "click", values: ["Listener"] },
+    ],
+    children: [{ Text: "0" }],
+} } }));
+onmessage = (message) => {
+    const event = JSON.parse(message.data).Event;
+    if (event && event.name === "click" && event.path.join() === "0") {
+        count += 1;
+        postMessage(JSON.stringify({ Patch: [
+            { ChangeText: { path: [0, 0], text: String(count) } },
+        ] }));
+    }
+};
+"#;
+
+/// a worker which posts a list, then truncates it to its first item
+const TRUNCATE_WORKER: &str = r#"
+const item = (text) => ({ Element: {
+    namespace: null,
+    tag: "li",
+    attrs: [],
+    children: [{ Text: text }],
+} });
+postMessage(JSON.stringify({ Mount: { Element: {
+    namespace: null,
+    tag: "ul",
+    attrs: [{ namespace: null, name: "id", values: [{ Text: "worker-list" }] }],
+    children: [item("1"), item("2"), item("3")],
+} } }));
+postMessage(JSON.stringify({ Patch: [
+    { RemoveChildrenFrom: { tag: "ul", path: [0], start_index: 1 } },
+] }));
+"#;
+
+fn worker_url(script: &str) -> String {
+    let parts = js_sys::Array::of1(&JsValue::from_str(script));
+    let blob = web_sys::Blob::new_with_str_sequence(&parts).unwrap();
+    web_sys::Url::create_object_url_with_blob(&blob).unwrap()
+}
+
+fn counter() -> Option<web_sys::HtmlElement> {
+    sauron::document()
+        .get_element_by_id("worker-counter")
+        .map(|counter| counter.unchecked_into())
+}
+
+#[wasm_bindgen_test]
+async fn the_view_posted_by_the_worker_is_mounted_and_patched() {
+    let host: WorkerHost<()> =
+        WorkerHost::mount(&worker_url(COUNTER_WORKER), &sauron::body());
+    sleep(300).await;
+    let button = counter().expect("must mount the view of the worker");
+    assert_eq!(button.text_content().as_deref(), Some("0"));
+
+    button.click();
+    button.click();
+    sleep(300).await;
+    assert_eq!(button.text_content().as_deref(), Some("2"));
+
+    host.terminate();
+    button.remove();
+}
+
+#[wasm_bindgen_test]
+async fn the_children_removed_by_the_worker_are_truncated() {
+    let host: WorkerHost<()> =
+        WorkerHost::mount(&worker_url(TRUNCATE_WORKER), &sauron::body());
+    sleep(300).await;
+    let list = sauron::document()
+        .get_element_by_id("worker-list")
+        .expect("must mount the view of the worker");
+    assert_eq!(list.child_element_count(), 1);
+    assert_eq!(list.text_content().as_deref(), Some("1"));
+
+    host.terminate();
+    list.remove();
+}